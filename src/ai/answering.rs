@@ -0,0 +1,97 @@
+/// How much retrieved text goes into the prompt.
+pub const MAX_CONTEXT_CHARS: usize = 24_000;
+
+pub const SYSTEM_PROMPT: &str = "You answer questions about history from a personal research knowledge base built from lecture transcripts. \
+Use only the numbered sources you are given. Cite every statement with the source numbers it rests on, like [2] or [1][4]. \
+If the sources don't answer the question, say so plainly instead of filling the gap from memory.";
+
+/// A retrieved piece of the knowledge base, numbered in the prompt by its position.
+#[derive(Debug, Clone)]
+pub enum Passage {
+    Claim { claim_id: i64, video_id: String, video_title: String, timestamp: Option<f64>, text: String },
+    Chunk { video_id: String, video_title: String, start_time: f64, text: String },
+}
+
+impl Passage {
+    pub fn video_id(&self) -> &str {
+        match self {
+            Passage::Claim { video_id, .. } | Passage::Chunk { video_id, .. } => video_id,
+        }
+    }
+
+    /// One-line reference, e.g. `claim #12, "Fall of Ur" at 05:32`.
+    pub fn reference(&self) -> String {
+        match self {
+            Passage::Claim { claim_id, video_title, timestamp, .. } => match timestamp {
+                Some(t) => format!("claim #{}, \"{}\" at {}", claim_id, video_title, clock(*t)),
+                None => format!("claim #{}, \"{}\"", claim_id, video_title),
+            },
+            Passage::Chunk { video_title, start_time, .. } => {
+                format!("transcript, \"{}\" at {}", video_title, clock(*start_time))
+            }
+        }
+    }
+
+    fn text(&self) -> &str {
+        match self {
+            Passage::Claim { text, .. } | Passage::Chunk { text, .. } => text,
+        }
+    }
+}
+
+/// The user prompt: numbered sources, then the question.
+pub fn answer_prompt(question: &str, passages: &[Passage]) -> String {
+    let mut prompt = String::from("Sources:\n\n");
+    for (i, passage) in passages.iter().enumerate() {
+        prompt.push_str(&format!("[{}] ({})\n{}\n\n", i + 1, passage.reference(), passage.text().trim()));
+    }
+    prompt.push_str(&format!("Question: {}\n\nAnswer in a few paragraphs, citing sources by number.", question));
+    prompt
+}
+
+/// Keep passages, in order, until the prompt would exceed `MAX_CONTEXT_CHARS`.
+pub fn fit_context(passages: Vec<Passage>) -> Vec<Passage> {
+    let mut used = 0;
+    passages
+        .into_iter()
+        .take_while(|p| {
+            used += p.text().len() + 80;
+            used <= MAX_CONTEXT_CHARS
+        })
+        .collect()
+}
+
+/// The source numbers (1-based) an answer cites, in order of first use, ignoring
+/// numbers that don't refer to a source.
+pub fn citations(answer: &str, sources: usize) -> Vec<usize> {
+    let mut cited = Vec::new();
+    for n in cited_numbers(answer) {
+        if (1..=sources).contains(&n) && !cited.contains(&n) {
+            cited.push(n);
+        }
+    }
+    cited
+}
+
+/// The sentences of an answer that cite source `n`, to record why it was used.
+pub fn citing_sentences(answer: &str, n: usize) -> String {
+    answer
+        .split_inclusive(['.', '\n'])
+        .map(str::trim)
+        .filter(|sentence| cited_numbers(sentence).any(|m| m == n))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Every number in `[n]` or `[n, m]` brackets.
+fn cited_numbers(text: &str) -> impl Iterator<Item = usize> + '_ {
+    text.split('[')
+        .skip(1)
+        .filter_map(|part| part.split_once(']').map(|(inside, _)| inside))
+        .flat_map(|inside| inside.split(',').filter_map(|n| n.trim().parse::<usize>().ok()))
+}
+
+fn clock(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    format!("{:02}:{:02}", total / 60, total % 60)
+}
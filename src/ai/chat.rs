@@ -0,0 +1,772 @@
+use anyhow::Result;
+use crate::storage::models::{LLMConfig, LLMProvider};
+#[cfg(any(feature = "anthropic", feature = "openai", feature = "ollama"))]
+use {super::http::{self, HttpError}, crate::transcript::fetcher::RetryPolicy};
+use std::cell::Cell;
+use std::time::Duration;
+
+// Each backend sits behind a cargo feature of the same name (all on by default), so a
+// build can leave out providers it is never meant to talk to.
+
+#[cfg(feature = "anthropic")]
+const ANTHROPIC_DEFAULT_URL: &str = "https://api.anthropic.com/v1";
+#[cfg(feature = "anthropic")]
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+#[cfg(feature = "openai")]
+const OPENAI_DEFAULT_URL: &str = "https://api.openai.com/v1";
+#[cfg(feature = "ollama")]
+const OLLAMA_DEFAULT_URL: &str = "http://localhost:11434";
+
+// Room for a full batch of extracted claims as JSON
+#[cfg(feature = "anthropic")]
+const DEFAULT_MAX_TOKENS: u32 = 8192;
+
+/// Long transcripts on local models are slow; give them time.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Generates text from a prompt: claim extraction, summaries, answers.
+pub trait ChatProvider {
+    /// Model name recorded with whatever the response produced
+    fn model(&self) -> &str;
+
+    /// Provider name recorded with whatever the response produced, as in `LLMProvider::as_str`
+    fn provider(&self) -> &str;
+
+    /// A single-turn completion: one system prompt, one user message.
+    fn complete(&self, system: &str, prompt: &str) -> Result<String>;
+
+    /// Like `complete`, passing text to `on_token` as it is generated. Returns the
+    /// whole response. Providers without streaming deliver it in one piece.
+    fn complete_stream(&self, system: &str, prompt: &str, on_token: &mut dyn FnMut(&str)) -> Result<String> {
+        let text = self.complete(system, prompt)?;
+        on_token(&text);
+        Ok(text)
+    }
+
+    /// Tokens used by the last successful call, when the provider reported them.
+    fn last_usage(&self) -> Option<Usage> {
+        None
+    }
+}
+
+/// Token counts for one request, as reported by the provider.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+/// How a chat request failed, the same for every provider. Returned inside
+/// `anyhow::Error`; `downcast_ref::<ChatError>()` to tell the cases apart.
+#[derive(Debug)]
+pub enum ChatError {
+    /// This build was compiled without the provider's feature
+    Disabled(&'static str),
+    /// The environment variable that should hold the API key is unset or empty
+    MissingKey(String),
+    /// No response: the server is down, unreachable or refused the connection
+    Unreachable { provider: &'static str, message: String },
+    Timeout { provider: &'static str, seconds: u64 },
+    /// The API key was rejected (HTTP 401/403)
+    Auth { provider: &'static str, message: String },
+    /// HTTP 429, still after retrying
+    RateLimited { provider: &'static str, message: String },
+    /// Any other error reported by the provider; no status for errors sent mid-stream
+    Api { provider: &'static str, status: Option<u16>, message: String },
+    /// A response that isn't what the API documents
+    BadResponse { provider: &'static str, message: String },
+}
+
+impl ChatError {
+    /// Whether trying again later, or with another provider, might succeed.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            ChatError::Unreachable { .. } | ChatError::Timeout { .. } | ChatError::RateLimited { .. } => true,
+            ChatError::Api { status, .. } => status.is_none_or(|s| s >= 500),
+            _ => false,
+        }
+    }
+
+    /// Classify an error from the `http` helpers.
+    #[cfg(any(feature = "anthropic", feature = "openai", feature = "ollama"))]
+    fn from_http(provider: &'static str, timeout: Duration, error: anyhow::Error) -> anyhow::Error {
+        let error = match error.downcast::<HttpError>() {
+            Ok(error) => error,
+            Err(other) => return other,
+        };
+        match error {
+            HttpError::Transport { timed_out: true, .. } => {
+                ChatError::Timeout { provider, seconds: timeout.as_secs() }
+            }
+            HttpError::Transport { message, .. } => ChatError::Unreachable { provider, message },
+            HttpError::Status { status, body, .. } => {
+                let message = error_message(&body);
+                match status {
+                    401 | 403 => ChatError::Auth { provider, message },
+                    429 => ChatError::RateLimited { provider, message },
+                    _ => ChatError::Api { provider, status: Some(status), message },
+                }
+            }
+            HttpError::InvalidJson { message, .. } => ChatError::BadResponse { provider, message },
+        }
+        .into()
+    }
+}
+
+impl std::fmt::Display for ChatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ChatError::Disabled(provider) => {
+                write!(f, "This build doesn't include the {} provider (cargo feature \"{}\")", provider, provider)
+            }
+            ChatError::MissingKey(var) => write!(f, "{} is not set", var),
+            ChatError::Unreachable { provider, message } => write!(f, "{} is unreachable: {}", provider, message),
+            ChatError::Timeout { provider, seconds } => write!(f, "{} timed out after {}s", provider, seconds),
+            ChatError::Auth { provider, message } => write!(f, "{} rejected the API key: {}", provider, message),
+            ChatError::RateLimited { provider, message } => write!(f, "{} rate limit reached: {}", provider, message),
+            ChatError::Api { provider, status: Some(status), message } => {
+                write!(f, "{} error (HTTP {}): {}", provider, status, message)
+            }
+            ChatError::Api { provider, status: None, message } => write!(f, "{} error: {}", provider, message),
+            ChatError::BadResponse { provider, message } => write!(f, "Unexpected response from {}: {}", provider, message),
+        }
+    }
+}
+
+impl std::error::Error for ChatError {}
+
+/// The message out of a provider's error body: `{"error": {"message": ...}}` for
+/// Anthropic and OpenAI, `{"error": "..."}` for Ollama, else the body itself.
+#[cfg(any(feature = "anthropic", feature = "openai", feature = "ollama"))]
+fn error_message(body: &str) -> String {
+    let json: Option<serde_json::Value> = serde_json::from_str(body.trim()).ok();
+    let message = json.as_ref().and_then(|j| {
+        j["error"]["message"].as_str().or(j["error"].as_str()).or(j["message"].as_str()).map(str::to_string)
+    });
+    message.unwrap_or_else(|| body.trim().chars().take(200).collect())
+}
+
+/// The model used when none is given.
+pub fn default_model(provider: &LLMProvider) -> &'static str {
+    match provider {
+        LLMProvider::Anthropic => "claude-3-5-sonnet-latest",
+        LLMProvider::OpenAI => "gpt-4o-mini",
+        LLMProvider::Local => "llama3.1",
+    }
+}
+
+/// The environment variable a provider's API key is read from by default.
+pub fn default_key_env(provider: &LLMProvider) -> Option<&'static str> {
+    match provider {
+        LLMProvider::Anthropic => Some("ANTHROPIC_API_KEY"),
+        LLMProvider::OpenAI => Some("OPENAI_API_KEY"),
+        LLMProvider::Local => None,
+    }
+}
+
+/// The API key from `var`, which must be set.
+#[cfg(any(feature = "anthropic", feature = "openai"))]
+fn api_key(var: &str) -> Result<String> {
+    std::env::var(var)
+        .ok()
+        .filter(|k| !k.is_empty())
+        .ok_or_else(|| ChatError::MissingKey(var.to_string()).into())
+}
+
+/// Build the chat provider for an LLM config. A config `key_env` or `base_url`
+/// overrides the provider's usual environment variables.
+#[cfg_attr(not(any(feature = "anthropic", feature = "openai", feature = "ollama")), allow(unreachable_code))]
+pub fn provider_for(config: &LLMConfig) -> Result<Box<dyn ChatProvider>> {
+    let timeout = config.timeout_secs.map(Duration::from_secs).unwrap_or(DEFAULT_TIMEOUT);
+    Ok(match config.provider {
+        #[cfg(feature = "anthropic")]
+        LLMProvider::Anthropic => {
+            let mut chat = match &config.key_env {
+                Some(var) => AnthropicChat::with_key(&config.model, api_key(var)?),
+                None => AnthropicChat::new(&config.model)?,
+            };
+            if let Some(url) = &config.base_url {
+                chat = chat.with_base_url(url);
+            }
+            Box::new(chat.with_temperature(config.temperature).with_timeout(timeout))
+        }
+        #[cfg(feature = "openai")]
+        LLMProvider::OpenAI => {
+            let mut chat = OpenAIChat::new(&config.model);
+            if let Some(var) = &config.key_env {
+                chat = chat.with_api_key(api_key(var)?);
+            }
+            if let Some(url) = &config.base_url {
+                chat = chat.with_base_url(url);
+            }
+            Box::new(chat.with_temperature(config.temperature).with_timeout(timeout))
+        }
+        #[cfg(feature = "ollama")]
+        LLMProvider::Local => {
+            let mut chat = OllamaChat::new(&config.model);
+            if let Some(url) = &config.base_url {
+                chat = chat.with_base_url(url);
+            }
+            Box::new(chat.with_temperature(config.temperature).with_timeout(timeout))
+        }
+        #[allow(unreachable_patterns)]
+        ref provider => {
+            let _ = timeout;
+            return Err(ChatError::Disabled(feature_name(provider)).into());
+        }
+    })
+}
+
+/// The cargo feature a provider's backend is behind.
+pub fn feature_name(provider: &LLMProvider) -> &'static str {
+    match provider {
+        LLMProvider::Anthropic => "anthropic",
+        LLMProvider::OpenAI => "openai",
+        LLMProvider::Local => "ollama",
+    }
+}
+
+/// Parse a fallback chain: comma-separated `provider` or `provider:model` entries,
+/// e.g. `local:llama3.1, openai`. A missing model means the provider's default.
+pub fn parse_chain(value: &str) -> Option<Vec<(LLMProvider, Option<String>)>> {
+    let mut chain = Vec::new();
+    for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let (name, model) = match entry.split_once(':') {
+            Some((name, model)) if !model.trim().is_empty() => (name, Some(model.trim().to_string())),
+            Some(_) => return None,
+            None => (entry, None),
+        };
+        chain.push((LLMProvider::from_str(name.trim())?, model));
+    }
+    (!chain.is_empty()).then_some(chain)
+}
+
+/// Tries each provider in order until one answers: when the primary errors, is rate
+/// limited or can't be reached, the next one gets the same request. `provider()` and
+/// `model()` name whichever answered last, so artifacts record where they came from.
+pub struct FallbackChat<'a> {
+    chain: Vec<Box<dyn ChatProvider + 'a>>,
+    answered: Cell<usize>,
+}
+
+impl<'a> FallbackChat<'a> {
+    /// `chain` must not be empty; the first entry is the primary.
+    pub fn new(chain: Vec<Box<dyn ChatProvider + 'a>>) -> Self {
+        assert!(!chain.is_empty(), "fallback chain is empty");
+        FallbackChat { chain, answered: Cell::new(0) }
+    }
+
+    /// Run `call` on each provider in turn, returning the first success. Stops early
+    /// when `call` says retrying elsewhere is unsafe. If all fail, the error lists each.
+    fn attempt(&self, mut call: impl FnMut(&dyn ChatProvider) -> (Result<String>, bool)) -> Result<String> {
+        let mut errors = Vec::new();
+        for (i, chat) in self.chain.iter().enumerate() {
+            let (result, can_retry) = call(chat.as_ref());
+            match result {
+                Ok(text) => {
+                    self.answered.set(i);
+                    return Ok(text);
+                }
+                Err(e) if !can_retry || errors.len() + 1 == self.chain.len() => {
+                    if errors.is_empty() {
+                        return Err(e);
+                    }
+                    errors.push(format!("{:#}", e));
+                    break;
+                }
+                Err(e) => errors.push(format!("{:#}", e)),
+            }
+        }
+        Err(anyhow::anyhow!("{}", errors.join("; then ")))
+    }
+}
+
+impl ChatProvider for FallbackChat<'_> {
+    fn model(&self) -> &str {
+        self.chain[self.answered.get()].model()
+    }
+
+    fn provider(&self) -> &str {
+        self.chain[self.answered.get()].provider()
+    }
+
+    fn complete(&self, system: &str, prompt: &str) -> Result<String> {
+        self.attempt(|chat| (chat.complete(system, prompt), true))
+    }
+
+    /// Falls back only while nothing has been streamed, so output is never repeated.
+    fn complete_stream(&self, system: &str, prompt: &str, on_token: &mut dyn FnMut(&str)) -> Result<String> {
+        self.attempt(|chat| {
+            let mut streamed = false;
+            let result = chat.complete_stream(system, prompt, &mut |token| {
+                streamed = true;
+                on_token(token);
+            });
+            (result, !streamed)
+        })
+    }
+
+    fn last_usage(&self) -> Option<Usage> {
+        self.chain[self.answered.get()].last_usage()
+    }
+}
+
+/// Claude via the Anthropic Messages API. Reads ANTHROPIC_API_KEY and ANTHROPIC_BASE_URL.
+#[cfg(feature = "anthropic")]
+pub struct AnthropicChat {
+    base_url: String,
+    api_key: String,
+    model: String,
+    temperature: f32,
+    max_tokens: u32,
+    timeout: Duration,
+    retry: RetryPolicy,
+    usage: Cell<Option<Usage>>,
+}
+
+#[cfg(feature = "anthropic")]
+impl AnthropicChat {
+    pub fn new(model: &str) -> Result<Self> {
+        Ok(Self::with_key(model, api_key("ANTHROPIC_API_KEY")?))
+    }
+
+    /// Use an API key from somewhere other than ANTHROPIC_API_KEY.
+    pub fn with_key(model: &str, api_key: String) -> Self {
+        let base_url = std::env::var("ANTHROPIC_BASE_URL")
+            .ok()
+            .filter(|u| !u.is_empty())
+            .unwrap_or_else(|| ANTHROPIC_DEFAULT_URL.to_string());
+        AnthropicChat {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key,
+            model: model.to_string(),
+            temperature: 0.0,
+            max_tokens: DEFAULT_MAX_TOKENS,
+            timeout: DEFAULT_TIMEOUT,
+            retry: RetryPolicy::default(),
+            usage: Cell::new(None),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn request(&self, system: &str, prompt: &str, stream: bool) -> serde_json::Value {
+        serde_json::json!({
+            "model": self.model,
+            "max_tokens": self.max_tokens,
+            "temperature": self.temperature,
+            "system": system,
+            "messages": [{ "role": "user", "content": prompt }],
+            "stream": stream,
+        })
+    }
+
+    fn headers(&self) -> [(&str, &str); 2] {
+        [("x-api-key", self.api_key.as_str()), ("anthropic-version", ANTHROPIC_VERSION)]
+    }
+}
+
+#[cfg(feature = "anthropic")]
+impl ChatProvider for AnthropicChat {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn provider(&self) -> &str {
+        LLMProvider::Anthropic.as_str()
+    }
+
+    fn complete(&self, system: &str, prompt: &str) -> Result<String> {
+        #[derive(serde::Deserialize)]
+        struct ContentBlock {
+            #[serde(default)]
+            text: String,
+        }
+        #[derive(serde::Deserialize)]
+        struct MessagesUsage {
+            input_tokens: u64,
+            output_tokens: u64,
+        }
+        #[derive(serde::Deserialize)]
+        struct MessagesResponse {
+            content: Vec<ContentBlock>,
+            #[serde(default)]
+            usage: Option<MessagesUsage>,
+        }
+
+        self.usage.set(None);
+        let url = format!("{}/messages", self.base_url);
+        let body = self.request(system, prompt, false);
+        let response = http::post_json_with_retry(&url, &self.headers(), &body, self.timeout, &self.retry)
+            .map_err(|e| ChatError::from_http("anthropic", self.timeout, e))?;
+        let parsed: MessagesResponse = serde_json::from_value(response)
+            .map_err(|e| ChatError::BadResponse { provider: "anthropic", message: e.to_string() })?;
+        self.usage.set(parsed.usage.map(|u| Usage { input_tokens: u.input_tokens, output_tokens: u.output_tokens }));
+        Ok(parsed.content.into_iter().map(|c| c.text).collect())
+    }
+
+    fn complete_stream(&self, system: &str, prompt: &str, on_token: &mut dyn FnMut(&str)) -> Result<String> {
+        let url = format!("{}/messages", self.base_url);
+        let body = self.request(system, prompt, true);
+        let mut text = String::new();
+        let mut error = None;
+        let mut usage: Option<Usage> = None;
+        self.usage.set(None);
+        http::post_stream(&url, &self.headers(), &body, self.timeout, &mut |line| {
+            let Some(event) = line.strip_prefix("data:").and_then(|d| serde_json::from_str::<serde_json::Value>(d.trim()).ok()) else {
+                return;
+            };
+            match event["type"].as_str() {
+                Some("content_block_delta") => {
+                    if let Some(delta) = event["delta"]["text"].as_str() {
+                        on_token(delta);
+                        text.push_str(delta);
+                    }
+                }
+                // Input tokens arrive at the start, output tokens with the final delta
+                Some("message_start") => {
+                    if let Some(input) = event["message"]["usage"]["input_tokens"].as_u64() {
+                        usage.get_or_insert_with(Usage::default).input_tokens = input;
+                    }
+                }
+                Some("message_delta") => {
+                    if let Some(output) = event["usage"]["output_tokens"].as_u64() {
+                        usage.get_or_insert_with(Usage::default).output_tokens = output;
+                    }
+                }
+                Some("error") => error = Some(error_message(&event.to_string())),
+                _ => {}
+            }
+        })
+        .map_err(|e| ChatError::from_http("anthropic", self.timeout, e))?;
+        match error {
+            Some(message) => Err(ChatError::Api { provider: "anthropic", status: None, message }.into()),
+            None => {
+                self.usage.set(usage);
+                Ok(text)
+            }
+        }
+    }
+
+    fn last_usage(&self) -> Option<Usage> {
+        self.usage.get()
+    }
+}
+
+/// Chat completions from the OpenAI API or any compatible server (LM Studio, vLLM,
+/// LiteLLM, ...). Reads OPENAI_API_KEY and OPENAI_BASE_URL.
+#[cfg(feature = "openai")]
+pub struct OpenAIChat {
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+    temperature: f32,
+    timeout: Duration,
+    retry: RetryPolicy,
+    usage: Cell<Option<Usage>>,
+}
+
+#[cfg(feature = "openai")]
+impl OpenAIChat {
+    pub fn new(model: &str) -> Self {
+        let base_url = std::env::var("OPENAI_BASE_URL")
+            .ok()
+            .filter(|u| !u.is_empty())
+            .unwrap_or_else(|| OPENAI_DEFAULT_URL.to_string());
+        OpenAIChat {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: std::env::var("OPENAI_API_KEY").ok().filter(|k| !k.is_empty()),
+            model: model.to_string(),
+            temperature: 0.0,
+            timeout: DEFAULT_TIMEOUT,
+            retry: RetryPolicy::default(),
+            usage: Cell::new(None),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_api_key(mut self, api_key: String) -> Self {
+        self.api_key = Some(api_key);
+        self
+    }
+
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn request(&self, system: &str, prompt: &str, stream: bool) -> serde_json::Value {
+        let mut request = serde_json::json!({
+            "model": self.model,
+            "temperature": self.temperature,
+            "messages": [
+                { "role": "system", "content": system },
+                { "role": "user", "content": prompt },
+            ],
+            "stream": stream,
+        });
+        if stream {
+            // Ask for token counts in a final chunk; servers that don't know the option ignore it
+            request["stream_options"] = serde_json::json!({ "include_usage": true });
+        }
+        request
+    }
+
+    fn authorization(&self) -> Option<String> {
+        self.api_key.as_ref().map(|k| format!("Bearer {}", k))
+    }
+}
+
+#[cfg(feature = "openai")]
+impl ChatProvider for OpenAIChat {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn provider(&self) -> &str {
+        LLMProvider::OpenAI.as_str()
+    }
+
+    fn complete(&self, system: &str, prompt: &str) -> Result<String> {
+        #[derive(serde::Deserialize)]
+        struct Message {
+            #[serde(default)]
+            content: Option<String>,
+        }
+        #[derive(serde::Deserialize)]
+        struct Choice {
+            message: Message,
+        }
+        #[derive(serde::Deserialize)]
+        struct CompletionUsage {
+            prompt_tokens: u64,
+            completion_tokens: u64,
+        }
+        #[derive(serde::Deserialize)]
+        struct CompletionResponse {
+            choices: Vec<Choice>,
+            #[serde(default)]
+            usage: Option<CompletionUsage>,
+        }
+
+        self.usage.set(None);
+        let auth = self.authorization();
+        let headers: Vec<(&str, &str)> = auth.iter().map(|a| ("Authorization", a.as_str())).collect();
+        let url = format!("{}/chat/completions", self.base_url);
+        let body = self.request(system, prompt, false);
+        let response = http::post_json_with_retry(&url, &headers, &body, self.timeout, &self.retry)
+            .map_err(|e| ChatError::from_http("openai", self.timeout, e))?;
+        let parsed: CompletionResponse = serde_json::from_value(response)
+            .map_err(|e| ChatError::BadResponse { provider: "openai", message: e.to_string() })?;
+        self.usage.set(parsed.usage.map(|u| Usage { input_tokens: u.prompt_tokens, output_tokens: u.completion_tokens }));
+        parsed
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|c| c.message.content)
+            .ok_or_else(|| ChatError::BadResponse { provider: "openai", message: "no completion".to_string() }.into())
+    }
+
+    fn complete_stream(&self, system: &str, prompt: &str, on_token: &mut dyn FnMut(&str)) -> Result<String> {
+        let auth = self.authorization();
+        let headers: Vec<(&str, &str)> = auth.iter().map(|a| ("Authorization", a.as_str())).collect();
+        let url = format!("{}/chat/completions", self.base_url);
+        let body = self.request(system, prompt, true);
+        let mut text = String::new();
+        let mut error = None;
+        let mut usage = None;
+        self.usage.set(None);
+        http::post_stream(&url, &headers, &body, self.timeout, &mut |line| {
+            let Some(event) = line.strip_prefix("data:").and_then(|d| serde_json::from_str::<serde_json::Value>(d.trim()).ok()) else {
+                // Including the closing "data: [DONE]"
+                return;
+            };
+            if event.get("error").is_some() {
+                error = Some(error_message(&event.to_string()));
+                return;
+            }
+            if let Some(delta) = event["choices"][0]["delta"]["content"].as_str() {
+                on_token(delta);
+                text.push_str(delta);
+            }
+            if let (Some(input), Some(output)) =
+                (event["usage"]["prompt_tokens"].as_u64(), event["usage"]["completion_tokens"].as_u64())
+            {
+                usage = Some(Usage { input_tokens: input, output_tokens: output });
+            }
+        })
+        .map_err(|e| ChatError::from_http("openai", self.timeout, e))?;
+        match error {
+            Some(message) => Err(ChatError::Api { provider: "openai", status: None, message }.into()),
+            None => {
+                self.usage.set(usage);
+                Ok(text)
+            }
+        }
+    }
+
+    fn last_usage(&self) -> Option<Usage> {
+        self.usage.get()
+    }
+}
+
+/// Chat with a local Ollama model (`ollama pull llama3.1`). The server address comes
+/// from OLLAMA_HOST, as for the ollama CLI.
+#[cfg(feature = "ollama")]
+pub struct OllamaChat {
+    base_url: String,
+    model: String,
+    temperature: f32,
+    timeout: Duration,
+    retry: RetryPolicy,
+    usage: Cell<Option<Usage>>,
+}
+
+#[cfg(feature = "ollama")]
+impl OllamaChat {
+    pub fn new(model: &str) -> Self {
+        let base_url = std::env::var("OLLAMA_HOST")
+            .ok()
+            .filter(|h| !h.is_empty())
+            .map(|h| if h.contains("://") { h } else { format!("http://{}", h) })
+            .unwrap_or_else(|| OLLAMA_DEFAULT_URL.to_string());
+        OllamaChat {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+            temperature: 0.0,
+            timeout: DEFAULT_TIMEOUT,
+            retry: RetryPolicy::default(),
+            usage: Cell::new(None),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn request(&self, system: &str, prompt: &str, stream: bool) -> serde_json::Value {
+        serde_json::json!({
+            "model": self.model,
+            "stream": stream,
+            "options": { "temperature": self.temperature },
+            "messages": [
+                { "role": "system", "content": system },
+                { "role": "user", "content": prompt },
+            ],
+        })
+    }
+}
+
+#[cfg(feature = "ollama")]
+impl ChatProvider for OllamaChat {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn provider(&self) -> &str {
+        LLMProvider::Local.as_str()
+    }
+
+    fn complete(&self, system: &str, prompt: &str) -> Result<String> {
+        #[derive(serde::Deserialize)]
+        struct Message {
+            content: String,
+        }
+        #[derive(serde::Deserialize)]
+        struct ChatResponse {
+            message: Message,
+            #[serde(default)]
+            prompt_eval_count: Option<u64>,
+            #[serde(default)]
+            eval_count: Option<u64>,
+        }
+
+        self.usage.set(None);
+        let url = format!("{}/api/chat", self.base_url);
+        let body = self.request(system, prompt, false);
+        let response = http::post_json_with_retry(&url, &[], &body, self.timeout, &self.retry)
+            .map_err(|e| ChatError::from_http("ollama", self.timeout, e))?;
+        let parsed: ChatResponse = serde_json::from_value(response)
+            .map_err(|e| ChatError::BadResponse { provider: "ollama", message: e.to_string() })?;
+        if let (Some(input), Some(output)) = (parsed.prompt_eval_count, parsed.eval_count) {
+            self.usage.set(Some(Usage { input_tokens: input, output_tokens: output }));
+        }
+        Ok(parsed.message.content)
+    }
+
+    fn complete_stream(&self, system: &str, prompt: &str, on_token: &mut dyn FnMut(&str)) -> Result<String> {
+        let url = format!("{}/api/chat", self.base_url);
+        let body = self.request(system, prompt, true);
+        let mut text = String::new();
+        let mut error = None;
+        let mut usage = None;
+        self.usage.set(None);
+        // One JSON object per line; the last ("done": true) carries the token counts
+        http::post_stream(&url, &[], &body, self.timeout, &mut |line| {
+            let Ok(event) = serde_json::from_str::<serde_json::Value>(line.trim()) else {
+                return;
+            };
+            if let Some(message) = event["error"].as_str() {
+                error = Some(message.to_string());
+                return;
+            }
+            if let Some(delta) = event["message"]["content"].as_str() {
+                on_token(delta);
+                text.push_str(delta);
+            }
+            if let (Some(input), Some(output)) = (event["prompt_eval_count"].as_u64(), event["eval_count"].as_u64()) {
+                usage = Some(Usage { input_tokens: input, output_tokens: output });
+            }
+        })
+        .map_err(|e| ChatError::from_http("ollama", self.timeout, e))?;
+        match error {
+            Some(message) => Err(ChatError::Api { provider: "ollama", status: None, message }.into()),
+            None => {
+                self.usage.set(usage);
+                Ok(text)
+            }
+        }
+    }
+
+    fn last_usage(&self) -> Option<Usage> {
+        self.usage.get()
+    }
+}
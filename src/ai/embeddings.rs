@@ -0,0 +1,225 @@
+use anyhow::Result;
+use super::http;
+use crate::storage::models::{LLMConfig, LLMProvider};
+use crate::transcript::fetcher::RetryPolicy;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const OLLAMA_DEFAULT_URL: &str = "http://localhost:11434";
+const OPENAI_DEFAULT_URL: &str = "https://api.openai.com/v1";
+
+// The OpenAI API accepts up to 2048 inputs per request; stay well under it
+const OPENAI_DEFAULT_BATCH: usize = 256;
+
+// Local models can take a while on the first request while they load
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Turns text into vectors for semantic search.
+pub trait EmbeddingProvider {
+    /// Model name recorded with each stored embedding
+    fn model(&self) -> &str;
+
+    /// One vector per input text, in order.
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>>;
+}
+
+/// The model used when none is given.
+pub fn default_model(provider: &LLMProvider) -> &'static str {
+    match provider {
+        LLMProvider::OpenAI => "text-embedding-3-small",
+        _ => "nomic-embed-text",
+    }
+}
+
+/// Build the embedding provider for an LLM config, with default connection settings.
+pub fn provider_for(config: &LLMConfig) -> Result<Box<dyn EmbeddingProvider>> {
+    match &config.provider {
+        LLMProvider::Local => Ok(Box::new(OllamaEmbeddings::new(&config.model))),
+        LLMProvider::OpenAI => Ok(Box::new(OpenAIEmbeddings::new(&config.model))),
+        other => anyhow::bail!("Embeddings are not supported for provider '{}' yet", other.as_str()),
+    }
+}
+
+/// Waits out the minimum interval between requests to one provider.
+struct Throttle {
+    min_interval: Duration,
+    last: Mutex<Option<Instant>>,
+}
+
+impl Throttle {
+    fn new(min_interval: Duration) -> Self {
+        Throttle { min_interval, last: Mutex::new(None) }
+    }
+
+    fn wait(&self) {
+        if self.min_interval.is_zero() {
+            return;
+        }
+        let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(prev) = *last {
+            let elapsed = prev.elapsed();
+            if elapsed < self.min_interval {
+                std::thread::sleep(self.min_interval - elapsed);
+            }
+        }
+        *last = Some(Instant::now());
+    }
+}
+
+/// Embeddings from a local Ollama server (`ollama pull nomic-embed-text`).
+/// The server address comes from OLLAMA_HOST, as for the ollama CLI.
+pub struct OllamaEmbeddings {
+    base_url: String,
+    model: String,
+    retry: RetryPolicy,
+}
+
+impl OllamaEmbeddings {
+    pub fn new(model: &str) -> Self {
+        let base_url = std::env::var("OLLAMA_HOST")
+            .ok()
+            .filter(|h| !h.is_empty())
+            .map(|h| if h.contains("://") { h } else { format!("http://{}", h) })
+            .unwrap_or_else(|| OLLAMA_DEFAULT_URL.to_string());
+        OllamaEmbeddings {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+}
+
+impl EmbeddingProvider for OllamaEmbeddings {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        #[derive(serde::Deserialize)]
+        struct EmbedResponse {
+            embeddings: Vec<Vec<f32>>,
+        }
+
+        let body = serde_json::json!({ "model": self.model, "input": texts });
+        let url = format!("{}/api/embed", self.base_url);
+        let response = http::post_json_with_retry(&url, &[], &body, REQUEST_TIMEOUT, &self.retry)?;
+        let parsed: EmbedResponse = serde_json::from_value(response)
+            .map_err(|e| anyhow::anyhow!("Unexpected response from Ollama: {}", e))?;
+
+        if parsed.embeddings.len() != texts.len() {
+            anyhow::bail!("Ollama returned {} embeddings for {} texts", parsed.embeddings.len(), texts.len());
+        }
+        Ok(parsed.embeddings)
+    }
+}
+
+/// Embeddings from the OpenAI API or any server exposing the same `/embeddings`
+/// endpoint (LM Studio, vLLM, LiteLLM, ...). Reads OPENAI_API_KEY and OPENAI_BASE_URL.
+pub struct OpenAIEmbeddings {
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+    batch_size: usize,
+    retry: RetryPolicy,
+    throttle: Throttle,
+}
+
+impl OpenAIEmbeddings {
+    pub fn new(model: &str) -> Self {
+        let base_url = std::env::var("OPENAI_BASE_URL")
+            .ok()
+            .filter(|u| !u.is_empty())
+            .unwrap_or_else(|| OPENAI_DEFAULT_URL.to_string());
+        OpenAIEmbeddings {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: std::env::var("OPENAI_API_KEY").ok().filter(|k| !k.is_empty()),
+            model: model.to_string(),
+            batch_size: OPENAI_DEFAULT_BATCH,
+            retry: RetryPolicy::default(),
+            throttle: Throttle::new(Duration::ZERO),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_api_key(mut self, api_key: &str) -> Self {
+        self.api_key = Some(api_key.to_string());
+        self
+    }
+
+    /// Most texts sent in one request; longer inputs are split across requests.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Minimum time between requests, to stay under a requests-per-minute limit.
+    pub fn with_rate_limit(mut self, min_interval: Duration) -> Self {
+        self.throttle = Throttle::new(min_interval);
+        self
+    }
+
+    fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        #[derive(serde::Deserialize)]
+        struct EmbeddingData {
+            index: usize,
+            embedding: Vec<f32>,
+        }
+        #[derive(serde::Deserialize)]
+        struct EmbeddingsResponse {
+            data: Vec<EmbeddingData>,
+        }
+
+        let body = serde_json::json!({ "model": self.model, "input": texts });
+        let auth = self.api_key.as_ref().map(|k| format!("Bearer {}", k));
+        let headers: Vec<(&str, &str)> = auth.iter().map(|a| ("Authorization", a.as_str())).collect();
+        let url = format!("{}/embeddings", self.base_url);
+
+        self.throttle.wait();
+        let response = http::post_json_with_retry(&url, &headers, &body, REQUEST_TIMEOUT, &self.retry)?;
+        let mut parsed: EmbeddingsResponse = serde_json::from_value(response)
+            .map_err(|e| anyhow::anyhow!("Unexpected response from {}: {}", url, e))?;
+
+        if parsed.data.len() != texts.len() {
+            anyhow::bail!("{} returned {} embeddings for {} texts", url, parsed.data.len(), texts.len());
+        }
+        parsed.data.sort_by_key(|d| d.index);
+        Ok(parsed.data.into_iter().map(|d| d.embedding).collect())
+    }
+}
+
+impl EmbeddingProvider for OpenAIEmbeddings {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(self.batch_size) {
+            vectors.extend(self.embed_batch(batch)?);
+        }
+        Ok(vectors)
+    }
+}
@@ -0,0 +1,152 @@
+use anyhow::Result;
+use super::validation::{check, parse_json};
+use crate::storage::models::TranscriptChunk;
+
+/// Recorded on every extracted entity; bump when the prompt changes meaningfully.
+pub const PROMPT_VERSION: &str = "entities-v1";
+
+pub const SYSTEM_PROMPT: &str = "You extract named entities from history lecture transcripts for a research knowledge base: \
+scholars and authors the speaker cites, technical terms the speaker defines, places discussed, and quotable lines. \
+Only include what the excerpt actually contains. Reply with JSON only, no commentary.";
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ExtractedScholar {
+    pub name: String,
+    #[serde(default)]
+    pub field: Option<String>,
+    /// What the speaker says about or draws from them
+    #[serde(default)]
+    pub context: Option<String>,
+    #[serde(default)]
+    pub timestamp: Option<f64>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ExtractedTerm {
+    pub term: String,
+    pub definition: String,
+    #[serde(default)]
+    pub timestamp: Option<f64>,
+}
+
+/// A place, with the model's best estimate of its coordinates (the ancient site, not a
+/// modern namesake). Used only for places not already on the map.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ExtractedPlace {
+    pub name: String,
+    #[serde(default)]
+    pub lat: Option<f64>,
+    #[serde(default)]
+    pub lon: Option<f64>,
+    #[serde(default)]
+    pub context: Option<String>,
+    #[serde(default)]
+    pub timestamp: Option<f64>,
+}
+
+impl ExtractedPlace {
+    /// Latitude and longitude, if both are given and in range.
+    pub fn coordinates(&self) -> Option<(f64, f64)> {
+        match (self.lat, self.lon) {
+            (Some(lat), Some(lon)) if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon) => Some((lat, lon)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ExtractedQuote {
+    pub text: String,
+    #[serde(default)]
+    pub speaker: Option<String>,
+    #[serde(default)]
+    pub timestamp: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct EntityBatch {
+    #[serde(default)]
+    pub scholars: Vec<ExtractedScholar>,
+    #[serde(default)]
+    pub terms: Vec<ExtractedTerm>,
+    #[serde(default)]
+    pub places: Vec<ExtractedPlace>,
+    #[serde(default)]
+    pub quotes: Vec<ExtractedQuote>,
+}
+
+impl EntityBatch {
+    /// Blank names and out-of-range coordinates, by position, e.g. `places[1]: ...`.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (i, s) in self.scholars.iter().enumerate() {
+            if s.name.trim().is_empty() {
+                problems.push(format!("scholars[{}]: name is empty", i));
+            }
+        }
+        for (i, t) in self.terms.iter().enumerate() {
+            if t.term.trim().is_empty() {
+                problems.push(format!("terms[{}]: term is empty", i));
+            }
+            if t.definition.trim().is_empty() {
+                problems.push(format!("terms[{}]: definition is empty", i));
+            }
+        }
+        for (i, p) in self.places.iter().enumerate() {
+            if p.name.trim().is_empty() {
+                problems.push(format!("places[{}]: name is empty", i));
+            }
+            if (p.lat.is_some() || p.lon.is_some()) && p.coordinates().is_none() {
+                problems.push(format!("places[{}]: lat and lon must both be given, within -90..90 and -180..180", i));
+            }
+        }
+        for (i, q) in self.quotes.iter().enumerate() {
+            if q.text.trim().is_empty() {
+                problems.push(format!("quotes[{}]: text is empty", i));
+            }
+        }
+        problems
+    }
+}
+
+/// The user prompt for one transcript chunk.
+pub fn chunk_prompt(video_title: &str, chunk: &TranscriptChunk) -> String {
+    format!(
+        r#"Video: {title}
+Excerpt {index} ({start:.0}s to {end:.0}s{chapter}):
+
+{text}
+
+Return a JSON object:
+{{
+  "scholars": [
+    {{ "name": "full name", "field": "e.g. archaeologist", "context": "what the speaker takes from them", "timestamp": seconds }}
+  ],
+  "terms": [
+    {{ "term": "the term", "definition": "the definition the speaker gives", "timestamp": seconds }}
+  ],
+  "places": [
+    {{ "name": "place name", "lat": latitude, "lon": longitude, "context": "why it comes up", "timestamp": seconds }}
+  ],
+  "quotes": [
+    {{ "text": "exact words from the excerpt", "speaker": "who is quoted, if not the lecturer", "timestamp": seconds }}
+  ]
+}}
+Timestamps are seconds into the video, estimated from the excerpt's range.
+Give coordinates for the historical site. Quotes must be verbatim and worth remembering on their own."#,
+        title = video_title,
+        index = chunk.chunk_index + 1,
+        start = chunk.start_time,
+        end = chunk.end_time,
+        chapter = chunk.chapter.as_deref().map(|c| format!(", chapter \"{}\"", c)).unwrap_or_default(),
+        text = chunk.text,
+    )
+}
+
+/// Parse a model response into an entity batch and validate it. Tolerates code fences
+/// and text around the JSON object.
+pub fn parse_entities(response: &str) -> Result<EntityBatch> {
+    let batch: EntityBatch = parse_json(response, "an entity list")?;
+    check(batch.problems())?;
+    Ok(batch)
+}
@@ -0,0 +1,128 @@
+use anyhow::Result;
+use super::validation::{check, parse_json};
+use crate::storage::models::{ClaimCategory, Confidence, LinkType, TranscriptChunk};
+
+/// Recorded on every extracted claim; bump when the prompt changes meaningfully.
+pub const PROMPT_VERSION: &str = "claims-v1";
+
+pub const SYSTEM_PROMPT: &str = "You extract atomic claims from history lecture transcripts for a research knowledge base. \
+Each claim is one self-contained statement that makes sense without the transcript. \
+Extract micro-claims as well as major theses: if the speaker makes three related points, that is three claims. \
+Quote the transcript exactly for each claim. Reply with JSON only, no commentary.";
+
+/// One claim as the model returns it. Unknown categories and confidences fall back
+/// to factual / medium when stored.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ExtractedClaim {
+    pub text: String,
+    #[serde(default)]
+    pub quote: String,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub confidence: Option<String>,
+    /// Seconds into the video, when the model can place it
+    #[serde(default)]
+    pub timestamp: Option<f64>,
+}
+
+/// A link between two claims of the same batch, by their position in `claims`.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ExtractedLink {
+    pub from: usize,
+    pub to: usize,
+    #[serde(rename = "type")]
+    pub link_type: String,
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct ClaimBatch {
+    #[serde(default)]
+    pub claims: Vec<ExtractedClaim>,
+    #[serde(default)]
+    pub links: Vec<ExtractedLink>,
+}
+
+impl ClaimBatch {
+    /// Everything in the batch that would otherwise be dropped or stored with a
+    /// fallback, by position, e.g. `claims[2]: quote is empty`.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (i, c) in self.claims.iter().enumerate() {
+            if c.text.trim().is_empty() {
+                problems.push(format!("claims[{}]: text is empty", i));
+            }
+            if c.quote.trim().is_empty() {
+                problems.push(format!("claims[{}]: quote is empty", i));
+            }
+            if let Some(category) = c.category.as_deref().filter(|s| ClaimCategory::from_str(s).is_none()) {
+                problems.push(format!("claims[{}]: unknown category \"{}\"", i, category));
+            }
+            if let Some(confidence) = c.confidence.as_deref().filter(|s| Confidence::from_str(s).is_none()) {
+                problems.push(format!("claims[{}]: confidence \"{}\" is not high, medium or low", i, confidence));
+            }
+        }
+        for (i, link) in self.links.iter().enumerate() {
+            for end in [link.from, link.to] {
+                if end >= self.claims.len() {
+                    problems.push(format!("links[{}]: there is no claim {}", i, end));
+                }
+            }
+            if link.from == link.to {
+                problems.push(format!("links[{}]: links a claim to itself", i));
+            }
+            if LinkType::from_str(&link.link_type).is_none() {
+                problems.push(format!("links[{}]: unknown type \"{}\"", i, link.link_type));
+            }
+        }
+        problems
+    }
+}
+
+/// The user prompt for one transcript chunk.
+pub fn chunk_prompt(video_title: &str, chunk: &TranscriptChunk) -> String {
+    format!(
+        r#"Video: {title}
+Excerpt {index} ({start:.0}s to {end:.0}s{chapter}):
+
+{text}
+
+Return a JSON object:
+{{
+  "claims": [
+    {{
+      "text": "the claim, as a standalone sentence",
+      "quote": "exact words from the excerpt",
+      "category": "factual | causal | cyclical | memetic | geopolitical | phenomenological | metaphysical",
+      "confidence": "high | medium | low (how firmly the speaker asserts it)",
+      "timestamp": seconds into the video, estimated from the excerpt's range
+    }}
+  ],
+  "links": [
+    {{ "from": 0, "to": 1, "type": "supports | contradicts | elaborates | causes | related" }}
+  ]
+}}
+Links refer to claims by their position in the "claims" array."#,
+        title = video_title,
+        index = chunk.chunk_index + 1,
+        start = chunk.start_time,
+        end = chunk.end_time,
+        chapter = chunk.chapter.as_deref().map(|c| format!(", chapter \"{}\"", c)).unwrap_or_default(),
+        text = chunk.text,
+    )
+}
+
+/// Parse a model response into a claim batch and validate it. Tolerates code fences
+/// and text around the JSON object.
+pub fn parse_batch(response: &str) -> Result<ClaimBatch> {
+    let batch: ClaimBatch = parse_json(response, "a claim batch")?;
+    check(batch.problems())?;
+    Ok(batch)
+}
+
+/// The outermost `{ ... }` in a response.
+pub fn extract_json(response: &str) -> Option<&str> {
+    let start = response.find('{')?;
+    let end = response.rfind('}')?;
+    (end > start).then(|| &response[start..=end])
+}
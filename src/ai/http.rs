@@ -0,0 +1,205 @@
+use anyhow::Result;
+use crate::transcript::fetcher::RetryPolicy;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+// HTTP(S) goes through the curl binary, like yt-dlp for fetching: no TLS stack to build.
+// The request is passed as a curl config on stdin so API keys never show up in `ps`.
+
+// curl's exit code when --max-time runs out
+const CURL_TIMED_OUT: i32 = 28;
+
+/// Why a request failed. Returned inside `anyhow::Error`, so callers that care can
+/// `downcast_ref::<HttpError>()` and everyone else just sees the message.
+#[derive(Debug)]
+pub enum HttpError {
+    /// No response: connection refused, DNS, TLS, or the timeout ran out
+    Transport { url: String, message: String, timed_out: bool },
+    /// The server answered with a non-2xx status
+    Status { url: String, status: u16, body: String },
+    /// A 2xx response that wasn't JSON
+    InvalidJson { url: String, message: String },
+}
+
+impl std::fmt::Display for HttpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            HttpError::Transport { url, message, .. } => write!(f, "Request to {} failed: {}", url, message),
+            HttpError::Status { url, status, body } => write!(f, "{} returned HTTP {}: {}", url, status, body.trim()),
+            HttpError::InvalidJson { url, message } => write!(f, "Invalid JSON from {}: {}", url, message),
+        }
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+/// POST a JSON body and parse the JSON response. Non-2xx responses become errors
+/// carrying the response body, which is where providers put their error message.
+pub fn post_json(
+    url: &str,
+    headers: &[(&str, &str)],
+    body: &serde_json::Value,
+    timeout: Duration,
+) -> Result<serde_json::Value> {
+    post_json_with_retry(url, headers, body, timeout, &RetryPolicy::none())
+}
+
+/// Like `post_json`, retrying connection failures, 429 and 5xx responses. A
+/// Retry-After header is honoured when the server sends one.
+pub fn post_json_with_retry(
+    url: &str,
+    headers: &[(&str, &str)],
+    body: &serde_json::Value,
+    timeout: Duration,
+    retry: &RetryPolicy,
+) -> Result<serde_json::Value> {
+    let mut attempt = 1;
+    loop {
+        let (status, response, retry_after) = match post(url, headers, body, timeout) {
+            Ok(r) => r,
+            Err(_) if attempt < retry.max_attempts => {
+                std::thread::sleep(retry.backoff_for(attempt));
+                attempt += 1;
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+
+        if (200..300).contains(&status) {
+            return serde_json::from_str(&response).map_err(|e| {
+                HttpError::InvalidJson { url: url.to_string(), message: e.to_string() }.into()
+            });
+        }
+        let transient = status == 429 || status >= 500;
+        if !transient || attempt >= retry.max_attempts {
+            return Err(HttpError::Status { url: url.to_string(), status, body: response }.into());
+        }
+        std::thread::sleep(retry_after.unwrap_or_else(|| retry.backoff_for(attempt)));
+        attempt += 1;
+    }
+}
+
+/// POST a JSON body and hand each line of the response to `on_line` as it arrives:
+/// server-sent events or NDJSON, for streaming completions. Not retried, since part
+/// of the response may already have been used.
+pub fn post_stream(
+    url: &str,
+    headers: &[(&str, &str)],
+    body: &serde_json::Value,
+    timeout: Duration,
+    on_line: &mut dyn FnMut(&str),
+) -> Result<()> {
+    // The status goes to stderr, so every stdout line can be passed on as soon as it arrives
+    let mut child = spawn_curl(&curl_config(url, headers, body, timeout, "%{stderr}\\n%{http_code}"))?;
+    let stdout = child.stdout.take().ok_or_else(|| anyhow::anyhow!("curl stdout unavailable"))?;
+
+    let mut received = String::new();
+    for line in BufReader::new(stdout).lines() {
+        let line = line?;
+        on_line(&line);
+        received.push_str(&line);
+        received.push('\n');
+    }
+    let output = child.wait_with_output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let (message, status) = stderr.trim_end().rsplit_once('\n').unwrap_or(("", &stderr));
+    if !output.status.success() {
+        return Err(transport_error(url, message, output.status.code()));
+    }
+
+    let status: u16 = status.trim().parse().unwrap_or(0);
+    if !(200..300).contains(&status) {
+        return Err(HttpError::Status { url: url.to_string(), status, body: received }.into());
+    }
+    Ok(())
+}
+
+/// One request: (status, body, Retry-After).
+fn post(
+    url: &str,
+    headers: &[(&str, &str)],
+    body: &serde_json::Value,
+    timeout: Duration,
+) -> Result<(u16, String, Option<Duration>)> {
+    let child = spawn_curl(&curl_config(url, headers, body, timeout, "\\n%{http_code} %header{retry-after}"))?;
+    let output = child.wait_with_output()?;
+
+    if !output.status.success() {
+        return Err(transport_error(url, &String::from_utf8_lossy(&output.stderr), output.status.code()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (response, trailer) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+    let mut trailer = trailer.split_whitespace();
+    let status: u16 = trailer.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+    // Only the delay-seconds form; an HTTP date falls back to the retry backoff
+    let retry_after = trailer.next().and_then(|s| s.parse::<u64>().ok()).map(Duration::from_secs);
+    Ok((status, response.to_string(), retry_after))
+}
+
+/// The curl config for a JSON POST, with `write_out` reporting the status after the body.
+fn curl_config(
+    url: &str,
+    headers: &[(&str, &str)],
+    body: &serde_json::Value,
+    timeout: Duration,
+    write_out: &str,
+) -> String {
+    let mut config = String::new();
+    config.push_str(&format!("url = {}\n", quote(url)));
+    config.push_str("request = \"POST\"\n");
+    config.push_str("header = \"Content-Type: application/json\"\n");
+    for (name, value) in headers {
+        config.push_str(&format!("header = {}\n", quote(&format!("{}: {}", name, value))));
+    }
+    config.push_str(&format!("data-binary = {}\n", quote(&body.to_string())));
+    config.push_str(&format!("max-time = {}\n", timeout.as_secs().max(1)));
+    config.push_str("silent\nshow-error\n");
+    config.push_str("no-buffer\n");
+    config.push_str(&format!("write-out = \"{}\"\n", write_out));
+    config
+}
+
+/// Start curl with its config written to stdin.
+fn spawn_curl(config: &str) -> Result<Child> {
+    let mut child = Command::new("curl")
+        .args(["--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Failed to run curl (is it installed?): {}", e))?;
+    child
+        .stdin
+        .take()
+        .ok_or_else(|| anyhow::anyhow!("curl stdin unavailable"))?
+        .write_all(config.as_bytes())?;
+    Ok(child)
+}
+
+fn transport_error(url: &str, stderr: &str, exit_code: Option<i32>) -> anyhow::Error {
+    HttpError::Transport {
+        url: url.to_string(),
+        message: stderr.trim().to_string(),
+        timed_out: exit_code == Some(CURL_TIMED_OUT),
+    }
+    .into()
+}
+
+/// Quote a value for a curl config file.
+fn quote(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
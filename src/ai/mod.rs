@@ -0,0 +1,11 @@
+pub mod answering;
+pub mod chat;
+pub mod embeddings;
+pub mod entities;
+pub mod extraction;
+pub mod http;
+pub mod patterns;
+pub mod summarization;
+pub mod tagging;
+pub mod usage;
+pub mod validation;
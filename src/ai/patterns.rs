@@ -0,0 +1,96 @@
+use anyhow::Result;
+use super::validation::parse_json;
+use crate::storage::models::{Claim, PatternType};
+
+/// Recorded on every detected pattern; bump when the prompt changes meaningfully.
+pub const PROMPT_VERSION: &str = "patterns-v1";
+
+pub const SYSTEM_PROMPT: &str = "You look for patterns across claims from history lectures in a research knowledge base: \
+recurring themes that several claims share, contradictions between claims, and consensus where separate sources agree. \
+Only report a pattern the claims themselves show; a shared keyword is not a theme. Reply with JSON only.";
+
+/// A pattern the model found, with claims as indexes into the prompt's claims.
+#[derive(Debug, Clone)]
+pub struct PatternCandidate {
+    pub pattern_type: PatternType,
+    pub description: String,
+    pub claims: Vec<usize>,
+    pub confidence: f32,
+}
+
+/// The user prompt for one cluster: numbered claims with the video each comes from.
+pub fn cluster_prompt(scope: &str, claims: &[(&Claim, &str)]) -> String {
+    let mut listed = String::new();
+    for (i, (claim, video_title)) in claims.iter().enumerate() {
+        listed.push_str(&format!("[{}] ({}) {}\n", i + 1, video_title, claim.text.trim()));
+    }
+    format!(
+        r#"Scope: {scope}
+Claims:
+
+{listed}
+Return a JSON object with the patterns among these claims:
+{{
+  "patterns": [
+    {{
+      "type": "recurring_theme | contradiction | consensus",
+      "description": "one sentence naming the pattern",
+      "claims": [1, 4, 7],
+      "confidence": 0.0 to 1.0
+    }}
+  ]
+}}
+Each pattern needs at least two claims. Prefer patterns that span several videos.
+Return an empty list if there are none."#,
+        scope = scope,
+        listed = listed,
+    )
+}
+
+/// Parse the model's reply into patterns of the requested kinds. Claim numbers are
+/// turned into indexes into the prompt's claims; unknown numbers are dropped, and so
+/// is any pattern left with fewer than two claims.
+pub fn parse_patterns(response: &str, claims: usize) -> Result<Vec<PatternCandidate>> {
+    #[derive(serde::Deserialize)]
+    struct Pattern {
+        #[serde(rename = "type")]
+        pattern_type: String,
+        description: String,
+        #[serde(default)]
+        claims: Vec<usize>,
+        #[serde(default)]
+        confidence: Option<f32>,
+    }
+    #[derive(serde::Deserialize)]
+    struct Reply {
+        #[serde(default)]
+        patterns: Vec<Pattern>,
+    }
+
+    let reply: Reply = parse_json(response, "a pattern list")?;
+
+    let mut patterns = Vec::new();
+    for p in reply.patterns {
+        let pattern_type = match PatternType::from_str(&p.pattern_type) {
+            Some(t @ (PatternType::RecurringTheme | PatternType::Contradiction | PatternType::Consensus)) => t,
+            _ => continue,
+        };
+        let mut indexes: Vec<usize> = Vec::new();
+        for n in p.claims {
+            if (1..=claims).contains(&n) && !indexes.contains(&(n - 1)) {
+                indexes.push(n - 1);
+            }
+        }
+        let description = p.description.trim().to_string();
+        if indexes.len() < 2 || description.is_empty() {
+            continue;
+        }
+        patterns.push(PatternCandidate {
+            pattern_type,
+            description,
+            claims: indexes,
+            confidence: p.confidence.unwrap_or(0.6).clamp(0.0, 1.0),
+        });
+    }
+    Ok(patterns)
+}
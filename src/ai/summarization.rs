@@ -0,0 +1,103 @@
+use anyhow::Result;
+use super::chat::ChatProvider;
+use crate::storage::models::TranscriptChunk;
+
+/// How much text goes into one request; longer input is condensed in batches first.
+pub const MAX_INPUT_CHARS: usize = 24_000;
+
+pub const SYSTEM_PROMPT: &str = "You condense history lecture transcripts into progressive summaries for a research knowledge base. \
+Never add facts the speaker didn't state. Reply with the requested markdown only, no preamble.";
+
+/// Layer 2, map step: the key passages of one chunk, quoted.
+pub fn passages_prompt(video_title: &str, chunk: &TranscriptChunk) -> String {
+    format!(
+        r#"Video: {title}
+Excerpt {index} ({start} to {end}{chapter}):
+
+{text}
+
+List the key passages of this excerpt: the sentences that carry its argument, evidence and conclusions.
+Quote each passage verbatim as a markdown bullet starting with its approximate [mm:ss] timestamp,
+and **bold** the phrase that makes it matter. Skip filler, asides and repetition."#,
+        title = video_title,
+        index = chunk.chunk_index + 1,
+        start = clock(chunk.start_time),
+        end = clock(chunk.end_time),
+        chapter = chunk.chapter.as_deref().map(|c| format!(", chapter \"{}\"", c)).unwrap_or_default(),
+        text = chunk.text,
+    )
+}
+
+/// Layer 3: the best of the key passages.
+pub fn highlights_prompt(video_title: &str, passages: &str) -> String {
+    format!(
+        r#"Video: {title}
+Key passages:
+
+{passages}
+
+Keep only the best of these: the 10-20% of passages someone should remember from this video.
+Copy them unchanged, bullets, timestamps and bold included, in their original order."#,
+        title = video_title,
+        passages = passages,
+    )
+}
+
+/// Layer 4: a short executive summary.
+pub fn summary_prompt(video_title: &str, highlights: &str) -> String {
+    format!(
+        r#"Video: {title}
+Highlights:
+
+{highlights}
+
+Write an executive summary of the video in your own words: its thesis in one sentence, then
+two or three short paragraphs on the main arguments and evidence. At most 250 words."#,
+        title = video_title,
+        highlights = highlights,
+    )
+}
+
+/// Run `prompt` over `text`. Text too long for one request is split into batches
+/// of whole lines, each condensed with the same prompt, and the results are
+/// condensed again until they fit (map-reduce).
+pub fn condense(chat: &dyn ChatProvider, text: &str, prompt: &dyn Fn(&str) -> String) -> Result<String> {
+    let mut text = text.to_string();
+    // Each round should shrink the text; stop rather than loop if the model doesn't
+    for _ in 0..4 {
+        if text.len() <= MAX_INPUT_CHARS {
+            break;
+        }
+        let mut condensed = Vec::new();
+        for batch in batches(&text, MAX_INPUT_CHARS) {
+            condensed.push(chat.complete(SYSTEM_PROMPT, &prompt(&batch))?.trim().to_string());
+        }
+        text = condensed.join("\n\n");
+    }
+    Ok(chat.complete(SYSTEM_PROMPT, &prompt(&text))?.trim().to_string())
+}
+
+/// Split text into pieces of at most `max_chars`, breaking between lines. A single
+/// line longer than that becomes a piece of its own.
+pub fn batches(text: &str, max_chars: usize) -> Vec<String> {
+    let mut batches = Vec::new();
+    let mut current = String::new();
+    for line in text.lines() {
+        if !current.is_empty() && current.len() + line.len() + 1 > max_chars {
+            batches.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(line);
+    }
+    if !current.trim().is_empty() {
+        batches.push(current);
+    }
+    batches
+}
+
+fn clock(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    format!("{:02}:{:02}", total / 60, total % 60)
+}
@@ -0,0 +1,57 @@
+use anyhow::Result;
+use super::validation::parse_json;
+use crate::storage::models::AutoTags;
+
+pub const SYSTEM_PROMPT: &str = "You tag history lecture videos with eras, regions and topics for a research knowledge base. \
+Tag what a video is actually about, not what it mentions in passing or by comparison: \
+\"the Rome of the East\" in a video about Constantinople is not a reason to tag Rome. Reply with JSON only.";
+
+/// The user prompt for one video. The known names are listed so the model reuses
+/// them instead of inventing near-duplicates.
+pub fn tags_prompt(
+    title: &str,
+    description: Option<&str>,
+    summary: Option<&str>,
+    known: &AutoTags,
+) -> String {
+    let list = |names: &[String]| if names.is_empty() { "(none yet)".to_string() } else { names.join("; ") };
+    format!(
+        r#"Title: {title}
+Description: {description}
+Summary: {summary}
+
+Known eras: {eras}
+Known regions: {regions}
+Known topics: {topics}
+
+Return a JSON object with the eras, regions and topics this video is about:
+{{ "eras": [...], "regions": [...], "topics": [...] }}
+Use the known names exactly where one fits. Only suggest a new name when none of them does.
+Leave a list empty rather than guess."#,
+        title = title,
+        description = description.filter(|d| !d.trim().is_empty()).unwrap_or("(none)"),
+        summary = summary.unwrap_or("(none)"),
+        eras = list(&known.eras),
+        regions = list(&known.regions),
+        topics = list(&known.topics),
+    )
+}
+
+/// Parse the model's reply into tag names, ignoring blanks.
+pub fn parse_tags(response: &str) -> Result<AutoTags> {
+    #[derive(serde::Deserialize)]
+    struct Reply {
+        #[serde(default)]
+        eras: Vec<String>,
+        #[serde(default)]
+        regions: Vec<String>,
+        #[serde(default)]
+        topics: Vec<String>,
+    }
+
+    let reply: Reply = parse_json(response, "a tag list")?;
+    let clean = |names: Vec<String>| -> Vec<String> {
+        names.into_iter().map(|n| n.trim().to_string()).filter(|n| !n.is_empty()).collect()
+    };
+    Ok(AutoTags { eras: clean(reply.eras), regions: clean(reply.regions), topics: clean(reply.topics) })
+}
@@ -0,0 +1,115 @@
+use anyhow::Result;
+use std::time::Instant;
+use super::chat::{ChatProvider, Usage};
+use crate::storage::store::Storage;
+use crate::storage::models::{LLMProvider, LlmUsage};
+
+/// USD per million input and output tokens for common hosted models, matched by the
+/// longest prefix of the model name. Prices drift; set `llm.price` to override.
+const PRICES: &[(&str, f64, f64)] = &[
+    ("claude-3-haiku", 0.25, 1.25),
+    ("claude-3-5-haiku", 0.8, 4.0),
+    ("claude-haiku-4", 1.0, 5.0),
+    ("claude-3-5-sonnet", 3.0, 15.0),
+    ("claude-3-7-sonnet", 3.0, 15.0),
+    ("claude-sonnet-4", 3.0, 15.0),
+    ("claude-3-opus", 15.0, 75.0),
+    ("claude-opus-4", 15.0, 75.0),
+    ("gpt-4o", 2.5, 10.0),
+    ("gpt-4o-mini", 0.15, 0.6),
+    ("gpt-4.1", 2.0, 8.0),
+    ("gpt-4.1-mini", 0.4, 1.6),
+    ("gpt-4.1-nano", 0.1, 0.4),
+];
+
+/// The price of a model as (input, output) USD per million tokens. Local models
+/// cost nothing; unknown hosted models have no price.
+pub fn price_for(provider: &LLMProvider, model: &str) -> Option<(f64, f64)> {
+    if matches!(provider, LLMProvider::Local) {
+        return Some((0.0, 0.0));
+    }
+    PRICES
+        .iter()
+        .filter(|(prefix, _, _)| model.starts_with(prefix))
+        .max_by_key(|(prefix, _, _)| prefix.len())
+        .map(|(_, input, output)| (*input, *output))
+}
+
+/// Parse a price given as `INPUT,OUTPUT` USD per million tokens, e.g. `3,15`.
+pub fn parse_price(value: &str) -> Option<(f64, f64)> {
+    let (input, output) = value.split_once(',')?;
+    let input: f64 = input.trim().parse().ok()?;
+    let output: f64 = output.trim().parse().ok()?;
+    (input.is_finite() && output.is_finite() && input >= 0.0 && output >= 0.0).then_some((input, output))
+}
+
+pub fn cost(usage: Usage, (input, output): (f64, f64)) -> f64 {
+    (usage.input_tokens as f64 * input + usage.output_tokens as f64 * output) / 1_000_000.0
+}
+
+/// A chat provider that logs every call to `llm_usage`: tokens, latency and estimated
+/// cost, under the task it was made for. Failed calls are logged too.
+pub struct TrackedChat<'a> {
+    inner: Box<dyn ChatProvider>,
+    db: &'a dyn Storage,
+    provider: String,
+    task: String,
+    price: Option<(f64, f64)>,
+}
+
+impl<'a> TrackedChat<'a> {
+    pub fn new(inner: Box<dyn ChatProvider>, db: &'a dyn Storage, provider: &str, task: &str) -> Self {
+        TrackedChat { inner, db, provider: provider.to_string(), task: task.to_string(), price: None }
+    }
+
+    /// Price per million (input, output) tokens, for the cost estimate.
+    pub fn with_price(mut self, price: Option<(f64, f64)>) -> Self {
+        self.price = price;
+        self
+    }
+
+    fn record(&self, started: Instant, success: bool) -> Result<()> {
+        let usage = if success { self.inner.last_usage() } else { None };
+        self.db.record_llm_usage(&LlmUsage {
+            id: 0,
+            task: self.task.clone(),
+            provider: self.provider.clone(),
+            model: self.inner.model().to_string(),
+            input_tokens: usage.map(|u| u.input_tokens as i64),
+            output_tokens: usage.map(|u| u.output_tokens as i64),
+            latency_ms: started.elapsed().as_millis() as i64,
+            cost_usd: usage.zip(self.price).map(|(u, price)| cost(u, price)),
+            success,
+            created_at: chrono::Utc::now(),
+        })?;
+        Ok(())
+    }
+}
+
+impl ChatProvider for TrackedChat<'_> {
+    fn model(&self) -> &str {
+        self.inner.model()
+    }
+
+    fn provider(&self) -> &str {
+        &self.provider
+    }
+
+    fn complete(&self, system: &str, prompt: &str) -> Result<String> {
+        let started = Instant::now();
+        let result = self.inner.complete(system, prompt);
+        self.record(started, result.is_ok())?;
+        result
+    }
+
+    fn complete_stream(&self, system: &str, prompt: &str, on_token: &mut dyn FnMut(&str)) -> Result<String> {
+        let started = Instant::now();
+        let result = self.inner.complete_stream(system, prompt, on_token);
+        self.record(started, result.is_ok())?;
+        result
+    }
+
+    fn last_usage(&self) -> Option<Usage> {
+        self.inner.last_usage()
+    }
+}
@@ -0,0 +1,78 @@
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use super::chat::ChatProvider;
+use super::extraction::extract_json;
+
+/// At most this many problems are listed back to the model in a repair prompt.
+const MAX_LISTED: usize = 12;
+
+/// What is wrong with a model's reply, one problem per entry. Parse functions return
+/// it inside their `anyhow::Error`, so a repair prompt can list the problems.
+#[derive(Debug, Clone)]
+pub struct Invalid(pub Vec<String>);
+
+impl std::fmt::Display for Invalid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.join("; "))
+    }
+}
+
+impl std::error::Error for Invalid {}
+
+/// Deserialize the JSON object in a reply, tolerating code fences and text around it.
+/// `what` names the expected shape for the error, e.g. "a claim batch".
+pub fn parse_json<T: DeserializeOwned>(response: &str, what: &str) -> Result<T> {
+    let json = extract_json(response).ok_or_else(|| Invalid(vec!["No JSON object in response".to_string()]))?;
+    serde_json::from_str(json).map_err(|e| Invalid(vec![format!("Response is not {}: {}", what, e)]).into())
+}
+
+/// Fail with the problems found, if there are any.
+pub fn check(problems: Vec<String>) -> Result<()> {
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(Invalid(problems).into())
+    }
+}
+
+/// The follow-up prompt after an unusable reply: the original request, the reply, and
+/// what was wrong with it.
+pub fn repair_prompt(prompt: &str, reply: &str, error: &anyhow::Error) -> String {
+    let problems = match error.downcast_ref::<Invalid>() {
+        Some(invalid) => invalid.0.clone(),
+        None => vec![format!("{:#}", error)],
+    };
+    let mut listed: String = problems.iter().take(MAX_LISTED).map(|p| format!("- {}\n", p)).collect();
+    if problems.len() > MAX_LISTED {
+        listed.push_str(&format!("- and {} more\n", problems.len() - MAX_LISTED));
+    }
+    format!(
+        "{prompt}\n\nYour previous reply could not be used:\n\n{reply}\n\nProblems:\n{listed}\n\
+Reply again with the complete, corrected JSON object only.",
+        prompt = prompt,
+        reply = reply.trim(),
+        listed = listed,
+    )
+}
+
+/// Ask for a reply and parse it. If parsing or validation fails, ask once more with the
+/// problems included; if that reply fails too, the error carries its problems. Errors
+/// from the provider itself are returned as they are. The flag tells whether a repair
+/// was needed.
+pub fn complete_validated<T>(
+    chat: &dyn ChatProvider,
+    system: &str,
+    prompt: &str,
+    parse: impl Fn(&str) -> Result<T>,
+) -> Result<(T, bool)> {
+    let reply = chat.complete(system, prompt)?;
+    let error = match parse(&reply) {
+        Ok(value) => return Ok((value, false)),
+        Err(e) => e,
+    };
+    let repaired = chat.complete(system, &repair_prompt(prompt, &reply, &error))?;
+    match parse(&repaired) {
+        Ok(value) => Ok((value, true)),
+        Err(e) => Err(e.context("reply still invalid after a repair attempt")),
+    }
+}
@@ -1,6 +1,17 @@
+pub mod ai;
 pub mod storage;
 pub mod transcript;
 
-pub use storage::database::Database;
-pub use storage::models::{Video, Transcript, TranscriptSegment, SearchResult, SegmentMatch, Era, Region, Topic, Collection, Note, Location, VideoLocation, MapPin, AutoTags, SavedSearch, AdvancedSearchResult, ReportEntry, GeoJsonFeature, GeoJsonGeometry, GeoJsonProperties, GeoJsonCollection, Claim, ClaimCategory, Confidence, ClaimLink, LinkType, ClaimWithLinks, TranscriptLayer, TranscriptChunk, Embedding, EmbeddingSource, SimilarityResult, HybridSearchResult, ChunkMatch, EmbeddingStats, CyclicalType, CyclicalIndicator, LoopType, RelationStrength, CausalRelation, TransmissionType, IdeaTransmission, SystemPosition, GeopoliticalEntity, SurplusFlow, BraudelTimescale, TemporalObservation, FrameworkStats, MapOfContent, MocClaim, MocWithClaims, QuestionStatus, ResearchQuestion, QuestionEvidence, QuestionWithEvidence, DetectedPattern, PatternType, ReviewQueue, ClaimAccess, LLMProvider, LLMConfig, SynthesisStats, ProcessingStatus, AIProcessingQueue, SourceType, Source, VideoSource, Scholar, VideoScholar, VisualType, Visual, Term, EvidenceType, Evidence, Quote, SourceWithVideos, ScholarWithReferences, VisualWithContext, TermWithUsages, EvidenceWithContext, SearchResultType, UnifiedSearchResult, SearchResponse, SearchFacets};
-pub use transcript::fetcher::Fetcher;
+pub use storage::cache::QueryCache;
+pub use storage::database::{Database, DatabaseLocation};
+pub use storage::store::Storage;
+pub use storage::models::{Video, Transcript, TranscriptSegment, Chapter, Subscription, Archive, Attachment, Availability, VideoAvailability, AuditEntry, IntegrityIssue, MigrationStatus, SearchResult, SegmentMatch, TextMatch, Era, Region, RegionCount, Topic, Collection, Note, Location, VideoLocation, MapPin, TimelineKind, TimelineEntry, Timeline, AutoTags, SavedSearch, SearchHistoryEntry, Alias, AdvancedSearchResult, AdvancedSearchResponse, MetadataFacets, RankingProfile, ReportEntry, GeoJsonFeature, GeoJsonGeometry, GeoJsonProperties, GeoJsonCollection, Claim, ClaimCategory, Confidence, ClaimStatus, ClaimLink, LinkType, ClaimWithLinks, ClaimGraph, ClaimGraphFilter, ClaimGraphPage, ClaimPathStep, ClaimMetrics, ClaimProvenance, Contradiction, ContradictionGroup, DuplicateClaims, TranscriptLayer, TranscriptChunk, Embedding, EmbeddingSource, SimilarityResult, ProjectionPoint, HybridSearchResult, Fusion, ChunkMatch, EmbeddingStats, EmbeddingModel, EmbeddingModelStats, EmbeddingQueueItem, CyclicalType, CyclicalIndicator, LoopType, RelationStrength, CausalRelation, CausalChain, CausalLoop, TransmissionType, IdeaTransmission, SystemPosition, GeopoliticalEntity, SurplusFlow, BraudelTimescale, TemporalObservation, FrameworkStats, MapOfContent, MocClaim, MocWithClaims, QuestionStatus, ResearchQuestion, QuestionEvidence, QuestionWithEvidence, SuggestedQuestion, DetectedPattern, PatternProvenance, PatternType, ReviewQueue, ClaimSchedule, ClaimAccess, LLMProvider, LLMConfig, LlmUsage, UsageGrouping, LlmUsageSummary, TokenScope, ApiToken, SynthesisStats, ProcessingStatus, AIProcessingQueue, QueueEvent, VideoStatus, SourceType, Source, VideoSource, Scholar, VideoScholar, ClaimScholar, VisualType, Visual, Term, EvidenceType, Evidence, Quote, PendingQuote, Cited, SourceWithVideos, ScholarWithReferences, VisualWithContext, TermWithUsages, EvidenceWithContext, SearchResultType, UnifiedSearchResult, SearchResponse, SearchFacets};
+pub use ai::chat::{ChatProvider, ChatError, FallbackChat, Usage};
+#[cfg(feature = "anthropic")]
+pub use ai::chat::AnthropicChat;
+#[cfg(feature = "openai")]
+pub use ai::chat::OpenAIChat;
+#[cfg(feature = "ollama")]
+pub use ai::chat::OllamaChat;
+pub use ai::embeddings::{EmbeddingProvider, OllamaEmbeddings, OpenAIEmbeddings};
+pub use transcript::fetcher::{Fetcher, RetryPolicy};
@@ -1,32 +1,526 @@
-use anyhow::Result;
-use clap::{Parser, Subcommand};
-use engine::{Database, Fetcher, SourceType, VisualType, EvidenceType};
+use anyhow::{Context, Result};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use engine::{Database, DatabaseLocation, Storage, Fetcher, RetryPolicy, SourceType, VisualType, EvidenceType};
+use engine::storage::database::ConnectionOptions;
+use engine::storage::query::near_expression;
 use std::path::PathBuf;
+use std::time::Duration;
+
+mod server;
 
 #[derive(Parser)]
 #[command(name = "engine")]
 #[command(about = "YouTube transcript knowledge base", long_about = None)]
 struct Cli {
-    /// Database file path
-    #[arg(short, long, default_value = "knowledge.db")]
-    database: PathBuf,
+    /// Database file path or connection URL (sqlite://path, or postgres://user@host/db with --features postgres)
+    #[arg(short, long, env = "ENGINE_DATABASE_URL", default_value = "knowledge.db")]
+    database: DatabaseLocation,
+
+    /// Milliseconds to wait for a lock held by another process before giving up
+    #[arg(long, env = "ENGINE_BUSY_TIMEOUT", default_value_t = 5000)]
+    busy_timeout: u64,
+
+    /// Extra SQLite setting as name=value, applied after the defaults (repeatable), e.g. journal_mode=DELETE
+    #[arg(long = "db-pragma", value_name = "NAME=VALUE")]
+    db_pragmas: Vec<String>,
+
+    /// Open the database read-only; any command that writes fails (also applies to serve)
+    #[arg(long, env = "ENGINE_READ_ONLY")]
+    read_only: bool,
+
+    /// Passphrase for an encrypted database (requires the sqlcipher feature)
+    #[arg(long, env = "ENGINE_DB_PASSPHRASE", hide_env_values = true, conflicts_with = "keyfile")]
+    passphrase: Option<String>,
+
+    /// File holding the passphrase for an encrypted database
+    #[arg(long, env = "ENGINE_DB_KEYFILE")]
+    keyfile: Option<PathBuf>,
 
     #[command(subcommand)]
     command: Commands,
 }
 
+impl Cli {
+    fn connection_options(&self) -> Result<ConnectionOptions> {
+        let pragmas = self
+            .db_pragmas
+            .iter()
+            .map(|p| {
+                p.split_once('=')
+                    .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+                    .ok_or_else(|| anyhow::anyhow!("Expected --db-pragma name=value, got: {}", p))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let passphrase = match (&self.passphrase, &self.keyfile) {
+            (Some(passphrase), _) => Some(passphrase.clone()),
+            (None, Some(path)) => {
+                let key = std::fs::read_to_string(path)
+                    .map_err(|e| anyhow::anyhow!("Can't read keyfile {}: {}", path.display(), e))?;
+                Some(key.trim_end_matches(['\r', '\n']).to_string())
+            }
+            (None, None) => None,
+        };
+        Ok(ConnectionOptions {
+            busy_timeout: Duration::from_millis(self.busy_timeout),
+            pragmas,
+            passphrase,
+        })
+    }
+}
+
+/// yt-dlp retry and rate-limit settings, overridable via environment
+#[derive(clap::Args, Debug, Clone)]
+struct FetchOptions {
+    /// Attempts per yt-dlp call before giving up on transient errors (429, 5xx, timeouts)
+    #[arg(long, env = "ENGINE_FETCH_RETRIES", default_value_t = 4)]
+    retries: u32,
+    /// Initial backoff in seconds, doubled after each failed attempt
+    #[arg(long, env = "ENGINE_FETCH_BACKOFF", default_value_t = 2.0, value_parser = parse_seconds)]
+    backoff: f64,
+    /// Maximum backoff in seconds
+    #[arg(long, env = "ENGINE_FETCH_MAX_BACKOFF", default_value_t = 60.0, value_parser = parse_seconds)]
+    max_backoff: f64,
+    /// Random spread applied to each backoff (0.25 = ±25%)
+    #[arg(long, env = "ENGINE_FETCH_JITTER", default_value_t = 0.25)]
+    jitter: f64,
+    /// Minimum seconds between yt-dlp requests
+    #[arg(long, env = "ENGINE_FETCH_RATE_LIMIT", default_value_t = 1.0, value_parser = parse_seconds)]
+    rate_limit: f64,
+    /// Proxy URL for yt-dlp (defaults to HTTPS_PROXY/HTTP_PROXY)
+    #[arg(long, env = "ENGINE_FETCH_PROXY")]
+    proxy: Option<String>,
+    /// Netscape-format cookies file for age-restricted or region-locked videos
+    #[arg(long, env = "ENGINE_FETCH_COOKIES")]
+    cookies_file: Option<PathBuf>,
+}
+
+impl FetchOptions {
+    fn fetcher(&self) -> Fetcher {
+        let mut fetcher = Fetcher::new()
+            .with_retry(RetryPolicy {
+                max_attempts: self.retries.max(1),
+                initial_backoff: Duration::from_secs_f64(self.backoff),
+                max_backoff: Duration::from_secs_f64(self.max_backoff),
+                jitter: self.jitter,
+            })
+            .with_rate_limit(Duration::from_secs_f64(self.rate_limit));
+        if let Some(proxy) = &self.proxy {
+            fetcher = fetcher.with_proxy(proxy);
+        }
+        if let Some(cookies) = &self.cookies_file {
+            fetcher = fetcher.with_cookies_file(cookies);
+        }
+        fetcher
+    }
+}
+
+/// Longest wait accepted for a backoff or rate limit: one day
+const MAX_WAIT_SECS: f64 = 86_400.0;
+
+/// Seconds for a backoff or rate limit; rejects negative, non-finite and oversized values
+fn parse_seconds(s: &str) -> Result<f64, String> {
+    let secs: f64 = s.trim().parse().map_err(|_| format!("expected a number of seconds, got: {}", s))?;
+    if !secs.is_finite() || !(0.0..=MAX_WAIT_SECS).contains(&secs) {
+        return Err(format!("expected 0 to {} seconds, got: {}", MAX_WAIT_SECS, s));
+    }
+    Ok(secs)
+}
+
+/// Embedding provider and connection settings, overridable via environment.
+/// Provider and model fall back to the embeddings.provider / embeddings.model settings.
+#[derive(clap::Args, Debug, Clone)]
+struct EmbeddingOptions {
+    /// Embedding provider: ollama, or openai for any OpenAI-compatible server (OPENAI_API_KEY)
+    #[arg(short, long, env = "ENGINE_EMBED_PROVIDER")]
+    provider: Option<String>,
+    /// Embedding model (default: nomic-embed-text for ollama, text-embedding-3-small for openai)
+    #[arg(short, long, env = "ENGINE_EMBED_MODEL")]
+    model: Option<String>,
+    /// Base URL of the embedding server (defaults to OLLAMA_HOST / OPENAI_BASE_URL)
+    #[arg(long, env = "ENGINE_EMBED_URL")]
+    embed_url: Option<String>,
+    /// Texts sent per request
+    #[arg(long, env = "ENGINE_EMBED_BATCH_SIZE", default_value_t = 64)]
+    batch_size: usize,
+    /// Minimum seconds between requests, to stay under a provider's rate limit
+    #[arg(long, env = "ENGINE_EMBED_RATE_LIMIT", default_value_t = 0.0, value_parser = parse_seconds)]
+    embed_rate_limit: f64,
+    /// Attempts per request before giving up on rate limits (429) and server errors
+    #[arg(long, env = "ENGINE_EMBED_RETRIES", default_value_t = 4)]
+    embed_retries: u32,
+}
+
+impl EmbeddingOptions {
+    /// Options from the ENGINE_EMBED_* environment and defaults alone, for commands
+    /// such as serve whose own flags would clash with the embedding ones.
+    fn from_env() -> Result<Self> {
+        use clap::{Args, FromArgMatches};
+        let matches = Self::augment_args(clap::Command::new("embedding")).try_get_matches_from(["embedding"])?;
+        Ok(Self::from_arg_matches(&matches)?)
+    }
+
+    /// The provider named by flag, environment or setting, or None if there is none.
+    fn configured(&self, db: &dyn Storage) -> Result<Option<engine::LLMConfig>> {
+        let Some(name) = self.provider.clone().or(db.get_setting("embeddings.provider")?) else {
+            return Ok(None);
+        };
+        let Some(provider) = engine::LLMProvider::from_str(&name) else {
+            anyhow::bail!("Unknown embedding provider: {} (use ollama or openai)", name);
+        };
+        let model = match self.model.clone().or(db.get_setting("embeddings.model")?) {
+            Some(model) => model,
+            None => engine::ai::embeddings::default_model(&provider).to_string(),
+        };
+        Ok(Some(engine::LLMConfig { provider, model, temperature: 0.0, key_env: None, base_url: None, timeout_secs: None }))
+    }
+
+    /// The configured provider, falling back to a local Ollama server.
+    fn configured_or_local(&self, db: &dyn Storage) -> Result<engine::LLMConfig> {
+        Ok(match self.configured(db)? {
+            Some(config) => config,
+            None => {
+                let provider = engine::LLMProvider::Local;
+                let model = match &self.model {
+                    Some(model) => model.clone(),
+                    None => engine::ai::embeddings::default_model(&provider).to_string(),
+                };
+                engine::LLMConfig { provider, model, temperature: 0.0, key_env: None, base_url: None, timeout_secs: None }
+            }
+        })
+    }
+
+    fn embedder(&self, config: &engine::LLMConfig) -> Result<Box<dyn engine::EmbeddingProvider>> {
+        let retry = RetryPolicy { max_attempts: self.embed_retries.max(1), ..RetryPolicy::default() };
+        match config.provider {
+            engine::LLMProvider::Local => {
+                let mut ollama = engine::OllamaEmbeddings::new(&config.model).with_retry(retry);
+                if let Some(url) = &self.embed_url {
+                    ollama = ollama.with_base_url(url);
+                }
+                Ok(Box::new(ollama))
+            }
+            engine::LLMProvider::OpenAI => {
+                let mut openai = engine::OpenAIEmbeddings::new(&config.model)
+                    .with_retry(retry)
+                    .with_batch_size(self.batch_size)
+                    .with_rate_limit(Duration::from_secs_f64(self.embed_rate_limit));
+                if let Some(url) = &self.embed_url {
+                    openai = openai.with_base_url(url);
+                }
+                Ok(Box::new(openai))
+            }
+            _ => engine::ai::embeddings::provider_for(config),
+        }
+    }
+
+    /// Embed a search query with the configured provider. None when no provider is configured.
+    fn embed_query(&self, db: &dyn Storage, text: &str) -> Result<Option<Vec<f32>>> {
+        let Some(config) = self.configured(db)? else {
+            return Ok(None);
+        };
+        let embedder = self.embedder(&config)?;
+        let vector = embedder
+            .embed(&[text.to_string()])?
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("{} returned no embedding", config.model))?;
+        match db.get_embedding_model(&config.model)? {
+            Some(known) if known.dimensions != vector.len() => anyhow::bail!(
+                "{} returned a {}-dimensional vector, but stored embeddings for it have {}",
+                config.model, vector.len(), known.dimensions
+            ),
+            Some(_) => {}
+            None => eprintln!("Warning: nothing has been embedded with {} yet; run embed-all first", config.model),
+        }
+        Ok(Some(vector))
+    }
+}
+
+/// Chat model for LLM-driven commands. Provider and model fall back to the
+/// llm.provider / llm.model settings.
+#[derive(clap::Args, Debug, Clone)]
+struct LlmOptions {
+    /// LLM provider: anthropic (ANTHROPIC_API_KEY), openai (OPENAI_API_KEY, OPENAI_BASE_URL) or local (Ollama)
+    #[arg(long, env = "ENGINE_LLM_PROVIDER")]
+    llm_provider: Option<String>,
+    /// LLM model (default depends on the provider)
+    #[arg(long, env = "ENGINE_LLM_MODEL")]
+    llm_model: Option<String>,
+}
+
+impl LlmOptions {
+    fn config(&self, db: &dyn Storage) -> Result<engine::LLMConfig> {
+        let Some(name) = self.llm_provider.clone().or(db.get_setting("llm.provider")?) else {
+            anyhow::bail!("No LLM configured: pass --llm-provider or run 'llm set --provider anthropic|openai|local'");
+        };
+        let Some(provider) = engine::LLMProvider::from_str(&name) else {
+            anyhow::bail!("Unknown LLM provider: {} (use anthropic, openai or local)", name);
+        };
+        let model = match self.llm_model.clone().or(db.get_setting("llm.model")?) {
+            Some(model) => model,
+            None => engine::ai::chat::default_model(&provider).to_string(),
+        };
+        let temperature = match db.get_setting("llm.temperature")? {
+            Some(t) => t.parse().map_err(|_| anyhow::anyhow!("Invalid llm.temperature: {}", t))?,
+            None => 0.0,
+        };
+        Ok(engine::LLMConfig {
+            provider,
+            model,
+            temperature,
+            key_env: db.get_setting("llm.key_env")?,
+            base_url: db.get_setting("llm.base_url")?,
+            timeout_secs: match db.get_setting("llm.timeout")? {
+                Some(t) => Some(t.parse().map_err(|_| anyhow::anyhow!("Invalid llm.timeout: {}", t))?),
+                None => None,
+            },
+        })
+    }
+}
+
+/// The chat provider for `config`, logging every call to llm_usage under `task`.
+/// Costs use the llm.price setting if set, else the built-in price of the model.
+/// Providers in llm.fallback are tried in order when the configured one fails.
+fn tracked_chat<'a>(db: &'a dyn Storage, config: &engine::LLMConfig, task: &str) -> Result<Box<dyn engine::ChatProvider + 'a>> {
+    use engine::ai::usage;
+
+    let price = match db.get_setting("llm.price")? {
+        Some(p) => Some(usage::parse_price(&p).ok_or_else(|| anyhow::anyhow!("Invalid llm.price: {}", p))?),
+        None => usage::price_for(&config.provider, &config.model),
+    };
+    let track = |chat, config: &engine::LLMConfig, price| -> Box<dyn engine::ChatProvider + 'a> {
+        Box::new(usage::TrackedChat::new(chat, db, config.provider.as_str(), task).with_price(price))
+    };
+    let fallbacks = fallback_configs(db, config)?;
+    let primary = engine::ai::chat::provider_for(config);
+    if fallbacks.is_empty() {
+        return Ok(track(primary?, config, price));
+    }
+
+    // A provider that can't even be set up (no API key, feature left out) is skipped
+    let mut chain = Vec::new();
+    match primary {
+        Ok(chat) => chain.push(track(chat, config, price)),
+        Err(e) => eprintln!("Warning: {:#}; falling back", e),
+    }
+    for fallback in &fallbacks {
+        match engine::ai::chat::provider_for(fallback) {
+            Ok(chat) => chain.push(track(chat, fallback, usage::price_for(&fallback.provider, &fallback.model))),
+            Err(e) => eprintln!("Warning: fallback {}/{} unavailable: {:#}", fallback.provider.as_str(), fallback.model, e),
+        }
+    }
+    if chain.is_empty() {
+        anyhow::bail!("No LLM provider in the fallback chain can be used");
+    }
+    Ok(Box::new(engine::FallbackChat::new(chain)))
+}
+
+/// The llm.fallback chain after `primary`. Temperature and timeout carry over; so do
+/// the key variable and server URL, for fallbacks with the primary's provider.
+fn fallback_configs(db: &dyn Storage, primary: &engine::LLMConfig) -> Result<Vec<engine::LLMConfig>> {
+    let Some(value) = db.get_setting("llm.fallback")? else {
+        return Ok(Vec::new());
+    };
+    let chain = engine::ai::chat::parse_chain(&value).ok_or_else(|| anyhow::anyhow!("Invalid llm.fallback: {}", value))?;
+    let mut configs: Vec<engine::LLMConfig> = Vec::new();
+    for (provider, model) in chain {
+        let model = model.unwrap_or_else(|| engine::ai::chat::default_model(&provider).to_string());
+        let same = provider.as_str() == primary.provider.as_str();
+        if (same && model == primary.model) || configs.iter().any(|c| c.provider.as_str() == provider.as_str() && c.model == model) {
+            continue;
+        }
+        configs.push(engine::LLMConfig {
+            provider,
+            model,
+            temperature: primary.temperature,
+            key_env: primary.key_env.clone().filter(|_| same),
+            base_url: primary.base_url.clone().filter(|_| same),
+            timeout_secs: primary.timeout_secs,
+        });
+    }
+    Ok(configs)
+}
+
+/// A note for a chunk's progress line: whether the reply needed a repair, and which
+/// provider answered when it wasn't the configured one.
+fn reply_note(chat: &dyn engine::ChatProvider, config: &engine::LLMConfig, repaired: bool) -> String {
+    let mut notes = Vec::new();
+    if repaired {
+        notes.push("reply repaired".to_string());
+    }
+    if chat.provider() != config.provider.as_str() || chat.model() != config.model {
+        notes.push(format!("via {}/{}", chat.provider(), chat.model()));
+    }
+    if notes.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", notes.join(", "))
+    }
+}
+
+/// Where an AI-extracted claim came from. Claims added with any of these set are
+/// listed by `all-claims --needs-review` until marked reviewed.
+#[derive(clap::Args, Debug, Clone)]
+struct ProvenanceOptions {
+    /// LLM provider that extracted the claim: anthropic, openai or local
+    #[arg(long, env = "ENGINE_LLM_PROVIDER")]
+    llm_provider: Option<String>,
+    /// LLM model that extracted the claim
+    #[arg(long, env = "ENGINE_LLM_MODEL")]
+    llm_model: Option<String>,
+    /// Version of the extraction prompt
+    #[arg(long)]
+    prompt_version: Option<String>,
+    /// Extraction run the claim belongs to
+    #[arg(long)]
+    run_id: Option<String>,
+}
+
+impl ProvenanceOptions {
+    fn is_empty(&self) -> bool {
+        self.llm_provider.is_none() && self.llm_model.is_none() && self.prompt_version.is_none() && self.run_id.is_none()
+    }
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Fetch a YouTube video transcript and store it
     Fetch {
-        /// YouTube URL or video ID
-        url: String,
+        /// YouTube URLs or video IDs
+        urls: Vec<String>,
+        /// Read more URLs from a file, one per line (# comments allowed)
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+        /// Number of videos to fetch concurrently
+        #[arg(short, long, env = "ENGINE_FETCH_JOBS", default_value_t = 4)]
+        jobs: usize,
+        /// Don't add to AI processing queue
+        #[arg(long)]
+        no_queue: bool,
+        /// Also download the thumbnail into this directory
+        #[arg(long, env = "ENGINE_THUMBNAIL_DIR")]
+        thumbnail_dir: Option<PathBuf>,
+        #[command(flatten)]
+        fetch: FetchOptions,
+        #[command(flatten)]
+        embedding: EmbeddingOptions,
+    },
+    /// Import a local media file or standalone transcript (SRT, VTT, TXT; speaker labels kept)
+    #[command(name = "import-media")]
+    ImportMedia {
+        /// Path to an audio/video file, or to a transcript file
+        path: PathBuf,
+        /// Title for the record
+        #[arg(long)]
+        title: String,
+        /// Transcript file to attach (SRT, VTT or plain text)
+        #[arg(long)]
+        transcript: Option<PathBuf>,
+        /// Channel, author or speaker
+        #[arg(long)]
+        channel: Option<String>,
+        /// Recording date (YYYY-MM-DD)
+        #[arg(long)]
+        date: Option<String>,
+        /// Description
+        #[arg(long)]
+        description: Option<String>,
         /// Don't add to AI processing queue
         #[arg(long)]
         no_queue: bool,
+        #[command(flatten)]
+        embedding: EmbeddingOptions,
     },
     /// List all stored videos
-    List,
+    List {
+        /// Only videos with a transcript in this language (e.g. en, de)
+        #[arg(long)]
+        lang: Option<String>,
+    },
+    /// Probe stored video URLs and report deleted or private videos
+    #[command(name = "check-availability")]
+    CheckAvailability {
+        /// Only check this video
+        #[arg(long)]
+        video: Option<String>,
+        /// Only print the report from the last check, without probing
+        #[arg(long)]
+        report: bool,
+        #[command(flatten)]
+        fetch: FetchOptions,
+    },
+    /// Follow a channel or playlist; new uploads are fetched by `watch`
+    Subscribe {
+        /// Channel or playlist URL
+        url: String,
+        /// Display name
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// List subscriptions
+    Subscriptions,
+    /// Stop following a channel or playlist
+    Unsubscribe {
+        /// Subscription ID
+        id: i64,
+    },
+    /// Poll subscriptions and a drop folder of URL files, fetching and queueing new videos
+    Watch {
+        /// Folder of text files with one URL per line; processed files move to <inbox>/processed, unreadable ones to <inbox>/failed
+        #[arg(long, env = "ENGINE_WATCH_INBOX")]
+        inbox: Option<PathBuf>,
+        /// Seconds between polls
+        #[arg(long, env = "ENGINE_WATCH_INTERVAL", default_value_t = 3600)]
+        interval: u64,
+        /// Recent uploads to look at per subscription
+        #[arg(long, default_value_t = 15)]
+        per_channel: usize,
+        /// Number of videos to fetch concurrently
+        #[arg(short, long, env = "ENGINE_FETCH_JOBS", default_value_t = 4)]
+        jobs: usize,
+        /// Run a single poll and exit (for cron)
+        #[arg(long)]
+        once: bool,
+        #[command(flatten)]
+        fetch: FetchOptions,
+        #[command(flatten)]
+        embedding: EmbeddingOptions,
+    },
+    /// List raw payloads archived for a video
+    Archives {
+        /// Video ID
+        video_id: String,
+    },
+    /// Attach a file (map, diagram, scan) to a video or claim
+    Attach {
+        /// Video ID, or claim ID with --claim
+        id: String,
+        /// File to attach
+        file: PathBuf,
+        /// Short description shown with the attachment
+        #[arg(long)]
+        caption: Option<String>,
+        /// Attach to a claim instead of a video
+        #[arg(long)]
+        claim: bool,
+        /// Reference the file where it is instead of storing a copy in the database
+        #[arg(long)]
+        link: bool,
+    },
+    /// Remove an attachment
+    Detach {
+        /// Attachment ID
+        id: i64,
+    },
+    /// Rebuild transcripts from archived caption payloads (discards edit-transcript changes)
+    Reparse {
+        /// Video ID, or "all"
+        id: String,
+    },
+    /// Re-extract chapter markers from descriptions and list them
+    Chapters {
+        /// Video ID, or "all" to rescan every video
+        id: String,
+    },
     /// Show a video and its transcript
     Show {
         /// Video ID
@@ -35,10 +529,31 @@ enum Commands {
         #[arg(short, long)]
         full: bool,
     },
+    /// Summarize every pipeline artifact for one video
+    Status {
+        /// Video ID
+        video_id: String,
+    },
     /// Search transcripts (basic full-text search)
+    ///
+    /// Query syntax:
+    ///   bronze age                      both words, anywhere
+    ///   "bronze age"                    exact phrase
+    ///   rome OR carthage                either word
+    ///   collapse NOT climate            first word without the second
+    ///   mesopotam*                      words starting with a prefix
+    ///   (rome OR carthage) AND trade    grouping
+    ///   NEAR(famine revolt, 10)         both within 10 words of each other
+    ///
+    /// AND, OR, NOT and NEAR must be uppercase. Other punctuation is ignored.
+    #[command(verbatim_doc_comment)]
     Search {
-        /// Search query
-        query: String,
+        /// Search query (see --help for syntax)
+        #[arg(required_unless_present = "near")]
+        query: Option<String>,
+        /// Terms that must occur close together, then an optional distance in words (default 10)
+        #[arg(long, value_name = "TERMS", allow_hyphen_values = true)]
+        near: Option<String>,
         /// Filter by era
         #[arg(short, long)]
         era: Option<String>,
@@ -48,6 +563,15 @@ enum Commands {
         /// Filter by topic
         #[arg(short, long)]
         topic: Option<String>,
+        /// Only segments spoken by this speaker (diarized transcripts)
+        #[arg(short, long)]
+        speaker: Option<String>,
+        /// Only videos in this collection
+        #[arg(short, long)]
+        collection: Option<String>,
+        /// Where to look, comma-separated (default: everywhere)
+        #[arg(long = "in", value_name = "SCOPES", value_delimiter = ',', value_parser = ["videos", "notes", "quotes", "terms", "summaries"])]
+        scopes: Vec<String>,
     },
     /// Tag a video with era and/or region
     Tag {
@@ -60,8 +584,37 @@ enum Commands {
         #[arg(short, long)]
         region: Option<String>,
     },
+    /// Remove an era, region and/or topic tag from a video
+    Untag {
+        /// Video ID
+        id: String,
+        /// Era to remove
+        #[arg(short, long)]
+        era: Option<String>,
+        /// Region to remove
+        #[arg(short, long)]
+        region: Option<String>,
+        /// Topic to remove
+        #[arg(short, long)]
+        topic: Option<String>,
+    },
     /// List all eras
     Eras,
+    /// Add a new era with an optional year range (negative years are BCE)
+    #[command(name = "add-era")]
+    AddEra {
+        /// Era name
+        name: String,
+        /// First year (e.g., -3300 for 3300 BCE)
+        #[arg(long, allow_negative_numbers = true)]
+        start: Option<i32>,
+        /// Last year
+        #[arg(long, allow_negative_numbers = true)]
+        end: Option<i32>,
+        /// Position in era listings (default: after existing eras)
+        #[arg(long)]
+        order: Option<i32>,
+    },
     /// List all regions
     Regions,
     /// Add a new region
@@ -72,6 +625,60 @@ enum Commands {
         #[arg(short, long)]
         parent: Option<String>,
     },
+    /// Rename an era
+    #[command(name = "rename-era")]
+    RenameEra {
+        /// Current name
+        old: String,
+        /// New name
+        new: String,
+    },
+    /// Rename a region
+    #[command(name = "rename-region")]
+    RenameRegion {
+        /// Current name
+        old: String,
+        /// New name
+        new: String,
+    },
+    /// Rename a topic
+    #[command(name = "rename-topic")]
+    RenameTopic {
+        /// Current name
+        old: String,
+        /// New name
+        new: String,
+    },
+    /// Merge one era into another (e.g., merge-eras "Late Antiquity" into "Classical Antiquity")
+    #[command(name = "merge-eras")]
+    MergeEras {
+        /// Era to fold away
+        source: String,
+        #[arg(value_name = "INTO", value_parser = ["into", "INTO"], hide = true)]
+        into: String,
+        /// Era to keep
+        target: String,
+    },
+    /// Merge one region into another (e.g., merge-regions Rome into "Roman Empire")
+    #[command(name = "merge-regions")]
+    MergeRegions {
+        /// Region to fold away
+        source: String,
+        #[arg(value_name = "INTO", value_parser = ["into", "INTO"], hide = true)]
+        into: String,
+        /// Region to keep
+        target: String,
+    },
+    /// Merge one topic into another
+    #[command(name = "merge-topics")]
+    MergeTopics {
+        /// Topic to fold away
+        source: String,
+        #[arg(value_name = "INTO", value_parser = ["into", "INTO"], hide = true)]
+        into: String,
+        /// Topic to keep
+        target: String,
+    },
     /// Browse videos by era and/or region
     Browse {
         /// Filter by era
@@ -80,7 +687,13 @@ enum Commands {
         /// Filter by region
         #[arg(short, long)]
         region: Option<String>,
+        /// Also match videos tagged with regions nested under --region
+        #[arg(long, requires = "region")]
+        include_subregions: bool,
     },
+    /// Show the region hierarchy with video counts
+    #[command(name = "region-tree")]
+    RegionTree,
     /// Add a topic to a video
     Topic {
         /// Video ID
@@ -104,6 +717,14 @@ enum Commands {
         #[arg(short, long)]
         into: String,
     },
+    /// Remove a video from a collection
+    Uncollect {
+        /// Video ID
+        id: String,
+        /// Collection name
+        #[arg(short, long)]
+        from: String,
+    },
     /// List all collections or show videos in a collection
     Collections {
         /// Collection name (optional, shows videos in collection)
@@ -162,22 +783,70 @@ enum Commands {
     Locations,
     /// Start web server for map visualization
     Serve {
+        /// Address to listen on: 127.0.0.1 for this machine only, 0.0.0.0 for every device on the network
+        #[arg(long, default_value = "127.0.0.1", env = "ENGINE_BIND")]
+        bind: std::net::IpAddr,
         /// Port to listen on
         #[arg(short, long, default_value = "3000")]
         port: u16,
+        /// Require this read-write token on API requests, besides any made with `token create`
+        #[arg(long, env = "ENGINE_AUTH_TOKEN", hide_env_values = true)]
+        auth_token: Option<String>,
+        /// Let pages from this origin (e.g. https://notes.example.com) call the API; repeatable,
+        /// or `*` for any. Without it only the server's own web UI can
+        #[arg(long = "cors-origin", value_name = "ORIGIN", env = "ENGINE_CORS_ORIGIN", value_delimiter = ',')]
+        cors_origins: Vec<String>,
+        /// PEM certificate chain to serve HTTPS with, alongside --key (requires the tls feature)
+        #[arg(long, requires = "key", env = "ENGINE_TLS_CERT")]
+        cert: Option<PathBuf>,
+        /// PEM private key for --cert
+        #[arg(long, requires = "cert", env = "ENGINE_TLS_KEY")]
+        key: Option<PathBuf>,
     },
     /// Suggest tags for a video based on title/description
     SuggestTags {
         /// Video ID
         id: String,
     },
-    /// Auto-tag a video based on title/description
+    /// Auto-tag a video based on title/description, or with the LLM (--llm)
     AutoTag {
         /// Video ID (or "all" to tag all videos)
         id: String,
+        /// Ask the configured LLM, giving it the title, description and layer-4 summary
+        #[arg(long = "llm")]
+        use_llm: bool,
+        /// With --llm, create eras, regions and topics the LLM names that don't exist yet
+        #[arg(long, requires = "use_llm")]
+        allow_new: bool,
+        #[command(flatten)]
+        llm: LlmOptions,
     },
     /// Rebuild the search index
     RebuildIndex,
+    /// Show recent changes from the audit log, or past searches with `history searches`
+    History {
+        /// What to list
+        #[arg(value_parser = ["changes", "searches"], default_value = "changes")]
+        what: String,
+        /// Only changes to this table, e.g. claims, video_topics
+        #[arg(short, long)]
+        entity: Option<String>,
+        /// Only changes to this row ID (use with --entity)
+        #[arg(long)]
+        id: Option<String>,
+        /// Only changes made by this command, e.g. cli:auto-tag
+        #[arg(short, long)]
+        source: Option<String>,
+        /// Number of entries to show
+        #[arg(short, long, default_value_t = 50)]
+        limit: usize,
+    },
+    /// Check database integrity: corruption, orphaned rows and stale search indexes
+    Doctor {
+        /// Repair what can be repaired (removes orphaned rows, rebuilds indexes)
+        #[arg(long)]
+        fix: bool,
+    },
 
     // Phase 5: Research Tools
 
@@ -197,6 +866,21 @@ enum Commands {
         /// Topic filter (optional)
         #[arg(short, long)]
         topic: Option<String>,
+        /// Channel filter (optional)
+        #[arg(long)]
+        channel: Option<String>,
+        /// Collection filter (optional)
+        #[arg(short, long)]
+        collection: Option<String>,
+        /// Uploaded on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        from: Option<String>,
+        /// Uploaded on or before this date (YYYY-MM-DD)
+        #[arg(long)]
+        to: Option<String>,
+        /// Query embedding as JSON array; makes this a hybrid (with --query) or semantic search
+        #[arg(short, long)]
+        vector: Option<String>,
     },
     /// List all saved searches
     Searches,
@@ -230,6 +914,73 @@ enum Commands {
         #[arg(short, long)]
         output: Option<String>,
     },
+    /// Export dated claims in chronological order, for timeline tools
+    #[command(name = "export-timeline")]
+    ExportTimeline {
+        /// Output format: json or csv
+        #[arg(short, long, value_parser = ["json", "csv"], default_value = "json")]
+        format: String,
+        /// Only claims overlapping years from this one (negative is BCE)
+        #[arg(long, allow_negative_numbers = true)]
+        from: Option<i32>,
+        /// ... up to this year
+        #[arg(long, allow_negative_numbers = true)]
+        to: Option<i32>,
+        /// Output file (prints to stdout if not specified)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Export the claim graph (claims, links, causal relations) for Graphviz or Gephi
+    #[command(name = "export-graph")]
+    ExportGraph {
+        /// Output format: dot or graphml
+        #[arg(short, long, value_parser = ["dot", "graphml"], default_value = "dot")]
+        format: String,
+        /// Only claims from this video
+        #[arg(long)]
+        video: Option<String>,
+        /// Only claims in this MOC
+        #[arg(long)]
+        moc: Option<i64>,
+        /// Only claims from videos tagged with this era
+        #[arg(short, long)]
+        era: Option<String>,
+        /// Output file (prints to stdout if not specified)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Export transcripts with metadata headers for external NLP pipelines
+    #[command(name = "export-corpus")]
+    ExportCorpus {
+        /// Output directory
+        #[arg(short, long)]
+        output: PathBuf,
+        /// Output format: txt (one file per video) or jsonl
+        #[arg(short, long, default_value = "txt")]
+        format: String,
+        /// Filter by era
+        #[arg(short, long)]
+        era: Option<String>,
+        /// Filter by topic
+        #[arg(short, long)]
+        topic: Option<String>,
+        /// Re-export everything, not just new or changed videos
+        #[arg(long)]
+        full: bool,
+    },
+    /// Export the whole database as a versioned NDJSON archive (gzipped if the name ends in .gz)
+    #[command(name = "export-all")]
+    ExportAll {
+        /// Output file, e.g. kb.ndjson.gz
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Load an archive written by export-all, replacing rows with the same keys
+    #[command(name = "import-all")]
+    ImportAll {
+        /// Archive file (.ndjson or .ndjson.gz)
+        input: PathBuf,
+    },
     /// Generate summary reports
     Report {
         /// Report type: era, region, or topic
@@ -259,6 +1010,45 @@ enum Commands {
         /// Timestamp in video (seconds)
         #[arg(short, long)]
         at: Option<f64>,
+        #[command(flatten)]
+        provenance: ProvenanceOptions,
+        #[command(flatten)]
+        embedding: EmbeddingOptions,
+    },
+    /// Mark AI-extracted claims as checked by a person
+    #[command(name = "mark-reviewed")]
+    MarkReviewed {
+        /// Claim IDs
+        ids: Vec<i64>,
+        /// Every claim from this extraction run
+        #[arg(long)]
+        run_id: Option<String>,
+        /// Put the claims back in the needs-review list
+        #[arg(long)]
+        undo: bool,
+    },
+    /// Correct a claim in place; with no field options, opens it in $EDITOR
+    #[command(name = "edit-claim")]
+    EditClaim {
+        /// Claim ID
+        id: i64,
+        /// New claim text
+        #[arg(short, long)]
+        text: Option<String>,
+        /// New source quote
+        #[arg(short, long)]
+        quote: Option<String>,
+        /// New category: cyclical, causal, memetic, geopolitical, factual
+        #[arg(short, long)]
+        category: Option<String>,
+        /// New confidence: high, medium, low
+        #[arg(long)]
+        confidence: Option<String>,
+        /// New timestamp in video (seconds)
+        #[arg(short, long)]
+        at: Option<f64>,
+        #[command(flatten)]
+        embedding: EmbeddingOptions,
     },
     /// List claims for a video
     Claims {
@@ -270,6 +1060,69 @@ enum Commands {
         /// Filter by category: cyclical, causal, memetic, geopolitical, factual
         #[arg(short, long)]
         category: Option<String>,
+        /// Filter by status: unverified, verified, disputed, retracted
+        #[arg(short, long)]
+        status: Option<String>,
+        /// Filter by topic (tagged on the claim or its video)
+        #[arg(short, long)]
+        topic: Option<String>,
+        /// Filter by era (tagged on the claim or its video)
+        #[arg(short, long)]
+        era: Option<String>,
+        /// Filter by origin: ai (extracted, with provenance) or manual
+        #[arg(long, value_parser = ["ai", "manual"])]
+        origin: Option<String>,
+        /// Only AI-extracted claims nobody has reviewed yet
+        #[arg(long)]
+        needs_review: bool,
+        /// Only claims dated within or overlapping years from this one (negative is BCE)
+        #[arg(long, allow_negative_numbers = true)]
+        from: Option<i32>,
+        /// ... up to this year
+        #[arg(long, allow_negative_numbers = true)]
+        to: Option<i32>,
+        /// Sort by the years claims are about, undated claims last
+        #[arg(long)]
+        chrono: bool,
+    },
+    /// Tag a claim with a topic and/or era (no options lists its tags)
+    #[command(name = "tag-claim")]
+    TagClaim {
+        /// Claim ID
+        id: i64,
+        /// Topic name (created if new)
+        #[arg(short, long)]
+        topic: Option<String>,
+        /// Era (e.g., "Bronze Age")
+        #[arg(short, long)]
+        era: Option<String>,
+        /// Remove the given tags instead
+        #[arg(long)]
+        remove: bool,
+    },
+    /// Mark a claim verified, disputed or retracted (or back to unverified), keeping its history
+    #[command(name = "set-claim-status")]
+    SetClaimStatus {
+        /// Claim ID
+        id: i64,
+        /// New status
+        #[arg(value_parser = ["unverified", "verified", "disputed", "retracted"])]
+        status: String,
+    },
+    /// Set the years a claim is about (negative years are BCE)
+    #[command(name = "date-claim")]
+    DateClaim {
+        /// Claim ID
+        id: i64,
+        /// First year, e.g. -1200 for 1200 BCE
+        #[arg(long, allow_negative_numbers = true, required_unless_present = "clear")]
+        from: Option<i32>,
+        /// Last year (default: same as --from)
+        #[arg(long, allow_negative_numbers = true, requires = "from")]
+        to: Option<i32>,
+        /// Remove the claim's dates
+        #[arg(long, conflicts_with = "from")]
+        clear: bool,
     },
     /// Show a claim with its links
     Claim {
@@ -286,6 +1139,25 @@ enum Commands {
         #[arg(short, long, default_value = "related")]
         r#as: String,
     },
+    /// Suggest links to semantically similar claims and accept them one by one as `related`
+    #[command(name = "suggest-links")]
+    SuggestLinks {
+        /// Claim ID
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        claim_id: Option<i64>,
+        /// Suggest links for every orphan claim (fewer than 2 links)
+        #[arg(long)]
+        all: bool,
+        /// Suggestions per claim
+        #[arg(short = 'k', long, default_value_t = 5)]
+        top: usize,
+        /// Ignore claims less similar than this
+        #[arg(long, default_value_t = 0.5)]
+        min_score: f32,
+        /// Only print the suggestions
+        #[arg(long)]
+        list: bool,
+    },
     /// Remove a link between claims
     Unlink {
         /// Source claim ID
@@ -295,37 +1167,102 @@ enum Commands {
     },
     /// Show claims that need more connections (< 2 links)
     Unlinked,
-    /// Delete a claim
-    DeleteClaim {
+    /// Shortest chain of links and causal relations between two claims
+    #[command(name = "claim-path")]
+    ClaimPath {
+        /// Starting claim ID
+        from: i64,
+        /// Target claim ID
+        to: i64,
+        /// Longest path to look for, in hops
+        #[arg(long, default_value_t = 6)]
+        max_depth: usize,
+    },
+    /// Claims within a few hops of a claim, nearest first
+    #[command(name = "claim-neighborhood")]
+    ClaimNeighborhood {
         /// Claim ID
         id: i64,
+        /// How many hops to follow
+        #[arg(short, long, default_value_t = 2)]
+        depth: usize,
     },
-    /// Generate chunks from a video transcript
+    /// Compute centrality and connected components over the claim graph and store them
+    #[command(name = "graph-metrics")]
+    GraphMetrics {
+        /// How many hub and bridging claims to list
+        #[arg(short = 'n', long, default_value_t = 10)]
+        top: usize,
+    },
+    /// Find near-identical claims by embedding similarity, and optionally merge them
+    #[command(name = "dedupe-claims")]
+    DedupeClaims {
+        /// Minimum cosine similarity for a pair to be reported
+        #[arg(short, long, default_value_t = 0.93)]
+        threshold: f32,
+        /// Most pairs to show
+        #[arg(short, long, default_value_t = 50)]
+        limit: usize,
+        /// Ask which claim to keep for each pair and merge the other into it
+        #[arg(long)]
+        merge: bool,
+    },
+    /// List claims joined by `contradicts` links, grouped by topic or MOC
+    Contradictions {
+        /// Group by: topic or moc
+        #[arg(short, long, value_parser = ["topic", "moc"], default_value = "topic")]
+        by: String,
+    },
+    /// Fold a duplicate claim into another: links, MOC entries, causal relations, question
+    /// evidence and embeddings move to the kept claim, then the duplicate is deleted
+    #[command(name = "merge-claims")]
+    MergeClaims {
+        /// Claim ID to keep
+        keep: i64,
+        /// Duplicate claim ID to merge and delete
+        duplicate: i64,
+    },
+    /// Delete a claim
+    DeleteClaim {
+        /// Claim ID
+        id: i64,
+    },
+    /// Generate chunks from a video transcript
     Chunk {
         /// Video ID (or "all" for all videos)
         id: String,
         /// Target tokens per chunk (default: 2000)
-        #[arg(short, long, default_value = "2000")]
+        #[arg(short, long, default_value_t = DEFAULT_CHUNK_TOKENS)]
         tokens: i32,
         /// Overlap percentage (default: 15)
-        #[arg(short, long, default_value = "15")]
+        #[arg(short, long, default_value_t = DEFAULT_CHUNK_OVERLAP)]
         overlap: i32,
+        #[command(flatten)]
+        embedding: EmbeddingOptions,
     },
     /// Show chunks for a video
     Chunks {
         /// Video ID
         video_id: String,
     },
-    /// Save a progressive summarization layer
+    /// Save a progressive summarization layer, or generate layers 2-4 with the LLM (--auto)
     Summarize {
         /// Video ID
         video_id: String,
-        /// Layer number (2-4)
-        #[arg(short, long)]
-        layer: u8,
+        /// Layer number (2-4); with --auto, regenerate just this layer (and any missing below it)
+        #[arg(short, long, required_unless_present = "auto")]
+        layer: Option<u8>,
         /// Summary content (reads from stdin if not provided)
-        #[arg(short, long)]
+        #[arg(short, long, conflicts_with = "auto")]
         content: Option<String>,
+        /// Generate the layers from the transcript chunks with the configured LLM
+        #[arg(long)]
+        auto: bool,
+        /// With --auto, replace layers that already exist
+        #[arg(long, requires = "auto")]
+        force: bool,
+        #[command(flatten)]
+        llm: LlmOptions,
     },
     /// Show summary layers for a video
     Layers {
@@ -368,17 +1305,50 @@ enum Commands {
         #[arg(short, long, default_value = "all")]
         source: String,
     },
-    /// Semantic search using a query embedding
+    /// Embed everything that has no embedding yet using a provider, e.g. a local Ollama server
+    #[command(name = "embed-all")]
+    EmbedAll {
+        /// Source type to embed: video, chunk, claim, all
+        #[arg(short, long, default_value = "all")]
+        source: String,
+        #[command(flatten)]
+        embedding: EmbeddingOptions,
+    },
+    /// Regenerate every embedding from one model with another, then make it the query default
+    #[command(name = "re-embed")]
+    ReEmbed {
+        /// Model whose embeddings are regenerated
+        #[arg(long)]
+        from_model: String,
+        /// Model to embed with (overrides --model)
+        #[arg(long)]
+        to_model: String,
+        /// Keep the old model's vectors instead of deleting them after the switch
+        #[arg(long)]
+        keep_old: bool,
+        #[command(flatten)]
+        embedding: EmbeddingOptions,
+    },
+    /// Semantic search by text (embedded with the configured provider) or a raw query embedding
     Semantic {
+        /// Text to search for; needs an embedding provider (--provider or `config embeddings.provider`)
+        #[arg(required_unless_present = "vector", conflicts_with = "vector")]
+        query: Option<String>,
         /// Query embedding as JSON array
         #[arg(short, long)]
-        vector: String,
+        vector: Option<String>,
         /// Filter by source type: video, chunk, claim
         #[arg(short, long)]
         source: Option<String>,
         /// Number of results (default: 10)
         #[arg(short, long, default_value = "10")]
         limit: usize,
+        /// Diversify results by maximal marginal relevance; optional weight from 0
+        /// (most diverse) to 1 (plain ranking)
+        #[arg(long, num_args = 0..=1, default_missing_value = "0.7")]
+        diversify: Option<f32>,
+        #[command(flatten)]
+        embedding: EmbeddingOptions,
     },
     /// Hybrid search combining keyword and semantic
     Hybrid {
@@ -396,6 +1366,29 @@ enum Commands {
         /// Number of results (default: 10)
         #[arg(short, long, default_value = "10")]
         limit: usize,
+        /// How to combine the lists: weighted score sum, or rrf (reciprocal rank fusion),
+        /// which is robust to keyword and cosine scores being on different scales
+        #[arg(long, default_value = "weighted", value_parser = ["weighted", "rrf"])]
+        fusion: String,
+        /// Embeds the query when no --vector is given and a provider is configured
+        #[command(flatten)]
+        embedding: EmbeddingOptions,
+    },
+    /// Answer a question from the knowledge base with the LLM, citing claims and videos
+    Answer {
+        /// The question
+        question: String,
+        /// Number of videos hybrid search draws passages from
+        #[arg(short = 'n', long, default_value = "5")]
+        videos: usize,
+        /// Attach the cited claims and videos as evidence to this research question
+        #[arg(long, value_name = "QUESTION_ID")]
+        attach: Option<i64>,
+        #[command(flatten)]
+        llm: LlmOptions,
+        /// Embeds the question for the semantic half of the search when a provider is configured
+        #[command(flatten)]
+        embedding: EmbeddingOptions,
     },
     /// Find similar items to a given embedding source
     Similar {
@@ -407,9 +1400,44 @@ enum Commands {
         /// Number of results (default: 10)
         #[arg(short, long, default_value = "10")]
         limit: usize,
+        /// Diversify results by maximal marginal relevance; optional weight from 0
+        /// (most diverse) to 1 (plain ranking)
+        #[arg(long, num_args = 0..=1, default_missing_value = "0.7")]
+        diversify: Option<f32>,
     },
     /// Show embedding statistics
-    EmbedStats,
+    EmbedStats {
+        /// Break counts down per model
+        #[arg(long)]
+        by_model: bool,
+    },
+    /// Reduce embeddings to 2D points (PCA) with labels and categories, for plotting a concept map
+    #[command(name = "export-projection")]
+    ExportProjection {
+        /// Output file (prints to stdout if omitted)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Only this source type: video, chunk, claim
+        #[arg(short, long)]
+        source: Option<String>,
+        /// Model to project (default: the one with the most embeddings)
+        #[arg(short, long)]
+        model: Option<String>,
+    },
+    /// List claims and chunks far from every cluster of embeddings, often garbled
+    /// transcription or mis-extracted claims
+    #[command(name = "embed-outliers")]
+    EmbedOutliers {
+        /// Only this source type: video, chunk, claim
+        #[arg(short, long)]
+        source: Option<String>,
+        /// Number of clusters (default: about sqrt(n/2))
+        #[arg(short = 'k', long)]
+        clusters: Option<usize>,
+        /// Number of results
+        #[arg(short, long, default_value_t = 20)]
+        limit: usize,
+    },
 
     // Phase 8: Analytical Frameworks
 
@@ -696,6 +1724,21 @@ enum Commands {
         /// Question ID
         id: i64,
     },
+    /// Propose research questions from contradictions, thin topics and unmapped clusters
+    #[command(name = "suggest-questions")]
+    SuggestQuestions,
+    /// Accept a suggested question, creating it with its evidence linked
+    #[command(name = "accept-question")]
+    AcceptQuestion {
+        /// Suggestion ID
+        id: i64,
+    },
+    /// Dismiss a suggested question so it is not proposed again
+    #[command(name = "dismiss-question")]
+    DismissQuestion {
+        /// Suggestion ID
+        id: i64,
+    },
     /// Record a detected pattern
     Pattern {
         /// Pattern type: recurring_theme, contradiction, consensus, evolution, parallel
@@ -724,6 +1767,18 @@ enum Commands {
         /// Pattern ID
         id: i64,
     },
+    /// Find recurring themes, contradictions and consensus among related claims with the LLM
+    #[command(name = "detect-patterns")]
+    DetectPatterns {
+        /// Claims to look at: era:NAME, region:NAME, topic:NAME, collection:NAME or video:ID
+        #[arg(long)]
+        scope: String,
+        /// Most claims sent to the LLM in one cluster
+        #[arg(long, default_value_t = 25)]
+        cluster_size: usize,
+        #[command(flatten)]
+        llm: LlmOptions,
+    },
     /// Show review queue (items needing attention)
     Review {
         /// Show only stale claims (not accessed in 30+ days)
@@ -732,9 +1787,30 @@ enum Commands {
         /// Show only orphan claims (< 2 links)
         #[arg(long)]
         orphans: bool,
+        /// Show only bridging claims (highest betweenness, from 'graph-metrics')
+        #[arg(long)]
+        bridges: bool,
+        /// Show only AI-extracted claims nobody has reviewed yet
+        #[arg(long)]
+        needs_review: bool,
         /// Number of random suggestions
         #[arg(short, long, default_value = "5")]
         random: usize,
+        /// Only claims with this status (retracted claims are skipped otherwise)
+        #[arg(long, value_parser = ["unverified", "verified", "disputed", "retracted"])]
+        status: Option<String>,
+    },
+    /// Quiz yourself on claims (results feed the spaced-repetition schedule)
+    Quiz {
+        /// Only claims from videos with this topic
+        #[arg(long)]
+        topic: Option<String>,
+        /// Number of questions
+        #[arg(short, long, default_value = "10")]
+        count: usize,
+        /// Question style: cloze (fill in the blank), source (which video), mixed
+        #[arg(long, default_value = "mixed")]
+        mode: String,
     },
     /// Show synthesis statistics
     SynthesisStats,
@@ -795,13 +1871,43 @@ enum Commands {
         #[arg(long)]
         failed: bool,
     },
-    /// Export transcript as plain text for AI processing
+    /// Drain the queue: chunk each pending video, extract claims and links with the LLM, mark it done
+    Process {
+        /// Process just this video (queued if it isn't already)
+        video_id: Option<String>,
+        /// Stop after this many videos
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+        /// Target tokens per chunk, for videos not chunked yet
+        #[arg(short, long, default_value_t = DEFAULT_CHUNK_TOKENS)]
+        tokens: i32,
+        #[command(flatten)]
+        llm: LlmOptions,
+        #[command(flatten)]
+        embedding: EmbeddingOptions,
+    },
+    /// Export a transcript as timestamped text, SRT, VTT, plain text or JSON
     ExportTranscript {
         /// Video ID
         video_id: String,
+        /// Output format: timestamped, txt, srt, vtt, json
+        #[arg(short, long, default_value = "timestamped")]
+        format: String,
     },
     /// Export pending video IDs from queue
     ExportQueue,
+    /// Correct transcript text, one segment at a time or in $EDITOR
+    #[command(name = "edit-transcript")]
+    EditTranscript {
+        /// Video ID
+        video_id: String,
+        /// Segment number to replace (1-based, as in SRT export)
+        #[arg(short, long, requires = "text")]
+        segment: Option<usize>,
+        /// Replacement text for the segment
+        #[arg(short, long, requires = "segment")]
+        text: Option<String>,
+    },
 
     // Phase 12: Expanded Knowledge Entities
 
@@ -874,6 +1980,24 @@ enum Commands {
         #[arg(long)]
         context: Option<String>,
     },
+    /// Cite a source or scholar for a claim, or list its citations
+    #[command(name = "cite-claim")]
+    CiteClaim {
+        /// Claim ID
+        claim_id: i64,
+        /// Source ID
+        #[arg(long)]
+        source: Option<i64>,
+        /// Scholar ID
+        #[arg(long)]
+        scholar: Option<i64>,
+        /// How the claim relates to the scholar (e.g. argues, disputes)
+        #[arg(long = "as", requires = "scholar")]
+        attribution: Option<String>,
+        /// Remove the citation instead
+        #[arg(long)]
+        remove: bool,
+    },
 
     /// Add a visual (image, diagram, artifact shown in video)
     #[command(name = "add-visual")]
@@ -975,28 +2099,248 @@ enum Commands {
         /// Video ID
         video_id: String,
     },
+    /// Find quoted speech and aphorisms in a transcript and stage them for review
+    #[command(name = "extract-quotes")]
+    ExtractQuotes {
+        /// Video ID
+        video_id: String,
+    },
+    /// Find cited scholars, defined terms, places and quotes in a transcript with the LLM
+    #[command(name = "extract-entities")]
+    ExtractEntities {
+        /// Video ID
+        video_id: String,
+        /// Target tokens per chunk, for videos not chunked yet
+        #[arg(short, long, default_value_t = DEFAULT_CHUNK_TOKENS)]
+        tokens: i32,
+        #[command(flatten)]
+        llm: LlmOptions,
+    },
+    /// List staged quotes awaiting confirmation
+    #[command(name = "pending-quotes")]
+    PendingQuotes {
+        /// Filter by video ID
+        #[arg(long)]
+        video: Option<String>,
+    },
+    /// Confirm a staged quote, moving it into the quotes table
+    #[command(name = "confirm-quote")]
+    ConfirmQuote {
+        /// Pending quote ID
+        id: i64,
+        /// Override the detected speaker
+        #[arg(long)]
+        speaker: Option<String>,
+    },
+    /// Discard a staged quote
+    #[command(name = "reject-quote")]
+    RejectQuote {
+        /// Pending quote ID
+        id: i64,
+    },
+    /// Move a video (with its claims) or a claim to the trash; hidden from lists, search and the graph
+    Archive {
+        /// Video ID, or claim ID with --claim
+        id: String,
+        /// Archive a single claim instead of a video
+        #[arg(long)]
+        claim: bool,
+    },
+    /// Restore an archived video or claim
+    Unarchive {
+        /// Video ID, or claim ID with --claim
+        id: String,
+        /// Restore a single claim instead of a video
+        #[arg(long)]
+        claim: bool,
+    },
+    /// Permanently delete archived videos and claims (lists them unless --yes is given)
+    Purge {
+        /// Only purge items archived more than this many days ago
+        #[arg(long)]
+        older_than: Option<i64>,
+        /// Actually delete
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Manage search aliases, e.g. `alias Achaemenid "Persian Empire"` (no arguments lists them)
+    Alias {
+        /// Name of the entity
+        name: Option<String>,
+        /// Other names it goes by
+        aliases: Vec<String>,
+        /// Remove the given aliases, or all aliases of the name if none are given
+        #[arg(long, requires = "name")]
+        remove: bool,
+    },
+    /// Show or change settings, e.g. `config search.weights 10,5,1` (no arguments lists them)
+    Config {
+        /// Setting name
+        key: Option<String>,
+        /// New value
+        value: Option<String>,
+        /// Reset the setting to its default
+        #[arg(long, conflicts_with = "value")]
+        unset: bool,
+    },
+    /// Configure, show and check the chat model used by process and other LLM commands
+    Llm {
+        #[command(subcommand)]
+        command: LlmCommand,
+    },
+    /// Manage tokens for the HTTP API; once one exists, serve requires a token
+    Token {
+        #[command(subcommand)]
+        command: TokenCommand,
+    },
+    /// Apply pending schema migrations (these also run automatically on open)
+    Migrate {
+        /// List migrations and whether each is applied, without applying anything
+        #[arg(long)]
+        status: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum TokenCommand {
+    /// Create a token and print it (it is shown only once)
+    Create {
+        /// A name to recognise and revoke it by, e.g. the client using it
+        name: String,
+        /// Allow changes through the write API; tokens are read-only by default
+        #[arg(long)]
+        write: bool,
+    },
+    /// List tokens with their scope and when they were last used
+    List,
+    /// Revoke a token
+    Revoke {
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum LlmCommand {
+    /// Save the LLM configuration (only the options given change)
+    Set {
+        /// anthropic, openai or local
+        #[arg(long)]
+        provider: Option<String>,
+        /// Model name (changing the provider alone resets it to that provider's default)
+        #[arg(long)]
+        model: Option<String>,
+        /// Environment variable holding the API key; the key itself is never stored
+        #[arg(long)]
+        key_env: Option<String>,
+        /// Server URL, e.g. an OpenAI-compatible proxy or a remote Ollama
+        #[arg(long)]
+        base_url: Option<String>,
+        #[arg(long)]
+        temperature: Option<f32>,
+        /// Providers to try in order when this one fails, e.g. "local:llama3.1,openai"
+        #[arg(long)]
+        fallback: Option<String>,
+        /// Clear the whole LLM configuration
+        #[arg(long, conflicts_with_all = ["provider", "model", "key_env", "base_url", "temperature", "fallback"])]
+        reset: bool,
+    },
+    /// Show the LLM configuration in effect, including environment overrides
+    Show {
+        #[command(flatten)]
+        llm: LlmOptions,
+    },
+    /// Send a one-line prompt to check the provider is reachable and the key works
+    Test {
+        /// Stream the reply as it is generated
+        #[arg(long)]
+        stream: bool,
+        #[command(flatten)]
+        llm: LlmOptions,
+    },
+    /// Tokens, latency and estimated cost of LLM calls, per model unless grouped otherwise
+    Usage {
+        /// Totals per day (UTC)
+        #[arg(long, conflicts_with = "by_task")]
+        by_day: bool,
+        /// Totals per task: extract-claims, summarize, auto-tag, answer, ...
+        #[arg(long)]
+        by_task: bool,
+        /// Only the last N days
+        #[arg(long)]
+        days: Option<i64>,
+    },
 }
 
 fn main() -> Result<()> {
-    let cli = Cli::parse();
-    let db = Database::open(&cli.database)?;
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches)?;
+    let options = cli.connection_options()?;
+    if let Commands::Migrate { status } = cli.command {
+        if cli.read_only && !status {
+            anyhow::bail!("Can't apply migrations with --read-only");
+        }
+        return cmd_migrate(&cli.database, &options, status);
+    }
+    let db = if cli.read_only {
+        Database::open_read_only(&cli.database, &options)?
+    } else {
+        Database::open_with(&cli.database, &options)?
+    };
+    if let Some(name) = matches.subcommand_name() {
+        db.set_audit_source(&format!("cli:{}", name))?;
+    }
 
     match cli.command {
-        Commands::Fetch { url, no_queue } => cmd_fetch(&db, &url, no_queue),
-        Commands::List => cmd_list(&db),
+        Commands::Fetch { urls, file, jobs, no_queue, thumbnail_dir, fetch, embedding } => {
+            cmd_fetch(&db, &urls, file.as_deref(), jobs, no_queue, thumbnail_dir.as_deref(), &fetch, &embedding)
+        }
+        Commands::ImportMedia { path, title, transcript, channel, date, description, no_queue, embedding } =>
+            cmd_import_media(&db, &path, &title, transcript.as_deref(), channel.as_deref(), date.as_deref(), description.as_deref(), no_queue, &embedding),
+        Commands::List { lang } => cmd_list(&db, lang.as_deref()),
+        Commands::CheckAvailability { video, report, fetch } => {
+            cmd_check_availability(&db, video.as_deref(), report, &fetch)
+        }
+        Commands::Subscribe { url, name } => cmd_subscribe(&db, &url, name.as_deref()),
+        Commands::Subscriptions => cmd_subscriptions(&db),
+        Commands::Unsubscribe { id } => cmd_unsubscribe(&db, id),
+        Commands::Watch { inbox, interval, per_channel, jobs, once, fetch, embedding } => {
+            cmd_watch(&db, inbox.as_deref(), interval, per_channel, jobs, once, &fetch, &embedding)
+        }
+        Commands::Attach { id, file, caption, claim, link } => cmd_attach(&db, &id, &file, caption.as_deref(), claim, link),
+        Commands::Detach { id } => cmd_detach(&db, id),
+        Commands::Archives { video_id } => cmd_archives(&db, &video_id),
+        Commands::Reparse { id } => cmd_reparse(&db, &id),
+        Commands::Chapters { id } => cmd_chapters(&db, &id),
         Commands::Show { id, full } => cmd_show(&db, &id, full),
-        Commands::Search { query, era, region, topic } => {
-            cmd_search(&db, &query, era.as_deref(), region.as_deref(), topic.as_deref())
+        Commands::Status { video_id } => cmd_status(&db, &video_id),
+        Commands::Search { query, near, era, region, topic, speaker, collection, scopes } => {
+            let query = match (query, near) {
+                (Some(q), Some(n)) => format!("({}) AND {}", q, near_expression(&n)),
+                (Some(q), None) => q,
+                (None, Some(n)) => near_expression(&n),
+                (None, None) => unreachable!("clap requires a query or --near"),
+            };
+            cmd_search(&db, &query, era.as_deref(), region.as_deref(), topic.as_deref(), speaker.as_deref(), collection.as_deref(), &scopes)
         }
         Commands::Tag { id, era, region } => cmd_tag(&db, &id, era.as_deref(), region.as_deref()),
+        Commands::Untag { id, era, region, topic } => cmd_untag(&db, &id, era.as_deref(), region.as_deref(), topic.as_deref()),
         Commands::Eras => cmd_eras(&db),
+        Commands::AddEra { name, start, end, order } => cmd_add_era(&db, &name, start, end, order),
         Commands::Regions => cmd_regions(&db),
         Commands::AddRegion { name, parent } => cmd_add_region(&db, &name, parent.as_deref()),
-        Commands::Browse { era, region } => cmd_browse(&db, era.as_deref(), region.as_deref()),
+        Commands::Browse { era, region, include_subregions } => cmd_browse(&db, era.as_deref(), region.as_deref(), include_subregions),
+        Commands::RegionTree => cmd_region_tree(&db),
         Commands::Topic { id, add } => cmd_topic(&db, &id, add.as_deref()),
         Commands::Topics => cmd_topics(&db),
+        Commands::RenameEra { old, new } => cmd_rename_tag(&db, "eras", &old, &new),
+        Commands::RenameRegion { old, new } => cmd_rename_tag(&db, "regions", &old, &new),
+        Commands::RenameTopic { old, new } => cmd_rename_tag(&db, "topics", &old, &new),
+        Commands::MergeEras { source, target, .. } => cmd_merge_tags(&db, "eras", &source, &target),
+        Commands::MergeRegions { source, target, .. } => cmd_merge_tags(&db, "regions", &source, &target),
+        Commands::MergeTopics { source, target, .. } => cmd_merge_tags(&db, "topics", &source, &target),
         Commands::ByTopic { name } => cmd_by_topic(&db, &name),
         Commands::Collect { id, into } => cmd_collect(&db, &id, &into),
+        Commands::Uncollect { id, from } => cmd_uncollect(&db, &id, &from),
         Commands::Collections { name } => cmd_collections(&db, name.as_deref()),
         Commands::NewCollection { name, description } => cmd_new_collection(&db, &name, description.as_deref()),
         Commands::Note { id, text, at } => cmd_note(&db, &id, &text, at),
@@ -1005,13 +2349,39 @@ fn main() -> Result<()> {
             cmd_locate(&db, &id, &place, lat, lon, era.as_deref(), topic.as_deref(), at, note.as_deref())
         }
         Commands::Locations => cmd_locations(&db),
-        Commands::Serve { port } => cmd_serve(cli.database, port),
+        Commands::Serve { bind, port, auth_token, cors_origins, cert, key } => {
+            let tls = cert.zip(key);
+            server::serve(cli.database, options, cli.read_only, server::ServeOptions { bind, port, auth_token, cors_origins, tls })
+        }
         Commands::SuggestTags { id } => cmd_suggest_tags(&db, &id),
-        Commands::AutoTag { id } => cmd_auto_tag(&db, &id),
+        Commands::AutoTag { id, use_llm: false, .. } => cmd_auto_tag(&db, &id),
+        Commands::AutoTag { id, allow_new, llm, .. } => cmd_auto_tag_llm(&db, &id, allow_new, &llm),
         Commands::RebuildIndex => cmd_rebuild_index(&db),
+        Commands::Doctor { fix } => cmd_doctor(&db, fix),
+        Commands::History { what, entity, id, source, limit } => {
+            if what == "searches" {
+                cmd_search_history(&db, limit)
+            } else {
+                cmd_history(&db, entity.as_deref(), id.as_deref(), source.as_deref(), limit)
+            }
+        }
         // Phase 5 commands
-        Commands::SaveSearch { name, query, era, region, topic } => {
-            cmd_save_search(&db, &name, query.as_deref(), era.as_deref(), region.as_deref(), topic.as_deref())
+        Commands::SaveSearch { name, query, era, region, topic, channel, collection, from, to, vector } => {
+            let search = engine::SavedSearch {
+                id: 0,
+                name,
+                query,
+                era,
+                region,
+                topic,
+                channel,
+                collection,
+                date_from: from,
+                date_to: to,
+                vector: None,
+                created_at: chrono::Utc::now(),
+            };
+            cmd_save_search(&db, search, vector.as_deref())
         }
         Commands::Searches => cmd_list_searches(&db),
         Commands::RunSearch { name } => cmd_run_search(&db, &name),
@@ -1020,24 +2390,64 @@ fn main() -> Result<()> {
         Commands::ExportMap { era, topic, output } => {
             cmd_export_map(&db, era.as_deref(), topic.as_deref(), output.as_deref())
         }
+        Commands::ExportTimeline { format, from, to, output } => {
+            cmd_export_timeline(&db, &format, from, to, output.as_deref())
+        }
+        Commands::ExportGraph { format, video, moc, era, output } => {
+            cmd_export_graph(&db, &format, video.as_deref(), moc, era.as_deref(), output.as_deref())
+        }
+        Commands::ExportAll { output } => cmd_export_all(&db, &output),
+        Commands::ImportAll { input } => cmd_import_all(&db, &input),
+        Commands::ExportCorpus { output, format, era, topic, full } => {
+            cmd_export_corpus(&db, &output, &format, era.as_deref(), topic.as_deref(), full)
+        }
         Commands::Report { by } => cmd_report(&db, &by),
         Commands::Stats => cmd_stats(&db),
         // Phase 6 commands
-        Commands::AddClaim { video_id, text, quote, category, confidence, at } => {
-            cmd_add_claim(&db, &video_id, &text, &quote, &category, &confidence, at)
+        Commands::AddClaim { video_id, text, quote, category, confidence, at, provenance, embedding } => {
+            cmd_add_claim(&db, &video_id, &text, &quote, &category, &confidence, at, &provenance, &embedding)
+        }
+        Commands::MarkReviewed { ids, run_id, undo } => cmd_mark_reviewed(&db, &ids, run_id.as_deref(), undo),
+        Commands::EditClaim { id, text, quote, category, confidence, at, embedding } => {
+            let fields = ClaimEdit { text, quote, category, confidence, at: at.map(Some) };
+            cmd_edit_claim(&db, id, fields, &embedding)
         }
         Commands::Claims { video_id } => cmd_claims(&db, &video_id),
-        Commands::AllClaims { category } => cmd_all_claims(&db, category.as_deref()),
+        Commands::AllClaims { category, status, topic, era, origin, needs_review, from, to, chrono } => cmd_all_claims(
+            &db,
+            category.as_deref(),
+            status.as_deref(),
+            topic.as_deref(),
+            era.as_deref(),
+            origin.as_deref(),
+            needs_review,
+            from,
+            to,
+            chrono,
+        ),
+        Commands::TagClaim { id, topic, era, remove } => cmd_tag_claim(&db, id, topic.as_deref(), era.as_deref(), remove),
+        Commands::SetClaimStatus { id, status } => cmd_set_claim_status(&db, id, &status),
+        Commands::DateClaim { id, from, to, clear } => cmd_date_claim(&db, id, from, to, clear),
         Commands::Claim { id } => cmd_claim(&db, id),
         Commands::Link { source, target, r#as } => cmd_link(&db, source, target, &r#as),
         Commands::Unlink { source, target } => cmd_unlink(&db, source, target),
         Commands::Unlinked => cmd_unlinked(&db),
+        Commands::ClaimPath { from, to, max_depth } => cmd_claim_path(&db, from, to, max_depth),
+        Commands::ClaimNeighborhood { id, depth } => cmd_claim_neighborhood(&db, id, depth),
+        Commands::GraphMetrics { top } => cmd_graph_metrics(&db, top),
+        Commands::SuggestLinks { claim_id, all, top, min_score, list } => {
+            cmd_suggest_links(&db, claim_id, all, top, min_score, list)
+        }
+        Commands::DedupeClaims { threshold, limit, merge } => cmd_dedupe_claims(&db, threshold, limit, merge),
+        Commands::Contradictions { by } => cmd_contradictions(&db, &by),
+        Commands::MergeClaims { keep, duplicate } => cmd_merge_claims(&db, keep, duplicate),
         Commands::DeleteClaim { id } => cmd_delete_claim(&db, id),
-        Commands::Chunk { id, tokens, overlap } => cmd_chunk(&db, &id, tokens, overlap),
+        Commands::Chunk { id, tokens, overlap, embedding } => cmd_chunk(&db, &id, tokens, overlap, &embedding),
         Commands::Chunks { video_id } => cmd_chunks(&db, &video_id),
-        Commands::Summarize { video_id, layer, content } => {
-            cmd_summarize(&db, &video_id, layer, content.as_deref())
+        Commands::Summarize { video_id, layer, content, auto: false, .. } => {
+            cmd_summarize(&db, &video_id, layer.unwrap_or_default(), content.as_deref())
         }
+        Commands::Summarize { video_id, layer, force, llm, .. } => cmd_summarize_auto(&db, &video_id, layer, force, &llm),
         Commands::Layers { video_id } => cmd_layers(&db, &video_id),
         Commands::ClaimStats => cmd_claim_stats(&db),
         // Phase 7 commands
@@ -1048,14 +2458,25 @@ fn main() -> Result<()> {
         Commands::ExportForEmbedding { output, source } => {
             cmd_export_for_embedding(&db, output.as_deref(), &source)
         }
-        Commands::Semantic { vector, source, limit } => {
-            cmd_semantic(&db, &vector, source.as_deref(), limit)
+        Commands::EmbedAll { source, embedding } => cmd_embed_all(&db, &source, &embedding),
+        Commands::ReEmbed { from_model, to_model, keep_old, embedding } => {
+            cmd_re_embed(&db, &from_model, &to_model, keep_old, &embedding)
+        }
+        Commands::Semantic { query, vector, source, limit, diversify, embedding } => {
+            cmd_semantic(&db, query.as_deref(), vector.as_deref(), source.as_deref(), limit, diversify, &embedding)
+        }
+        Commands::Hybrid { query, vector, kw_weight, sem_weight, limit, fusion, embedding } => {
+            cmd_hybrid(&db, &query, vector.as_deref(), kw_weight, sem_weight, limit, &fusion, &embedding)
+        }
+        Commands::Answer { question, videos, attach, llm, embedding } => {
+            cmd_answer(&db, &question, videos, attach, &llm, &embedding)
         }
-        Commands::Hybrid { query, vector, kw_weight, sem_weight, limit } => {
-            cmd_hybrid(&db, &query, vector.as_deref(), kw_weight, sem_weight, limit)
+        Commands::Similar { source, id, limit, diversify } => cmd_similar(&db, &source, &id, limit, diversify),
+        Commands::EmbedStats { by_model } => cmd_embed_stats(&db, by_model),
+        Commands::ExportProjection { output, source, model } => {
+            cmd_export_projection(&db, output.as_deref(), source.as_deref(), model.as_deref())
         }
-        Commands::Similar { source, id, limit } => cmd_similar(&db, &source, &id, limit),
-        Commands::EmbedStats => cmd_embed_stats(&db),
+        Commands::EmbedOutliers { source, clusters, limit } => cmd_embed_outliers(&db, source.as_deref(), clusters, limit),
         // Phase 8 commands
         Commands::Cyclical { video_id, r#type, entity, description, claim, era, at } => {
             cmd_cyclical(&db, &video_id, &r#type, &entity, &description, claim, era.as_deref(), at)
@@ -1112,12 +2533,19 @@ fn main() -> Result<()> {
         }
         Commands::AnswerQuestion { id, status } => cmd_answer_question(&db, id, &status),
         Commands::DeleteQuestion { id } => cmd_delete_question(&db, id),
+        Commands::SuggestQuestions => cmd_suggest_questions(&db),
+        Commands::AcceptQuestion { id } => cmd_accept_question(&db, id),
+        Commands::DismissQuestion { id } => cmd_dismiss_question(&db, id),
         Commands::Pattern { r#type, description, videos, claims, confidence } => {
             cmd_add_pattern(&db, &r#type, &description, videos.as_deref(), claims.as_deref(), confidence)
         }
         Commands::Patterns { r#type } => cmd_list_patterns(&db, r#type.as_deref()),
         Commands::DeletePattern { id } => cmd_delete_pattern(&db, id),
-        Commands::Review { stale, orphans, random } => cmd_review(&db, stale, orphans, random),
+        Commands::DetectPatterns { scope, cluster_size, llm } => cmd_detect_patterns(&db, &scope, cluster_size, &llm),
+        Commands::Review { stale, orphans, bridges, needs_review, random, status } => {
+            cmd_review(&db, stale, orphans, bridges, needs_review, random, status.as_deref())
+        }
+        Commands::Quiz { topic, count, mode } => cmd_quiz(&db, topic.as_deref(), count, &mode),
         Commands::SynthesisStats => cmd_synthesis_stats(&db),
 
         // Phase 10: AI Processing Queue
@@ -1129,8 +2557,14 @@ fn main() -> Result<()> {
         Commands::QueueComplete { video_id, claims } => cmd_queue_complete(&db, &video_id, claims),
         Commands::QueueFail { video_id, reason } => cmd_queue_fail(&db, &video_id, &reason),
         Commands::QueueClear { completed, failed } => cmd_queue_clear(&db, completed, failed),
-        Commands::ExportTranscript { video_id } => cmd_export_transcript(&db, &video_id),
+        Commands::Process { video_id, limit, tokens, llm, embedding } => {
+            cmd_process(&db, video_id.as_deref(), limit, tokens, &llm, &embedding)
+        }
+        Commands::ExportTranscript { video_id, format } => cmd_export_transcript(&db, &video_id, &format),
         Commands::ExportQueue => cmd_export_queue(&db),
+        Commands::EditTranscript { video_id, segment, text } => {
+            cmd_edit_transcript(&db, &video_id, segment.zip(text))
+        }
 
         // Phase 12: Expanded Knowledge Entities
         Commands::AddSource { title, author, source_type, year, url, notes } =>
@@ -1143,6 +2577,8 @@ fn main() -> Result<()> {
         Commands::Scholars => cmd_list_scholars(&db),
         Commands::CiteScholar { video_id, scholar_id, at, context } =>
             cmd_cite_scholar(&db, &video_id, scholar_id, at, context.as_deref()),
+        Commands::CiteClaim { claim_id, source, scholar, attribution, remove } =>
+            cmd_cite_claim(&db, claim_id, source, scholar, attribution.as_deref(), remove),
         Commands::AddVisual { video_id, description, at, visual_type, significance, location, era } =>
             cmd_add_visual(&db, &video_id, &description, at, &visual_type, significance.as_deref(), location.as_deref(), era.as_deref()),
         Commands::Visuals { video_id } => cmd_list_visuals(&db, &video_id),
@@ -1155,49 +2591,373 @@ fn main() -> Result<()> {
         Commands::AddQuote { video_id, text, speaker, at, context } =>
             cmd_add_quote(&db, &video_id, &text, speaker.as_deref(), at, context.as_deref()),
         Commands::Quotes { video_id } => cmd_list_quotes(&db, &video_id),
+        Commands::ExtractQuotes { video_id } => cmd_extract_quotes(&db, &video_id),
+        Commands::ExtractEntities { video_id, tokens, llm } => cmd_extract_entities(&db, &video_id, tokens, &llm),
+        Commands::PendingQuotes { video } => cmd_pending_quotes(&db, video.as_deref()),
+        Commands::ConfirmQuote { id, speaker } => cmd_confirm_quote(&db, id, speaker.as_deref()),
+        Commands::RejectQuote { id } => cmd_reject_quote(&db, id),
+        Commands::Archive { id, claim } => cmd_archive(&db, &id, claim, true),
+        Commands::Unarchive { id, claim } => cmd_archive(&db, &id, claim, false),
+        Commands::Purge { older_than, yes } => cmd_purge(&db, older_than, yes),
+        Commands::Alias { name, aliases, remove } => cmd_alias(&db, name.as_deref(), &aliases, remove),
+        Commands::Config { key, value, unset } => cmd_config(&db, key.as_deref(), value.as_deref(), unset),
+        Commands::Llm { command } => match command {
+            LlmCommand::Set { provider, model, key_env, base_url, temperature, fallback, reset } => cmd_llm_set(
+                &db,
+                provider.as_deref(),
+                model.as_deref(),
+                key_env.as_deref(),
+                base_url.as_deref(),
+                temperature,
+                fallback.as_deref(),
+                reset,
+            ),
+            LlmCommand::Show { llm } => cmd_llm_show(&db, &llm),
+            LlmCommand::Test { stream, llm } => cmd_llm_test(&db, &llm, stream),
+            LlmCommand::Usage { by_day, by_task, days } => cmd_llm_usage(&db, by_day, by_task, days),
+        },
+        Commands::Token { command } => match command {
+            TokenCommand::Create { name, write } => cmd_token_create(&db, &name, write),
+            TokenCommand::List => cmd_token_list(&db),
+            TokenCommand::Revoke { name } => cmd_token_revoke(&db, &name),
+        },
+        Commands::Migrate { .. } => unreachable!("handled before the database is opened"),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn cmd_fetch(
+    db: &dyn Storage,
+    urls: &[String],
+    file: Option<&std::path::Path>,
+    jobs: usize,
+    no_queue: bool,
+    thumbnail_dir: Option<&std::path::Path>,
+    options: &FetchOptions,
+    embedding: &EmbeddingOptions,
+) -> Result<()> {
+    let mut urls = urls.to_vec();
+    if let Some(path) = file {
+        let content = std::fs::read_to_string(path)?;
+        urls.extend(
+            content
+                .lines()
+                .map(|l| l.trim())
+                .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                .map(|l| l.to_string()),
+        );
     }
+
+    let fetched = match urls.len() {
+        0 => {
+            println!("No URLs given. Pass one or more URLs, or --file.");
+            return Ok(());
+        }
+        1 => cmd_fetch_one(db, &urls[0], no_queue, thumbnail_dir, options),
+        _ => cmd_fetch_batch(db, &urls, jobs, no_queue, thumbnail_dir, options),
+    };
+    // Videos saved before a failure still get embedded
+    drain_embedding_queue(db, embedding)?;
+    fetched
 }
 
-fn cmd_fetch(db: &Database, url: &str, no_queue: bool) -> Result<()> {
+fn cmd_fetch_one(
+    db: &dyn Storage,
+    url: &str,
+    no_queue: bool,
+    thumbnail_dir: Option<&std::path::Path>,
+    options: &FetchOptions,
+) -> Result<()> {
     println!("Fetching: {}", url);
 
-    let fetcher = Fetcher::new();
-    let (video, transcript) = fetcher.fetch(url)?;
+    let fetcher = options.fetcher();
+    let (video, transcript, archives) = fetcher.fetch_archived(url)?;
 
     println!("Title: {}", video.title);
     if let Some(ref channel) = video.channel {
         println!("Channel: {}", channel);
     }
+    if let Some(duration) = video.duration {
+        println!("Duration: {}", format_duration(duration));
+    }
+
+    if let Some(dir) = thumbnail_dir {
+        match fetcher.download_thumbnail(&video.url, &video.id, dir) {
+            Ok(Some(path)) => println!("Thumbnail: {}", path.display()),
+            Ok(None) => println!("Thumbnail: not available"),
+            Err(e) => println!("Thumbnail: failed ({})", e),
+        }
+    }
+
+    let chapters = store_fetched(db, &video, transcript.as_ref(), &archives, no_queue)?;
+    if chapters > 0 {
+        println!("Chapters: {}", chapters);
+    }
+
+    if let Some(ref t) = transcript {
+        println!("Transcript: {} segments, {} chars", t.segments.len(), t.full_text.len());
+        if !no_queue {
+            println!("Added to AI processing queue");
+        }
+    } else {
+        println!("Transcript: not available");
+    }
+
+    println!("Saved: {}", video.id);
+    Ok(())
+}
+
+/// Fetch many videos with up to `jobs` yt-dlp downloads in flight. Downloads
+/// run on blocking tasks; results are written to the database as they arrive.
+/// Fails after the whole batch has run if any video couldn't be saved.
+fn cmd_fetch_batch(
+    db: &dyn Storage,
+    urls: &[String],
+    jobs: usize,
+    no_queue: bool,
+    thumbnail_dir: Option<&std::path::Path>,
+    options: &FetchOptions,
+) -> Result<()> {
+    use std::sync::Arc;
+
+    let jobs = jobs.max(1);
+    let fetcher = Arc::new(options.fetcher());
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(jobs));
+    let total = urls.len();
+
+    println!("Fetching {} videos ({} at a time)...\n", total, jobs);
+
+    let failed = tokio::runtime::Runtime::new()?.block_on(async {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+        for url in urls {
+            let (fetcher, semaphore, tx) = (fetcher.clone(), semaphore.clone(), tx.clone());
+            let url = url.clone();
+            let thumbnail_dir = thumbnail_dir.map(|d| d.to_path_buf());
+
+            tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let task_url = url.clone();
+                let result = tokio::task::spawn_blocking(move || {
+                    let (video, transcript, archives) = fetcher.fetch_archived(&task_url)?;
+                    if let Some(dir) = thumbnail_dir {
+                        // A missing thumbnail shouldn't fail the whole video
+                        let _ = fetcher.download_thumbnail(&video.url, &video.id, &dir);
+                    }
+                    Ok::<_, anyhow::Error>((video, transcript, archives))
+                })
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|r| r);
+                let _ = tx.send((url, result));
+            });
+        }
+        drop(tx);
+
+        // The database connection stays on this thread; only downloads run in parallel
+        let (mut done, mut saved, mut failed) = (0, 0, Vec::new());
+        while let Some((url, result)) = rx.recv().await {
+            done += 1;
+            match result.and_then(|(video, transcript, archives)| {
+                store_fetched(db, &video, transcript.as_ref(), &archives, no_queue)?;
+                Ok((video, transcript))
+            }) {
+                Ok((video, transcript)) => {
+                    saved += 1;
+                    let detail = match &transcript {
+                        Some(t) => format!("{} segments", t.segments.len()),
+                        None => "no transcript".to_string(),
+                    };
+                    println!("[{}/{}] {:<12} {} ({})", done, total, video.id, truncate(&video.title, 50), detail);
+                }
+                Err(e) => {
+                    println!("[{}/{}] FAILED {}: {}", done, total, url, e.to_string().lines().next().unwrap_or(""));
+                    failed.push(url);
+                }
+            }
+        }
+
+        println!("\nSaved {} of {} videos.", saved, total);
+        if !failed.is_empty() {
+            println!("Failed:");
+            for url in &failed {
+                println!("  {}", url);
+            }
+        }
+        failed
+    });
+
+    if !failed.is_empty() {
+        anyhow::bail!("{} of {} videos failed to fetch", failed.len(), total);
+    }
+    Ok(())
+}
+
+/// Write a fetched video, its transcript and raw payloads. Returns the number of chapters found.
+fn store_fetched(
+    db: &dyn Storage,
+    video: &engine::Video,
+    transcript: Option<&engine::Transcript>,
+    archives: &[engine::Archive],
+    no_queue: bool,
+) -> Result<usize> {
+    db.insert_video(video)?;
+    db.set_video_availability(&video.id, engine::Availability::Available, None)?;
+    let chapters = save_description_chapters(db, video)?;
+    for archive in archives {
+        db.save_archive(archive)?;
+    }
+
+    if let Some(t) = transcript {
+        db.insert_transcript(t)?;
+
+        // Add to AI processing queue unless --no-queue is set
+        if !no_queue {
+            db.add_to_queue(&video.id, 0)?;
+        }
+    }
+    schedule_embeddings(db, &video.id, transcript)?;
+    Ok(chapters)
+}
+
+fn save_description_chapters(db: &dyn Storage, video: &engine::Video) -> Result<usize> {
+    let description = video.description.as_deref().unwrap_or("");
+    let chapters = engine::transcript::parser::parse_chapters(description, &video.id);
+    db.save_chapters(&video.id, &chapters)?;
+    Ok(chapters.len())
+}
+
+const TRANSCRIPT_EXTENSIONS: &[&str] = &["srt", "vtt", "txt"];
+
+// Parses via the archive so imports can be re-parsed later like fetched captions
+fn load_transcript_file(path: &std::path::Path, video_id: &str) -> Result<(engine::Transcript, engine::Archive)> {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+    let archive = engine::Archive {
+        id: 0,
+        video_id: video_id.to_string(),
+        kind: "captions".to_string(),
+        format: if ext == "srt" || ext == "vtt" { ext } else { "txt".to_string() },
+        language: None,
+        content: std::fs::read_to_string(path)?,
+        fetched_at: chrono::Utc::now(),
+    };
+    Ok((engine::transcript::parser::parse_archive(&archive)?, archive))
+}
+
+// Stable short ID for local imports (FNV-1a over the canonical path)
+fn local_video_id(seed: &str) -> String {
+    format!("local-{:012x}", fnv_hash(seed) & 0xffff_ffff_ffff)
+}
+
+// FNV-1a; stable across runs and platforms, unlike DefaultHasher
+fn fnv_hash(s: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in s.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+#[allow(clippy::too_many_arguments)]
+fn cmd_import_media(
+    db: &dyn Storage,
+    path: &std::path::Path,
+    title: &str,
+    transcript_path: Option<&std::path::Path>,
+    channel: Option<&str>,
+    date: Option<&str>,
+    description: Option<&str>,
+    no_queue: bool,
+    embedding: &EmbeddingOptions,
+) -> Result<()> {
+    if !path.exists() {
+        println!("File not found: {}", path.display());
+        return Ok(());
+    }
+
+    let upload_date = match date {
+        Some(d) => match chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d") {
+            Ok(date) => Some(date),
+            Err(_) => {
+                println!("Invalid date: {} (expected YYYY-MM-DD)", d);
+                return Ok(());
+            }
+        },
+        None => None,
+    };
+
+    let canonical = std::fs::canonicalize(path)?;
+    let video_id = local_video_id(&canonical.to_string_lossy());
+
+    // A transcript passed as the main path is a standalone transcript
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+    let transcript_path = transcript_path.or_else(|| {
+        TRANSCRIPT_EXTENSIONS.contains(&ext.as_str()).then_some(path)
+    });
+
+    let (transcript, archive) = match transcript_path {
+        Some(p) => {
+            let (t, a) = load_transcript_file(p, &video_id)?;
+            (Some(t), Some(a))
+        }
+        None => (None, None),
+    };
+
+    let video = engine::Video {
+        id: video_id.clone(),
+        url: format!("file://{}", canonical.display()),
+        title: title.to_string(),
+        channel: channel.map(|c| c.to_string()),
+        upload_date,
+        description: description.map(|d| d.to_string()),
+        added_at: chrono::Utc::now(),
+        // No media probing; a timed transcript's end is close enough
+        duration: transcript.as_ref().and_then(|t| t.segments.last()).map(|s| s.start_time + s.duration),
+        view_count: None,
+        thumbnail_url: None,
+    };
 
     db.insert_video(&video)?;
+    println!("Title: {}", video.title);
+    let chapters = save_description_chapters(db, &video)?;
+    if chapters > 0 {
+        println!("Chapters: {}", chapters);
+    }
 
     if let Some(ref t) = transcript {
         db.insert_transcript(t)?;
+        if let Some(ref a) = archive {
+            db.save_archive(a)?;
+        }
         println!("Transcript: {} segments, {} chars", t.segments.len(), t.full_text.len());
 
-        // Add to AI processing queue unless --no-queue is set
         if !no_queue {
             db.add_to_queue(&video.id, 0)?;
             println!("Added to AI processing queue");
         }
     } else {
-        println!("Transcript: not available");
+        println!("Transcript: none attached");
     }
+    schedule_embeddings(db, &video.id, transcript.as_ref())?;
 
     println!("Saved: {}", video.id);
-    Ok(())
+    drain_embedding_queue(db, embedding)
 }
 
-fn cmd_list(db: &Database) -> Result<()> {
-    let videos = db.list_videos()?;
+fn cmd_list(db: &dyn Storage, lang: Option<&str>) -> Result<()> {
+    let videos = match lang {
+        Some(l) => db.list_videos_by_language(l)?,
+        None => db.list_videos()?,
+    };
 
     if videos.is_empty() {
         println!("No videos stored yet.");
         return Ok(());
     }
 
-    println!("{:<12} {:<50} {}", "ID", "TITLE", "CHANNEL");
-    println!("{}", "-".repeat(80));
+    println!("{:<12} {:<50} {:>8} {}", "ID", "TITLE", "LENGTH", "CHANNEL");
+    println!("{}", "-".repeat(89));
 
     for video in videos {
         let title = if video.title.len() > 48 {
@@ -1205,27 +2965,482 @@ fn cmd_list(db: &Database) -> Result<()> {
         } else {
             video.title.clone()
         };
+        let length = video.duration.map(format_duration).unwrap_or_else(|| "-".to_string());
         let channel = video.channel.unwrap_or_default();
-        println!("{:<12} {:<50} {}", video.id, title, channel);
+        println!("{:<12} {:<50} {:>8} {}", video.id, title, length, channel);
     }
 
     Ok(())
 }
 
-fn cmd_show(db: &Database, id: &str, full: bool) -> Result<()> {
-    let video = db.get_video(id)?;
-
-    match video {
-        Some(v) => {
-            println!("Title: {}", v.title);
-            println!("ID: {}", v.id);
-            println!("URL: {}", v.url);
-            if let Some(ref channel) = v.channel {
-                println!("Channel: {}", channel);
-            }
-            if let Some(date) = v.upload_date {
-                println!("Upload Date: {}", date);
-            }
+fn cmd_check_availability(db: &dyn Storage, video_id: Option<&str>, report_only: bool, options: &FetchOptions) -> Result<()> {
+    use engine::Availability;
+
+    if !report_only {
+        let videos = match video_id {
+            Some(id) => match db.get_video(id)? {
+                Some(v) => vec![v],
+                None => {
+                    println!("Video not found: {}", id);
+                    return Ok(());
+                }
+            },
+            None => db.list_videos()?,
+        };
+
+        let fetcher = options.fetcher();
+        println!("Checking {} videos...\n", videos.len());
+
+        let mut counts = [0usize; 4];
+        for video in &videos {
+            let (availability, detail) = match fetcher.check_availability(&video.url) {
+                Ok(result) => result,
+                Err(e) => (Availability::Unknown, Some(e.to_string())),
+            };
+            db.set_video_availability(&video.id, availability, detail.as_deref())?;
+            counts[availability as usize] += 1;
+            if availability != Availability::Available {
+                println!("  {:<14} {:<10} {}", truncate(&video.id, 14), availability.as_str(), truncate(&video.title, 50));
+            }
+        }
+
+        println!(
+            "\n{} available, {} private, {} removed, {} unknown",
+            counts[Availability::Available as usize],
+            counts[Availability::Private as usize],
+            counts[Availability::Removed as usize],
+            counts[Availability::Unknown as usize],
+        );
+    }
+
+    let dead = db.list_unavailable_videos()?;
+    if dead.is_empty() {
+        println!("\nNo dead links.");
+        return Ok(());
+    }
+
+    println!("\nDead links:\n");
+    println!("{:<14} {:<9} {:<11} {:<12} TITLE", "ID", "STATUS", "TRANSCRIPT", "CHECKED");
+    println!("{}", "-".repeat(90));
+    let mut only_copies = 0;
+    for entry in &dead {
+        let has_transcript = db.get_transcript(&entry.video.id)?.is_some();
+        if has_transcript {
+            only_copies += 1;
+        }
+        println!(
+            "{:<14} {:<9} {:<11} {:<12} {}",
+            truncate(&entry.video.id, 14),
+            entry.availability.as_str(),
+            if has_transcript { "only copy" } else { "-" },
+            entry.checked_at.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default(),
+            truncate(&entry.video.title, 40),
+        );
+    }
+    println!("\n{} dead links; {} transcripts exist only in this database.", dead.len(), only_copies);
+    Ok(())
+}
+
+fn cmd_subscribe(db: &dyn Storage, url: &str, name: Option<&str>) -> Result<()> {
+    let sub = db.add_subscription(url, name)?;
+    println!("Subscribed #{}: {}", sub.id, sub.name.as_deref().unwrap_or(&sub.url));
+    println!("Run 'watch' to fetch new uploads.");
+    Ok(())
+}
+
+fn cmd_subscriptions(db: &dyn Storage) -> Result<()> {
+    let subs = db.list_subscriptions()?;
+    if subs.is_empty() {
+        println!("No subscriptions. Use 'subscribe <channel-url>' to add one.");
+        return Ok(());
+    }
+
+    println!("{:<5} {:<25} {:<17} URL", "ID", "NAME", "LAST CHECKED");
+    println!("{}", "-".repeat(90));
+    for sub in subs {
+        println!(
+            "{:<5} {:<25} {:<17} {}",
+            sub.id,
+            truncate(sub.name.as_deref().unwrap_or("-"), 23),
+            sub.last_checked.map(|d| d.format("%Y-%m-%d %H:%M").to_string()).unwrap_or_else(|| "never".to_string()),
+            sub.url,
+        );
+    }
+    Ok(())
+}
+
+fn cmd_unsubscribe(db: &dyn Storage, id: i64) -> Result<()> {
+    if db.delete_subscription(id)? {
+        println!("Removed subscription #{}", id);
+    } else {
+        println!("Subscription not found: {}", id);
+    }
+    Ok(())
+}
+
+fn cmd_migrate(location: &DatabaseLocation, options: &ConnectionOptions, status: bool) -> Result<()> {
+    let db = Database::open_unmigrated(location, options)?;
+
+    if status {
+        let migrations = db.migration_status()?;
+        println!("{:<8} {:<35} APPLIED", "VERSION", "NAME");
+        println!("{}", "-".repeat(70));
+        for m in &migrations {
+            let applied = m.applied_at
+                .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_else(|| "pending".to_string());
+            println!("{:<8} {:<35} {}", m.version, m.name, applied);
+        }
+        let pending = migrations.iter().filter(|m| m.applied_at.is_none()).count();
+        println!("\n{} migration(s), {} pending", migrations.len(), pending);
+        return Ok(());
+    }
+
+    let applied = db.migrate()?;
+    if applied.is_empty() {
+        println!("Schema is up to date");
+    }
+    for m in &applied {
+        println!("Applied migration {}: {}", m.version, m.name);
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn cmd_watch(
+    db: &dyn Storage,
+    inbox: Option<&std::path::Path>,
+    interval: u64,
+    per_channel: usize,
+    jobs: usize,
+    once: bool,
+    options: &FetchOptions,
+    embedding: &EmbeddingOptions,
+) -> Result<()> {
+    if let Some(dir) = inbox {
+        std::fs::create_dir_all(dir.join("processed"))?;
+        std::fs::create_dir_all(dir.join("failed"))?;
+    }
+    println!("Watching {} subscriptions{} every {}s",
+        db.list_subscriptions()?.len(),
+        inbox.map(|d| format!(" and {}", d.display())).unwrap_or_default(),
+        interval);
+
+    loop {
+        println!("\n[{}] Polling...", chrono::Local::now().format("%Y-%m-%d %H:%M:%S"));
+        // One bad poll (network down, unreadable file) shouldn't stop the watcher
+        if let Err(e) = watch_poll(db, inbox, per_channel, jobs, options) {
+            println!("Poll failed: {}", e);
+        }
+        if let Err(e) = drain_embedding_queue(db, embedding) {
+            println!("Embedding failed: {}", e);
+        }
+        if once {
+            return Ok(());
+        }
+        std::thread::sleep(Duration::from_secs(interval.max(1)));
+    }
+}
+
+fn watch_poll(
+    db: &dyn Storage,
+    inbox: Option<&std::path::Path>,
+    per_channel: usize,
+    jobs: usize,
+    options: &FetchOptions,
+) -> Result<()> {
+    let fetcher = options.fetcher();
+    let mut urls: Vec<String> = Vec::new();
+
+    for sub in db.list_subscriptions()? {
+        let label = sub.name.clone().unwrap_or_else(|| sub.url.clone());
+        match fetcher.list_channel_videos(&sub.url, per_channel) {
+            Ok(ids) => {
+                let mut new = 0;
+                for id in ids {
+                    if db.get_video(&id)?.is_none() {
+                        urls.push(format!("https://www.youtube.com/watch?v={}", id));
+                        new += 1;
+                    }
+                }
+                db.mark_subscription_checked(sub.id)?;
+                println!("  {}: {} new", label, new);
+            }
+            Err(e) => println!("  {}: error - {}", label, e.to_string().lines().next().unwrap_or("")),
+        }
+    }
+
+    let mut inbox_files = Vec::new();
+    if let Some(dir) = inbox {
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let hidden = path.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with('.'));
+            if !path.is_file() || hidden {
+                continue;
+            }
+            // An unreadable file (not UTF-8, permissions) is set aside so the rest still go through
+            let content = match std::fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(e) => {
+                    println!("  {}: error - {}", path.display(), e);
+                    if let Some(name) = path.file_name() {
+                        if let Err(e) = std::fs::rename(&path, dir.join("failed").join(name)) {
+                            println!("  {}: could not move to failed - {}", path.display(), e);
+                        }
+                    }
+                    continue;
+                }
+            };
+            let before = urls.len();
+            urls.extend(
+                content
+                    .lines()
+                    .map(|l| l.trim())
+                    .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                    .map(|l| l.to_string()),
+            );
+            println!("  {}: {} URLs", path.display(), urls.len() - before);
+            inbox_files.push(path);
+        }
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    urls.retain(|u| seen.insert(u.clone()));
+
+    let fetched = if urls.is_empty() {
+        println!("Nothing new.");
+        Ok(())
+    } else {
+        cmd_fetch_batch(db, &urls, jobs, false, None, options)
+    };
+
+    // Move inbox files aside only after their URLs were attempted; failed URLs are listed above
+    for path in inbox_files {
+        if let (Some(dir), Some(name)) = (path.parent(), path.file_name()) {
+            std::fs::rename(&path, dir.join("processed").join(name))?;
+        }
+    }
+    fetched
+}
+
+fn cmd_archives(db: &dyn Storage, video_id: &str) -> Result<()> {
+    let archives = db.get_archives(video_id)?;
+    if archives.is_empty() {
+        println!("No archived payloads for: {}", video_id);
+        return Ok(());
+    }
+
+    println!("{:<6} {:<10} {:<7} {:<6} {:>10} FETCHED", "ID", "KIND", "FORMAT", "LANG", "BYTES");
+    println!("{}", "-".repeat(62));
+    for a in archives {
+        println!(
+            "{:<6} {:<10} {:<7} {:<6} {:>10} {}",
+            a.id,
+            a.kind,
+            a.format,
+            a.language.as_deref().unwrap_or("-"),
+            a.content.len(),
+            a.fetched_at.format("%Y-%m-%d %H:%M"),
+        );
+    }
+    Ok(())
+}
+
+/// Best-effort MIME type from a file extension.
+fn guess_mime_type(path: &std::path::Path) -> &'static str {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+    match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "tif" | "tiff" => "image/tiff",
+        "pdf" => "application/pdf",
+        "txt" | "md" => "text/plain",
+        "csv" => "text/csv",
+        "json" | "geojson" => "application/json",
+        "kml" => "application/vnd.google-earth.kml+xml",
+        "mp3" => "audio/mpeg",
+        "mp4" => "video/mp4",
+        _ => "application/octet-stream",
+    }
+}
+
+fn cmd_attach(db: &dyn Storage, id: &str, file: &std::path::Path, caption: Option<&str>, claim: bool, link: bool) -> Result<()> {
+    let (video_id, claim_id) = if claim {
+        let claim_id: i64 = id.parse().map_err(|_| anyhow::anyhow!("Invalid claim ID: {}", id))?;
+        if db.get_claim(claim_id)?.is_none() {
+            println!("Claim not found: {}", id);
+            return Ok(());
+        }
+        (None, Some(claim_id))
+    } else {
+        if db.get_video(id)?.is_none() {
+            println!("Video not found: {}", id);
+            return Ok(());
+        }
+        (Some(id.to_string()), None)
+    };
+
+    let metadata = std::fs::metadata(file).map_err(|e| anyhow::anyhow!("{}: {}", file.display(), e))?;
+    if !metadata.is_file() {
+        anyhow::bail!("Not a file: {}", file.display());
+    }
+    let data = if link { None } else { Some(std::fs::read(file)?) };
+    let path = if link { Some(std::fs::canonicalize(file)?.to_string_lossy().into_owned()) } else { None };
+
+    let attachment = engine::Attachment {
+        id: 0,
+        video_id,
+        claim_id,
+        file_name: file.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default(),
+        mime_type: guess_mime_type(file).to_string(),
+        caption: caption.map(String::from),
+        size: metadata.len() as i64,
+        path,
+        created_at: chrono::Utc::now(),
+    };
+    let attachment_id = db.add_attachment(&attachment, data.as_deref())?;
+
+    println!(
+        "Attached #{}: {} ({}, {}){}",
+        attachment_id,
+        attachment.file_name,
+        attachment.mime_type,
+        format_size(attachment.size),
+        if link { " - linked, not copied" } else { "" },
+    );
+    Ok(())
+}
+
+fn cmd_detach(db: &dyn Storage, id: i64) -> Result<()> {
+    if db.delete_attachment(id)? {
+        println!("Removed attachment #{}", id);
+    } else {
+        println!("Attachment not found: {}", id);
+    }
+    Ok(())
+}
+
+fn print_attachments(attachments: &[engine::Attachment]) {
+    if attachments.is_empty() {
+        return;
+    }
+    println!("\nAttachments:");
+    for a in attachments {
+        let caption = a.caption.as_deref().map(|c| format!(" - {}", c)).unwrap_or_default();
+        println!("  #{} {} ({}, {}){}", a.id, a.file_name, a.mime_type, format_size(a.size), caption);
+        if let Some(ref path) = a.path {
+            println!("      {}", path);
+        }
+    }
+}
+
+fn cmd_reparse(db: &dyn Storage, id: &str) -> Result<()> {
+    let ids: Vec<String> = if id == "all" {
+        db.list_videos()?.into_iter().map(|v| v.id).collect()
+    } else {
+        vec![id.to_string()]
+    };
+
+    let mut reparsed = 0;
+    for video_id in &ids {
+        let archive = match db.get_latest_archive(video_id, "captions")? {
+            Some(a) => a,
+            None => {
+                if ids.len() == 1 {
+                    println!("No archived captions for: {}", video_id);
+                }
+                continue;
+            }
+        };
+
+        match engine::transcript::parser::parse_archive(&archive) {
+            Ok(transcript) => {
+                db.update_transcript_segments(video_id, &transcript.segments)?;
+                println!("  {}: {} segments", video_id, transcript.segments.len());
+                reparsed += 1;
+            }
+            Err(e) => println!("  {}: error - {}", video_id, e),
+        }
+    }
+
+    println!("\nReparsed {} transcript(s).", reparsed);
+    Ok(())
+}
+
+fn cmd_chapters(db: &dyn Storage, id: &str) -> Result<()> {
+    if id == "all" {
+        let videos = db.list_videos()?;
+        let mut with_chapters = 0;
+        for video in &videos {
+            let description = video.description.as_deref().unwrap_or("");
+            let chapters = engine::transcript::parser::parse_chapters(description, &video.id);
+            db.save_chapters(&video.id, &chapters)?;
+            if !chapters.is_empty() {
+                println!("  {}: {} chapters", video.id, chapters.len());
+                with_chapters += 1;
+            }
+        }
+        println!("\nFound chapters in {} of {} videos.", with_chapters, videos.len());
+        return Ok(());
+    }
+
+    let video = match db.get_video(id)? {
+        Some(v) => v,
+        None => {
+            println!("Video not found: {}", id);
+            return Ok(());
+        }
+    };
+
+    let description = video.description.as_deref().unwrap_or("");
+    let chapters = engine::transcript::parser::parse_chapters(description, &video.id);
+    db.save_chapters(&video.id, &chapters)?;
+
+    if chapters.is_empty() {
+        println!("No chapter markers in the description of: {}", video.title);
+        return Ok(());
+    }
+
+    println!("Chapters for: {}\n", video.title);
+    for ch in db.get_chapters(id)? {
+        let mins = (ch.start_time / 60.0) as u32;
+        let secs = (ch.start_time % 60.0) as u32;
+        println!("[{:02}:{:02}] {}", mins, secs, ch.title);
+    }
+    Ok(())
+}
+
+fn cmd_show(db: &dyn Storage, id: &str, full: bool) -> Result<()> {
+    let video = db.get_video(id)?;
+
+    match video {
+        Some(v) => {
+            println!("Title: {}", v.title);
+            println!("ID: {}", v.id);
+            println!("URL: {}", v.url);
+            if let Some(ref channel) = v.channel {
+                println!("Channel: {}", channel);
+            }
+            if let Some(date) = v.upload_date {
+                println!("Upload Date: {}", date);
+            }
+            if let Some(duration) = v.duration {
+                println!("Duration: {}", format_duration(duration));
+            }
+            if let Some(views) = v.view_count {
+                println!("Views: {}", views);
+            }
+            if let Some(ref thumb) = v.thumbnail_url {
+                println!("Thumbnail: {}", thumb);
+            }
+            if let Some(a) = db.get_video_availability(id)? {
+                if let Some(checked) = a.checked_at {
+                    println!("Availability: {} (checked {})", a.availability.as_str(), checked.format("%Y-%m-%d"));
+                }
+            }
 
             // Show eras, regions, topics, collections
             let eras = db.get_video_eras(id)?;
@@ -1259,13 +3474,28 @@ fn cmd_show(db: &Database, id: &str, full: bool) -> Result<()> {
                 println!("\nDescription:\n{}", desc_preview);
             }
 
+            print_attachments(&db.get_video_attachments(id)?);
+
+            let chapters = db.get_chapters(id)?;
+            if !chapters.is_empty() {
+                println!("\nChapters:");
+                for ch in &chapters {
+                    let mins = (ch.start_time / 60.0) as u32;
+                    let secs = (ch.start_time % 60.0) as u32;
+                    println!("  [{:02}:{:02}] {}", mins, secs, ch.title);
+                }
+            }
+
             if let Some(transcript) = db.get_transcript(id)? {
                 println!("\n--- Transcript ({} segments) ---\n", transcript.segments.len());
                 if full {
                     for seg in &transcript.segments {
                         let mins = (seg.start_time / 60.0) as u32;
                         let secs = (seg.start_time % 60.0) as u32;
-                        println!("[{:02}:{:02}] {}", mins, secs, seg.text);
+                        match &seg.speaker {
+                            Some(name) => println!("[{:02}:{:02}] {}: {}", mins, secs, name, seg.text),
+                            None => println!("[{:02}:{:02}] {}", mins, secs, seg.text),
+                        }
                     }
                 } else {
                     let preview = if transcript.full_text.len() > 500 {
@@ -1288,30 +3518,211 @@ fn cmd_show(db: &Database, id: &str, full: bool) -> Result<()> {
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn cmd_search(
-    db: &Database,
+    db: &dyn Storage,
     query: &str,
     era: Option<&str>,
     region: Option<&str>,
     topic: Option<&str>,
+    speaker: Option<&str>,
+    collection: Option<&str>,
+    scopes: &[String],
 ) -> Result<()> {
+    // "!N" re-runs entry N from `history searches`; filters given on the command line take precedence
+    let recalled = match query.strip_prefix('!').and_then(|n| n.parse::<i64>().ok()) {
+        Some(n) => match db.get_search_history(n)? {
+            Some(entry) => Some(entry),
+            None => {
+                println!("No search #{} in history.", n);
+                return Ok(());
+            }
+        },
+        None => None,
+    };
+    let (query, era, region, topic, speaker, collection, scopes) = match &recalled {
+        Some(entry) => {
+            println!("Re-running search #{}: {}\n", entry.id, entry.query);
+            (
+                entry.query.as_str(),
+                era.or(entry.era.as_deref()),
+                region.or(entry.region.as_deref()),
+                topic.or(entry.topic.as_deref()),
+                speaker.or(entry.speaker.as_deref()),
+                collection.or(entry.collection.as_deref()),
+                if scopes.is_empty() { entry.scopes.as_slice() } else { scopes },
+            )
+        }
+        None => (query, era, region, topic, speaker, collection, scopes),
+    };
+
+    // "videos" covers titles, descriptions and transcripts; the rest live in the text index
+    let show_videos = scopes.is_empty() || scopes.iter().any(|s| s == "videos");
+    let kinds: Vec<&str> = scopes
+        .iter()
+        .filter_map(|s| match s.as_str() {
+            "notes" => Some("note"),
+            "quotes" => Some("quote"),
+            "terms" => Some("term"),
+            "summaries" => Some("summary"),
+            _ => None,
+        })
+        .collect();
+
+    if let Some(name) = collection {
+        if db.get_collection_by_name(name)?.is_none() {
+            println!("Collection '{}' not found.", name);
+            return Ok(());
+        }
+    }
+
+    let video_count = if show_videos {
+        print_video_search(db, query, era, region, topic, speaker, collection)?
+    } else {
+        0
+    };
+
+    let mut text_hits = if scopes.is_empty() || !kinds.is_empty() {
+        db.search_text(query, &kinds, collection)?
+    } else {
+        Vec::new()
+    };
+    if era.is_some() || region.is_some() || topic.is_some() {
+        let tagged = |video_id: &str| -> Result<bool> {
+            let in_era = match era {
+                Some(e) => db.get_video_eras(video_id)?.iter().any(|x| x.name.eq_ignore_ascii_case(e)),
+                None => true,
+            };
+            let in_region = match region {
+                Some(r) => db.get_video_regions(video_id)?.iter().any(|x| x.name.eq_ignore_ascii_case(r)),
+                None => true,
+            };
+            let in_topic = match topic {
+                Some(t) => db.get_video_topics(video_id)?.iter().any(|x| x.name.eq_ignore_ascii_case(t)),
+                None => true,
+            };
+            Ok(in_era && in_region && in_topic)
+        };
+        let mut kept = Vec::new();
+        for hit in text_hits {
+            if let Some(ref video_id) = hit.video_id {
+                if tagged(video_id)? {
+                    kept.push(hit);
+                }
+            }
+        }
+        text_hits = kept;
+    }
+    if let Some(want) = speaker {
+        text_hits.retain(|h| h.kind == "quote" && h.title.to_lowercase().contains(&want.to_lowercase()));
+    }
+
+    if !text_hits.is_empty() {
+        println!("Found {} matches in notes, quotes, terms and summaries\n", text_hits.len());
+        for hit in &text_hits {
+            let label = match (hit.kind.as_str(), hit.title.is_empty()) {
+                ("note", _) | (_, true) => format!("[{} #{}]", hit.kind, hit.id),
+                _ => format!("[{} #{}] {}", hit.kind, hit.id, hit.title),
+            };
+            match hit.video_id {
+                Some(ref video_id) => println!("{} ({})", label, video_id),
+                None => println!("{}", label),
+            }
+            println!("  {}", hit.snippet.replace('\n', " "));
+            println!();
+        }
+    }
+
+    if !db.is_read_only()? {
+        db.record_search(&engine::SearchHistoryEntry {
+            id: 0,
+            query: query.to_string(),
+            era: era.map(|s| s.to_string()),
+            region: region.map(|s| s.to_string()),
+            topic: topic.map(|s| s.to_string()),
+            speaker: speaker.map(|s| s.to_string()),
+            collection: collection.map(|s| s.to_string()),
+            scopes: scopes.to_vec(),
+            result_count: (video_count + text_hits.len()) as i64,
+            created_at: chrono::Utc::now(),
+        })?;
+    }
+
+    if video_count == 0 && text_hits.is_empty() {
+        let mut filter_desc = vec![format!("query '{}'", query)];
+        if let Some(e) = era { filter_desc.push(format!("era '{}'", e)); }
+        if let Some(r) = region { filter_desc.push(format!("region '{}'", r)); }
+        if let Some(t) = topic { filter_desc.push(format!("topic '{}'", t)); }
+        if let Some(s) = speaker { filter_desc.push(format!("speaker '{}'", s)); }
+        if let Some(c) = collection { filter_desc.push(format!("collection '{}'", c)); }
+        if !scopes.is_empty() { filter_desc.push(format!("in {}", scopes.join(","))); }
+        println!("No results found for: {}", filter_desc.join(", "));
+        if let Some(suggestion) = db.suggest_spelling(query)? {
+            println!("Did you mean: {}", suggestion);
+        }
+    }
+
+    Ok(())
+}
+
+/// One line per facet, e.g. "Eras: Bronze Age (3), Iron Age (1)", to suggest narrower filters.
+fn print_facets(facets: &engine::MetadataFacets) {
+    let mut printed = false;
+    for (label, counts) in [("Eras", &facets.eras), ("Regions", &facets.regions), ("Topics", &facets.topics), ("Channels", &facets.channels)] {
+        if counts.is_empty() {
+            continue;
+        }
+        let shown: Vec<String> = counts.iter().take(8).map(|(name, n)| format!("{} ({})", name, n)).collect();
+        println!("{}: {}", label, shown.join(", "));
+        printed = true;
+    }
+    if printed {
+        println!();
+    }
+}
+
+/// Print transcript, title and description matches. Returns how many videos matched.
+fn print_video_search(
+    db: &dyn Storage,
+    query: &str,
+    era: Option<&str>,
+    region: Option<&str>,
+    topic: Option<&str>,
+    speaker: Option<&str>,
+    collection: Option<&str>,
+) -> Result<usize> {
+    // Keep only segments attributed to the requested speaker, dropping videos left with none
+    let by_speaker = |m: &engine::SegmentMatch| match (speaker, &m.speaker) {
+        (None, _) => true,
+        (Some(want), Some(got)) => got.to_lowercase().contains(&want.to_lowercase()),
+        (Some(_), None) => false,
+    };
+
+    let count;
+
     // Use advanced search if any filters are provided
-    let has_filters = era.is_some() || region.is_some() || topic.is_some();
+    let has_filters = era.is_some() || region.is_some() || topic.is_some() || collection.is_some();
 
     if has_filters {
-        let results = db.advanced_search(Some(query), era, region, topic)?;
+        let response = db.advanced_search(Some(query), era, region, topic, collection, None, None, None)?;
+        let mut results = response.results;
+        if speaker.is_some() {
+            for r in &mut results {
+                r.matches.retain(by_speaker);
+            }
+            results.retain(|r| !r.matches.is_empty());
+        }
 
         if results.is_empty() {
-            let mut filter_desc = vec![format!("query '{}'", query)];
-            if let Some(e) = era { filter_desc.push(format!("era '{}'", e)); }
-            if let Some(r) = region { filter_desc.push(format!("region '{}'", r)); }
-            if let Some(t) = topic { filter_desc.push(format!("topic '{}'", t)); }
-            println!("No results found for: {}", filter_desc.join(", "));
-            return Ok(());
+            return Ok(0);
         }
+        count = results.len();
 
         let total_matches: usize = results.iter().map(|r| r.matches.len()).sum();
         println!("Found {} matches across {} videos\n", total_matches, results.len());
+        if speaker.is_none() {
+            print_facets(&response.facets);
+        }
 
         for result in results {
             println!("--- {} ---", result.video.id);
@@ -1336,19 +3747,28 @@ fn cmd_search(
                 let mins = (m.start_time / 60.0) as u32;
                 let secs = (m.start_time % 60.0) as u32;
                 let url_with_time = format!("{}&t={}s", result.video.url, m.start_time as u32);
-                println!("  [{:02}:{:02}] {}", mins, secs, m.text);
+                match &m.speaker {
+                    Some(name) => println!("  [{:02}:{:02}] {}: {}", mins, secs, name, m.text),
+                    None => println!("  [{:02}:{:02}] {}", mins, secs, m.text),
+                }
                 println!("          {}", url_with_time);
                 println!();
             }
         }
     } else {
         // Use basic search for simple queries
-        let results = db.search_with_timestamps(query)?;
+        let mut results = db.search_with_timestamps(query)?;
+        if speaker.is_some() {
+            for r in &mut results {
+                r.matches.retain(by_speaker);
+            }
+            results.retain(|r| !r.matches.is_empty());
+        }
 
         if results.is_empty() {
-            println!("No results found for: {}", query);
-            return Ok(());
+            return Ok(0);
         }
+        count = results.len();
 
         let total_matches: usize = results.iter().map(|r| r.matches.len()).sum();
         println!("Found {} matches across {} videos for: {}\n", total_matches, results.len(), query);
@@ -1366,17 +3786,20 @@ fn cmd_search(
                 let mins = (m.start_time / 60.0) as u32;
                 let secs = (m.start_time % 60.0) as u32;
                 let url_with_time = format!("{}&t={}s", result.video.url, m.start_time as u32);
-                println!("  [{:02}:{:02}] {}", mins, secs, m.text);
+                match &m.speaker {
+                    Some(name) => println!("  [{:02}:{:02}] {}: {}", mins, secs, name, m.text),
+                    None => println!("  [{:02}:{:02}] {}", mins, secs, m.text),
+                }
                 println!("          {}", url_with_time);
                 println!();
             }
         }
     }
 
-    Ok(())
+    Ok(count)
 }
 
-fn cmd_tag(db: &Database, video_id: &str, era: Option<&str>, region: Option<&str>) -> Result<()> {
+fn cmd_tag(db: &dyn Storage, video_id: &str, era: Option<&str>, region: Option<&str>) -> Result<()> {
     // Verify video exists
     if db.get_video(video_id)?.is_none() {
         println!("Video not found: {}", video_id);
@@ -1413,7 +3836,45 @@ fn cmd_tag(db: &Database, video_id: &str, era: Option<&str>, region: Option<&str
     Ok(())
 }
 
-fn cmd_eras(db: &Database) -> Result<()> {
+fn cmd_untag(db: &dyn Storage, video_id: &str, era: Option<&str>, region: Option<&str>, topic: Option<&str>) -> Result<()> {
+    if db.get_video(video_id)?.is_none() {
+        println!("Video not found: {}", video_id);
+        return Ok(());
+    }
+
+    if era.is_none() && region.is_none() && topic.is_none() {
+        println!("Please specify --era, --region and/or --topic");
+        return Ok(());
+    }
+
+    if let Some(era_name) = era {
+        match db.get_era_by_name(era_name)? {
+            Some(era_obj) if db.untag_video_era(video_id, era_obj.id)? => println!("Removed era: {}", era_obj.name),
+            Some(era_obj) => println!("Video is not tagged with era: {}", era_obj.name),
+            None => println!("Era not found: {}", era_name),
+        }
+    }
+
+    if let Some(region_name) = region {
+        match db.get_region_by_name(region_name)? {
+            Some(region_obj) if db.untag_video_region(video_id, region_obj.id)? => println!("Removed region: {}", region_obj.name),
+            Some(region_obj) => println!("Video is not tagged with region: {}", region_obj.name),
+            None => println!("Region not found: {}", region_name),
+        }
+    }
+
+    if let Some(topic_name) = topic {
+        match db.get_topic_by_name(topic_name)? {
+            Some(topic_obj) if db.untag_video_topic(video_id, topic_obj.id)? => println!("Removed topic: {}", topic_obj.name),
+            Some(topic_obj) => println!("Video is not tagged with topic: {}", topic_obj.name),
+            None => println!("Topic not found: {}", topic_name),
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_eras(db: &dyn Storage) -> Result<()> {
     let eras = db.list_eras()?;
 
     if eras.is_empty() {
@@ -1423,13 +3884,52 @@ fn cmd_eras(db: &Database) -> Result<()> {
 
     println!("Available eras:\n");
     for era in eras {
-        println!("  {}", era.name);
+        match format_year_range(era.start_year, era.end_year) {
+            Some(range) => println!("  {:<25} {}", era.name, range),
+            None => println!("  {}", era.name),
+        }
+    }
+
+    Ok(())
+}
+
+/// Render an era's span, e.g. "3300 BCE – 1200 BCE" or "1800 CE –".
+fn format_year_range(start: Option<i32>, end: Option<i32>) -> Option<String> {
+    let year = |y: i32| if y < 0 { format!("{} BCE", -y) } else { format!("{} CE", y) };
+    match (start, end) {
+        (None, None) => None,
+        (Some(s), Some(e)) => Some(format!("{} – {}", year(s), year(e))),
+        (Some(s), None) => Some(format!("{} –", year(s))),
+        (None, Some(e)) => Some(format!("– {}", year(e))),
+    }
+}
+
+fn cmd_add_era(db: &dyn Storage, name: &str, start: Option<i32>, end: Option<i32>, order: Option<i32>) -> Result<()> {
+    if let (Some(s), Some(e)) = (start, end) {
+        if s > e {
+            println!("--start ({}) must not be after --end ({})", s, e);
+            return Ok(());
+        }
+    }
+    if db.get_era_by_name(name)?.is_some() {
+        println!("Era already exists: {}", name);
+        return Ok(());
+    }
+
+    let sort_order = match order {
+        Some(o) => o,
+        None => db.next_era_sort_order()?,
+    };
+    let era = db.create_era(name, sort_order, start, end)?;
+    match format_year_range(era.start_year, era.end_year) {
+        Some(range) => println!("Created era: {} ({})", era.name, range),
+        None => println!("Created era: {}", era.name),
     }
 
     Ok(())
 }
 
-fn cmd_regions(db: &Database) -> Result<()> {
+fn cmd_regions(db: &dyn Storage) -> Result<()> {
     let regions = db.list_regions()?;
 
     if regions.is_empty() {
@@ -1449,7 +3949,30 @@ fn cmd_regions(db: &Database) -> Result<()> {
     Ok(())
 }
 
-fn cmd_add_region(db: &Database, name: &str, parent: Option<&str>) -> Result<()> {
+fn cmd_region_tree(db: &dyn Storage) -> Result<()> {
+    let regions = db.region_video_counts()?;
+
+    if regions.is_empty() {
+        println!("No regions defined yet. Use 'add-region' or tag a video with --region to create one.");
+        return Ok(());
+    }
+
+    fn print_subtree(regions: &[engine::RegionCount], parent_id: Option<i64>, depth: usize) {
+        for region in regions.iter().filter(|r| r.parent_id == parent_id) {
+            let label = format!("{}{}", "  ".repeat(depth), region.name);
+            println!("{:<40} {:>6} {:>6}", label, region.video_count, region.subtree_video_count);
+            print_subtree(regions, Some(region.id), depth + 1);
+        }
+    }
+
+    println!("{:<40} {:>6}  TOTAL", "REGION", "DIRECT");
+    println!("{}", "-".repeat(54));
+    print_subtree(&regions, None, 0);
+
+    Ok(())
+}
+
+fn cmd_add_region(db: &dyn Storage, name: &str, parent: Option<&str>) -> Result<()> {
     let parent_id = if let Some(parent_name) = parent {
         match db.get_region_by_name(parent_name)? {
             Some(p) => Some(p.id),
@@ -1468,8 +3991,54 @@ fn cmd_add_region(db: &Database, name: &str, parent: Option<&str>) -> Result<()>
     Ok(())
 }
 
-fn cmd_browse(db: &Database, era: Option<&str>, region: Option<&str>) -> Result<()> {
-    let videos = db.browse_videos(era, region)?;
+/// Look up an era, region or topic id by name.
+fn find_tag_id(db: &dyn Storage, table: &str, name: &str) -> Result<Option<i64>> {
+    Ok(match table {
+        "eras" => db.get_era_by_name(name)?.map(|e| e.id),
+        "regions" => db.get_region_by_name(name)?.map(|r| r.id),
+        _ => db.get_topic_by_name(name)?.map(|t| t.id),
+    })
+}
+
+fn cmd_rename_tag(db: &dyn Storage, table: &str, old: &str, new: &str) -> Result<()> {
+    let kind = table.trim_end_matches('s');
+    let id = match find_tag_id(db, table, old)? {
+        Some(id) => id,
+        None => {
+            println!("No {} named '{}'", kind, old);
+            return Ok(());
+        }
+    };
+    if let Some(existing) = find_tag_id(db, table, new)? {
+        if existing != id {
+            println!("A {} named '{}' already exists; use merge-{} to combine them", kind, new, table);
+            return Ok(());
+        }
+    }
+
+    db.rename_tag(table, id, new)?;
+    println!("Renamed {} '{}' to '{}'", kind, old, new);
+    Ok(())
+}
+
+fn cmd_merge_tags(db: &dyn Storage, table: &str, source: &str, target: &str) -> Result<()> {
+    let kind = table.trim_end_matches('s');
+    let Some(source_id) = find_tag_id(db, table, source)? else {
+        println!("No {} named '{}'", kind, source);
+        return Ok(());
+    };
+    let Some(target_id) = find_tag_id(db, table, target)? else {
+        println!("No {} named '{}'", kind, target);
+        return Ok(());
+    };
+
+    db.merge_tags(table, source_id, target_id)?;
+    println!("Merged {} '{}' into '{}'", kind, source, target);
+    Ok(())
+}
+
+fn cmd_browse(db: &dyn Storage, era: Option<&str>, region: Option<&str>, include_subregions: bool) -> Result<()> {
+    let videos = db.browse_videos(era, region, include_subregions)?;
 
     if videos.is_empty() {
         let filter = match (era, region) {
@@ -1506,7 +4075,7 @@ fn cmd_browse(db: &Database, era: Option<&str>, region: Option<&str>) -> Result<
     Ok(())
 }
 
-fn cmd_topic(db: &Database, video_id: &str, add: Option<&str>) -> Result<()> {
+fn cmd_topic(db: &dyn Storage, video_id: &str, add: Option<&str>) -> Result<()> {
     if db.get_video(video_id)?.is_none() {
         println!("Video not found: {}", video_id);
         return Ok(());
@@ -1534,7 +4103,7 @@ fn cmd_topic(db: &Database, video_id: &str, add: Option<&str>) -> Result<()> {
     Ok(())
 }
 
-fn cmd_topics(db: &Database) -> Result<()> {
+fn cmd_topics(db: &dyn Storage) -> Result<()> {
     let topics = db.list_topics()?;
 
     if topics.is_empty() {
@@ -1550,7 +4119,7 @@ fn cmd_topics(db: &Database) -> Result<()> {
     Ok(())
 }
 
-fn cmd_by_topic(db: &Database, topic_name: &str) -> Result<()> {
+fn cmd_by_topic(db: &dyn Storage, topic_name: &str) -> Result<()> {
     let videos = db.browse_by_topic(topic_name)?;
 
     if videos.is_empty() {
@@ -1575,7 +4144,7 @@ fn cmd_by_topic(db: &Database, topic_name: &str) -> Result<()> {
     Ok(())
 }
 
-fn cmd_collect(db: &Database, video_id: &str, collection_name: &str) -> Result<()> {
+fn cmd_collect(db: &dyn Storage, video_id: &str, collection_name: &str) -> Result<()> {
     if db.get_video(video_id)?.is_none() {
         println!("Video not found: {}", video_id);
         return Ok(());
@@ -1595,7 +4164,25 @@ fn cmd_collect(db: &Database, video_id: &str, collection_name: &str) -> Result<(
     Ok(())
 }
 
-fn cmd_collections(db: &Database, name: Option<&str>) -> Result<()> {
+fn cmd_uncollect(db: &dyn Storage, video_id: &str, collection_name: &str) -> Result<()> {
+    let collection = match db.get_collection_by_name(collection_name)? {
+        Some(c) => c,
+        None => {
+            println!("Collection '{}' not found.", collection_name);
+            return Ok(());
+        }
+    };
+
+    if db.remove_video_from_collection(video_id, collection.id)? {
+        println!("Removed from collection: {}", collection.name);
+    } else {
+        println!("Video {} is not in collection: {}", video_id, collection.name);
+    }
+
+    Ok(())
+}
+
+fn cmd_collections(db: &dyn Storage, name: Option<&str>) -> Result<()> {
     match name {
         Some(collection_name) => {
             let videos = db.get_collection_videos(collection_name)?;
@@ -1641,13 +4228,13 @@ fn cmd_collections(db: &Database, name: Option<&str>) -> Result<()> {
     Ok(())
 }
 
-fn cmd_new_collection(db: &Database, name: &str, description: Option<&str>) -> Result<()> {
+fn cmd_new_collection(db: &dyn Storage, name: &str, description: Option<&str>) -> Result<()> {
     let collection = db.create_collection(name, description)?;
     println!("Created collection: {}", collection.name);
     Ok(())
 }
 
-fn cmd_note(db: &Database, video_id: &str, text: &str, timestamp: Option<f64>) -> Result<()> {
+fn cmd_note(db: &dyn Storage, video_id: &str, text: &str, timestamp: Option<f64>) -> Result<()> {
     if db.get_video(video_id)?.is_none() {
         println!("Video not found: {}", video_id);
         return Ok(());
@@ -1666,7 +4253,7 @@ fn cmd_note(db: &Database, video_id: &str, text: &str, timestamp: Option<f64>) -
     Ok(())
 }
 
-fn cmd_notes(db: &Database, video_id: &str) -> Result<()> {
+fn cmd_notes(db: &dyn Storage, video_id: &str) -> Result<()> {
     let video = match db.get_video(video_id)? {
         Some(v) => v,
         None => {
@@ -1702,7 +4289,7 @@ fn cmd_notes(db: &Database, video_id: &str) -> Result<()> {
 
 #[allow(clippy::too_many_arguments)]
 fn cmd_locate(
-    db: &Database,
+    db: &dyn Storage,
     video_id: &str,
     place: &str,
     lat: f64,
@@ -1750,7 +4337,7 @@ fn cmd_locate(
     Ok(())
 }
 
-fn cmd_locations(db: &Database) -> Result<()> {
+fn cmd_locations(db: &dyn Storage) -> Result<()> {
     let locations = db.list_locations()?;
 
     if locations.is_empty() {
@@ -1769,607 +4356,93 @@ fn cmd_locations(db: &Database) -> Result<()> {
     Ok(())
 }
 
-fn cmd_serve(db_path: PathBuf, port: u16) -> Result<()> {
-    use axum::{
-        extract::{Path, Query, State},
-        http::StatusCode,
-        response::Json,
-        routing::get,
-        Router,
-    };
-    use std::sync::Arc;
-    use tower_http::cors::CorsLayer;
-
-    #[derive(Clone)]
-    struct AppState {
-        db_path: PathBuf,
-    }
-
-    fn open_db(state: &AppState) -> Result<Database, StatusCode> {
-        Database::open(&state.db_path).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
-    }
-
-    #[derive(serde::Deserialize)]
-    struct MapQuery {
-        era: Option<String>,  // Comma-separated eras
-        topic: Option<String>,
-    }
-
-    fn parse_eras(era: &Option<String>) -> Vec<String> {
-        era.as_ref()
-            .map(|s| s.split(',').map(|e| e.trim().to_string()).filter(|e| !e.is_empty()).collect())
-            .unwrap_or_default()
-    }
-
-    #[derive(serde::Deserialize)]
-    struct ClaimsQuery {
-        video_id: Option<String>,
-        category: Option<String>,
-        limit: Option<usize>,
-    }
-
-    #[derive(serde::Deserialize)]
-    struct GraphQuery {
-        video_id: Option<String>,
-        moc_id: Option<i64>,
-        era: Option<String>,  // Comma-separated eras
-        topic: Option<String>,
-    }
-
-    #[derive(serde::Deserialize)]
-    struct SearchQuery {
-        q: String,                      // Search query
-        types: Option<String>,          // Comma-separated: "claim,video,scholar"
-        video_id: Option<String>,       // Filter to specific video
-        limit: Option<usize>,           // Max results (default 50)
-        fuzzy_threshold: Option<f64>,   // 0.0-1.0, default 0.6
-    }
-
-    // Graph node/edge structures for vis.js
-    #[derive(serde::Serialize)]
-    struct GraphNode {
-        id: i64,
-        label: String,
-        title: String,      // Hover text
-        group: String,      // Category for coloring
-        value: usize,       // Node size (connection count)
-        video_id: String,
-        timestamp: Option<f64>,
+fn cmd_history(db: &dyn Storage, entity: Option<&str>, id: Option<&str>, source: Option<&str>, limit: usize) -> Result<()> {
+    let entries = db.list_audit_log(entity, id, source, limit)?;
+    if entries.is_empty() {
+        println!("No recorded changes.");
+        return Ok(());
     }
 
-    #[derive(serde::Serialize)]
-    struct GraphEdge {
-        from: i64,
-        to: i64,
-        label: String,
-        arrows: String,
-        dashes: bool,       // Dashed for contradicts
-        color: EdgeColor,
+    println!("{:<19} {:<16} {:<6} {:<20} {:<14} SUMMARY", "WHEN", "SOURCE", "ACTION", "ENTITY", "ID");
+    println!("{}", "-".repeat(110));
+    for e in &entries {
+        println!(
+            "{:<19} {:<16} {:<6} {:<20} {:<14} {}",
+            e.created_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S"),
+            truncate(e.source.as_deref().unwrap_or("-"), 16),
+            e.action,
+            truncate(&e.entity_type, 20),
+            truncate(&e.entity_id, 14),
+            truncate(&e.summary, 60),
+        );
     }
+    Ok(())
+}
 
-    #[derive(serde::Serialize)]
-    struct EdgeColor {
-        color: String,
+fn cmd_search_history(db: &dyn Storage, limit: usize) -> Result<()> {
+    let entries = db.list_search_history(limit)?;
+    if entries.is_empty() {
+        println!("No searches recorded yet.");
+        return Ok(());
     }
 
-    #[derive(serde::Serialize)]
-    struct GraphData {
-        nodes: Vec<GraphNode>,
-        edges: Vec<GraphEdge>,
+    println!("{:>5}  {:<16} {:>7}  SEARCH", "#", "WHEN", "RESULTS");
+    println!("{}", "-".repeat(90));
+    for e in entries.iter().rev() {
+        let mut filters = vec![format!("\"{}\"", e.query)];
+        if let Some(ref v) = e.era { filters.push(format!("era:{}", v)); }
+        if let Some(ref v) = e.region { filters.push(format!("region:{}", v)); }
+        if let Some(ref v) = e.topic { filters.push(format!("topic:{}", v)); }
+        if let Some(ref v) = e.speaker { filters.push(format!("speaker:{}", v)); }
+        if let Some(ref v) = e.collection { filters.push(format!("collection:{}", v)); }
+        if !e.scopes.is_empty() { filters.push(format!("in:{}", e.scopes.join(","))); }
+        println!(
+            "{:>5}  {:<16} {:>7}  {}",
+            format!("!{}", e.id),
+            e.created_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+            e.result_count,
+            truncate(&filters.join(" + "), 80),
+        );
     }
+    println!("\nRe-run one with: engine search '!N'");
+    Ok(())
+}
 
-    #[derive(serde::Serialize)]
-    struct MocSummary {
-        id: i64,
-        title: String,
-        description: Option<String>,
-        claim_count: usize,
+fn cmd_doctor(db: &dyn Storage, fix: bool) -> Result<()> {
+    let issues = db.check_integrity()?;
+    if issues.is_empty() {
+        println!("No problems found.");
+        return Ok(());
     }
 
-    #[derive(serde::Serialize)]
-    struct QuestionSummary {
-        id: i64,
-        question: String,
-        status: String,
-        evidence_count: usize,
+    println!("{:<10} {:>6}  PROBLEM", "CHECK", "COUNT");
+    println!("{}", "-".repeat(70));
+    for issue in &issues {
+        println!("{:<10} {:>6}  {}", issue.check, issue.count, issue.description);
     }
 
-    #[derive(serde::Serialize)]
-    struct QueueSummary {
-        pending: usize,
-        in_progress: usize,
-        completed: usize,
-        failed: usize,
-        current: Option<String>,
+    let fixable = issues.iter().any(|i| i.fixable);
+    if issues.iter().any(|i| !i.fixable) {
+        println!("\nThe database file itself is damaged. Restore a backup, or try export-all into a fresh database.");
     }
-
-    #[derive(serde::Serialize)]
-    struct FullStats {
-        videos: i64,
-        claims: i64,
-        links: i64,
-        mocs: i64,
-        questions: i64,
-        active_questions: i64,
-        patterns: i64,
-        orphan_claims: usize,
-        stale_claims: usize,
-        framework: engine::FrameworkStats,
-        claims_by_category: Vec<CategoryCount>,
-        // Phase 12: Expanded knowledge entities
-        sources: i64,
-        scholars: i64,
-        terms: i64,
-        visuals: i64,
-        evidence: i64,
-        quotes: i64,
+    if !fixable {
+        return Ok(());
     }
-
-    #[derive(serde::Serialize)]
-    struct CategoryCount {
-        category: String,
-        count: i64,
+    if !fix {
+        println!("\nRun with --fix to repair.");
+        return Ok(());
     }
 
-    #[derive(serde::Serialize)]
-    struct VideoSummary {
-        id: String,
-        title: String,
+    let repaired = db.repair_integrity()?;
+    println!("\nRemoved or cleared {} row(s) and rebuilt the search indexes.", repaired);
+    let remaining = db.check_integrity()?;
+    if remaining.iter().any(|i| i.fixable) {
+        println!("Some problems remain; run doctor again for details.");
     }
-
-    async fn get_pins(
-        State(state): State<Arc<AppState>>,
-        Query(q): Query<MapQuery>,
-    ) -> Result<Json<Vec<engine::MapPin>>, StatusCode> {
-        let db = open_db(&state)?;
-        let eras = parse_eras(&q.era);
-        let pins = if eras.is_empty() {
-            // No era filter - show all pins
-            db.get_map_pins(None, q.topic.as_deref())
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        } else {
-            // Multiple eras - union pins from each era
-            let mut all_pins = Vec::new();
-            let mut seen_ids = std::collections::HashSet::new();
-            for era in &eras {
-                let era_pins = db.get_map_pins(Some(era), q.topic.as_deref())
-                    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-                for pin in era_pins {
-                    let key = (pin.location.id, pin.video_id.clone());
-                    if seen_ids.insert(key) {
-                        all_pins.push(pin);
-                    }
-                }
-            }
-            all_pins
-        };
-        Ok(Json(pins))
-    }
-
-    async fn get_eras(
-        State(state): State<Arc<AppState>>,
-    ) -> Result<Json<Vec<engine::Era>>, StatusCode> {
-        let db = open_db(&state)?;
-        let eras = db.list_eras().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        Ok(Json(eras))
-    }
-
-    async fn get_topics(
-        State(state): State<Arc<AppState>>,
-    ) -> Result<Json<Vec<engine::Topic>>, StatusCode> {
-        let db = open_db(&state)?;
-        let topics = db.list_topics().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        Ok(Json(topics))
-    }
-
-    async fn get_videos(
-        State(state): State<Arc<AppState>>,
-    ) -> Result<Json<Vec<VideoSummary>>, StatusCode> {
-        let db = open_db(&state)?;
-        let videos = db.list_videos().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        Ok(Json(videos.into_iter().map(|v| VideoSummary {
-            id: v.id,
-            title: v.title,
-        }).collect()))
-    }
-
-    async fn get_claims(
-        State(state): State<Arc<AppState>>,
-        Query(q): Query<ClaimsQuery>,
-    ) -> Result<Json<Vec<engine::Claim>>, StatusCode> {
-        let db = open_db(&state)?;
-        let claims = if let Some(video_id) = q.video_id {
-            db.list_claims_for_video(&video_id).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        } else {
-            // Get all claims (limited)
-            db.get_random_claims(q.limit.unwrap_or(100)).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        };
-        Ok(Json(claims))
-    }
-
-    async fn get_claim(
-        State(state): State<Arc<AppState>>,
-        Path(id): Path<i64>,
-    ) -> Result<Json<engine::ClaimWithLinks>, StatusCode> {
-        let db = open_db(&state)?;
-        let claim = db.get_claim_with_links(id)
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-            .ok_or(StatusCode::NOT_FOUND)?;
-        Ok(Json(claim))
-    }
-
-    async fn get_graph(
-        State(state): State<Arc<AppState>>,
-        Query(q): Query<GraphQuery>,
-    ) -> Result<Json<GraphData>, StatusCode> {
-        let db = open_db(&state)?;
-
-        // Get claims based on filter
-        let claims: Vec<engine::Claim> = if let Some(video_id) = q.video_id {
-            db.list_claims_for_video(&video_id).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        } else if let Some(moc_id) = q.moc_id {
-            let moc = db.get_moc_with_claims(moc_id)
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-                .ok_or(StatusCode::NOT_FOUND)?;
-            moc.claims
-        } else if q.era.is_some() {
-            // Filter by era(s): get videos with these eras, then get their claims
-            let eras = parse_eras(&q.era);
-            let mut era_claims = Vec::new();
-            let mut seen_videos = std::collections::HashSet::new();
-            for era in &eras {
-                let videos = db.browse_videos(Some(era), None).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-                for video in videos {
-                    if seen_videos.insert(video.id.clone()) {
-                        era_claims.extend(db.list_claims_for_video(&video.id).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?);
-                    }
-                }
-            }
-            era_claims
-        } else if let Some(ref topic) = q.topic {
-            // Filter by topic: get videos with this topic, then get their claims
-            let videos = db.browse_by_topic(topic).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            let mut topic_claims = Vec::new();
-            for video in videos {
-                topic_claims.extend(db.list_claims_for_video(&video.id).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?);
-            }
-            topic_claims
-        } else {
-            // Default: get all claims (limited to 500 for performance)
-            db.get_all_claims_limited(500).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        };
-
-        let claim_ids: std::collections::HashSet<i64> = claims.iter().map(|c| c.id).collect();
-
-        // Build nodes
-        let mut nodes = Vec::new();
-        for claim in &claims {
-            let link_count = db.get_claim_link_count(claim.id).unwrap_or(0);
-            let label = if claim.text.len() > 40 {
-                format!("{}...", &claim.text[..37])
-            } else {
-                claim.text.clone()
-            };
-            nodes.push(GraphNode {
-                id: claim.id,
-                label,
-                title: claim.text.clone(),
-                group: claim.category.as_str().to_string(),
-                value: (link_count + 1) as usize,
-                video_id: claim.video_id.clone(),
-                timestamp: claim.timestamp,
-            });
-        }
-
-        // Build edges
-        let mut edges = Vec::new();
-        for claim in &claims {
-            if let Ok(claim_with_links) = db.get_claim_with_links(claim.id) {
-                if let Some(cwl) = claim_with_links {
-                    for (link, _target) in &cwl.outgoing_links {
-                        // Only include edges where both nodes are in our set
-                        if claim_ids.contains(&link.target_claim_id) {
-                            let (color, dashes) = match link.link_type {
-                                engine::LinkType::Supports => ("#4CAF50", false),
-                                engine::LinkType::Contradicts => ("#f44336", true),
-                                engine::LinkType::Elaborates => ("#2196F3", false),
-                                engine::LinkType::Causes => ("#FF9800", false),
-                                engine::LinkType::CausedBy => ("#FF9800", false),
-                                engine::LinkType::Related => ("#9E9E9E", true),
-                            };
-                            edges.push(GraphEdge {
-                                from: link.source_claim_id,
-                                to: link.target_claim_id,
-                                label: link.link_type.as_str().to_string(),
-                                arrows: "to".to_string(),
-                                dashes,
-                                color: EdgeColor { color: color.to_string() },
-                            });
-                        }
-                    }
-                }
-            }
-        }
-
-        Ok(Json(GraphData { nodes, edges }))
-    }
-
-    async fn get_mocs(
-        State(state): State<Arc<AppState>>,
-    ) -> Result<Json<Vec<MocSummary>>, StatusCode> {
-        let db = open_db(&state)?;
-        let mocs = db.list_mocs().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        let mut summaries = Vec::new();
-        for moc in mocs {
-            let claim_count = db.get_moc_with_claims(moc.id)
-                .map(|m| m.map(|x| x.claims.len()).unwrap_or(0))
-                .unwrap_or(0);
-            summaries.push(MocSummary {
-                id: moc.id,
-                title: moc.title,
-                description: moc.description,
-                claim_count,
-            });
-        }
-        Ok(Json(summaries))
-    }
-
-    async fn get_moc(
-        State(state): State<Arc<AppState>>,
-        Path(id): Path<i64>,
-    ) -> Result<Json<engine::MocWithClaims>, StatusCode> {
-        let db = open_db(&state)?;
-        let moc = db.get_moc_with_claims(id)
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-            .ok_or(StatusCode::NOT_FOUND)?;
-        Ok(Json(moc))
-    }
-
-    async fn get_questions(
-        State(state): State<Arc<AppState>>,
-    ) -> Result<Json<Vec<QuestionSummary>>, StatusCode> {
-        let db = open_db(&state)?;
-        let questions = db.list_research_questions(None)
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        let mut summaries = Vec::new();
-        for q in questions {
-            let evidence_count = db.get_question_with_evidence(q.id)
-                .map(|qe| qe.map(|x| x.claims.len() + x.videos.len()).unwrap_or(0))
-                .unwrap_or(0);
-            summaries.push(QuestionSummary {
-                id: q.id,
-                question: q.question,
-                status: q.status.as_str().to_string(),
-                evidence_count,
-            });
-        }
-        Ok(Json(summaries))
-    }
-
-    async fn get_question(
-        State(state): State<Arc<AppState>>,
-        Path(id): Path<i64>,
-    ) -> Result<Json<engine::QuestionWithEvidence>, StatusCode> {
-        let db = open_db(&state)?;
-        let question = db.get_question_with_evidence(id)
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-            .ok_or(StatusCode::NOT_FOUND)?;
-        Ok(Json(question))
-    }
-
-    async fn get_stats(
-        State(state): State<Arc<AppState>>,
-    ) -> Result<Json<FullStats>, StatusCode> {
-        let db = open_db(&state)?;
-        let synthesis = db.get_synthesis_stats().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        let framework = db.get_framework_stats().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-        // Get counts
-        let videos = db.list_videos().map(|v| v.len() as i64).unwrap_or(0);
-        let claims = db.get_random_claims(10000).map(|c| c.len() as i64).unwrap_or(0);
-        let orphans = db.get_orphan_claims().map(|o| o.len()).unwrap_or(0);
-        let stale = db.get_stale_claims(30).map(|s| s.len()).unwrap_or(0);
-
-        // Count claims by category (simplified)
-        let claims_by_category = vec![
-            CategoryCount { category: "factual".to_string(), count: 0 },
-            CategoryCount { category: "causal".to_string(), count: 0 },
-            CategoryCount { category: "cyclical".to_string(), count: 0 },
-            CategoryCount { category: "memetic".to_string(), count: 0 },
-            CategoryCount { category: "geopolitical".to_string(), count: 0 },
-        ];
-
-        // Phase 12: Get expanded entity counts
-        let sources = db.get_sources().map(|s| s.len() as i64).unwrap_or(0);
-        let scholars = db.get_scholars().map(|s| s.len() as i64).unwrap_or(0);
-        let terms = db.get_terms().map(|t| t.len() as i64).unwrap_or(0);
-        let visuals = db.get_all_visuals().map(|v| v.len() as i64).unwrap_or(0);
-        let evidence = db.get_all_evidence().map(|e| e.len() as i64).unwrap_or(0);
-        let quotes = db.get_all_quotes().map(|q| q.len() as i64).unwrap_or(0);
-
-        Ok(Json(FullStats {
-            videos,
-            claims,
-            links: 0, // Would need a query
-            mocs: synthesis.mocs,
-            questions: synthesis.research_questions,
-            active_questions: synthesis.active_questions,
-            patterns: synthesis.detected_patterns,
-            orphan_claims: orphans,
-            stale_claims: stale,
-            framework,
-            claims_by_category,
-            sources,
-            scholars,
-            terms,
-            visuals,
-            evidence,
-            quotes,
-        }))
-    }
-
-    async fn get_review_orphans(
-        State(state): State<Arc<AppState>>,
-    ) -> Result<Json<Vec<engine::Claim>>, StatusCode> {
-        let db = open_db(&state)?;
-        let orphans = db.get_orphan_claims().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        Ok(Json(orphans))
-    }
-
-    async fn get_review_stale(
-        State(state): State<Arc<AppState>>,
-    ) -> Result<Json<Vec<engine::Claim>>, StatusCode> {
-        let db = open_db(&state)?;
-        let stale = db.get_stale_claims(30).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        Ok(Json(stale))
-    }
-
-    async fn get_queue(
-        State(state): State<Arc<AppState>>,
-    ) -> Result<Json<QueueSummary>, StatusCode> {
-        let db = open_db(&state)?;
-        let items = db.get_queue(true).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-        let mut pending = 0;
-        let mut in_progress = 0;
-        let mut completed = 0;
-        let mut failed = 0;
-        let mut current = None;
-
-        for item in items {
-            match item.status {
-                engine::ProcessingStatus::Pending => pending += 1,
-                engine::ProcessingStatus::InProgress => {
-                    in_progress += 1;
-                    current = Some(item.video_id.clone());
-                }
-                engine::ProcessingStatus::Completed => completed += 1,
-                engine::ProcessingStatus::Failed => failed += 1,
-                engine::ProcessingStatus::Skipped => {}
-            }
-        }
-
-        Ok(Json(QueueSummary { pending, in_progress, completed, failed, current }))
-    }
-
-    // Phase 12: API endpoints for expanded knowledge entities
-
-    async fn get_sources(
-        State(state): State<Arc<AppState>>,
-    ) -> Result<Json<Vec<engine::Source>>, StatusCode> {
-        let db = open_db(&state)?;
-        let sources = db.get_sources().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        Ok(Json(sources))
-    }
-
-    async fn get_scholars(
-        State(state): State<Arc<AppState>>,
-    ) -> Result<Json<Vec<engine::Scholar>>, StatusCode> {
-        let db = open_db(&state)?;
-        let scholars = db.get_scholars().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        Ok(Json(scholars))
-    }
-
-    async fn get_terms(
-        State(state): State<Arc<AppState>>,
-    ) -> Result<Json<Vec<engine::Term>>, StatusCode> {
-        let db = open_db(&state)?;
-        let terms = db.get_terms().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        Ok(Json(terms))
-    }
-
-    async fn get_visuals(
-        State(state): State<Arc<AppState>>,
-    ) -> Result<Json<Vec<engine::Visual>>, StatusCode> {
-        let db = open_db(&state)?;
-        let visuals = db.get_all_visuals().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        Ok(Json(visuals))
-    }
-
-    async fn get_evidence(
-        State(state): State<Arc<AppState>>,
-    ) -> Result<Json<Vec<engine::Evidence>>, StatusCode> {
-        let db = open_db(&state)?;
-        let evidence = db.get_all_evidence().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        Ok(Json(evidence))
-    }
-
-    async fn get_quotes(
-        State(state): State<Arc<AppState>>,
-    ) -> Result<Json<Vec<engine::Quote>>, StatusCode> {
-        let db = open_db(&state)?;
-        let quotes = db.get_all_quotes().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        Ok(Json(quotes))
-    }
-
-    async fn search(
-        State(state): State<Arc<AppState>>,
-        Query(q): Query<SearchQuery>,
-    ) -> Result<Json<engine::SearchResponse>, StatusCode> {
-        let db = open_db(&state)?;
-
-        // Parse comma-separated types
-        let types: Option<Vec<&str>> = q.types.as_ref()
-            .map(|t| t.split(',').map(|s| s.trim()).collect());
-
-        let results = db.unified_search(
-            &q.q,
-            types.as_deref(),
-            q.video_id.as_deref(),
-            q.limit.unwrap_or(50),
-            q.fuzzy_threshold.unwrap_or(0.6),
-        ).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-        Ok(Json(results))
-    }
-
-    async fn get_index() -> axum::response::Html<&'static str> {
-        axum::response::Html(include_str!("../static/index.html"))
-    }
-
-    let state = Arc::new(AppState { db_path });
-
-    let app = Router::new()
-        .route("/", get(get_index))
-        .route("/api/pins", get(get_pins))
-        .route("/api/eras", get(get_eras))
-        .route("/api/topics", get(get_topics))
-        .route("/api/videos", get(get_videos))
-        .route("/api/claims", get(get_claims))
-        .route("/api/claims/:id", get(get_claim))
-        .route("/api/graph", get(get_graph))
-        .route("/api/mocs", get(get_mocs))
-        .route("/api/mocs/:id", get(get_moc))
-        .route("/api/questions", get(get_questions))
-        .route("/api/questions/:id", get(get_question))
-        .route("/api/stats", get(get_stats))
-        .route("/api/review/orphans", get(get_review_orphans))
-        .route("/api/review/stale", get(get_review_stale))
-        .route("/api/queue", get(get_queue))
-        // Phase 12: Expanded knowledge entity endpoints
-        .route("/api/sources", get(get_sources))
-        .route("/api/scholars", get(get_scholars))
-        .route("/api/terms", get(get_terms))
-        .route("/api/visuals", get(get_visuals))
-        .route("/api/evidence", get(get_evidence))
-        .route("/api/quotes", get(get_quotes))
-        // Unified search endpoint
-        .route("/api/search", get(search))
-        .layer(CorsLayer::permissive())
-        .with_state(state);
-
-    println!("Starting server at http://localhost:{}", port);
-    println!("Open in your browser to view the knowledge base.");
-
-    tokio::runtime::Runtime::new()?
-        .block_on(async {
-            let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", port)).await?;
-            axum::serve(listener, app).await
-        })
-        .map_err(|e| anyhow::anyhow!("Server error: {}", e))
+    Ok(())
 }
 
-fn cmd_suggest_tags(db: &Database, video_id: &str) -> Result<()> {
+fn cmd_suggest_tags(db: &dyn Storage, video_id: &str) -> Result<()> {
     let video = match db.get_video(video_id)? {
         Some(v) => v,
         None => {
@@ -2402,7 +4475,7 @@ fn cmd_suggest_tags(db: &Database, video_id: &str) -> Result<()> {
     Ok(())
 }
 
-fn cmd_auto_tag(db: &Database, id: &str) -> Result<()> {
+fn cmd_auto_tag(db: &dyn Storage, id: &str) -> Result<()> {
     if id == "all" {
         let videos = db.list_videos()?;
         let mut total_tags = 0;
@@ -2441,15 +4514,75 @@ fn cmd_auto_tag(db: &Database, id: &str) -> Result<()> {
         if !tags.regions.is_empty() {
             println!("Applied regions: {}", tags.regions.join(", "));
         }
-        if !tags.topics.is_empty() {
-            println!("Applied topics: {}", tags.topics.join(", "));
+        if !tags.topics.is_empty() {
+            println!("Applied topics: {}", tags.topics.join(", "));
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_auto_tag_llm(db: &dyn Storage, id: &str, allow_new: bool, llm: &LlmOptions) -> Result<()> {
+    use engine::ai::tagging;
+    use engine::ai::validation::complete_validated;
+
+    let videos = if id == "all" {
+        db.list_videos()?
+    } else {
+        match db.get_video(id)? {
+            Some(v) => vec![v],
+            None => {
+                println!("Video not found: {}", id);
+                return Ok(());
+            }
+        }
+    };
+
+    let config = llm.config(db)?;
+    let chat = tracked_chat(db, &config, "auto-tag")?;
+    println!("Tagging with {} ({})\n", config.model, config.provider.as_str());
+
+    let mut total = 0;
+    let mut unknown: Vec<String> = Vec::new();
+    for video in &videos {
+        let known = engine::AutoTags {
+            eras: db.list_eras()?.into_iter().map(|e| e.name).collect(),
+            regions: db.list_regions()?.into_iter().map(|r| r.name).collect(),
+            topics: db.list_topics()?.into_iter().map(|t| t.name).collect(),
+        };
+        let summary = db.get_transcript_layer(&video.id, 4)?.map(|l| l.content);
+        let prompt = tagging::tags_prompt(&video.title, video.description.as_deref(), summary.as_deref(), &known);
+        let tags = match complete_validated(chat.as_ref(), tagging::SYSTEM_PROMPT, &prompt, tagging::parse_tags) {
+            Ok((tags, _)) => tags,
+            Err(e) => {
+                println!("{} {}\n  Failed: {:#}", video.id, truncate(&video.title, 60), e);
+                continue;
+            }
+        };
+        let (applied, skipped) = db.apply_tags(&video.id, &tags, allow_new)?;
+
+        println!("{} {}", video.id, truncate(&video.title, 60));
+        for (label, names) in [("Eras", &applied.eras), ("Regions", &applied.regions), ("Topics", &applied.topics)] {
+            if !names.is_empty() {
+                println!("  {}: {}", label, names.join(", "));
+            }
+        }
+        let skipped: Vec<String> = skipped.eras.into_iter().chain(skipped.regions).chain(skipped.topics).collect();
+        if !skipped.is_empty() {
+            println!("  Not in the taxonomy: {}", skipped.join(", "));
         }
+        total += applied.eras.len() + applied.regions.len() + applied.topics.len();
+        unknown.extend(skipped);
     }
 
+    println!("\nApplied {} tags to {} video(s).", total, videos.len());
+    if !unknown.is_empty() {
+        println!("Skipped {} unknown name(s); rerun with --allow-new to create them.", unknown.len());
+    }
     Ok(())
 }
 
-fn cmd_rebuild_index(db: &Database) -> Result<()> {
+fn cmd_rebuild_index(db: &dyn Storage) -> Result<()> {
     println!("Rebuilding search index...");
     let count = db.rebuild_search_index()?;
     println!("Indexed {} videos.", count);
@@ -2458,41 +4591,59 @@ fn cmd_rebuild_index(db: &Database) -> Result<()> {
 
 // Phase 5: Research Tools
 
-fn cmd_save_search(
-    db: &Database,
-    name: &str,
-    query: Option<&str>,
-    era: Option<&str>,
-    region: Option<&str>,
-    topic: Option<&str>,
-) -> Result<()> {
-    if query.is_none() && era.is_none() && region.is_none() && topic.is_none() {
-        println!("Please specify at least one of: --query, --era, --region, --topic");
+fn cmd_save_search(db: &dyn Storage, mut search: engine::SavedSearch, vector: Option<&str>) -> Result<()> {
+    if let Some(v) = vector {
+        match serde_json::from_str::<Vec<f32>>(v) {
+            Ok(vec) if !vec.is_empty() => search.vector = Some(vec),
+            Ok(_) => anyhow::bail!("Query embedding is empty"),
+            Err(e) => anyhow::bail!("Invalid vector JSON: {}", e),
+        }
+    }
+    for date in [&search.date_from, &search.date_to].into_iter().flatten() {
+        if chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+            anyhow::bail!("Invalid date '{}': expected YYYY-MM-DD", date);
+        }
+    }
+
+    let filters = saved_search_filters(&search);
+    if filters.is_empty() {
+        println!("Please specify at least one of: --query, --era, --region, --topic, --channel, --collection, --from, --to, --vector");
         return Ok(());
     }
 
-    let saved = db.save_search(name, query, era, region, topic)?;
+    let saved = db.save_search(&search)?;
     println!("Saved search '{}'", saved.name);
+    println!("  {}", filters.join(", "));
+
+    Ok(())
+}
 
+/// Short descriptions of everything a saved search filters on, e.g. `era:Bronze Age`.
+fn saved_search_filters(search: &engine::SavedSearch) -> Vec<String> {
     let mut filters = Vec::new();
-    if let Some(q) = &saved.query {
-        filters.push(format!("query: \"{}\"", q));
-    }
-    if let Some(e) = &saved.era {
-        filters.push(format!("era: {}", e));
-    }
-    if let Some(r) = &saved.region {
-        filters.push(format!("region: {}", r));
+    if let Some(q) = &search.query {
+        filters.push(format!("\"{}\"", q));
+    }
+    for (label, value) in [
+        ("era", &search.era),
+        ("region", &search.region),
+        ("topic", &search.topic),
+        ("channel", &search.channel),
+        ("collection", &search.collection),
+        ("from", &search.date_from),
+        ("to", &search.date_to),
+    ] {
+        if let Some(v) = value {
+            filters.push(format!("{}:{}", label, v));
+        }
     }
-    if let Some(t) = &saved.topic {
-        filters.push(format!("topic: {}", t));
+    if let Some(v) = &search.vector {
+        filters.push(format!("embedding:{}d", v.len()));
     }
-    println!("  {}", filters.join(", "));
-
-    Ok(())
+    filters
 }
 
-fn cmd_list_searches(db: &Database) -> Result<()> {
+fn cmd_list_searches(db: &dyn Storage) -> Result<()> {
     let searches = db.list_saved_searches()?;
 
     if searches.is_empty() {
@@ -2505,26 +4656,13 @@ fn cmd_list_searches(db: &Database) -> Result<()> {
     println!("{}", "-".repeat(60));
 
     for search in searches {
-        let mut filters = Vec::new();
-        if let Some(q) = &search.query {
-            filters.push(format!("\"{}\"", q));
-        }
-        if let Some(e) = &search.era {
-            filters.push(format!("era:{}", e));
-        }
-        if let Some(r) = &search.region {
-            filters.push(format!("region:{}", r));
-        }
-        if let Some(t) = &search.topic {
-            filters.push(format!("topic:{}", t));
-        }
-        println!("{:<20} {}", search.name, filters.join(" + "));
+        println!("{:<20} {}", search.name, saved_search_filters(&search).join(" + "));
     }
 
     Ok(())
 }
 
-fn cmd_run_search(db: &Database, name: &str) -> Result<()> {
+fn cmd_run_search(db: &dyn Storage, name: &str) -> Result<()> {
     let search = match db.get_saved_search(name)? {
         Some(s) => s,
         None => {
@@ -2535,12 +4673,21 @@ fn cmd_run_search(db: &Database, name: &str) -> Result<()> {
 
     println!("Running saved search: {}\n", search.name);
 
-    let results = db.advanced_search(
+    if let Some(ref vector) = search.vector {
+        return run_saved_semantic_search(db, &search, vector);
+    }
+
+    let response = db.advanced_search(
         search.query.as_deref(),
         search.era.as_deref(),
         search.region.as_deref(),
         search.topic.as_deref(),
+        search.collection.as_deref(),
+        search.channel.as_deref(),
+        search.date_from.as_deref(),
+        search.date_to.as_deref(),
     )?;
+    let results = response.results;
 
     if results.is_empty() {
         println!("No results found.");
@@ -2549,6 +4696,7 @@ fn cmd_run_search(db: &Database, name: &str) -> Result<()> {
 
     let total_matches: usize = results.iter().map(|r| r.matches.len()).sum();
     println!("Found {} videos ({} transcript matches)\n", results.len(), total_matches);
+    print_facets(&response.facets);
 
     for result in results {
         println!("--- {} ---", result.video.id);
@@ -2582,7 +4730,69 @@ fn cmd_run_search(db: &Database, name: &str) -> Result<()> {
     Ok(())
 }
 
-fn cmd_delete_search(db: &Database, name: &str) -> Result<()> {
+/// Saved searches with an embedding run as hybrid searches (semantic only without a query),
+/// then keep the videos that pass the saved metadata filters.
+fn run_saved_semantic_search(db: &dyn Storage, search: &engine::SavedSearch, vector: &[f32]) -> Result<()> {
+    let mut results = db.hybrid_search(search.query.as_deref().unwrap_or(""), Some(vector), 0.5, 0.5, 20, engine::Fusion::Weighted)?;
+
+    let filtered = search.era.is_some()
+        || search.region.is_some()
+        || search.topic.is_some()
+        || search.collection.is_some()
+        || search.channel.is_some()
+        || search.date_from.is_some()
+        || search.date_to.is_some();
+    if filtered {
+        let allowed: std::collections::HashSet<String> = db
+            .advanced_search(
+                None,
+                search.era.as_deref(),
+                search.region.as_deref(),
+                search.topic.as_deref(),
+                search.collection.as_deref(),
+                search.channel.as_deref(),
+                search.date_from.as_deref(),
+                search.date_to.as_deref(),
+            )?
+            .results
+            .into_iter()
+            .map(|r| r.video.id)
+            .collect();
+        results.retain(|r| allowed.contains(&r.video.id));
+    }
+
+    if results.is_empty() {
+        if filtered {
+            println!("No results found.");
+        } else {
+            println!("No results found. Make sure embeddings exist in the database.");
+        }
+        return Ok(());
+    }
+
+    println!("{:<8} {:<6} {:<6} {:<12} TITLE", "SCORE", "KW", "SEM", "ID");
+    println!("{}", "-".repeat(80));
+    for result in results {
+        println!(
+            "{:<8.3} {:<6.3} {:<6.3} {:<12} {}",
+            result.combined_score,
+            result.keyword_score,
+            result.semantic_score,
+            result.video.id,
+            truncate(&result.video.title, 35)
+        );
+        if !result.matching_chunks.is_empty() {
+            println!("  Matching chunks: {}", result.matching_chunks.len());
+        }
+        if !result.matching_claims.is_empty() {
+            println!("  Matching claims: {}", result.matching_claims.len());
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_delete_search(db: &dyn Storage, name: &str) -> Result<()> {
     if db.delete_saved_search(name)? {
         println!("Deleted saved search: {}", name);
     } else {
@@ -2591,7 +4801,7 @@ fn cmd_delete_search(db: &Database, name: &str) -> Result<()> {
     Ok(())
 }
 
-fn cmd_export(db: &Database, collection: &str, output: Option<&str>) -> Result<()> {
+fn cmd_export(db: &dyn Storage, collection: &str, output: Option<&str>) -> Result<()> {
     match db.export_collection_markdown(collection)? {
         Some(markdown) => {
             if let Some(path) = output {
@@ -2608,8 +4818,248 @@ fn cmd_export(db: &Database, collection: &str, output: Option<&str>) -> Result<(
     Ok(())
 }
 
+const ARCHIVE_FORMAT: &str = "engine-archive";
+const ARCHIVE_VERSION: i64 = 1;
+
+fn is_gzip(path: &std::path::Path) -> bool {
+    path.extension().is_some_and(|e| e == "gz")
+}
+
+fn cmd_export_all(db: &dyn Storage, output: &std::path::Path) -> Result<()> {
+    use std::io::Write;
+
+    let file = std::fs::File::create(output)?;
+    let mut gzip = if is_gzip(output) {
+        Some(
+            std::process::Command::new("gzip")
+                .arg("-c")
+                .stdin(std::process::Stdio::piped())
+                .stdout(file.try_clone()?)
+                .spawn()
+                .map_err(|e| anyhow::anyhow!("Failed to run gzip: {}", e))?,
+        )
+    } else {
+        None
+    };
+    let sink: Box<dyn Write> = match gzip.as_mut() {
+        Some(child) => Box::new(child.stdin.take().expect("piped stdin")),
+        None => Box::new(file),
+    };
+    let mut out = std::io::BufWriter::new(sink);
+
+    let header = serde_json::json!({
+        "format": ARCHIVE_FORMAT,
+        "version": ARCHIVE_VERSION,
+        "schema_version": db.schema_version()?,
+        "exported_at": chrono::Utc::now().to_rfc3339(),
+    });
+    writeln!(out, "{}", header)?;
+
+    let mut total = 0;
+    for table in db.exportable_tables()? {
+        let count = db.export_table_rows(&table, &mut |row| {
+            writeln!(out, "{}", serde_json::json!({ "table": table, "row": row }))?;
+            Ok(())
+        })?;
+        if count > 0 {
+            println!("  {:<28} {}", table, count);
+        }
+        total += count;
+    }
+    out.flush()?;
+    drop(out);
+
+    if let Some(mut child) = gzip {
+        if !child.wait()?.success() {
+            anyhow::bail!("gzip failed");
+        }
+    }
+
+    println!("Exported {} rows to {}", total, output.display());
+    Ok(())
+}
+
+fn cmd_import_all(db: &dyn Storage, input: &std::path::Path) -> Result<()> {
+    use std::io::BufRead;
+
+    let mut gzip = None;
+    let source: Box<dyn std::io::Read> = if is_gzip(input) {
+        let mut child = std::process::Command::new("gzip")
+            .arg("-dc")
+            .arg(input)
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .map_err(|e| anyhow::anyhow!("Failed to run gzip: {}", e))?;
+        let stdout = child.stdout.take().expect("piped stdout");
+        gzip = Some(child);
+        Box::new(stdout)
+    } else {
+        Box::new(std::fs::File::open(input)?)
+    };
+    let mut lines = std::io::BufReader::new(source).lines();
+
+    let header: serde_json::Value = match lines.next() {
+        Some(line) => serde_json::from_str(&line?)?,
+        None => anyhow::bail!("Archive is empty"),
+    };
+    if header["format"] != ARCHIVE_FORMAT {
+        anyhow::bail!("Not an export-all archive: {}", input.display());
+    }
+    let version = header["version"].as_i64().unwrap_or(0);
+    if version > ARCHIVE_VERSION {
+        anyhow::bail!("Archive format version {} is newer than this build supports ({})", version, ARCHIVE_VERSION);
+    }
+
+    let mut records = lines.filter(|l| !matches!(l, Ok(l) if l.trim().is_empty())).map(|line| {
+        let mut record: serde_json::Value = serde_json::from_str(&line?)?;
+        let table = record["table"].as_str().ok_or_else(|| anyhow::anyhow!("Record without a table"))?.to_string();
+        match record["row"].take() {
+            serde_json::Value::Object(row) => Ok((table, row)),
+            _ => anyhow::bail!("Record for {} without a row", table),
+        }
+    });
+    let counts = db.import_table_rows(&mut records)?;
+
+    if let Some(mut child) = gzip {
+        if !child.wait()?.success() {
+            anyhow::bail!("gzip failed to decompress {}", input.display());
+        }
+    }
+
+    let mut total = 0;
+    for (table, count) in &counts {
+        println!("  {:<28} {}", table, count);
+        total += count;
+    }
+    println!(
+        "Imported {} rows (archive schema v{}, this database v{})",
+        total,
+        header["schema_version"].as_i64().unwrap_or(0),
+        db.schema_version()?
+    );
+    Ok(())
+}
+
+fn cmd_export_corpus(
+    db: &dyn Storage,
+    output: &std::path::Path,
+    format: &str,
+    era: Option<&str>,
+    topic: Option<&str>,
+    full: bool,
+) -> Result<()> {
+    if format != "txt" && format != "jsonl" {
+        println!("Invalid format: {}. Valid options: txt, jsonl", format);
+        return Ok(());
+    }
+
+    let mut videos = match era {
+        Some(e) => db.browse_videos(Some(e), None, false)?,
+        None => db.list_videos()?,
+    };
+    if let Some(t) = topic {
+        let ids: std::collections::HashSet<String> =
+            db.browse_by_topic(t)?.into_iter().map(|v| v.id).collect();
+        videos.retain(|v| ids.contains(&v.id));
+    }
+
+    std::fs::create_dir_all(output)?;
+
+    // Fingerprints of what was last written, so reruns only emit new or changed videos
+    let manifest_path = output.join(format!(".corpus-manifest-{}.json", format));
+    let mut manifest: std::collections::BTreeMap<String, String> = if full {
+        Default::default()
+    } else {
+        std::fs::read_to_string(&manifest_path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    };
+
+    let mut records = Vec::new();
+    let mut unchanged = 0;
+    let mut no_transcript = 0;
+
+    for video in &videos {
+        let transcript = match db.get_transcript(&video.id)? {
+            Some(t) => t,
+            None => {
+                no_transcript += 1;
+                continue;
+            }
+        };
+
+        let eras: Vec<String> = db.get_video_eras(&video.id)?.into_iter().map(|e| e.name).collect();
+        let regions: Vec<String> = db.get_video_regions(&video.id)?.into_iter().map(|r| r.name).collect();
+        let topics: Vec<String> = db.get_video_topics(&video.id)?.into_iter().map(|t| t.name).collect();
+
+        let record = if format == "jsonl" {
+            serde_json::json!({
+                "id": video.id,
+                "url": video.url,
+                "title": video.title,
+                "channel": video.channel,
+                "upload_date": video.upload_date,
+                "language": transcript.language,
+                "eras": eras,
+                "regions": regions,
+                "topics": topics,
+                "text": transcript.full_text,
+            }).to_string()
+        } else {
+            let mut doc = String::new();
+            doc.push_str(&format!("# Title: {}\n", video.title));
+            doc.push_str(&format!("# ID: {}\n", video.id));
+            doc.push_str(&format!("# URL: {}\n", video.url));
+            if let Some(channel) = &video.channel {
+                doc.push_str(&format!("# Channel: {}\n", channel));
+            }
+            if let Some(date) = video.upload_date {
+                doc.push_str(&format!("# Date: {}\n", date));
+            }
+            doc.push_str(&format!("# Language: {}\n", transcript.language));
+            doc.push_str(&format!("# Eras: {}\n", eras.join(", ")));
+            doc.push_str(&format!("# Regions: {}\n", regions.join(", ")));
+            doc.push_str(&format!("# Topics: {}\n", topics.join(", ")));
+            doc.push('\n');
+            doc.push_str(&transcript.full_text);
+            doc.push('\n');
+            doc
+        };
+
+        let fingerprint = format!("{:016x}", fnv_hash(&record));
+        let file_missing = format == "txt" && !output.join(format!("{}.txt", video.id)).exists();
+        if !file_missing && manifest.get(&video.id) == Some(&fingerprint) {
+            unchanged += 1;
+            continue;
+        }
+        manifest.insert(video.id.clone(), fingerprint);
+        records.push((video.id.clone(), record));
+    }
+
+    if !records.is_empty() {
+        if format == "jsonl" {
+            // Each run writes a batch of only the new or changed records
+            let path = output.join(format!("corpus-{}.jsonl", chrono::Utc::now().format("%Y%m%dT%H%M%S")));
+            let lines: Vec<&str> = records.iter().map(|(_, r)| r.as_str()).collect();
+            std::fs::write(&path, lines.join("\n") + "\n")?;
+            println!("Wrote {}", path.display());
+        } else {
+            for (id, doc) in &records {
+                std::fs::write(output.join(format!("{}.txt", id)), doc)?;
+            }
+        }
+
+        std::fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+    }
+
+    println!("Exported {} videos to {} ({} unchanged, {} without transcript)",
+        records.len(), output.display(), unchanged, no_transcript);
+    Ok(())
+}
+
 fn cmd_export_map(
-    db: &Database,
+    db: &dyn Storage,
     era: Option<&str>,
     topic: Option<&str>,
     output: Option<&str>,
@@ -2640,7 +5090,103 @@ fn cmd_export_map(
     Ok(())
 }
 
-fn cmd_report(db: &Database, by: &str) -> Result<()> {
+fn cmd_export_timeline(db: &dyn Storage, format: &str, from: Option<i32>, to: Option<i32>, output: Option<&str>) -> Result<()> {
+    let claims = db.list_dated_claims(from, to)?;
+    if claims.is_empty() {
+        match format_year_range(from, to) {
+            Some(years) => println!("No dated claims for {}", years),
+            None => println!("No dated claims (set them with 'date-claim ID --from YEAR')."),
+        }
+        return Ok(());
+    }
+
+    let mut out = String::new();
+    if format == "csv" {
+        let field = |s: &str| {
+            if s.contains([',', '"', '\n']) {
+                format!("\"{}\"", s.replace('"', "\"\""))
+            } else {
+                s.to_string()
+            }
+        };
+        out.push_str("id,start,end,label,text,category,status,video_id,timestamp\n");
+        for c in &claims {
+            out.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{}\n",
+                c.id,
+                c.date_start.map(|y| y.to_string()).unwrap_or_default(),
+                c.date_end.map(|y| y.to_string()).unwrap_or_default(),
+                field(&format_claim_dates(c.date_start, c.date_end).unwrap_or_default()),
+                field(&c.text),
+                c.category.as_str(),
+                c.status.as_str(),
+                field(&c.video_id),
+                c.timestamp.map(|t| t.to_string()).unwrap_or_default(),
+            ));
+        }
+    } else {
+        let events: Vec<serde_json::Value> = claims
+            .iter()
+            .map(|c| {
+                serde_json::json!({
+                    "id": c.id,
+                    "start": c.date_start,
+                    "end": c.date_end,
+                    "label": format_claim_dates(c.date_start, c.date_end),
+                    "text": c.text,
+                    "category": c.category.as_str(),
+                    "status": c.status.as_str(),
+                    "video_id": c.video_id,
+                    "timestamp": c.timestamp,
+                })
+            })
+            .collect();
+        out = serde_json::to_string_pretty(&events)?;
+        out.push('\n');
+    }
+
+    if let Some(path) = output {
+        std::fs::write(path, &out)?;
+        println!("Exported {} dated claims to {}", claims.len(), path);
+    } else {
+        print!("{}", out);
+    }
+    Ok(())
+}
+
+fn cmd_export_graph(
+    db: &dyn Storage,
+    format: &str,
+    video_id: Option<&str>,
+    moc_id: Option<i64>,
+    era: Option<&str>,
+    output: Option<&str>,
+) -> Result<()> {
+    let graph = db.claim_graph(video_id, moc_id, era)?;
+    if graph.claims.is_empty() {
+        println!("No claims match.");
+        return Ok(());
+    }
+
+    let rendered = match format {
+        "graphml" => engine::storage::graph::to_graphml(&graph),
+        _ => engine::storage::graph::to_dot(&graph),
+    };
+
+    if let Some(path) = output {
+        std::fs::write(path, &rendered)?;
+        println!(
+            "Exported {} claims, {} links and {} causal relations to {}",
+            graph.claims.len(), graph.links.len(), graph.causal_relations.len(), path
+        );
+    } else {
+        print!("{}", rendered);
+    }
+
+    Ok(())
+}
+
+fn cmd_report(db: &dyn Storage, by: &str) -> Result<()> {
     match by.to_lowercase().as_str() {
         "era" | "eras" => {
             let entries = db.report_by_era()?;
@@ -2656,19 +5202,18 @@ fn cmd_report(db: &Database, by: &str) -> Result<()> {
             println!("{:<25} {:>10}", "TOTAL (with era tag)", total);
         }
         "region" | "regions" => {
+            // Counts include subregions, so the total is taken separately to avoid double counting
             let entries = db.report_by_region()?;
-            println!("Videos by Region:\n");
+            println!("Videos by Region (including subregions):\n");
             println!("{:<25} {:>10}", "REGION", "VIDEOS");
             println!("{}", "-".repeat(37));
-            let mut total = 0;
             for entry in entries {
                 if entry.video_count > 0 {
                     println!("{:<25} {:>10}", entry.name, entry.video_count);
-                    total += entry.video_count;
                 }
             }
             println!("{}", "-".repeat(37));
-            println!("{:<25} {:>10}", "TOTAL (with region tag)", total);
+            println!("{:<25} {:>10}", "TOTAL (with region tag)", db.count_region_tagged_videos()?);
         }
         "topic" | "topics" => {
             let entries = db.report_by_topic()?;
@@ -2693,7 +5238,7 @@ fn cmd_report(db: &Database, by: &str) -> Result<()> {
     Ok(())
 }
 
-fn cmd_stats(db: &Database) -> Result<()> {
+fn cmd_stats(db: &dyn Storage) -> Result<()> {
     let (videos, transcripts, locations, notes, collections, searches, claims, chunks, embeddings) = db.get_summary_stats()?;
 
     println!("Database Statistics:\n");
@@ -2710,18 +5255,97 @@ fn cmd_stats(db: &Database) -> Result<()> {
     Ok(())
 }
 
+fn cmd_status(db: &dyn Storage, video_id: &str) -> Result<()> {
+    let status = match db.get_video_status(video_id)? {
+        Some(s) => s,
+        None => {
+            println!("Video not found: {}", video_id);
+            return Ok(());
+        }
+    };
+
+    let yes_no = |b: bool| if b { "yes" } else { "no" };
+
+    println!("{} ({})\n", status.video.title, status.video.id);
+
+    println!("Transcript:");
+    match &status.transcript_language {
+        Some(lang) => {
+            println!("  {:<22} {}", "Language", lang);
+            println!("  {:<22} {}", "Segments", status.transcript_segments);
+            println!("  {:<22} {}", "Characters", status.transcript_chars);
+            println!("  {:<22} {}", "Punctuated",
+                if status.transcript_punctuated { "yes" } else { "no (likely auto-captions)" });
+        }
+        None => println!("  not available"),
+    }
+
+    println!("\nDerived:");
+    println!("  {:<22} {}", "Chunks", status.chunks);
+    let layers = if status.layers.is_empty() {
+        "none".to_string()
+    } else {
+        status.layers.iter().map(|l| l.to_string()).collect::<Vec<_>>().join(", ")
+    };
+    println!("  {:<22} {}", "Summary layers", layers);
+
+    println!("\nEmbeddings:");
+    println!("  {:<22} {}", "Video", yes_no(status.video_embedded));
+    println!("  {:<22} {}/{}", "Chunks", status.chunk_embeddings, status.chunks);
+    println!("  {:<22} {}/{}", "Claims", status.claim_embeddings, status.claims);
+    println!("  {:<22} {}/{}", "Summaries", status.summary_embeddings, status.layers.len());
+
+    println!("\nClaims:");
+    println!("  {:<22} {}", "Claims", status.claims);
+    println!("  {:<22} {}", "Links", status.claim_links);
+    if status.claims > 0 {
+        println!("  {:<22} {:.1}", "Links per claim", status.claim_links as f64 / status.claims as f64);
+    }
+    println!("  {:<22} {}", "Unlinked claims", status.unlinked_claims);
+
+    println!("\nCitations:");
+    println!("  {:<22} {}", "Sources", status.sources_cited);
+    println!("  {:<22} {}", "Scholars", status.scholars_cited);
+    println!("  {:<22} {}", "Evidence", status.evidence);
+    println!("  {:<22} {} ({} pending)", "Quotes", status.quotes, status.pending_quotes);
+
+    println!("\nQueue:");
+    match &status.queue {
+        Some(q) => {
+            println!("  {:<22} {}", "Status", q.status.as_str());
+            println!("  {:<22} {}", "Priority", q.priority);
+            if let Some(err) = &q.error_message {
+                println!("  {:<22} {}", "Error", err);
+            }
+        }
+        None => println!("  not queued"),
+    }
+    for event in &status.queue_history {
+        println!("  {}  {:<12} {}",
+            event.created_at.format("%Y-%m-%d %H:%M"),
+            event.status.as_str(),
+            event.detail.as_deref().unwrap_or(""),
+        );
+    }
+
+    Ok(())
+}
+
 // Phase 6: Claim Extraction & Atomic Notes
 
+#[allow(clippy::too_many_arguments)]
 fn cmd_add_claim(
-    db: &Database,
+    db: &dyn Storage,
     video_id: &str,
     text: &str,
     quote: &str,
     category: &str,
     confidence: &str,
     timestamp: Option<f64>,
+    provenance: &ProvenanceOptions,
+    embedding: &EmbeddingOptions,
 ) -> Result<()> {
-    use engine::{ClaimCategory, Confidence};
+    use engine::{ClaimCategory, Confidence, LLMProvider};
 
     // Verify video exists
     if db.get_video(video_id)?.is_none() {
@@ -2747,22 +5371,255 @@ fn cmd_add_claim(
         }
     };
 
+    let provider = match provenance.llm_provider.as_deref() {
+        Some(p) => match LLMProvider::from_str(p) {
+            Some(p) => Some(p.as_str()),
+            None => {
+                println!("Invalid LLM provider: {}", p);
+                println!("Valid options: anthropic, openai, local");
+                return Ok(());
+            }
+        },
+        None => None,
+    };
+
     let claim = db.create_claim(text, video_id, timestamp, quote, cat, conf)?;
+    if !provenance.is_empty() {
+        db.set_claim_provenance(
+            claim.id,
+            provider,
+            provenance.llm_model.as_deref(),
+            provenance.prompt_version.as_deref(),
+            provenance.run_id.as_deref(),
+        )?;
+    }
     println!("Created claim #{}", claim.id);
     println!("  Text: {}", claim.text);
     println!("  Category: {}", claim.category.as_str());
     println!("  Confidence: {}", claim.confidence.as_str());
+    if !provenance.is_empty() {
+        println!("  Extracted by AI; needs review ('mark-reviewed {}' once checked)", claim.id);
+    }
+
+    let link_count = db.get_claim_link_count(claim.id)?;
+    if link_count < 2 {
+        println!("\nNote: This claim needs {} more connection(s) to meet the minimum of 2.", 2 - link_count);
+        println!("Use 'link {} <other-claim-id> --as <type>' to connect claims.", claim.id);
+    }
+
+    if auto_embed(db)? {
+        db.queue_embedding(engine::EmbeddingSource::Claim, &claim.id.to_string())?;
+    }
+    drain_embedding_queue(db, embedding)
+}
+
+fn cmd_mark_reviewed(db: &dyn Storage, ids: &[i64], run_id: Option<&str>, undo: bool) -> Result<()> {
+    let mut ids = ids.to_vec();
+    if let Some(run_id) = run_id {
+        let run = db.list_claim_ids_for_run(run_id)?;
+        if run.is_empty() {
+            println!("No claims from run: {}", run_id);
+            return Ok(());
+        }
+        ids.extend(run);
+    }
+    if ids.is_empty() {
+        println!("Give claim IDs or --run-id.");
+        return Ok(());
+    }
+    ids.sort();
+    ids.dedup();
+
+    let mut marked = 0;
+    for id in ids {
+        if db.mark_claim_reviewed(id, !undo)? {
+            marked += 1;
+        } else if db.get_claim(id)?.is_none() {
+            println!("Claim not found: {}", id);
+        } else {
+            println!("Claim #{} was added by hand; nothing to review", id);
+        }
+    }
+    if undo {
+        println!("Returned {} claim(s) to the needs-review list", marked);
+    } else {
+        println!("Marked {} claim(s) reviewed", marked);
+    }
+    Ok(())
+}
+
+/// Fields to change on a claim; None leaves a field as it is.
+#[derive(Default)]
+struct ClaimEdit {
+    text: Option<String>,
+    quote: Option<String>,
+    category: Option<String>,
+    confidence: Option<String>,
+    at: Option<Option<f64>>,
+}
+
+impl ClaimEdit {
+    fn is_empty(&self) -> bool {
+        self.text.is_none() && self.quote.is_none() && self.category.is_none() && self.confidence.is_none() && self.at.is_none()
+    }
+}
+
+fn cmd_edit_claim(db: &dyn Storage, id: i64, fields: ClaimEdit, embedding: &EmbeddingOptions) -> Result<()> {
+    use engine::{ClaimCategory, Confidence};
+
+    let claim = match db.get_claim(id)? {
+        Some(c) => c,
+        None => {
+            println!("Claim not found: {}", id);
+            return Ok(());
+        }
+    };
+
+    let fields = if fields.is_empty() {
+        match edit_claim_in_editor(&claim)? {
+            Some(f) => f,
+            None => return Ok(()),
+        }
+    } else {
+        fields
+    };
+
+    let category = match fields.category.as_deref() {
+        Some(s) => match ClaimCategory::from_str(s) {
+            Some(c) => Some(c),
+            None => {
+                println!("Invalid category: {}", s);
+                println!("Valid options: cyclical, causal, memetic, geopolitical, factual, phenomenological, metaphysical");
+                return Ok(());
+            }
+        },
+        None => None,
+    };
+    let confidence = match fields.confidence.as_deref() {
+        Some(s) => match Confidence::from_str(s) {
+            Some(c) => Some(c),
+            None => {
+                println!("Invalid confidence: {}", s);
+                println!("Valid options: high, medium, low");
+                return Ok(());
+            }
+        },
+        None => None,
+    };
+    let text = fields.text.as_deref().map(str::trim);
+    if text == Some("") {
+        println!("Claim text cannot be empty.");
+        return Ok(());
+    }
 
-    let link_count = db.get_claim_link_count(claim.id)?;
-    if link_count < 2 {
-        println!("\nNote: This claim needs {} more connection(s) to meet the minimum of 2.", 2 - link_count);
-        println!("Use 'link {} <other-claim-id> --as <type>' to connect claims.", claim.id);
+    db.update_claim(id, text, fields.quote.as_deref().map(str::trim), category, confidence, fields.at)?;
+    let updated = db.get_claim(id)?.expect("claim exists");
+    println!("Updated claim #{}", id);
+    if updated.text != claim.text {
+        println!("  Text: {} -> {}", claim.text, updated.text);
+    }
+    if updated.source_quote != claim.source_quote {
+        println!("  Quote: {}", updated.source_quote);
+    }
+    if updated.category != claim.category {
+        println!("  Category: {} -> {}", claim.category.as_str(), updated.category.as_str());
+    }
+    if updated.confidence != claim.confidence {
+        println!("  Confidence: {} -> {}", claim.confidence.as_str(), updated.confidence.as_str());
+    }
+    if updated.timestamp != claim.timestamp {
+        println!("  At: {}", updated.timestamp.map(|t| format!("{}s", t)).unwrap_or_else(|| "-".to_string()));
     }
 
+    // A stored vector describes the old wording, so queue the claim for re-embedding
+    if updated.text != claim.text {
+        let embedded = match db.default_embedding_model()? {
+            Some(model) => db.get_embedding(engine::EmbeddingSource::Claim, &id.to_string(), &model)?.is_some(),
+            None => false,
+        };
+        let auto = auto_embed(db)?;
+        if embedded || auto {
+            db.queue_embedding(engine::EmbeddingSource::Claim, &id.to_string())?;
+        }
+        if auto {
+            drain_embedding_queue(db, embedding)?;
+        }
+    }
     Ok(())
 }
 
-fn cmd_claims(db: &Database, video_id: &str) -> Result<()> {
+/// Open a claim as `field: value` lines in $EDITOR. None when nothing changed.
+fn edit_claim_in_editor(claim: &engine::Claim) -> Result<Option<ClaimEdit>> {
+    let path = std::env::temp_dir().join(format!("claim-{}.edit.txt", claim.id));
+    let original = format!(
+        "# Edit claim #{}; lines starting with # are ignored. Leave `at` empty to clear it.\n\
+         text: {}\nquote: {}\ncategory: {}\nconfidence: {}\nat: {}\n",
+        claim.id,
+        claim.text,
+        claim.source_quote.replace('\n', " "),
+        claim.category.as_str(),
+        claim.confidence.as_str(),
+        claim.timestamp.map(|t| t.to_string()).unwrap_or_default(),
+    );
+    std::fs::write(&path, &original)?;
+
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let status = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", editor))
+        .arg(&editor)
+        .arg(&path)
+        .status()?;
+
+    let content = std::fs::read_to_string(&path)?;
+    let _ = std::fs::remove_file(&path);
+    if !status.success() {
+        println!("Editor exited with an error; claim unchanged.");
+        return Ok(None);
+    }
+    if content == original {
+        println!("No changes.");
+        return Ok(None);
+    }
+
+    let mut fields = ClaimEdit::default();
+    for line in content.lines() {
+        if line.trim_start().starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            anyhow::bail!("Expected `field: value`, got: {}", line);
+        };
+        let value = value.trim().to_string();
+        match key.trim() {
+            "text" if value != claim.text => fields.text = Some(value),
+            "quote" if value != claim.source_quote.replace('\n', " ") => fields.quote = Some(value),
+            "category" if value != claim.category.as_str() => fields.category = Some(value),
+            "confidence" if value != claim.confidence.as_str() => fields.confidence = Some(value),
+            "at" => {
+                let at = if value.is_empty() {
+                    None
+                } else {
+                    Some(value.parse::<f64>().map_err(|_| anyhow::anyhow!("Invalid timestamp: {}", value))?)
+                };
+                if at != claim.timestamp {
+                    fields.at = Some(at);
+                }
+            }
+            "text" | "quote" | "category" | "confidence" => {}
+            other => anyhow::bail!("Unknown field: {}", other),
+        }
+    }
+    if fields.is_empty() {
+        println!("No changes.");
+        return Ok(None);
+    }
+    Ok(Some(fields))
+}
+
+fn cmd_claims(db: &dyn Storage, video_id: &str) -> Result<()> {
     let video = match db.get_video(video_id)? {
         Some(v) => v,
         None => {
@@ -2805,10 +5662,34 @@ fn cmd_claims(db: &Database, video_id: &str) -> Result<()> {
     Ok(())
 }
 
-fn cmd_all_claims(db: &Database, category: Option<&str>) -> Result<()> {
-    use engine::ClaimCategory;
+#[allow(clippy::too_many_arguments)]
+fn cmd_all_claims(
+    db: &dyn Storage,
+    category: Option<&str>,
+    status: Option<&str>,
+    topic: Option<&str>,
+    era: Option<&str>,
+    origin: Option<&str>,
+    needs_review: bool,
+    from: Option<i32>,
+    to: Option<i32>,
+    chrono: bool,
+) -> Result<()> {
+    use engine::{ClaimCategory, ClaimStatus};
+
+    let status = match status {
+        Some(s) => match ClaimStatus::from_str(s) {
+            Some(st) => Some(st),
+            None => {
+                println!("Invalid status: {}", s);
+                println!("Valid options: unverified, verified, disputed, retracted");
+                return Ok(());
+            }
+        },
+        None => None,
+    };
 
-    let claims = if let Some(cat_str) = category {
+    let mut claims = if let Some(cat_str) = category {
         match ClaimCategory::from_str(cat_str) {
             Some(cat) => db.list_claims_by_category(cat)?,
             None => {
@@ -2820,25 +5701,80 @@ fn cmd_all_claims(db: &Database, category: Option<&str>) -> Result<()> {
     } else {
         db.list_all_claims()?
     };
+    if let Some(st) = status {
+        claims.retain(|c| c.status == st);
+    }
+    if let Some(t) = topic {
+        let ids: std::collections::HashSet<i64> = db.list_claims_by_topic(t)?.into_iter().map(|c| c.id).collect();
+        claims.retain(|c| ids.contains(&c.id));
+    }
+    if let Some(e) = era {
+        let ids: std::collections::HashSet<i64> = db.list_claims_by_era(e)?.into_iter().map(|c| c.id).collect();
+        claims.retain(|c| ids.contains(&c.id));
+    }
+    if origin.is_some() || needs_review {
+        let provenance = db.list_claim_provenance()?;
+        match origin {
+            Some("ai") => claims.retain(|c| provenance.contains_key(&c.id)),
+            Some(_) => claims.retain(|c| !provenance.contains_key(&c.id)),
+            None => {}
+        }
+        if needs_review {
+            claims.retain(|c| provenance.get(&c.id).is_some_and(|p| p.needs_review()));
+        }
+    }
+    if from.is_some() || to.is_some() {
+        let ids: std::collections::HashSet<i64> = db.list_dated_claims(from, to)?.into_iter().map(|c| c.id).collect();
+        claims.retain(|c| ids.contains(&c.id));
+    }
+    let dated = chrono || from.is_some() || to.is_some();
+    if dated {
+        claims.sort_by_key(|c| (c.date_start.is_none(), c.date_start, c.date_end, c.id));
+    }
+
+    let mut filters = Vec::new();
+    if let Some(cat) = category {
+        filters.push(format!("category: {}", cat));
+    }
+    if let Some(st) = status {
+        filters.push(format!("status: {}", st.as_str()));
+    }
+    if let Some(t) = topic {
+        filters.push(format!("topic: {}", t));
+    }
+    if let Some(e) = era {
+        filters.push(format!("era: {}", e));
+    }
+    if let Some(o) = origin {
+        filters.push(format!("origin: {}", o));
+    }
+    if needs_review {
+        filters.push("needs review".to_string());
+    }
+    if let Some(years) = format_year_range(from, to) {
+        filters.push(format!("years: {}", years));
+    }
 
     if claims.is_empty() {
-        if category.is_some() {
-            println!("No claims found for category: {}", category.unwrap());
-        } else {
+        if filters.is_empty() {
             println!("No claims in database.");
+        } else {
+            println!("No claims found for {}", filters.join(", "));
         }
         return Ok(());
     }
 
-    let header = if let Some(cat) = category {
-        format!("Claims (category: {})", cat)
-    } else {
+    let header = if filters.is_empty() {
         "All Claims".to_string()
+    } else {
+        format!("Claims ({})", filters.join(", "))
     };
 
+    // Dated listings show the years in place of the video
+    let (second, width) = if dated { ("DATES", 21) } else { ("VIDEO", 12) };
     println!("{}\n", header);
-    println!("{:<6} {:<12} {:<12} {}", "ID", "VIDEO", "CATEGORY", "TEXT");
-    println!("{}", "-".repeat(80));
+    println!("{:<6} {:<width$} {:<12} {:<11} TEXT", "ID", second, "CATEGORY", "STATUS");
+    println!("{}", "-".repeat(80 + width));
 
     for claim in claims {
         let text_preview = if claim.text.len() > 40 {
@@ -2851,11 +5787,17 @@ fn cmd_all_claims(db: &Database, category: Option<&str>) -> Result<()> {
         } else {
             claim.video_id.clone()
         };
+        let second = if dated {
+            format_claim_dates(claim.date_start, claim.date_end).unwrap_or_else(|| "-".to_string())
+        } else {
+            video_id_short
+        };
         println!(
-            "{:<6} {:<12} {:<12} {}",
+            "{:<6} {:<width$} {:<12} {:<11} {}",
             claim.id,
-            video_id_short,
+            second,
             claim.category.as_str(),
+            claim.status.as_str(),
             text_preview
         );
     }
@@ -2863,7 +5805,107 @@ fn cmd_all_claims(db: &Database, category: Option<&str>) -> Result<()> {
     Ok(())
 }
 
-fn cmd_claim(db: &Database, id: i64) -> Result<()> {
+fn cmd_tag_claim(db: &dyn Storage, id: i64, topic: Option<&str>, era: Option<&str>, remove: bool) -> Result<()> {
+    if db.get_claim(id)?.is_none() {
+        println!("Claim not found: {}", id);
+        return Ok(());
+    }
+
+    if topic.is_none() && era.is_none() {
+        let topics = db.get_claim_topics(id)?;
+        let eras = db.get_claim_eras(id)?;
+        if topics.is_empty() && eras.is_empty() {
+            println!("No tags on claim #{}. Use --topic and/or --era to add some.", id);
+            return Ok(());
+        }
+        for topic in &topics {
+            println!("  topic: {}", topic.name);
+        }
+        for era in &eras {
+            println!("  era:   {}", era.name);
+        }
+        return Ok(());
+    }
+
+    if let Some(topic_name) = topic {
+        if remove {
+            match db.get_topic_by_name(topic_name)? {
+                Some(t) if db.untag_claim_topic(id, t.id)? => println!("Removed topic: {}", t.name),
+                _ => println!("Claim #{} is not tagged with topic: {}", id, topic_name),
+            }
+        } else {
+            let t = db.get_or_create_topic(topic_name)?;
+            db.tag_claim_topic(id, t.id)?;
+            println!("Tagged with topic: {}", t.name);
+        }
+    }
+
+    if let Some(era_name) = era {
+        match db.get_era_by_name(era_name)? {
+            Some(e) if remove => {
+                if db.untag_claim_era(id, e.id)? {
+                    println!("Removed era: {}", e.name);
+                } else {
+                    println!("Claim #{} is not tagged with era: {}", id, e.name);
+                }
+            }
+            Some(e) => {
+                db.tag_claim_era(id, e.id)?;
+                println!("Tagged with era: {}", e.name);
+            }
+            None => println!("Era not found: {}. Use 'eras' command to see available eras.", era_name),
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_set_claim_status(db: &dyn Storage, id: i64, status: &str) -> Result<()> {
+    let Some(claim) = db.get_claim(id)? else {
+        println!("Claim not found: {}", id);
+        return Ok(());
+    };
+    let status = engine::ClaimStatus::from_str(status).expect("validated by clap");
+    if claim.status == status {
+        println!("Claim #{} is already {}", id, status.as_str());
+        return Ok(());
+    }
+    db.set_claim_status(id, status)?;
+    println!("Claim #{}: {} -> {}", id, claim.status.as_str(), status.as_str());
+    Ok(())
+}
+
+fn cmd_date_claim(db: &dyn Storage, id: i64, from: Option<i32>, to: Option<i32>, clear: bool) -> Result<()> {
+    if db.get_claim(id)?.is_none() {
+        println!("Claim not found: {}", id);
+        return Ok(());
+    }
+    if clear {
+        db.set_claim_dates(id, None, None)?;
+        println!("Cleared dates on claim #{}", id);
+        return Ok(());
+    }
+
+    let start = from.expect("required by clap unless --clear");
+    let end = to.unwrap_or(start);
+    if end < start {
+        println!("--to ({}) is before --from ({})", end, start);
+        return Ok(());
+    }
+    db.set_claim_dates(id, Some(start), Some(end))?;
+    println!("Claim #{}: {}", id, format_claim_dates(Some(start), Some(end)).unwrap_or_default());
+    Ok(())
+}
+
+/// Render a claim's dates: a single year when start and end match, else a range.
+fn format_claim_dates(start: Option<i32>, end: Option<i32>) -> Option<String> {
+    match (start, end) {
+        (Some(s), Some(e)) if s == e => format_year_range(Some(s), None).map(|y| y.trim_end_matches(" –").to_string()),
+        _ => format_year_range(start, end),
+    }
+}
+
+fn cmd_claim(db: &dyn Storage, id: i64) -> Result<()> {
     let claim_with_links = match db.get_claim_with_links(id)? {
         Some(c) => c,
         None => {
@@ -2885,7 +5927,41 @@ fn cmd_claim(db: &Database, id: i64) -> Result<()> {
     }
     println!("Category: {}", claim.category.as_str());
     println!("Confidence: {}", claim.confidence.as_str());
+    println!("Status: {}", claim.status.as_str());
+    if let Some(dates) = format_claim_dates(claim.date_start, claim.date_end) {
+        println!("Dates: {}", dates);
+    }
+    let topics = db.get_claim_topics(claim.id)?;
+    if !topics.is_empty() {
+        println!("Topics: {}", topics.iter().map(|t| t.name.as_str()).collect::<Vec<_>>().join(", "));
+    }
+    let eras = db.get_claim_eras(claim.id)?;
+    if !eras.is_empty() {
+        println!("Eras: {}", eras.iter().map(|e| e.name.as_str()).collect::<Vec<_>>().join(", "));
+    }
+    let citations = db.claim_citations(claim.id)?;
+    if !citations.is_empty() {
+        println!("Cites: {}", citations.join("; "));
+    }
+    if let Some(p) = db.get_claim_provenance(claim.id)? {
+        let mut by = [p.provider.as_deref(), p.model.as_deref()].into_iter().flatten().collect::<Vec<_>>().join("/");
+        if by.is_empty() {
+            by = "AI".to_string();
+        }
+        if let Some(v) = &p.prompt_version {
+            by.push_str(&format!(", prompt {}", v));
+        }
+        if let Some(r) = &p.run_id {
+            by.push_str(&format!(", run {}", r));
+        }
+        println!("Extracted by: {}", by);
+        match p.reviewed_at {
+            Some(t) => println!("Reviewed: {}", t.format("%Y-%m-%d %H:%M")),
+            None => println!("Reviewed: no (needs review)"),
+        }
+    }
     println!("Created: {}", claim.created_at.format("%Y-%m-%d %H:%M"));
+    print_attachments(&db.get_claim_attachments(claim.id)?);
 
     let total_links = claim_with_links.outgoing_links.len() + claim_with_links.incoming_links.len();
     println!("\nConnections: {} total", total_links);
@@ -2921,7 +5997,7 @@ fn cmd_claim(db: &Database, id: i64) -> Result<()> {
     Ok(())
 }
 
-fn cmd_link(db: &Database, source: i64, target: i64, link_type: &str) -> Result<()> {
+fn cmd_link(db: &dyn Storage, source: i64, target: i64, link_type: &str) -> Result<()> {
     use engine::LinkType;
 
     // Verify both claims exist
@@ -2954,7 +6030,7 @@ fn cmd_link(db: &Database, source: i64, target: i64, link_type: &str) -> Result<
     Ok(())
 }
 
-fn cmd_unlink(db: &Database, source: i64, target: i64) -> Result<()> {
+fn cmd_unlink(db: &dyn Storage, source: i64, target: i64) -> Result<()> {
     if db.delete_claim_link(source, target)? {
         println!("Removed link: #{} -> #{}", source, target);
     } else {
@@ -2963,39 +6039,290 @@ fn cmd_unlink(db: &Database, source: i64, target: i64) -> Result<()> {
     Ok(())
 }
 
-fn cmd_unlinked(db: &Database) -> Result<()> {
-    let claims = db.get_unlinked_claims()?;
-
-    if claims.is_empty() {
-        println!("All claims have at least 2 connections.");
+fn cmd_claim_path(db: &dyn Storage, from: i64, to: i64, max_depth: usize) -> Result<()> {
+    for id in [from, to] {
+        if db.get_claim(id)?.is_none() {
+            println!("Claim not found: {}", id);
+            return Ok(());
+        }
+    }
+
+    let Some(path) = db.claim_path(from, to, max_depth)? else {
+        println!("No path from #{} to #{} within {} hops.", from, to, max_depth);
+        return Ok(());
+    };
+
+    println!("Path from #{} to #{} ({} hops):\n", from, to, path.len() - 1);
+    for step in &path {
+        if let Some(via) = &step.via {
+            if step.forward {
+                println!("    --{}-->", via);
+            } else {
+                println!("    <--{}--", via);
+            }
+        }
+        println!("  #{:<5} {}", step.claim.id, truncate(&step.claim.text, 70));
+    }
+    Ok(())
+}
+
+fn cmd_claim_neighborhood(db: &dyn Storage, id: i64, depth: usize) -> Result<()> {
+    let Some(claim) = db.get_claim(id)? else {
+        println!("Claim not found: {}", id);
+        return Ok(());
+    };
+
+    let neighbors = db.claim_neighborhood(id, depth)?;
+    println!("#{} {}\n", claim.id, truncate(&claim.text, 70));
+    if neighbors.is_empty() {
+        println!("No connected claims within {} hops.", depth);
+        return Ok(());
+    }
+
+    println!("{:<5} {:<6} {:<12} TEXT", "HOPS", "ID", "CATEGORY");
+    println!("{}", "-".repeat(80));
+    for (neighbor, distance) in &neighbors {
+        println!(
+            "{:<5} {:<6} {:<12} {}",
+            distance,
+            neighbor.id,
+            neighbor.category.as_str(),
+            truncate(&neighbor.text, 55)
+        );
+    }
+    println!("\n{} claim(s) within {} hops", neighbors.len(), depth);
+    Ok(())
+}
+
+fn cmd_graph_metrics(db: &dyn Storage, top: usize) -> Result<()> {
+    let metrics = db.compute_claim_metrics()?;
+    if metrics.is_empty() {
+        println!("No claims yet.");
+        return Ok(());
+    }
+
+    let edges: usize = metrics.iter().map(|m| m.degree).sum::<usize>() / 2;
+    let mut sizes: Vec<usize> = Vec::new();
+    for m in &metrics {
+        if sizes.len() <= m.component {
+            sizes.resize(m.component + 1, 0);
+        }
+        sizes[m.component] += 1;
+    }
+    let isolated = metrics.iter().filter(|m| m.degree == 0).count();
+
+    println!("Claims:      {}", metrics.len());
+    println!("Connections: {}", edges);
+    println!("Components:  {} (largest {}, {} isolated claim(s))", sizes.len(), sizes[0], isolated);
+
+    let mut hubs: Vec<&engine::ClaimMetrics> = metrics.iter().filter(|m| m.degree > 0).collect();
+    hubs.sort_by(|a, b| b.degree.cmp(&a.degree).then(b.betweenness.total_cmp(&a.betweenness)));
+    if !hubs.is_empty() {
+        println!("\nHub claims (most connected):");
+        println!("{:<6} {:<7} {:<8} TEXT", "ID", "DEGREE", "BETWEEN");
+        println!("{}", "-".repeat(80));
+        for m in hubs.iter().take(top) {
+            let text = db.get_claim(m.claim_id)?.map(|c| c.text).unwrap_or_default();
+            println!("{:<6} {:<7} {:<8.3} {}", m.claim_id, m.degree, m.betweenness, truncate(&text, 55));
+        }
+    }
+
+    let bridges: Vec<&engine::ClaimMetrics> = metrics.iter().filter(|m| m.betweenness > 0.0).take(top).collect();
+    if !bridges.is_empty() {
+        println!("\nBridging claims (highest betweenness):");
+        println!("{:<6} {:<7} {:<8} TEXT", "ID", "DEGREE", "BETWEEN");
+        println!("{}", "-".repeat(80));
+        for m in bridges {
+            let text = db.get_claim(m.claim_id)?.map(|c| c.text).unwrap_or_default();
+            println!("{:<6} {:<7} {:<8.3} {}", m.claim_id, m.degree, m.betweenness, truncate(&text, 55));
+        }
+    }
+
+    println!("\nStored for the web graph and 'review --bridges'.");
+    Ok(())
+}
+
+fn cmd_unlinked(db: &dyn Storage) -> Result<()> {
+    let claims = db.get_unlinked_claims()?;
+
+    if claims.is_empty() {
+        println!("All claims have at least 2 connections.");
+        return Ok(());
+    }
+
+    println!("Claims needing more connections (< 2 links):\n");
+    println!("{:<6} {:<12} {:<8} {}", "ID", "VIDEO", "LINKS", "TEXT");
+    println!("{}", "-".repeat(70));
+
+    for claim in claims {
+        let link_count = db.get_claim_link_count(claim.id)?;
+        let text_preview = if claim.text.len() > 35 {
+            format!("{}...", &claim.text[..32])
+        } else {
+            claim.text.clone()
+        };
+        let video_short = if claim.video_id.len() > 10 {
+            format!("{}...", &claim.video_id[..7])
+        } else {
+            claim.video_id.clone()
+        };
+        println!("{:<6} {:<12} {:<8} {}", claim.id, video_short, link_count, text_preview);
+    }
+
+    println!("\nUse 'link <source> <target> --as <type>' to connect claims.");
+
+    Ok(())
+}
+
+fn cmd_suggest_links(db: &dyn Storage, claim_id: Option<i64>, all: bool, top: usize, min_score: f32, list: bool) -> Result<()> {
+    use engine::LinkType;
+
+    let claims = match claim_id {
+        Some(id) if !all => match db.get_claim(id)? {
+            Some(claim) => vec![claim],
+            None => {
+                println!("Claim not found: {}", id);
+                return Ok(());
+            }
+        },
+        _ => db.get_orphan_claims()?,
+    };
+    if db.get_embedding_stats()?.claim_embeddings == 0 {
+        println!("No claims are embedded yet; run 'embed-all --source claim' first.");
+        return Ok(());
+    }
+    if !list {
+        println!("y links the suggestion as related, Enter skips, q stops.");
+    }
+
+    let mut linked = 0;
+    'claims: for claim in &claims {
+        let suggestions: Vec<(engine::Claim, f32)> = db
+            .suggest_claim_links(claim.id, top)?
+            .into_iter()
+            .filter(|(_, score)| *score >= min_score)
+            .collect();
+        if suggestions.is_empty() {
+            continue;
+        }
+
+        println!("\n[{}] {}", claim.id, truncate(&claim.text, 80));
+        for (other, score) in &suggestions {
+            println!("  {:.3}  [{}] {}", score, other.id, truncate(&other.text, 70));
+            if list {
+                continue;
+            }
+            let Some(answer) = read_answer()? else { break 'claims };
+            if answer.eq_ignore_ascii_case("y") {
+                db.create_claim_link(claim.id, other.id, LinkType::Related)?;
+                linked += 1;
+            }
+        }
+    }
+
+    if !list {
+        println!("\nCreated {} link(s).", linked);
+    }
+    Ok(())
+}
+
+fn cmd_dedupe_claims(db: &dyn Storage, threshold: f32, limit: usize, merge: bool) -> Result<()> {
+    let pairs = db.find_duplicate_claims(threshold)?;
+    if pairs.is_empty() {
+        let stats = db.get_embedding_stats()?;
+        println!("No claim pairs with similarity >= {:.2}.", threshold);
+        if stats.claim_embeddings == 0 {
+            println!("No claims are embedded yet; run 'embed-all --source claim' first.");
+        }
+        return Ok(());
+    }
+
+    println!("{} candidate pair(s){}\n", pairs.len(), if pairs.len() > limit { format!(", showing {}", limit) } else { String::new() });
+    if merge {
+        println!("For each pair: 1 keeps the first claim, 2 keeps the second, Enter skips, q stops.\n");
+    }
+
+    // Claims merged away earlier in this run no longer exist
+    let mut merged = std::collections::HashSet::new();
+    let mut merge_count = 0;
+    for pair in pairs.iter().take(limit) {
+        if merged.contains(&pair.claim.id) || merged.contains(&pair.duplicate.id) {
+            continue;
+        }
+        println!("{:.3}  [{}] {}", pair.similarity, pair.claim.id, truncate(&pair.claim.text, 80));
+        println!("       [{}] {}", pair.duplicate.id, truncate(&pair.duplicate.text, 80));
+        if !merge {
+            println!();
+            continue;
+        }
+
+        let Some(answer) = read_answer()? else { break };
+        let (keep, duplicate) = match answer.as_str() {
+            "1" => (pair.claim.id, pair.duplicate.id),
+            "2" => (pair.duplicate.id, pair.claim.id),
+            _ => {
+                println!();
+                continue;
+            }
+        };
+        let moved = db.merge_claims(keep, duplicate)?;
+        merged.insert(duplicate);
+        merge_count += 1;
+        println!("Merged #{} into #{} ({} references moved)\n", duplicate, keep, moved);
+    }
+
+    if merge {
+        println!("Merged {} pair(s).", merge_count);
+    } else {
+        println!("Re-run with --merge to merge them.");
+    }
+    Ok(())
+}
+
+fn cmd_contradictions(db: &dyn Storage, by: &str) -> Result<()> {
+    let groups = db.list_contradictions(by)?;
+    if groups.is_empty() {
+        println!("No contradictions recorded. Use 'link <a> <b> --as contradicts' to record one.");
+        return Ok(());
+    }
+
+    for group in &groups {
+        println!("== {} ({}) ==\n", group.name, group.contradictions.len());
+        for c in &group.contradictions {
+            for (claim, title) in [(&c.claim, &c.claim_video_title), (&c.other, &c.other_video_title)] {
+                println!(
+                    "  #{:<5} [{:<6}] {}",
+                    claim.id,
+                    claim.confidence.as_str(),
+                    truncate(&claim.text, 70)
+                );
+                println!("          from: {} ({})", truncate(title.as_deref().unwrap_or("?"), 50), claim.video_id);
+            }
+            println!();
+        }
+    }
+    Ok(())
+}
+
+fn cmd_merge_claims(db: &dyn Storage, keep: i64, duplicate: i64) -> Result<()> {
+    let (Some(kept), Some(dup)) = (db.get_claim(keep)?, db.get_claim(duplicate)?) else {
+        let missing = if db.get_claim(keep)?.is_none() { keep } else { duplicate };
+        println!("Claim not found: {}", missing);
+        return Ok(());
+    };
+    if keep == duplicate {
+        println!("Cannot merge claim #{} into itself", keep);
         return Ok(());
     }
 
-    println!("Claims needing more connections (< 2 links):\n");
-    println!("{:<6} {:<12} {:<8} {}", "ID", "VIDEO", "LINKS", "TEXT");
-    println!("{}", "-".repeat(70));
-
-    for claim in claims {
-        let link_count = db.get_claim_link_count(claim.id)?;
-        let text_preview = if claim.text.len() > 35 {
-            format!("{}...", &claim.text[..32])
-        } else {
-            claim.text.clone()
-        };
-        let video_short = if claim.video_id.len() > 10 {
-            format!("{}...", &claim.video_id[..7])
-        } else {
-            claim.video_id.clone()
-        };
-        println!("{:<6} {:<12} {:<8} {}", claim.id, video_short, link_count, text_preview);
-    }
-
-    println!("\nUse 'link <source> <target> --as <type>' to connect claims.");
-
+    let moved = db.merge_claims(keep, duplicate)?;
+    println!("Merged #{} into #{} ({} references moved)", duplicate, keep, moved);
+    println!("  Kept:    {}", truncate(&kept.text, 70));
+    println!("  Deleted: {}", truncate(&dup.text, 70));
     Ok(())
 }
 
-fn cmd_delete_claim(db: &Database, id: i64) -> Result<()> {
+fn cmd_delete_claim(db: &dyn Storage, id: i64) -> Result<()> {
     if db.delete_claim(id)? {
         println!("Deleted claim #{}", id);
     } else {
@@ -3004,89 +6331,107 @@ fn cmd_delete_claim(db: &Database, id: i64) -> Result<()> {
     Ok(())
 }
 
-fn cmd_chunk(db: &Database, id: &str, target_tokens: i32, overlap_percent: i32) -> Result<()> {
+// Chunking used by `chunk` by default and by auto-embed after a fetch
+const DEFAULT_CHUNK_TOKENS: i32 = 2000;
+const DEFAULT_CHUNK_OVERLAP: i32 = 15;
+
+/// Split a transcript into overlapping chunks of roughly `target_tokens` tokens.
+fn chunk_transcript(transcript: &engine::Transcript, target_tokens: i32, overlap_percent: i32) -> Vec<engine::TranscriptChunk> {
     use engine::TranscriptChunk;
 
-    let process_video = |video_id: &str| -> Result<usize> {
-        let transcript = match db.get_transcript(video_id)? {
-            Some(t) => t,
-            None => {
-                println!("  No transcript for: {}", video_id);
-                return Ok(0);
-            }
-        };
+    // Simple token estimation: ~4 chars per token (rough approximation)
+    let chars_per_token: usize = 4;
+    let target_chars = (target_tokens as usize) * chars_per_token;
+    let overlap_chars = (target_chars * overlap_percent as usize) / 100;
 
-        // Simple token estimation: ~4 chars per token (rough approximation)
-        let chars_per_token: usize = 4;
-        let target_chars = (target_tokens as usize) * chars_per_token;
-        let overlap_chars = (target_chars * overlap_percent as usize) / 100;
-
-        let mut chunks = Vec::new();
-        let mut current_chunk_text = String::new();
-        let mut current_chunk_start = 0.0_f64;
-        let mut current_chunk_end = 0.0_f64;
-        let mut chunk_index = 0;
-        let mut overlap_text = String::new();
-
-        for segment in &transcript.segments {
-            // Add overlap from previous chunk if starting new chunk
-            if current_chunk_text.is_empty() && !overlap_text.is_empty() {
-                current_chunk_text = overlap_text.clone();
-                current_chunk_start = segment.start_time;
-            }
-
-            if current_chunk_text.is_empty() {
-                current_chunk_start = segment.start_time;
-            }
-
-            current_chunk_text.push_str(&segment.text);
-            current_chunk_text.push(' ');
-            current_chunk_end = segment.start_time + segment.duration;
-
-            // Check if we've reached target size
-            if current_chunk_text.len() >= target_chars {
-                let token_count = (current_chunk_text.len() / chars_per_token) as i32;
-
-                chunks.push(TranscriptChunk {
-                    id: 0, // Will be set by database
-                    video_id: video_id.to_string(),
-                    chunk_index,
-                    start_time: current_chunk_start,
-                    end_time: current_chunk_end,
-                    text: current_chunk_text.trim().to_string(),
-                    token_count,
-                    overlap_with_previous: chunk_index > 0,
-                });
-
-                // Save overlap for next chunk
-                if current_chunk_text.len() > overlap_chars {
-                    overlap_text = current_chunk_text[current_chunk_text.len() - overlap_chars..].to_string();
-                } else {
-                    overlap_text = current_chunk_text.clone();
-                }
+    let mut chunks = Vec::new();
+    let mut current_chunk_text = String::new();
+    let mut current_chunk_start = 0.0_f64;
+    let mut current_chunk_end = 0.0_f64;
+    let mut chunk_index = 0;
+    let mut overlap_text = String::new();
 
-                current_chunk_text = String::new();
-                chunk_index += 1;
-            }
+    for segment in &transcript.segments {
+        // Add overlap from previous chunk if starting new chunk
+        if current_chunk_text.is_empty() && !overlap_text.is_empty() {
+            current_chunk_text = overlap_text.clone();
+            current_chunk_start = segment.start_time;
         }
 
-        // Don't forget the last chunk
-        if !current_chunk_text.is_empty() {
+        if current_chunk_text.is_empty() {
+            current_chunk_start = segment.start_time;
+        }
+
+        current_chunk_text.push_str(&segment.text);
+        current_chunk_text.push(' ');
+        current_chunk_end = segment.start_time + segment.duration;
+
+        // Check if we've reached target size
+        if current_chunk_text.len() >= target_chars {
             let token_count = (current_chunk_text.len() / chars_per_token) as i32;
+
             chunks.push(TranscriptChunk {
-                id: 0,
-                video_id: video_id.to_string(),
+                id: 0, // Will be set by database
+                video_id: transcript.video_id.clone(),
                 chunk_index,
                 start_time: current_chunk_start,
                 end_time: current_chunk_end,
                 text: current_chunk_text.trim().to_string(),
                 token_count,
                 overlap_with_previous: chunk_index > 0,
+                chapter: None, // Resolved from the chapters table on read
             });
+
+            // Save overlap for next chunk
+            if current_chunk_text.len() > overlap_chars {
+                overlap_text = current_chunk_text[current_chunk_text.len() - overlap_chars..].to_string();
+            } else {
+                overlap_text = current_chunk_text.clone();
+            }
+
+            current_chunk_text = String::new();
+            chunk_index += 1;
         }
+    }
+
+    // Don't forget the last chunk
+    if !current_chunk_text.is_empty() {
+        let token_count = (current_chunk_text.len() / chars_per_token) as i32;
+        chunks.push(TranscriptChunk {
+            id: 0,
+            video_id: transcript.video_id.clone(),
+            chunk_index,
+            start_time: current_chunk_start,
+            end_time: current_chunk_end,
+            text: current_chunk_text.trim().to_string(),
+            token_count,
+            overlap_with_previous: chunk_index > 0,
+            chapter: None,
+        });
+    }
+
+    chunks
+}
+
+fn cmd_chunk(db: &dyn Storage, id: &str, target_tokens: i32, overlap_percent: i32, embedding: &EmbeddingOptions) -> Result<()> {
+    let auto = auto_embed(db)?;
+    let process_video = |video_id: &str| -> Result<usize> {
+        let transcript = match db.get_transcript(video_id)? {
+            Some(t) => t,
+            None => {
+                println!("  No transcript for: {}", video_id);
+                return Ok(0);
+            }
+        };
 
+        let chunks = chunk_transcript(&transcript, target_tokens, overlap_percent);
         let chunk_count = chunks.len();
         db.save_transcript_chunks(video_id, &chunks)?;
+        if auto {
+            for chunk in &chunks {
+                db.queue_embedding(engine::EmbeddingSource::Chunk, &format!("{}:{}", video_id, chunk.chunk_index))?;
+            }
+        }
 
         Ok(chunk_count)
     };
@@ -3124,10 +6469,10 @@ fn cmd_chunk(db: &Database, id: &str, target_tokens: i32, overlap_percent: i32)
         println!("Created {} chunks.", count);
     }
 
-    Ok(())
+    drain_embedding_queue(db, embedding)
 }
 
-fn cmd_chunks(db: &Database, video_id: &str) -> Result<()> {
+fn cmd_chunks(db: &dyn Storage, video_id: &str) -> Result<()> {
     let video = match db.get_video(video_id)? {
         Some(v) => v,
         None => {
@@ -3145,8 +6490,14 @@ fn cmd_chunks(db: &Database, video_id: &str) -> Result<()> {
     }
 
     println!("Chunks for: {}\n", video.title);
-    println!("{:<6} {:>8} {:>8} {:>8} {}", "INDEX", "START", "END", "TOKENS", "PREVIEW");
-    println!("{}", "-".repeat(75));
+    let has_chapters = chunks.iter().any(|c| c.chapter.is_some());
+    if has_chapters {
+        println!("{:<6} {:>8} {:>8} {:>8} {:<22} PREVIEW", "INDEX", "START", "END", "TOKENS", "CHAPTER");
+        println!("{}", "-".repeat(98));
+    } else {
+        println!("{:<6} {:>8} {:>8} {:>8} {}", "INDEX", "START", "END", "TOKENS", "PREVIEW");
+        println!("{}", "-".repeat(75));
+    }
 
     for chunk in chunks {
         let start_mins = (chunk.start_time / 60.0) as u32;
@@ -3160,20 +6511,32 @@ fn cmd_chunks(db: &Database, video_id: &str) -> Result<()> {
             chunk.text.clone()
         };
 
-        println!(
-            "{:<6} {:02}:{:02} {:02}:{:02} {:>8} {}",
-            chunk.chunk_index,
-            start_mins, start_secs,
-            end_mins, end_secs,
-            chunk.token_count,
-            preview
-        );
+        if has_chapters {
+            println!(
+                "{:<6} {:02}:{:02} {:02}:{:02} {:>8} {:<22} {}",
+                chunk.chunk_index,
+                start_mins, start_secs,
+                end_mins, end_secs,
+                chunk.token_count,
+                truncate(chunk.chapter.as_deref().unwrap_or("-"), 20),
+                preview
+            );
+        } else {
+            println!(
+                "{:<6} {:02}:{:02} {:02}:{:02} {:>8} {}",
+                chunk.chunk_index,
+                start_mins, start_secs,
+                end_mins, end_secs,
+                chunk.token_count,
+                preview
+            );
+        }
     }
 
     Ok(())
 }
 
-fn cmd_summarize(db: &Database, video_id: &str, layer: u8, content: Option<&str>) -> Result<()> {
+fn cmd_summarize(db: &dyn Storage, video_id: &str, layer: u8, content: Option<&str>) -> Result<()> {
     if layer < 2 || layer > 4 {
         println!("Layer must be 2, 3, or 4.");
         println!("  Layer 2: Key passages (bolded)");
@@ -3212,7 +6575,79 @@ fn cmd_summarize(db: &Database, video_id: &str, layer: u8, content: Option<&str>
     Ok(())
 }
 
-fn cmd_layers(db: &Database, video_id: &str) -> Result<()> {
+fn cmd_summarize_auto(db: &dyn Storage, video_id: &str, only: Option<u8>, force: bool, llm: &LlmOptions) -> Result<()> {
+    use engine::ai::summarization;
+
+    if only.is_some_and(|layer| !(2..=4).contains(&layer)) {
+        println!("Layer must be 2, 3, or 4.");
+        return Ok(());
+    }
+    let video = match db.get_video(video_id)? {
+        Some(v) => v,
+        None => {
+            println!("Video not found: {}", video_id);
+            return Ok(());
+        }
+    };
+
+    let config = llm.config(db)?;
+    let chat = tracked_chat(db, &config, "summarize")?;
+    println!("Summarizing '{}' with {} ({})\n", video.title, config.model, config.provider.as_str());
+
+    // Each layer is built from the one below it
+    let mut previous = String::new();
+    for layer in 2..=4u8 {
+        if only.is_some_and(|o| layer > o) {
+            break;
+        }
+        let name = match layer {
+            2 => "Key Passages",
+            3 => "Best of Best",
+            _ => "Executive Summary",
+        };
+        let stored = db.get_transcript_layer(video_id, layer)?;
+        if let Some(stored) = stored.filter(|_| !force && only != Some(layer)) {
+            println!("Layer {} ({}): kept existing ({} chars)", layer, name, stored.content.len());
+            previous = stored.content;
+            continue;
+        }
+
+        println!("Layer {} ({})...", layer, name);
+        let content = match layer {
+            2 => {
+                let chunks = chunks_for_llm(db, video_id, DEFAULT_CHUNK_TOKENS)?;
+                let mut passages = Vec::with_capacity(chunks.len());
+                for (i, chunk) in chunks.iter().enumerate() {
+                    println!("  Chunk {}/{}", i + 1, chunks.len());
+                    let response = chat
+                        .complete(summarization::SYSTEM_PROMPT, &summarization::passages_prompt(&video.title, chunk))
+                        .with_context(|| format!("chunk {}", i + 1))?;
+                    passages.push(response.trim().to_string());
+                }
+                passages.join("\n\n")
+            }
+            3 => summarization::condense(chat.as_ref(), &previous, &|text| {
+                summarization::highlights_prompt(&video.title, text)
+            })?,
+            _ => summarization::condense(chat.as_ref(), &previous, &|text| {
+                summarization::summary_prompt(&video.title, text)
+            })?,
+        };
+        if content.trim().is_empty() {
+            anyhow::bail!("The model returned an empty layer {}", layer);
+        }
+        db.save_transcript_layer(video_id, layer, content.trim())?;
+        println!("  Saved ({} chars)", content.trim().len());
+        previous = content;
+    }
+
+    if let Some(summary) = db.get_transcript_layer(video_id, 4)?.filter(|_| only.is_none_or(|o| o == 4)) {
+        println!("\n{}", summary.content);
+    }
+    Ok(())
+}
+
+fn cmd_layers(db: &dyn Storage, video_id: &str) -> Result<()> {
     let video = match db.get_video(video_id)? {
         Some(v) => v,
         None => {
@@ -3253,7 +6688,7 @@ fn cmd_layers(db: &Database, video_id: &str) -> Result<()> {
     Ok(())
 }
 
-fn cmd_claim_stats(db: &Database) -> Result<()> {
+fn cmd_claim_stats(db: &dyn Storage) -> Result<()> {
     let (total, linked, links) = db.get_claim_stats()?;
     let unlinked = total - linked;
 
@@ -3289,7 +6724,7 @@ fn cmd_claim_stats(db: &Database) -> Result<()> {
 
 // Phase 7: Semantic Search & Embeddings
 
-fn cmd_embed(db: &Database, source: &str, id: &str, vector: &str, model: &str) -> Result<()> {
+fn cmd_embed(db: &dyn Storage, source: &str, id: &str, vector: &str, model: &str) -> Result<()> {
     use engine::EmbeddingSource;
 
     let source_type = match EmbeddingSource::from_str(source) {
@@ -3322,7 +6757,7 @@ fn cmd_embed(db: &Database, source: &str, id: &str, vector: &str, model: &str) -
     Ok(())
 }
 
-fn cmd_import_embeddings(db: &Database, file: &str, model: &str) -> Result<()> {
+fn cmd_import_embeddings(db: &dyn Storage, file: &str, model: &str) -> Result<()> {
     use engine::EmbeddingSource;
 
     let content = std::fs::read_to_string(file)?;
@@ -3352,7 +6787,7 @@ fn cmd_import_embeddings(db: &Database, file: &str, model: &str) -> Result<()> {
     Ok(())
 }
 
-fn cmd_export_for_embedding(db: &Database, output: Option<&str>, source: &str) -> Result<()> {
+fn cmd_export_for_embedding(db: &dyn Storage, output: Option<&str>, source: &str) -> Result<()> {
     #[derive(serde::Serialize)]
     struct ExportItem {
         source_type: String,
@@ -3360,90 +6795,310 @@ fn cmd_export_for_embedding(db: &Database, output: Option<&str>, source: &str) -
         text: String,
     }
 
-    let mut items = Vec::new();
+    let items: Vec<ExportItem> = embedding_inputs(db, source)?
+        .into_iter()
+        .map(|(source_type, source_id, text)| ExportItem {
+            source_type: source_type.as_str().to_string(),
+            source_id,
+            text,
+        })
+        .collect();
 
-    let export_videos = source == "all" || source == "video";
-    let export_chunks = source == "all" || source == "chunk";
-    let export_claims = source == "all" || source == "claim";
+    let json = serde_json::to_string_pretty(&items)?;
 
-    // Export videos
-    if export_videos {
-        let videos = db.list_videos()?;
-        for video in videos {
-            if !db.has_embedding(engine::EmbeddingSource::Video, &video.id)? {
-                let text = format!(
-                    "{}\n{}",
-                    video.title,
-                    video.description.unwrap_or_default()
-                );
-                items.push(ExportItem {
-                    source_type: "video".to_string(),
-                    source_id: video.id,
-                    text,
-                });
+    if let Some(path) = output {
+        std::fs::write(path, &json)?;
+        println!("Exported {} items to {} for embedding", items.len(), path);
+    } else {
+        println!("{}", json);
+    }
+
+    Ok(())
+}
+
+/// Videos, chunks and claims without an embedding yet, with the text to embed for each.
+/// `source` is video, chunk, claim or all.
+fn embedding_inputs(db: &dyn Storage, source: &str) -> Result<Vec<(engine::EmbeddingSource, String, String)>> {
+    use engine::EmbeddingSource;
+
+    let mut items = Vec::new();
+
+    if source == "all" || source == "video" {
+        for video in db.list_videos()? {
+            if !db.has_embedding(EmbeddingSource::Video, &video.id)? {
+                let text = video_embedding_text(&video);
+                items.push((EmbeddingSource::Video, video.id, text));
             }
         }
     }
 
-    // Export chunks
-    if export_chunks {
-        let videos = db.list_videos()?;
-        for video in videos {
-            let chunks = db.get_transcript_chunks(&video.id)?;
-            for chunk in chunks {
+    if source == "all" || source == "chunk" {
+        for video in db.list_videos()? {
+            for chunk in db.get_transcript_chunks(&video.id)? {
                 let source_id = format!("{}:{}", video.id, chunk.chunk_index);
-                if !db.has_embedding(engine::EmbeddingSource::Chunk, &source_id)? {
-                    items.push(ExportItem {
-                        source_type: "chunk".to_string(),
-                        source_id,
-                        text: chunk.text,
-                    });
+                if !db.has_embedding(EmbeddingSource::Chunk, &source_id)? {
+                    items.push((EmbeddingSource::Chunk, source_id, chunk.text));
                 }
             }
         }
     }
 
-    // Export claims
-    if export_claims {
-        let claims = db.list_all_claims()?;
-        for claim in claims {
+    if source == "all" || source == "claim" {
+        for claim in db.list_all_claims()? {
             let source_id = claim.id.to_string();
-            if !db.has_embedding(engine::EmbeddingSource::Claim, &source_id)? {
-                items.push(ExportItem {
-                    source_type: "claim".to_string(),
-                    source_id,
-                    text: claim.text,
-                });
+            if !db.has_embedding(EmbeddingSource::Claim, &source_id)? {
+                items.push((EmbeddingSource::Claim, source_id, claim.text));
             }
         }
     }
 
-    let json = serde_json::to_string_pretty(&items)?;
+    Ok(items)
+}
 
-    if let Some(path) = output {
-        std::fs::write(path, &json)?;
-        println!("Exported {} items to {} for embedding", items.len(), path);
-    } else {
-        println!("{}", json);
+fn video_embedding_text(video: &engine::Video) -> String {
+    format!("{}\n{}", video.title, video.description.as_deref().unwrap_or_default())
+}
+
+/// The text embedded for one item, or None if it no longer exists.
+fn embedding_text(db: &dyn Storage, source_type: engine::EmbeddingSource, source_id: &str) -> Result<Option<String>> {
+    use engine::EmbeddingSource;
+
+    Ok(match source_type {
+        EmbeddingSource::Video => db.get_video(source_id)?.map(|v| video_embedding_text(&v)),
+        EmbeddingSource::Chunk => match source_id.rsplit_once(':') {
+            Some((video_id, index)) => db
+                .get_transcript_chunks(video_id)?
+                .into_iter()
+                .find(|c| c.chunk_index.to_string() == index)
+                .map(|c| c.text),
+            None => None,
+        },
+        EmbeddingSource::Claim => match source_id.parse() {
+            Ok(id) => db.get_claim(id)?.map(|c| c.text),
+            Err(_) => None,
+        },
+        _ => None,
+    })
+}
+
+/// Whether ingesting commands queue new content for embedding (the embeddings.auto setting).
+fn auto_embed(db: &dyn Storage) -> Result<bool> {
+    Ok(db.get_setting("embeddings.auto")?.as_deref() == Some("on"))
+}
+
+/// In auto-embed mode, chunk a newly stored video and queue it and its chunks for embedding.
+fn schedule_embeddings(db: &dyn Storage, video_id: &str, transcript: Option<&engine::Transcript>) -> Result<()> {
+    use engine::EmbeddingSource;
+
+    if !auto_embed(db)? {
+        return Ok(());
     }
+    db.queue_embedding(EmbeddingSource::Video, video_id)?;
+    if let Some(t) = transcript {
+        let chunks = chunk_transcript(t, DEFAULT_CHUNK_TOKENS, DEFAULT_CHUNK_OVERLAP);
+        db.save_transcript_chunks(video_id, &chunks)?;
+        for chunk in &chunks {
+            db.queue_embedding(EmbeddingSource::Chunk, &format!("{}:{}", video_id, chunk.chunk_index))?;
+        }
+    }
+    Ok(())
+}
 
+/// Embed one batch and store the vectors, taking the items off the embedding queue.
+fn embed_batch(
+    db: &dyn Storage,
+    embedder: &dyn engine::EmbeddingProvider,
+    provider_name: &str,
+    batch: &[(engine::EmbeddingSource, String, String)],
+) -> Result<()> {
+    let texts: Vec<String> = batch.iter().map(|(_, _, text)| text.clone()).collect();
+    let vectors = embedder.embed(&texts)?;
+    if let Some(first) = vectors.first() {
+        db.record_embedding_model(embedder.model(), Some(provider_name), first.len())?;
+    }
+    for ((source_type, source_id, _), vector) in batch.iter().zip(&vectors) {
+        db.save_embedding(*source_type, source_id, embedder.model(), vector)?;
+        db.dequeue_embedding(*source_type, source_id)?;
+    }
     Ok(())
 }
 
-fn cmd_semantic(db: &Database, vector: &str, source: Option<&str>, limit: usize) -> Result<()> {
-    use engine::EmbeddingSource;
+/// Embed everything on the embedding queue with the configured provider. Without one,
+/// or if the provider fails, items stay queued for the next run.
+fn drain_embedding_queue(db: &dyn Storage, options: &EmbeddingOptions) -> Result<()> {
+    let queued = db.list_embedding_queue()?;
+    if queued.is_empty() {
+        return Ok(());
+    }
+    let Some(config) = options.configured(db)? else {
+        println!("{} items queued for embedding; set embeddings.provider to embed them", queued.len());
+        return Ok(());
+    };
 
-    let query_vec: Vec<f32> = match serde_json::from_str(vector) {
-        Ok(v) => v,
-        Err(e) => {
-            println!("Invalid vector JSON: {}", e);
+    let mut items = Vec::new();
+    for item in queued {
+        match embedding_text(db, item.source_type, &item.source_id)? {
+            Some(text) => items.push((item.source_type, item.source_id, text)),
+            // Deleted since it was queued
+            None => {
+                db.dequeue_embedding(item.source_type, &item.source_id)?;
+            }
+        }
+    }
+
+    let embedder = options.embedder(&config)?;
+    let provider_name = embedding_provider_name(&config.provider);
+    let mut done = 0;
+    for batch in items.chunks(options.batch_size.max(1)) {
+        if let Err(e) = embed_batch(db, embedder.as_ref(), provider_name, batch) {
+            let error = e.to_string();
+            for (source_type, source_id, _) in batch {
+                db.fail_queued_embedding(*source_type, source_id, &error)?;
+            }
+            println!("Embedding failed, {} items left queued: {}", items.len() - done, error.lines().next().unwrap_or(""));
             return Ok(());
         }
+        done += batch.len();
+    }
+    if done > 0 {
+        println!("Embedded {} items with {}", done, embedder.model());
+    }
+    Ok(())
+}
+
+fn cmd_embed_all(db: &dyn Storage, source: &str, options: &EmbeddingOptions) -> Result<()> {
+    if !["all", "video", "chunk", "claim"].contains(&source) {
+        anyhow::bail!("Unknown source type: {} (use video, chunk, claim or all)", source);
+    }
+    let config = options.configured_or_local(db)?;
+    let embedder = options.embedder(&config)?;
+    let provider_name = embedding_provider_name(&config.provider);
+
+    let mut items = embedding_inputs(db, source)?;
+    // Queued items may already have an embedding that is out of date (e.g. after re-chunking)
+    for item in db.list_embedding_queue()? {
+        if source != "all" && item.source_type.as_str() != source {
+            continue;
+        }
+        if items.iter().any(|(t, id, _)| *t == item.source_type && *id == item.source_id) {
+            continue;
+        }
+        match embedding_text(db, item.source_type, &item.source_id)? {
+            Some(text) => items.push((item.source_type, item.source_id, text)),
+            None => {
+                db.dequeue_embedding(item.source_type, &item.source_id)?;
+            }
+        }
+    }
+    if items.is_empty() {
+        println!("Nothing to embed.");
+        return Ok(());
+    }
+
+    println!("Embedding {} items with {} ({})...", items.len(), embedder.model(), provider_name);
+    let mut done = 0;
+    for batch in items.chunks(options.batch_size.max(1)) {
+        embed_batch(db, embedder.as_ref(), provider_name, batch)?;
+        done += batch.len();
+        println!("  {}/{}", done, items.len());
+    }
+    println!("Embedded {} items", done);
+
+    Ok(())
+}
+
+fn cmd_re_embed(db: &dyn Storage, from: &str, to: &str, keep_old: bool, options: &EmbeddingOptions) -> Result<()> {
+    if from == to {
+        anyhow::bail!("--from-model and --to-model are the same");
+    }
+    let old = db.list_embeddings_by_model(from)?;
+    if old.is_empty() {
+        println!("No embeddings with model {}", from);
+        return Ok(());
+    }
+
+    let options = EmbeddingOptions { model: Some(to.to_string()), ..options.clone() };
+    let config = options.configured_or_local(db)?;
+    let embedder = options.embedder(&config)?;
+    let provider_name = embedding_provider_name(&config.provider);
+
+    // Anything already embedded with the new model is skipped, so an interrupted run can resume
+    let (mut items, mut missing) = (Vec::new(), 0);
+    for emb in &old {
+        if db.get_embedding(emb.source_type, &emb.source_id, to)?.is_some() {
+            continue;
+        }
+        match db.get_text_for_embedding(emb)? {
+            Some(text) => items.push((emb.source_type, emb.source_id.clone(), text)),
+            None => missing += 1,
+        }
+    }
+
+    println!("Re-embedding {} of {} items from {} with {} ({})...", items.len(), old.len(), from, to, provider_name);
+    let mut done = 0;
+    for batch in items.chunks(options.batch_size.max(1)) {
+        embed_batch(db, embedder.as_ref(), provider_name, batch)?;
+        done += batch.len();
+        println!("  {}/{}", done, items.len());
+    }
+
+    let removed = db.swap_embedding_model(from, to, provider_name, keep_old)?;
+    println!("Queries now use {} ({})", to, provider_name);
+    if keep_old {
+        println!("Kept the {} embeddings from {}", old.len(), from);
+    } else {
+        println!("Removed {} embeddings from {}", removed, from);
+    }
+    if missing > 0 {
+        println!("{} items no longer exist and were not re-embedded", missing);
+    }
+    Ok(())
+}
+
+fn embedding_provider_name(provider: &engine::LLMProvider) -> &'static str {
+    match provider {
+        engine::LLMProvider::Local => "ollama",
+        other => other.as_str(),
+    }
+}
+
+fn cmd_semantic(
+    db: &dyn Storage,
+    query: Option<&str>,
+    vector: Option<&str>,
+    source: Option<&str>,
+    limit: usize,
+    diversify: Option<f32>,
+    embedding: &EmbeddingOptions,
+) -> Result<()> {
+    use engine::EmbeddingSource;
+
+    let query_vec: Vec<f32> = match (vector, query) {
+        (Some(vector), _) => match serde_json::from_str(vector) {
+            Ok(v) => v,
+            Err(e) => {
+                println!("Invalid vector JSON: {}", e);
+                return Ok(());
+            }
+        },
+        (None, Some(query)) => match embedding.embed_query(db, query)? {
+            Some(v) => v,
+            None => anyhow::bail!(
+                "No embedding provider configured: pass --provider, or run `engine config embeddings.provider ollama`"
+            ),
+        },
+        (None, None) => unreachable!("clap requires a query or --vector"),
     };
 
     let source_type = source.and_then(EmbeddingSource::from_str);
 
-    let similar = db.find_similar(&query_vec, source_type, limit)?;
+    let similar = match diversify {
+        Some(lambda) => {
+            let candidates = db.find_similar(&query_vec, source_type, None, limit * MMR_CANDIDATES)?;
+            db.diversify(&query_vec, candidates, limit, lambda.clamp(0.0, 1.0))
+        }
+        None => db.find_similar(&query_vec, source_type, None, limit)?,
+    };
 
     if similar.is_empty() {
         println!("No results found. Make sure embeddings exist in the database.");
@@ -3460,33 +7115,203 @@ fn cmd_semantic(db: &Database, vector: &str, source: Option<&str>, limit: usize)
         let text_preview = if result.text.len() > 40 {
             format!("{}...", &result.text[..37].replace('\n', " "))
         } else {
-            result.text.replace('\n', " ")
+            result.text.replace('\n', " ")
+        };
+        let id_short = if result.source_id.len() > 13 {
+            format!("{}...", &result.source_id[..10])
+        } else {
+            result.source_id.clone()
+        };
+        println!(
+            "{:<8.4} {:<10} {:<15} {}",
+            result.score,
+            result.source_type.as_str(),
+            id_short,
+            text_preview
+        );
+    }
+
+    Ok(())
+}
+
+fn cmd_answer(
+    db: &dyn Storage,
+    question: &str,
+    videos: usize,
+    attach: Option<i64>,
+    llm: &LlmOptions,
+    embedding: &EmbeddingOptions,
+) -> Result<()> {
+    use engine::ai::answering::{self, Passage};
+
+    if let Some(id) = attach {
+        if db.get_research_question(id)?.is_none() {
+            println!("Research question not found: {}", id);
+            return Ok(());
+        }
+    }
+    let config = llm.config(db)?;
+    let chat = tracked_chat(db, &config, "answer")?;
+
+    // Retrieval: hybrid search picks the videos; within each, the claims and chunks
+    // closest to the question (by embedding when there is one, else by shared words)
+    const STOPWORDS: &[&str] = &["what", "when", "where", "which", "were", "does", "that", "this", "with", "from", "have", "about", "there", "their", "they"];
+    let terms: Vec<String> = question
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|w| w.len() >= 4 && !STOPWORDS.contains(&w.as_str()))
+        .collect();
+    if terms.is_empty() {
+        println!("Ask a more specific question.");
+        return Ok(());
+    }
+    // Any of the question's words, since full-text search would otherwise require all of them
+    let keywords = terms.join(" OR ");
+    let query_vec = embedding.embed_query(db, question)?;
+    let results = db.hybrid_search(&keywords, query_vec.as_deref(), 0.5, 0.5, videos, engine::Fusion::Rrf)?;
+    let overlap = |text: &str| {
+        let text = text.to_lowercase();
+        terms.iter().filter(|t| text.contains(t.as_str())).count()
+    };
+
+    let mut claims = Vec::new();
+    let mut chunks = Vec::new();
+    let mut seen_claims = std::collections::HashSet::new();
+    let mut titles: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+    let mut add_claim = |db: &dyn Storage, claims: &mut Vec<Passage>, claim: engine::Claim| -> Result<()> {
+        if claim.status == engine::ClaimStatus::Retracted || !seen_claims.insert(claim.id) {
+            return Ok(());
+        }
+        let video_title = match titles.get(&claim.video_id) {
+            Some(title) => title.clone(),
+            None => {
+                let title = db.get_video(&claim.video_id)?.map(|v| v.title).unwrap_or_default();
+                titles.insert(claim.video_id.clone(), title.clone());
+                title
+            }
+        };
+        claims.push(Passage::Claim {
+            claim_id: claim.id,
+            video_id: claim.video_id,
+            video_title,
+            timestamp: claim.timestamp,
+            text: claim.text,
+        });
+        Ok(())
+    };
+
+    // Claims matching the question directly, wherever they come from
+    let direct = db.unified_search(&terms.join(" "), Some(&["claim"]), None, videos * 2, 0.3)?;
+    for result in direct.results {
+        if let Some(claim) = db.get_claim(result.id)? {
+            add_claim(db, &mut claims, claim)?;
+        }
+    }
+
+    for result in &results {
+        let video = &result.video;
+        let matched: Vec<engine::Claim> = if result.matching_claims.is_empty() {
+            let mut scored: Vec<(usize, engine::Claim)> = db
+                .list_claims_for_video(&video.id)?
+                .into_iter()
+                .map(|c| (overlap(&c.text), c))
+                .filter(|(score, _)| *score > 0)
+                .collect();
+            scored.sort_by_key(|s| std::cmp::Reverse(s.0));
+            scored.into_iter().map(|(_, c)| c).collect()
+        } else {
+            result.matching_claims.clone()
         };
-        let id_short = if result.source_id.len() > 13 {
-            format!("{}...", &result.source_id[..10])
+        for claim in matched.into_iter().take(3) {
+            add_claim(db, &mut claims, claim)?;
+        }
+
+        let mut matched: Vec<(f32, engine::TranscriptChunk)> = if result.matching_chunks.is_empty() {
+            db.get_transcript_chunks(&video.id)?
+                .into_iter()
+                .map(|c| (overlap(&c.text) as f32, c))
+                .filter(|(score, _)| *score > 0.0)
+                .collect()
         } else {
-            result.source_id.clone()
+            result.matching_chunks.iter().map(|m| (m.score, m.chunk.clone())).collect()
         };
-        println!(
-            "{:<8.4} {:<10} {:<15} {}",
-            result.score,
-            result.source_type.as_str(),
-            id_short,
-            text_preview
-        );
+        matched.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        chunks.extend(matched.into_iter().take(2).map(|(_, c)| Passage::Chunk {
+            video_id: video.id.clone(),
+            video_title: video.title.clone(),
+            start_time: c.start_time,
+            text: c.text,
+        }));
+    }
+
+    // Claims first: they are short, already distilled, and citable on their own
+    let passages = answering::fit_context(claims.into_iter().chain(chunks).collect());
+    if passages.is_empty() {
+        println!("Nothing in the knowledge base matches: {}", question);
+        if db.get_embedding_stats()?.chunk_embeddings == 0 {
+            println!("Chunk and embed transcripts ('chunk', 'embed-all') so answers can draw on them.");
+        }
+        return Ok(());
     }
 
+    println!("Answering from {} passages with {} ({})\n", passages.len(), config.model, config.provider.as_str());
+    let answer = chat.complete_stream(
+        answering::SYSTEM_PROMPT,
+        &answering::answer_prompt(question, &passages),
+        &mut |token| {
+            print!("{}", token);
+            let _ = std::io::Write::flush(&mut std::io::stdout());
+        },
+    )?;
+    println!("\n");
+
+    let cited = answering::citations(&answer, passages.len());
+    println!("Sources:");
+    if cited.is_empty() {
+        println!("  (the answer cites none of the {} passages)", passages.len());
+    }
+    for &n in &cited {
+        let passage = &passages[n - 1];
+        println!("  [{}] {} ({})", n, passage.reference(), passage.video_id());
+    }
+
+    if let Some(question_id) = attach {
+        let existing_claims: Vec<i64> = db.get_question_evidence_claims(question_id)?.into_iter().map(|c| c.id).collect();
+        let mut existing_videos: Vec<String> = db.get_question_evidence_videos(question_id)?.into_iter().map(|v| v.id).collect();
+        let mut added = 0;
+        for &n in &cited {
+            let relevance = truncate(&answering::citing_sentences(&answer, n), 500);
+            let relevance = Some(relevance.as_str()).filter(|r| !r.is_empty());
+            match &passages[n - 1] {
+                Passage::Claim { claim_id, .. } if !existing_claims.contains(claim_id) => {
+                    db.add_evidence_to_question(question_id, Some(*claim_id), None, relevance)?;
+                    added += 1;
+                }
+                Passage::Chunk { video_id, .. } if !existing_videos.contains(video_id) => {
+                    db.add_evidence_to_question(question_id, None, Some(video_id), relevance)?;
+                    existing_videos.push(video_id.clone());
+                    added += 1;
+                }
+                _ => {}
+            }
+        }
+        println!("\nAttached {} new piece(s) of evidence to question #{}", added, question_id);
+    }
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn cmd_hybrid(
-    db: &Database,
+    db: &dyn Storage,
     query: &str,
     vector: Option<&str>,
     kw_weight: f32,
     sem_weight: f32,
     limit: usize,
+    fusion: &str,
+    embedding: &EmbeddingOptions,
 ) -> Result<()> {
+    let fusion = engine::Fusion::from_str(fusion).unwrap_or(engine::Fusion::Weighted);
     let query_vec: Option<Vec<f32>> = if let Some(v) = vector {
         match serde_json::from_str(v) {
             Ok(vec) => Some(vec),
@@ -3496,7 +7321,7 @@ fn cmd_hybrid(
             }
         }
     } else {
-        None
+        embedding.embed_query(db, query)?
     };
 
     let results = db.hybrid_search(
@@ -3505,6 +7330,7 @@ fn cmd_hybrid(
         kw_weight,
         sem_weight,
         limit,
+        fusion,
     )?;
 
     if results.is_empty() {
@@ -3512,7 +7338,7 @@ fn cmd_hybrid(
         return Ok(());
     }
 
-    println!("Hybrid Search Results (kw:{:.1}, sem:{:.1}):\n", kw_weight, sem_weight);
+    println!("Hybrid Search Results ({}, kw:{:.1}, sem:{:.1}):\n", fusion.as_str(), kw_weight, sem_weight);
     println!("{:<8} {:<6} {:<6} {:<12} {}", "SCORE", "KW", "SEM", "ID", "TITLE");
     println!("{}", "-".repeat(80));
 
@@ -3523,7 +7349,9 @@ fn cmd_hybrid(
             result.video.title.clone()
         };
         println!(
-            "{:<8.3} {:<6.3} {:<6.3} {:<12} {}",
+            "{:<8.*} {:<6.3} {:<6.3} {:<12} {}",
+            // RRF scores are small (at most 1/61) and need more digits to tell apart
+            if fusion == engine::Fusion::Rrf { 5 } else { 3 },
             result.combined_score,
             result.keyword_score,
             result.semantic_score,
@@ -3542,7 +7370,10 @@ fn cmd_hybrid(
     Ok(())
 }
 
-fn cmd_similar(db: &Database, source: &str, id: &str, limit: usize) -> Result<()> {
+// Diversified searches pick from this many times as many nearest neighbours
+const MMR_CANDIDATES: usize = 5;
+
+fn cmd_similar(db: &dyn Storage, source: &str, id: &str, limit: usize, diversify: Option<f32>) -> Result<()> {
     use engine::EmbeddingSource;
 
     let source_type = match EmbeddingSource::from_str(source) {
@@ -3555,24 +7386,30 @@ fn cmd_similar(db: &Database, source: &str, id: &str, limit: usize) -> Result<()
     };
 
     // Get the embedding for the source item
-    let embedding = match db.get_embedding(source_type, id, "default")? {
+    let model = db.default_embedding_model()?.unwrap_or_else(|| "default".to_string());
+    let embedding = match db.get_embedding(source_type, id, &model)? {
         Some(e) => e,
         None => {
-            println!("No embedding found for {} '{}'", source, id);
+            println!("No {} embedding found for {} '{}'", model, source, id);
             println!("Use 'embed' or 'import-embeddings' to add embeddings first.");
             return Ok(());
         }
     };
 
     // Find similar items
-    let similar = db.find_similar(&embedding.vector, None, limit + 1)?;
+    let pool = if diversify.is_some() { limit * MMR_CANDIDATES } else { limit };
+    let similar = db.find_similar(&embedding.vector, None, Some(&model), pool + 1)?;
 
     // Filter out the source item itself
     let similar: Vec<_> = similar
         .into_iter()
         .filter(|(e, _)| !(e.source_type == source_type && e.source_id == id))
-        .take(limit)
+        .take(pool)
         .collect();
+    let similar = match diversify {
+        Some(lambda) => db.diversify(&embedding.vector, similar, limit, lambda.clamp(0.0, 1.0)),
+        None => similar,
+    };
 
     if similar.is_empty() {
         println!("No similar items found.");
@@ -3616,7 +7453,84 @@ fn cmd_similar(db: &Database, source: &str, id: &str, limit: usize) -> Result<()
     Ok(())
 }
 
-fn cmd_embed_stats(db: &Database) -> Result<()> {
+fn cmd_export_projection(db: &dyn Storage, output: Option<&std::path::Path>, source: Option<&str>, model: Option<&str>) -> Result<()> {
+    let source_type = match source {
+        Some(s) => match engine::EmbeddingSource::from_str(s) {
+            Some(st) => Some(st),
+            None => anyhow::bail!("Invalid source type: {} (use video, chunk or claim)", s),
+        },
+        None => None,
+    };
+
+    let points = db.embedding_projection(source_type, model)?;
+    let json = serde_json::to_string_pretty(&points)?;
+    match output {
+        Some(path) => {
+            std::fs::write(path, &json)?;
+            println!("Exported {} points to {}", points.len(), path.display());
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+fn cmd_embed_outliers(db: &dyn Storage, source: Option<&str>, clusters: Option<usize>, limit: usize) -> Result<()> {
+    let source_type = match source {
+        Some(s) => match engine::EmbeddingSource::from_str(s) {
+            Some(st) => Some(st),
+            None => {
+                println!("Invalid source type: {}", s);
+                println!("Valid options: video, chunk, claim");
+                return Ok(());
+            }
+        },
+        None => None,
+    };
+
+    let outliers = db.embedding_outliers(source_type, clusters, limit)?;
+    if outliers.is_empty() {
+        println!("No embeddings found. Run 'embed-all' first.");
+        return Ok(());
+    }
+
+    let results = db.build_similarity_results(outliers)?;
+    println!("Furthest from any cluster (cosine distance to the nearest centroid):\n");
+    println!("{:<8} {:<10} {:<22} TEXT", "DIST", "TYPE", "ID");
+    println!("{}", "-".repeat(80));
+    for result in results {
+        println!(
+            "{:<8.4} {:<10} {:<22} {}",
+            result.score,
+            result.source_type.as_str(),
+            truncate(&result.source_id, 22),
+            truncate(&result.text.replace('\n', " "), 45)
+        );
+    }
+
+    Ok(())
+}
+
+fn cmd_embed_stats(db: &dyn Storage, by_model: bool) -> Result<()> {
+    if by_model {
+        let models = db.embedding_stats_by_model()?;
+        if models.is_empty() {
+            println!("No embeddings yet.");
+            return Ok(());
+        }
+        let default = db.default_embedding_model()?;
+        println!("{:<30} {:>6} {:>8} {:>8} {:>8} {:>8} {:>8}  PROVIDER", "MODEL", "DIMS", "VIDEOS", "CHUNKS", "CLAIMS", "SUMMARY", "TOTAL");
+        for m in &models {
+            let marker = if default.as_deref() == Some(m.model.as_str()) { "*" } else { " " };
+            println!(
+                "{}{:<29} {:>6} {:>8} {:>8} {:>8} {:>8} {:>8}  {}",
+                marker, truncate(&m.model, 29), m.dimensions, m.videos, m.chunks, m.claims, m.summaries, m.total,
+                m.provider.as_deref().unwrap_or("-")
+            );
+        }
+        println!("\n* default model for similar and hybrid (set with `engine config embeddings.default_model <model>`)");
+        return Ok(());
+    }
+
     let stats = db.get_embedding_stats()?;
 
     println!("Embedding Statistics:\n");
@@ -3633,6 +7547,14 @@ fn cmd_embed_stats(db: &Database) -> Result<()> {
         println!("Dimensions: {}", dims);
     }
 
+    let models = db.list_embedding_models()?;
+    if models.len() > 1 {
+        println!("\nModels:");
+        for m in &models {
+            println!("  {:<30} {:>6} dims  {}", m.model, m.dimensions, m.provider.as_deref().unwrap_or("-"));
+        }
+    }
+
     // Show what needs embeddings
     let (videos, chunks, claims) = db.get_items_needing_embeddings()?;
     let needs_count = videos.len() + chunks.len() + claims.len();
@@ -3648,7 +7570,14 @@ fn cmd_embed_stats(db: &Database) -> Result<()> {
         if !claims.is_empty() {
             println!("  Claims: {}", claims.len());
         }
-        println!("\nUse 'export-for-embedding' to export text for external embedding.");
+        println!("\nUse 'embed-all', or 'export-for-embedding' to export text for external embedding.");
+    }
+
+    if stats.queued > 0 {
+        println!("\nQueued for embedding: {}", stats.queued);
+        if db.get_setting("embeddings.provider")?.is_none() && std::env::var_os("ENGINE_EMBED_PROVIDER").is_none() {
+            println!("No embedding provider configured; run `engine config embeddings.provider ollama`.");
+        }
     }
 
     Ok(())
@@ -3657,7 +7586,7 @@ fn cmd_embed_stats(db: &Database) -> Result<()> {
 // Phase 8: Analytical Frameworks
 
 fn cmd_cyclical(
-    db: &Database,
+    db: &dyn Storage,
     video_id: &str,
     type_str: &str,
     entity: &str,
@@ -3714,7 +7643,7 @@ fn cmd_cyclical(
     Ok(())
 }
 
-fn cmd_list_cyclical(db: &Database, type_filter: Option<&str>, entity_filter: Option<&str>) -> Result<()> {
+fn cmd_list_cyclical(db: &dyn Storage, type_filter: Option<&str>, entity_filter: Option<&str>) -> Result<()> {
     use engine::CyclicalType;
 
     let indicators = if let Some(type_str) = type_filter {
@@ -3757,7 +7686,7 @@ fn cmd_list_cyclical(db: &Database, type_filter: Option<&str>, entity_filter: Op
     Ok(())
 }
 
-fn cmd_delete_cyclical(db: &Database, id: i64) -> Result<()> {
+fn cmd_delete_cyclical(db: &dyn Storage, id: i64) -> Result<()> {
     if db.delete_cyclical_indicator(id)? {
         println!("Deleted cyclical indicator #{}", id);
     } else {
@@ -3767,7 +7696,7 @@ fn cmd_delete_cyclical(db: &Database, id: i64) -> Result<()> {
 }
 
 fn cmd_causal(
-    db: &Database,
+    db: &dyn Storage,
     cause_id: i64,
     effect_id: i64,
     loop_type_str: &str,
@@ -3824,7 +7753,7 @@ fn cmd_causal(
     Ok(())
 }
 
-fn cmd_list_causal(db: &Database, loop_type_filter: Option<&str>, claim_filter: Option<i64>) -> Result<()> {
+fn cmd_list_causal(db: &dyn Storage, loop_type_filter: Option<&str>, claim_filter: Option<i64>) -> Result<()> {
     use engine::LoopType;
 
     let relations = if let Some(loop_str) = loop_type_filter {
@@ -3867,7 +7796,7 @@ fn cmd_list_causal(db: &Database, loop_type_filter: Option<&str>, claim_filter:
     Ok(())
 }
 
-fn cmd_delete_causal(db: &Database, id: i64) -> Result<()> {
+fn cmd_delete_causal(db: &dyn Storage, id: i64) -> Result<()> {
     if db.delete_causal_relation(id)? {
         println!("Deleted causal relation #{}", id);
     } else {
@@ -3877,7 +7806,7 @@ fn cmd_delete_causal(db: &Database, id: i64) -> Result<()> {
 }
 
 fn cmd_transmission(
-    db: &Database,
+    db: &dyn Storage,
     idea: &str,
     source_entity: &str,
     target_entity: &str,
@@ -3950,7 +7879,7 @@ fn cmd_transmission(
     Ok(())
 }
 
-fn cmd_list_transmissions(db: &Database, idea_filter: Option<&str>, type_filter: Option<&str>) -> Result<()> {
+fn cmd_list_transmissions(db: &dyn Storage, idea_filter: Option<&str>, type_filter: Option<&str>) -> Result<()> {
     use engine::TransmissionType;
 
     let transmissions = if let Some(idea) = idea_filter {
@@ -4004,7 +7933,7 @@ fn cmd_list_transmissions(db: &Database, idea_filter: Option<&str>, type_filter:
     Ok(())
 }
 
-fn cmd_delete_transmission(db: &Database, id: i64) -> Result<()> {
+fn cmd_delete_transmission(db: &dyn Storage, id: i64) -> Result<()> {
     if db.delete_idea_transmission(id)? {
         println!("Deleted idea transmission #{}", id);
     } else {
@@ -4014,7 +7943,7 @@ fn cmd_delete_transmission(db: &Database, id: i64) -> Result<()> {
 }
 
 fn cmd_position(
-    db: &Database,
+    db: &dyn Storage,
     name: &str,
     era_name: &str,
     position_str: &str,
@@ -4057,7 +7986,7 @@ fn cmd_position(
     Ok(())
 }
 
-fn cmd_list_positions(db: &Database, era_filter: Option<&str>, position_filter: Option<&str>) -> Result<()> {
+fn cmd_list_positions(db: &dyn Storage, era_filter: Option<&str>, position_filter: Option<&str>) -> Result<()> {
     use engine::SystemPosition;
 
     let entities = if let Some(era_name) = era_filter {
@@ -4106,7 +8035,7 @@ fn cmd_list_positions(db: &Database, era_filter: Option<&str>, position_filter:
     Ok(())
 }
 
-fn cmd_update_position(db: &Database, id: i64, position_str: &str) -> Result<()> {
+fn cmd_update_position(db: &dyn Storage, id: i64, position_str: &str) -> Result<()> {
     use engine::SystemPosition;
 
     let position = match SystemPosition::from_str(position_str) {
@@ -4128,7 +8057,7 @@ fn cmd_update_position(db: &Database, id: i64, position_str: &str) -> Result<()>
 }
 
 fn cmd_flow(
-    db: &Database,
+    db: &dyn Storage,
     from_entity_id: i64,
     to_entity_id: i64,
     commodity: &str,
@@ -4180,7 +8109,7 @@ fn cmd_flow(
     Ok(())
 }
 
-fn cmd_list_flows(db: &Database, era_filter: Option<&str>, entity_filter: Option<i64>) -> Result<()> {
+fn cmd_list_flows(db: &dyn Storage, era_filter: Option<&str>, entity_filter: Option<i64>) -> Result<()> {
     let flows = if let Some(era_name) = era_filter {
         let era = match db.get_era_by_name(era_name)? {
             Some(e) => e,
@@ -4235,7 +8164,7 @@ fn cmd_list_flows(db: &Database, era_filter: Option<&str>, entity_filter: Option
     Ok(())
 }
 
-fn cmd_delete_flow(db: &Database, id: i64) -> Result<()> {
+fn cmd_delete_flow(db: &dyn Storage, id: i64) -> Result<()> {
     if db.delete_surplus_flow(id)? {
         println!("Deleted surplus flow #{}", id);
     } else {
@@ -4244,7 +8173,7 @@ fn cmd_delete_flow(db: &Database, id: i64) -> Result<()> {
     Ok(())
 }
 
-fn cmd_timescale(db: &Database, claim_id: i64, scale_str: &str, notes: Option<&str>) -> Result<()> {
+fn cmd_timescale(db: &dyn Storage, claim_id: i64, scale_str: &str, notes: Option<&str>) -> Result<()> {
     use engine::BraudelTimescale;
 
     let timescale = match BraudelTimescale::from_str(scale_str) {
@@ -4274,7 +8203,7 @@ fn cmd_timescale(db: &Database, claim_id: i64, scale_str: &str, notes: Option<&s
     Ok(())
 }
 
-fn cmd_list_timescales(db: &Database, scale_filter: Option<&str>) -> Result<()> {
+fn cmd_list_timescales(db: &dyn Storage, scale_filter: Option<&str>) -> Result<()> {
     use engine::BraudelTimescale;
 
     let observations = if let Some(scale_str) = scale_filter {
@@ -4313,7 +8242,7 @@ fn cmd_list_timescales(db: &Database, scale_filter: Option<&str>) -> Result<()>
     Ok(())
 }
 
-fn cmd_framework_stats(db: &Database) -> Result<()> {
+fn cmd_framework_stats(db: &dyn Storage) -> Result<()> {
     let stats = db.get_framework_stats()?;
 
     println!("Analytical Framework Statistics:\n");
@@ -4335,7 +8264,7 @@ fn cmd_framework_stats(db: &Database) -> Result<()> {
 
 // Phase 9: Synthesis & Pattern Detection
 
-fn cmd_moc_create(db: &Database, title: &str, description: Option<&str>) -> Result<()> {
+fn cmd_moc_create(db: &dyn Storage, title: &str, description: Option<&str>) -> Result<()> {
     // Check if already exists
     if db.get_moc_by_title(title)?.is_some() {
         println!("MOC '{}' already exists.", title);
@@ -4352,7 +8281,7 @@ fn cmd_moc_create(db: &Database, title: &str, description: Option<&str>) -> Resu
     Ok(())
 }
 
-fn cmd_list_mocs(db: &Database) -> Result<()> {
+fn cmd_list_mocs(db: &dyn Storage) -> Result<()> {
     let mocs = db.list_mocs()?;
 
     if mocs.is_empty() {
@@ -4378,7 +8307,7 @@ fn cmd_list_mocs(db: &Database) -> Result<()> {
     Ok(())
 }
 
-fn cmd_show_moc(db: &Database, id_or_title: &str) -> Result<()> {
+fn cmd_show_moc(db: &dyn Storage, id_or_title: &str) -> Result<()> {
     // Try as ID first, then as title
     let moc_with_claims = if let Ok(id) = id_or_title.parse::<i64>() {
         db.get_moc_with_claims(id)?
@@ -4429,7 +8358,7 @@ fn cmd_show_moc(db: &Database, id_or_title: &str) -> Result<()> {
     Ok(())
 }
 
-fn cmd_moc_add(db: &Database, moc_id: i64, claim_id: i64, order: i32) -> Result<()> {
+fn cmd_moc_add(db: &dyn Storage, moc_id: i64, claim_id: i64, order: i32) -> Result<()> {
     // Verify MOC exists
     if db.get_moc(moc_id)?.is_none() {
         println!("MOC #{} not found", moc_id);
@@ -4452,7 +8381,7 @@ fn cmd_moc_add(db: &Database, moc_id: i64, claim_id: i64, order: i32) -> Result<
     Ok(())
 }
 
-fn cmd_moc_remove(db: &Database, moc_id: i64, claim_id: i64) -> Result<()> {
+fn cmd_moc_remove(db: &dyn Storage, moc_id: i64, claim_id: i64) -> Result<()> {
     if db.remove_claim_from_moc(moc_id, claim_id)? {
         println!("Removed claim #{} from MOC #{}", claim_id, moc_id);
     } else {
@@ -4461,7 +8390,7 @@ fn cmd_moc_remove(db: &Database, moc_id: i64, claim_id: i64) -> Result<()> {
     Ok(())
 }
 
-fn cmd_delete_moc(db: &Database, id: i64) -> Result<()> {
+fn cmd_delete_moc(db: &dyn Storage, id: i64) -> Result<()> {
     if db.delete_moc(id)? {
         println!("Deleted MOC #{}", id);
     } else {
@@ -4470,7 +8399,7 @@ fn cmd_delete_moc(db: &Database, id: i64) -> Result<()> {
     Ok(())
 }
 
-fn cmd_ask(db: &Database, question: &str, parent_id: Option<i64>, notes: Option<&str>) -> Result<()> {
+fn cmd_ask(db: &dyn Storage, question: &str, parent_id: Option<i64>, notes: Option<&str>) -> Result<()> {
     // Verify parent exists if specified
     if let Some(pid) = parent_id {
         if db.get_research_question(pid)?.is_none() {
@@ -4490,7 +8419,7 @@ fn cmd_ask(db: &Database, question: &str, parent_id: Option<i64>, notes: Option<
     Ok(())
 }
 
-fn cmd_list_questions(db: &Database, status_filter: Option<&str>) -> Result<()> {
+fn cmd_list_questions(db: &dyn Storage, status_filter: Option<&str>) -> Result<()> {
     use engine::QuestionStatus;
 
     let status = status_filter.and_then(QuestionStatus::from_str);
@@ -4528,7 +8457,52 @@ fn cmd_list_questions(db: &Database, status_filter: Option<&str>) -> Result<()>
     Ok(())
 }
 
-fn cmd_show_question(db: &Database, id: i64) -> Result<()> {
+fn cmd_suggest_questions(db: &dyn Storage) -> Result<()> {
+    let suggestions = db.generate_question_suggestions()?;
+
+    if suggestions.is_empty() {
+        println!("No question suggestions right now.");
+        println!("Suggestions come from contradicting claims, single-video topics and linked claims outside any MOC.");
+        return Ok(());
+    }
+
+    println!("{:<5} {:<17} {:<60} EVIDENCE", "ID", "REASON", "QUESTION");
+    println!("{}", "-".repeat(95));
+
+    for s in suggestions {
+        let evidence = if s.claim_ids.is_empty() {
+            format!("{} video(s)", s.video_ids.len())
+        } else {
+            format!("{} claim(s)", s.claim_ids.len())
+        };
+        println!("{:<5} {:<17} {:<60} {}", s.id, s.reason, truncate(&s.question, 58), evidence);
+    }
+
+    println!("\nUse 'accept-question <id>' to adopt or 'dismiss-question <id>' to hide.");
+    Ok(())
+}
+
+fn cmd_accept_question(db: &dyn Storage, id: i64) -> Result<()> {
+    match db.accept_question_suggestion(id)? {
+        Some(q) => {
+            println!("Created question #{}: {}", q.id, q.question);
+            println!("Use 'question {}' to see its evidence.", q.id);
+        }
+        None => println!("Pending suggestion not found: {}", id),
+    }
+    Ok(())
+}
+
+fn cmd_dismiss_question(db: &dyn Storage, id: i64) -> Result<()> {
+    if db.dismiss_question_suggestion(id)? {
+        println!("Dismissed suggestion #{}", id);
+    } else {
+        println!("Pending suggestion not found: {}", id);
+    }
+    Ok(())
+}
+
+fn cmd_show_question(db: &dyn Storage, id: i64) -> Result<()> {
     let qwe = match db.get_question_with_evidence(id)? {
         Some(q) => q,
         None => {
@@ -4580,7 +8554,7 @@ fn cmd_show_question(db: &Database, id: i64) -> Result<()> {
 }
 
 fn cmd_add_evidence(
-    db: &Database,
+    db: &dyn Storage,
     question_id: i64,
     claim_id: Option<i64>,
     video_id: Option<&str>,
@@ -4625,7 +8599,7 @@ fn cmd_add_evidence(
     Ok(())
 }
 
-fn cmd_answer_question(db: &Database, id: i64, status_str: &str) -> Result<()> {
+fn cmd_answer_question(db: &dyn Storage, id: i64, status_str: &str) -> Result<()> {
     use engine::QuestionStatus;
 
     let status = match QuestionStatus::from_str(status_str) {
@@ -4646,7 +8620,7 @@ fn cmd_answer_question(db: &Database, id: i64, status_str: &str) -> Result<()> {
     Ok(())
 }
 
-fn cmd_delete_question(db: &Database, id: i64) -> Result<()> {
+fn cmd_delete_question(db: &dyn Storage, id: i64) -> Result<()> {
     if db.delete_research_question(id)? {
         println!("Deleted question #{}", id);
     } else {
@@ -4656,7 +8630,7 @@ fn cmd_delete_question(db: &Database, id: i64) -> Result<()> {
 }
 
 fn cmd_add_pattern(
-    db: &Database,
+    db: &dyn Storage,
     type_str: &str,
     description: &str,
     videos_str: Option<&str>,
@@ -4700,7 +8674,7 @@ fn cmd_add_pattern(
     Ok(())
 }
 
-fn cmd_list_patterns(db: &Database, type_filter: Option<&str>) -> Result<()> {
+fn cmd_list_patterns(db: &dyn Storage, type_filter: Option<&str>) -> Result<()> {
     use engine::PatternType;
 
     let pattern_type = type_filter.and_then(PatternType::from_str);
@@ -4711,8 +8685,9 @@ fn cmd_list_patterns(db: &Database, type_filter: Option<&str>) -> Result<()> {
         return Ok(());
     }
 
-    println!("{:<5} {:<20} {:<45} {}", "ID", "TYPE", "DESCRIPTION", "CONF");
-    println!("{}", "-".repeat(80));
+    let provenance = db.list_pattern_provenance()?;
+    println!("{:<5} {:<20} {:<45} {:<6} {}", "ID", "TYPE", "DESCRIPTION", "CONF", "SOURCE");
+    println!("{}", "-".repeat(90));
 
     for p in patterns {
         let desc_preview = if p.description.len() > 43 {
@@ -4720,19 +8695,24 @@ fn cmd_list_patterns(db: &Database, type_filter: Option<&str>) -> Result<()> {
         } else {
             p.description.clone()
         };
+        let source = match provenance.get(&p.id) {
+            Some(prov) => format!("llm ({})", prov.scope.as_deref().unwrap_or("-")),
+            None => "manual".to_string(),
+        };
         println!(
-            "{:<5} {:<20} {:<45} {:.0}%",
+            "{:<5} {:<20} {:<45} {:<6} {}",
             p.id,
             p.pattern_type.as_str(),
             desc_preview,
-            p.confidence * 100.0
+            format!("{:.0}%", p.confidence * 100.0),
+            source
         );
     }
 
     Ok(())
 }
 
-fn cmd_delete_pattern(db: &Database, id: i64) -> Result<()> {
+fn cmd_delete_pattern(db: &dyn Storage, id: i64) -> Result<()> {
     if db.delete_detected_pattern(id)? {
         println!("Deleted pattern #{}", id);
     } else {
@@ -4741,9 +8721,165 @@ fn cmd_delete_pattern(db: &Database, id: i64) -> Result<()> {
     Ok(())
 }
 
-fn cmd_review(db: &Database, stale_only: bool, orphans_only: bool, random_count: usize) -> Result<()> {
+/// Claims within a `detect-patterns` scope such as `era:Bronze Age`. None if the scope
+/// names an unknown kind.
+fn claims_in_scope(db: &dyn Storage, scope: &str) -> Result<Option<Vec<engine::Claim>>> {
+    let Some((kind, value)) = scope.split_once(':') else { return Ok(None) };
+    let value = value.trim();
+    let videos = match kind.trim() {
+        "era" => return Ok(Some(db.list_claims_by_era(value)?)),
+        "topic" => return Ok(Some(db.list_claims_by_topic(value)?)),
+        "video" => return Ok(Some(db.list_claims_for_video(value)?)),
+        "region" => db.browse_videos(None, Some(value), true)?,
+        "collection" => db.get_collection_videos(value)?,
+        _ => return Ok(None),
+    };
+    let mut claims = Vec::new();
+    for video in videos {
+        claims.extend(db.list_claims_for_video(&video.id)?);
+    }
+    Ok(Some(claims))
+}
+
+fn cmd_detect_patterns(db: &dyn Storage, scope: &str, cluster_size: usize, llm: &LlmOptions) -> Result<()> {
+    use engine::ai::patterns;
+    use engine::ai::validation::complete_validated;
+
+    let scope = &scope.replace('"', "");
+    let Some(claims) = claims_in_scope(db, scope)? else {
+        println!("Invalid scope: {}", scope);
+        println!("Use era:NAME, region:NAME, topic:NAME, collection:NAME or video:ID");
+        return Ok(());
+    };
+    if claims.len() < 2 {
+        println!("Need at least two claims in {} (found {}).", scope, claims.len());
+        return Ok(());
+    }
+
+    let config = llm.config(db)?;
+    let chat = tracked_chat(db, &config, "detect-patterns")?;
+    let clusters = db.cluster_claims(&claims, cluster_size)?;
+    println!(
+        "Looking for patterns in {} claims ({} clusters) with {} ({})\n",
+        claims.len(),
+        clusters.len(),
+        config.model,
+        config.provider.as_str()
+    );
+
+    // Skip patterns already recorded over the same claims, so reruns don't duplicate them
+    let mut known: Vec<(engine::PatternType, Vec<i64>)> = db
+        .list_detected_patterns(None)?
+        .into_iter()
+        .map(|p| {
+            let mut ids = p.claim_ids;
+            ids.sort();
+            (p.pattern_type, ids)
+        })
+        .collect();
+
+    let mut titles: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+    let run_id = format!("patterns-{}", chrono::Utc::now().format("%Y%m%d%H%M%S"));
+    let (mut saved, mut failed) = (0, 0);
+    for (i, cluster) in clusters.iter().enumerate() {
+        for claim in cluster {
+            if !titles.contains_key(&claim.video_id) {
+                let title = db.get_video(&claim.video_id)?.map(|v| v.title).unwrap_or_else(|| claim.video_id.clone());
+                titles.insert(claim.video_id.clone(), title);
+            }
+        }
+        let listed: Vec<(&engine::Claim, &str)> = cluster.iter().map(|c| (c, titles[&c.video_id].as_str())).collect();
+        println!("Cluster {}/{} ({} claims)", i + 1, clusters.len(), cluster.len());
+
+        let prompt = patterns::cluster_prompt(scope, &listed);
+        let found = match complete_validated(chat.as_ref(), patterns::SYSTEM_PROMPT, &prompt, |r| {
+            patterns::parse_patterns(r, cluster.len())
+        }) {
+            Ok((found, _)) => found,
+            Err(e) => {
+                println!("  Failed: {:#}", e);
+                failed += 1;
+                continue;
+            }
+        };
+        let (provider, model) = (chat.provider().to_string(), chat.model().to_string());
+
+        for candidate in found {
+            let members: Vec<&engine::Claim> = candidate.claims.iter().map(|&i| &cluster[i]).collect();
+            let mut claim_ids: Vec<i64> = members.iter().map(|c| c.id).collect();
+            claim_ids.sort();
+            if known.iter().any(|(t, ids)| *t == candidate.pattern_type && *ids == claim_ids) {
+                continue;
+            }
+            let mut video_ids: Vec<String> = Vec::new();
+            for claim in &members {
+                if !video_ids.contains(&claim.video_id) {
+                    video_ids.push(claim.video_id.clone());
+                }
+            }
+
+            let pattern = db.with_transaction(|db| {
+                let pattern = db.save_detected_pattern(
+                    candidate.pattern_type,
+                    &candidate.description,
+                    &video_ids,
+                    &claim_ids,
+                    candidate.confidence,
+                )?;
+                db.set_pattern_provenance(&engine::PatternProvenance {
+                    pattern_id: pattern.id,
+                    provider: Some(provider.clone()),
+                    model: Some(model.clone()),
+                    prompt_version: Some(patterns::PROMPT_VERSION.to_string()),
+                    run_id: Some(run_id.clone()),
+                    scope: Some(scope.to_string()),
+                })?;
+                Ok(pattern)
+            })?;
+            println!(
+                "  #{} {} ({:.0}%, {} claims from {} video(s)): {}",
+                pattern.id,
+                pattern.pattern_type.as_str(),
+                pattern.confidence * 100.0,
+                claim_ids.len(),
+                video_ids.len(),
+                truncate(&pattern.description, 80)
+            );
+            known.push((pattern.pattern_type, claim_ids));
+            saved += 1;
+        }
+    }
+
+    println!("\nRecorded {} pattern(s) from {} cluster(s).", saved, clusters.len());
+    if failed > 0 {
+        println!("{} cluster(s) failed; rerun to retry them.", failed);
+    }
+    if saved > 0 {
+        println!("List them with 'patterns'.");
+    }
+    Ok(())
+}
+
+fn cmd_review(
+    db: &dyn Storage,
+    stale_only: bool,
+    orphans_only: bool,
+    bridges_only: bool,
+    needs_review_only: bool,
+    random_count: usize,
+    status: Option<&str>,
+) -> Result<()> {
+    use engine::ClaimStatus;
+
+    let status = status.and_then(ClaimStatus::from_str);
+    let wanted = |claim: &engine::Claim| match status {
+        Some(st) => claim.status == st,
+        None => claim.status != ClaimStatus::Retracted,
+    };
+
     if stale_only {
-        let stale = db.get_stale_claims(30)?;
+        let mut stale = db.get_stale_claims(30)?;
+        stale.retain(wanted);
         if stale.is_empty() {
             println!("No stale claims (all accessed within 30 days).");
         } else {
@@ -4764,7 +8900,8 @@ fn cmd_review(db: &Database, stale_only: bool, orphans_only: bool, random_count:
     }
 
     if orphans_only {
-        let orphans = db.get_orphan_claims()?;
+        let mut orphans = db.get_orphan_claims()?;
+        orphans.retain(wanted);
         if orphans.is_empty() {
             println!("No orphan claims (all have 2+ connections).");
         } else {
@@ -4785,11 +8922,69 @@ fn cmd_review(db: &Database, stale_only: bool, orphans_only: bool, random_count:
         return Ok(());
     }
 
+    // Bridging claims hold distant parts of the graph together, so errors there spread furthest
+    let mut bridges = db.list_bridging_claims(if bridges_only { 100 } else { 25 })?;
+    bridges.retain(|(claim, _)| wanted(claim));
+
+    // Machine-extracted claims no one has checked, oldest first
+    let provenance = db.list_claim_provenance()?;
+    let mut unreviewed: Vec<engine::Claim> = db
+        .list_all_claims()?
+        .into_iter()
+        .filter(|c| provenance.get(&c.id).is_some_and(|p| p.needs_review()) && wanted(c))
+        .collect();
+    unreviewed.sort_by_key(|c| c.id);
+
+    if needs_review_only {
+        if unreviewed.is_empty() {
+            println!("No AI-extracted claims waiting for review.");
+        } else {
+            println!("Needs Review (AI-extracted, unchecked): {}\n", unreviewed.len());
+            for claim in unreviewed.iter().take(20) {
+                println!("  [{}] {}", claim.id, truncate(&claim.text, 55));
+            }
+            if unreviewed.len() > 20 {
+                println!("  ... and {} more", unreviewed.len() - 20);
+            }
+            println!("\nMark checked claims with 'mark-reviewed ID...'");
+        }
+        return Ok(());
+    }
+
+    if bridges_only {
+        if bridges.is_empty() {
+            println!("No bridging claims (run 'graph-metrics' to compute them).");
+        } else {
+            println!("Bridging Claims (highest betweenness): {}\n", bridges.len());
+            for (claim, m) in bridges.iter().take(20) {
+                println!("  [{}] ({:.3}, {} links) {}", claim.id, m.betweenness, m.degree, truncate(&claim.text, 50));
+            }
+            if bridges.len() > 20 {
+                println!("  ... and {} more", bridges.len() - 20);
+            }
+        }
+        return Ok(());
+    }
+
     // Full review queue
-    let queue = db.get_review_queue(30, random_count)?;
+    let mut queue = db.get_review_queue(30, random_count)?;
+    queue.stale_claims.retain(wanted);
+    queue.orphan_claims.retain(wanted);
+    queue.random_suggestions.retain(wanted);
 
     println!("Review Queue:\n");
 
+    if !unreviewed.is_empty() {
+        println!("Needs Review (AI-extracted): {}", unreviewed.len());
+        for claim in unreviewed.iter().take(5) {
+            println!("  [{}] {}", claim.id, truncate(&claim.text, 50));
+        }
+        if unreviewed.len() > 5 {
+            println!("  ... use 'review --needs-review' to see all");
+        }
+        println!();
+    }
+
     println!("Stale Claims (30+ days): {}", queue.stale_claims.len());
     if !queue.stale_claims.is_empty() {
         for claim in queue.stale_claims.iter().take(5) {
@@ -4820,6 +9015,16 @@ fn cmd_review(db: &Database, stale_only: bool, orphans_only: bool, random_count:
         }
     }
 
+    if !bridges.is_empty() {
+        println!("\nBridging Claims: {}", bridges.len());
+        for (claim, m) in bridges.iter().take(5) {
+            println!("  [{}] ({:.3}) {}", claim.id, m.betweenness, truncate(&claim.text, 50));
+        }
+        if bridges.len() > 5 {
+            println!("  ... use 'review --bridges' to see all");
+        }
+    }
+
     if !queue.random_suggestions.is_empty() {
         println!("\nRandom Suggestions (for serendipitous review):");
         for claim in &queue.random_suggestions {
@@ -4837,7 +9042,119 @@ fn cmd_review(db: &Database, stale_only: bool, orphans_only: bool, random_count:
     Ok(())
 }
 
-fn cmd_synthesis_stats(db: &Database) -> Result<()> {
+const QUIZ_STOPWORDS: &[&str] = &[
+    "about", "after", "again", "against", "because", "before", "being", "between", "could",
+    "during", "every", "their", "there", "these", "those", "through", "under", "until",
+    "where", "which", "while", "would", "other", "often", "something",
+];
+
+// Blank out the most significant word of a claim, returning (prompt, answer)
+fn make_cloze(text: &str) -> Option<(String, String)> {
+    let answer = text
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|w| w.len() >= 5 && !QUIZ_STOPWORDS.contains(&w.to_lowercase().as_str()))
+        .max_by_key(|w| w.len())?
+        .to_string();
+    let prompt = text.replacen(&answer, &"_".repeat(answer.len().min(12)), 1);
+    Some((prompt, answer))
+}
+
+fn read_answer() -> Result<Option<String>> {
+    use std::io::Write;
+    print!("> ");
+    std::io::stdout().flush()?;
+    let mut line = String::new();
+    if std::io::stdin().read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    let line = line.trim().to_string();
+    if line.eq_ignore_ascii_case("q") {
+        return Ok(None);
+    }
+    Ok(Some(line))
+}
+
+fn cmd_quiz(db: &dyn Storage, topic: Option<&str>, count: usize, mode: &str) -> Result<()> {
+    if !matches!(mode, "cloze" | "source" | "mixed") {
+        println!("Invalid mode: {}", mode);
+        println!("Valid options: cloze, source, mixed");
+        return Ok(());
+    }
+
+    let claims = db.get_due_claims(topic, count)?;
+    if claims.is_empty() {
+        println!("No claims due for review{}.", topic.map(|t| format!(" in topic '{}'", t)).unwrap_or_default());
+        return Ok(());
+    }
+
+    let videos = db.list_videos()?;
+    println!("Quiz: {} question(s). Type 'q' to stop.\n", claims.len());
+
+    let mut asked = 0;
+    let mut correct_count = 0;
+
+    for (i, claim) in claims.iter().enumerate() {
+        let want_source = mode == "source" || (mode == "mixed" && i % 2 == 1);
+        let cloze = make_cloze(&claim.text);
+
+        let correct = if (want_source || cloze.is_none()) && videos.len() >= 2 {
+            // Which video did this claim come from?
+            let Some(source) = videos.iter().find(|v| v.id == claim.video_id) else { continue };
+            let others: Vec<&engine::Video> = videos.iter().filter(|v| v.id != claim.video_id).collect();
+            let start = claim.id as usize % others.len();
+            let mut options: Vec<&engine::Video> = others.iter()
+                .cycle()
+                .skip(start)
+                .take(others.len().min(3))
+                .copied()
+                .collect();
+            let slot = (claim.id as usize) % (options.len() + 1);
+            options.insert(slot, source);
+
+            println!("Q{}. Which video is this claim from?", i + 1);
+            println!("   \"{}\"", claim.text);
+            for (n, v) in options.iter().enumerate() {
+                println!("   {}) {}", n + 1, truncate(&v.title, 70));
+            }
+            let Some(answer) = read_answer()? else { break };
+            let chosen = answer.parse::<usize>().ok().and_then(|n| options.get(n.wrapping_sub(1)));
+            let ok = chosen.map(|v| v.id == claim.video_id).unwrap_or(false);
+            if !ok {
+                println!("   Answer: {}) {}", slot + 1, source.title);
+            }
+            ok
+        } else if let Some((prompt, answer)) = cloze {
+            println!("Q{}. Fill in the blank:", i + 1);
+            println!("   {}", prompt);
+            let Some(guess) = read_answer()? else { break };
+            let ok = strsim::jaro_winkler(&guess.to_lowercase(), &answer.to_lowercase()) >= 0.9;
+            if !ok {
+                println!("   Answer: {}", answer);
+            }
+            ok
+        } else {
+            println!("Q{}. (skipped claim #{}: too short to quiz)\n", i + 1, claim.id);
+            continue;
+        };
+
+        let schedule = db.record_review_result(claim.id, correct)?;
+        asked += 1;
+        if correct {
+            correct_count += 1;
+            println!("   Correct. Next review in {:.0} day(s).\n", schedule.interval_days);
+        } else {
+            println!("   Claim #{} will come back tomorrow.\n", claim.id);
+        }
+    }
+
+    if asked > 0 {
+        println!("Score: {}/{} ({:.0}%)", correct_count, asked, correct_count as f64 * 100.0 / asked as f64);
+    }
+    Ok(())
+}
+
+fn cmd_synthesis_stats(db: &dyn Storage) -> Result<()> {
     let stats = db.get_synthesis_stats()?;
 
     println!("Synthesis Statistics:\n");
@@ -4854,7 +9171,7 @@ fn cmd_synthesis_stats(db: &Database) -> Result<()> {
 
 // Phase 10: AI Processing Queue Commands
 
-fn cmd_queue(db: &Database, show_all: bool) -> Result<()> {
+fn cmd_queue(db: &dyn Storage, show_all: bool) -> Result<()> {
     let items = db.get_queue(show_all)?;
 
     if items.is_empty() {
@@ -4863,16 +9180,21 @@ fn cmd_queue(db: &Database, show_all: bool) -> Result<()> {
     }
 
     println!("AI Processing Queue:\n");
-    println!("{:<15} {:<12} {:<8} {:<20} {:<6}", "VIDEO_ID", "STATUS", "PRIORITY", "CREATED", "CLAIMS");
-    println!("{}", "-".repeat(65));
+    println!("{:<15} {:<12} {:<8} {:>8} {:<20} {:<6}", "VIDEO_ID", "STATUS", "PRIORITY", "LENGTH", "CREATED", "CLAIMS");
+    println!("{}", "-".repeat(74));
 
     for item in items {
         let created = item.created_at.format("%Y-%m-%d %H:%M").to_string();
+        let length = db.get_video(&item.video_id)?
+            .and_then(|v| v.duration)
+            .map(format_duration)
+            .unwrap_or_else(|| "-".to_string());
         println!(
-            "{:<15} {:<12} {:<8} {:<20} {:<6}",
+            "{:<15} {:<12} {:<8} {:>8} {:<20} {:<6}",
             &item.video_id[..item.video_id.len().min(14)],
             item.status.as_str(),
             item.priority,
+            length,
             created,
             item.claims_extracted
         );
@@ -4884,7 +9206,7 @@ fn cmd_queue(db: &Database, show_all: bool) -> Result<()> {
     Ok(())
 }
 
-fn cmd_queue_add(db: &Database, video_id: &str, priority: i32) -> Result<()> {
+fn cmd_queue_add(db: &dyn Storage, video_id: &str, priority: i32) -> Result<()> {
     // Check if video exists
     if db.get_video(video_id)?.is_none() {
         anyhow::bail!("Video '{}' not found", video_id);
@@ -4895,7 +9217,7 @@ fn cmd_queue_add(db: &Database, video_id: &str, priority: i32) -> Result<()> {
     Ok(())
 }
 
-fn cmd_queue_skip(db: &Database, video_id: &str) -> Result<()> {
+fn cmd_queue_skip(db: &dyn Storage, video_id: &str) -> Result<()> {
     if db.queue_skip(video_id)? {
         println!("Skipped '{}'", video_id);
     } else {
@@ -4904,7 +9226,7 @@ fn cmd_queue_skip(db: &Database, video_id: &str) -> Result<()> {
     Ok(())
 }
 
-fn cmd_queue_reset(db: &Database, video_id: &str) -> Result<()> {
+fn cmd_queue_reset(db: &dyn Storage, video_id: &str) -> Result<()> {
     if db.queue_reset(video_id)? {
         println!("Reset '{}' to pending", video_id);
     } else {
@@ -4913,7 +9235,7 @@ fn cmd_queue_reset(db: &Database, video_id: &str) -> Result<()> {
     Ok(())
 }
 
-fn cmd_queue_start(db: &Database, video_id: &str) -> Result<()> {
+fn cmd_queue_start(db: &dyn Storage, video_id: &str) -> Result<()> {
     if db.queue_start(video_id)? {
         println!("Started processing '{}'", video_id);
     } else {
@@ -4922,7 +9244,7 @@ fn cmd_queue_start(db: &Database, video_id: &str) -> Result<()> {
     Ok(())
 }
 
-fn cmd_queue_complete(db: &Database, video_id: &str, claims: i32) -> Result<()> {
+fn cmd_queue_complete(db: &dyn Storage, video_id: &str, claims: i32) -> Result<()> {
     if db.queue_complete(video_id, claims)? {
         println!("Completed '{}' with {} claims extracted", video_id, claims);
     } else {
@@ -4931,7 +9253,7 @@ fn cmd_queue_complete(db: &Database, video_id: &str, claims: i32) -> Result<()>
     Ok(())
 }
 
-fn cmd_queue_fail(db: &Database, video_id: &str, reason: &str) -> Result<()> {
+fn cmd_queue_fail(db: &dyn Storage, video_id: &str, reason: &str) -> Result<()> {
     if db.queue_fail(video_id, reason)? {
         println!("Marked '{}' as failed: {}", video_id, reason);
     } else {
@@ -4940,7 +9262,7 @@ fn cmd_queue_fail(db: &Database, video_id: &str, reason: &str) -> Result<()> {
     Ok(())
 }
 
-fn cmd_queue_clear(db: &Database, completed: bool, failed: bool) -> Result<()> {
+fn cmd_queue_clear(db: &dyn Storage, completed: bool, failed: bool) -> Result<()> {
     use engine::ProcessingStatus;
 
     if !completed && !failed {
@@ -4960,13 +9282,207 @@ fn cmd_queue_clear(db: &Database, completed: bool, failed: bool) -> Result<()> {
     Ok(())
 }
 
-fn cmd_export_transcript(db: &Database, video_id: &str) -> Result<()> {
+fn cmd_process(
+    db: &dyn Storage,
+    video_id: Option<&str>,
+    limit: Option<usize>,
+    tokens: i32,
+    llm: &LlmOptions,
+    embedding: &EmbeddingOptions,
+) -> Result<()> {
+    use engine::ProcessingStatus;
+
+    if let Some(id) = video_id {
+        if db.get_video(id)?.is_none() {
+            println!("Video not found: {}", id);
+            return Ok(());
+        }
+        match db.get_queue_item(id)? {
+            None => {
+                db.add_to_queue(id, 0)?;
+            }
+            Some(item) if item.status == ProcessingStatus::Completed => {
+                println!("'{}' was already processed ({} claims); 'queue-reset {}' to run it again", id, item.claims_extracted, id);
+                return Ok(());
+            }
+            Some(item) if item.status != ProcessingStatus::Pending => {
+                db.queue_reset(id)?;
+            }
+            Some(_) => {}
+        }
+    }
+
+    let config = llm.config(db)?;
+    let chat = tracked_chat(db, &config, "extract-claims")?;
+    println!("Extracting claims with {} ({})\n", config.model, config.provider.as_str());
+
+    let (mut completed, mut failed, mut claims) = (0, 0, 0);
+    loop {
+        if limit.is_some_and(|n| completed + failed >= n) {
+            break;
+        }
+        let id = match video_id {
+            Some(_) if completed + failed > 0 => break,
+            Some(id) => id.to_string(),
+            None => match db.get_next_pending()? {
+                Some(item) => item.video_id,
+                None => break,
+            },
+        };
+
+        let title = db.get_video(&id)?.map(|v| v.title).unwrap_or_default();
+        println!("{} {}", id, truncate(&title, 60));
+        db.queue_start(&id)?;
+        match extract_video_claims(db, chat.as_ref(), &config, &id, tokens) {
+            Ok(count) => {
+                db.queue_complete(&id, count as i32)?;
+                println!("  Done: {} claims\n", count);
+                completed += 1;
+                claims += count;
+            }
+            Err(e) => {
+                db.queue_fail(&id, &format!("{:#}", e))?;
+                println!("  Failed: {:#}\n", e);
+                failed += 1;
+            }
+        }
+    }
+
+    if completed + failed == 0 {
+        println!("Nothing pending in the queue.");
+    } else {
+        println!("Processed {} video(s): {} claims extracted, {} failed", completed + failed, claims, failed);
+        if claims > 0 {
+            println!("Check them with 'review --needs-review'.");
+        }
+    }
+    drain_embedding_queue(db, embedding)
+}
+
+/// A video's stored chunks, chunking (and saving) its transcript first if needed.
+fn chunks_for_llm(db: &dyn Storage, video_id: &str, tokens: i32) -> Result<Vec<engine::TranscriptChunk>> {
+    let chunks = if db.has_chunks(video_id)? {
+        db.get_transcript_chunks(video_id)?
+    } else {
+        let transcript = db.get_transcript(video_id)?.ok_or_else(|| anyhow::anyhow!("No transcript"))?;
+        let chunks = chunk_transcript(&transcript, tokens, DEFAULT_CHUNK_OVERLAP);
+        db.save_transcript_chunks(video_id, &chunks)?;
+        if auto_embed(db)? {
+            for chunk in &chunks {
+                db.queue_embedding(engine::EmbeddingSource::Chunk, &format!("{}:{}", video_id, chunk.chunk_index))?;
+            }
+        }
+        chunks
+    };
+    if chunks.is_empty() {
+        anyhow::bail!("Transcript is empty");
+    }
+    Ok(chunks)
+}
+
+/// Run claim extraction over every chunk of a video, then store all claims and links
+/// at once, so a failure part-way leaves nothing half-imported.
+fn extract_video_claims(
+    db: &dyn Storage,
+    chat: &dyn engine::ChatProvider,
+    config: &engine::LLMConfig,
+    video_id: &str,
+    tokens: i32,
+) -> Result<usize> {
+    use engine::ai::extraction;
+    use engine::ai::validation::complete_validated;
+    use engine::{ClaimCategory, Confidence, LinkType};
+
+    let video = db.get_video(video_id)?.ok_or_else(|| anyhow::anyhow!("Video not found"))?;
+    let chunks = chunks_for_llm(db, video_id, tokens)?;
+
+    let mut batches = Vec::with_capacity(chunks.len());
+    for (i, chunk) in chunks.iter().enumerate() {
+        let prompt = extraction::chunk_prompt(&video.title, chunk);
+        let (batch, repaired) = complete_validated(chat, extraction::SYSTEM_PROMPT, &prompt, extraction::parse_batch)
+            .with_context(|| format!("chunk {}", i + 1))?;
+        println!("  Chunk {}/{}{}", i + 1, chunks.len(), reply_note(chat, config, repaired));
+        batches.push((chunk, batch, chat.provider().to_string(), chat.model().to_string()));
+    }
+
+    let run_id = format!("{}-{}", video_id, chrono::Utc::now().format("%Y%m%d%H%M%S"));
+    let auto = auto_embed(db)?;
+    db.with_transaction(|db| {
+        let mut count = 0;
+        for (chunk, batch, provider, model) in &batches {
+            // Claim IDs by position in the batch, so links can be resolved
+            let mut ids: Vec<Option<i64>> = Vec::with_capacity(batch.claims.len());
+            for c in &batch.claims {
+                if c.text.trim().is_empty() {
+                    ids.push(None);
+                    continue;
+                }
+                let category = c.category.as_deref().and_then(ClaimCategory::from_str).unwrap_or(ClaimCategory::Factual);
+                let confidence = c.confidence.as_deref().and_then(Confidence::from_str).unwrap_or(Confidence::Medium);
+                let timestamp = c
+                    .timestamp
+                    .filter(|t| (chunk.start_time..=chunk.end_time).contains(t))
+                    .unwrap_or(chunk.start_time);
+                let claim = db.create_claim(c.text.trim(), video_id, Some(timestamp), c.quote.trim(), category, confidence)?;
+                db.set_claim_provenance(
+                    claim.id,
+                    Some(provider),
+                    Some(model),
+                    Some(extraction::PROMPT_VERSION),
+                    Some(&run_id),
+                )?;
+                if auto {
+                    db.queue_embedding(engine::EmbeddingSource::Claim, &claim.id.to_string())?;
+                }
+                ids.push(Some(claim.id));
+                count += 1;
+            }
+            for link in &batch.links {
+                let (Some(Some(from)), Some(Some(to))) = (ids.get(link.from), ids.get(link.to)) else { continue };
+                let Some(link_type) = LinkType::from_str(&link.link_type) else { continue };
+                if from != to {
+                    db.create_claim_link(*from, *to, link_type)?;
+                }
+            }
+        }
+        Ok(count)
+    })
+}
+
+fn cmd_export_transcript(db: &dyn Storage, video_id: &str, format: &str) -> Result<()> {
+    use engine::transcript::export;
+
     let video = db.get_video(video_id)?
         .ok_or_else(|| anyhow::anyhow!("Video '{}' not found", video_id))?;
 
     let transcript = db.get_transcript(video_id)?
         .ok_or_else(|| anyhow::anyhow!("No transcript for video '{}'", video_id))?;
 
+    match format {
+        "timestamped" => {}
+        "txt" => {
+            print!("{}", export::to_plain_text(&transcript));
+            return Ok(());
+        }
+        "srt" => {
+            print!("{}", export::to_srt(&transcript));
+            return Ok(());
+        }
+        "vtt" => {
+            print!("{}", export::to_vtt(&transcript));
+            return Ok(());
+        }
+        "json" => {
+            let json = serde_json::json!({ "video": video, "transcript": transcript });
+            println!("{}", serde_json::to_string_pretty(&json)?);
+            return Ok(());
+        }
+        _ => {
+            println!("Invalid format: {}. Valid options: timestamped, txt, srt, vtt, json", format);
+            return Ok(());
+        }
+    }
+
     // Print header comment with video info
     println!("# Video: {}", video.title);
     println!("# ID: {}", video.id);
@@ -4982,13 +9498,73 @@ fn cmd_export_transcript(db: &Database, video_id: &str) -> Result<()> {
     for segment in &transcript.segments {
         let minutes = (segment.start_time / 60.0) as u32;
         let seconds = (segment.start_time % 60.0) as u32;
-        println!("[{:02}:{:02}] {}", minutes, seconds, segment.text);
+        match &segment.speaker {
+            Some(name) => println!("[{:02}:{:02}] {}: {}", minutes, seconds, name, segment.text),
+            None => println!("[{:02}:{:02}] {}", minutes, seconds, segment.text),
+        }
     }
 
     Ok(())
 }
 
-fn cmd_export_queue(db: &Database) -> Result<()> {
+fn cmd_edit_transcript(db: &dyn Storage, video_id: &str, edit: Option<(usize, String)>) -> Result<()> {
+    let transcript = match db.get_transcript(video_id)? {
+        Some(t) => t,
+        None => {
+            println!("No transcript for video: {}", video_id);
+            return Ok(());
+        }
+    };
+
+    let segments = match edit {
+        Some((number, text)) => {
+            let mut segments = transcript.segments.clone();
+            let Some(segment) = number.checked_sub(1).and_then(|i| segments.get_mut(i)) else {
+                println!("Segment {} out of range (1-{})", number, segments.len());
+                return Ok(());
+            };
+            println!("Before: {}", segment.text);
+            segment.text = text.trim().to_string();
+            println!("After:  {}", segment.text);
+            segments
+        }
+        None => {
+            // Round-trip through SRT so timings can be adjusted and cues deleted too
+            let path = std::env::temp_dir().join(format!("{}.edit.srt", video_id));
+            let original = engine::transcript::export::to_srt(&transcript);
+            std::fs::write(&path, &original)?;
+
+            let editor = std::env::var("VISUAL")
+                .or_else(|_| std::env::var("EDITOR"))
+                .unwrap_or_else(|_| "vi".to_string());
+            let status = std::process::Command::new("sh")
+                .arg("-c")
+                .arg(format!("{} \"$1\"", editor))
+                .arg(&editor)
+                .arg(&path)
+                .status()?;
+
+            let content = std::fs::read_to_string(&path)?;
+            let _ = std::fs::remove_file(&path);
+            if !status.success() {
+                println!("Editor exited with an error; transcript unchanged.");
+                return Ok(());
+            }
+
+            if content == original {
+                println!("No changes.");
+                return Ok(());
+            }
+            engine::transcript::parser::parse_subtitles(&content, video_id)?.segments
+        }
+    };
+
+    db.update_transcript_segments(video_id, &segments)?;
+    println!("Updated transcript for {} ({} segments); search index rebuilt.", video_id, segments.len());
+    Ok(())
+}
+
+fn cmd_export_queue(db: &dyn Storage) -> Result<()> {
     let ids = db.get_pending_video_ids()?;
 
     if ids.is_empty() {
@@ -5009,7 +9585,7 @@ fn cmd_export_queue(db: &Database) -> Result<()> {
 // ============================================
 
 fn cmd_add_source(
-    db: &Database,
+    db: &dyn Storage,
     title: &str,
     author: Option<&str>,
     source_type: &str,
@@ -5032,7 +9608,7 @@ fn cmd_add_source(
     Ok(())
 }
 
-fn cmd_list_sources(db: &Database) -> Result<()> {
+fn cmd_list_sources(db: &dyn Storage) -> Result<()> {
     let sources = db.get_sources()?;
     if sources.is_empty() {
         println!("No sources in knowledge base.");
@@ -5053,14 +9629,14 @@ fn cmd_list_sources(db: &Database) -> Result<()> {
     Ok(())
 }
 
-fn cmd_cite_source(db: &Database, video_id: &str, source_id: i64, timestamp: Option<f64>, context: Option<&str>) -> Result<()> {
+fn cmd_cite_source(db: &dyn Storage, video_id: &str, source_id: i64, timestamp: Option<f64>, context: Option<&str>) -> Result<()> {
     db.cite_source(video_id, source_id, timestamp, context)?;
     println!("Cited source #{} in video {}", source_id, video_id);
     Ok(())
 }
 
 fn cmd_add_scholar(
-    db: &Database,
+    db: &dyn Storage,
     name: &str,
     field: Option<&str>,
     era: Option<&str>,
@@ -5083,34 +9659,107 @@ fn cmd_add_scholar(
     Ok(())
 }
 
-fn cmd_list_scholars(db: &Database) -> Result<()> {
+fn cmd_list_scholars(db: &dyn Storage) -> Result<()> {
     let scholars = db.get_scholars()?;
     if scholars.is_empty() {
         println!("No scholars in knowledge base.");
         return Ok(());
     }
 
+    let machine = scholars.iter().any(|s| s.extracted_by.is_some());
     println!("{:<5} {:<30} {:<20} {:<15}", "ID", "NAME", "FIELD", "ERA");
     println!("{}", "-".repeat(72));
     for s in scholars {
         println!("{:<5} {:<30} {:<20} {:<15}",
             s.id,
-            truncate(&s.name, 28),
+            format!("{}{}", truncate(&s.name, 27), if s.extracted_by.is_some() { "*" } else { "" }),
             s.field.as_deref().map(|f| truncate(f, 18)).unwrap_or("-".to_string()),
             s.era.as_deref().map(|e| truncate(e, 13)).unwrap_or("-".to_string()),
         );
     }
+    if machine {
+        println!("\n* machine-extracted (extract-entities)");
+    }
     Ok(())
 }
 
-fn cmd_cite_scholar(db: &Database, video_id: &str, scholar_id: i64, timestamp: Option<f64>, context: Option<&str>) -> Result<()> {
+fn cmd_cite_scholar(db: &dyn Storage, video_id: &str, scholar_id: i64, timestamp: Option<f64>, context: Option<&str>) -> Result<()> {
     db.cite_scholar(video_id, scholar_id, timestamp, context)?;
     println!("Cited scholar #{} in video {}", scholar_id, video_id);
     Ok(())
 }
 
+fn cmd_cite_claim(
+    db: &dyn Storage,
+    claim_id: i64,
+    source_id: Option<i64>,
+    scholar_id: Option<i64>,
+    attribution: Option<&str>,
+    remove: bool,
+) -> Result<()> {
+    if db.get_claim(claim_id)?.is_none() {
+        println!("Claim not found: {}", claim_id);
+        return Ok(());
+    }
+
+    if source_id.is_none() && scholar_id.is_none() {
+        let sources = db.get_claim_sources(claim_id)?;
+        let scholars = db.get_claim_scholars(claim_id)?;
+        if sources.is_empty() && scholars.is_empty() {
+            println!("Claim #{} cites no sources or scholars.", claim_id);
+            return Ok(());
+        }
+        for s in &sources {
+            println!("  source  #{:<4} {} ({})", s.id, truncate(&s.title, 50), s.citation());
+        }
+        for cs in &scholars {
+            match &cs.attribution {
+                Some(a) => println!("  scholar #{:<4} {} ({})", cs.scholar.id, cs.scholar.name, a),
+                None => println!("  scholar #{:<4} {}", cs.scholar.id, cs.scholar.name),
+            }
+        }
+        return Ok(());
+    }
+
+    if let Some(source_id) = source_id {
+        let Some(source) = db.get_source(source_id)? else {
+            println!("Source not found: {}", source_id);
+            return Ok(());
+        };
+        if remove {
+            if db.uncite_claim_source(claim_id, source_id)? {
+                println!("Removed source #{} from claim #{}", source_id, claim_id);
+            } else {
+                println!("Claim #{} does not cite source #{}", claim_id, source_id);
+            }
+        } else if db.cite_claim_source(claim_id, source_id)? {
+            println!("Claim #{} now cites {} (per {})", claim_id, source.title, source.citation());
+        } else {
+            println!("Claim #{} already cites source #{}", claim_id, source_id);
+        }
+    }
+
+    if let Some(scholar_id) = scholar_id {
+        let Some(scholar) = db.get_scholar(scholar_id)? else {
+            println!("Scholar not found: {}", scholar_id);
+            return Ok(());
+        };
+        if remove {
+            if db.uncite_claim_scholar(claim_id, scholar_id)? {
+                println!("Removed scholar #{} from claim #{}", scholar_id, claim_id);
+            } else {
+                println!("Claim #{} does not cite scholar #{}", claim_id, scholar_id);
+            }
+        } else {
+            db.cite_claim_scholar(claim_id, scholar_id, attribution)?;
+            println!("Claim #{} now cites {}", claim_id, scholar.name);
+        }
+    }
+    Ok(())
+}
+
 fn cmd_add_visual(
-    db: &Database,
+    db: &dyn Storage,
     video_id: &str,
     description: &str,
     timestamp: f64,
@@ -5146,7 +9795,7 @@ fn cmd_add_visual(
     Ok(())
 }
 
-fn cmd_list_visuals(db: &Database, video_id: &str) -> Result<()> {
+fn cmd_list_visuals(db: &dyn Storage, video_id: &str) -> Result<()> {
     let visuals = db.get_visuals_for_video(video_id)?;
     if visuals.is_empty() {
         println!("No visuals for video {}.", video_id);
@@ -5164,7 +9813,7 @@ fn cmd_list_visuals(db: &Database, video_id: &str) -> Result<()> {
 }
 
 fn cmd_define_term(
-    db: &Database,
+    db: &dyn Storage,
     term: &str,
     definition: &str,
     domain: Option<&str>,
@@ -5188,28 +9837,32 @@ fn cmd_define_term(
     Ok(())
 }
 
-fn cmd_list_terms(db: &Database) -> Result<()> {
+fn cmd_list_terms(db: &dyn Storage) -> Result<()> {
     let terms = db.get_terms()?;
     if terms.is_empty() {
         println!("No terms in knowledge base.");
         return Ok(());
     }
 
+    let machine = terms.iter().any(|t| t.extracted_by.is_some());
     println!("{:<5} {:<25} {:<50} {:<15}", "ID", "TERM", "DEFINITION", "DOMAIN");
     println!("{}", "-".repeat(97));
     for t in terms {
         println!("{:<5} {:<25} {:<50} {:<15}",
             t.id,
-            truncate(&t.term, 23),
+            format!("{}{}", truncate(&t.term, 22), if t.extracted_by.is_some() { "*" } else { "" }),
             truncate(&t.definition, 48),
             t.domain.as_deref().map(|d| truncate(d, 13)).unwrap_or("-".to_string()),
         );
     }
+    if machine {
+        println!("\n* machine-extracted (extract-entities)");
+    }
     Ok(())
 }
 
 fn cmd_add_cited_evidence(
-    db: &Database,
+    db: &dyn Storage,
     video_id: &str,
     description: &str,
     evidence_type: &str,
@@ -5243,7 +9896,7 @@ fn cmd_add_cited_evidence(
     Ok(())
 }
 
-fn cmd_list_cited_evidence(db: &Database, video_id: &str) -> Result<()> {
+fn cmd_list_cited_evidence(db: &dyn Storage, video_id: &str) -> Result<()> {
     let evidence = db.get_evidence_for_video(video_id)?;
     if evidence.is_empty() {
         println!("No evidence for video {}.", video_id);
@@ -5259,7 +9912,7 @@ fn cmd_list_cited_evidence(db: &Database, video_id: &str) -> Result<()> {
 }
 
 fn cmd_add_quote(
-    db: &Database,
+    db: &dyn Storage,
     video_id: &str,
     text: &str,
     speaker: Option<&str>,
@@ -5284,7 +9937,7 @@ fn cmd_add_quote(
     Ok(())
 }
 
-fn cmd_list_quotes(db: &Database, video_id: &str) -> Result<()> {
+fn cmd_list_quotes(db: &dyn Storage, video_id: &str) -> Result<()> {
     let quotes = db.get_quotes_for_video(video_id)?;
     if quotes.is_empty() {
         println!("No quotes for video {}.", video_id);
@@ -5305,10 +9958,670 @@ fn cmd_list_quotes(db: &Database, video_id: &str) -> Result<()> {
     Ok(())
 }
 
+fn cmd_extract_quotes(db: &dyn Storage, video_id: &str) -> Result<()> {
+    let transcript = match db.get_transcript(video_id)? {
+        Some(t) => t,
+        None => {
+            println!("No transcript for video: {}", video_id);
+            return Ok(());
+        }
+    };
+
+    let candidates = engine::transcript::quotes::extract_quotes(&transcript);
+    let mut staged = 0;
+    for c in &candidates {
+        if let Some(id) = db.stage_pending_quote(
+            video_id,
+            &c.text,
+            c.speaker.as_deref(),
+            Some(c.timestamp),
+            c.reason,
+            Some(&c.context),
+        )? {
+            let mins = (c.timestamp / 60.0) as u32;
+            let secs = (c.timestamp % 60.0) as u32;
+            println!("#{:<5} [{:02}:{:02}] {:<15} \"{}\"", id, mins, secs, c.reason, truncate(&c.text, 70));
+            staged += 1;
+        }
+    }
+
+    println!("\nStaged {} quote(s) ({} found, {} already known).", staged, candidates.len(), candidates.len() - staged);
+    if staged > 0 {
+        println!("Review with: pending-quotes --video {}", video_id);
+    }
+    Ok(())
+}
+
+/// Run entity extraction over every chunk of a video, then store everything at once,
+/// each row flagged with the provider, model and prompt that produced it.
+fn cmd_extract_entities(db: &dyn Storage, video_id: &str, tokens: i32, llm: &LlmOptions) -> Result<()> {
+    use engine::ai::entities;
+    use engine::ai::validation::complete_validated;
+
+    let video = match db.get_video(video_id)? {
+        Some(v) => v,
+        None => {
+            println!("Video not found: {}", video_id);
+            return Ok(());
+        }
+    };
+    let config = llm.config(db)?;
+    let chat = tracked_chat(db, &config, "extract-entities")?;
+    let chunks = chunks_for_llm(db, video_id, tokens)?;
+    println!("Extracting entities with {} ({})\n", config.model, config.provider.as_str());
+
+    let mut batches = Vec::with_capacity(chunks.len());
+    for (i, chunk) in chunks.iter().enumerate() {
+        let prompt = entities::chunk_prompt(&video.title, chunk);
+        let (batch, repaired) = complete_validated(chat.as_ref(), entities::SYSTEM_PROMPT, &prompt, entities::parse_entities)
+            .with_context(|| format!("chunk {}", i + 1))?;
+        println!("  Chunk {}/{}{}", i + 1, chunks.len(), reply_note(chat.as_ref(), &config, repaired));
+        let extracted_by = format!("{}/{} {}", chat.provider(), chat.model(), entities::PROMPT_VERSION);
+        batches.push((chunk, batch, extracted_by));
+    }
+
+    let at = |chunk: &engine::TranscriptChunk, t: Option<f64>| {
+        t.filter(|t| (chunk.start_time..=chunk.end_time).contains(t)).unwrap_or(chunk.start_time)
+    };
+    // Quotes must appear in the excerpt; compare words only, as transcripts are loosely punctuated
+    let words = |s: &str| {
+        s.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).map(str::to_lowercase).collect::<Vec<_>>().join(" ")
+    };
+
+    let mut seen: Vec<String> = Vec::new();
+    let (mut scholars, mut terms, mut places, mut quotes) = (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    let (mut unplaced, mut unquoted) = (Vec::new(), 0);
+    db.with_transaction(|db| {
+        for (chunk, batch, extracted_by) in &batches {
+            for s in &batch.scholars {
+                let name = s.name.trim();
+                if name.is_empty() || seen.contains(&format!("scholar:{}", name.to_lowercase())) {
+                    continue;
+                }
+                seen.push(format!("scholar:{}", name.to_lowercase()));
+                let (id, created) = db.add_extracted_scholar(
+                    video_id,
+                    name,
+                    s.field.as_deref(),
+                    Some(at(chunk, s.timestamp)),
+                    s.context.as_deref(),
+                    extracted_by,
+                )?;
+                scholars.push(format!("#{} {}{}", id, name, if created { " (new)" } else { "" }));
+            }
+            for t in &batch.terms {
+                let (term, definition) = (t.term.trim(), t.definition.trim());
+                if term.is_empty() || definition.is_empty() {
+                    continue;
+                }
+                if let Some(id) = db.add_extracted_term(term, definition, video_id, Some(at(chunk, t.timestamp)), extracted_by)? {
+                    terms.push(format!("#{} {}: {}", id, term, truncate(definition, 60)));
+                }
+            }
+            for p in &batch.places {
+                let name = p.name.trim();
+                if name.is_empty() || seen.contains(&format!("place:{}", name.to_lowercase())) {
+                    continue;
+                }
+                seen.push(format!("place:{}", name.to_lowercase()));
+                match db.add_extracted_location(
+                    video_id,
+                    name,
+                    p.coordinates(),
+                    Some(at(chunk, p.timestamp)),
+                    p.context.as_deref(),
+                    extracted_by,
+                )? {
+                    Some((location, true)) => places.push(format!("{} ({:.2}, {:.2})", location.name, location.lat, location.lon)),
+                    Some((_, false)) => {}
+                    None => unplaced.push(name.to_string()),
+                }
+            }
+            for q in &batch.quotes {
+                let text = q.text.trim();
+                if text.is_empty() {
+                    continue;
+                }
+                if !words(&chunk.text).contains(&words(text)) {
+                    unquoted += 1;
+                    continue;
+                }
+                let speaker = q.speaker.as_deref().map(str::trim).filter(|s| !s.is_empty());
+                if let Some(id) = db.stage_pending_quote(video_id, text, speaker, Some(at(chunk, q.timestamp)), "llm", None)? {
+                    quotes.push(format!("#{} \"{}\"", id, truncate(text, 70)));
+                }
+            }
+        }
+        Ok(())
+    })?;
+
+    println!();
+    for (label, lines) in [("Scholars", &scholars), ("Terms", &terms), ("Places", &places), ("Quotes staged", &quotes)] {
+        println!("{}: {}", label, lines.len());
+        for line in lines {
+            println!("  {}", line);
+        }
+    }
+    if !unplaced.is_empty() {
+        println!("\nNot pinned (no coordinates): {}", unplaced.join(", "));
+    }
+    if unquoted > 0 {
+        println!("Dropped {} quote(s) not found verbatim in the transcript.", unquoted);
+    }
+    if !quotes.is_empty() {
+        println!("\nReview quotes with: pending-quotes --video {}", video_id);
+    }
+    Ok(())
+}
+
+fn cmd_pending_quotes(db: &dyn Storage, video_id: Option<&str>) -> Result<()> {
+    let quotes = db.get_pending_quotes(video_id)?;
+    if quotes.is_empty() {
+        println!("No pending quotes.");
+        return Ok(());
+    }
+
+    println!("{:<6} {:<14} {:<8} {:<15} {:<20} Text", "ID", "Video", "Time", "Reason", "Speaker");
+    println!("{}", "-".repeat(110));
+    for q in quotes {
+        let ts = q.timestamp.map(|t| format!("{:02}:{:02}", (t / 60.0) as u32, (t % 60.0) as u32)).unwrap_or("-".to_string());
+        println!("{:<6} {:<14} {:<8} {:<15} {:<20} \"{}\"",
+            q.id,
+            truncate(&q.video_id, 12),
+            ts,
+            q.reason,
+            q.speaker.as_deref().map(|s| truncate(s, 18)).unwrap_or("-".to_string()),
+            truncate(&q.text, 60),
+        );
+    }
+    Ok(())
+}
+
+fn cmd_confirm_quote(db: &dyn Storage, id: i64, speaker: Option<&str>) -> Result<()> {
+    match db.confirm_pending_quote(id, speaker)? {
+        Some(quote_id) => println!("Confirmed pending quote #{} as quote #{}", id, quote_id),
+        None => println!("Pending quote not found: {}", id),
+    }
+    Ok(())
+}
+
+fn cmd_reject_quote(db: &dyn Storage, id: i64) -> Result<()> {
+    if db.reject_pending_quote(id)? {
+        println!("Rejected pending quote #{}", id);
+    } else {
+        println!("Pending quote not found: {}", id);
+    }
+    Ok(())
+}
+
+fn cmd_archive(db: &dyn Storage, id: &str, claim: bool, archive: bool) -> Result<()> {
+    let (changed, label) = if claim {
+        let claim_id: i64 = id.parse().map_err(|_| anyhow::anyhow!("Invalid claim ID: {}", id))?;
+        let changed = if archive { db.archive_claim(claim_id)? } else { db.unarchive_claim(claim_id)? };
+        (changed, format!("claim #{}", claim_id))
+    } else {
+        let changed = if archive { db.archive_video(id)? } else { db.unarchive_video(id)? };
+        (changed, format!("video {}", id))
+    };
+
+    match (changed, archive) {
+        (true, true) => println!("Archived {} (restore with unarchive, delete for good with purge)", label),
+        (true, false) => println!("Restored {}", label),
+        (false, true) => println!("Not found or already archived: {}", label),
+        (false, false) => println!("Not found or not archived: {}", label),
+    }
+    Ok(())
+}
+
+fn cmd_purge(db: &dyn Storage, older_than: Option<i64>, yes: bool) -> Result<()> {
+    let before = older_than.map(|days| chrono::Utc::now() - chrono::Duration::days(days));
+    let videos = db.list_archived_videos(before)?;
+    let claims = db.list_archived_claims(before)?;
+
+    if videos.is_empty() && claims.is_empty() {
+        println!("Trash is empty.");
+        return Ok(());
+    }
+
+    for (video, archived_at) in &videos {
+        println!("  video  {:<14} {}  {}", video.id, archived_at.format("%Y-%m-%d"), truncate(&video.title, 50));
+    }
+    for (claim, archived_at) in &claims {
+        println!("  claim  {:<14} {}  {}", format!("#{}", claim.id), archived_at.format("%Y-%m-%d"), truncate(&claim.text, 50));
+    }
+
+    if !yes {
+        println!("\n{} video(s) and {} claim(s) in the trash. Re-run with --yes to delete them permanently.", videos.len(), claims.len());
+        return Ok(());
+    }
+
+    let mut purged = 0;
+    for (video, _) in &videos {
+        purged += db.purge_video(&video.id)? as usize;
+    }
+    for (claim, _) in &claims {
+        purged += db.purge_claim(claim.id)? as usize;
+    }
+    println!("\nPurged {} item(s).", purged);
+    Ok(())
+}
+
+// Settings accepted by `engine config`: (key, default, description)
+const SETTINGS: &[(&str, &str, &str)] = &[
+    ("search.weights", "10,5,1", "bm25 weights for title, description and transcript"),
+    ("search.recency_boost", "0", "extra weight for recently published videos (0 = off, 1 = up to 2x)"),
+    ("embeddings.provider", "-", "ollama or openai; when set, hybrid and semantic embed text queries"),
+    ("embeddings.model", "-", "embedding model (default depends on the provider)"),
+    ("embeddings.default_model", "-", "model similar and hybrid compare stored vectors from (default: the most used)"),
+    ("embeddings.auto", "off", "on: fetch, chunk and add-claim queue new content and embed it right away"),
+    ("llm.provider", "-", "anthropic, openai or local; the chat model process extracts claims with"),
+    ("llm.model", "-", "chat model (default depends on the provider)"),
+    ("llm.key_env", "-", "environment variable with the API key (default ANTHROPIC_API_KEY / OPENAI_API_KEY)"),
+    ("llm.base_url", "-", "chat server URL (default: the provider's, or its *_BASE_URL / OLLAMA_HOST)"),
+    ("llm.temperature", "0", "sampling temperature for LLM commands"),
+    ("llm.timeout", "600", "seconds to wait for each LLM response"),
+    ("llm.price", "-", "USD per million input,output tokens for llm usage costs, e.g. 3,15 (default: built-in list)"),
+    ("llm.fallback", "-", "providers to try in order when the LLM fails, e.g. local:llama3.1,openai"),
+];
+
+fn validate_setting(key: &str, value: &str) -> Result<()> {
+    let valid = match key {
+        "search.weights" => engine::RankingProfile::parse_weights(value).is_some(),
+        "search.recency_boost" => value.parse::<f64>().is_ok_and(|b| b.is_finite() && b >= 0.0),
+        "embeddings.provider" => matches!(
+            engine::LLMProvider::from_str(value),
+            Some(engine::LLMProvider::Local | engine::LLMProvider::OpenAI)
+        ),
+        "embeddings.model" | "embeddings.default_model" => !value.trim().is_empty(),
+        "embeddings.auto" => matches!(value, "on" | "off"),
+        "llm.provider" => engine::LLMProvider::from_str(value).is_some(),
+        "llm.model" => !value.trim().is_empty(),
+        "llm.key_env" => {
+            value.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        "llm.base_url" => value.starts_with("http://") || value.starts_with("https://"),
+        "llm.temperature" => value.parse::<f32>().is_ok_and(|t| (0.0..=2.0).contains(&t)),
+        "llm.timeout" => value.parse::<u64>().is_ok_and(|t| t > 0),
+        "llm.price" => engine::ai::usage::parse_price(value).is_some(),
+        "llm.fallback" => engine::ai::chat::parse_chain(value).is_some(),
+        _ => true,
+    };
+    if !valid {
+        anyhow::bail!("Invalid value for {}: {}", key, value);
+    }
+    Ok(())
+}
+
+fn cmd_config(db: &dyn Storage, key: Option<&str>, value: Option<&str>, unset: bool) -> Result<()> {
+    let Some(key) = key else {
+        println!("{:<24} {:<12} DESCRIPTION", "KEY", "VALUE");
+        println!("{}", "-".repeat(90));
+        for (key, default, description) in SETTINGS {
+            let value = db.get_setting(key)?.unwrap_or_else(|| default.to_string());
+            println!("{:<24} {:<12} {}", key, value, description);
+        }
+        return Ok(());
+    };
+
+    let Some(&(_, default, _)) = SETTINGS.iter().find(|(k, _, _)| *k == key) else {
+        let known: Vec<&str> = SETTINGS.iter().map(|(k, _, _)| *k).collect();
+        anyhow::bail!("Unknown setting: {} (known: {})", key, known.join(", "));
+    };
+
+    if unset {
+        db.delete_setting(key)?;
+        println!("{} reset to {}", key, default);
+    } else if let Some(value) = value {
+        validate_setting(key, value)?;
+        db.set_setting(key, value)?;
+        println!("{} = {}", key, value);
+    } else {
+        println!("{}", db.get_setting(key)?.unwrap_or_else(|| default.to_string()));
+    }
+    Ok(())
+}
+
+const LLM_SETTINGS: &[&str] = &[
+    "llm.provider",
+    "llm.model",
+    "llm.key_env",
+    "llm.base_url",
+    "llm.temperature",
+    "llm.timeout",
+    "llm.price",
+    "llm.fallback",
+];
+
+#[allow(clippy::too_many_arguments)]
+fn cmd_llm_set(
+    db: &dyn Storage,
+    provider: Option<&str>,
+    model: Option<&str>,
+    key_env: Option<&str>,
+    base_url: Option<&str>,
+    temperature: Option<f32>,
+    fallback: Option<&str>,
+    reset: bool,
+) -> Result<()> {
+    if reset {
+        for key in LLM_SETTINGS {
+            db.delete_setting(key)?;
+        }
+        println!("LLM configuration cleared.");
+        return Ok(());
+    }
+
+    let temperature = temperature.map(|t| t.to_string());
+    let changes = [
+        ("llm.provider", provider),
+        ("llm.model", model),
+        ("llm.key_env", key_env),
+        ("llm.base_url", base_url),
+        ("llm.temperature", temperature.as_deref()),
+        ("llm.fallback", fallback),
+    ];
+    if changes.iter().all(|(_, value)| value.is_none()) {
+        println!("Nothing to set: pass --provider, --model, --key-env, --base-url, --temperature or --fallback.");
+        return Ok(());
+    }
+    for (key, value) in changes {
+        if let Some(value) = value {
+            validate_setting(key, value)?;
+        }
+    }
+
+    // A model name only makes sense for the provider it was chosen with, and a price for its model
+    if let (Some(provider), None) = (provider, model) {
+        let previous = db.get_setting("llm.provider")?;
+        let name = |p: &str| engine::LLMProvider::from_str(p).map(|p| p.as_str());
+        if previous.as_deref().and_then(name) != name(provider) {
+            db.delete_setting("llm.model")?;
+            db.delete_setting("llm.price")?;
+        }
+    }
+    if model.is_some() && db.get_setting("llm.model")?.as_deref() != model {
+        db.delete_setting("llm.price")?;
+    }
+    for (key, value) in changes {
+        if let Some(value) = value {
+            let value = match key {
+                "llm.provider" => engine::LLMProvider::from_str(value).map(|p| p.as_str()).unwrap_or(value),
+                _ => value,
+            };
+            db.set_setting(key, value)?;
+        }
+    }
+    cmd_llm_show(db, &LlmOptions { llm_provider: None, llm_model: None })
+}
+
+fn cmd_llm_show(db: &dyn Storage, llm: &LlmOptions) -> Result<()> {
+    let config = match llm.config(db) {
+        Ok(config) => config,
+        Err(e) => {
+            println!("{}", e);
+            return Ok(());
+        }
+    };
+
+    let source = |flag: &Option<String>, key: &str| -> Result<&'static str> {
+        Ok(if flag.is_some() {
+            "flag/environment"
+        } else if db.get_setting(key)?.is_some() {
+            "setting"
+        } else {
+            "default"
+        })
+    };
+    println!("{:<12} {} ({})", "Provider:", config.provider.as_str(), source(&llm.llm_provider, "llm.provider")?);
+    println!("{:<12} {} ({})", "Model:", config.model, source(&llm.llm_model, "llm.model")?);
+
+    let key_env = config.key_env.as_deref().or(engine::ai::chat::default_key_env(&config.provider));
+    match key_env {
+        Some(var) => {
+            let set = std::env::var(var).is_ok_and(|k| !k.is_empty());
+            println!("{:<12} {} ({})", "API key:", var, if set { "set" } else { "NOT SET" });
+        }
+        None => println!("{:<12} none needed", "API key:"),
+    }
+    let base_url = match (&config.base_url, &config.provider) {
+        (Some(url), _) => url.clone(),
+        (None, engine::LLMProvider::Anthropic) => {
+            std::env::var("ANTHROPIC_BASE_URL").unwrap_or_else(|_| "provider default".to_string())
+        }
+        (None, engine::LLMProvider::OpenAI) => {
+            std::env::var("OPENAI_BASE_URL").unwrap_or_else(|_| "provider default".to_string())
+        }
+        (None, engine::LLMProvider::Local) => {
+            std::env::var("OLLAMA_HOST").unwrap_or_else(|_| "http://localhost:11434".to_string())
+        }
+    };
+    println!("{:<12} {}", "Server:", base_url);
+    println!("{:<12} {}", "Temperature:", config.temperature);
+    let price = match db.get_setting("llm.price")? {
+        Some(p) => engine::ai::usage::parse_price(&p).map(|price| (price, "setting")),
+        None => engine::ai::usage::price_for(&config.provider, &config.model).map(|price| (price, "built-in")),
+    };
+    match price {
+        Some(((input, output), source)) => {
+            println!("{:<12} ${} in / ${} out per million tokens ({})", "Price:", input, output, source)
+        }
+        None => println!("{:<12} unknown (set with 'config llm.price IN,OUT')", "Price:"),
+    }
+    let fallbacks = fallback_configs(db, &config)?;
+    if !fallbacks.is_empty() {
+        let chain: Vec<String> = fallbacks.iter().map(|c| format!("{}/{}", c.provider.as_str(), c.model)).collect();
+        println!("{:<12} {}", "Fallback:", chain.join(", "));
+    }
+    Ok(())
+}
+
+fn cmd_llm_test(db: &dyn Storage, llm: &LlmOptions, stream: bool) -> Result<()> {
+    let config = llm.config(db)?;
+    let chat = tracked_chat(db, &config, "llm-test")?;
+    println!("Testing {} ({})...", config.model, config.provider.as_str());
+
+    let (system, prompt) = ("You are a connectivity check.", "Reply with the single word OK.");
+    let started = std::time::Instant::now();
+    if stream {
+        let mut first_token = None;
+        chat.complete_stream(system, prompt, &mut |token| {
+            first_token.get_or_insert_with(|| started.elapsed());
+            print!("{}", token);
+            let _ = std::io::Write::flush(&mut std::io::stdout());
+        })?;
+        println!();
+        let first_token = first_token.unwrap_or_else(|| started.elapsed());
+        println!("OK: first token after {:.1}s, done in {:.1}s", first_token.as_secs_f64(), started.elapsed().as_secs_f64());
+    } else {
+        let reply = chat.complete(system, prompt)?;
+        println!("OK in {:.1}s: {}", started.elapsed().as_secs_f64(), truncate(reply.trim(), 60));
+    }
+    if chat.provider() != config.provider.as_str() || chat.model() != config.model {
+        println!("Answered by fallback {}/{}", chat.provider(), chat.model());
+    }
+    Ok(())
+}
+
+fn cmd_llm_usage(db: &dyn Storage, by_day: bool, by_task: bool, days: Option<i64>) -> Result<()> {
+    use engine::UsageGrouping;
+
+    let (grouping, label) = match (by_day, by_task) {
+        (true, _) => (UsageGrouping::Day, "DAY"),
+        (_, true) => (UsageGrouping::Task, "TASK"),
+        _ => (UsageGrouping::Model, "MODEL"),
+    };
+    let since = days.map(|d| chrono::Utc::now() - chrono::Duration::days(d));
+    let rows = db.llm_usage_summary(grouping, since)?;
+    if rows.is_empty() {
+        println!("No LLM calls recorded{}.", days.map(|d| format!(" in the last {} days", d)).unwrap_or_default());
+        return Ok(());
+    }
+
+    let cost = |usd: Option<f64>, unpriced: i64| match usd {
+        Some(usd) => format!("${:.4}{}", usd, if unpriced > 0 { "*" } else { "" }),
+        None => "-".to_string(),
+    };
+    println!("{:<32} {:>6} {:>6} {:>12} {:>12} {:>9} {:>11}", label, "CALLS", "FAILED", "TOKENS IN", "TOKENS OUT", "AVG TIME", "COST");
+    println!("{}", "-".repeat(94));
+    for r in &rows {
+        println!(
+            "{:<32} {:>6} {:>6} {:>12} {:>12} {:>8.1}s {:>11}",
+            truncate(&r.key, 32),
+            r.calls,
+            r.failed,
+            r.input_tokens,
+            r.output_tokens,
+            r.latency_ms as f64 / r.calls.max(1) as f64 / 1000.0,
+            cost(r.cost_usd, r.unpriced)
+        );
+    }
+
+    let calls: i64 = rows.iter().map(|r| r.calls).sum();
+    let unpriced: i64 = rows.iter().map(|r| r.unpriced).sum();
+    let total_cost = rows.iter().filter_map(|r| r.cost_usd).reduce(|a, b| a + b);
+    println!("{}", "-".repeat(94));
+    println!(
+        "{:<32} {:>6} {:>6} {:>12} {:>12} {:>8.1}s {:>11}",
+        "Total",
+        calls,
+        rows.iter().map(|r| r.failed).sum::<i64>(),
+        rows.iter().map(|r| r.input_tokens).sum::<i64>(),
+        rows.iter().map(|r| r.output_tokens).sum::<i64>(),
+        rows.iter().map(|r| r.latency_ms).sum::<i64>() as f64 / calls.max(1) as f64 / 1000.0,
+        cost(total_cost, unpriced)
+    );
+    if unpriced > 0 {
+        println!("\n* {} call(s) with no known price are left out; set one with 'config llm.price IN,OUT'.", unpriced);
+    }
+    Ok(())
+}
+
+fn cmd_token_create(db: &dyn Storage, name: &str, write: bool) -> Result<()> {
+    let name = name.trim();
+    if name.is_empty() {
+        println!("Token name cannot be empty.");
+        return Ok(());
+    }
+    if db.list_api_tokens()?.iter().any(|t| t.name == name) {
+        println!("A token named '{}' already exists; revoke it first or pick another name.", name);
+        return Ok(());
+    }
+    let scope = if write { engine::TokenScope::Write } else { engine::TokenScope::Read };
+    let token = db.create_api_token(name, scope)?;
+    println!("Created {} token '{}':\n\n  {}\n", token.scope.as_str(), token.name, token.token);
+    println!("It won't be shown again. Send it as 'Authorization: Bearer <token>', or open the web UI at /?token=<token>.");
+    println!("'serve' now requires a token on every API request.");
+    Ok(())
+}
+
+fn cmd_token_list(db: &dyn Storage) -> Result<()> {
+    let tokens = db.list_api_tokens()?;
+    if tokens.is_empty() {
+        println!("No API tokens; the server answers API requests without one.");
+        return Ok(());
+    }
+    println!("{:<24} {:<6} {:<12} {:<17} LAST USED", "NAME", "SCOPE", "TOKEN", "CREATED");
+    println!("{}", "-".repeat(80));
+    for t in &tokens {
+        println!(
+            "{:<24} {:<6} {:<12} {:<17} {}",
+            truncate(&t.name, 24),
+            t.scope.as_str(),
+            format!("{}...", t.prefix),
+            t.created_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+            t.last_used_at
+                .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| "never".to_string()),
+        );
+    }
+    Ok(())
+}
+
+fn cmd_token_revoke(db: &dyn Storage, name: &str) -> Result<()> {
+    if db.revoke_api_token(name)? {
+        println!("Revoked token '{}'", name);
+        if !db.has_api_tokens()? {
+            println!("No tokens are left; the server answers API requests without one unless started with --auth-token.");
+        }
+    } else {
+        println!("Token not found: {}", name);
+    }
+    Ok(())
+}
+
+fn cmd_alias(db: &dyn Storage, name: Option<&str>, aliases: &[String], remove: bool) -> Result<()> {
+    if let Some(name) = name {
+        if remove {
+            let removed = if aliases.is_empty() {
+                db.remove_alias(name, None)?
+            } else {
+                let mut n = 0;
+                for alias in aliases {
+                    n += db.remove_alias(name, Some(alias))?;
+                }
+                n
+            };
+            println!("Removed {} alias(es) of {}", removed, name);
+            return Ok(());
+        }
+        for alias in aliases {
+            if alias.trim().is_empty() || alias.eq_ignore_ascii_case(name) {
+                anyhow::bail!("An alias must differ from the name it stands for");
+            }
+            if db.add_alias(name, alias)? {
+                println!("Added alias: {} = {}", name, alias);
+            } else {
+                println!("Already an alias: {} = {}", name, alias);
+            }
+        }
+        if !aliases.is_empty() {
+            return Ok(());
+        }
+    }
+
+    let list = db.list_aliases(name)?;
+    if list.is_empty() {
+        println!("No aliases defined.");
+        return Ok(());
+    }
+    let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+    for a in list {
+        match groups.last_mut() {
+            Some((n, names)) if n.eq_ignore_ascii_case(&a.name) => names.push(a.alias),
+            _ => groups.push((a.name, vec![a.alias])),
+        }
+    }
+    for (name, aliases) in groups {
+        println!("{} = {}", name, aliases.join(", "));
+    }
+    Ok(())
+}
+
+/// Format a byte count as B, KB or MB.
+fn format_size(bytes: i64) -> String {
+    match bytes {
+        b if b < 1024 => format!("{} B", b),
+        b if b < 1024 * 1024 => format!("{:.1} KB", b as f64 / 1024.0),
+        b => format!("{:.1} MB", b as f64 / (1024.0 * 1024.0)),
+    }
+}
+
+/// Format seconds as H:MM:SS, or M:SS under an hour.
+fn format_duration(seconds: f64) -> String {
+    let total = seconds.max(0.0).round() as u64;
+    let (h, m, s) = (total / 3600, (total % 3600) / 60, total % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{}:{:02}", m, s)
+    }
+}
+
 fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
+    if s.chars().count() <= max_len {
         s.to_string()
     } else {
-        format!("{}...", &s[..max_len.saturating_sub(3)])
+        format!("{}...", s.chars().take(max_len.saturating_sub(3)).collect::<String>())
     }
 }
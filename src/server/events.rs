@@ -0,0 +1,129 @@
+use axum::extract::State;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use chrono::{DateTime, Utc};
+use engine::Storage;
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use super::handlers::queue_summary;
+use super::models::{QueueSummary, VideoSummary};
+use super::{open_db, AppState};
+
+/// How often the database is checked for changes while anyone is listening. Fetches
+/// and the worker run as separate processes, so polling is how the server hears of them.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// At most this many new claims are sent per poll; the rest follow on the next.
+const MAX_CLAIMS_PER_POLL: usize = 200;
+
+/// Clients of `/api/events`, each fed through its own channel as (event name, JSON).
+#[derive(Default)]
+pub struct Subscribers(Mutex<Vec<UnboundedSender<(&'static str, String)>>>);
+
+impl Subscribers {
+    fn subscribe(&self) -> UnboundedReceiver<(&'static str, String)> {
+        let (tx, rx) = unbounded_channel();
+        self.0.lock().unwrap().push(tx);
+        rx
+    }
+
+    fn is_empty(&self) -> bool {
+        let mut subscribers = self.0.lock().unwrap();
+        subscribers.retain(|tx| !tx.is_closed());
+        subscribers.is_empty()
+    }
+
+    fn send(&self, name: &'static str, data: &impl serde::Serialize) {
+        let Ok(json) = serde_json::to_string(data) else { return };
+        self.0.lock().unwrap().retain(|tx| tx.send((name, json.clone())).is_ok());
+    }
+}
+
+pub struct EventStream(UnboundedReceiver<(&'static str, String)>);
+
+impl futures_core::Stream for EventStream {
+    type Item = Result<Event, Infallible>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.poll_recv(cx).map(|event| event.map(|(name, data)| Ok(Event::default().event(name).data(data))))
+    }
+}
+
+/// Server-sent events: `queue` with the queue summary when it changes, `video` for each
+/// video saved (so a fetch shows up as it goes), and `claim` for each new claim.
+pub async fn get_events(State(state): State<Arc<AppState>>) -> Sse<EventStream> {
+    Sse::new(EventStream(state.subscribers.subscribe())).keep_alive(KeepAlive::default())
+}
+
+/// What the subscribers have already been told.
+struct Seen {
+    generation: u64,
+    queue: QueueSummary,
+    claim_id: i64,
+    videos_after: DateTime<Utc>,
+}
+
+impl Seen {
+    fn now(db: &dyn Storage) -> anyhow::Result<Seen> {
+        Ok(Seen {
+            generation: db.generation(),
+            queue: queue_summary(db)?,
+            claim_id: db.max_claim_id()?,
+            videos_after: Utc::now(),
+        })
+    }
+}
+
+/// Poll the database while anyone is subscribed and send them what changed. A client
+/// that connects gets changes from then on; the current state is in the other endpoints.
+pub async fn watch(state: Arc<AppState>) {
+    let mut seen: Option<Seen> = None;
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        if state.subscribers.is_empty() {
+            seen = None;
+            continue;
+        }
+        let Ok(db) = open_db(&state) else { continue };
+        let Some(last) = seen.as_mut() else {
+            seen = Seen::now(&*db).ok();
+            continue;
+        };
+        if db.generation() == last.generation {
+            continue;
+        }
+        // A failed poll is retried on the next tick
+        let _ = poll(&state, &*db, last);
+    }
+}
+
+fn poll(state: &AppState, db: &dyn Storage, last: &mut Seen) -> anyhow::Result<()> {
+    let generation = db.generation();
+
+    let queue = queue_summary(db)?;
+    if queue != last.queue {
+        state.subscribers.send("queue", &queue);
+        last.queue = queue;
+    }
+
+    for video in db.list_videos_added_after(last.videos_after)? {
+        last.videos_after = last.videos_after.max(video.added_at);
+        state.subscribers.send("video", &VideoSummary { id: video.id, title: video.title });
+    }
+
+    let claims = db.list_claims_after(last.claim_id, MAX_CLAIMS_PER_POLL)?;
+    for claim in &claims {
+        last.claim_id = claim.id;
+        state.subscribers.send("claim", claim);
+    }
+
+    // Leave the generation alone while claims are still queued, so the next tick continues
+    if claims.len() < MAX_CLAIMS_PER_POLL {
+        last.generation = generation;
+    }
+    Ok(())
+}
@@ -0,0 +1,1688 @@
+use anyhow::Result;
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use engine::Storage;
+use std::collections::HashSet;
+use std::sync::Arc;
+use crate::{auto_embed, MMR_CANDIDATES};
+use super::models::*;
+
+/// Claims per graph page unless `limit` says otherwise, and the most one page may hold.
+const GRAPH_PAGE: usize = 2000;
+const MAX_GRAPH_PAGE: usize = 10_000;
+/// Size in pixels of the squares pins are clustered in, on 256-pixel Web Mercator tiles,
+/// and the deepest zoom clustered at.
+const CLUSTER_CELL: f64 = 80.0;
+const MAX_ZOOM: u8 = 22;
+/// Causal relations followed from a claim unless `depth` says otherwise, the furthest
+/// allowed, and the most feedback loops reported.
+const CAUSAL_DEPTH: usize = 3;
+const MAX_CAUSAL_DEPTH: usize = 6;
+const MAX_LOOPS: usize = 100;
+/// Tables the cached responses are built from; their ETags change when any of these do.
+const GRAPH_TABLES: &[&str] = &[
+    "claims", "claim_links", "claim_metrics", "moc_claims", "claim_eras", "claim_topics", "video_eras", "video_topics",
+    "eras", "topics",
+];
+const PIN_TABLES: &[&str] = &["locations", "video_locations", "videos", "video_eras", "video_topics", "eras", "topics"];
+const STATS_TABLES: &[&str] = &[
+    "videos", "claims", "claim_links", "claim_access", "mocs", "research_questions", "detected_patterns",
+    "cyclical_indicators", "causal_relations", "idea_transmissions", "geopolitical_entities", "surplus_flows",
+    "temporal_observations", "sources", "scholars", "terms", "visuals", "evidence", "quotes",
+];
+use super::{internal, open_db, open_db_for_write, paginate, parse_eras, AppState};
+
+/// A weak ETag for a response built from `tables`. The version is part of it so an
+/// upgraded server doesn't validate what an older one sent.
+fn etag(db: &dyn Storage, tables: &[&str]) -> Result<String, StatusCode> {
+    let mark = db.watermark(tables).map_err(internal)?;
+    Ok(format!("W/\"{}-{:x}\"", env!("CARGO_PKG_VERSION"), mark))
+}
+
+/// An empty 304 if the request's `If-None-Match` already names `etag`.
+fn not_modified(headers: &HeaderMap, etag: &str) -> Option<Response> {
+    let weak = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let matched = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.split(',').any(|tag| tag.trim() == "*" || weak(tag) == weak(etag)));
+    matched.then(|| (StatusCode::NOT_MODIFIED, [(header::ETAG, etag.to_string())]).into_response())
+}
+
+/// `body` with its ETag, to be revalidated on every use.
+fn with_etag(etag: String, body: impl IntoResponse) -> Response {
+    ([(header::ETAG, etag), (header::CACHE_CONTROL, "no-cache".to_string())], body).into_response()
+}
+
+pub async fn get_pins(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<MapQuery>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let db = open_db(&state)?;
+    let tag = etag(&*db, PIN_TABLES)?;
+    if let Some(response) = not_modified(&headers, &tag) {
+        return Ok(response);
+    }
+    let eras = parse_eras(&q.era);
+    let mut pins = if eras.is_empty() {
+        // No era filter - show all pins
+        db.get_map_pins(None, q.topic.as_deref())
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    } else {
+        // Multiple eras - union pins from each era
+        let mut all_pins = Vec::new();
+        let mut seen_ids = std::collections::HashSet::new();
+        for era in &eras {
+            let era_pins = db.get_map_pins(Some(era), q.topic.as_deref())
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            for pin in era_pins {
+                let key = (pin.location.id, pin.video_id.clone());
+                if seen_ids.insert(key) {
+                    all_pins.push(pin);
+                }
+            }
+        }
+        all_pins
+    };
+
+    if let Some(ref bbox) = q.bbox {
+        let bounds: Vec<f64> = bbox.split(',')
+            .map(|s| s.trim().parse())
+            .collect::<Result<_, _>>()
+            .map_err(|_| StatusCode::BAD_REQUEST)?;
+        let [west, south, east, north] = bounds[..] else {
+            return Err(StatusCode::BAD_REQUEST);
+        };
+        pins.retain(|pin| {
+            let (lat, lon) = (pin.location.lat, pin.location.lon);
+            // West past east means the box crosses the antimeridian
+            let in_lon = if west <= east { lon >= west && lon <= east } else { lon >= west || lon <= east };
+            in_lon && lat >= south && lat <= north
+        });
+    }
+
+    Ok(match q.zoom {
+        Some(zoom) => with_etag(tag, Json(cluster_pins(pins, zoom.min(MAX_ZOOM)))),
+        None => with_etag(tag, Json(pins)),
+    })
+}
+
+/// Groups the pins falling in the same `CLUSTER_CELL` square at `zoom`.
+fn cluster_pins(pins: Vec<engine::MapPin>, zoom: u8) -> Vec<PinCluster> {
+    // Cells across the whole world at this zoom
+    let scale = 256.0 * f64::from(1u32 << zoom) / CLUSTER_CELL;
+    let mut cells: std::collections::BTreeMap<(i64, i64), Vec<engine::MapPin>> = std::collections::BTreeMap::new();
+    for pin in pins {
+        let x = (pin.location.lon + 180.0) / 360.0;
+        let lat = pin.location.lat.clamp(-85.051_128, 85.051_128).to_radians();
+        let y = (1.0 - (lat.tan() + 1.0 / lat.cos()).ln() / std::f64::consts::PI) / 2.0;
+        cells.entry(((x * scale).floor() as i64, (y * scale).floor() as i64)).or_default().push(pin);
+    }
+
+    cells.into_values().map(|pins| {
+        let count = pins.len();
+        let lats = pins.iter().map(|p| p.location.lat);
+        let lons = pins.iter().map(|p| p.location.lon);
+        let bbox = (
+            lons.clone().fold(f64::INFINITY, f64::min),
+            lats.clone().fold(f64::INFINITY, f64::min),
+            lons.clone().fold(f64::NEG_INFINITY, f64::max),
+            lats.clone().fold(f64::NEG_INFINITY, f64::max),
+        );
+        let one_place = pins.iter().all(|p| p.location.id == pins[0].location.id);
+        PinCluster {
+            lat: lats.sum::<f64>() / count as f64,
+            lon: lons.sum::<f64>() / count as f64,
+            count,
+            bbox,
+            pins: if one_place { pins } else { Vec::new() },
+        }
+    }).collect()
+}
+
+pub async fn get_timeline(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<TimelineQuery>,
+) -> Result<Json<engine::Timeline>, StatusCode> {
+    use engine::TimelineKind::*;
+    let db = open_db(&state)?;
+    let kinds = match q.kind {
+        Some(ref kind) => kind
+            .split(',')
+            .map(|s| {
+                [Video, Claim, CyclicalIndicator, IdeaTransmission, GeopoliticalEntity, SurplusFlow]
+                    .into_iter()
+                    .find(|k| k.as_str().eq_ignore_ascii_case(s.trim()))
+                    .ok_or(StatusCode::BAD_REQUEST)
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        None => Vec::new(),
+    };
+    let timeline = db.timeline(&kinds, q.from, q.to)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(timeline))
+}
+
+pub async fn get_eras(
+    State(state): State<Arc<AppState>>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<engine::Era>, StatusCode> {
+    let db = open_db(&state)?;
+    let eras = db.list_eras().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    paginate(eras, &page, |e: &engine::Era| e.id.to_string(), |e| &e.name)
+}
+
+pub async fn get_topics(
+    State(state): State<Arc<AppState>>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<engine::Topic>, StatusCode> {
+    let db = open_db(&state)?;
+    let topics = db.list_topics().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    paginate(topics, &page, |t: &engine::Topic| t.id.to_string(), |t| &t.name)
+}
+
+pub async fn get_videos(
+    State(state): State<Arc<AppState>>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<VideoSummary>, StatusCode> {
+    let db = open_db(&state)?;
+    let videos = db.list_videos().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let videos = videos.into_iter().map(|v| VideoSummary {
+        id: v.id,
+        title: v.title,
+    }).collect();
+    paginate(videos, &page, |v: &VideoSummary| v.id.clone(), |v| &v.title)
+}
+
+pub async fn get_claims(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<ClaimsQuery>,
+    Query(mut page): Query<PageQuery>,
+) -> Result<Paged<engine::Claim>, StatusCode> {
+    let db = open_db(&state)?;
+    let category = match q.category.as_deref() {
+        Some(c) => Some(engine::ClaimCategory::from_str(c).ok_or(StatusCode::BAD_REQUEST)?),
+        None => None,
+    };
+    let status = match q.status.as_deref() {
+        Some(s) => Some(engine::ClaimStatus::from_str(s).ok_or(StatusCode::BAD_REQUEST)?),
+        None => None,
+    };
+    let mut claims = if let Some(video_id) = q.video_id {
+        db.list_claims_for_video(&video_id).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    } else {
+        // The whole knowledge base is pages of 100 unless asked otherwise
+        page.limit = page.limit.or(Some(100));
+        db.list_all_claims().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    };
+    claims.retain(|c| category.is_none_or(|cat| c.category == cat) && status.is_none_or(|s| c.status == s));
+    paginate(claims, &page, |c: &engine::Claim| c.id.to_string(), |c| &c.text)
+}
+
+pub async fn get_claim(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<ClaimDetail>, StatusCode> {
+    let db = open_db(&state)?;
+    let claim = db.get_claim_with_links(id)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let sources = db.get_claim_sources(id).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let scholars = db.get_claim_scholars(id).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let provenance = db.get_claim_provenance(id).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(ClaimDetail { claim, sources, scholars, provenance }))
+}
+
+/// An export as a file download.
+fn download(content_type: &'static str, file_name: &str, body: String) -> Response {
+    let disposition = format!("attachment; filename=\"{}\"", file_name.replace('"', ""));
+    ([(header::CONTENT_TYPE, content_type.to_string()), (header::CONTENT_DISPOSITION, disposition)], body).into_response()
+}
+
+// Exports read a lot of the database at once, so they run off the async workers
+
+pub async fn get_export_collection(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<Response, StatusCode> {
+    tokio::task::spawn_blocking(move || {
+        let db = open_db(&state)?;
+        let markdown = db.export_collection_markdown(&name).map_err(internal)?.ok_or(StatusCode::NOT_FOUND)?;
+        Ok(download("text/markdown; charset=utf-8", &format!("{}.md", name), markdown))
+    })
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+}
+
+pub async fn get_export_map(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<MapQuery>,
+) -> Result<Response, StatusCode> {
+    tokio::task::spawn_blocking(move || {
+        let db = open_db(&state)?;
+        let geojson = db.export_map_geojson(q.era.as_deref(), q.topic.as_deref()).map_err(internal)?;
+        let json = serde_json::to_string_pretty(&geojson).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        Ok(download("application/geo+json", "map.geojson", json))
+    })
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+}
+
+pub async fn get_export_graph(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<ExportGraphQuery>,
+) -> Result<Response, StatusCode> {
+    let graphml = match q.format.as_deref() {
+        None | Some("dot") => false,
+        Some("graphml") => true,
+        Some(_) => return Err(StatusCode::BAD_REQUEST),
+    };
+    tokio::task::spawn_blocking(move || {
+        let db = open_db(&state)?;
+        let graph = db.claim_graph(q.video_id.as_deref(), q.moc_id, q.era.as_deref()).map_err(internal)?;
+        Ok(if graphml {
+            download("application/graphml+xml", "graph.graphml", engine::storage::graph::to_graphml(&graph))
+        } else {
+            download("text/vnd.graphviz", "graph.dot", engine::storage::graph::to_dot(&graph))
+        })
+    })
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+}
+
+pub async fn get_attachments(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<AttachmentsQuery>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<engine::Attachment>, StatusCode> {
+    let db = open_db(&state)?;
+    let attachments = match (q.video_id, q.claim_id) {
+        (Some(video_id), _) => db.get_video_attachments(&video_id),
+        (None, Some(claim_id)) => db.get_claim_attachments(claim_id),
+        (None, None) => return Err(StatusCode::BAD_REQUEST),
+    };
+    let attachments = attachments.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    paginate(attachments, &page, |a: &engine::Attachment| a.id.to_string(), |a| &a.file_name)
+}
+
+pub async fn get_attachment_file(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<impl axum::response::IntoResponse, StatusCode> {
+
+    let db = open_db(&state)?;
+    let attachment = db.get_attachment(id)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    // A linked file that has since moved is reported as missing
+    let data = db.read_attachment(id)
+        .map_err(|_| StatusCode::NOT_FOUND)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let disposition = format!("inline; filename=\"{}\"", attachment.file_name.replace('"', ""));
+    Ok((
+        [(header::CONTENT_TYPE, attachment.mime_type), (header::CONTENT_DISPOSITION, disposition)],
+        data,
+    ))
+}
+
+pub async fn get_graph(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<GraphQuery>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let db = open_db(&state)?;
+    let tag = etag(&*db, GRAPH_TABLES)?;
+    if let Some(response) = not_modified(&headers, &tag) {
+        return Ok(response);
+    }
+
+    if let Some(moc_id) = q.moc_id {
+        if db.get_moc(moc_id).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?.is_none() {
+            return Err(StatusCode::NOT_FOUND);
+        }
+    }
+    let statuses = match q.status {
+        Some(ref status) => status
+            .split(',')
+            .map(|s| engine::ClaimStatus::from_str(s.trim()).ok_or(StatusCode::BAD_REQUEST))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => Vec::new(),
+    };
+    let categories = match q.category {
+        Some(ref category) => category
+            .split(',')
+            .map(|s| engine::ClaimCategory::from_str(s.trim()).ok_or(StatusCode::BAD_REQUEST))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => Vec::new(),
+    };
+    let min_confidence = match q.min_confidence {
+        Some(ref confidence) => Some(engine::Confidence::from_str(confidence.trim()).ok_or(StatusCode::BAD_REQUEST)?),
+        None => None,
+    };
+    let link_types = match q.link_types {
+        Some(ref types) => types
+            .split(',')
+            .map(|s| engine::LinkType::from_str(s.trim()).ok_or(StatusCode::BAD_REQUEST))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => Vec::new(),
+    };
+    let filter = engine::ClaimGraphFilter {
+        video_id: q.video_id,
+        moc_id: q.moc_id,
+        eras: parse_eras(&q.era),
+        topic: q.topic,
+        statuses,
+        categories,
+        min_confidence,
+        link_types,
+    };
+    let limit = q.limit.unwrap_or(GRAPH_PAGE).min(MAX_GRAPH_PAGE);
+    let graph = db.claim_graph_page(&filter, limit, q.offset.unwrap_or(0))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Build nodes
+    let metrics = db.list_claim_metrics().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let mut nodes = Vec::new();
+    for (claim, link_count) in &graph.claims {
+        let m = metrics.get(&claim.id);
+        let value = match m {
+            Some(m) => m.degree + 1 + (m.betweenness * 20.0).round() as usize,
+            None => link_count + 1,
+        };
+        let label = if claim.text.len() > 40 {
+            format!("{}...", &claim.text[..37])
+        } else {
+            claim.text.clone()
+        };
+        nodes.push(GraphNode {
+            id: claim.id,
+            label,
+            title: claim.text.clone(),
+            group: claim.category.as_str().to_string(),
+            value,
+            video_id: claim.video_id.clone(),
+            timestamp: claim.timestamp,
+            betweenness: m.map(|m| m.betweenness),
+            component: m.map(|m| m.component),
+        });
+    }
+
+    // Build edges; the page's links are already limited to claims on the page
+    let edges = graph.links.iter().map(|link| {
+        let (color, dashes) = match link.link_type {
+            engine::LinkType::Supports => ("#4CAF50", false),
+            engine::LinkType::Contradicts => ("#f44336", true),
+            engine::LinkType::Elaborates => ("#2196F3", false),
+            engine::LinkType::Causes => ("#FF9800", false),
+            engine::LinkType::CausedBy => ("#FF9800", false),
+            engine::LinkType::Related => ("#9E9E9E", true),
+        };
+        GraphEdge {
+            from: link.source_claim_id,
+            to: link.target_claim_id,
+            label: link.link_type.as_str().to_string(),
+            arrows: "to".to_string(),
+            dashes,
+            color: EdgeColor { color: color.to_string() },
+        }
+    }).collect();
+
+    Ok(with_etag(tag, Json(GraphData { nodes, edges, total: graph.total })))
+}
+
+pub async fn get_causal(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<CausalQuery>,
+) -> Result<Json<CausalDiagram>, StatusCode> {
+    let db = open_db(&state)?;
+    let depth = q.depth.unwrap_or(CAUSAL_DEPTH).min(MAX_CAUSAL_DEPTH);
+    let chain = db.causal_chain(q.claim, depth).map_err(internal)?.ok_or(StatusCode::NOT_FOUND)?;
+
+    let nodes = chain.claims.into_iter().map(|(claim, depth)| CausalNode {
+        id: claim.id,
+        label: if claim.text.len() > 40 { format!("{}...", &claim.text[..37]) } else { claim.text.clone() },
+        title: claim.text,
+        group: claim.category.as_str().to_string(),
+        depth,
+        video_id: claim.video_id,
+    }).collect();
+    let loops = engine::storage::graph::causal_loops(&chain.relations, MAX_LOOPS);
+    let edges = chain.relations.into_iter().map(|r| CausalEdge {
+        id: r.id,
+        from: r.cause_claim_id,
+        to: r.effect_claim_id,
+        loop_type: r.loop_type,
+        strength: r.strength,
+        notes: r.notes,
+        video_id: r.video_id,
+    }).collect();
+
+    Ok(Json(CausalDiagram { claim_id: q.claim, nodes, edges, loops }))
+}
+
+pub async fn get_mocs(
+    State(state): State<Arc<AppState>>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<MocSummary>, StatusCode> {
+    let db = open_db(&state)?;
+    let mocs = db.list_mocs().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let mut summaries = Vec::new();
+    for moc in mocs {
+        let claim_count = db.get_moc_with_claims(moc.id)
+            .map(|m| m.map(|x| x.claims.len()).unwrap_or(0))
+            .unwrap_or(0);
+        summaries.push(MocSummary {
+            id: moc.id,
+            title: moc.title,
+            description: moc.description,
+            claim_count,
+        });
+    }
+    paginate(summaries, &page, |m: &MocSummary| m.id.to_string(), |m| &m.title)
+}
+
+pub async fn get_moc(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<engine::MocWithClaims>, StatusCode> {
+    let db = open_db(&state)?;
+    let moc = db.get_moc_with_claims(id)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(moc))
+}
+
+pub async fn get_questions(
+    State(state): State<Arc<AppState>>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<QuestionSummary>, StatusCode> {
+    let db = open_db(&state)?;
+    let questions = db.list_research_questions(None)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let mut summaries = Vec::new();
+    for q in questions {
+        let evidence_count = db.get_question_with_evidence(q.id)
+            .map(|qe| qe.map(|x| x.claims.len() + x.videos.len()).unwrap_or(0))
+            .unwrap_or(0);
+        summaries.push(QuestionSummary {
+            id: q.id,
+            question: q.question,
+            status: q.status.as_str().to_string(),
+            evidence_count,
+        });
+    }
+    paginate(summaries, &page, |q: &QuestionSummary| q.id.to_string(), |q| &q.question)
+}
+
+fn moc_with_claims(db: &dyn Storage, id: i64) -> Result<Json<engine::MocWithClaims>, StatusCode> {
+    Ok(Json(db.get_moc_with_claims(id).map_err(internal)?.ok_or(StatusCode::NOT_FOUND)?))
+}
+
+pub async fn post_moc(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<NewMoc>,
+) -> Result<(StatusCode, Json<engine::MapOfContent>), StatusCode> {
+    let db = open_db_for_write(&state)?;
+    let title = body.title.trim();
+    if title.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if db.get_moc_by_title(title).map_err(internal)?.is_some() {
+        return Err(StatusCode::CONFLICT);
+    }
+    let moc = db.create_moc(title, body.description.as_deref()).map_err(internal)?;
+    Ok((StatusCode::CREATED, Json(moc)))
+}
+
+pub async fn put_moc(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(body): Json<MocChanges>,
+) -> Result<Json<engine::MapOfContent>, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    let title = body.title.as_deref().map(str::trim);
+    if title == Some("") {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if let Some(title) = title {
+        if db.get_moc_by_title(title).map_err(internal)?.is_some_and(|m| m.id != id) {
+            return Err(StatusCode::CONFLICT);
+        }
+    }
+    if !db.update_moc(id, title, body.description.as_deref()).map_err(internal)? {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    Ok(Json(db.get_moc(id).map_err(internal)?.ok_or(StatusCode::NOT_FOUND)?))
+}
+
+pub async fn delete_moc(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    match db.delete_moc(id).map_err(internal)? {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Err(StatusCode::NOT_FOUND),
+    }
+}
+
+pub async fn post_moc_claim(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(body): Json<MocMember>,
+) -> Result<(StatusCode, Json<engine::MocWithClaims>), StatusCode> {
+    let db = open_db_for_write(&state)?;
+    if db.get_moc(id).map_err(internal)?.is_none() || db.get_claim(body.claim_id).map_err(internal)?.is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let sort_order = match body.sort_order {
+        Some(order) => order,
+        None => db.next_moc_sort_order(id).map_err(internal)?,
+    };
+    db.add_claim_to_moc(id, body.claim_id, sort_order).map_err(internal)?;
+    Ok((StatusCode::CREATED, moc_with_claims(&*db, id)?))
+}
+
+pub async fn put_moc_claims(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(body): Json<MocOrder>,
+) -> Result<Json<engine::MocWithClaims>, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    let mut current: Vec<i64> = db.get_moc_claims(id).map_err(internal)?.into_iter().map(|c| c.id).collect();
+    if current.is_empty() && db.get_moc(id).map_err(internal)?.is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    // The new order has to name each claim in the MOC once
+    let mut given = body.claim_ids.clone();
+    current.sort_unstable();
+    given.sort_unstable();
+    if given != current {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    db.reorder_moc_claims(id, &body.claim_ids).map_err(internal)?;
+    moc_with_claims(&*db, id)
+}
+
+pub async fn delete_moc_claim(
+    State(state): State<Arc<AppState>>,
+    Path((id, claim_id)): Path<(i64, i64)>,
+) -> Result<StatusCode, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    match db.remove_claim_from_moc(id, claim_id).map_err(internal)? {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Err(StatusCode::NOT_FOUND),
+    }
+}
+
+fn question_with_evidence(db: &dyn Storage, id: i64) -> Result<Json<engine::QuestionWithEvidence>, StatusCode> {
+    Ok(Json(db.get_question_with_evidence(id).map_err(internal)?.ok_or(StatusCode::NOT_FOUND)?))
+}
+
+pub async fn post_question(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<NewQuestion>,
+) -> Result<(StatusCode, Json<engine::ResearchQuestion>), StatusCode> {
+    let db = open_db_for_write(&state)?;
+    let text = body.question.trim();
+    if text.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if let Some(parent_id) = body.parent_id {
+        if db.get_research_question(parent_id).map_err(internal)?.is_none() {
+            return Err(StatusCode::NOT_FOUND);
+        }
+    }
+    let question = db.create_research_question(text, body.parent_id, body.notes.as_deref()).map_err(internal)?;
+    Ok((StatusCode::CREATED, Json(question)))
+}
+
+pub async fn put_question_status(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(body): Json<StatusChange>,
+) -> Result<Json<engine::QuestionWithEvidence>, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    let status = engine::QuestionStatus::from_str(&body.status).ok_or(StatusCode::BAD_REQUEST)?;
+    if !db.update_question_status(id, status).map_err(internal)? {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    question_with_evidence(&*db, id)
+}
+
+pub async fn delete_question(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    match db.delete_research_question(id).map_err(internal)? {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Err(StatusCode::NOT_FOUND),
+    }
+}
+
+pub async fn post_question_evidence(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(body): Json<NewEvidence>,
+) -> Result<(StatusCode, Json<engine::QuestionWithEvidence>), StatusCode> {
+    let db = open_db_for_write(&state)?;
+    // A claim or a video, not both
+    if body.claim_id.is_some() == body.video_id.is_some() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let existing = db.get_question_with_evidence(id).map_err(internal)?.ok_or(StatusCode::NOT_FOUND)?;
+    if let Some(claim_id) = body.claim_id {
+        if db.get_claim(claim_id).map_err(internal)?.is_none() {
+            return Err(StatusCode::NOT_FOUND);
+        }
+        if existing.claims.iter().any(|c| c.id == claim_id) {
+            return Err(StatusCode::CONFLICT);
+        }
+    }
+    if let Some(ref video_id) = body.video_id {
+        if db.get_video(video_id).map_err(internal)?.is_none() {
+            return Err(StatusCode::NOT_FOUND);
+        }
+        if existing.videos.iter().any(|v| &v.id == video_id) {
+            return Err(StatusCode::CONFLICT);
+        }
+    }
+    let relevance = body.relevance.as_deref().map(str::trim).filter(|r| !r.is_empty());
+    db.add_evidence_to_question(id, body.claim_id, body.video_id.as_deref(), relevance).map_err(internal)?;
+    Ok((StatusCode::CREATED, question_with_evidence(&*db, id)?))
+}
+
+pub async fn get_question(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<engine::QuestionWithEvidence>, StatusCode> {
+    let db = open_db(&state)?;
+    let question = db.get_question_with_evidence(id)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(question))
+}
+
+pub async fn get_stats(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let db = open_db(&state)?;
+    let tag = etag(&*db, STATS_TABLES)?;
+    if let Some(response) = not_modified(&headers, &tag) {
+        return Ok(response);
+    }
+    let stats = state.cache.get_or_compute("stats", db.generation(), || compute_stats(&*db))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(with_etag(tag, Json(stats)))
+}
+
+fn compute_stats(db: &dyn Storage) -> Result<FullStats> {
+    let synthesis = db.get_synthesis_stats()?;
+    let framework = db.get_framework_stats()?;
+
+    // Get counts
+    let videos = db.list_videos().map(|v| v.len() as i64).unwrap_or(0);
+    let claims = db.get_random_claims(10000).map(|c| c.len() as i64).unwrap_or(0);
+    let orphans = db.get_orphan_claims().map(|o| o.len()).unwrap_or(0);
+    let stale = db.get_stale_claims(30).map(|s| s.len()).unwrap_or(0);
+
+    // Count claims by category (simplified)
+    let claims_by_category = vec![
+        CategoryCount { category: "factual".to_string(), count: 0 },
+        CategoryCount { category: "causal".to_string(), count: 0 },
+        CategoryCount { category: "cyclical".to_string(), count: 0 },
+        CategoryCount { category: "memetic".to_string(), count: 0 },
+        CategoryCount { category: "geopolitical".to_string(), count: 0 },
+    ];
+
+    // Phase 12: Get expanded entity counts
+    let sources = db.get_sources().map(|s| s.len() as i64).unwrap_or(0);
+    let scholars = db.get_scholars().map(|s| s.len() as i64).unwrap_or(0);
+    let terms = db.get_terms().map(|t| t.len() as i64).unwrap_or(0);
+    let visuals = db.get_all_visuals().map(|v| v.len() as i64).unwrap_or(0);
+    let evidence = db.get_all_evidence().map(|e| e.len() as i64).unwrap_or(0);
+    let quotes = db.get_all_quotes().map(|q| q.len() as i64).unwrap_or(0);
+
+    Ok(FullStats {
+        videos,
+        claims,
+        links: 0, // Would need a query
+        mocs: synthesis.mocs,
+        questions: synthesis.research_questions,
+        active_questions: synthesis.active_questions,
+        patterns: synthesis.detected_patterns,
+        orphan_claims: orphans,
+        stale_claims: stale,
+        framework,
+        claims_by_category,
+        sources,
+        scholars,
+        terms,
+        visuals,
+        evidence,
+        quotes,
+    })
+}
+
+pub async fn get_report(
+    State(state): State<Arc<AppState>>,
+    Path(dimension): Path<String>,
+) -> Result<Json<Vec<engine::ReportEntry>>, StatusCode> {
+    if !matches!(dimension.as_str(), "era" | "region" | "topic") {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let db = open_db(&state)?;
+    let key = format!("report:{}", dimension);
+    let report = state.cache.get_or_compute(&key, db.generation(), || match dimension.as_str() {
+        "era" => db.report_by_era(),
+        "region" => db.report_by_region(),
+        _ => db.report_by_topic(),
+    }).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(report))
+}
+
+pub async fn get_projection(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ProjectionQuery>,
+) -> Result<Json<Vec<engine::ProjectionPoint>>, StatusCode> {
+    let source_type = match query.source.as_deref() {
+        Some(s) => Some(engine::EmbeddingSource::from_str(s).ok_or(StatusCode::BAD_REQUEST)?),
+        None => None,
+    };
+    let db = open_db(&state)?;
+    // PCA over every vector is slow enough to be worth caching until the next write
+    let key = format!("projection:{}:{}", query.source.as_deref().unwrap_or(""), query.model.as_deref().unwrap_or(""));
+    let points = state
+        .cache
+        .get_or_compute(&key, db.generation(), || db.embedding_projection(source_type, query.model.as_deref()))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(points))
+}
+
+pub async fn get_contradictions(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ContradictionsQuery>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<engine::ContradictionGroup>, StatusCode> {
+    let by = query.by.unwrap_or_else(|| "topic".to_string());
+    if by != "topic" && by != "moc" {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let db = open_db(&state)?;
+    let groups = state
+        .cache
+        .get_or_compute(&format!("contradictions:{}", by), db.generation(), || db.list_contradictions(&by))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    paginate(groups, &page, |g: &engine::ContradictionGroup| g.name.clone(), |g| &g.name)
+}
+
+pub async fn get_semantic(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<SemanticQuery>,
+) -> Result<Json<Vec<engine::SimilarityResult>>, StatusCode> {
+    let source_type = match query.source.as_deref() {
+        Some(s) => Some(engine::EmbeddingSource::from_str(s).ok_or(StatusCode::BAD_REQUEST)?),
+        None => None,
+    };
+    if query.q.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let limit = query.limit.unwrap_or(20).min(100);
+    // Embedding the query calls out to the provider, so keep it off the async workers
+    tokio::task::spawn_blocking(move || {
+        let db = open_db(&state)?;
+        let vector = match state.embedding.embed_query(&*db, &query.q) {
+            Ok(Some(v)) => v,
+            Ok(None) => return Err(StatusCode::SERVICE_UNAVAILABLE),
+            Err(e) => {
+                eprintln!("Embedding query failed: {:#}", e);
+                return Err(StatusCode::BAD_GATEWAY);
+            }
+        };
+        let similar = match query.diversify {
+            Some(lambda) => {
+                let candidates = db.find_similar(&vector, source_type, None, limit * MMR_CANDIDATES).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+                db.diversify(&vector, candidates, limit, lambda.clamp(0.0, 1.0))
+            }
+            None => db.find_similar(&vector, source_type, None, limit).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        };
+        let results = db.build_similarity_results(similar).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        Ok(Json(results))
+    })
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+}
+
+pub async fn get_similar(
+    State(state): State<Arc<AppState>>,
+    Path((source, id)): Path<(String, String)>,
+    Query(query): Query<SimilarQuery>,
+) -> Result<Json<Vec<engine::SimilarityResult>>, StatusCode> {
+    let source_type = engine::EmbeddingSource::from_str(&source).ok_or(StatusCode::BAD_REQUEST)?;
+    let limit = query.limit.unwrap_or(10).min(100);
+    let db = open_db(&state)?;
+    let key = format!("similar:{}:{}:{}:{:?}", source, id, limit, query.diversify);
+    let results = state
+        .cache
+        .get_or_compute(&key, db.generation(), || {
+            let model = db.default_embedding_model()?.unwrap_or_else(|| "default".to_string());
+            let Some(embedding) = db.get_embedding(source_type, &id, &model)? else {
+                return Ok(None);
+            };
+            let pool = if query.diversify.is_some() { limit * MMR_CANDIDATES } else { limit };
+            let similar: Vec<_> = db
+                .find_similar(&embedding.vector, None, Some(&model), pool + 1)?
+                .into_iter()
+                .filter(|(e, _)| !(e.source_type == source_type && e.source_id == id))
+                .take(pool)
+                .collect();
+            let similar = match query.diversify {
+                Some(lambda) => db.diversify(&embedding.vector, similar, limit, lambda.clamp(0.0, 1.0)),
+                None => similar,
+            };
+            db.build_similarity_results(similar).map(Some)
+        })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(results))
+}
+
+pub async fn get_review_orphans(
+    State(state): State<Arc<AppState>>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<engine::Claim>, StatusCode> {
+    let db = open_db(&state)?;
+    let orphans = db.get_orphan_claims().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    paginate(orphans, &page, |c: &engine::Claim| c.id.to_string(), |c| &c.text)
+}
+
+pub async fn get_review_stale(
+    State(state): State<Arc<AppState>>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<engine::Claim>, StatusCode> {
+    let db = open_db(&state)?;
+    let stale = db.get_stale_claims(30).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    paginate(stale, &page, |c: &engine::Claim| c.id.to_string(), |c| &c.text)
+}
+
+pub async fn get_queue(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<QueueSummary>, StatusCode> {
+    let db = open_db(&state)?;
+    Ok(Json(queue_summary(&*db).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?))
+}
+
+pub async fn get_queue_items(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<QueueItemsQuery>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<QueueItem>, StatusCode> {
+    let db = open_db(&state)?;
+    let statuses = match q.status {
+        Some(ref status) => status
+            .split(',')
+            .map(|s| engine::ProcessingStatus::from_str(s.trim()).ok_or(StatusCode::BAD_REQUEST))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => Vec::new(),
+    };
+    let mut items = Vec::new();
+    for item in db.get_queue(true).map_err(internal)? {
+        if !statuses.is_empty() && !statuses.contains(&item.status) {
+            continue;
+        }
+        items.push(queue_item(&*db, item)?);
+    }
+    paginate(items, &page, |i: &QueueItem| i.item.video_id.clone(), |i| i.title.as_deref().unwrap_or(&i.item.video_id))
+}
+
+fn queue_item(db: &dyn Storage, item: engine::AIProcessingQueue) -> Result<QueueItem, StatusCode> {
+    let title = db.get_video(&item.video_id).map_err(internal)?.map(|v| v.title);
+    Ok(QueueItem { item, title })
+}
+
+// The queue entry for a video after a change, or 404 when it isn't queued
+fn changed_queue_item(db: &dyn Storage, video_id: &str, changed: bool) -> Result<Json<QueueItem>, StatusCode> {
+    if !changed {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let item = db.get_queue_item(video_id).map_err(internal)?.ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(queue_item(db, item)?))
+}
+
+pub async fn post_queue_item(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<NewQueueItem>,
+) -> Result<(StatusCode, Json<QueueItem>), StatusCode> {
+    let db = open_db_for_write(&state)?;
+    if db.get_video(&body.video_id).map_err(internal)?.is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    db.add_to_queue(&body.video_id, body.priority.unwrap_or(0)).map_err(internal)?;
+    Ok((StatusCode::CREATED, changed_queue_item(&*db, &body.video_id, true)?))
+}
+
+pub async fn post_queue_skip(
+    State(state): State<Arc<AppState>>,
+    Path(video_id): Path<String>,
+) -> Result<Json<QueueItem>, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    let changed = db.queue_skip(&video_id).map_err(internal)?;
+    changed_queue_item(&*db, &video_id, changed)
+}
+
+pub async fn post_queue_reset(
+    State(state): State<Arc<AppState>>,
+    Path(video_id): Path<String>,
+) -> Result<Json<QueueItem>, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    let changed = db.queue_reset(&video_id).map_err(internal)?;
+    changed_queue_item(&*db, &video_id, changed)
+}
+
+pub async fn post_queue_priority(
+    State(state): State<Arc<AppState>>,
+    Path(video_id): Path<String>,
+    Json(body): Json<PriorityChange>,
+) -> Result<Json<QueueItem>, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    let changed = db.queue_set_priority(&video_id, body.priority).map_err(internal)?;
+    changed_queue_item(&*db, &video_id, changed)
+}
+
+pub fn queue_summary(db: &dyn Storage) -> Result<QueueSummary> {
+    let items = db.get_queue(true)?;
+
+    let mut pending = 0;
+    let mut in_progress = 0;
+    let mut completed = 0;
+    let mut failed = 0;
+    let mut current = None;
+
+    for item in items {
+        match item.status {
+            engine::ProcessingStatus::Pending => pending += 1,
+            engine::ProcessingStatus::InProgress => {
+                in_progress += 1;
+                current = Some(item.video_id.clone());
+            }
+            engine::ProcessingStatus::Completed => completed += 1,
+            engine::ProcessingStatus::Failed => failed += 1,
+            engine::ProcessingStatus::Skipped => {}
+        }
+    }
+
+    Ok(QueueSummary { pending, in_progress, completed, failed, current })
+}
+
+// Phase 8: API endpoints for the analytical frameworks
+
+/// Ids of the eras named in a comma-separated `era` filter, or None without one.
+/// Names that aren't eras match nothing.
+fn era_ids(db: &dyn Storage, era: &Option<String>) -> Result<Option<HashSet<i64>>, StatusCode> {
+    let names = parse_eras(era);
+    if names.is_empty() {
+        return Ok(None);
+    }
+    let mut ids = HashSet::new();
+    for name in &names {
+        if let Some(era) = db.get_era_by_name(name).map_err(internal)? {
+            ids.insert(era.id);
+        }
+    }
+    Ok(Some(ids))
+}
+
+fn in_eras(eras: &Option<HashSet<i64>>, era_id: Option<i64>) -> bool {
+    match eras {
+        Some(ids) => era_id.is_some_and(|id| ids.contains(&id)),
+        None => true,
+    }
+}
+
+pub async fn get_world_entities(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<WorldSystemsQuery>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<engine::GeopoliticalEntity>, StatusCode> {
+    let db = open_db(&state)?;
+    let eras = era_ids(&*db, &q.era)?;
+    let position = match q.position.as_deref() {
+        Some(p) => Some(engine::SystemPosition::from_str(p).ok_or(StatusCode::BAD_REQUEST)?),
+        None => None,
+    };
+    let mut entities = db.list_all_geopolitical_entities().map_err(internal)?;
+    entities.retain(|e| in_eras(&eras, Some(e.era_id)) && position.is_none_or(|p| e.position == p));
+    paginate(entities, &page, |e: &engine::GeopoliticalEntity| e.id.to_string(), |e| &e.name)
+}
+
+pub async fn get_world_flows(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<WorldSystemsQuery>,
+) -> Result<Json<SankeyData>, StatusCode> {
+    let db = open_db(&state)?;
+    let eras = era_ids(&*db, &q.era)?;
+    let commodity = q.commodity.as_deref().map(str::trim).filter(|c| !c.is_empty());
+    let mut flows = db.list_all_surplus_flows().map_err(internal)?;
+    flows.retain(|f| {
+        in_eras(&eras, Some(f.era_id)) && commodity.is_none_or(|c| f.commodity.eq_ignore_ascii_case(c))
+    });
+
+    // One link per pair of entities, in the order the pairs first appear
+    let mut links: Vec<SankeyLink> = Vec::new();
+    for flow in flows {
+        let link = match links.iter().position(|l| l.source == flow.from_entity_id && l.target == flow.to_entity_id) {
+            Some(i) => &mut links[i],
+            None => {
+                links.push(SankeyLink {
+                    source: flow.from_entity_id,
+                    target: flow.to_entity_id,
+                    value: 0,
+                    commodities: Vec::new(),
+                    flow_ids: Vec::new(),
+                });
+                links.last_mut().expect("just pushed")
+            }
+        };
+        link.value += 1;
+        if !link.commodities.contains(&flow.commodity) {
+            link.commodities.push(flow.commodity);
+        }
+        link.flow_ids.push(flow.id);
+    }
+
+    let ids: HashSet<i64> = links.iter().flat_map(|l| [l.source, l.target]).collect();
+    let nodes = db.list_all_geopolitical_entities().map_err(internal)?
+        .into_iter()
+        .filter(|e| ids.contains(&e.id))
+        .map(|e| SankeyNode { id: e.id, name: e.name, position: e.position, era_id: e.era_id })
+        .collect();
+    Ok(Json(SankeyData { nodes, links }))
+}
+
+pub async fn get_transmissions(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<TransmissionsQuery>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<engine::IdeaTransmission>, StatusCode> {
+    let db = open_db(&state)?;
+    let eras = era_ids(&*db, &q.era)?;
+    let kind = match q.kind.as_deref() {
+        Some(t) => Some(engine::TransmissionType::from_str(t).ok_or(StatusCode::BAD_REQUEST)?),
+        None => None,
+    };
+    let mut transmissions = db.list_all_idea_transmissions().map_err(internal)?;
+    transmissions.retain(|t| in_eras(&eras, t.era_id) && kind.is_none_or(|k| t.transmission_type == k));
+    paginate(transmissions, &page, |t: &engine::IdeaTransmission| t.id.to_string(), |t| &t.idea)
+}
+
+pub async fn get_cyclical(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<CyclicalQuery>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<engine::CyclicalIndicator>, StatusCode> {
+    let db = open_db(&state)?;
+    let eras = era_ids(&*db, &q.era)?;
+    let kind = match q.kind.as_deref() {
+        Some(t) => Some(engine::CyclicalType::from_str(t).ok_or(StatusCode::BAD_REQUEST)?),
+        None => None,
+    };
+    let entity = q.entity.as_deref().map(str::trim).filter(|e| !e.is_empty());
+    let mut indicators = db.list_all_cyclical_indicators().map_err(internal)?;
+    indicators.retain(|i| {
+        in_eras(&eras, i.era_id)
+            && kind.is_none_or(|k| i.indicator_type == k)
+            && entity.is_none_or(|e| i.entity.eq_ignore_ascii_case(e))
+    });
+    paginate(indicators, &page, |i: &engine::CyclicalIndicator| i.id.to_string(), |i| &i.description)
+}
+
+// Phase 12: API endpoints for expanded knowledge entities
+
+pub async fn get_sources(
+    State(state): State<Arc<AppState>>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<engine::Source>, StatusCode> {
+    let db = open_db(&state)?;
+    let sources = db.get_sources().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    paginate(sources, &page, |x: &engine::Source| x.id.to_string(), |x| &x.title)
+}
+
+pub async fn get_scholars(
+    State(state): State<Arc<AppState>>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<engine::Scholar>, StatusCode> {
+    let db = open_db(&state)?;
+    let scholars = db.get_scholars().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    paginate(scholars, &page, |x: &engine::Scholar| x.id.to_string(), |x| &x.name)
+}
+
+pub async fn get_terms(
+    State(state): State<Arc<AppState>>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<engine::Term>, StatusCode> {
+    let db = open_db(&state)?;
+    let terms = db.get_terms().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    paginate(terms, &page, |x: &engine::Term| x.id.to_string(), |x| &x.term)
+}
+
+pub async fn get_visuals(
+    State(state): State<Arc<AppState>>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<engine::Visual>, StatusCode> {
+    let db = open_db(&state)?;
+    let visuals = db.get_all_visuals().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    paginate(visuals, &page, |x: &engine::Visual| x.id.to_string(), |x| &x.description)
+}
+
+pub async fn get_evidence(
+    State(state): State<Arc<AppState>>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<engine::Evidence>, StatusCode> {
+    let db = open_db(&state)?;
+    let evidence = db.get_all_evidence().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    paginate(evidence, &page, |x: &engine::Evidence| x.id.to_string(), |x| &x.description)
+}
+
+pub async fn get_quotes(
+    State(state): State<Arc<AppState>>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<engine::Quote>, StatusCode> {
+    let db = open_db(&state)?;
+    let quotes = db.get_all_quotes().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    paginate(quotes, &page, |x: &engine::Quote| x.id.to_string(), |x| &x.text)
+}
+
+pub async fn search(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<SearchQuery>,
+) -> Result<Json<engine::SearchResponse>, StatusCode> {
+    let db = open_db(&state)?;
+
+    // Parse comma-separated types
+    let types: Option<Vec<&str>> = q.types.as_ref()
+        .map(|t| t.split(',').map(|s| s.trim()).collect());
+
+    let results = db.unified_search(
+        &q.q,
+        types.as_deref(),
+        q.video_id.as_deref(),
+        q.limit.unwrap_or(50),
+        q.fuzzy_threshold.unwrap_or(0.6),
+    ).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(results))
+}
+
+fn parse_category(s: Option<&str>) -> Result<Option<engine::ClaimCategory>, StatusCode> {
+    s.map(|s| engine::ClaimCategory::from_str(s).ok_or(StatusCode::BAD_REQUEST)).transpose()
+}
+
+fn parse_confidence(s: Option<&str>) -> Result<Option<engine::Confidence>, StatusCode> {
+    s.map(|s| engine::Confidence::from_str(s).ok_or(StatusCode::BAD_REQUEST)).transpose()
+}
+
+/// Queue a claim for (re-)embedding when auto-embed is on or it already had a vector.
+fn queue_claim_embedding(db: &dyn Storage, id: i64, had_vector: bool) -> Result<(), StatusCode> {
+    if had_vector || auto_embed(db).map_err(internal)? {
+        db.queue_embedding(engine::EmbeddingSource::Claim, &id.to_string()).map_err(internal)?;
+    }
+    Ok(())
+}
+
+pub async fn post_claim(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<NewClaim>,
+) -> Result<(StatusCode, Json<engine::Claim>), StatusCode> {
+    let db = open_db_for_write(&state)?;
+    let text = body.text.trim();
+    if text.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let category = parse_category(body.category.as_deref())?.unwrap_or(engine::ClaimCategory::Factual);
+    let confidence = parse_confidence(body.confidence.as_deref())?.unwrap_or(engine::Confidence::Medium);
+    if db.get_video(&body.video_id).map_err(internal)?.is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let claim = db
+        .create_claim(text, &body.video_id, body.timestamp, body.quote.trim(), category, confidence)
+        .map_err(internal)?;
+    queue_claim_embedding(&*db, claim.id, false)?;
+    Ok((StatusCode::CREATED, Json(claim)))
+}
+
+pub async fn put_claim(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(body): Json<ClaimChanges>,
+) -> Result<Json<engine::Claim>, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    let claim = db.get_claim(id).map_err(internal)?.ok_or(StatusCode::NOT_FOUND)?;
+    let text = body.text.as_deref().map(str::trim);
+    if text == Some("") {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let category = parse_category(body.category.as_deref())?;
+    let confidence = parse_confidence(body.confidence.as_deref())?;
+    db.update_claim(id, text, body.quote.as_deref().map(str::trim), category, confidence, body.timestamp)
+        .map_err(internal)?;
+    let updated = db.get_claim(id).map_err(internal)?.ok_or(StatusCode::NOT_FOUND)?;
+    // A stored vector describes the old wording
+    if updated.text != claim.text {
+        let had_vector = match db.default_embedding_model().map_err(internal)? {
+            Some(model) => {
+                db.get_embedding(engine::EmbeddingSource::Claim, &id.to_string(), &model).map_err(internal)?.is_some()
+            }
+            None => false,
+        };
+        queue_claim_embedding(&*db, id, had_vector)?;
+    }
+    Ok(Json(updated))
+}
+
+pub async fn delete_claim(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    match db.delete_claim(id).map_err(internal)? {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Err(StatusCode::NOT_FOUND),
+    }
+}
+
+pub async fn post_claim_link(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(body): Json<NewLink>,
+) -> Result<(StatusCode, Json<engine::ClaimLink>), StatusCode> {
+    let db = open_db_for_write(&state)?;
+    let link_type = engine::LinkType::from_str(&body.link_type).ok_or(StatusCode::BAD_REQUEST)?;
+    if id == body.target {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    for claim in [id, body.target] {
+        if db.get_claim(claim).map_err(internal)?.is_none() {
+            return Err(StatusCode::NOT_FOUND);
+        }
+    }
+    let link = db.create_claim_link(id, body.target, link_type).map_err(internal)?;
+    Ok((StatusCode::CREATED, Json(link)))
+}
+
+/// Replace whatever links run from the claim to the target with one of the given type.
+pub async fn put_claim_link(
+    State(state): State<Arc<AppState>>,
+    Path((id, target)): Path<(i64, i64)>,
+    Json(body): Json<LinkChange>,
+) -> Result<Json<engine::ClaimLink>, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    let link_type = engine::LinkType::from_str(&body.link_type).ok_or(StatusCode::BAD_REQUEST)?;
+    let link = db
+        .with_transaction(|db| {
+            if !db.delete_claim_link(id, target)? {
+                return Ok(None);
+            }
+            db.create_claim_link(id, target, link_type).map(Some)
+        })
+        .map_err(internal)?;
+    link.map(Json).ok_or(StatusCode::NOT_FOUND)
+}
+
+pub async fn delete_claim_link(
+    State(state): State<Arc<AppState>>,
+    Path((id, target)): Path<(i64, i64)>,
+) -> Result<StatusCode, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    match db.delete_claim_link(id, target).map_err(internal)? {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Err(StatusCode::NOT_FOUND),
+    }
+}
+
+fn claim_tags(db: &dyn Storage, id: i64) -> Result<Json<ClaimTags>, StatusCode> {
+    Ok(Json(ClaimTags {
+        eras: db.get_claim_eras(id).map_err(internal)?,
+        topics: db.get_claim_topics(id).map_err(internal)?,
+    }))
+}
+
+pub async fn post_claim_tag(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(body): Json<NewTag>,
+) -> Result<Json<ClaimTags>, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    if db.get_claim(id).map_err(internal)?.is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let name = body.name.trim();
+    match body.kind.as_str() {
+        _ if name.is_empty() => return Err(StatusCode::BAD_REQUEST),
+        "era" => {
+            let era = db.get_era_by_name(name).map_err(internal)?.ok_or(StatusCode::BAD_REQUEST)?;
+            db.tag_claim_era(id, era.id).map_err(internal)?;
+        }
+        "topic" => {
+            let topic = db.get_or_create_topic(name).map_err(internal)?;
+            db.tag_claim_topic(id, topic.id).map_err(internal)?;
+        }
+        _ => return Err(StatusCode::BAD_REQUEST),
+    }
+    claim_tags(&*db, id)
+}
+
+pub async fn delete_claim_tag(
+    State(state): State<Arc<AppState>>,
+    Path((id, kind, name)): Path<(i64, String, String)>,
+) -> Result<Json<ClaimTags>, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    let removed = match kind.as_str() {
+        "era" => match db.get_era_by_name(&name).map_err(internal)? {
+            Some(era) => db.untag_claim_era(id, era.id).map_err(internal)?,
+            None => false,
+        },
+        "topic" => match db.get_topic_by_name(&name).map_err(internal)? {
+            Some(topic) => db.untag_claim_topic(id, topic.id).map_err(internal)?,
+            None => false,
+        },
+        _ => return Err(StatusCode::BAD_REQUEST),
+    };
+    if !removed {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    claim_tags(&*db, id)
+}
+
+fn video_tags(db: &dyn Storage, video_id: &str) -> Result<Json<VideoTags>, StatusCode> {
+    Ok(Json(VideoTags {
+        eras: db.get_video_eras(video_id).map_err(internal)?,
+        regions: db.get_video_regions(video_id).map_err(internal)?,
+        topics: db.get_video_topics(video_id).map_err(internal)?,
+    }))
+}
+
+pub async fn post_video_tag(
+    State(state): State<Arc<AppState>>,
+    Path(video_id): Path<String>,
+    Json(body): Json<NewTag>,
+) -> Result<Json<VideoTags>, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    if db.get_video(&video_id).map_err(internal)?.is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let name = body.name.trim();
+    match body.kind.as_str() {
+        _ if name.is_empty() => return Err(StatusCode::BAD_REQUEST),
+        "era" => {
+            let era = db.get_era_by_name(name).map_err(internal)?.ok_or(StatusCode::BAD_REQUEST)?;
+            db.tag_video_era(&video_id, era.id).map_err(internal)?;
+        }
+        "region" => {
+            let region = match db.get_region_by_name(name).map_err(internal)? {
+                Some(r) => r,
+                None => db.create_region(name, None).map_err(internal)?,
+            };
+            db.tag_video_region(&video_id, region.id).map_err(internal)?;
+        }
+        "topic" => {
+            let topic = db.get_or_create_topic(name).map_err(internal)?;
+            db.tag_video_topic(&video_id, topic.id).map_err(internal)?;
+        }
+        _ => return Err(StatusCode::BAD_REQUEST),
+    }
+    video_tags(&*db, &video_id)
+}
+
+pub async fn delete_video_tag(
+    State(state): State<Arc<AppState>>,
+    Path((video_id, kind, name)): Path<(String, String, String)>,
+) -> Result<Json<VideoTags>, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    let removed = match kind.as_str() {
+        "era" => match db.get_era_by_name(&name).map_err(internal)? {
+            Some(era) => db.untag_video_era(&video_id, era.id).map_err(internal)?,
+            None => false,
+        },
+        "region" => match db.get_region_by_name(&name).map_err(internal)? {
+            Some(region) => db.untag_video_region(&video_id, region.id).map_err(internal)?,
+            None => false,
+        },
+        "topic" => match db.get_topic_by_name(&name).map_err(internal)? {
+            Some(topic) => db.untag_video_topic(&video_id, topic.id).map_err(internal)?,
+            None => false,
+        },
+        _ => return Err(StatusCode::BAD_REQUEST),
+    };
+    if !removed {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    video_tags(&*db, &video_id)
+}
+
+pub async fn get_video_full(
+    State(state): State<Arc<AppState>>,
+    Path(video_id): Path<String>,
+) -> Result<Json<VideoDetail>, StatusCode> {
+    let db = open_db(&state)?;
+    let video = db.get_video(&video_id).map_err(internal)?.ok_or(StatusCode::NOT_FOUND)?;
+    let transcript = match db.get_transcript(&video_id).map_err(internal)? {
+        Some(t) => Some(TranscriptInfo {
+            language: t.language,
+            segments: t.segments.len(),
+            layers: db.list_transcript_layers(&video_id).map_err(internal)?.into_iter().map(|l| l.layer).collect(),
+        }),
+        None => None,
+    };
+    Ok(Json(VideoDetail {
+        video,
+        transcript,
+        tags: video_tags(&*db, &video_id)?.0,
+        notes: db.get_video_notes(&video_id).map_err(internal)?,
+        claims: db.list_claims_for_video(&video_id).map_err(internal)?,
+        visuals: db.get_visuals_for_video(&video_id).map_err(internal)?,
+        evidence: db.get_evidence_for_video(&video_id).map_err(internal)?,
+        quotes: db.get_quotes_for_video(&video_id).map_err(internal)?,
+        sources: db.get_sources_for_video(&video_id).map_err(internal)?,
+        scholars: db.get_scholars_for_video(&video_id).map_err(internal)?,
+        locations: db.get_video_locations(&video_id).map_err(internal)?,
+    }))
+}
+
+pub async fn get_video_transcript(
+    State(state): State<Arc<AppState>>,
+    Path(video_id): Path<String>,
+    Query(q): Query<TranscriptQuery>,
+) -> Result<Json<TranscriptSection>, StatusCode> {
+    let db = open_db(&state)?;
+    let transcript = db.get_transcript(&video_id).map_err(internal)?.ok_or(StatusCode::NOT_FOUND)?;
+    // Segments overlapping from..to
+    let segments: Vec<engine::TranscriptSegment> = transcript.segments.into_iter()
+        .filter(|s| q.from.is_none_or(|from| s.start_time + s.duration > from))
+        .filter(|s| q.to.is_none_or(|to| s.start_time < to))
+        .collect();
+    let segments = if q.paragraphs.unwrap_or(false) {
+        engine::transcript::export::to_paragraphs(&segments)
+    } else {
+        segments
+    };
+    Ok(Json(TranscriptSection { video_id, language: transcript.language, segments }))
+}
+
+pub async fn get_video_notes(
+    State(state): State<Arc<AppState>>,
+    Path(video_id): Path<String>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<engine::Note>, StatusCode> {
+    let db = open_db(&state)?;
+    if db.get_video(&video_id).map_err(internal)?.is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let notes = db.get_video_notes(&video_id).map_err(internal)?;
+    paginate(notes, &page, |n: &engine::Note| n.id.to_string(), |n| &n.text)
+}
+
+pub async fn post_video_note(
+    State(state): State<Arc<AppState>>,
+    Path(video_id): Path<String>,
+    Json(body): Json<NewNote>,
+) -> Result<(StatusCode, Json<engine::Note>), StatusCode> {
+    let db = open_db_for_write(&state)?;
+    if body.text.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if db.get_video(&video_id).map_err(internal)?.is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let note = db.add_note(&video_id, body.timestamp, body.text.trim()).map_err(internal)?;
+    Ok((StatusCode::CREATED, Json(note)))
+}
+
+pub async fn put_note(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(body): Json<NoteChanges>,
+) -> Result<Json<engine::Note>, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    let text = body.text.as_deref().map(str::trim);
+    if text == Some("") {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if db.get_note(id).map_err(internal)?.is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    db.update_note(id, text, body.timestamp).map_err(internal)?;
+    db.get_note(id).map_err(internal)?.map(Json).ok_or(StatusCode::NOT_FOUND)
+}
+
+pub async fn delete_note(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    match db.delete_note(id).map_err(internal)? {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Err(StatusCode::NOT_FOUND),
+    }
+}
+
+pub async fn get_collections(
+    State(state): State<Arc<AppState>>,
+    Query(page): Query<PageQuery>,
+) -> Result<Paged<engine::Collection>, StatusCode> {
+    let db = open_db(&state)?;
+    let collections = db.list_collections().map_err(internal)?;
+    paginate(collections, &page, |c: &engine::Collection| c.id.to_string(), |c| &c.name)
+}
+
+pub async fn post_collection(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<NewCollection>,
+) -> Result<(StatusCode, Json<engine::Collection>), StatusCode> {
+    let db = open_db_for_write(&state)?;
+    let name = body.name.trim();
+    if name.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if db.get_collection_by_name(name).map_err(internal)?.is_some() {
+        return Err(StatusCode::CONFLICT);
+    }
+    let collection = db.create_collection(name, body.description.as_deref()).map_err(internal)?;
+    Ok((StatusCode::CREATED, Json(collection)))
+}
+
+pub async fn put_collection(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Json(body): Json<CollectionChanges>,
+) -> Result<Json<engine::Collection>, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    let collection = db.get_collection_by_name(&name).map_err(internal)?.ok_or(StatusCode::NOT_FOUND)?;
+    let new_name = body.name.as_deref().map(str::trim);
+    if new_name == Some("") {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if let Some(n) = new_name {
+        if db.get_collection_by_name(n).map_err(internal)?.is_some_and(|c| c.id != collection.id) {
+            return Err(StatusCode::CONFLICT);
+        }
+    }
+    db.update_collection(collection.id, new_name, body.description.as_ref().map(|d| d.as_deref()))
+        .map_err(internal)?;
+    let name = new_name.unwrap_or(&collection.name);
+    db.get_collection_by_name(name).map_err(internal)?.map(Json).ok_or(StatusCode::NOT_FOUND)
+}
+
+pub async fn delete_collection(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    let collection = db.get_collection_by_name(&name).map_err(internal)?.ok_or(StatusCode::NOT_FOUND)?;
+    db.delete_collection(collection.id).map_err(internal)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn post_collection_video(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Json(body): Json<CollectionMember>,
+) -> Result<StatusCode, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    let collection = db.get_collection_by_name(&name).map_err(internal)?.ok_or(StatusCode::NOT_FOUND)?;
+    if db.get_video(&body.video_id).map_err(internal)?.is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    db.add_video_to_collection(&body.video_id, collection.id).map_err(internal)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn delete_collection_video(
+    State(state): State<Arc<AppState>>,
+    Path((name, video_id)): Path<(String, String)>,
+) -> Result<StatusCode, StatusCode> {
+    let db = open_db_for_write(&state)?;
+    let collection = db.get_collection_by_name(&name).map_err(internal)?.ok_or(StatusCode::NOT_FOUND)?;
+    match db.remove_video_from_collection(&video_id, collection.id).map_err(internal)? {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Err(StatusCode::NOT_FOUND),
+    }
+}
+
+/// Liveness for load balancers and supervisors: 200 while the database answers, 503 when
+/// it doesn't. Needs no token.
+pub async fn get_healthz(State(state): State<Arc<AppState>>) -> (StatusCode, &'static str) {
+    match open_db(&state).map(|db| db.schema_version()) {
+        Ok(Ok(_)) => (StatusCode::OK, "ok\n"),
+        _ => (StatusCode::SERVICE_UNAVAILABLE, "database unavailable\n"),
+    }
+}
+
+pub async fn get_version(State(state): State<Arc<AppState>>) -> Result<Json<BuildInfo>, StatusCode> {
+    let db = open_db(&state)?;
+    let features = [
+        ("anthropic", cfg!(feature = "anthropic")),
+        ("openai", cfg!(feature = "openai")),
+        ("ollama", cfg!(feature = "ollama")),
+        ("sqlite-vec", cfg!(feature = "sqlite-vec")),
+        ("sqlcipher", cfg!(feature = "sqlcipher")),
+        ("tls", cfg!(feature = "tls")),
+    ];
+    Ok(Json(BuildInfo {
+        version: env!("CARGO_PKG_VERSION"),
+        schema_version: db.schema_version().map_err(internal)?,
+        features: features.into_iter().filter(|(_, on)| *on).map(|(name, _)| name).collect(),
+        read_only: state.read_only,
+        uptime_seconds: state.metrics.uptime_seconds(),
+    }))
+}
+
+pub async fn get_openapi() -> Json<serde_json::Value> {
+    Json(super::openapi::spec())
+}
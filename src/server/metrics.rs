@@ -0,0 +1,139 @@
+use axum::extract::{MatchedPath, State};
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use super::handlers::queue_summary;
+use super::{internal, open_db, AppState};
+
+/// Upper bounds, in seconds, of the request latency histogram's buckets.
+const BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// Requests served since startup, by route pattern (e.g. `/api/claims/:id`) rather than
+/// path, so the series stay few.
+pub struct Metrics {
+    started: Instant,
+    routes: Mutex<BTreeMap<(String, String), Route>>,
+}
+
+/// One method and route: requests by status, and a latency histogram.
+#[derive(Default)]
+struct Route {
+    statuses: BTreeMap<u16, u64>,
+    buckets: [u64; BUCKETS.len()],
+    count: u64,
+    seconds: f64,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Metrics { started: Instant::now(), routes: Mutex::default() }
+    }
+
+    fn record(&self, method: &str, route: &str, status: StatusCode, seconds: f64) {
+        let mut routes = self.routes.lock().unwrap();
+        let stats = routes.entry((method.to_string(), route.to_string())).or_default();
+        *stats.statuses.entry(status.as_u16()).or_default() += 1;
+        for (bucket, bound) in stats.buckets.iter_mut().zip(BUCKETS) {
+            if seconds <= *bound {
+                *bucket += 1;
+            }
+        }
+        stats.count += 1;
+        stats.seconds += seconds;
+    }
+
+    pub fn uptime_seconds(&self) -> u64 {
+        self.started.elapsed().as_secs()
+    }
+}
+
+/// Times every routed request, including those turned away for want of a token. For
+/// `/api/events` this is the time to open the stream, not how long it stayed open.
+pub async fn track(
+    State(state): State<Arc<AppState>>,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| request.uri().path().to_string(), |p| p.as_str().to_string());
+    let start = Instant::now();
+    let response = next.run(request).await;
+    state.metrics.record(&method, &route, response.status(), start.elapsed().as_secs_f64());
+    response
+}
+
+/// Prometheus text exposition: the request counters and latencies, plus the database's
+/// size, the processing queue and row counts read fresh on each scrape.
+pub async fn get_metrics(State(state): State<Arc<AppState>>) -> Result<impl IntoResponse, StatusCode> {
+    let db = open_db(&state)?;
+    let size = db.size_bytes().map_err(internal)?;
+    let queue = queue_summary(&*db).map_err(internal)?;
+    let entities = db.entity_counts().map_err(internal)?;
+    drop(db);
+
+    let mut out = String::new();
+    let _ = writeln!(out, "# HELP engine_build_info Version of the running server.");
+    let _ = writeln!(out, "# TYPE engine_build_info gauge");
+    let _ = writeln!(out, "engine_build_info{{version=\"{}\"}} 1", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(out, "# HELP engine_uptime_seconds Seconds since the server started.");
+    let _ = writeln!(out, "# TYPE engine_uptime_seconds gauge");
+    let _ = writeln!(out, "engine_uptime_seconds {}", state.metrics.uptime_seconds());
+
+    {
+        let routes = state.metrics.routes.lock().unwrap();
+        let _ = writeln!(out, "# HELP engine_http_requests_total HTTP requests served, by route and status.");
+        let _ = writeln!(out, "# TYPE engine_http_requests_total counter");
+        for ((method, route), stats) in routes.iter() {
+            for (status, count) in &stats.statuses {
+                let _ = writeln!(
+                    out,
+                    "engine_http_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}",
+                    method, escape(route), status, count
+                );
+            }
+        }
+        let _ = writeln!(out, "# HELP engine_http_request_duration_seconds Time to answer HTTP requests, by route.");
+        let _ = writeln!(out, "# TYPE engine_http_request_duration_seconds histogram");
+        for ((method, route), stats) in routes.iter() {
+            let labels = format!("method=\"{}\",route=\"{}\"", method, escape(route));
+            for (bound, count) in BUCKETS.iter().zip(stats.buckets) {
+                let _ = writeln!(out, "engine_http_request_duration_seconds_bucket{{{},le=\"{}\"}} {}", labels, bound, count);
+            }
+            let _ = writeln!(out, "engine_http_request_duration_seconds_bucket{{{},le=\"+Inf\"}} {}", labels, stats.count);
+            let _ = writeln!(out, "engine_http_request_duration_seconds_sum{{{}}} {}", labels, stats.seconds);
+            let _ = writeln!(out, "engine_http_request_duration_seconds_count{{{}}} {}", labels, stats.count);
+        }
+    }
+
+    let _ = writeln!(out, "# HELP engine_database_size_bytes Size of the database, not counting its write-ahead log.");
+    let _ = writeln!(out, "# TYPE engine_database_size_bytes gauge");
+    let _ = writeln!(out, "engine_database_size_bytes {}", size);
+    let _ = writeln!(out, "# HELP engine_queue_items Processing queue entries, by status.");
+    let _ = writeln!(out, "# TYPE engine_queue_items gauge");
+    for (status, count) in [
+        ("pending", queue.pending),
+        ("in_progress", queue.in_progress),
+        ("completed", queue.completed),
+        ("failed", queue.failed),
+    ] {
+        let _ = writeln!(out, "engine_queue_items{{status=\"{}\"}} {}", status, count);
+    }
+    let _ = writeln!(out, "# HELP engine_entities Rows in each knowledge table.");
+    let _ = writeln!(out, "# TYPE engine_entities gauge");
+    for (table, count) in entities {
+        let _ = writeln!(out, "engine_entities{{table=\"{}\"}} {}", table, count);
+    }
+
+    Ok(([(header::CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")], out))
+}
+
+/// A label value with its backslashes and quotes escaped.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
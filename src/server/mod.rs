@@ -0,0 +1,353 @@
+use anyhow::Result;
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::Json,
+    routing::{delete, get, post, put},
+    Router,
+};
+use engine::{Database, DatabaseLocation, Storage};
+use engine::storage::database::ConnectionOptions;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tower_http::compression::CompressionLayer;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use crate::EmbeddingOptions;
+
+mod events;
+mod handlers;
+mod metrics;
+mod models;
+mod openapi;
+
+use handlers::*;
+use models::{PageQuery, Paged};
+
+/// How `serve` listens and whom it lets in.
+pub struct ServeOptions {
+    pub bind: IpAddr,
+    pub port: u16,
+    pub auth_token: Option<String>,
+    pub cors_origins: Vec<String>,
+    /// PEM certificate chain and private key; HTTPS when given
+    pub tls: Option<(PathBuf, PathBuf)>,
+}
+
+struct AppState {
+    database: DatabaseLocation,
+    db_options: ConnectionOptions,
+    read_only: bool,
+    cache: Arc<engine::QueryCache>,
+    embedding: EmbeddingOptions,
+    auth_token: Option<String>,
+    subscribers: events::Subscribers,
+    metrics: metrics::Metrics,
+}
+
+fn open_db(state: &AppState) -> Result<Box<dyn Storage>, StatusCode> {
+    if state.read_only {
+        let db = Database::open_read_only(&state.database, &state.db_options).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        return Ok(Box::new(db));
+    }
+    let db = Database::open_with(&state.database, &state.db_options).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    db.set_audit_source("api").map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Box::new(db))
+}
+
+fn parse_eras(era: &Option<String>) -> Vec<String> {
+    era.as_ref()
+        .map(|s| s.split(',').map(|e| e.trim().to_string()).filter(|e| !e.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+/// Filter `items` by `page.q` against `text`, then cut out the requested page. `key`
+/// identifies an item for cursors. An unknown cursor is a bad request.
+fn paginate<T>(
+    items: Vec<T>,
+    page: &PageQuery,
+    key: impl Fn(&T) -> String,
+    text: impl Fn(&T) -> &str,
+) -> Result<Paged<T>, StatusCode> {
+    let needle = page.q.as_deref().map(str::trim).filter(|q| !q.is_empty()).map(str::to_lowercase);
+    let items: Vec<T> = match needle {
+        Some(needle) => items.into_iter().filter(|item| text(item).to_lowercase().contains(&needle)).collect(),
+        None => items,
+    };
+    let total = items.len();
+    let start = match &page.cursor {
+        Some(cursor) => items.iter().position(|item| &key(item) == cursor).ok_or(StatusCode::BAD_REQUEST)? + 1,
+        None => 0,
+    };
+    let start = start.saturating_add(page.offset.unwrap_or(0)).min(total);
+    let end = page.limit.map_or(total, |limit| start.saturating_add(limit).min(total));
+
+    let mut headers = axum::http::HeaderMap::new();
+    headers.insert("x-total-count", total.into());
+    if end < total && end > 0 {
+        if let Ok(cursor) = key(&items[end - 1]).parse() {
+            headers.insert("x-next-cursor", cursor);
+        }
+    }
+    let items = items.into_iter().skip(start).take(end - start).collect();
+    Ok((headers, Json(items)))
+}
+
+/// For the write API: the same edits as the CLI commands, refused when serving read-only.
+fn open_db_for_write(state: &AppState) -> Result<Box<dyn Storage>, StatusCode> {
+    if state.read_only {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    open_db(state)
+}
+
+fn internal<E>(_: E) -> StatusCode {
+    StatusCode::INTERNAL_SERVER_ERROR
+}
+
+/// Once `--auth-token` is given or any token exists, API requests (and `/metrics`) need
+/// one: as `Authorization: Bearer <token>`, or `?token=` where a client can't set headers.
+/// Read-only tokens may only read.
+async fn require_token(
+    State(state): State<Arc<AppState>>,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    use axum::http::{header, Method};
+    use axum::response::IntoResponse;
+
+    let path = request.uri().path();
+    if !(path.starts_with("/api/") || path == "/metrics") || request.method() == Method::OPTIONS {
+        return next.run(request).await;
+    }
+    let presented = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|t| t.trim().to_string())
+        .or_else(|| {
+            request.uri().query()?.split('&').find_map(|pair| pair.strip_prefix("token=")).map(str::to_string)
+        });
+
+    let db = match open_db(&state) {
+        Ok(db) => db,
+        Err(status) => return status.into_response(),
+    };
+    let scope = match presented {
+        Some(t) if state.auth_token.as_deref() == Some(t.as_str()) => Some(engine::TokenScope::Write),
+        Some(t) => match db.find_api_token(&t) {
+            Ok(Some(token)) => {
+                // A read-only server can't record the use; that's fine
+                if !state.read_only {
+                    let _ = db.touch_api_token(token.id);
+                }
+                Some(token.scope)
+            }
+            Ok(None) => None,
+            Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        },
+        None => None,
+    };
+    let required = state.auth_token.is_some() || db.has_api_tokens().unwrap_or(true);
+    drop(db);
+
+    match scope {
+        None if required => (StatusCode::UNAUTHORIZED, [(header::WWW_AUTHENTICATE, "Bearer")]).into_response(),
+        Some(engine::TokenScope::Read) if !matches!(*request.method(), Method::GET | Method::HEAD) => {
+            StatusCode::FORBIDDEN.into_response()
+        }
+        _ => next.run(request).await,
+    }
+}
+
+/// CORS for the given origins: none without any (the web UI is same-origin), anything
+/// with `*`. Requests may send a token and JSON, and read the paging headers and ETag.
+fn cors_layer(origins: &[String]) -> Result<Option<CorsLayer>> {
+    use axum::http::{header, HeaderName, HeaderValue, Method};
+
+    let origins: Vec<&str> = origins.iter().map(|o| o.trim().trim_end_matches('/')).filter(|o| !o.is_empty()).collect();
+    if origins.is_empty() {
+        return Ok(None);
+    }
+    if origins.contains(&"*") {
+        return Ok(Some(CorsLayer::permissive()));
+    }
+    let allowed = origins
+        .iter()
+        .map(|o| {
+            if !o.starts_with("http://") && !o.starts_with("https://") {
+                anyhow::bail!("CORS origin must be scheme://host[:port], e.g. https://example.com: {}", o);
+            }
+            HeaderValue::from_str(o).map_err(|_| anyhow::anyhow!("Invalid CORS origin: {}", o))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Some(
+        CorsLayer::new()
+            .allow_origin(AllowOrigin::list(allowed))
+            .allow_methods([Method::GET, Method::HEAD, Method::POST, Method::PUT, Method::DELETE])
+            .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE, header::IF_NONE_MATCH])
+            .expose_headers([
+                HeaderName::from_static("x-total-count"),
+                HeaderName::from_static("x-next-cursor"),
+                header::ETAG,
+                header::CONTENT_DISPOSITION,
+            ]),
+    ))
+}
+
+async fn get_index() -> axum::response::Html<&'static str> {
+    axum::response::Html(include_str!("../../static/index.html"))
+}
+
+pub fn serve(database: DatabaseLocation, db_options: ConnectionOptions, read_only: bool, options: ServeOptions) -> Result<()> {
+    let ServeOptions { bind, port, auth_token, cors_origins, tls } = options;
+    if tls.is_some() && !cfg!(feature = "tls") {
+        anyhow::bail!("HTTPS needs a build with --features tls");
+    }
+    let cors = cors_layer(&cors_origins)?;
+    let state = Arc::new(AppState {
+        database,
+        db_options,
+        read_only,
+        cache: Arc::new(engine::QueryCache::new()),
+        embedding: EmbeddingOptions::from_env()?,
+        auth_token: auth_token.filter(|t| !t.is_empty()),
+        subscribers: events::Subscribers::default(),
+        metrics: metrics::Metrics::new(),
+    });
+    let auth_required = state.auth_token.is_some()
+        || open_db(&state).map_err(|_| anyhow::anyhow!("Can't open the database"))?.has_api_tokens()?;
+
+    let watched = state.clone();
+    let app = Router::new()
+        .route("/", get(get_index))
+        .route("/healthz", get(get_healthz))
+        .route("/metrics", get(metrics::get_metrics))
+        .route("/api/version", get(get_version))
+        .route("/api/pins", get(get_pins))
+        .route("/api/timeline", get(get_timeline))
+        .route("/api/eras", get(get_eras))
+        .route("/api/topics", get(get_topics))
+        .route("/api/videos", get(get_videos))
+        .route("/api/claims", get(get_claims).post(post_claim))
+        .route("/api/claims/:id", get(get_claim).put(put_claim).delete(delete_claim))
+        .route("/api/claims/:id/links", post(post_claim_link))
+        .route("/api/claims/:id/links/:target", put(put_claim_link).delete(delete_claim_link))
+        .route("/api/claims/:id/tags", post(post_claim_tag))
+        .route("/api/claims/:id/tags/:kind/:name", delete(delete_claim_tag))
+        .route("/api/videos/:id/full", get(get_video_full))
+        .route("/api/videos/:id/transcript", get(get_video_transcript))
+        .route("/api/videos/:id/tags", post(post_video_tag))
+        .route("/api/videos/:id/tags/:kind/:name", delete(delete_video_tag))
+        .route("/api/videos/:id/notes", get(get_video_notes).post(post_video_note))
+        .route("/api/notes/:id", put(put_note).delete(delete_note))
+        .route("/api/collections", get(get_collections).post(post_collection))
+        .route("/api/collections/:name", put(put_collection).delete(delete_collection))
+        .route("/api/collections/:name/videos", post(post_collection_video))
+        .route("/api/collections/:name/videos/:video_id", delete(delete_collection_video))
+        .route("/api/attachments", get(get_attachments))
+        .route("/api/attachments/:id", get(get_attachment_file))
+        .route("/api/graph", get(get_graph))
+        .route("/api/causal", get(get_causal))
+        .route("/api/export/collections/:name", get(get_export_collection))
+        .route("/api/export/map", get(get_export_map))
+        .route("/api/export/graph", get(get_export_graph))
+        .route("/api/mocs", get(get_mocs).post(post_moc))
+        .route("/api/mocs/:id", get(get_moc).put(put_moc).delete(delete_moc))
+        .route("/api/mocs/:id/claims", post(post_moc_claim).put(put_moc_claims))
+        .route("/api/mocs/:id/claims/:claim_id", delete(delete_moc_claim))
+        .route("/api/questions", get(get_questions).post(post_question))
+        .route("/api/questions/:id", get(get_question).delete(delete_question))
+        .route("/api/questions/:id/status", put(put_question_status))
+        .route("/api/questions/:id/evidence", post(post_question_evidence))
+        .route("/api/stats", get(get_stats))
+        .route("/api/reports/:dimension", get(get_report))
+        .route("/api/projection", get(get_projection))
+        .route("/api/contradictions", get(get_contradictions))
+        .route("/api/semantic", get(get_semantic))
+        .route("/api/similar/:type/:id", get(get_similar))
+        .route("/api/review/orphans", get(get_review_orphans))
+        .route("/api/review/stale", get(get_review_stale))
+        .route("/api/queue", get(get_queue))
+        .route("/api/queue/items", get(get_queue_items).post(post_queue_item))
+        .route("/api/queue/items/:video_id/skip", post(post_queue_skip))
+        .route("/api/queue/items/:video_id/reset", post(post_queue_reset))
+        .route("/api/queue/items/:video_id/priority", post(post_queue_priority))
+        // Phase 8: Analytical frameworks
+        .route("/api/world-systems/entities", get(get_world_entities))
+        .route("/api/world-systems/flows", get(get_world_flows))
+        .route("/api/transmissions", get(get_transmissions))
+        .route("/api/cyclical", get(get_cyclical))
+        // Phase 12: Expanded knowledge entity endpoints
+        .route("/api/sources", get(get_sources))
+        .route("/api/scholars", get(get_scholars))
+        .route("/api/terms", get(get_terms))
+        .route("/api/visuals", get(get_visuals))
+        .route("/api/evidence", get(get_evidence))
+        .route("/api/quotes", get(get_quotes))
+        // Unified search endpoint
+        .route("/api/search", get(search))
+        .route("/api/events", get(events::get_events))
+        .route("/api/openapi.json", get(get_openapi))
+        .route_layer(axum::middleware::from_fn_with_state(state.clone(), require_token))
+        .route_layer(axum::middleware::from_fn_with_state(state.clone(), metrics::track))
+        // Server-sent events are left uncompressed, so they aren't held back in a buffer
+        .layer(CompressionLayer::new())
+        .with_state(state);
+    let app = match cors {
+        Some(cors) => app.layer(cors),
+        None => app,
+    };
+
+    let addr = SocketAddr::new(bind, port);
+    let scheme = if tls.is_some() { "https" } else { "http" };
+    let host = if bind.is_unspecified() || bind.is_loopback() { format!("localhost:{}", port) } else { addr.to_string() };
+    println!("Starting server at {}://{}", scheme, host);
+    if bind.is_unspecified() {
+        println!("Listening on every network interface; other devices can connect at {}://<this machine's address>:{}.", scheme, port);
+        if tls.is_none() {
+            println!("Traffic is unencrypted: tokens and notes cross the network in the clear. Use --cert and --key for HTTPS.");
+        }
+        if !auth_required && !read_only {
+            println!("Anyone who can reach this machine can edit the knowledge base; consider --auth-token or --read-only.");
+        }
+    }
+    if read_only {
+        println!("Database opened read-only; the knowledge base can't be modified through this server.");
+    }
+    if !cors_origins.is_empty() {
+        println!("Allowing cross-origin API requests from {}.", cors_origins.join(", "));
+    }
+    if auth_required {
+        println!("API requests need a token; open the web UI at /?token=<token>.");
+    }
+    println!("Open in your browser to view the knowledge base.");
+
+    tokio::runtime::Runtime::new()?
+        .block_on(async {
+            tokio::spawn(events::watch(watched));
+            match tls {
+                Some((cert, key)) => serve_tls(addr, app, cert, key).await,
+                None => {
+                    let listener = tokio::net::TcpListener::bind(addr).await?;
+                    axum::serve(listener, app).await
+                }
+            }
+        })
+        .map_err(|e| anyhow::anyhow!("Server error: {}", e))
+}
+
+#[cfg(feature = "tls")]
+async fn serve_tls(addr: SocketAddr, app: Router, cert: PathBuf, key: PathBuf) -> std::io::Result<()> {
+    let config = axum_server::tls_rustls::RustlsConfig::from_pem_file(&cert, &key).await.map_err(|e| {
+        std::io::Error::new(e.kind(), format!("Can't load {} and {}: {}", cert.display(), key.display(), e))
+    })?;
+    axum_server::bind_rustls(addr, config).serve(app.into_make_service()).await
+}
+
+// Unreachable: `serve` refuses --cert without the feature
+#[cfg(not(feature = "tls"))]
+async fn serve_tls(_: SocketAddr, _: Router, _: PathBuf, _: PathBuf) -> std::io::Result<()> {
+    Err(std::io::Error::other("HTTPS needs a build with --features tls"))
+}
@@ -0,0 +1,521 @@
+use axum::response::Json;
+
+// Query parameters
+
+#[derive(serde::Deserialize)]
+pub struct MapQuery {
+    pub era: Option<String>,  // Comma-separated eras
+    pub topic: Option<String>,
+    pub bbox: Option<String>, // west,south,east,north in degrees
+    pub zoom: Option<u8>,     // Leaflet zoom level; clusters the pins when given
+}
+
+#[derive(serde::Deserialize)]
+pub struct ClaimsQuery {
+    pub video_id: Option<String>,
+    pub category: Option<String>,
+    pub status: Option<String>,
+}
+
+/// Paging and text filtering shared by the list endpoints. `cursor` is the key of the
+/// last item of the previous page, as returned in `X-Next-Cursor`; `offset` counts from
+/// there (or from the start).
+#[derive(serde::Deserialize)]
+pub struct PageQuery {
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+    pub cursor: Option<String>,
+    pub q: Option<String>,  // Case-insensitive substring of the item's name or text
+}
+
+/// A page of a list: the items as a JSON array, with `X-Total-Count` (items matching
+/// the filter, across all pages) and, when more remain, `X-Next-Cursor`.
+pub type Paged<T> = (axum::http::HeaderMap, Json<Vec<T>>);
+
+#[derive(serde::Deserialize)]
+pub struct GraphQuery {
+    pub video_id: Option<String>,
+    pub moc_id: Option<i64>,
+    pub era: Option<String>,  // Comma-separated eras
+    pub topic: Option<String>,
+    pub status: Option<String>, // Comma-separated claim statuses
+    pub category: Option<String>, // Comma-separated claim categories
+    pub min_confidence: Option<String>,
+    pub link_types: Option<String>, // Comma-separated link types
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct TimelineQuery {
+    pub kind: Option<String>, // Comma-separated entry kinds
+    pub from: Option<i32>,    // Years, negative for BCE
+    pub to: Option<i32>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct CausalQuery {
+    pub claim: i64,
+    pub depth: Option<usize>, // Hops from the claim, default 3
+}
+
+#[derive(serde::Deserialize)]
+pub struct TranscriptQuery {
+    pub from: Option<f64>, // Seconds
+    pub to: Option<f64>,
+    pub paragraphs: Option<bool>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct ProjectionQuery {
+    pub source: Option<String>, // video, chunk or claim
+    pub model: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct ContradictionsQuery {
+    pub by: Option<String>, // topic (default) or moc
+}
+
+#[derive(serde::Deserialize)]
+pub struct SemanticQuery {
+    pub q: String,
+    pub source: Option<String>, // video, chunk or claim
+    pub limit: Option<usize>,
+    pub diversify: Option<f32>, // MMR weight, 0 (most diverse) to 1
+}
+
+#[derive(serde::Deserialize)]
+pub struct SimilarQuery {
+    pub limit: Option<usize>,
+    pub diversify: Option<f32>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct SearchQuery {
+    pub q: String,                      // Search query
+    pub types: Option<String>,          // Comma-separated: "claim,video,scholar"
+    pub video_id: Option<String>,       // Filter to specific video
+    pub limit: Option<usize>,           // Max results (default 50)
+    pub fuzzy_threshold: Option<f64>,   // 0.0-1.0, default 0.6
+}
+
+#[derive(serde::Deserialize)]
+pub struct ExportGraphQuery {
+    pub format: Option<String>, // dot (default) or graphml
+    pub video_id: Option<String>,
+    pub moc_id: Option<i64>,
+    pub era: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct QueueItemsQuery {
+    pub status: Option<String>, // Comma-separated processing statuses
+}
+
+#[derive(serde::Deserialize)]
+pub struct WorldSystemsQuery {
+    pub era: Option<String>,       // Comma-separated eras
+    pub position: Option<String>,  // core, semi_periphery or periphery; entities only
+    pub commodity: Option<String>, // Flows only
+}
+
+#[derive(serde::Deserialize)]
+pub struct TransmissionsQuery {
+    pub era: Option<String>, // Comma-separated eras
+    #[serde(rename = "type")]
+    pub kind: Option<String>, // horizontal, vertical or oblique
+}
+
+#[derive(serde::Deserialize)]
+pub struct CyclicalQuery {
+    pub era: Option<String>, // Comma-separated eras
+    #[serde(rename = "type")]
+    pub kind: Option<String>, // Cyclical indicator type, e.g. fiscal_strain
+    pub entity: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct AttachmentsQuery {
+    pub video_id: Option<String>,
+    pub claim_id: Option<i64>,
+}
+
+// Request bodies
+
+/// Tells a field set to null (Some(None)) apart from one left out (None).
+pub fn nullable<'de, D, T>(d: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Deserialize<'de>,
+{
+    <Option<T> as serde::Deserialize>::deserialize(d).map(Some)
+}
+
+#[derive(serde::Deserialize)]
+pub struct NewClaim {
+    pub video_id: String,
+    pub text: String,
+    #[serde(default)]
+    pub quote: String,
+    pub category: Option<String>,
+    pub confidence: Option<String>,
+    pub timestamp: Option<f64>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct ClaimChanges {
+    pub text: Option<String>,
+    pub quote: Option<String>,
+    pub category: Option<String>,
+    pub confidence: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub timestamp: Option<Option<f64>>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct NewLink {
+    pub target: i64,
+    #[serde(rename = "type")]
+    pub link_type: String,
+}
+
+#[derive(serde::Deserialize)]
+pub struct LinkChange {
+    #[serde(rename = "type")]
+    pub link_type: String,
+}
+
+#[derive(serde::Deserialize)]
+pub struct NewMoc {
+    pub title: String,
+    pub description: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct MocChanges {
+    pub title: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct MocMember {
+    pub claim_id: i64,
+    pub sort_order: Option<i32>, // After the MOC's other claims if left out
+}
+
+#[derive(serde::Deserialize)]
+pub struct MocOrder {
+    pub claim_ids: Vec<i64>, // Every claim in the MOC, in the new order
+}
+
+#[derive(serde::Deserialize)]
+pub struct NewQuestion {
+    pub question: String,
+    pub parent_id: Option<i64>,
+    pub notes: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct NewEvidence {
+    pub claim_id: Option<i64>,
+    pub video_id: Option<String>,
+    pub relevance: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct StatusChange {
+    pub status: String,
+}
+
+#[derive(serde::Deserialize)]
+pub struct NewQueueItem {
+    pub video_id: String,
+    pub priority: Option<i32>, // Higher is processed first; 0 if left out
+}
+
+#[derive(serde::Deserialize)]
+pub struct PriorityChange {
+    pub priority: i32,
+}
+
+#[derive(serde::Deserialize)]
+pub struct NewNote {
+    pub text: String,
+    pub timestamp: Option<f64>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct NoteChanges {
+    pub text: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub timestamp: Option<Option<f64>>,
+}
+
+/// An era, region or topic to tag with, by name. Regions and topics are created
+/// as needed, as with `tag`; eras must already exist.
+#[derive(serde::Deserialize)]
+pub struct NewTag {
+    pub kind: String,
+    pub name: String,
+}
+
+#[derive(serde::Deserialize)]
+pub struct NewCollection {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct CollectionChanges {
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub description: Option<Option<String>>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct CollectionMember {
+    pub video_id: String,
+}
+
+// Responses
+
+// Graph node/edge structures for vis.js
+#[derive(serde::Serialize)]
+pub struct GraphNode {
+    pub id: i64,
+    pub label: String,
+    pub title: String,      // Hover text
+    pub group: String,      // Category for coloring
+    pub value: usize,       // Node size (connections, boosted by betweenness once graph-metrics has run)
+    pub video_id: String,
+    pub timestamp: Option<f64>,
+    pub betweenness: Option<f64>,
+    pub component: Option<usize>,
+}
+
+#[derive(serde::Serialize)]
+pub struct GraphEdge {
+    pub from: i64,
+    pub to: i64,
+    pub label: String,
+    pub arrows: String,
+    pub dashes: bool,       // Dashed for contradicts
+    pub color: EdgeColor,
+}
+
+#[derive(serde::Serialize)]
+pub struct EdgeColor {
+    pub color: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct GraphData {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    /// Claims matching the filters, across all pages
+    pub total: usize,
+}
+
+/// The causal relations around a claim, for a causal loop diagram.
+#[derive(serde::Serialize)]
+pub struct CausalDiagram {
+    pub claim_id: i64,
+    pub nodes: Vec<CausalNode>,
+    pub edges: Vec<CausalEdge>,
+    pub loops: Vec<engine::CausalLoop>,
+}
+
+#[derive(serde::Serialize)]
+pub struct CausalNode {
+    pub id: i64,
+    pub label: String,
+    pub title: String,  // Hover text
+    pub group: String,  // Category for coloring
+    pub depth: usize,   // Hops from the claim asked about
+    pub video_id: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct CausalEdge {
+    pub id: i64,  // Causal relation ID
+    pub from: i64,
+    pub to: i64,
+    pub loop_type: engine::LoopType,
+    pub strength: engine::RelationStrength,
+    pub notes: Option<String>,
+    pub video_id: String,
+}
+
+/// Pins close together at the requested zoom, drawn as one marker.
+#[derive(serde::Serialize)]
+pub struct PinCluster {
+    /// Mean position of the pins
+    pub lat: f64,
+    pub lon: f64,
+    pub count: usize,
+    /// Bounds of the pins, west, south, east, north, for zooming in
+    pub bbox: (f64, f64, f64, f64),
+    /// The pins themselves when they all share one location; empty otherwise
+    pub pins: Vec<engine::MapPin>,
+}
+
+/// Surplus flows laid out for a Sankey diagram: the entities they run between, and one
+/// link per pair of entities whose `value` is how many flows it stands for.
+#[derive(serde::Serialize)]
+pub struct SankeyData {
+    pub nodes: Vec<SankeyNode>,
+    pub links: Vec<SankeyLink>,
+}
+
+#[derive(serde::Serialize)]
+pub struct SankeyNode {
+    pub id: i64, // Geopolitical entity ID
+    pub name: String,
+    pub position: engine::SystemPosition,
+    pub era_id: i64,
+}
+
+#[derive(serde::Serialize)]
+pub struct SankeyLink {
+    pub source: i64,
+    pub target: i64,
+    pub value: usize,
+    pub commodities: Vec<String>,
+    pub flow_ids: Vec<i64>,
+}
+
+#[derive(serde::Serialize)]
+pub struct MocSummary {
+    pub id: i64,
+    pub title: String,
+    pub description: Option<String>,
+    pub claim_count: usize,
+}
+
+#[derive(serde::Serialize)]
+pub struct QuestionSummary {
+    pub id: i64,
+    pub question: String,
+    pub status: String,
+    pub evidence_count: usize,
+}
+
+/// A processing queue entry with its video's title, which is None if the video is gone.
+#[derive(serde::Serialize)]
+pub struct QueueItem {
+    #[serde(flatten)]
+    pub item: engine::AIProcessingQueue,
+    pub title: Option<String>,
+}
+
+#[derive(serde::Serialize, Clone, PartialEq)]
+pub struct QueueSummary {
+    pub pending: usize,
+    pub in_progress: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub current: Option<String>,
+}
+
+#[derive(serde::Serialize, Clone)]
+pub struct FullStats {
+    pub videos: i64,
+    pub claims: i64,
+    pub links: i64,
+    pub mocs: i64,
+    pub questions: i64,
+    pub active_questions: i64,
+    pub patterns: i64,
+    pub orphan_claims: usize,
+    pub stale_claims: usize,
+    pub framework: engine::FrameworkStats,
+    pub claims_by_category: Vec<CategoryCount>,
+    // Phase 12: Expanded knowledge entities
+    pub sources: i64,
+    pub scholars: i64,
+    pub terms: i64,
+    pub visuals: i64,
+    pub evidence: i64,
+    pub quotes: i64,
+}
+
+#[derive(serde::Serialize, Clone)]
+pub struct CategoryCount {
+    pub category: String,
+    pub count: i64,
+}
+
+/// What `/api/version` reports about the running server.
+#[derive(serde::Serialize)]
+pub struct BuildInfo {
+    pub version: &'static str,
+    /// Highest database migration applied
+    pub schema_version: i64,
+    /// Cargo features compiled in
+    pub features: Vec<&'static str>,
+    pub read_only: bool,
+    pub uptime_seconds: u64,
+}
+
+#[derive(serde::Serialize)]
+pub struct VideoSummary {
+    pub id: String,
+    pub title: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct ClaimDetail {
+    #[serde(flatten)]
+    pub claim: engine::ClaimWithLinks,
+    pub sources: Vec<engine::Source>,
+    pub scholars: Vec<engine::ClaimScholar>,
+    pub provenance: Option<engine::ClaimProvenance>,
+}
+
+/// Everything the UI shows for one video, in one response.
+#[derive(serde::Serialize)]
+pub struct VideoDetail {
+    pub video: engine::Video,
+    /// None when the video has no transcript yet
+    pub transcript: Option<TranscriptInfo>,
+    pub tags: VideoTags,
+    pub notes: Vec<engine::Note>,
+    pub claims: Vec<engine::Claim>,
+    pub visuals: Vec<engine::Visual>,
+    pub evidence: Vec<engine::Evidence>,
+    pub quotes: Vec<engine::Quote>,
+    /// Sources and scholars cited, with timestamp and context
+    pub sources: Vec<engine::Cited<engine::Source>>,
+    pub scholars: Vec<engine::Cited<engine::Scholar>>,
+    pub locations: Vec<engine::MapPin>,
+}
+
+/// Part or all of a video's transcript, as segments or merged into paragraphs.
+#[derive(serde::Serialize)]
+pub struct TranscriptSection {
+    pub video_id: String,
+    pub language: String,
+    pub segments: Vec<engine::TranscriptSegment>,
+}
+
+#[derive(serde::Serialize)]
+pub struct TranscriptInfo {
+    pub language: String,
+    pub segments: usize,
+    /// Summary layers generated so far
+    pub layers: Vec<u8>,
+}
+
+#[derive(serde::Serialize)]
+pub struct VideoTags {
+    pub eras: Vec<engine::Era>,
+    pub regions: Vec<engine::Region>,
+    pub topics: Vec<engine::Topic>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ClaimTags {
+    pub eras: Vec<engine::Era>,
+    pub topics: Vec<engine::Topic>,
+}
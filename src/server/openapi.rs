@@ -0,0 +1,638 @@
+use serde_json::{json, Map, Value};
+
+// Written by hand: keep it in step with the routes in `serve` and the structs in
+// `models` (and the `engine` models they return).
+
+/// Component schemas as (field, type). A type is a primitive (`string`, `integer`,
+/// `number`, `boolean`, `date`, `date-time`), another schema's name, `[T]` for an array,
+/// `(A,B)` for a tuple (serialized as an array), with a trailing `?` when it may be null.
+const SCHEMAS: &[(&str, &[(&str, &str)])] = &[
+    ("Claim", &[
+        ("id", "integer"), ("text", "string"), ("video_id", "string"), ("timestamp", "number?"),
+        ("source_quote", "string"), ("category", "ClaimCategory"), ("confidence", "Confidence"),
+        ("created_at", "date-time"), ("status", "ClaimStatus"), ("date_start", "integer?"), ("date_end", "integer?"),
+    ]),
+    ("ClaimLink", &[
+        ("id", "integer"), ("source_claim_id", "integer"), ("target_claim_id", "integer"),
+        ("link_type", "LinkType"), ("created_at", "date-time"),
+    ]),
+    ("ClaimDetail", &[
+        ("claim", "Claim"), ("outgoing_links", "[(ClaimLink,Claim)]"), ("incoming_links", "[(ClaimLink,Claim)]"),
+        ("sources", "[Source]"), ("scholars", "[ClaimScholar]"), ("provenance", "ClaimProvenance?"),
+    ]),
+    ("ClaimScholar", &[("scholar", "Scholar"), ("attribution", "string?")]),
+    ("ClaimProvenance", &[
+        ("claim_id", "integer"), ("provider", "string?"), ("model", "string?"), ("prompt_version", "string?"),
+        ("run_id", "string?"), ("reviewed_at", "date-time?"),
+    ]),
+    ("ClaimTags", &[("eras", "[Era]"), ("topics", "[Topic]")]),
+    ("VideoTags", &[("eras", "[Era]"), ("regions", "[Region]"), ("topics", "[Topic]")]),
+    ("Video", &[
+        ("id", "string"), ("url", "string"), ("title", "string"), ("channel", "string?"), ("upload_date", "date?"),
+        ("description", "string?"), ("added_at", "date-time"), ("duration", "number?"), ("view_count", "integer?"),
+        ("thumbnail_url", "string?"),
+    ]),
+    ("VideoSummary", &[("id", "string"), ("title", "string")]),
+    ("VideoDetail", &[
+        ("video", "Video"), ("transcript", "TranscriptInfo?"), ("tags", "VideoTags"), ("notes", "[Note]"),
+        ("claims", "[Claim]"), ("visuals", "[Visual]"), ("evidence", "[Evidence]"), ("quotes", "[Quote]"),
+        ("sources", "[(Source,number?,string?)]"), ("scholars", "[(Scholar,number?,string?)]"), ("locations", "[MapPin]"),
+    ]),
+    ("QueueItem", &[
+        ("id", "integer"), ("video_id", "string"), ("status", "ProcessingStatus"), ("priority", "integer"),
+        ("created_at", "date-time"), ("started_at", "date-time?"), ("completed_at", "date-time?"),
+        ("error_message", "string?"), ("claims_extracted", "integer"), ("title", "string?"),
+    ]),
+    ("TranscriptSection", &[("video_id", "string"), ("language", "string"), ("segments", "[TranscriptSegment]")]),
+    ("TranscriptSegment", &[("start_time", "number"), ("duration", "number"), ("text", "string"), ("speaker", "string")]),
+    ("TranscriptInfo", &[("language", "string"), ("segments", "integer"), ("layers", "[integer]")]),
+    ("Era", &[("id", "integer"), ("name", "string"), ("sort_order", "integer"), ("start_year", "integer?"), ("end_year", "integer?")]),
+    ("Region", &[("id", "integer"), ("name", "string"), ("parent_id", "integer?")]),
+    ("Topic", &[("id", "integer"), ("name", "string")]),
+    ("Location", &[("id", "integer"), ("name", "string"), ("lat", "number"), ("lon", "number")]),
+    ("MapPin", &[
+        ("location", "Location"), ("video_id", "string"), ("video_title", "string"), ("era", "string?"),
+        ("topic", "string?"), ("timestamp", "number?"), ("note", "string?"),
+    ]),
+    ("PinCluster", &[
+        ("lat", "number"), ("lon", "number"), ("count", "integer"), ("bbox", "(number,number,number,number)"),
+        ("pins", "[MapPin]"),
+    ]),
+    ("TimelineEntry", &[
+        ("kind", "TimelineKind"), ("id", "string"), ("label", "string"), ("start_year", "integer"), ("end_year", "integer"),
+        ("era", "string?"), ("video_id", "string?"),
+    ]),
+    ("Timeline", &[("eras", "[Era]"), ("entries", "[TimelineEntry]")]),
+    ("Note", &[("id", "integer"), ("video_id", "string"), ("timestamp", "number?"), ("text", "string"), ("created_at", "date-time")]),
+    ("Collection", &[("id", "integer"), ("name", "string"), ("description", "string?")]),
+    ("Attachment", &[
+        ("id", "integer"), ("video_id", "string?"), ("claim_id", "integer?"), ("file_name", "string"),
+        ("mime_type", "string"), ("caption", "string?"), ("size", "integer"), ("path", "string?"), ("created_at", "date-time"),
+    ]),
+    ("GraphNode", &[
+        ("id", "integer"), ("label", "string"), ("title", "string"), ("group", "string"), ("value", "integer"),
+        ("video_id", "string"), ("timestamp", "number?"), ("betweenness", "number?"), ("component", "integer?"),
+    ]),
+    ("GraphEdge", &[
+        ("from", "integer"), ("to", "integer"), ("label", "string"), ("arrows", "string"), ("dashes", "boolean"),
+        ("color", "EdgeColor"),
+    ]),
+    ("EdgeColor", &[("color", "string")]),
+    ("CausalNode", &[
+        ("id", "integer"), ("label", "string"), ("title", "string"), ("group", "string"), ("depth", "integer"),
+        ("video_id", "string"),
+    ]),
+    ("CausalEdge", &[
+        ("id", "integer"), ("from", "integer"), ("to", "integer"), ("loop_type", "LoopType"),
+        ("strength", "RelationStrength"), ("notes", "string?"), ("video_id", "string"),
+    ]),
+    ("CausalLoop", &[("relations", "[integer]"), ("polarity", "LoopType")]),
+    ("CausalDiagram", &[("claim_id", "integer"), ("nodes", "[CausalNode]"), ("edges", "[CausalEdge]"), ("loops", "[CausalLoop]")]),
+    ("GraphData", &[("nodes", "[GraphNode]"), ("edges", "[GraphEdge]"), ("total", "integer")]),
+    ("MapOfContent", &[
+        ("id", "integer"), ("title", "string"), ("description", "string?"), ("created_at", "date-time"), ("updated_at", "date-time"),
+    ]),
+    ("MocSummary", &[("id", "integer"), ("title", "string"), ("description", "string?"), ("claim_count", "integer")]),
+    ("MocWithClaims", &[("moc", "MapOfContent"), ("claims", "[Claim]"), ("sub_mocs", "[MapOfContent]")]),
+    ("ResearchQuestion", &[
+        ("id", "integer"), ("question", "string"), ("status", "QuestionStatus"), ("parent_question_id", "integer?"),
+        ("notes", "string?"), ("created_at", "date-time"), ("updated_at", "date-time"),
+    ]),
+    ("QuestionSummary", &[("id", "integer"), ("question", "string"), ("status", "string"), ("evidence_count", "integer")]),
+    ("QuestionWithEvidence", &[
+        ("question", "ResearchQuestion"), ("claims", "[Claim]"), ("videos", "[Video]"), ("sub_questions", "[ResearchQuestion]"),
+    ]),
+    ("FrameworkStats", &[
+        ("cyclical_indicators", "integer"), ("causal_relations", "integer"), ("idea_transmissions", "integer"),
+        ("geopolitical_entities", "integer"), ("surplus_flows", "integer"), ("temporal_observations", "integer"),
+    ]),
+    ("GeopoliticalEntity", &[
+        ("id", "integer"), ("name", "string"), ("era_id", "integer"), ("position", "SystemPosition"), ("notes", "string?"),
+        ("created_at", "date-time"),
+    ]),
+    ("SankeyNode", &[("id", "integer"), ("name", "string"), ("position", "SystemPosition"), ("era_id", "integer")]),
+    ("SankeyLink", &[
+        ("source", "integer"), ("target", "integer"), ("value", "integer"), ("commodities", "[string]"), ("flow_ids", "[integer]"),
+    ]),
+    ("SankeyData", &[("nodes", "[SankeyNode]"), ("links", "[SankeyLink]")]),
+    ("IdeaTransmission", &[
+        ("id", "integer"), ("idea", "string"), ("source_entity", "string"), ("target_entity", "string"),
+        ("transmission_type", "TransmissionType"), ("era_id", "integer?"), ("region_id", "integer?"), ("video_id", "string"),
+        ("claim_id", "integer?"), ("notes", "string?"), ("created_at", "date-time"),
+    ]),
+    ("CyclicalIndicator", &[
+        ("id", "integer"), ("video_id", "string"), ("claim_id", "integer?"), ("indicator_type", "CyclicalType"),
+        ("entity", "string"), ("era_id", "integer?"), ("description", "string"), ("timestamp", "number?"),
+        ("created_at", "date-time"),
+    ]),
+    ("BuildInfo", &[
+        ("version", "string"), ("schema_version", "integer"), ("features", "[string]"), ("read_only", "boolean"),
+        ("uptime_seconds", "integer"),
+    ]),
+    ("CategoryCount", &[("category", "string"), ("count", "integer")]),
+    ("FullStats", &[
+        ("videos", "integer"), ("claims", "integer"), ("links", "integer"), ("mocs", "integer"), ("questions", "integer"),
+        ("active_questions", "integer"), ("patterns", "integer"), ("orphan_claims", "integer"), ("stale_claims", "integer"),
+        ("framework", "FrameworkStats"), ("claims_by_category", "[CategoryCount]"), ("sources", "integer"),
+        ("scholars", "integer"), ("terms", "integer"), ("visuals", "integer"), ("evidence", "integer"), ("quotes", "integer"),
+    ]),
+    ("QueueSummary", &[
+        ("pending", "integer"), ("in_progress", "integer"), ("completed", "integer"), ("failed", "integer"), ("current", "string?"),
+    ]),
+    ("ReportEntry", &[("name", "string"), ("video_count", "integer")]),
+    ("ProjectionPoint", &[
+        ("source_type", "EmbeddingSource"), ("source_id", "string"), ("x", "number"), ("y", "number"), ("label", "string"),
+        ("category", "string?"), ("video_id", "string?"),
+    ]),
+    ("Contradiction", &[
+        ("link_id", "integer"), ("claim", "Claim"), ("claim_video_title", "string?"), ("other", "Claim"),
+        ("other_video_title", "string?"),
+    ]),
+    ("ContradictionGroup", &[("name", "string"), ("contradictions", "[Contradiction]")]),
+    ("SimilarityResult", &[
+        ("source_type", "EmbeddingSource"), ("source_id", "string"), ("score", "number"), ("text", "string"), ("video_id", "string?"),
+    ]),
+    ("SearchResult", &[
+        ("result_type", "SearchResultType"), ("id", "integer"), ("title", "string"), ("subtitle", "string?"),
+        ("snippet", "string?"), ("score", "number"), ("video_id", "string?"), ("timestamp", "number?"),
+        ("location", "(number,number)?"),
+    ]),
+    ("SearchFacets", &[
+        ("types", "[(SearchResultType,integer)]"), ("videos", "[(string,string,integer)]"), ("eras", "[(string,integer)]"),
+        ("regions", "[(string,integer)]"), ("topics", "[(string,integer)]"), ("channels", "[(string,integer)]"),
+    ]),
+    ("SearchResponse", &[("query", "string"), ("total", "integer"), ("results", "[SearchResult]"), ("facets", "SearchFacets")]),
+    ("Source", &[
+        ("id", "integer"), ("title", "string"), ("author", "string?"), ("source_type", "SourceType"), ("year", "integer?"),
+        ("url", "string?"), ("notes", "string?"), ("created_at", "date-time"),
+    ]),
+    ("Scholar", &[
+        ("id", "integer"), ("name", "string"), ("field", "string?"), ("era", "string?"), ("contribution", "string?"),
+        ("created_at", "date-time"), ("extracted_by", "string?"),
+    ]),
+    ("Term", &[
+        ("id", "integer"), ("term", "string"), ("definition", "string"), ("domain", "string?"), ("video_id", "string?"),
+        ("timestamp", "number?"), ("scholar_id", "integer?"), ("created_at", "date-time"), ("extracted_by", "string?"),
+    ]),
+    ("Visual", &[
+        ("id", "integer"), ("video_id", "string"), ("timestamp", "number"), ("visual_type", "VisualType"),
+        ("description", "string"), ("significance", "string?"), ("location_id", "integer?"), ("era_id", "integer?"),
+        ("created_at", "date-time"),
+    ]),
+    ("Evidence", &[
+        ("id", "integer"), ("video_id", "string"), ("evidence_type", "EvidenceType"), ("description", "string"),
+        ("location_id", "integer?"), ("era_id", "integer?"), ("timestamp", "number?"), ("source_id", "integer?"),
+        ("created_at", "date-time"),
+    ]),
+    ("Quote", &[
+        ("id", "integer"), ("video_id", "string"), ("text", "string"), ("speaker", "string?"), ("scholar_id", "integer?"),
+        ("timestamp", "number?"), ("context", "string?"), ("created_at", "date-time"),
+    ]),
+    // Request bodies; optional fields are left out of `required` below
+    ("NewClaim", &[
+        ("video_id", "string"), ("text", "string"), ("quote", "string"), ("category", "string?"),
+        ("confidence", "string?"), ("timestamp", "number?"),
+    ]),
+    ("ClaimChanges", &[
+        ("text", "string?"), ("quote", "string?"), ("category", "string?"), ("confidence", "string?"), ("timestamp", "number?"),
+    ]),
+    ("NewLink", &[("target", "integer"), ("type", "string")]),
+    ("LinkChange", &[("type", "string")]),
+    ("NewTag", &[("kind", "string"), ("name", "string")]),
+    ("NewNote", &[("text", "string"), ("timestamp", "number?")]),
+    ("NoteChanges", &[("text", "string?"), ("timestamp", "number?")]),
+    ("NewCollection", &[("name", "string"), ("description", "string?")]),
+    ("CollectionChanges", &[("name", "string?"), ("description", "string?")]),
+    ("CollectionMember", &[("video_id", "string")]),
+    ("NewMoc", &[("title", "string"), ("description", "string?")]),
+    ("MocChanges", &[("title", "string?"), ("description", "string?")]),
+    ("MocMember", &[("claim_id", "integer"), ("sort_order", "integer?")]),
+    ("MocOrder", &[("claim_ids", "[integer]")]),
+    ("NewQuestion", &[("question", "string"), ("parent_id", "integer?"), ("notes", "string?")]),
+    ("NewEvidence", &[("claim_id", "integer?"), ("video_id", "string?"), ("relevance", "string?")]),
+    ("StatusChange", &[("status", "string")]),
+    ("NewQueueItem", &[("video_id", "string"), ("priority", "integer?")]),
+    ("PriorityChange", &[("priority", "integer")]),
+];
+
+/// Request bodies whose fields may all be left out; other bodies require their
+/// non-null fields, except those listed in `DEFAULTED`, which also lists response
+/// fields left out when empty.
+const PARTIAL: &[&str] = &["ClaimChanges", "NoteChanges", "CollectionChanges", "MocChanges"];
+const DEFAULTED: &[(&str, &str)] = &[("NewClaim", "quote"), ("TranscriptSegment", "speaker")];
+
+/// Enums, serialized by variant name.
+const ENUMS: &[(&str, &[&str])] = &[
+    ("ClaimCategory", &[
+        "CyclicalPattern", "CausalClaim", "MemeticTransmission", "GeopoliticalDynamic", "Factual",
+        "Phenomenological", "Metaphysical",
+    ]),
+    ("Confidence", &["High", "Medium", "Low"]),
+    ("ProcessingStatus", &["Pending", "InProgress", "Completed", "Failed", "Skipped"]),
+    ("TimelineKind", &["Video", "Claim", "CyclicalIndicator", "IdeaTransmission", "GeopoliticalEntity", "SurplusFlow"]),
+    ("ClaimStatus", &["Unverified", "Verified", "Disputed", "Retracted"]),
+    ("LinkType", &["Supports", "Contradicts", "Elaborates", "CausedBy", "Causes", "Related"]),
+    ("EmbeddingSource", &["Chunk", "Claim", "Summary", "Video"]),
+    ("LoopType", &["Positive", "Negative", "Linear"]),
+    ("RelationStrength", &["Strong", "Moderate", "Weak", "Speculative"]),
+    ("SystemPosition", &["Core", "SemiPeriphery", "Periphery"]),
+    ("TransmissionType", &["Horizontal", "Vertical", "Oblique"]),
+    ("CyclicalType", &[
+        "EliteOverproduction", "FiscalStrain", "SocialUnrest", "PopulationPressure", "Asabiyyah", "CenterPeriphery",
+    ]),
+    ("QuestionStatus", &["Active", "Answered", "Refined", "Parked"]),
+    ("SourceType", &["Book", "Paper", "Documentary", "Article", "Lecture", "Website"]),
+    ("VisualType", &[
+        "Painting", "Map", "Diagram", "Artifact", "Chart", "Photo", "Skeleton", "Symbol", "Architecture", "Inscription",
+    ]),
+    ("EvidenceType", &[
+        "Archaeological", "Genetic", "Textual", "Anthropological", "Linguistic", "Artistic", "Scientific", "Historical",
+    ]),
+    ("SearchResultType", &[
+        "Claim", "Video", "Moc", "Source", "Scholar", "Term", "Quote", "Evidence", "Visual", "Location", "Question",
+    ]),
+];
+
+struct Operation {
+    method: &'static str,
+    path: &'static str,
+    summary: &'static str,
+    /// Query parameters as (name, type, description); path parameters come from the path
+    query: &'static [(&'static str, &'static str, &'static str)],
+    body: Option<&'static str>,
+    status: u16,
+    /// Response type, or "" for none
+    response: &'static str,
+    /// Takes the paging parameters and answers with the paging headers
+    paged: bool,
+}
+
+const fn op(method: &'static str, path: &'static str, summary: &'static str, response: &'static str) -> Operation {
+    Operation { method, path, summary, query: &[], body: None, status: 200, response, paged: false }
+}
+
+const fn paged(path: &'static str, summary: &'static str, item: &'static str) -> Operation {
+    Operation { method: "get", path, summary, query: &[], body: None, status: 200, response: item, paged: true }
+}
+
+const fn write(method: &'static str, path: &'static str, summary: &'static str, body: Option<&'static str>, status: u16, response: &'static str) -> Operation {
+    Operation { method, path, summary, query: &[], body, status, response, paged: false }
+}
+
+const OPERATIONS: &[Operation] = &[
+    Operation {
+        query: &[
+            ("era", "string", "Comma-separated era names"),
+            ("topic", "string", "Topic name"),
+            ("bbox", "string", "Only pins inside west,south,east,north (degrees)"),
+            ("zoom", "integer", "Map zoom level; returns clusters instead of pins"),
+        ],
+        ..op("get", "/api/pins", "Map pins: locations mentioned in videos, or clusters of them when `zoom` is given", "[MapPin]|[PinCluster]")
+    },
+    Operation {
+        query: &[
+            ("kind", "string", "Comma-separated kinds: video, claim, cyclical, transmission, entity, flow"),
+            ("from", "integer", "Earliest year, negative for BCE"),
+            ("to", "integer", "Latest year, negative for BCE"),
+        ],
+        ..op("get", "/api/timeline", "Videos, claims and framework entries laid out by year, oldest first", "Timeline")
+    },
+    paged("/api/eras", "Eras, in chronological order", "Era"),
+    paged("/api/topics", "Topics", "Topic"),
+    paged("/api/videos", "Videos", "VideoSummary"),
+    Operation {
+        query: &[
+            ("video_id", "string", "Claims from this video"),
+            ("category", "string", "Claim category, e.g. factual"),
+            ("status", "string", "unverified, verified, disputed or retracted"),
+        ],
+        ..paged("/api/claims", "Claims; 100 per page unless `limit` is given", "Claim")
+    },
+    write("post", "/api/claims", "Add a claim", Some("NewClaim"), 201, "Claim"),
+    op("get", "/api/claims/{id}", "A claim with its links, sources, scholars and provenance", "ClaimDetail"),
+    write("put", "/api/claims/{id}", "Edit a claim", Some("ClaimChanges"), 200, "Claim"),
+    write("delete", "/api/claims/{id}", "Delete a claim", None, 204, ""),
+    write("post", "/api/claims/{id}/links", "Link a claim to another", Some("NewLink"), 201, "ClaimLink"),
+    write("put", "/api/claims/{id}/links/{target}", "Change a link's type", Some("LinkChange"), 200, "ClaimLink"),
+    write("delete", "/api/claims/{id}/links/{target}", "Remove a link", None, 204, ""),
+    write("post", "/api/claims/{id}/tags", "Tag a claim with an era or topic", Some("NewTag"), 200, "ClaimTags"),
+    write("delete", "/api/claims/{id}/tags/{kind}/{name}", "Remove a claim's era or topic tag", None, 200, "ClaimTags"),
+    write("post", "/api/videos/{id}/tags", "Tag a video with an era, region or topic", Some("NewTag"), 200, "VideoTags"),
+    write("delete", "/api/videos/{id}/tags/{kind}/{name}", "Remove a video's era, region or topic tag", None, 200, "VideoTags"),
+    op(
+        "get",
+        "/api/videos/{id}/full",
+        "A video with its transcript status, tags, notes, claims, visuals, evidence, quotes, citations and locations",
+        "VideoDetail",
+    ),
+    Operation {
+        query: &[
+            ("from", "number", "Only segments running past this many seconds"),
+            ("to", "number", "Only segments starting before this many seconds"),
+            ("paragraphs", "boolean", "Merge segments into paragraphs"),
+        ],
+        ..op("get", "/api/videos/{id}/transcript", "A video's transcript, or the part between `from` and `to`", "TranscriptSection")
+    },
+    paged("/api/videos/{id}/notes", "A video's notes", "Note"),
+    write("post", "/api/videos/{id}/notes", "Add a note to a video", Some("NewNote"), 201, "Note"),
+    write("put", "/api/notes/{id}", "Edit a note", Some("NoteChanges"), 200, "Note"),
+    write("delete", "/api/notes/{id}", "Delete a note", None, 204, ""),
+    paged("/api/collections", "Collections", "Collection"),
+    write("post", "/api/collections", "Create a collection", Some("NewCollection"), 201, "Collection"),
+    write("put", "/api/collections/{name}", "Rename or describe a collection", Some("CollectionChanges"), 200, "Collection"),
+    write("delete", "/api/collections/{name}", "Delete a collection", None, 204, ""),
+    write("post", "/api/collections/{name}/videos", "Add a video to a collection", Some("CollectionMember"), 204, ""),
+    write("delete", "/api/collections/{name}/videos/{video_id}", "Remove a video from a collection", None, 204, ""),
+    Operation {
+        query: &[("video_id", "string", "Attachments of this video"), ("claim_id", "integer", "Attachments of this claim")],
+        ..paged("/api/attachments", "Attachments of a video or claim; one of the two is required", "Attachment")
+    },
+    op("get", "/api/attachments/{id}", "An attachment's file, with its MIME type", "binary"),
+    Operation {
+        query: &[
+            ("video_id", "string", "Claims from this video"),
+            ("moc_id", "integer", "Claims in this map of content"),
+            ("era", "string", "Comma-separated era names"),
+            ("topic", "string", "Topic name"),
+            ("status", "string", "Comma-separated claim statuses"),
+            ("category", "string", "Comma-separated claim categories"),
+            ("min_confidence", "string", "low, medium or high"),
+            ("link_types", "string", "Comma-separated link types; only these edges are drawn and counted"),
+            ("limit", "integer", "Claims per page (default 2000, at most 10000)"),
+            ("offset", "integer", "Claims to skip, newest first"),
+        ],
+        ..op("get", "/api/graph", "A page of the claim graph, as vis.js nodes and edges; filters combine", "GraphData")
+    },
+    Operation {
+        query: &[
+            ("claim", "integer", "The claim to start from (required)"),
+            ("depth", "integer", "Causal relations to follow from it, either way (default 3, at most 6)"),
+        ],
+        ..op(
+            "get",
+            "/api/causal",
+            "The causal relations around a claim, with loop type and strength on each edge and the feedback loops among them",
+            "CausalDiagram",
+        )
+    },
+    op("get", "/api/export/collections/{name}", "Download a collection as markdown, like `engine export`", "binary"),
+    Operation {
+        query: &[("era", "string", "Era name"), ("topic", "string", "Topic name")],
+        ..op("get", "/api/export/map", "Download the map as GeoJSON, like `engine export-map`", "binary")
+    },
+    Operation {
+        query: &[
+            ("format", "string", "dot (default) or graphml"),
+            ("video_id", "string", "Claims from this video"),
+            ("moc_id", "integer", "Claims in this map of content"),
+            ("era", "string", "Claims from videos tagged with this era"),
+        ],
+        ..op("get", "/api/export/graph", "Download the claim graph for Graphviz or Gephi, like `engine export-graph`", "binary")
+    },
+    paged("/api/mocs", "Maps of content", "MocSummary"),
+    op("get", "/api/mocs/{id}", "A map of content with its claims", "MocWithClaims"),
+    write("post", "/api/mocs", "Create a map of content", Some("NewMoc"), 201, "MapOfContent"),
+    write("put", "/api/mocs/{id}", "Rename or redescribe a map of content", Some("MocChanges"), 200, "MapOfContent"),
+    write("delete", "/api/mocs/{id}", "Delete a map of content", None, 204, ""),
+    write("post", "/api/mocs/{id}/claims", "Add a claim to a map of content, or move it if already there", Some("MocMember"), 201, "MocWithClaims"),
+    write("put", "/api/mocs/{id}/claims", "Reorder a map of content's claims", Some("MocOrder"), 200, "MocWithClaims"),
+    write("delete", "/api/mocs/{id}/claims/{claim_id}", "Remove a claim from a map of content", None, 204, ""),
+    paged("/api/questions", "Research questions", "QuestionSummary"),
+    op("get", "/api/questions/{id}", "A research question with its evidence", "QuestionWithEvidence"),
+    write("post", "/api/questions", "Create a research question", Some("NewQuestion"), 201, "ResearchQuestion"),
+    write("delete", "/api/questions/{id}", "Delete a research question", None, 204, ""),
+    write("put", "/api/questions/{id}/status", "Change a research question's status", Some("StatusChange"), 200, "QuestionWithEvidence"),
+    write("post", "/api/questions/{id}/evidence", "Attach a claim or a video to a research question", Some("NewEvidence"), 201, "QuestionWithEvidence"),
+    op("get", "/api/stats", "Counts across the knowledge base", "FullStats"),
+    op("get", "/api/reports/{dimension}", "Videos per era, region or topic (`dimension`)", "[ReportEntry]"),
+    Operation {
+        query: &[("source", "string", "video, chunk or claim"), ("model", "string", "Embedding model")],
+        ..op("get", "/api/projection", "2D projection of embeddings", "[ProjectionPoint]")
+    },
+    Operation {
+        query: &[("by", "string", "topic (default) or moc")],
+        ..paged("/api/contradictions", "Contradicting claim pairs, grouped", "ContradictionGroup")
+    },
+    Operation {
+        query: &[
+            ("q", "string", "Query text (required)"),
+            ("source", "string", "video, chunk or claim"),
+            ("limit", "integer", "Maximum results"),
+            ("diversify", "number", "MMR weight, 0 (most diverse) to 1"),
+        ],
+        ..op("get", "/api/semantic", "Semantic search over embeddings", "[SimilarityResult]")
+    },
+    Operation {
+        query: &[("limit", "integer", "Maximum results, up to 100"), ("diversify", "number", "MMR weight, 0 to 1")],
+        ..op("get", "/api/similar/{type}/{id}", "Items similar to a video, chunk or claim", "[SimilarityResult]")
+    },
+    paged("/api/review/orphans", "Claims with no links", "Claim"),
+    paged("/api/review/stale", "Claims unreviewed for 30 days", "Claim"),
+    op("get", "/api/queue", "Processing queue counts", "QueueSummary"),
+    Operation {
+        query: &[("status", "string", "Comma-separated statuses: pending, in_progress, completed, failed, skipped")],
+        ..paged("/api/queue/items", "Processing queue entries: in progress, pending, failed, completed, then skipped; highest priority first within each", "QueueItem")
+    },
+    write("post", "/api/queue/items", "Queue a video, or change its priority if already queued", Some("NewQueueItem"), 201, "QueueItem"),
+    write("post", "/api/queue/items/{video_id}/skip", "Skip a queued video", None, 200, "QueueItem"),
+    write("post", "/api/queue/items/{video_id}/reset", "Put a queued video back to pending", None, 200, "QueueItem"),
+    write("post", "/api/queue/items/{video_id}/priority", "Change a queued video's priority", Some("PriorityChange"), 200, "QueueItem"),
+    Operation {
+        query: &[
+            ("era", "string", "Comma-separated era names"),
+            ("position", "string", "core, semi_periphery or periphery"),
+        ],
+        ..paged("/api/world-systems/entities", "Geopolitical entities and their world-system positions", "GeopoliticalEntity")
+    },
+    Operation {
+        query: &[("era", "string", "Comma-separated era names"), ("commodity", "string", "Only flows of this commodity")],
+        ..op("get", "/api/world-systems/flows", "Surplus flows as Sankey nodes and links, one link per pair of entities", "SankeyData")
+    },
+    Operation {
+        query: &[("era", "string", "Comma-separated era names"), ("type", "string", "horizontal, vertical or oblique")],
+        ..paged("/api/transmissions", "Idea transmissions; `q` matches the idea", "IdeaTransmission")
+    },
+    Operation {
+        query: &[
+            ("era", "string", "Comma-separated era names"),
+            ("type", "string", "Indicator type, e.g. fiscal_strain or asabiyyah"),
+            ("entity", "string", "The civilization or state described"),
+        ],
+        ..paged("/api/cyclical", "Cyclical indicators; `q` matches the description", "CyclicalIndicator")
+    },
+    paged("/api/sources", "Sources", "Source"),
+    paged("/api/scholars", "Scholars", "Scholar"),
+    paged("/api/terms", "Terms", "Term"),
+    paged("/api/visuals", "Visuals", "Visual"),
+    paged("/api/evidence", "Evidence", "Evidence"),
+    paged("/api/quotes", "Quotes", "Quote"),
+    Operation {
+        query: &[
+            ("q", "string", "Query text (required)"),
+            ("types", "string", "Comma-separated result types, e.g. claim,video,scholar"),
+            ("video_id", "string", "Results from this video"),
+            ("limit", "integer", "Maximum results (default 50)"),
+            ("fuzzy_threshold", "number", "0 to 1 (default 0.6)"),
+        ],
+        ..op("get", "/api/search", "Search across every kind of item", "SearchResponse")
+    },
+    op(
+        "get",
+        "/api/events",
+        "Server-sent events as things change: `queue` (a QueueSummary), `video` (a VideoSummary, for each video saved) and `claim` (a Claim)",
+        "event-stream",
+    ),
+    op("get", "/api/version", "The server's version, database schema version and compiled-in features", "BuildInfo"),
+    op("get", "/api/openapi.json", "This document", "object"),
+];
+
+/// The schema for a type in the notation of `SCHEMAS`.
+fn schema(ty: &str) -> Value {
+    if ty.contains('|') {
+        return json!({ "oneOf": ty.split('|').map(schema).collect::<Vec<_>>() });
+    }
+    if let Some(inner) = ty.strip_suffix('?') {
+        let inner = schema(inner);
+        return match inner.get("$ref") {
+            Some(_) => json!({ "oneOf": [inner, { "type": "null" }] }),
+            None => {
+                let mut inner = inner;
+                let base = inner["type"].clone();
+                inner["type"] = json!([base, "null"]);
+                inner
+            }
+        };
+    }
+    if let Some(inner) = ty.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        return json!({ "type": "array", "items": schema(inner) });
+    }
+    if let Some(inner) = ty.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+        let items: Vec<Value> = inner.split(',').map(schema).collect();
+        let len = items.len();
+        return json!({ "type": "array", "prefixItems": items, "minItems": len, "maxItems": len });
+    }
+    match ty {
+        "string" | "integer" | "number" | "boolean" | "object" => json!({ "type": ty }),
+        "date" | "date-time" => json!({ "type": "string", "format": ty }),
+        "binary" => json!({ "type": "string", "format": "binary" }),
+        name => json!({ "$ref": format!("#/components/schemas/{}", name) }),
+    }
+}
+
+fn components() -> Value {
+    let mut schemas = Map::new();
+    for (name, fields) in SCHEMAS {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for (field, ty) in fields.iter() {
+            properties.insert(field.to_string(), schema(ty));
+            // Responses carry every field but the defaulted ones; bodies need only the non-null ones
+            let defaulted = DEFAULTED.contains(&(*name, *field));
+            let optional = PARTIAL.contains(name) || defaulted || is_body(name) && ty.ends_with('?');
+            if !optional {
+                required.push(*field);
+            }
+        }
+        schemas.insert(name.to_string(), json!({ "type": "object", "properties": properties, "required": required }));
+    }
+    for (name, variants) in ENUMS {
+        schemas.insert(name.to_string(), json!({ "type": "string", "enum": variants }));
+    }
+    json!({
+        "schemas": schemas,
+        "securitySchemes": {
+            "bearer": {
+                "type": "http",
+                "scheme": "bearer",
+                "description": "An API token from `engine token create`, or the server's --auth-token. Needed once any token exists.",
+            },
+        },
+    })
+}
+
+fn is_body(name: &str) -> bool {
+    OPERATIONS.iter().any(|op| op.body == Some(name))
+}
+
+fn parameters(op: &Operation) -> Vec<Value> {
+    let mut params = Vec::new();
+    for segment in op.path.split('/') {
+        if let Some(name) = segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            // Videos (and the items `similar` takes) have string ids; everything else is numbered
+            let ty = match name {
+                "target" => "integer",
+                "id" if !op.path.starts_with("/api/videos") && !op.path.starts_with("/api/similar") => "integer",
+                _ => "string",
+            };
+            params.push(json!({ "name": name, "in": "path", "required": true, "schema": schema(ty) }));
+        }
+    }
+    for (name, ty, description) in op.query {
+        let required = description.ends_with("(required)");
+        params.push(json!({ "name": name, "in": "query", "required": required, "description": description, "schema": schema(ty) }));
+    }
+    if op.paged {
+        params.extend([
+            json!({ "name": "limit", "in": "query", "description": "Items per page", "schema": schema("integer") }),
+            json!({ "name": "offset", "in": "query", "description": "Items to skip, after `cursor` if given", "schema": schema("integer") }),
+            json!({ "name": "cursor", "in": "query", "description": "X-Next-Cursor from the previous page", "schema": schema("string") }),
+            json!({ "name": "q", "in": "query", "description": "Case-insensitive text filter", "schema": schema("string") }),
+        ]);
+    }
+    params
+}
+
+fn operation(op: &Operation) -> Value {
+    let mut success = json!({ "description": if op.status == 201 { "Created" } else { "OK" } });
+    if op.response == "binary" {
+        success["content"] = json!({ "application/octet-stream": { "schema": schema("binary") } });
+    } else if op.response == "event-stream" {
+        success["content"] = json!({ "text/event-stream": { "schema": schema("string") } });
+    } else if op.paged {
+        success["content"] = json!({ "application/json": { "schema": schema(&format!("[{}]", op.response)) } });
+        success["headers"] = json!({
+            "X-Total-Count": { "description": "Items matching the filter, across all pages", "schema": schema("integer") },
+            "X-Next-Cursor": { "description": "Cursor for the next page, when there is one", "schema": schema("string") },
+        });
+    } else if !op.response.is_empty() {
+        success["content"] = json!({ "application/json": { "schema": schema(op.response) } });
+    } else {
+        success = json!({ "description": "No content" });
+    }
+
+    let mut value = json!({
+        "summary": op.summary,
+        "operationId": format!("{}{}", op.method, op.path.replace(['{', '}'], "").replace('/', "_")),
+        "parameters": parameters(op),
+        "responses": {
+            op.status.to_string(): success,
+            "default": { "description": "An error status with no body: 400 bad input, 401 missing or unknown token, \
+403 read-only token or server, 404 not found, 409 conflict; or 304 when `If-None-Match` names the current ETag \
+(/api/graph, /api/pins and /api/stats)" },
+        },
+    });
+    if let Some(body) = op.body {
+        value["requestBody"] = json!({ "required": true, "content": { "application/json": { "schema": schema(body) } } });
+    }
+    value
+}
+
+/// The OpenAPI 3.1 document for the HTTP API, served at `/api/openapi.json`.
+pub fn spec() -> Value {
+    let mut paths = Map::new();
+    for op in OPERATIONS {
+        let entry = paths.entry(op.path.to_string()).or_insert_with(|| json!({}));
+        entry[op.method] = operation(op);
+    }
+    json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": "Knowledge base API",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "The HTTP API behind `engine serve`. Lists are plain JSON arrays; paged lists carry their total in X-Total-Count.",
+        },
+        "paths": paths,
+        "components": components(),
+        "security": [{ "bearer": [] }, {}],
+    })
+}
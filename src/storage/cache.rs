@@ -0,0 +1,54 @@
+use anyhow::Result;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+struct CacheEntry {
+    generation: u64,
+    value: Arc<dyn Any + Send + Sync>,
+}
+
+/// Read-through cache for expensive aggregate queries.
+///
+/// Entries are tagged with the database generation they were computed at
+/// (see `Database::generation`) and recomputed once the generation moves.
+#[derive(Default)]
+pub struct QueryCache {
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl QueryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get_or_compute<T, F>(&self, key: &str, generation: u64, compute: F) -> Result<T>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce() -> Result<T>,
+    {
+        if let Some(entry) = self.lock().get(key) {
+            if entry.generation == generation {
+                if let Some(value) = entry.value.downcast_ref::<T>() {
+                    return Ok(value.clone());
+                }
+            }
+        }
+
+        // Computed outside the lock so slow queries don't block other keys
+        let value = compute()?;
+        self.lock().insert(key.to_string(), CacheEntry {
+            generation,
+            value: Arc::new(value.clone()),
+        });
+        Ok(value)
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, CacheEntry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
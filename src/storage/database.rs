@@ -1,31 +1,433 @@
 use anyhow::Result;
-use rusqlite::{Connection, params, OptionalExtension};
-use std::path::Path;
-use std::collections::HashMap;
+use rusqlite::{Connection, OpenFlags, params};
+use std::path::{Path, PathBuf};
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
 use strsim::{jaro_winkler, normalized_levenshtein};
-use super::models::{Video, Transcript, TranscriptSegment, SearchResult, SegmentMatch, Era, Region, Topic, Collection, Note, Location, MapPin, AutoTags, SavedSearch, AdvancedSearchResult, ReportEntry, GeoJsonFeature, GeoJsonGeometry, GeoJsonProperties, GeoJsonCollection, Claim, ClaimCategory, Confidence, ClaimLink, LinkType, ClaimWithLinks, TranscriptLayer, TranscriptChunk, Embedding, EmbeddingSource, SimilarityResult, HybridSearchResult, ChunkMatch, EmbeddingStats, CyclicalType, CyclicalIndicator, LoopType, RelationStrength, CausalRelation, TransmissionType, IdeaTransmission, SystemPosition, GeopoliticalEntity, SurplusFlow, BraudelTimescale, TemporalObservation, FrameworkStats, MapOfContent, MocWithClaims, QuestionStatus, ResearchQuestion, QuestionWithEvidence, DetectedPattern, PatternType, ReviewQueue, SynthesisStats, ProcessingStatus, AIProcessingQueue, SourceType, Source, Scholar, VisualType, Visual, Term, EvidenceType, Evidence, Quote, SearchResultType, UnifiedSearchResult, SearchResponse, SearchFacets};
+use super::models::{Video, Transcript, TranscriptSegment, Chapter, Subscription, Archive, Attachment, Availability, VideoAvailability, SearchResult, SegmentMatch, TextMatch, Era, Region, RegionCount, Topic, Collection, Note, Location, MapPin, Cited, TimelineKind, TimelineEntry, Timeline, AutoTags, SavedSearch, SearchHistoryEntry, Alias, AdvancedSearchResult, AdvancedSearchResponse, MetadataFacets, RankingProfile, ReportEntry, GeoJsonFeature, GeoJsonGeometry, GeoJsonProperties, GeoJsonCollection, Claim, ClaimCategory, Confidence, ClaimStatus, ClaimLink, LinkType, ClaimWithLinks, ClaimGraph, ClaimGraphFilter, ClaimGraphPage, ClaimPathStep, ClaimMetrics, ClaimProvenance, Contradiction, ContradictionGroup, DuplicateClaims, TranscriptLayer, TranscriptChunk, Embedding, EmbeddingSource, SimilarityResult, ProjectionPoint, HybridSearchResult, Fusion, ChunkMatch, EmbeddingStats, EmbeddingModel, EmbeddingModelStats, EmbeddingQueueItem, CyclicalType, CyclicalIndicator, LoopType, RelationStrength, CausalRelation, CausalChain, TransmissionType, IdeaTransmission, SystemPosition, GeopoliticalEntity, SurplusFlow, BraudelTimescale, TemporalObservation, FrameworkStats, MapOfContent, MocWithClaims, QuestionStatus, ResearchQuestion, QuestionWithEvidence, SuggestedQuestion, DetectedPattern, PatternProvenance, PatternType, ReviewQueue, ClaimSchedule, SynthesisStats, LlmUsage, UsageGrouping, LlmUsageSummary, TokenScope, ApiToken, ProcessingStatus, AIProcessingQueue, QueueEvent, VideoStatus, SourceType, Source, Scholar, ClaimScholar, VisualType, Visual, Term, EvidenceType, Evidence, Quote, PendingQuote, AuditEntry, IntegrityIssue, MigrationStatus, SearchResultType, UnifiedSearchResult, SearchResponse, SearchFacets};
 use chrono::{DateTime, NaiveDate, Utc};
+use crate::transcript::language::normalize_language_tag;
+use super::sql::{self, params_from_iter, Conn, Dialect, OptionalExtension, Row};
+use super::store::Storage;
+use super::query::{expand_aliases, match_terms, near_groups, to_fts_query, to_tsquery, Near};
+
+// A row whose foreign key points at a missing parent
+struct DanglingReference {
+    table: String,
+    rowid: i64,
+    parent: String,
+    column: String,
+    /// NOT NULL or ON DELETE CASCADE, so the row goes rather than the reference
+    required: bool,
+}
+
+// Embeddings have no foreign key; this matches rows (aliased `e`) whose source is gone
+const ORPHANED_EMBEDDING: &str = "NOT CASE e.source_type
+    WHEN 'video' THEN EXISTS (SELECT 1 FROM videos WHERE id = e.source_id)
+    WHEN 'claim' THEN EXISTS (SELECT 1 FROM claims WHERE CAST(id AS TEXT) = e.source_id)
+    WHEN 'chunk' THEN EXISTS (SELECT 1 FROM transcript_chunks WHERE video_id || ':' || chunk_index = e.source_id)
+    WHEN 'summary' THEN EXISTS (SELECT 1 FROM transcript_layers WHERE video_id || ':' || layer = e.source_id)
+    ELSE TRUE END";
+
+// Matches embeddings (aliased `e`) of archived videos and claims, a video's chunks and summaries included
+const ARCHIVED_EMBEDDING: &str = "CASE e.source_type
+    WHEN 'video' THEN EXISTS (SELECT 1 FROM videos WHERE id = e.source_id AND archived_at IS NOT NULL)
+    WHEN 'claim' THEN EXISTS (SELECT 1 FROM claims WHERE CAST(id AS TEXT) = e.source_id AND archived_at IS NOT NULL)
+    ELSE EXISTS (SELECT 1 FROM videos WHERE archived_at IS NOT NULL AND substr(e.source_id, 1, length(id) + 1) = id || ':') END";
+
+type MigrationFn = fn(&Database) -> Result<()>;
+
+// Applied in order and recorded in schema_migrations. Append new entries; never renumber or edit applied ones.
+const MIGRATIONS: &[(i64, &str, MigrationFn)] = &[
+    (1, "baseline", Database::migrate_baseline),
+    (2, "normalize_transcript_languages", Database::normalize_transcript_languages),
+    (3, "video_metadata", Database::migrate_video_metadata),
+    (4, "video_availability", Database::migrate_video_availability),
+    (5, "soft_delete", Database::migrate_soft_delete),
+    (6, "audit_log", Database::migrate_audit_log),
+    (7, "era_date_ranges", Database::migrate_era_date_ranges),
+    (8, "attachments", Database::migrate_attachments),
+    (9, "text_index", Database::migrate_text_index),
+    (10, "settings", Database::migrate_settings),
+    (11, "search_history", Database::migrate_search_history),
+    (12, "aliases", Database::migrate_aliases),
+    (13, "saved_search_filters", Database::migrate_saved_search_filters),
+    (14, "embedding_models", Database::migrate_embedding_models),
+    (15, "embedding_blobs", Database::migrate_embedding_blobs),
+    (16, "embedding_queue", Database::migrate_embedding_queue),
+    (17, "claim_status", Database::migrate_claim_status),
+    (18, "claim_tags", Database::migrate_claim_tags),
+    (19, "claim_metrics", Database::migrate_claim_metrics),
+    (20, "claim_provenance", Database::migrate_claim_provenance),
+    (21, "claim_dates", Database::migrate_claim_dates),
+    (22, "pattern_provenance", Database::migrate_pattern_provenance),
+    (23, "extracted_entities", Database::migrate_extracted_entities),
+    (24, "llm_usage", Database::migrate_llm_usage),
+    (25, "api_tokens", Database::migrate_api_tokens),
+    (26, "api_token_hashes", Database::migrate_api_token_hashes),
+];
+
+// The migration postgres.sql brings a new PostgreSQL database up to
+const POSTGRES_SCHEMA_VERSION: i64 = 5;
+const POSTGRES_SCHEMA: &str = include_str!("postgres.sql");
+
+// Seeded on every open; years are astronomical (negative = BCE), open-ended where None
+const DEFAULT_ERAS: &[(&str, i32, Option<i32>, Option<i32>)] = &[
+    ("Prehistoric", 0, None, Some(-3300)),
+    ("Bronze Age", 10, Some(-3300), Some(-1200)),
+    ("Iron Age", 20, Some(-1200), Some(-550)),
+    ("Classical Antiquity", 30, Some(-800), Some(300)),
+    ("Late Antiquity", 40, Some(300), Some(700)),
+    ("Medieval", 50, Some(500), Some(1500)),
+    ("Early Modern", 60, Some(1500), Some(1800)),
+    ("Modern", 70, Some(1800), None),
+    ("Timeless", 100, None, None),  // For philosophy, spirituality, perennial wisdom
+];
+
+// Free text outside transcripts kept in the text_index FTS table: (kind, table, title, body), as column expressions
+pub const TEXT_INDEX_SOURCES: &[(&str, &str, &str, &str)] = &[
+    ("note", "notes", "''", "text"),
+    ("quote", "quotes", "COALESCE(speaker, '')", "text || ' ' || COALESCE(context, '')"),
+    ("term", "terms", "term", "definition"),
+    ("summary", "transcript_layers", "'Layer ' || layer", "content"),
+];
+
+// Derived or high-churn tables left out of the audit log
+const UNAUDITED_TABLES: &[&str] = &["api_tokens", "audit_log", "claim_access", "claim_metrics", "embedding_models", "embedding_queue", "embeddings", "search_history", "transcript_chunks"];
+
+// Claim links and causal relations as undirected edges between live claims, for graph
+// traversal; `forward` is 1 where the edge runs the way it was recorded
+const CLAIM_EDGES_CTE: &str = r#"
+    live(id) AS (SELECT id FROM claims WHERE archived_at IS NULL),
+    all_edges(src, dst, kind, forward) AS (
+        SELECT source_claim_id, target_claim_id, link_type, 1 FROM claim_links
+        UNION ALL SELECT target_claim_id, source_claim_id, link_type, 0 FROM claim_links
+        UNION ALL SELECT cause_claim_id, effect_claim_id, 'causal', 1 FROM causal_relations
+        UNION ALL SELECT effect_claim_id, cause_claim_id, 'causal', 0 FROM causal_relations
+    ),
+    edges(src, dst, kind, forward) AS (
+        SELECT * FROM all_edges WHERE src IN (SELECT id FROM live) AND dst IN (SELECT id FROM live)
+    )"#;
+
+// Columns too large to be useful in an audit summary
+const UNAUDITED_COLUMNS: &[&str] = &["segments_json", "full_text", "vector_json", "content", "description", "data"];
+
+// Bumped on every row change made through any connection in this process
+static WRITE_GENERATION: AtomicU64 = AtomicU64::new(0);
 
 pub struct Database {
-    conn: Connection,
+    conn: Conn,
+}
+
+/// Where the data lives, from `--database`: a SQLite file (a plain path or a
+/// `sqlite://` URL) or, in builds with the postgres feature, a `postgres://` URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DatabaseLocation {
+    Sqlite(PathBuf),
+    #[cfg(feature = "postgres")]
+    Postgres(String),
+}
+
+impl std::str::FromStr for DatabaseLocation {
+    type Err = anyhow::Error;
+
+    fn from_str(location: &str) -> Result<Self> {
+        match location.split_once("://") {
+            None => Ok(Self::Sqlite(location.into())),
+            Some(("sqlite", path)) => Ok(Self::Sqlite(path.into())),
+            #[cfg(feature = "postgres")]
+            Some(("postgres" | "postgresql", _)) => Ok(Self::Postgres(location.to_string())),
+            #[cfg(not(feature = "postgres"))]
+            Some(("postgres" | "postgresql", _)) => anyhow::bail!("PostgreSQL databases need a build with --features postgres"),
+            Some((scheme, _)) => anyhow::bail!("Unknown database scheme: {}://", scheme),
+        }
+    }
+}
+
+/// Connection settings applied on every open.
+#[derive(Clone)]
+pub struct ConnectionOptions {
+    /// How long to wait on a lock held by another connection before failing with SQLITE_BUSY
+    pub busy_timeout: std::time::Duration,
+    /// Extra `PRAGMA name = value` settings, applied after the defaults so they can override them
+    pub pragmas: Vec<(String, String)>,
+    /// SQLCipher passphrase; the database is encrypted with it on creation and unreadable without it
+    pub passphrase: Option<String>,
+}
+
+impl Default for ConnectionOptions {
+    fn default() -> Self {
+        Self {
+            busy_timeout: std::time::Duration::from_secs(5),
+            pragmas: Vec::new(),
+            passphrase: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for ConnectionOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConnectionOptions")
+            .field("busy_timeout", &self.busy_timeout)
+            .field("pragmas", &self.pragmas)
+            .field("passphrase", &self.passphrase.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+// The key must be set before anything else touches the file
+#[cfg(feature = "sqlcipher")]
+fn unlock(conn: &Connection, passphrase: &str) -> Result<()> {
+    conn.pragma_update(None, "key", passphrase)?;
+    conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |_| Ok(()))
+        .map_err(|_| anyhow::anyhow!("Can't open database: wrong passphrase, or the file isn't encrypted"))
+}
+
+#[cfg(not(feature = "sqlcipher"))]
+fn unlock(_conn: &Connection, _passphrase: &str) -> Result<()> {
+    anyhow::bail!("Encrypted databases need a build with --features sqlcipher")
+}
+
+/// Whether a transcript segment contains any of the searched words or phrases.
+fn segment_matches(text: &str, terms: &[String]) -> bool {
+    let text = text.to_lowercase();
+    terms.iter().any(|t| text.contains(t.as_str()))
+}
+
+/// Indexes of the segments spanned by passages where every term of a NEAR group occurs
+/// within its distance of the others. Terms match word prefixes, which roughly covers
+/// the stemming FTS5 applies.
+fn near_segments(segments: &[TranscriptSegment], groups: &[Near]) -> std::collections::HashSet<usize> {
+    let mut hit = std::collections::HashSet::new();
+    if groups.is_empty() {
+        return hit;
+    }
+
+    // Every word of the transcript with the segment it came from
+    let mut words: Vec<(String, usize)> = Vec::new();
+    for (i, seg) in segments.iter().enumerate() {
+        for word in seg.text.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()) {
+            words.push((word.to_lowercase(), i));
+        }
+    }
+
+    // Word positions (first, last) where a phrase occurs
+    let occurrences = |phrase: &str| -> Vec<(usize, usize)> {
+        let parts: Vec<&str> = phrase.split_whitespace().collect();
+        if parts.is_empty() || parts.len() > words.len() {
+            return Vec::new();
+        }
+        (0..=words.len() - parts.len())
+            .filter(|&start| parts.iter().enumerate().all(|(k, p)| words[start + k].0.starts_with(p)))
+            .map(|start| (start, start + parts.len() - 1))
+            .collect()
+    };
+
+    for group in groups {
+        let found: Vec<Vec<(usize, usize)>> = group.terms.iter().map(|t| occurrences(t)).collect();
+        let Some((first, rest)) = found.split_first() else { continue };
+        for &(start, end) in first {
+            let mut span = (start, end);
+            let close = rest.iter().all(|others| {
+                let nearest = others.iter().copied().find(|&(s, e)| {
+                    let gap = if s > end { s - end - 1 } else if start > e { start - e - 1 } else { 0 };
+                    gap <= group.distance
+                });
+                if let Some((s, e)) = nearest {
+                    span = (span.0.min(s), span.1.max(e));
+                }
+                nearest.is_some()
+            });
+            if close {
+                hit.extend(words[span.0].1..=words[span.1].1);
+            }
+        }
+    }
+    hit
 }
 
 impl Database {
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let conn = Connection::open(path)?;
-        let db = Self { conn };
-        db.init_schema()?;
+        Self::open_with(&DatabaseLocation::Sqlite(path.as_ref().to_path_buf()), &ConnectionOptions::default())
+    }
+
+    pub fn open_with(location: &DatabaseLocation, options: &ConnectionOptions) -> Result<Self> {
+        let db = Self::open_unmigrated(location, options)?;
+        db.migrate()?;
+        db.install_audit_triggers()?;
+        Ok(db)
+    }
+
+    /// Open without applying pending migrations (for `engine migrate --status`).
+    pub fn open_unmigrated(location: &DatabaseLocation, options: &ConnectionOptions) -> Result<Self> {
+        let db = Self::connect(location, options, false)?;
+        db.track_writes();
+        Ok(db)
+    }
+
+    /// Open without write access, e.g. for a public web UI or to query while a worker writes.
+    /// Nothing is migrated, so the schema must already be current.
+    pub fn open_read_only(location: &DatabaseLocation, options: &ConnectionOptions) -> Result<Self> {
+        let db = Self::connect(location, options, true)?;
+        let pending = db.migration_status()?.iter().filter(|m| m.applied_at.is_none()).count();
+        if pending > 0 {
+            anyhow::bail!("Database schema is out of date ({} pending migrations); run `engine migrate` first", pending);
+        }
+        db.track_writes();
         Ok(db)
     }
 
     pub fn open_in_memory() -> Result<Self> {
-        let conn = Connection::open_in_memory()?;
+        let db = Self { conn: Conn::Sqlite(Connection::open_in_memory()?) };
+        db.configure(&ConnectionOptions::default(), false)?;
+        #[cfg(feature = "sqlite-vec")]
+        db.load_vector_extension()?;
+        db.migrate()?;
+        db.install_audit_triggers()?;
+        db.track_writes();
+        Ok(db)
+    }
+
+    fn connect(location: &DatabaseLocation, options: &ConnectionOptions, read_only: bool) -> Result<Self> {
+        let conn = match location {
+            DatabaseLocation::Sqlite(path) if read_only => Conn::Sqlite(Connection::open_with_flags(
+                path,
+                OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_URI | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+            )?),
+            DatabaseLocation::Sqlite(path) => Conn::Sqlite(Connection::open(path)?),
+            #[cfg(feature = "postgres")]
+            DatabaseLocation::Postgres(url) => Conn::Postgres(super::postgres::Client::connect(url)?),
+        };
         let db = Self { conn };
-        db.init_schema()?;
+        db.configure(options, read_only)?;
+        #[cfg(feature = "sqlite-vec")]
+        db.load_vector_extension()?;
         Ok(db)
     }
 
-    fn init_schema(&self) -> Result<()> {
+    // WAL lets readers (e.g. the web server) proceed while another process writes
+    fn configure(&self, options: &ConnectionOptions, read_only: bool) -> Result<()> {
+        let Some(conn) = self.conn.sqlite() else {
+            if options.passphrase.is_some() || !options.pragmas.is_empty() {
+                anyhow::bail!("--passphrase and --db-pragma only apply to SQLite databases");
+            }
+            // The server's lock_timeout stands in for SQLite's busy timeout
+            self.conn.execute_batch(&format!("SET lock_timeout = {}", options.busy_timeout.as_millis()))?;
+            if read_only {
+                self.conn.execute_batch("SET default_transaction_read_only = on")?;
+            }
+            return Ok(());
+        };
+        if let Some(passphrase) = &options.passphrase {
+            unlock(conn, passphrase)?;
+        }
+        conn.busy_timeout(options.busy_timeout)?;
+        // Switching journal mode writes the header; read-only connections use whatever is set
+        if !read_only {
+            conn.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))?;
+        }
+        conn.pragma_update(None, "foreign_keys", true)?;
+        for (name, value) in &options.pragmas {
+            conn.execute_batch(&format!("PRAGMA {} = {}", name, value))
+                .map_err(|e| anyhow::anyhow!("PRAGMA {} = {} failed: {}", name, value, e))?;
+        }
+        Ok(())
+    }
+
+    fn track_writes(&self) {
+        // temp holds per-connection audit context, set on every open; not data
+        if let Some(conn) = self.conn.sqlite() {
+            conn.update_hook(Some(|_: rusqlite::hooks::Action, db_name: &str, _: &str, _: i64| {
+                if db_name != "temp" {
+                    WRITE_GENERATION.fetch_add(1, Ordering::Relaxed);
+                }
+            }));
+        }
+    }
+
+    // A new PostgreSQL database gets the schema of POSTGRES_SCHEMA_VERSION in one go;
+    // the migrations it covers are recorded as applied and later ones run as usual
+    fn create_postgres_schema(&self) -> Result<Vec<MigrationStatus>> {
+        let tx = self.conn.unchecked_transaction()?;
+        // Another process may be creating it at the same time
+        tx.query_row("SELECT pg_advisory_xact_lock(hashtext('engine schema'))", [], |_| Ok(()))?;
+        if self.has_table("schema_migrations")? {
+            return Ok(Vec::new());
+        }
+        tx.execute_batch(POSTGRES_SCHEMA)?;
+
+        let now = Utc::now();
+        let mut applied = Vec::new();
+        for (version, name, _) in MIGRATIONS.iter().filter(|(v, _, _)| *v <= POSTGRES_SCHEMA_VERSION) {
+            tx.execute(
+                "INSERT INTO schema_migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+                params![version, name, now.to_rfc3339()],
+            )?;
+            applied.push(MigrationStatus { version: *version, name: name.to_string(), applied_at: Some(now) });
+        }
+        tx.commit()?;
+        Ok(applied)
+    }
+
+    // PostgreSQL enforces every foreign key, so only SQLite files can hold these
+    // PostgreSQL triggers are part of the schema, shared by every client, so they're
+    // only added to tables that don't have them yet (e.g. ones a migration created)
+    fn install_postgres_triggers(&self) -> Result<()> {
+        let mut stmt = self.conn.prepare(
+            "SELECT c.relname, t.tgname FROM pg_trigger t JOIN pg_class c ON c.oid = t.tgrelid
+             WHERE c.relnamespace = CAST(current_schema() AS regnamespace) AND NOT t.tgisinternal",
+        )?;
+        let existing = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<sql::Result<HashSet<_>>>()?;
+        let has_trigger = |table: &str, name: &str| existing.contains(&(table.to_string(), name.to_string()));
+
+        let mut sql = String::new();
+        for table in self.exportable_tables()? {
+            if !has_trigger(&table, "data_version") {
+                sql.push_str(&format!(
+                    "CREATE OR REPLACE TRIGGER data_version AFTER INSERT OR UPDATE OR DELETE ON \"{table}\"
+                     FOR EACH ROW EXECUTE FUNCTION engine_data_version();\n"
+                ));
+            }
+            if UNAUDITED_TABLES.contains(&table.as_str()) || has_trigger(&table, "audit") {
+                continue;
+            }
+            let columns = self.table_columns(&table)?;
+            let key: String = if columns.iter().any(|c| c == "id") {
+                "id".to_string()
+            } else {
+                self.conn.query_row(
+                    "SELECT string_agg(a.attname, ',' ORDER BY k.n)
+                     FROM pg_index i
+                     CROSS JOIN unnest(i.indkey) WITH ORDINALITY k(attnum, n)
+                     JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = k.attnum
+                     WHERE i.indrelid = to_regclass(?1) AND i.indisprimary",
+                    params![table],
+                    |row| row.get(0),
+                )?
+            };
+            let shown: Vec<&str> = columns
+                .iter()
+                .map(String::as_str)
+                .filter(|c| *c != "id" && !UNAUDITED_COLUMNS.contains(c))
+                .take(6)
+                .collect();
+            sql.push_str(&format!(
+                "CREATE OR REPLACE TRIGGER audit AFTER INSERT OR UPDATE OR DELETE ON \"{table}\"
+                 FOR EACH ROW EXECUTE FUNCTION engine_audit('{key}', '{}', '{}');\n",
+                shown.join(","),
+                columns.join(","),
+            ));
+        }
+        if !sql.is_empty() {
+            self.conn.execute_batch(&sql)?;
+        }
+        Ok(())
+    }
+
+    fn migrate_baseline(&self) -> Result<()> {
         self.conn.execute_batch(
             r#"
             CREATE TABLE IF NOT EXISTS videos (
@@ -49,6 +451,34 @@ impl Database {
 
             CREATE INDEX IF NOT EXISTS idx_transcripts_video ON transcripts(video_id);
 
+            CREATE TABLE IF NOT EXISTS subscriptions (
+                id INTEGER PRIMARY KEY,
+                url TEXT NOT NULL UNIQUE,
+                name TEXT,
+                last_checked TEXT,
+                created_at TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS archives (
+                id INTEGER PRIMARY KEY,
+                video_id TEXT NOT NULL REFERENCES videos(id),
+                kind TEXT NOT NULL,
+                format TEXT NOT NULL,
+                language TEXT,
+                content TEXT NOT NULL,
+                fetched_at TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_archives_video ON archives(video_id, kind);
+
+            CREATE TABLE IF NOT EXISTS chapters (
+                id INTEGER PRIMARY KEY,
+                video_id TEXT NOT NULL REFERENCES videos(id),
+                start_time REAL NOT NULL,
+                title TEXT NOT NULL,
+                UNIQUE(video_id, start_time)
+            );
+
             CREATE TABLE IF NOT EXISTS eras (
                 id INTEGER PRIMARY KEY,
                 name TEXT NOT NULL UNIQUE,
@@ -365,6 +795,16 @@ impl Database {
             CREATE INDEX IF NOT EXISTS idx_question_evidence_question ON question_evidence(question_id);
             CREATE INDEX IF NOT EXISTS idx_question_evidence_claim ON question_evidence(claim_id);
 
+            CREATE TABLE IF NOT EXISTS question_suggestions (
+                id INTEGER PRIMARY KEY,
+                question TEXT NOT NULL UNIQUE,
+                reason TEXT NOT NULL,
+                claim_ids_json TEXT NOT NULL,
+                video_ids_json TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'pending',
+                created_at TEXT NOT NULL
+            );
+
             -- 9.3 Pattern Detection
             CREATE TABLE IF NOT EXISTS detected_patterns (
                 id INTEGER PRIMARY KEY,
@@ -384,6 +824,18 @@ impl Database {
                 last_accessed TEXT NOT NULL
             );
 
+            CREATE TABLE IF NOT EXISTS claim_schedule (
+                claim_id INTEGER PRIMARY KEY REFERENCES claims(id) ON DELETE CASCADE,
+                interval_days REAL NOT NULL,
+                ease REAL NOT NULL,
+                repetitions INTEGER NOT NULL DEFAULT 0,
+                lapses INTEGER NOT NULL DEFAULT 0,
+                due_at TEXT NOT NULL,
+                last_reviewed TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_claim_schedule_due ON claim_schedule(due_at);
+
             -- Phase 10: AI Processing Queue
             CREATE TABLE IF NOT EXISTS ai_processing_queue (
                 id INTEGER PRIMARY KEY,
@@ -400,6 +852,16 @@ impl Database {
             CREATE INDEX IF NOT EXISTS idx_ai_queue_status ON ai_processing_queue(status);
             CREATE INDEX IF NOT EXISTS idx_ai_queue_priority ON ai_processing_queue(priority DESC);
 
+            CREATE TABLE IF NOT EXISTS queue_history (
+                id INTEGER PRIMARY KEY,
+                video_id TEXT NOT NULL REFERENCES videos(id),
+                status TEXT NOT NULL,
+                detail TEXT,
+                created_at TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_queue_history_video ON queue_history(video_id);
+
             -- Phase 12: Expanded Knowledge Entities
 
             -- Sources (books, papers, documentaries)
@@ -522,6 +984,18 @@ impl Database {
                 created_at TEXT NOT NULL
             );
 
+            -- Quotes staged by automatic extraction
+            CREATE TABLE IF NOT EXISTS pending_quotes (
+                id INTEGER PRIMARY KEY,
+                video_id TEXT NOT NULL REFERENCES videos(id),
+                text TEXT NOT NULL,
+                speaker TEXT,
+                timestamp REAL,
+                reason TEXT NOT NULL,
+                context TEXT,
+                created_at TEXT NOT NULL
+            );
+
             -- Indexes for new tables
             CREATE INDEX IF NOT EXISTS idx_sources_title ON sources(title);
             CREATE INDEX IF NOT EXISTS idx_scholars_name ON scholars(name);
@@ -529,11 +1003,10 @@ impl Database {
             CREATE INDEX IF NOT EXISTS idx_terms_term ON terms(term);
             CREATE INDEX IF NOT EXISTS idx_evidence_video ON evidence(video_id);
             CREATE INDEX IF NOT EXISTS idx_quotes_video ON quotes(video_id);
+            CREATE INDEX IF NOT EXISTS idx_pending_quotes_video ON pending_quotes(video_id);
             "#,
         )?;
 
-        self.seed_default_eras()?;
-
         // Create unified search index FTS table
         let search_fts_exists: bool = self.conn.query_row(
             "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type='table' AND name='search_index'",
@@ -595,929 +1068,3446 @@ impl Database {
         Ok(())
     }
 
-    pub fn insert_video(&self, video: &Video) -> Result<()> {
-        self.conn.execute(
-            r#"
-            INSERT OR REPLACE INTO videos (id, url, title, channel, upload_date, description, added_at)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
-            "#,
-            params![
-                video.id,
-                video.url,
-                video.title,
-                video.channel,
-                video.upload_date.map(|d| d.format("%Y-%m-%d").to_string()),
-                video.description,
-                video.added_at.to_rfc3339(),
-            ],
-        )?;
-        self.update_search_index(&video.id)?;
-        Ok(())
+    fn migrate_video_metadata(&self) -> Result<()> {
+        self.add_column_if_missing("videos", "duration", "REAL")?;
+        self.add_column_if_missing("videos", "view_count", "INTEGER")?;
+        self.add_column_if_missing("videos", "thumbnail_url", "TEXT")
     }
 
-    pub fn insert_transcript(&self, transcript: &Transcript) -> Result<()> {
-        let segments_json = serde_json::to_string(&transcript.segments)?;
-        self.conn.execute(
-            r#"
-            INSERT OR REPLACE INTO transcripts (video_id, language, full_text, segments_json)
-            VALUES (?1, ?2, ?3, ?4)
-            "#,
-            params![
-                transcript.video_id,
-                transcript.language,
-                transcript.full_text,
-                segments_json,
-            ],
-        )?;
-        self.update_search_index(&transcript.video_id)?;
-        Ok(())
+    fn migrate_video_availability(&self) -> Result<()> {
+        self.add_column_if_missing("videos", "availability", "TEXT NOT NULL DEFAULT 'unknown'")?;
+        self.add_column_if_missing("videos", "availability_checked_at", "TEXT")?;
+        self.add_column_if_missing("videos", "availability_detail", "TEXT")
     }
 
-    fn update_search_index(&self, video_id: &str) -> Result<()> {
-        // Get video info
-        let video = match self.get_video(video_id)? {
-            Some(v) => v,
-            None => return Ok(()),
-        };
-
-        // Get transcript if exists
-        let transcript_text = self.get_transcript(video_id)?
-            .map(|t| t.full_text)
-            .unwrap_or_default();
-
-        // Delete existing entry
-        self.conn.execute(
-            "DELETE FROM search_index WHERE video_id = ?1",
-            params![video_id],
-        )?;
+    fn migrate_audit_log(&self) -> Result<()> {
+        if self.conn.dialect() == Dialect::Postgres {
+            // Rows are written by the engine_audit trigger install_postgres_triggers puts on each table
+            self.conn.execute_batch(
+                r#"
+                CREATE TABLE audit_log (
+                    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+                    entity_type TEXT NOT NULL,
+                    entity_id TEXT NOT NULL,
+                    action TEXT NOT NULL,
+                    summary TEXT NOT NULL,
+                    source TEXT,
+                    created_at TEXT NOT NULL
+                );
+                CREATE INDEX idx_audit_log_created ON audit_log(created_at);
+                CREATE INDEX idx_audit_log_entity ON audit_log(entity_type, entity_id);
+
+                -- Arguments: the key columns (joined with ':' for entity_id), the columns shown
+                -- in the summary, and the columns compared on update. The session's
+                -- engine.audit_source setting is recorded as the source.
+                CREATE FUNCTION engine_audit() RETURNS trigger LANGUAGE plpgsql AS $$
+                DECLARE
+                    target JSON;
+                    entity TEXT;
+                    summary TEXT;
+                    changed TEXT;
+                BEGIN
+                    IF TG_OP = 'DELETE' THEN
+                        target := row_to_json(OLD);
+                    ELSE
+                        target := row_to_json(NEW);
+                    END IF;
+
+                    SELECT string_agg(value #>> '{}', ':' ORDER BY ord) INTO entity
+                    FROM json_each(target) WITH ORDINALITY AS field(key, value, ord)
+                    WHERE key = ANY (string_to_array(TG_ARGV[0], ','));
+
+                    SELECT '{' || coalesce(string_agg(
+                        to_json(key)::TEXT || ':' ||
+                            CASE WHEN json_typeof(value) = 'string' THEN to_json(left(value #>> '{}', 60))::TEXT ELSE value::TEXT END,
+                        ',' ORDER BY ord), '') || '}' INTO summary
+                    FROM json_each(target) WITH ORDINALITY AS field(key, value, ord)
+                    WHERE key = ANY (string_to_array(TG_ARGV[1], ','));
+
+                    IF TG_OP = 'UPDATE' THEN
+                        SELECT coalesce(string_agg(new_field.key, ' ' ORDER BY new_field.ord), '') INTO changed
+                        FROM json_each(target) WITH ORDINALITY AS new_field(key, value, ord)
+                        JOIN json_each(row_to_json(OLD)) AS old_field ON old_field.key = new_field.key
+                        WHERE new_field.key = ANY (string_to_array(TG_ARGV[2], ','))
+                          AND new_field.value::TEXT IS DISTINCT FROM old_field.value::TEXT;
+                        summary := '{"changed":' || to_json(changed)::TEXT || CASE WHEN summary = '{}' THEN '}' ELSE ',' || substr(summary, 2) END;
+                    END IF;
+
+                    INSERT INTO audit_log (entity_type, entity_id, action, summary, source, created_at)
+                    VALUES (TG_TABLE_NAME, entity, lower(TG_OP), summary, current_setting('engine.audit_source', true),
+                            to_char(clock_timestamp() AT TIME ZONE 'utc', 'YYYY-MM-DD"T"HH24:MI:SS.MS"Z"'));
+                    RETURN NULL;
+                END
+                $$;
+                "#,
+            )?;
+            return Ok(());
+        }
+        self.conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS audit_log (
+                id INTEGER PRIMARY KEY,
+                entity_type TEXT NOT NULL,
+                entity_id TEXT NOT NULL,
+                action TEXT NOT NULL,
+                summary TEXT NOT NULL,
+                source TEXT,
+                created_at TEXT NOT NULL
+            );
 
-        // Insert updated entry
-        self.conn.execute(
-            "INSERT INTO search_index (video_id, title, description, transcript) VALUES (?1, ?2, ?3, ?4)",
-            params![
-                video_id,
-                video.title,
-                video.description.unwrap_or_default(),
-                transcript_text,
-            ],
+            CREATE INDEX IF NOT EXISTS idx_audit_log_created ON audit_log(created_at);
+            CREATE INDEX IF NOT EXISTS idx_audit_log_entity ON audit_log(entity_type, entity_id);
+            "#,
         )?;
-
         Ok(())
     }
 
-    pub fn rebuild_search_index(&self) -> Result<usize> {
-        // Clear existing index
-        self.conn.execute("DELETE FROM search_index", [])?;
-
-        // Get all videos
-        let videos = self.list_videos()?;
-        let count = videos.len();
-
-        for video in videos {
-            let transcript_text = self.get_transcript(&video.id)?
-                .map(|t| t.full_text)
-                .unwrap_or_default();
+    fn migrate_soft_delete(&self) -> Result<()> {
+        self.add_column_if_missing("videos", "archived_at", "TEXT")?;
+        self.add_column_if_missing("claims", "archived_at", "TEXT")
+    }
 
+    fn migrate_era_date_ranges(&self) -> Result<()> {
+        self.add_column_if_missing("eras", "start_year", "INTEGER")?;
+        self.add_column_if_missing("eras", "end_year", "INTEGER")?;
+        for (name, _, start_year, end_year) in DEFAULT_ERAS {
             self.conn.execute(
-                "INSERT INTO search_index (video_id, title, description, transcript) VALUES (?1, ?2, ?3, ?4)",
-                params![
-                    video.id,
-                    video.title,
-                    video.description.unwrap_or_default(),
-                    transcript_text,
-                ],
+                "UPDATE eras SET start_year = ?2, end_year = ?3 WHERE name = ?1",
+                params![name, start_year, end_year],
             )?;
         }
-
-        Ok(count)
+        Ok(())
     }
 
-    pub fn get_video(&self, id: &str) -> Result<Option<Video>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, url, title, channel, upload_date, description, added_at FROM videos WHERE id = ?1"
-        )?;
-
-        let mut rows = stmt.query(params![id])?;
-
-        if let Some(row) = rows.next()? {
-            Ok(Some(self.row_to_video(row)?))
-        } else {
-            Ok(None)
+    fn migrate_text_index(&self) -> Result<()> {
+        if self.conn.dialect() == Dialect::Postgres {
+            // Stands in for the FTS5 table; engine_text_index takes the kind, then the
+            // title and body expressions from TEXT_INDEX_SOURCES
+            self.conn.execute_batch(
+                r#"
+                CREATE TABLE text_index (
+                    kind TEXT NOT NULL,
+                    ref_id BIGINT NOT NULL,
+                    video_id TEXT,
+                    title TEXT NOT NULL,
+                    body TEXT NOT NULL,
+                    document tsvector GENERATED ALWAYS AS (
+                        setweight(to_tsvector('english', title), 'A') || setweight(to_tsvector('english', body), 'B')
+                    ) STORED
+                );
+                CREATE INDEX idx_text_index_ref ON text_index(kind, ref_id);
+                CREATE INDEX idx_text_index_document ON text_index USING gin (document);
+
+                CREATE FUNCTION engine_text_index() RETURNS trigger LANGUAGE plpgsql AS $$
+                BEGIN
+                    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+                        DELETE FROM text_index WHERE kind = TG_ARGV[0] AND ref_id = OLD.id;
+                    END IF;
+                    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+                        EXECUTE format(
+                            'INSERT INTO text_index (kind, ref_id, video_id, title, body) SELECT %L, id, video_id, %s, %s FROM %I WHERE id = $1',
+                            TG_ARGV[0], TG_ARGV[1], TG_ARGV[2], TG_TABLE_NAME
+                        ) USING NEW.id;
+                    END IF;
+                    RETURN NULL;
+                END
+                $$;
+                "#,
+            )?;
+            for (kind, table, title, body) in TEXT_INDEX_SOURCES {
+                self.conn.execute_batch(&format!(
+                    "CREATE TRIGGER text_index AFTER INSERT OR UPDATE OR DELETE ON {table}
+                     FOR EACH ROW EXECUTE FUNCTION engine_text_index('{kind}', '{}', '{}')",
+                    title.replace('\'', "''"),
+                    body.replace('\'', "''"),
+                ))?;
+            }
+            return self.rebuild_text_index();
         }
-    }
-
-    pub fn get_transcript(&self, video_id: &str) -> Result<Option<Transcript>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT video_id, language, full_text, segments_json FROM transcripts WHERE video_id = ?1"
-        )?;
-
-        let mut rows = stmt.query(params![video_id])?;
-
-        if let Some(row) = rows.next()? {
-            Ok(Some(self.row_to_transcript(row)?))
-        } else {
-            Ok(None)
+        let mut sql = String::from(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS text_index USING fts5(
+                kind UNINDEXED, ref_id UNINDEXED, video_id UNINDEXED, title, body, tokenize='porter'
+            );",
+        );
+        for (kind, table, title, body) in TEXT_INDEX_SOURCES {
+            let insert = format!(
+                "INSERT INTO text_index (kind, ref_id, video_id, title, body) SELECT '{kind}', id, video_id, {title}, {body} FROM {table} WHERE id = new.id;"
+            );
+            let delete = format!("DELETE FROM text_index WHERE kind = '{kind}' AND ref_id = old.id;");
+            sql.push_str(&format!(
+                "
+                CREATE TRIGGER IF NOT EXISTS text_index_{table}_ai AFTER INSERT ON {table} BEGIN {insert} END;
+                CREATE TRIGGER IF NOT EXISTS text_index_{table}_ad AFTER DELETE ON {table} BEGIN {delete} END;
+                CREATE TRIGGER IF NOT EXISTS text_index_{table}_au AFTER UPDATE ON {table} BEGIN {delete} {insert} END;"
+            ));
         }
+        self.conn.execute_batch(&sql)?;
+        self.rebuild_text_index()
     }
 
-    pub fn list_videos(&self) -> Result<Vec<Video>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, url, title, channel, upload_date, description, added_at FROM videos ORDER BY added_at DESC"
+    fn migrate_settings(&self) -> Result<()> {
+        self.conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+            "#,
         )?;
-
-        let mut videos = Vec::new();
-        let mut rows = stmt.query([])?;
-
-        while let Some(row) = rows.next()? {
-            videos.push(self.row_to_video(row)?);
-        }
-
-        Ok(videos)
+        Ok(())
     }
 
-    pub fn search(&self, query: &str) -> Result<Vec<(Video, String)>> {
-        let mut stmt = self.conn.prepare(
-            r#"
-            SELECT v.id, v.url, v.title, v.channel, v.upload_date, v.description, v.added_at,
-                   snippet(transcript_fts, 1, '>>>', '<<<', '...', 32) as snippet
-            FROM transcript_fts
-            JOIN videos v ON v.id = transcript_fts.video_id
-            WHERE transcript_fts MATCH ?1
-            ORDER BY rank
+    fn migrate_search_history(&self) -> Result<()> {
+        let sql = match self.conn.dialect() {
+            Dialect::Sqlite => {
+                r#"
+                CREATE TABLE IF NOT EXISTS search_history (
+                    id INTEGER PRIMARY KEY,
+                    query TEXT NOT NULL,
+                    era TEXT,
+                    region TEXT,
+                    topic TEXT,
+                    speaker TEXT,
+                    collection TEXT,
+                    scopes TEXT,
+                    result_count INTEGER NOT NULL DEFAULT 0,
+                    created_at TEXT NOT NULL
+                );
+                "#
+            }
+            Dialect::Postgres => {
+                r#"
+                CREATE TABLE search_history (
+                    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+                    query TEXT NOT NULL,
+                    era TEXT,
+                    region TEXT,
+                    topic TEXT,
+                    speaker TEXT,
+                    collection TEXT,
+                    scopes TEXT,
+                    result_count BIGINT NOT NULL DEFAULT 0,
+                    created_at TEXT NOT NULL
+                );
+                "#
+            }
+        };
+        self.conn.execute_batch(sql)?;
+        Ok(())
+    }
+
+    // Vectors move from JSON text to packed f32 BLOBs. The table is rebuilt so the
+    // column keeps its NOT NULL; ids are preserved for the sqlite-vec index.
+    fn migrate_embedding_blobs(&self) -> Result<()> {
+        if !self.has_column("embeddings", "vector_json")? {
+            return Ok(());
+        }
+        if self.conn.dialect() == Dialect::Postgres {
+            // Converted in place, which keeps the id sequence and the table's triggers
+            self.conn.execute_batch("ALTER TABLE embeddings ADD COLUMN vector BYTEA")?;
+            {
+                let mut select = self.conn.prepare("SELECT id, vector_json FROM embeddings")?;
+                let mut update = self.conn.prepare("UPDATE embeddings SET vector = ?2 WHERE id = ?1")?;
+                let mut rows = select.query([])?;
+                while let Some(row) = rows.next()? {
+                    let vector: Vec<f32> = serde_json::from_str(&row.get::<_, String>(1)?)?;
+                    update.execute(params![row.get::<_, i64>(0)?, vector_to_blob(&vector)])?;
+                }
+            }
+            self.conn.execute_batch("ALTER TABLE embeddings ALTER COLUMN vector SET NOT NULL, DROP COLUMN vector_json")?;
+            return Ok(());
+        }
+        self.conn.execute_batch(
+            r#"
+            CREATE TABLE embeddings_new (
+                id INTEGER PRIMARY KEY,
+                source_type TEXT NOT NULL,
+                source_id TEXT NOT NULL,
+                model TEXT NOT NULL,
+                vector BLOB NOT NULL,
+                dimensions INTEGER NOT NULL,
+                created_at TEXT NOT NULL,
+                UNIQUE(source_type, source_id, model)
+            );
+            "#,
+        )?;
+        {
+            let mut select = self.conn.prepare(
+                "SELECT id, source_type, source_id, model, vector_json, created_at FROM embeddings"
+            )?;
+            let mut insert = self.conn.prepare(
+                "INSERT INTO embeddings_new (id, source_type, source_id, model, vector, dimensions, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"
+            )?;
+            let mut rows = select.query([])?;
+            while let Some(row) = rows.next()? {
+                let vector_json: String = row.get(4)?;
+                let vector: Vec<f32> = serde_json::from_str(&vector_json)?;
+                insert.execute(params![
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    vector_to_blob(&vector),
+                    vector.len() as i64,
+                    row.get::<_, String>(5)?,
+                ])?;
+            }
+        }
+        self.conn.execute_batch(
+            r#"
+            DROP TABLE embeddings;
+            ALTER TABLE embeddings_new RENAME TO embeddings;
+            CREATE INDEX IF NOT EXISTS idx_embeddings_source ON embeddings(source_type, source_id);
+            CREATE INDEX IF NOT EXISTS idx_embeddings_model ON embeddings(model);
+            "#,
+        )?;
+        Ok(())
+    }
+
+    fn migrate_embedding_queue(&self) -> Result<()> {
+        let sql = match self.conn.dialect() {
+            Dialect::Sqlite => {
+                r#"
+                CREATE TABLE IF NOT EXISTS embedding_queue (
+                    id INTEGER PRIMARY KEY,
+                    source_type TEXT NOT NULL,
+                    source_id TEXT NOT NULL,
+                    attempts INTEGER NOT NULL DEFAULT 0,
+                    error TEXT,
+                    created_at TEXT NOT NULL,
+                    UNIQUE(source_type, source_id)
+                );
+                "#
+            }
+            Dialect::Postgres => {
+                r#"
+                CREATE TABLE embedding_queue (
+                    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+                    source_type TEXT NOT NULL,
+                    source_id TEXT NOT NULL,
+                    attempts BIGINT NOT NULL DEFAULT 0,
+                    error TEXT,
+                    created_at TEXT NOT NULL,
+                    UNIQUE(source_type, source_id)
+                );
+                "#
+            }
+        };
+        self.conn.execute_batch(sql)?;
+        Ok(())
+    }
+
+    fn migrate_claim_status(&self) -> Result<()> {
+        self.add_column_if_missing("claims", "status", "TEXT NOT NULL DEFAULT 'unverified'")?;
+        self.conn.execute("CREATE INDEX IF NOT EXISTS idx_claims_status ON claims(status)", [])?;
+        Ok(())
+    }
+
+    fn migrate_claim_dates(&self) -> Result<()> {
+        self.add_column_if_missing("claims", "date_start", "INTEGER")?;
+        self.add_column_if_missing("claims", "date_end", "INTEGER")?;
+        self.conn.execute("CREATE INDEX IF NOT EXISTS idx_claims_dates ON claims(date_start, date_end)", [])?;
+        Ok(())
+    }
+
+    fn migrate_claim_tags(&self) -> Result<()> {
+        let sql = match self.conn.dialect() {
+            Dialect::Sqlite => {
+                r#"
+                CREATE TABLE IF NOT EXISTS claim_topics (
+                    claim_id INTEGER NOT NULL REFERENCES claims(id) ON DELETE CASCADE,
+                    topic_id INTEGER NOT NULL REFERENCES topics(id),
+                    PRIMARY KEY (claim_id, topic_id)
+                );
+
+                CREATE TABLE IF NOT EXISTS claim_eras (
+                    claim_id INTEGER NOT NULL REFERENCES claims(id) ON DELETE CASCADE,
+                    era_id INTEGER NOT NULL REFERENCES eras(id),
+                    PRIMARY KEY (claim_id, era_id)
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_claim_topics_topic ON claim_topics(topic_id);
+                CREATE INDEX IF NOT EXISTS idx_claim_eras_era ON claim_eras(era_id);
+                "#
+            }
+            Dialect::Postgres => {
+                r#"
+                CREATE TABLE claim_topics (
+                    claim_id BIGINT NOT NULL REFERENCES claims(id) ON DELETE CASCADE DEFERRABLE,
+                    topic_id BIGINT NOT NULL REFERENCES topics(id) DEFERRABLE,
+                    PRIMARY KEY (claim_id, topic_id)
+                );
+                CREATE INDEX idx_claim_topics_topic ON claim_topics(topic_id);
+                CREATE TABLE claim_eras (
+                    claim_id BIGINT NOT NULL REFERENCES claims(id) ON DELETE CASCADE DEFERRABLE,
+                    era_id BIGINT NOT NULL REFERENCES eras(id) DEFERRABLE,
+                    PRIMARY KEY (claim_id, era_id)
+                );
+                CREATE INDEX idx_claim_eras_era ON claim_eras(era_id);
+                "#
+            }
+        };
+        self.conn.execute_batch(sql)?;
+        Ok(())
+    }
+
+    fn migrate_claim_metrics(&self) -> Result<()> {
+        let sql = match self.conn.dialect() {
+            Dialect::Sqlite => {
+                r#"
+                CREATE TABLE IF NOT EXISTS claim_metrics (
+                    claim_id INTEGER PRIMARY KEY REFERENCES claims(id) ON DELETE CASCADE,
+                    degree INTEGER NOT NULL,
+                    betweenness REAL NOT NULL,
+                    component INTEGER NOT NULL,
+                    computed_at TEXT NOT NULL
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_claim_metrics_betweenness ON claim_metrics(betweenness);
+                "#
+            }
+            Dialect::Postgres => {
+                r#"
+                CREATE TABLE claim_metrics (
+                    claim_id BIGINT PRIMARY KEY REFERENCES claims(id) ON DELETE CASCADE DEFERRABLE,
+                    degree BIGINT NOT NULL,
+                    betweenness DOUBLE PRECISION NOT NULL,
+                    component BIGINT NOT NULL,
+                    computed_at TEXT NOT NULL
+                );
+                CREATE INDEX idx_claim_metrics_betweenness ON claim_metrics(betweenness);
+                "#
+            }
+        };
+        self.conn.execute_batch(sql)?;
+        Ok(())
+    }
+
+    fn migrate_claim_provenance(&self) -> Result<()> {
+        let sql = match self.conn.dialect() {
+            Dialect::Sqlite => {
+                r#"
+                CREATE TABLE IF NOT EXISTS claim_provenance (
+                    claim_id INTEGER PRIMARY KEY REFERENCES claims(id) ON DELETE CASCADE,
+                    provider TEXT,
+                    model TEXT,
+                    prompt_version TEXT,
+                    run_id TEXT,
+                    reviewed_at TEXT
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_claim_provenance_run ON claim_provenance(run_id);
+                "#
+            }
+            Dialect::Postgres => {
+                r#"
+                CREATE TABLE claim_provenance (
+                    claim_id BIGINT PRIMARY KEY REFERENCES claims(id) ON DELETE CASCADE DEFERRABLE,
+                    provider TEXT,
+                    model TEXT,
+                    prompt_version TEXT,
+                    run_id TEXT,
+                    reviewed_at TEXT
+                );
+                CREATE INDEX idx_claim_provenance_run ON claim_provenance(run_id);
+                "#
+            }
+        };
+        self.conn.execute_batch(sql)?;
+        Ok(())
+    }
+
+    fn migrate_pattern_provenance(&self) -> Result<()> {
+        let sql = match self.conn.dialect() {
+            Dialect::Sqlite => {
+                r#"
+                CREATE TABLE IF NOT EXISTS pattern_provenance (
+                    pattern_id INTEGER PRIMARY KEY REFERENCES detected_patterns(id) ON DELETE CASCADE,
+                    provider TEXT,
+                    model TEXT,
+                    prompt_version TEXT,
+                    run_id TEXT,
+                    scope TEXT
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_pattern_provenance_run ON pattern_provenance(run_id);
+                "#
+            }
+            Dialect::Postgres => {
+                r#"
+                CREATE TABLE pattern_provenance (
+                    pattern_id BIGINT PRIMARY KEY REFERENCES detected_patterns(id) ON DELETE CASCADE DEFERRABLE,
+                    provider TEXT,
+                    model TEXT,
+                    prompt_version TEXT,
+                    run_id TEXT,
+                    scope TEXT
+                );
+                CREATE INDEX idx_pattern_provenance_run ON pattern_provenance(run_id);
+                "#
+            }
+        };
+        self.conn.execute_batch(sql)?;
+        Ok(())
+    }
+
+    // Rows added by `extract-entities` record which provider/model and prompt produced them
+    fn migrate_extracted_entities(&self) -> Result<()> {
+        for table in ["scholars", "video_scholars", "terms", "locations", "video_locations"] {
+            self.add_column_if_missing(table, "extracted_by", "TEXT")?;
+        }
+        Ok(())
+    }
+
+    fn migrate_llm_usage(&self) -> Result<()> {
+        let sql = match self.conn.dialect() {
+            Dialect::Sqlite => {
+                r#"
+                CREATE TABLE IF NOT EXISTS llm_usage (
+                    id INTEGER PRIMARY KEY,
+                    task TEXT NOT NULL,
+                    provider TEXT NOT NULL,
+                    model TEXT NOT NULL,
+                    input_tokens INTEGER,
+                    output_tokens INTEGER,
+                    latency_ms INTEGER NOT NULL,
+                    cost_usd REAL,
+                    success INTEGER NOT NULL,
+                    created_at TEXT NOT NULL
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_llm_usage_created ON llm_usage(created_at);
+                CREATE INDEX IF NOT EXISTS idx_llm_usage_task ON llm_usage(task);
+                "#
+            }
+            Dialect::Postgres => {
+                r#"
+                CREATE TABLE llm_usage (
+                    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+                    task TEXT NOT NULL,
+                    provider TEXT NOT NULL,
+                    model TEXT NOT NULL,
+                    input_tokens BIGINT,
+                    output_tokens BIGINT,
+                    latency_ms BIGINT NOT NULL,
+                    cost_usd DOUBLE PRECISION,
+                    success BIGINT NOT NULL,
+                    created_at TEXT NOT NULL
+                );
+                CREATE INDEX idx_llm_usage_created ON llm_usage(created_at);
+                CREATE INDEX idx_llm_usage_task ON llm_usage(task);
+                "#
+            }
+        };
+        self.conn.execute_batch(sql)?;
+        Ok(())
+    }
+
+    fn migrate_api_tokens(&self) -> Result<()> {
+        let sql = match self.conn.dialect() {
+            Dialect::Sqlite => {
+                r#"
+                CREATE TABLE IF NOT EXISTS api_tokens (
+                    id INTEGER PRIMARY KEY,
+                    name TEXT NOT NULL UNIQUE,
+                    token TEXT NOT NULL UNIQUE,
+                    scope TEXT NOT NULL,
+                    created_at TEXT NOT NULL,
+                    last_used_at TEXT
+                );
+                "#
+            }
+            Dialect::Postgres => {
+                r#"
+                CREATE TABLE api_tokens (
+                    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+                    name TEXT NOT NULL UNIQUE,
+                    token TEXT NOT NULL UNIQUE,
+                    scope TEXT NOT NULL,
+                    created_at TEXT NOT NULL,
+                    last_used_at TEXT
+                );
+                "#
+            }
+        };
+        self.conn.execute_batch(sql)?;
+        Ok(())
+    }
+
+    // Tokens were stored as issued; keep only their hash and the prefix `token list` shows
+    fn migrate_api_token_hashes(&self) -> Result<()> {
+        self.conn.execute("ALTER TABLE api_tokens RENAME COLUMN token TO token_hash", [])?;
+        self.add_column_if_missing("api_tokens", "prefix", "TEXT NOT NULL DEFAULT ''")?;
+        let mut stmt = self.conn.prepare("SELECT id, token_hash FROM api_tokens")?;
+        let tokens = stmt
+            .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<sql::Result<Vec<_>>>()?;
+        for (id, token) in tokens {
+            self.conn.execute(
+                "UPDATE api_tokens SET token_hash = ?1, prefix = ?2 WHERE id = ?3",
+                params![hash_api_token(&token), token_prefix(&token), id],
+            )?;
+        }
+        Ok(())
+    }
+
+    fn migrate_embedding_models(&self) -> Result<()> {
+        let sql = match self.conn.dialect() {
+            Dialect::Sqlite => {
+                r#"
+                CREATE TABLE IF NOT EXISTS embedding_models (
+                    model TEXT PRIMARY KEY,
+                    provider TEXT,
+                    dimensions INTEGER NOT NULL,
+                    created_at TEXT NOT NULL
+                );
+
+                INSERT OR IGNORE INTO embedding_models (model, provider, dimensions, created_at)
+                SELECT model, NULL, MAX(dimensions), MIN(created_at) FROM embeddings GROUP BY model;
+                "#
+            }
+            Dialect::Postgres => {
+                r#"
+                CREATE TABLE embedding_models (
+                    model TEXT PRIMARY KEY,
+                    provider TEXT,
+                    dimensions BIGINT NOT NULL,
+                    created_at TEXT NOT NULL
+                );
+
+                INSERT INTO embedding_models (model, provider, dimensions, created_at)
+                SELECT model, NULL, MAX(dimensions), MIN(created_at) FROM embeddings GROUP BY model;
+                "#
+            }
+        };
+        self.conn.execute_batch(sql)?;
+        Ok(())
+    }
+
+    fn migrate_saved_search_filters(&self) -> Result<()> {
+        for column in ["channel", "collection", "date_from", "date_to", "vector_json"] {
+            self.add_column_if_missing("saved_searches", column, "TEXT")?;
+        }
+        Ok(())
+    }
+
+    fn migrate_aliases(&self) -> Result<()> {
+        let sql = match self.conn.dialect() {
+            Dialect::Sqlite => {
+                r#"
+                CREATE TABLE IF NOT EXISTS aliases (
+                    id INTEGER PRIMARY KEY,
+                    name TEXT NOT NULL COLLATE NOCASE,
+                    alias TEXT NOT NULL COLLATE NOCASE,
+                    created_at TEXT NOT NULL,
+                    UNIQUE(name, alias)
+                );
+                CREATE INDEX IF NOT EXISTS idx_aliases_alias ON aliases(alias);
+                "#
+            }
+            Dialect::Postgres => {
+                r#"
+                CREATE TABLE aliases (
+                    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+                    name TEXT NOT NULL COLLATE nocase,
+                    alias TEXT NOT NULL COLLATE nocase,
+                    created_at TEXT NOT NULL,
+                    UNIQUE(name, alias)
+                );
+                CREATE INDEX idx_aliases_alias ON aliases(alias);
+                "#
+            }
+        };
+        self.conn.execute_batch(sql)?;
+        Ok(())
+    }
+
+    fn migrate_attachments(&self) -> Result<()> {
+        let sql = match self.conn.dialect() {
+            Dialect::Sqlite => {
+                r#"
+                CREATE TABLE IF NOT EXISTS attachments (
+                    id INTEGER PRIMARY KEY,
+                    video_id TEXT REFERENCES videos(id) ON DELETE CASCADE,
+                    claim_id INTEGER REFERENCES claims(id) ON DELETE CASCADE,
+                    file_name TEXT NOT NULL,
+                    mime_type TEXT NOT NULL,
+                    caption TEXT,
+                    size INTEGER NOT NULL,
+                    path TEXT,
+                    data BLOB,
+                    created_at TEXT NOT NULL,
+                    CHECK (video_id IS NOT NULL OR claim_id IS NOT NULL),
+                    CHECK (path IS NOT NULL OR data IS NOT NULL)
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_attachments_video ON attachments(video_id);
+                CREATE INDEX IF NOT EXISTS idx_attachments_claim ON attachments(claim_id);
+                "#
+            }
+            Dialect::Postgres => {
+                r#"
+                CREATE TABLE attachments (
+                    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+                    video_id TEXT REFERENCES videos(id) ON DELETE CASCADE DEFERRABLE,
+                    claim_id BIGINT REFERENCES claims(id) ON DELETE CASCADE DEFERRABLE,
+                    file_name TEXT NOT NULL,
+                    mime_type TEXT NOT NULL,
+                    caption TEXT,
+                    size BIGINT NOT NULL,
+                    path TEXT,
+                    data BYTEA,
+                    created_at TEXT NOT NULL,
+                    CHECK (video_id IS NOT NULL OR claim_id IS NOT NULL),
+                    CHECK (path IS NOT NULL OR data IS NOT NULL)
+                );
+                CREATE INDEX idx_attachments_video ON attachments(video_id);
+                CREATE INDEX idx_attachments_claim ON attachments(claim_id);
+                "#
+            }
+        };
+        self.conn.execute_batch(sql)?;
+        Ok(())
+    }
+
+    // Columns may already exist on databases that predate schema_migrations
+    fn add_column_if_missing(&self, table: &str, column: &str, definition: &str) -> Result<()> {
+        if self.has_column(table, column)? {
+            return Ok(());
+        }
+        // Definitions use SQLite's type names, whose INTEGER and REAL are 64-bit
+        let definition = match (self.conn.dialect(), definition.split_once(' ').unwrap_or((definition, ""))) {
+            (Dialect::Postgres, ("INTEGER", rest)) => format!("BIGINT {}", rest),
+            (Dialect::Postgres, ("REAL", rest)) => format!("DOUBLE PRECISION {}", rest),
+            _ => definition.to_string(),
+        };
+        self.conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition.trim_end()), [])?;
+        Ok(())
+    }
+
+    // Older rows may carry region-qualified tags ("en-US"); collapse to the primary subtag
+    fn normalize_transcript_languages(&self) -> Result<()> {
+        self.conn.execute(
+            r#"
+            UPDATE OR IGNORE transcripts
+            SET language = lower(CASE
+                WHEN instr(replace(language, '_', '-'), '-') > 0
+                THEN substr(language, 1, instr(replace(language, '_', '-'), '-') - 1)
+                ELSE language END)
+            WHERE language GLOB '*[-_A-Z]*'
+            "#,
+            [],
+        )?;
+        Ok(())
+    }
+
+    fn row_to_subscription(&self, row: &Row) -> Result<Subscription> {
+        let last_checked: Option<String> = row.get(3)?;
+        let created_at: String = row.get(4)?;
+        Ok(Subscription {
+            id: row.get(0)?,
+            url: row.get(1)?,
+            name: row.get(2)?,
+            last_checked: last_checked
+                .map(|s| DateTime::parse_from_rfc3339(&s).map(|d| d.with_timezone(&Utc)))
+                .transpose()?,
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+        })
+    }
+
+    fn row_to_archive(&self, row: &Row) -> Result<Archive> {
+        let fetched_at: String = row.get(6)?;
+        Ok(Archive {
+            id: row.get(0)?,
+            video_id: row.get(1)?,
+            kind: row.get(2)?,
+            format: row.get(3)?,
+            language: row.get(4)?,
+            content: row.get(5)?,
+            fetched_at: DateTime::parse_from_rfc3339(&fetched_at)?.with_timezone(&Utc),
+        })
+    }
+
+    fn row_to_attachment(&self, row: &Row) -> Result<Attachment> {
+        let created_at: String = row.get(8)?;
+        Ok(Attachment {
+            id: row.get(0)?,
+            video_id: row.get(1)?,
+            claim_id: row.get(2)?,
+            file_name: row.get(3)?,
+            mime_type: row.get(4)?,
+            caption: row.get(5)?,
+            size: row.get(6)?,
+            path: row.get(7)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+        })
+    }
+
+    fn update_search_index(&self, video_id: &str) -> Result<()> {
+        // Get video info
+        let video = match self.get_video(video_id)? {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+
+        // Get transcript if exists
+        let transcript_text = self.get_transcript(video_id)?
+            .map(|t| t.full_text)
+            .unwrap_or_default();
+
+        // Delete existing entry
+        self.conn.execute(
+            "DELETE FROM search_index WHERE video_id = ?1",
+            params![video_id],
+        )?;
+
+        // Insert updated entry
+        self.conn.execute(
+            "INSERT INTO search_index (video_id, title, description, transcript) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                video_id,
+                video.title,
+                video.description.unwrap_or_default(),
+                transcript_text,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    // Audit log
+
+    // TEMP triggers live only on this connection, so they can read the per-connection
+    // audit source and never break other tools (or older builds) writing to the file.
+    fn install_audit_triggers(&self) -> Result<()> {
+        if self.conn.dialect() == Dialect::Postgres {
+            return self.install_postgres_triggers();
+        }
+        self.conn.execute_batch(
+            "CREATE TEMP TABLE IF NOT EXISTS audit_context (source TEXT);
+             INSERT INTO audit_context (source) SELECT NULL WHERE NOT EXISTS (SELECT 1 FROM audit_context);"
+        )?;
+
+        let mut sql = String::new();
+        for table in self.exportable_tables()? {
+            if UNAUDITED_TABLES.contains(&table.as_str()) {
+                continue;
+            }
+            let mut stmt = self.conn.prepare("SELECT name FROM pragma_table_info(?1) ORDER BY cid")?;
+            let columns = stmt.query_map(params![table], |row| row.get::<_, String>(0))?.collect::<sql::Result<Vec<_>>>()?;
+            let key = if columns.iter().any(|c| c == "id") { "id" } else { "rowid" };
+            let shown: Vec<&String> = columns
+                .iter()
+                .filter(|c| *c != "id" && !UNAUDITED_COLUMNS.contains(&c.as_str()))
+                .take(6)
+                .collect();
+
+            let snapshot = |row: &str| {
+                let fields: Vec<String> = shown
+                    .iter()
+                    .map(|c| format!("'{0}', CASE WHEN typeof({1}.\"{0}\") = 'text' THEN substr({1}.\"{0}\", 1, 60) ELSE {1}.\"{0}\" END", c, row))
+                    .collect();
+                format!("json_object({})", fields.join(", "))
+            };
+            let changed: Vec<String> = columns
+                .iter()
+                .map(|c| format!("CASE WHEN OLD.\"{0}\" IS NOT NEW.\"{0}\" THEN '{0} ' ELSE '' END", c))
+                .collect();
+
+            for (action, row, summary) in [
+                ("insert", "NEW", snapshot("NEW")),
+                ("update", "NEW", format!("json_patch(json_object('changed', rtrim({})), {})", changed.join(" || "), snapshot("NEW"))),
+                ("delete", "OLD", snapshot("OLD")),
+            ] {
+                sql.push_str(&format!(
+                    "CREATE TEMP TRIGGER IF NOT EXISTS audit_{table}_{action} AFTER {event} ON main.\"{table}\" BEGIN
+                        INSERT INTO audit_log (entity_type, entity_id, action, summary, source, created_at)
+                        VALUES ('{table}', {row}.{key}, '{action}', {summary}, (SELECT source FROM audit_context),
+                                strftime('%Y-%m-%dT%H:%M:%fZ', 'now'));
+                     END;\n",
+                    event = action.to_uppercase(),
+                ));
+            }
+        }
+        self.conn.execute_batch(&sql)?;
+        Ok(())
+    }
+
+    fn dangling_references(&self) -> Result<Vec<DanglingReference>> {
+        if self.conn.dialect() == Dialect::Postgres {
+            return Ok(Vec::new());
+        }
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT fkc."table", fkc.rowid, fkc.parent, fk."from", ti."notnull" OR fk.on_delete = 'CASCADE'
+            FROM pragma_foreign_key_check fkc
+            JOIN pragma_foreign_key_list(fkc."table") fk ON fk.id = fkc.fkid
+            JOIN pragma_table_info(fkc."table") ti ON ti.name = fk."from"
+            "#
+        )?;
+        let rows = stmt
+            .query_map([], |row| Ok(DanglingReference {
+                table: row.get(0)?,
+                rowid: row.get(1)?,
+                parent: row.get(2)?,
+                column: row.get(3)?,
+                required: row.get(4)?,
+            }))?
+            .collect::<sql::Result<Vec<_>>>()?;
+        Ok(rows)
+    }
+
+    fn row_to_video_availability(&self, row: &Row) -> Result<VideoAvailability> {
+        let availability: String = row.get(10)?;
+        let checked_at: Option<String> = row.get(11)?;
+
+        Ok(VideoAvailability {
+            video: self.row_to_video(row)?,
+            availability: Availability::from_str(&availability).unwrap_or(Availability::Unknown),
+            checked_at: checked_at
+                .map(|s| DateTime::parse_from_rfc3339(&s).map(|d| d.with_timezone(&Utc)))
+                .transpose()?,
+            detail: row.get(12)?,
+        })
+    }
+
+    fn rebuild_text_index(&self) -> Result<()> {
+        self.conn.execute("DELETE FROM text_index", [])?;
+        for (kind, table, title, body) in TEXT_INDEX_SOURCES {
+            self.conn.execute(
+                &format!("INSERT INTO text_index (kind, ref_id, video_id, title, body) SELECT '{kind}', id, video_id, {title}, {body} FROM {table}"),
+                [],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Calculate fuzzy match score combining multiple algorithms
+    fn fuzzy_score(&self, query: &str, text: &str, query_words: &[&str]) -> f64 {
+        let text_lower = text.to_lowercase();
+
+        // Exact substring match bonus
+        if text_lower.contains(query) {
+            return 1.0;
+        }
+
+        // Word-level matching using Jaro-Winkler
+        let text_words: Vec<&str> = text_lower.split_whitespace().collect();
+        let mut word_score = 0.0;
+        let mut matched_words = 0;
+
+        for qword in query_words {
+            let best_match = text_words.iter()
+                .map(|tw| jaro_winkler(qword, tw))
+                .max_by(|a, b| a.partial_cmp(b).unwrap())
+                .unwrap_or(0.0);
+            if best_match > 0.8 {
+                matched_words += 1;
+                word_score += best_match;
+            }
+        }
+
+        // Combine word matching with overall similarity
+        let overall = normalized_levenshtein(query, &text_lower);
+        let word_ratio = if !query_words.is_empty() {
+            matched_words as f64 / query_words.len() as f64
+        } else {
+            0.0
+        };
+
+        // Weighted combination: word matching is most important
+        let word_component = if !query_words.is_empty() {
+            word_score / query_words.len() as f64
+        } else {
+            0.0
+        };
+        word_component * 0.6 + overall * 0.2 + word_ratio * 0.2
+    }
+
+    /// Highlight matching words in text
+    fn highlight_match(&self, text: &str, query_words: &[&str]) -> String {
+        let mut result = text.to_string();
+        for word in query_words {
+            // Simple case-insensitive highlighting
+            let lower = result.to_lowercase();
+            if let Some(pos) = lower.find(word) {
+                let end = pos + word.len();
+                let matched = &result[pos..end];
+                result = format!("{}>>>{}<<<{}", &result[..pos], matched, &result[end..]);
+            }
+        }
+        self.truncate_snippet(&result, 200)
+    }
+
+    fn truncate_snippet(&self, text: &str, max_len: usize) -> String {
+        if text.len() <= max_len {
+            text.to_string()
+        } else {
+            format!("{}...", &text.chars().take(max_len).collect::<String>())
+        }
+    }
+
+    /// Search claims with fuzzy matching
+    fn search_claims_fuzzy(
+        &self,
+        query: &str,
+        query_words: &[&str],
+        video_filter: Option<&str>,
+        threshold: f64,
+    ) -> Result<Vec<UnifiedSearchResult>> {
+        let sql = if video_filter.is_some() {
+            "SELECT id, text, video_id, timestamp, source_quote, category FROM claims WHERE video_id = ?1 AND archived_at IS NULL"
+        } else {
+            "SELECT id, text, video_id, timestamp, source_quote, category FROM claims WHERE archived_at IS NULL"
+        };
+
+        let mut stmt = self.conn.prepare(sql)?;
+        let mut rows = if let Some(vid) = video_filter {
+            stmt.query(params![vid])?
+        } else {
+            stmt.query([])?
+        };
+
+        let mut results = Vec::new();
+        while let Some(row) = rows.next()? {
+            let id: i64 = row.get(0)?;
+            let text: String = row.get(1)?;
+            let video_id: String = row.get(2)?;
+            let timestamp: Option<f64> = row.get(3)?;
+            let source_quote: String = row.get(4)?;
+            let category: String = row.get(5)?;
+
+            // Score against both text and source_quote
+            let text_score = self.fuzzy_score(query, &text, query_words);
+            let quote_score = self.fuzzy_score(query, &source_quote, query_words);
+            let score = text_score.max(quote_score);
+
+            if score >= threshold {
+                results.push(UnifiedSearchResult {
+                    result_type: SearchResultType::Claim,
+                    id,
+                    title: self.truncate_snippet(&text, 100),
+                    subtitle: Some(format!("{} | {}", category, video_id)),
+                    snippet: Some(self.highlight_match(&source_quote, query_words)),
+                    score,
+                    video_id: Some(video_id),
+                    timestamp,
+                    location: None,
+                });
+            }
+        }
+        Ok(results)
+    }
+
+    /// Search videos with fuzzy matching
+    fn search_videos_fuzzy(
+        &self,
+        query: &str,
+        query_words: &[&str],
+        threshold: f64,
+    ) -> Result<Vec<UnifiedSearchResult>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, title, channel, description FROM videos WHERE archived_at IS NULL"
+        )?;
+        let mut rows = stmt.query([])?;
+        let mut results = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let id: String = row.get(0)?;
+            let title: String = row.get(1)?;
+            let channel: Option<String> = row.get(2)?;
+            let description: Option<String> = row.get(3)?;
+
+            let title_score = self.fuzzy_score(query, &title, query_words);
+            let desc_score = description.as_ref()
+                .map(|d| self.fuzzy_score(query, d, query_words) * 0.8)
+                .unwrap_or(0.0);
+            let score = title_score.max(desc_score);
+
+            if score >= threshold {
+                results.push(UnifiedSearchResult {
+                    result_type: SearchResultType::Video,
+                    id: 0, // Videos use string IDs, store in video_id
+                    title,
+                    subtitle: channel,
+                    snippet: description.map(|d| self.truncate_snippet(&d, 100)),
+                    score,
+                    video_id: Some(id),
+                    timestamp: None,
+                    location: None,
+                });
+            }
+        }
+        Ok(results)
+    }
+
+    /// Search MOCs with fuzzy matching
+    fn search_mocs_fuzzy(
+        &self,
+        query: &str,
+        query_words: &[&str],
+        threshold: f64,
+    ) -> Result<Vec<UnifiedSearchResult>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, title, description FROM mocs"
+        )?;
+        let mut rows = stmt.query([])?;
+        let mut results = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let id: i64 = row.get(0)?;
+            let title: String = row.get(1)?;
+            let description: Option<String> = row.get(2)?;
+
+            let title_score = self.fuzzy_score(query, &title, query_words);
+            let desc_score = description.as_ref()
+                .map(|d| self.fuzzy_score(query, d, query_words) * 0.7)
+                .unwrap_or(0.0);
+            let score = title_score.max(desc_score);
+
+            if score >= threshold {
+                results.push(UnifiedSearchResult {
+                    result_type: SearchResultType::Moc,
+                    id,
+                    title,
+                    subtitle: None,
+                    snippet: description.map(|d| self.truncate_snippet(&d, 100)),
+                    score,
+                    video_id: None,
+                    timestamp: None,
+                    location: None,
+                });
+            }
+        }
+        Ok(results)
+    }
+
+    /// Search sources with fuzzy matching
+    fn search_sources_fuzzy(
+        &self,
+        query: &str,
+        query_words: &[&str],
+        threshold: f64,
+    ) -> Result<Vec<UnifiedSearchResult>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, title, author, source_type, notes FROM sources"
+        )?;
+        let mut rows = stmt.query([])?;
+        let mut results = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let id: i64 = row.get(0)?;
+            let title: String = row.get(1)?;
+            let author: Option<String> = row.get(2)?;
+            let source_type: String = row.get(3)?;
+            let notes: Option<String> = row.get(4)?;
+
+            let title_score = self.fuzzy_score(query, &title, query_words);
+            let author_score = author.as_ref()
+                .map(|a| self.fuzzy_score(query, a, query_words) * 0.8)
+                .unwrap_or(0.0);
+            let notes_score = notes.as_ref()
+                .map(|n| self.fuzzy_score(query, n, query_words) * 0.6)
+                .unwrap_or(0.0);
+            let score = title_score.max(author_score).max(notes_score);
+
+            if score >= threshold {
+                results.push(UnifiedSearchResult {
+                    result_type: SearchResultType::Source,
+                    id,
+                    title,
+                    subtitle: Some(format!("{}{}", source_type, author.map(|a| format!(" by {}", a)).unwrap_or_default())),
+                    snippet: notes.map(|n| self.truncate_snippet(&n, 100)),
+                    score,
+                    video_id: None,
+                    timestamp: None,
+                    location: None,
+                });
+            }
+        }
+        Ok(results)
+    }
+
+    /// Search scholars with fuzzy matching
+    fn search_scholars_fuzzy(
+        &self,
+        query: &str,
+        query_words: &[&str],
+        threshold: f64,
+    ) -> Result<Vec<UnifiedSearchResult>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, field, contribution FROM scholars"
+        )?;
+        let mut rows = stmt.query([])?;
+        let mut results = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let id: i64 = row.get(0)?;
+            let name: String = row.get(1)?;
+            let field: Option<String> = row.get(2)?;
+            let contribution: Option<String> = row.get(3)?;
+
+            let name_score = self.fuzzy_score(query, &name, query_words);
+            let contrib_score = contribution.as_ref()
+                .map(|c| self.fuzzy_score(query, c, query_words) * 0.7)
+                .unwrap_or(0.0);
+            let score = name_score.max(contrib_score);
+
+            if score >= threshold {
+                results.push(UnifiedSearchResult {
+                    result_type: SearchResultType::Scholar,
+                    id,
+                    title: name,
+                    subtitle: field,
+                    snippet: contribution,
+                    score,
+                    video_id: None,
+                    timestamp: None,
+                    location: None,
+                });
+            }
+        }
+        Ok(results)
+    }
+
+    /// Search terms with fuzzy matching
+    fn search_terms_fuzzy(
+        &self,
+        query: &str,
+        query_words: &[&str],
+        video_filter: Option<&str>,
+        threshold: f64,
+    ) -> Result<Vec<UnifiedSearchResult>> {
+        let sql = if video_filter.is_some() {
+            "SELECT id, term, definition, domain, video_id, timestamp FROM terms WHERE video_id = ?1"
+        } else {
+            "SELECT id, term, definition, domain, video_id, timestamp FROM terms"
+        };
+
+        let mut stmt = self.conn.prepare(sql)?;
+        let mut rows = if let Some(vid) = video_filter {
+            stmt.query(params![vid])?
+        } else {
+            stmt.query([])?
+        };
+        let mut results = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let id: i64 = row.get(0)?;
+            let term: String = row.get(1)?;
+            let definition: String = row.get(2)?;
+            let domain: Option<String> = row.get(3)?;
+            let video_id: Option<String> = row.get(4)?;
+            let timestamp: Option<f64> = row.get(5)?;
+
+            let term_score = self.fuzzy_score(query, &term, query_words);
+            let def_score = self.fuzzy_score(query, &definition, query_words) * 0.8;
+            let score = term_score.max(def_score);
+
+            if score >= threshold {
+                results.push(UnifiedSearchResult {
+                    result_type: SearchResultType::Term,
+                    id,
+                    title: term,
+                    subtitle: domain,
+                    snippet: Some(self.truncate_snippet(&definition, 150)),
+                    score,
+                    video_id,
+                    timestamp,
+                    location: None,
+                });
+            }
+        }
+        Ok(results)
+    }
+
+    /// Search quotes with fuzzy matching
+    fn search_quotes_fuzzy(
+        &self,
+        query: &str,
+        query_words: &[&str],
+        video_filter: Option<&str>,
+        threshold: f64,
+    ) -> Result<Vec<UnifiedSearchResult>> {
+        let sql = if video_filter.is_some() {
+            "SELECT id, text, speaker, video_id, timestamp, context FROM quotes WHERE video_id = ?1"
+        } else {
+            "SELECT id, text, speaker, video_id, timestamp, context FROM quotes"
+        };
+
+        let mut stmt = self.conn.prepare(sql)?;
+        let mut rows = if let Some(vid) = video_filter {
+            stmt.query(params![vid])?
+        } else {
+            stmt.query([])?
+        };
+        let mut results = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let id: i64 = row.get(0)?;
+            let text: String = row.get(1)?;
+            let speaker: Option<String> = row.get(2)?;
+            let video_id: String = row.get(3)?;
+            let timestamp: Option<f64> = row.get(4)?;
+            let context: Option<String> = row.get(5)?;
+
+            let text_score = self.fuzzy_score(query, &text, query_words);
+            let speaker_score = speaker.as_ref()
+                .map(|s| self.fuzzy_score(query, s, query_words) * 0.9)
+                .unwrap_or(0.0);
+            let score = text_score.max(speaker_score);
+
+            if score >= threshold {
+                results.push(UnifiedSearchResult {
+                    result_type: SearchResultType::Quote,
+                    id,
+                    title: self.truncate_snippet(&text, 100),
+                    subtitle: speaker.map(|s| format!("— {}", s)),
+                    snippet: context,
+                    score,
+                    video_id: Some(video_id),
+                    timestamp,
+                    location: None,
+                });
+            }
+        }
+        Ok(results)
+    }
+
+    /// Search evidence with fuzzy matching
+    fn search_evidence_fuzzy(
+        &self,
+        query: &str,
+        query_words: &[&str],
+        video_filter: Option<&str>,
+        threshold: f64,
+    ) -> Result<Vec<UnifiedSearchResult>> {
+        let sql = if video_filter.is_some() {
+            "SELECT id, video_id, description, evidence_type, timestamp FROM evidence WHERE video_id = ?1"
+        } else {
+            "SELECT id, video_id, description, evidence_type, timestamp FROM evidence"
+        };
+
+        let mut stmt = self.conn.prepare(sql)?;
+        let mut rows = if let Some(vid) = video_filter {
+            stmt.query(params![vid])?
+        } else {
+            stmt.query([])?
+        };
+        let mut results = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let id: i64 = row.get(0)?;
+            let video_id: String = row.get(1)?;
+            let description: String = row.get(2)?;
+            let evidence_type: String = row.get(3)?;
+            let timestamp: Option<f64> = row.get(4)?;
+
+            let score = self.fuzzy_score(query, &description, query_words);
+
+            if score >= threshold {
+                results.push(UnifiedSearchResult {
+                    result_type: SearchResultType::Evidence,
+                    id,
+                    title: self.truncate_snippet(&description, 100),
+                    subtitle: Some(evidence_type),
+                    snippet: None,
+                    score,
+                    video_id: Some(video_id),
+                    timestamp,
+                    location: None,
+                });
+            }
+        }
+        Ok(results)
+    }
+
+    /// Search visuals with fuzzy matching
+    fn search_visuals_fuzzy(
+        &self,
+        query: &str,
+        query_words: &[&str],
+        video_filter: Option<&str>,
+        threshold: f64,
+    ) -> Result<Vec<UnifiedSearchResult>> {
+        let sql = if video_filter.is_some() {
+            "SELECT id, video_id, description, visual_type, timestamp, significance FROM visuals WHERE video_id = ?1"
+        } else {
+            "SELECT id, video_id, description, visual_type, timestamp, significance FROM visuals"
+        };
+
+        let mut stmt = self.conn.prepare(sql)?;
+        let mut rows = if let Some(vid) = video_filter {
+            stmt.query(params![vid])?
+        } else {
+            stmt.query([])?
+        };
+        let mut results = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let id: i64 = row.get(0)?;
+            let video_id: String = row.get(1)?;
+            let description: String = row.get(2)?;
+            let visual_type: String = row.get(3)?;
+            let timestamp: f64 = row.get(4)?;
+            let significance: Option<String> = row.get(5)?;
+
+            let desc_score = self.fuzzy_score(query, &description, query_words);
+            let sig_score = significance.as_ref()
+                .map(|s| self.fuzzy_score(query, s, query_words) * 0.7)
+                .unwrap_or(0.0);
+            let score = desc_score.max(sig_score);
+
+            if score >= threshold {
+                results.push(UnifiedSearchResult {
+                    result_type: SearchResultType::Visual,
+                    id,
+                    title: self.truncate_snippet(&description, 100),
+                    subtitle: Some(visual_type),
+                    snippet: significance,
+                    score,
+                    video_id: Some(video_id),
+                    timestamp: Some(timestamp),
+                    location: None,
+                });
+            }
+        }
+        Ok(results)
+    }
+
+    /// Search locations with fuzzy matching
+    fn search_locations_fuzzy(
+        &self,
+        query: &str,
+        query_words: &[&str],
+        threshold: f64,
+    ) -> Result<Vec<UnifiedSearchResult>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, lat, lon FROM locations"
+        )?;
+        let mut rows = stmt.query([])?;
+        let mut results = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let id: i64 = row.get(0)?;
+            let name: String = row.get(1)?;
+            let lat: f64 = row.get(2)?;
+            let lon: f64 = row.get(3)?;
+
+            let score = self.fuzzy_score(query, &name, query_words);
+
+            if score >= threshold {
+                results.push(UnifiedSearchResult {
+                    result_type: SearchResultType::Location,
+                    id,
+                    title: name,
+                    subtitle: Some(format!("{:.4}, {:.4}", lat, lon)),
+                    snippet: None,
+                    score,
+                    video_id: None,
+                    timestamp: None,
+                    location: Some((lat, lon)),
+                });
+            }
+        }
+        Ok(results)
+    }
+
+    /// Search research questions with fuzzy matching
+    fn search_questions_fuzzy(
+        &self,
+        query: &str,
+        query_words: &[&str],
+        threshold: f64,
+    ) -> Result<Vec<UnifiedSearchResult>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, question, notes, status FROM research_questions"
+        )?;
+        let mut rows = stmt.query([])?;
+        let mut results = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let id: i64 = row.get(0)?;
+            let question: String = row.get(1)?;
+            let notes: Option<String> = row.get(2)?;
+            let status: String = row.get(3)?;
+
+            let q_score = self.fuzzy_score(query, &question, query_words);
+            let notes_score = notes.as_ref()
+                .map(|n| self.fuzzy_score(query, n, query_words) * 0.6)
+                .unwrap_or(0.0);
+            let score = q_score.max(notes_score);
+
+            if score >= threshold {
+                results.push(UnifiedSearchResult {
+                    result_type: SearchResultType::Question,
+                    id,
+                    title: self.truncate_snippet(&question, 100),
+                    subtitle: Some(status),
+                    snippet: notes.map(|n| self.truncate_snippet(&n, 100)),
+                    score,
+                    video_id: None,
+                    timestamp: None,
+                    location: None,
+                });
+            }
+        }
+        Ok(results)
+    }
+
+    /// Build search facets from results
+    fn build_search_facets(&self, results: &[UnifiedSearchResult]) -> Result<SearchFacets> {
+        let mut type_counts: HashMap<SearchResultType, usize> = HashMap::new();
+        let mut video_counts: HashMap<String, usize> = HashMap::new();
+
+        for result in results {
+            *type_counts.entry(result.result_type).or_insert(0) += 1;
+            if let Some(ref vid) = result.video_id {
+                *video_counts.entry(vid.clone()).or_insert(0) += 1;
+            }
+        }
+
+        // Get video titles for facets
+        let mut video_facets = Vec::new();
+        for (vid, count) in video_counts {
+            if let Some(video) = self.get_video(&vid)? {
+                video_facets.push((vid, video.title, count));
+            }
+        }
+        video_facets.sort_by(|a, b| b.2.cmp(&a.2));
+
+        let mut types_vec: Vec<(SearchResultType, usize)> = type_counts.into_iter().collect();
+        types_vec.sort_by(|a, b| b.1.cmp(&a.1));
+
+        let hits: Vec<(String, usize)> = video_facets.iter().map(|(vid, _, count)| (vid.clone(), *count)).collect();
+
+        Ok(SearchFacets {
+            types: types_vec,
+            videos: video_facets,
+            metadata: self.metadata_facets(&hits)?,
+        })
+    }
+
+    fn row_to_video(&self, row: &Row) -> Result<Video> {
+        let upload_date: Option<String> = row.get(4)?;
+        let added_at: String = row.get(6)?;
+
+        Ok(Video {
+            id: row.get(0)?,
+            url: row.get(1)?,
+            title: row.get(2)?,
+            channel: row.get(3)?,
+            upload_date: upload_date.and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok()),
+            description: row.get(5)?,
+            added_at: DateTime::parse_from_rfc3339(&added_at)?.with_timezone(&Utc),
+            duration: row.get(7)?,
+            view_count: row.get(8)?,
+            thumbnail_url: row.get(9)?,
+        })
+    }
+
+    fn row_to_transcript(&self, row: &Row) -> Result<Transcript> {
+        let segments_json: String = row.get(3)?;
+        let segments: Vec<TranscriptSegment> = serde_json::from_str(&segments_json)?;
+
+        Ok(Transcript {
+            video_id: row.get(0)?,
+            language: row.get(1)?,
+            full_text: row.get(2)?,
+            segments,
+        })
+    }
+
+    // Era operations
+
+    fn seed_default_eras(&self) -> Result<()> {
+        for (name, order, start_year, end_year) in DEFAULT_ERAS {
+            self.conn.execute(
+                "INSERT OR IGNORE INTO eras (name, sort_order, start_year, end_year) VALUES (?1, ?2, ?3, ?4)",
+                params![name, order, start_year, end_year],
+            )?;
+        }
+        Ok(())
+    }
+
+    fn check_tag_table(table: &str) -> Result<()> {
+        match table {
+            "eras" | "regions" | "topics" => Ok(()),
+            _ => anyhow::bail!("Not a taxonomy table: {}", table),
+        }
+    }
+
+    fn row_to_saved_search(&self, row: &Row) -> Result<SavedSearch> {
+        let vector_json: Option<String> = row.get(10)?;
+        let created_at: String = row.get(11)?;
+        Ok(SavedSearch {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            query: row.get(2)?,
+            era: row.get(3)?,
+            region: row.get(4)?,
+            topic: row.get(5)?,
+            channel: row.get(6)?,
+            collection: row.get(7)?,
+            date_from: row.get(8)?,
+            date_to: row.get(9)?,
+            vector: vector_json.map(|v| serde_json::from_str(&v)).transpose()?,
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+        })
+    }
+
+    fn expand_query(&self, query: &str) -> Result<String> {
+        let groups = self.alias_groups()?;
+        if groups.is_empty() {
+            return Ok(query.to_string());
+        }
+        Ok(expand_aliases(query, &groups))
+    }
+
+    fn row_to_search_history(&self, row: &Row) -> Result<SearchHistoryEntry> {
+        let scopes: Option<String> = row.get(7)?;
+        let created_at: String = row.get(9)?;
+        Ok(SearchHistoryEntry {
+            id: row.get(0)?,
+            query: row.get(1)?,
+            era: row.get(2)?,
+            region: row.get(3)?,
+            topic: row.get(4)?,
+            speaker: row.get(5)?,
+            collection: row.get(6)?,
+            scopes: scopes
+                .map(|s| s.split(',').map(|p| p.to_string()).collect())
+                .unwrap_or_default(),
+            result_count: row.get(8)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+        })
+    }
+
+    fn delete_claim_and_dependents(&self, id: i64) -> Result<()> {
+        self.delete_dependents("claims", &id)?;
+        self.conn.execute(
+            "DELETE FROM embeddings WHERE source_type = 'claim' AND source_id = ?1",
+            params![id.to_string()],
+        )?;
+        self.conn.execute("DELETE FROM claims WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    // Clear everything that references a row about to be deleted: cascading or required
+    // references are deleted (recursively), optional ones are set to NULL. Done by hand
+    // because most references predate enforcement and declare no ON DELETE action.
+    fn delete_dependents(&self, parent: &str, id: &dyn rusqlite::ToSql) -> Result<()> {
+        for (table, column, required) in self.foreign_keys_to(parent)? {
+            if !required {
+                self.conn.execute(&format!("UPDATE \"{}\" SET \"{}\" = NULL WHERE \"{}\" = ?1", table, column, column), params![id])?;
+                continue;
+            }
+            if table != parent && self.has_column(&table, "id")? {
+                let mut stmt = self.conn.prepare(&format!("SELECT id FROM \"{}\" WHERE \"{}\" = ?1", table, column))?;
+                let child_ids = stmt
+                    .query_map(params![id], |row| row.get::<_, rusqlite::types::Value>(0))?
+                    .collect::<sql::Result<Vec<_>>>()?;
+                for child_id in child_ids {
+                    self.delete_dependents(&table, &child_id)?;
+                }
+            }
+            self.conn.execute(&format!("DELETE FROM \"{}\" WHERE \"{}\" = ?1", table, column), params![id])?;
+        }
+        Ok(())
+    }
+
+    fn has_table(&self, table: &str) -> Result<bool> {
+        let sql = match self.conn.dialect() {
+            Dialect::Sqlite => "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            Dialect::Postgres => "SELECT to_regclass(?1) IS NOT NULL",
+        };
+        Ok(self.conn.query_row(sql, params![table], |row| row.get(0))?)
+    }
+
+    // (table, column) pairs referencing `parent`, and whether the reference is required:
+    // NOT NULL or ON DELETE CASCADE
+    fn foreign_keys_to(&self, parent: &str) -> Result<Vec<(String, String, bool)>> {
+        let sql = match self.conn.dialect() {
+            Dialect::Sqlite => {
+                r#"
+                SELECT m.name, fk."from", ti."notnull" OR fk.on_delete = 'CASCADE'
+                FROM sqlite_master m
+                JOIN pragma_foreign_key_list(m.name) fk
+                JOIN pragma_table_info(m.name) ti ON ti.name = fk."from"
+                WHERE m.type = 'table' AND fk."table" = ?1
+                "#
+            }
+            Dialect::Postgres => {
+                r#"
+                SELECT child.relname, a.attname, a.attnotnull OR c.confdeltype = 'c'
+                FROM pg_constraint c
+                JOIN pg_class child ON child.oid = c.conrelid
+                JOIN pg_attribute a ON a.attrelid = c.conrelid AND a.attnum = c.conkey[1]
+                WHERE c.contype = 'f' AND c.confrelid = to_regclass(?1)
+                ORDER BY child.oid, a.attnum
+                "#
+            }
+        };
+        let mut stmt = self.conn.prepare(sql)?;
+        let references = stmt
+            .query_map(params![parent], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<sql::Result<Vec<_>>>()?;
+        Ok(references)
+    }
+
+    // The full-text query for this backend: an FTS5 MATCH expression or a tsquery
+    fn match_query(&self, query: &str) -> String {
+        match self.conn.dialect() {
+            Dialect::Sqlite => to_fts_query(query),
+            Dialect::Postgres => to_tsquery(query),
+        }
+    }
+
+    // search_index in a FROM clause, with the tsquery as `query` on PostgreSQL
+    fn search_index_from(&self) -> &'static str {
+        match self.conn.dialect() {
+            Dialect::Sqlite => "search_index",
+            Dialect::Postgres => "search_index CROSS JOIN to_tsquery('english', ?1) query",
+        }
+    }
+
+    // The condition matching search_index rows against ?1
+    fn search_index_matches(&self) -> &'static str {
+        match self.conn.dialect() {
+            Dialect::Sqlite => "search_index MATCH ?1",
+            Dialect::Postgres => "search_index.document @@ query",
+        }
+    }
+
+    // Stored columns in declaration order; Postgres' generated search vectors are left out
+    fn table_columns(&self, table: &str) -> Result<Vec<String>> {
+        let sql = match self.conn.dialect() {
+            Dialect::Sqlite => "SELECT name FROM pragma_table_info(?1)",
+            Dialect::Postgres => {
+                "SELECT column_name FROM information_schema.columns
+                 WHERE table_schema = current_schema() AND table_name = ?1 AND is_generated = 'NEVER'
+                 ORDER BY ordinal_position"
+            }
+        };
+        let mut stmt = self.conn.prepare(sql)?;
+        let columns = stmt.query_map(params![table], |row| row.get(0))?.collect::<sql::Result<Vec<String>>>()?;
+        Ok(columns)
+    }
+
+    fn has_column(&self, table: &str, column: &str) -> Result<bool> {
+        Ok(self.table_columns(table)?.iter().any(|c| c == column))
+    }
+
+    fn row_to_claim(&self, row: &Row) -> Result<Claim> {
+        let category_str: String = row.get(5)?;
+        let confidence_str: String = row.get(6)?;
+        let created_at: String = row.get(7)?;
+        let status_str: String = row.get(8)?;
+
+        Ok(Claim {
+            id: row.get(0)?,
+            text: row.get(1)?,
+            video_id: row.get(2)?,
+            timestamp: row.get(3)?,
+            source_quote: row.get(4)?,
+            category: ClaimCategory::from_str(&category_str).unwrap_or(ClaimCategory::Factual),
+            confidence: Confidence::from_str(&confidence_str).unwrap_or(Confidence::Medium),
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+            status: ClaimStatus::from_str(&status_str).unwrap_or(ClaimStatus::Unverified),
+            date_start: row.get(9)?,
+            date_end: row.get(10)?,
+        })
+    }
+
+    fn row_to_claim_provenance(&self, row: &Row) -> Result<ClaimProvenance> {
+        let reviewed_at: Option<String> = row.get(5)?;
+        Ok(ClaimProvenance {
+            claim_id: row.get(0)?,
+            provider: row.get(1)?,
+            model: row.get(2)?,
+            prompt_version: row.get(3)?,
+            run_id: row.get(4)?,
+            reviewed_at: reviewed_at
+                .map(|t| DateTime::parse_from_rfc3339(&t).map(|dt| dt.with_timezone(&Utc)))
+                .transpose()?,
+        })
+    }
+
+    fn row_to_claim_metrics(&self, row: &Row) -> Result<ClaimMetrics> {
+        self.row_to_claim_metrics_from_offset(row, 0)
+    }
+
+    fn row_to_claim_metrics_from_offset(&self, row: &Row, offset: usize) -> Result<ClaimMetrics> {
+        let computed_at: String = row.get(offset + 4)?;
+        Ok(ClaimMetrics {
+            claim_id: row.get(offset)?,
+            degree: row.get::<_, i64>(offset + 1)? as usize,
+            betweenness: row.get(offset + 2)?,
+            component: row.get::<_, i64>(offset + 3)? as usize,
+            computed_at: DateTime::parse_from_rfc3339(&computed_at)?.with_timezone(&Utc),
+        })
+    }
+
+    fn row_to_claim_link(&self, row: &Row) -> Result<ClaimLink> {
+        let link_type_str: String = row.get(3)?;
+        let created_at: String = row.get(4)?;
+
+        Ok(ClaimLink {
+            id: row.get(0)?,
+            source_claim_id: row.get(1)?,
+            target_claim_id: row.get(2)?,
+            link_type: LinkType::from_str(&link_type_str).unwrap_or(LinkType::Related),
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+        })
+    }
+
+    fn row_to_claim_from_offset(&self, row: &Row, offset: usize) -> Result<Claim> {
+        let category_str: String = row.get(offset + 5)?;
+        let confidence_str: String = row.get(offset + 6)?;
+        let created_at: String = row.get(offset + 7)?;
+        let status_str: String = row.get(offset + 8)?;
+
+        Ok(Claim {
+            id: row.get(offset)?,
+            text: row.get(offset + 1)?,
+            video_id: row.get(offset + 2)?,
+            timestamp: row.get(offset + 3)?,
+            source_quote: row.get(offset + 4)?,
+            category: ClaimCategory::from_str(&category_str).unwrap_or(ClaimCategory::Factual),
+            confidence: Confidence::from_str(&confidence_str).unwrap_or(Confidence::Medium),
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+            status: ClaimStatus::from_str(&status_str).unwrap_or(ClaimStatus::Unverified),
+            date_start: row.get(offset + 9)?,
+            date_end: row.get(offset + 10)?,
+        })
+    }
+
+    fn row_to_embedding(&self, row: &Row) -> Result<Embedding> {
+        let source_type_str: String = row.get(1)?;
+        let blob: Vec<u8> = row.get(4)?;
+        let created_at: String = row.get(5)?;
+
+        Ok(Embedding {
+            id: row.get(0)?,
+            source_type: EmbeddingSource::from_str(&source_type_str).unwrap_or(EmbeddingSource::Chunk),
+            source_id: row.get(2)?,
+            model: row.get(3)?,
+            vector: blob_to_vector(&blob)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+        })
+    }
+
+    // Cosine similarity against every live stored vector of the given type and model
+    fn scan_similar(
+        &self,
+        query_vector: &[f32],
+        source_type: Option<EmbeddingSource>,
+        model: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<(Embedding, f32)>> {
+        let mut stmt = self.conn.prepare(&format!(
+            r#"
+            SELECT e.id, e.source_type, e.source_id, e.model, e.vector, e.created_at FROM embeddings e
+            WHERE (?1 IS NULL OR e.source_type = ?1) AND (?2 IS NULL OR e.model = ?2) AND NOT {}
+            "#,
+            ARCHIVED_EMBEDDING
+        ))?;
+        let mut scored: Vec<(Embedding, f32)> = Vec::new();
+        let mut rows = stmt.query(params![source_type.map(|st| st.as_str()), model])?;
+        while let Some(row) = rows.next()? {
+            let emb = self.row_to_embedding(row)?;
+            let score = cosine_similarity(query_vector, &emb.vector);
+            scored.push((emb, score));
+        }
+
+        // Sort by score descending
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        Ok(scored.into_iter().take(limit).collect())
+    }
+
+    // sqlite-vec index: one vec0 table per dimension count, keyed by embeddings.id.
+    // Kept in step by save_embedding; anything written without the extension loaded
+    // (deletes, purges, older builds) is reconciled before each query.
+
+    #[cfg(feature = "sqlite-vec")]
+    fn load_vector_extension(&self) -> Result<()> {
+        let Some(conn) = self.conn.sqlite() else { return Ok(()) };
+        let path = std::env::var("ENGINE_SQLITE_VEC").unwrap_or_else(|_| "vec0".to_string());
+        // SAFETY: loading is re-disabled immediately, and only the configured library is loaded
+        unsafe {
+            conn.load_extension_enable()?;
+            let loaded = conn.load_extension(&path, None::<&str>);
+            conn.load_extension_disable()?;
+            loaded.map_err(|e| anyhow::anyhow!("Failed to load sqlite-vec from {} (set ENGINE_SQLITE_VEC): {}", path, e))?;
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "sqlite-vec")]
+    fn ensure_vector_table(&self, dimensions: usize) -> Result<()> {
+        // Tables from before the model column are dropped; sync_vector_table refills them
+        let table = format!("vec_embeddings_{}", dimensions);
+        let exists: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE name = ?1",
+            params![table],
+            |row| row.get(0),
+        )?;
+        let has_model: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM pragma_table_info(?1) WHERE name = 'model'",
+            params![table],
+            |row| row.get(0),
+        )?;
+        if exists > 0 && has_model == 0 {
+            self.conn.execute_batch(&format!("DROP TABLE {}", table))?;
+        }
+        self.conn.execute_batch(&format!(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS {} USING vec0(embedding float[{}] distance_metric=cosine, source_type text, model text)",
+            table, dimensions
+        ))?;
+        Ok(())
+    }
+
+    #[cfg(feature = "sqlite-vec")]
+    fn sync_vector_table(&self, dimensions: usize) -> Result<()> {
+        self.ensure_vector_table(dimensions)?;
+        let table = format!("vec_embeddings_{}", dimensions);
+        self.conn.execute(
+            &format!("DELETE FROM {0} WHERE rowid NOT IN (SELECT id FROM embeddings WHERE dimensions = ?1)", table),
+            params![dimensions as i64],
+        )?;
+        self.conn.execute(
+            &format!(
+                "INSERT INTO {0} (rowid, embedding, source_type, model)
+                 SELECT id, vector, source_type, model FROM embeddings
+                 WHERE dimensions = ?1 AND id NOT IN (SELECT rowid FROM {0})",
+                table
+            ),
+            params![dimensions as i64],
+        )?;
+        Ok(())
+    }
+
+    fn row_to_embedding_model(&self, row: &Row) -> Result<EmbeddingModel> {
+        let created_at: String = row.get(3)?;
+        Ok(EmbeddingModel {
+            model: row.get(0)?,
+            provider: row.get(1)?,
+            dimensions: row.get::<_, i64>(2)? as usize,
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+        })
+    }
+
+    fn row_to_cyclical_indicator(&self, row: &Row) -> Result<CyclicalIndicator> {
+        let indicator_type_str: String = row.get(3)?;
+        let created_at: String = row.get(8)?;
+
+        Ok(CyclicalIndicator {
+            id: row.get(0)?,
+            video_id: row.get(1)?,
+            claim_id: row.get(2)?,
+            indicator_type: CyclicalType::from_str(&indicator_type_str).unwrap_or(CyclicalType::SocialUnrest),
+            entity: row.get(4)?,
+            era_id: row.get(5)?,
+            description: row.get(6)?,
+            timestamp: row.get(7)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+        })
+    }
+
+    fn row_to_causal_relation(&self, row: &Row) -> Result<CausalRelation> {
+        let loop_type_str: String = row.get(3)?;
+        let strength_str: String = row.get(4)?;
+        let created_at: String = row.get(7)?;
+
+        Ok(CausalRelation {
+            id: row.get(0)?,
+            cause_claim_id: row.get(1)?,
+            effect_claim_id: row.get(2)?,
+            loop_type: LoopType::from_str(&loop_type_str).unwrap_or(LoopType::Linear),
+            strength: RelationStrength::from_str(&strength_str).unwrap_or(RelationStrength::Moderate),
+            video_id: row.get(5)?,
+            notes: row.get(6)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+        })
+    }
+
+    fn row_to_idea_transmission(&self, row: &Row) -> Result<IdeaTransmission> {
+        let transmission_type_str: String = row.get(4)?;
+        let created_at: String = row.get(10)?;
+
+        Ok(IdeaTransmission {
+            id: row.get(0)?,
+            idea: row.get(1)?,
+            source_entity: row.get(2)?,
+            target_entity: row.get(3)?,
+            transmission_type: TransmissionType::from_str(&transmission_type_str).unwrap_or(TransmissionType::Horizontal),
+            era_id: row.get(5)?,
+            region_id: row.get(6)?,
+            video_id: row.get(7)?,
+            claim_id: row.get(8)?,
+            notes: row.get(9)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+        })
+    }
+
+    fn row_to_geopolitical_entity(&self, row: &Row) -> Result<GeopoliticalEntity> {
+        let position_str: String = row.get(3)?;
+        let created_at: String = row.get(5)?;
+
+        Ok(GeopoliticalEntity {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            era_id: row.get(2)?,
+            position: SystemPosition::from_str(&position_str).unwrap_or(SystemPosition::Periphery),
+            notes: row.get(4)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+        })
+    }
+
+    fn row_to_surplus_flow(&self, row: &Row) -> Result<SurplusFlow> {
+        let created_at: String = row.get(8)?;
+
+        Ok(SurplusFlow {
+            id: row.get(0)?,
+            from_entity_id: row.get(1)?,
+            to_entity_id: row.get(2)?,
+            commodity: row.get(3)?,
+            era_id: row.get(4)?,
+            video_id: row.get(5)?,
+            claim_id: row.get(6)?,
+            notes: row.get(7)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+        })
+    }
+
+    fn row_to_temporal_observation(&self, row: &Row) -> Result<TemporalObservation> {
+        let timescale_str: String = row.get(2)?;
+        let created_at: String = row.get(4)?;
+
+        Ok(TemporalObservation {
+            id: row.get(0)?,
+            claim_id: row.get(1)?,
+            timescale: BraudelTimescale::from_str(&timescale_str).unwrap_or(BraudelTimescale::Event),
+            notes: row.get(3)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+        })
+    }
+
+    fn row_to_moc(&self, row: &Row) -> Result<MapOfContent> {
+        let created_at: String = row.get(3)?;
+        let updated_at: String = row.get(4)?;
+
+        Ok(MapOfContent {
+            id: row.get(0)?,
+            title: row.get(1)?,
+            description: row.get(2)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&updated_at)?.with_timezone(&Utc),
+        })
+    }
+
+    fn row_to_research_question(&self, row: &Row) -> Result<ResearchQuestion> {
+        let status_str: String = row.get(2)?;
+        let created_at: String = row.get(5)?;
+        let updated_at: String = row.get(6)?;
+
+        Ok(ResearchQuestion {
+            id: row.get(0)?,
+            question: row.get(1)?,
+            status: QuestionStatus::from_str(&status_str).unwrap_or(QuestionStatus::Active),
+            parent_question_id: row.get(3)?,
+            notes: row.get(4)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&updated_at)?.with_timezone(&Utc),
+        })
+    }
+
+    fn unmapped_claim_clusters(&self, min_size: usize) -> Result<Vec<Vec<i64>>> {
+        let mut adjacency: HashMap<i64, Vec<i64>> = HashMap::new();
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT source_claim_id, target_claim_id FROM claim_links
+            WHERE source_claim_id NOT IN (SELECT claim_id FROM moc_claims)
+              AND target_claim_id NOT IN (SELECT claim_id FROM moc_claims)
             "#
         )?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let (a, b): (i64, i64) = (row.get(0)?, row.get(1)?);
+            adjacency.entry(a).or_default().push(b);
+            adjacency.entry(b).or_default().push(a);
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        let mut nodes: Vec<i64> = adjacency.keys().copied().collect();
+        nodes.sort();
+
+        let mut clusters = Vec::new();
+        for start in nodes {
+            if !seen.insert(start) {
+                continue;
+            }
+            let mut cluster = vec![start];
+            let mut stack = vec![start];
+            while let Some(node) = stack.pop() {
+                for next in adjacency.get(&node).into_iter().flatten() {
+                    if seen.insert(*next) {
+                        cluster.push(*next);
+                        stack.push(*next);
+                    }
+                }
+            }
+            if cluster.len() >= min_size {
+                cluster.sort();
+                clusters.push(cluster);
+            }
+        }
+        Ok(clusters)
+    }
+
+    fn row_to_suggested_question(&self, row: &Row) -> Result<SuggestedQuestion> {
+        let claim_ids_json: String = row.get(3)?;
+        let video_ids_json: String = row.get(4)?;
+        let created_at: String = row.get(6)?;
+        Ok(SuggestedQuestion {
+            id: row.get(0)?,
+            question: row.get(1)?,
+            reason: row.get(2)?,
+            claim_ids: serde_json::from_str(&claim_ids_json)?,
+            video_ids: serde_json::from_str(&video_ids_json)?,
+            status: row.get(5)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+        })
+    }
+
+    fn row_to_detected_pattern(&self, row: &Row) -> Result<DetectedPattern> {
+        let pattern_type_str: String = row.get(1)?;
+        let video_ids_json: String = row.get(3)?;
+        let claim_ids_json: String = row.get(4)?;
+        let detected_at: String = row.get(6)?;
+
+        Ok(DetectedPattern {
+            id: row.get(0)?,
+            pattern_type: PatternType::from_str(&pattern_type_str).unwrap_or(PatternType::RecurringTheme),
+            description: row.get(2)?,
+            video_ids: serde_json::from_str(&video_ids_json)?,
+            claim_ids: serde_json::from_str(&claim_ids_json)?,
+            confidence: row.get(5)?,
+            detected_at: DateTime::parse_from_rfc3339(&detected_at)?.with_timezone(&Utc),
+        })
+    }
+
+    fn record_queue_event(&self, video_id: &str, status: ProcessingStatus, detail: Option<&str>) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO queue_history (video_id, status, detail, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![video_id, status.as_str(), detail, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
 
-        let mut results = Vec::new();
-        let mut rows = stmt.query(params![query])?;
+    fn row_to_queue_item(&self, row: &Row) -> Result<AIProcessingQueue> {
+        let status_str: String = row.get(2)?;
+        let created_str: String = row.get(4)?;
+        let started_str: Option<String> = row.get(5)?;
+        let completed_str: Option<String> = row.get(6)?;
 
-        while let Some(row) = rows.next()? {
-            let video = self.row_to_video(row)?;
-            let snippet: String = row.get(7)?;
-            results.push((video, snippet));
-        }
+        Ok(AIProcessingQueue {
+            id: row.get(0)?,
+            video_id: row.get(1)?,
+            status: ProcessingStatus::from_str(&status_str).unwrap_or(ProcessingStatus::Pending),
+            priority: row.get(3)?,
+            created_at: DateTime::parse_from_rfc3339(&created_str)?.with_timezone(&Utc),
+            started_at: started_str.map(|s| DateTime::parse_from_rfc3339(&s).ok()).flatten().map(|d| d.with_timezone(&Utc)),
+            completed_at: completed_str.map(|s| DateTime::parse_from_rfc3339(&s).ok()).flatten().map(|d| d.with_timezone(&Utc)),
+            error_message: row.get(7)?,
+            claims_extracted: row.get(8)?,
+        })
+    }
 
-        Ok(results)
+    fn row_to_api_token(row: &Row) -> Result<ApiToken> {
+        let scope: String = row.get(3)?;
+        let created_at: String = row.get(4)?;
+        let last_used_at: Option<String> = row.get(5)?;
+        Ok(ApiToken {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            token: String::new(),
+            prefix: row.get(2)?,
+            // Anything unexpected grants the least
+            scope: if scope == TokenScope::Write.as_str() { TokenScope::Write } else { TokenScope::Read },
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+            last_used_at: last_used_at
+                .map(|t| DateTime::parse_from_rfc3339(&t).map(|t| t.with_timezone(&Utc)))
+                .transpose()?,
+        })
     }
 
-    pub fn search_with_timestamps(&self, query: &str) -> Result<Vec<SearchResult>> {
-        // Use weighted search: title (10x), description (5x), transcript (1x)
-        // bm25() returns negative scores, lower is better
-        let mut stmt = self.conn.prepare(
-            r#"
-            SELECT v.id, v.url, v.title, v.channel, v.upload_date, v.description, v.added_at,
-                   t.segments_json,
-                   bm25(search_index, 0.0, 10.0, 5.0, 1.0) as rank
-            FROM search_index
-            JOIN videos v ON v.id = search_index.video_id
-            LEFT JOIN transcripts t ON t.video_id = v.id
-            WHERE search_index MATCH ?1
-            ORDER BY rank
-            "#
-        )?;
+    fn row_to_pending_quote(&self, row: &Row) -> Result<PendingQuote> {
+        let created_str: String = row.get(7)?;
+        Ok(PendingQuote {
+            id: row.get(0)?,
+            video_id: row.get(1)?,
+            text: row.get(2)?,
+            speaker: row.get(3)?,
+            timestamp: row.get(4)?,
+            reason: row.get(5)?,
+            context: row.get(6)?,
+            created_at: DateTime::parse_from_rfc3339(&created_str)?.with_timezone(&Utc),
+        })
+    }
+}
 
-        let query_lower = query.to_lowercase();
-        let mut results = Vec::new();
-        let mut rows = stmt.query(params![query])?;
+impl Storage for Database {
+    fn is_read_only(&self) -> Result<bool> {
+        match self.conn.sqlite() {
+            Some(conn) => Ok(conn.is_readonly(rusqlite::DatabaseName::Main)?),
+            None => Ok(self.conn.query_row("SELECT current_setting('default_transaction_read_only') = 'on'", [], |row| row.get(0))?),
+        }
+    }
 
-        while let Some(row) = rows.next()? {
-            let video = self.row_to_video(row)?;
-            let segments_json: Option<String> = row.get(7)?;
+    fn generation(&self) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        WRITE_GENERATION.load(Ordering::Relaxed).hash(&mut hasher);
 
-            // Find segments containing the query (if transcript exists)
-            let mut matches = Vec::new();
-            if let Some(json) = segments_json {
-                let segments: Vec<TranscriptSegment> = serde_json::from_str(&json)?;
-                for seg in &segments {
-                    if seg.text.to_lowercase().contains(&query_lower) {
-                        matches.push(SegmentMatch {
-                            start_time: seg.start_time,
-                            duration: seg.duration,
-                            text: seg.text.clone(),
-                        });
+        match self.conn.sqlite() {
+            Some(conn) => {
+                if let Some(path) = conn.path().filter(|p| !p.is_empty()) {
+                    for file in [path.to_string(), format!("{}-wal", path)] {
+                        if let Ok(meta) = std::fs::metadata(&file) {
+                            meta.len().hash(&mut hasher);
+                            meta.modified().ok().hash(&mut hasher);
+                        }
                     }
                 }
             }
-
-            // Include video even if no transcript matches (title/description matched)
-            results.push(SearchResult { video, matches });
+            // Triggers bump data_version on every write, from any client
+            None => {
+                if let Ok(version) = self.conn.query_row("SELECT last_value FROM data_version", [], |row| row.get::<_, i64>(0)) {
+                    version.hash(&mut hasher);
+                }
+            }
         }
-
-        Ok(results)
+        hasher.finish()
     }
 
-    // ========================================================================
-    // Unified Fuzzy Search
-    // ========================================================================
+    fn migrate(&self) -> Result<Vec<MigrationStatus>> {
+        let mut applied = Vec::new();
+        if self.conn.dialect() == Dialect::Postgres && !self.has_table("schema_migrations")? {
+            applied = self.create_postgres_schema()?;
+        }
+        self.conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS schema_migrations (
+                version INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                applied_at TEXT NOT NULL
+            );
+            "#,
+        )?;
 
-    /// Unified search across all entity types with fuzzy matching
-    pub fn unified_search(
-        &self,
-        query: &str,
-        types: Option<&[&str]>,
-        video_filter: Option<&str>,
-        limit: usize,
-        fuzzy_threshold: f64,
-    ) -> Result<SearchResponse> {
-        let query_lower = query.to_lowercase();
-        let query_words: Vec<&str> = query_lower.split_whitespace().collect();
-        let mut all_results = Vec::new();
+        for status in self.migration_status()? {
+            if status.applied_at.is_some() {
+                continue;
+            }
+            let (_, _, up) = MIGRATIONS.iter().find(|(v, _, _)| *v == status.version).expect("known migration");
+            let now = Utc::now();
+            let tx = self.conn.unchecked_transaction()?;
+            up(self).map_err(|e| anyhow::anyhow!("Migration {} ({}) failed: {}", status.version, status.name, e))?;
+            tx.execute(
+                "INSERT INTO schema_migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+                params![status.version, status.name, now.to_rfc3339()],
+            )?;
+            tx.commit()?;
+            applied.push(MigrationStatus { applied_at: Some(now), ..status });
+        }
 
-        // Define which types to search
-        let default_types = ["claim", "video", "moc", "source", "scholar",
-            "term", "quote", "evidence", "visual", "location", "question"];
-        let search_types = types.unwrap_or(&default_types);
+        self.seed_default_eras()?;
+        Ok(applied)
+    }
 
-        for search_type in search_types {
-            match *search_type {
-                "claim" => all_results.extend(self.search_claims_fuzzy(&query_lower, &query_words, video_filter, fuzzy_threshold)?),
-                "video" => all_results.extend(self.search_videos_fuzzy(&query_lower, &query_words, fuzzy_threshold)?),
-                "moc" => all_results.extend(self.search_mocs_fuzzy(&query_lower, &query_words, fuzzy_threshold)?),
-                "source" => all_results.extend(self.search_sources_fuzzy(&query_lower, &query_words, fuzzy_threshold)?),
-                "scholar" => all_results.extend(self.search_scholars_fuzzy(&query_lower, &query_words, fuzzy_threshold)?),
-                "term" => all_results.extend(self.search_terms_fuzzy(&query_lower, &query_words, video_filter, fuzzy_threshold)?),
-                "quote" => all_results.extend(self.search_quotes_fuzzy(&query_lower, &query_words, video_filter, fuzzy_threshold)?),
-                "evidence" => all_results.extend(self.search_evidence_fuzzy(&query_lower, &query_words, video_filter, fuzzy_threshold)?),
-                "visual" => all_results.extend(self.search_visuals_fuzzy(&query_lower, &query_words, video_filter, fuzzy_threshold)?),
-                "location" => all_results.extend(self.search_locations_fuzzy(&query_lower, &query_words, fuzzy_threshold)?),
-                "question" => all_results.extend(self.search_questions_fuzzy(&query_lower, &query_words, fuzzy_threshold)?),
-                _ => {}
+    fn migration_status(&self) -> Result<Vec<MigrationStatus>> {
+        let tracked = self.has_table("schema_migrations")?;
+
+        let mut applied: HashMap<i64, String> = HashMap::new();
+        if tracked {
+            let mut stmt = self.conn.prepare("SELECT version, applied_at FROM schema_migrations")?;
+            let mut rows = stmt.query([])?;
+            while let Some(row) = rows.next()? {
+                applied.insert(row.get(0)?, row.get(1)?);
             }
         }
 
-        // Sort by score descending
-        all_results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        MIGRATIONS
+            .iter()
+            .map(|(version, name, _)| {
+                let applied_at = applied
+                    .get(version)
+                    .map(|s| DateTime::parse_from_rfc3339(s).map(|d| d.with_timezone(&Utc)))
+                    .transpose()?;
+                Ok(MigrationStatus { version: *version, name: name.to_string(), applied_at })
+            })
+            .collect()
+    }
 
-        // Build facets
-        let facets = self.build_search_facets(&all_results)?;
+    fn insert_video(&self, video: &Video) -> Result<()> {
+        self.conn.execute(
+            r#"
+            INSERT INTO videos (id, url, title, channel, upload_date, description, added_at, duration, view_count, thumbnail_url)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+            ON CONFLICT(id) DO UPDATE SET
+                url = excluded.url, title = excluded.title, channel = excluded.channel,
+                upload_date = excluded.upload_date, description = excluded.description, added_at = excluded.added_at,
+                duration = excluded.duration, view_count = excluded.view_count, thumbnail_url = excluded.thumbnail_url
+            "#,
+            params![
+                video.id,
+                video.url,
+                video.title,
+                video.channel,
+                video.upload_date.map(|d| d.format("%Y-%m-%d").to_string()),
+                video.description,
+                video.added_at.to_rfc3339(),
+                video.duration,
+                video.view_count,
+                video.thumbnail_url,
+            ],
+        )?;
+        self.update_search_index(&video.id)?;
+        Ok(())
+    }
 
-        let total = all_results.len();
-        all_results.truncate(limit);
+    fn insert_transcript(&self, transcript: &Transcript) -> Result<()> {
+        let segments_json = serde_json::to_string(&transcript.segments)?;
+        let language = normalize_language_tag(&transcript.language);
+        // An upsert fires the FTS update trigger; REPLACE's implicit delete doesn't, leaving stale index rows
+        self.conn.execute(
+            r#"
+            INSERT INTO transcripts (video_id, language, full_text, segments_json)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT(video_id, language) DO UPDATE SET
+                full_text = excluded.full_text, segments_json = excluded.segments_json
+            "#,
+            params![
+                transcript.video_id,
+                language,
+                transcript.full_text,
+                segments_json,
+            ],
+        )?;
+        self.update_search_index(&transcript.video_id)?;
+        Ok(())
+    }
 
-        Ok(SearchResponse {
-            query: query.to_string(),
-            total,
-            results: all_results,
-            facets,
-        })
+    fn update_transcript_segments(&self, video_id: &str, segments: &[TranscriptSegment]) -> Result<bool> {
+        let segments_json = serde_json::to_string(segments)?;
+        let full_text = segments.iter().map(|s| s.text.as_str()).collect::<Vec<_>>().join(" ");
+        let affected = self.conn.execute(
+            "UPDATE transcripts SET segments_json = ?1, full_text = ?2 WHERE video_id = ?3",
+            params![segments_json, full_text, video_id],
+        )?;
+        if affected > 0 {
+            self.update_search_index(video_id)?;
+        }
+        Ok(affected > 0)
     }
 
-    /// Calculate fuzzy match score combining multiple algorithms
-    fn fuzzy_score(&self, query: &str, text: &str, query_words: &[&str]) -> f64 {
-        let text_lower = text.to_lowercase();
+    // Subscription operations
 
-        // Exact substring match bonus
-        if text_lower.contains(query) {
-            return 1.0;
+    fn add_subscription(&self, url: &str, name: Option<&str>) -> Result<Subscription> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO subscriptions (url, name, created_at) VALUES (?1, ?2, ?3)",
+            params![url, name, Utc::now().to_rfc3339()],
+        )?;
+        let mut stmt = self.conn.prepare(
+            "SELECT id, url, name, last_checked, created_at FROM subscriptions WHERE url = ?1"
+        )?;
+        let mut rows = stmt.query(params![url])?;
+        match rows.next()? {
+            Some(row) => self.row_to_subscription(row),
+            None => anyhow::bail!("Failed to add subscription"),
         }
+    }
 
-        // Word-level matching using Jaro-Winkler
-        let text_words: Vec<&str> = text_lower.split_whitespace().collect();
-        let mut word_score = 0.0;
-        let mut matched_words = 0;
+    fn list_subscriptions(&self) -> Result<Vec<Subscription>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, url, name, last_checked, created_at FROM subscriptions ORDER BY id"
+        )?;
+        let mut subscriptions = Vec::new();
+        let mut rows = stmt.query([])?;
 
-        for qword in query_words {
-            let best_match = text_words.iter()
-                .map(|tw| jaro_winkler(qword, tw))
-                .max_by(|a, b| a.partial_cmp(b).unwrap())
-                .unwrap_or(0.0);
-            if best_match > 0.8 {
-                matched_words += 1;
-                word_score += best_match;
-            }
+        while let Some(row) = rows.next()? {
+            subscriptions.push(self.row_to_subscription(row)?);
         }
+        Ok(subscriptions)
+    }
 
-        // Combine word matching with overall similarity
-        let overall = normalized_levenshtein(query, &text_lower);
-        let word_ratio = if !query_words.is_empty() {
-            matched_words as f64 / query_words.len() as f64
-        } else {
-            0.0
-        };
+    fn mark_subscription_checked(&self, id: i64) -> Result<()> {
+        self.conn.execute(
+            "UPDATE subscriptions SET last_checked = ?1 WHERE id = ?2",
+            params![Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(())
+    }
 
-        // Weighted combination: word matching is most important
-        let word_component = if !query_words.is_empty() {
-            word_score / query_words.len() as f64
-        } else {
-            0.0
-        };
-        word_component * 0.6 + overall * 0.2 + word_ratio * 0.2
+    fn delete_subscription(&self, id: i64) -> Result<bool> {
+        let affected = self.conn.execute("DELETE FROM subscriptions WHERE id = ?1", params![id])?;
+        Ok(affected > 0)
     }
 
-    /// Highlight matching words in text
-    fn highlight_match(&self, text: &str, query_words: &[&str]) -> String {
-        let mut result = text.to_string();
-        for word in query_words {
-            // Simple case-insensitive highlighting
-            let lower = result.to_lowercase();
-            if let Some(pos) = lower.find(word) {
-                let end = pos + word.len();
-                let matched = &result[pos..end];
-                result = format!("{}>>>{}<<<{}", &result[..pos], matched, &result[end..]);
+    fn save_archive(&self, archive: &Archive) -> Result<Option<i64>> {
+        if let Some(latest) = self.get_latest_archive(&archive.video_id, &archive.kind)? {
+            if latest.content == archive.content && latest.format == archive.format {
+                return Ok(None);
             }
         }
-        self.truncate_snippet(&result, 200)
+        self.conn.execute(
+            r#"
+            INSERT INTO archives (video_id, kind, format, language, content, fetched_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+            params![
+                archive.video_id,
+                archive.kind,
+                archive.format,
+                archive.language,
+                archive.content,
+                archive.fetched_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(Some(self.conn.last_insert_rowid()))
+    }
+
+    fn get_archives(&self, video_id: &str) -> Result<Vec<Archive>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, video_id, kind, format, language, content, fetched_at FROM archives WHERE video_id = ?1 ORDER BY id DESC"
+        )?;
+        let mut archives = Vec::new();
+        let mut rows = stmt.query(params![video_id])?;
+
+        while let Some(row) = rows.next()? {
+            archives.push(self.row_to_archive(row)?);
+        }
+        Ok(archives)
+    }
+
+    fn get_latest_archive(&self, video_id: &str, kind: &str) -> Result<Option<Archive>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, video_id, kind, format, language, content, fetched_at FROM archives WHERE video_id = ?1 AND kind = ?2 ORDER BY id DESC LIMIT 1"
+        )?;
+        let mut rows = stmt.query(params![video_id, kind])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(self.row_to_archive(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    // Settings
+
+    fn transaction(&self, f: &mut dyn FnMut(&dyn Storage) -> Result<()>) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        f(self)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn get_setting(&self, key: &str) -> Result<Option<String>> {
+        Ok(self.conn.query_row(
+            "SELECT value FROM settings WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        ).optional()?)
+    }
+
+    fn set_setting(&self, key: &str, value: &str) -> Result<()> {
+        self.conn.execute(
+            r#"
+            INSERT INTO settings (key, value, updated_at) VALUES (?1, ?2, ?3)
+            ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at
+            "#,
+            params![key, value, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
     }
 
-    fn truncate_snippet(&self, text: &str, max_len: usize) -> String {
-        if text.len() <= max_len {
-            text.to_string()
-        } else {
-            format!("{}...", &text.chars().take(max_len).collect::<String>())
+    fn delete_setting(&self, key: &str) -> Result<bool> {
+        let affected = self.conn.execute("DELETE FROM settings WHERE key = ?1", params![key])?;
+        Ok(affected > 0)
+    }
+
+    fn ranking_profile(&self) -> Result<RankingProfile> {
+        let mut profile = RankingProfile::default();
+        if let Some((t, d, x)) = self.get_setting("search.weights")?.as_deref().and_then(RankingProfile::parse_weights) {
+            profile.title_weight = t;
+            profile.description_weight = d;
+            profile.transcript_weight = x;
+        }
+        if let Some(boost) = self.get_setting("search.recency_boost")?.and_then(|b| b.parse::<f64>().ok()).filter(|b| b.is_finite()) {
+            profile.recency_boost = boost.max(0.0);
         }
+        Ok(profile)
     }
 
-    /// Search claims with fuzzy matching
-    fn search_claims_fuzzy(
-        &self,
-        query: &str,
-        query_words: &[&str],
-        video_filter: Option<&str>,
-        threshold: f64,
-    ) -> Result<Vec<UnifiedSearchResult>> {
-        let sql = if video_filter.is_some() {
-            "SELECT id, text, video_id, timestamp, source_quote, category FROM claims WHERE video_id = ?1"
-        } else {
-            "SELECT id, text, video_id, timestamp, source_quote, category FROM claims"
-        };
+    // Attachments
 
-        let mut stmt = self.conn.prepare(sql)?;
-        let mut rows = if let Some(vid) = video_filter {
-            stmt.query(params![vid])?
-        } else {
-            stmt.query([])?
-        };
+    fn add_attachment(&self, attachment: &Attachment, data: Option<&[u8]>) -> Result<i64> {
+        self.conn.execute(
+            r#"
+            INSERT INTO attachments (video_id, claim_id, file_name, mime_type, caption, size, path, data, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            "#,
+            params![
+                attachment.video_id,
+                attachment.claim_id,
+                attachment.file_name,
+                attachment.mime_type,
+                attachment.caption,
+                attachment.size,
+                attachment.path,
+                data,
+                attachment.created_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
 
-        let mut results = Vec::new();
-        while let Some(row) = rows.next()? {
-            let id: i64 = row.get(0)?;
-            let text: String = row.get(1)?;
-            let video_id: String = row.get(2)?;
-            let timestamp: Option<f64> = row.get(3)?;
-            let source_quote: String = row.get(4)?;
-            let category: String = row.get(5)?;
+    fn get_attachment(&self, id: i64) -> Result<Option<Attachment>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, video_id, claim_id, file_name, mime_type, caption, size, path, created_at FROM attachments WHERE id = ?1"
+        )?;
+        let mut rows = stmt.query(params![id])?;
 
-            // Score against both text and source_quote
-            let text_score = self.fuzzy_score(query, &text, query_words);
-            let quote_score = self.fuzzy_score(query, &source_quote, query_words);
-            let score = text_score.max(quote_score);
+        if let Some(row) = rows.next()? {
+            Ok(Some(self.row_to_attachment(row)?))
+        } else {
+            Ok(None)
+        }
+    }
 
-            if score >= threshold {
-                results.push(UnifiedSearchResult {
-                    result_type: SearchResultType::Claim,
-                    id,
-                    title: self.truncate_snippet(&text, 100),
-                    subtitle: Some(format!("{} | {}", category, video_id)),
-                    snippet: Some(self.highlight_match(&source_quote, query_words)),
-                    score,
-                    video_id: Some(video_id),
-                    timestamp,
-                    location: None,
-                });
-            }
+    fn get_video_attachments(&self, video_id: &str) -> Result<Vec<Attachment>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, video_id, claim_id, file_name, mime_type, caption, size, path, created_at FROM attachments WHERE video_id = ?1 ORDER BY id"
+        )?;
+        let mut attachments = Vec::new();
+        let mut rows = stmt.query(params![video_id])?;
+
+        while let Some(row) = rows.next()? {
+            attachments.push(self.row_to_attachment(row)?);
         }
-        Ok(results)
+        Ok(attachments)
     }
 
-    /// Search videos with fuzzy matching
-    fn search_videos_fuzzy(
-        &self,
-        query: &str,
-        query_words: &[&str],
-        threshold: f64,
-    ) -> Result<Vec<UnifiedSearchResult>> {
+    fn get_claim_attachments(&self, claim_id: i64) -> Result<Vec<Attachment>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, title, channel, description FROM videos"
+            "SELECT id, video_id, claim_id, file_name, mime_type, caption, size, path, created_at FROM attachments WHERE claim_id = ?1 ORDER BY id"
         )?;
-        let mut rows = stmt.query([])?;
-        let mut results = Vec::new();
+        let mut attachments = Vec::new();
+        let mut rows = stmt.query(params![claim_id])?;
 
         while let Some(row) = rows.next()? {
-            let id: String = row.get(0)?;
-            let title: String = row.get(1)?;
-            let channel: Option<String> = row.get(2)?;
-            let description: Option<String> = row.get(3)?;
+            attachments.push(self.row_to_attachment(row)?);
+        }
+        Ok(attachments)
+    }
 
-            let title_score = self.fuzzy_score(query, &title, query_words);
-            let desc_score = description.as_ref()
-                .map(|d| self.fuzzy_score(query, d, query_words) * 0.8)
-                .unwrap_or(0.0);
-            let score = title_score.max(desc_score);
+    fn read_attachment(&self, id: i64) -> Result<Option<Vec<u8>>> {
+        let row: Option<(Option<Vec<u8>>, Option<String>)> = self.conn.query_row(
+            "SELECT data, path FROM attachments WHERE id = ?1",
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).optional()?;
 
-            if score >= threshold {
-                results.push(UnifiedSearchResult {
-                    result_type: SearchResultType::Video,
-                    id: 0, // Videos use string IDs, store in video_id
-                    title,
-                    subtitle: channel,
-                    snippet: description.map(|d| self.truncate_snippet(&d, 100)),
-                    score,
-                    video_id: Some(id),
-                    timestamp: None,
-                    location: None,
-                });
-            }
+        match row {
+            None => Ok(None),
+            Some((Some(data), _)) => Ok(Some(data)),
+            Some((None, Some(path))) => Ok(Some(std::fs::read(&path).map_err(|e| anyhow::anyhow!("{}: {}", path, e))?)),
+            Some((None, None)) => Ok(None),
         }
-        Ok(results)
     }
 
-    /// Search MOCs with fuzzy matching
-    fn search_mocs_fuzzy(
-        &self,
-        query: &str,
-        query_words: &[&str],
-        threshold: f64,
-    ) -> Result<Vec<UnifiedSearchResult>> {
+    fn delete_attachment(&self, id: i64) -> Result<bool> {
+        let affected = self.conn.execute("DELETE FROM attachments WHERE id = ?1", params![id])?;
+        Ok(affected > 0)
+    }
+
+    fn save_chapters(&self, video_id: &str, chapters: &[Chapter]) -> Result<()> {
+        self.conn.execute("DELETE FROM chapters WHERE video_id = ?1", params![video_id])?;
+        for chapter in chapters {
+            self.conn.execute(
+                "INSERT OR IGNORE INTO chapters (video_id, start_time, title) VALUES (?1, ?2, ?3)",
+                params![video_id, chapter.start_time, chapter.title],
+            )?;
+        }
+        Ok(())
+    }
+
+    fn get_chapters(&self, video_id: &str) -> Result<Vec<Chapter>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, title, description FROM mocs"
+            "SELECT video_id, start_time, title FROM chapters WHERE video_id = ?1 ORDER BY start_time"
         )?;
-        let mut rows = stmt.query([])?;
-        let mut results = Vec::new();
+        let mut chapters: Vec<Chapter> = Vec::new();
+        let mut rows = stmt.query(params![video_id])?;
 
         while let Some(row) = rows.next()? {
-            let id: i64 = row.get(0)?;
-            let title: String = row.get(1)?;
-            let description: Option<String> = row.get(2)?;
+            let start_time: f64 = row.get(1)?;
+            if let Some(prev) = chapters.last_mut() {
+                prev.end_time = Some(start_time);
+            }
+            chapters.push(Chapter {
+                video_id: row.get(0)?,
+                start_time,
+                end_time: None,
+                title: row.get(2)?,
+            });
+        }
+        Ok(chapters)
+    }
 
-            let title_score = self.fuzzy_score(query, &title, query_words);
-            let desc_score = description.as_ref()
-                .map(|d| self.fuzzy_score(query, d, query_words) * 0.7)
-                .unwrap_or(0.0);
-            let score = title_score.max(desc_score);
+    fn rebuild_search_index(&self) -> Result<usize> {
+        self.rebuild_text_index()?;
 
-            if score >= threshold {
-                results.push(UnifiedSearchResult {
-                    result_type: SearchResultType::Moc,
-                    id,
-                    title,
-                    subtitle: None,
-                    snippet: description.map(|d| self.truncate_snippet(&d, 100)),
-                    score,
-                    video_id: None,
-                    timestamp: None,
-                    location: None,
-                });
-            }
+        // Clear existing index
+        self.conn.execute("DELETE FROM search_index", [])?;
+
+        // Get all videos
+        let videos = self.list_videos()?;
+        let count = videos.len();
+
+        for video in videos {
+            let transcript_text = self.get_transcript(&video.id)?
+                .map(|t| t.full_text)
+                .unwrap_or_default();
+
+            self.conn.execute(
+                "INSERT INTO search_index (video_id, title, description, transcript) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    video.id,
+                    video.title,
+                    video.description.unwrap_or_default(),
+                    transcript_text,
+                ],
+            )?;
         }
-        Ok(results)
+
+        Ok(count)
     }
 
-    /// Search sources with fuzzy matching
-    fn search_sources_fuzzy(
+    fn set_audit_source(&self, source: &str) -> Result<()> {
+        if self.is_read_only()? {
+            return Ok(());
+        }
+        match self.conn.dialect() {
+            Dialect::Sqlite => self.conn.execute("UPDATE temp.audit_context SET source = ?1", params![source])?,
+            // Read by the engine_audit trigger function
+            Dialect::Postgres => self.conn.query_row("SELECT set_config('engine.audit_source', ?1, false)", params![source], |_| Ok(0))?,
+        };
+        Ok(())
+    }
+
+    fn watermark(&self, tables: &[&str]) -> Result<u64> {
+        const TIMESTAMPED: &[(&str, &str)] = &[("claim_metrics", "computed_at"), ("claim_access", "last_accessed")];
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        for table in tables {
+            let mark: (i64, Option<String>) = match TIMESTAMPED.iter().find(|(t, _)| t == table) {
+                Some((_, column)) => self.conn.query_row(
+                    &format!("SELECT COUNT(*), MAX({}) FROM {}", column, table),
+                    [],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )?,
+                None => self.conn.query_row(
+                    "SELECT COALESCE(MAX(id), 0), NULL FROM audit_log WHERE entity_type = ?1",
+                    params![table],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )?,
+            };
+            (table, mark).hash(&mut hasher);
+        }
+        Ok(hasher.finish())
+    }
+
+    fn list_audit_log(
         &self,
-        query: &str,
-        query_words: &[&str],
-        threshold: f64,
-    ) -> Result<Vec<UnifiedSearchResult>> {
+        entity_type: Option<&str>,
+        entity_id: Option<&str>,
+        source: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<AuditEntry>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, title, author, source_type, notes FROM sources"
+            r#"
+            SELECT id, entity_type, entity_id, action, summary, source, created_at
+            FROM audit_log
+            WHERE (?1 IS NULL OR entity_type = ?1)
+              AND (?2 IS NULL OR entity_id = ?2)
+              AND (?3 IS NULL OR source = ?3)
+            ORDER BY id DESC
+            LIMIT ?4
+            "#
         )?;
-        let mut rows = stmt.query([])?;
-        let mut results = Vec::new();
-
+        let mut entries = Vec::new();
+        let mut rows = stmt.query(params![entity_type, entity_id, source, limit as i64])?;
         while let Some(row) = rows.next()? {
-            let id: i64 = row.get(0)?;
-            let title: String = row.get(1)?;
-            let author: Option<String> = row.get(2)?;
-            let source_type: String = row.get(3)?;
-            let notes: Option<String> = row.get(4)?;
+            let created_at: String = row.get(6)?;
+            entries.push(AuditEntry {
+                id: row.get(0)?,
+                entity_type: row.get(1)?,
+                entity_id: row.get(2)?,
+                action: row.get(3)?,
+                summary: row.get(4)?,
+                source: row.get(5)?,
+                created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+            });
+        }
+        Ok(entries)
+    }
 
-            let title_score = self.fuzzy_score(query, &title, query_words);
-            let author_score = author.as_ref()
-                .map(|a| self.fuzzy_score(query, a, query_words) * 0.8)
-                .unwrap_or(0.0);
-            let notes_score = notes.as_ref()
-                .map(|n| self.fuzzy_score(query, n, query_words) * 0.6)
-                .unwrap_or(0.0);
-            let score = title_score.max(author_score).max(notes_score);
+    // Integrity checks (engine doctor)
 
-            if score >= threshold {
-                results.push(UnifiedSearchResult {
-                    result_type: SearchResultType::Source,
-                    id,
-                    title,
-                    subtitle: Some(format!("{}{}", source_type, author.map(|a| format!(" by {}", a)).unwrap_or_default())),
-                    snippet: notes.map(|n| self.truncate_snippet(&n, 100)),
-                    score,
-                    video_id: None,
-                    timestamp: None,
-                    location: None,
+    fn check_integrity(&self) -> Result<Vec<IntegrityIssue>> {
+        let mut issues = Vec::new();
+
+        if self.conn.dialect() == Dialect::Sqlite {
+            let mut stmt = self.conn.prepare("PRAGMA integrity_check(20)")?;
+            let messages = stmt.query_map([], |row| row.get::<_, String>(0))?.collect::<sql::Result<Vec<_>>>()?;
+            for message in messages.into_iter().filter(|m| m != "ok") {
+                issues.push(IntegrityIssue {
+                    check: "integrity".to_string(),
+                    description: message,
+                    count: 1,
+                    fixable: false,
                 });
             }
         }
-        Ok(results)
-    }
 
-    /// Search scholars with fuzzy matching
-    fn search_scholars_fuzzy(
-        &self,
-        query: &str,
-        query_words: &[&str],
-        threshold: f64,
-    ) -> Result<Vec<UnifiedSearchResult>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, name, field, contribution FROM scholars"
-        )?;
-        let mut rows = stmt.query([])?;
-        let mut results = Vec::new();
+        let mut orphans: Vec<(String, String, String, i64)> = Vec::new();
+        for r in self.dangling_references()? {
+            match orphans.iter_mut().find(|(t, p, c, _)| *t == r.table && *p == r.parent && *c == r.column) {
+                Some((_, _, _, n)) => *n += 1,
+                None => orphans.push((r.table, r.parent, r.column, 1)),
+            }
+        }
+        for (table, parent, column, count) in orphans {
+            issues.push(IntegrityIssue {
+                check: "orphan".to_string(),
+                description: format!("{}.{} points at missing {} rows", table, column, parent),
+                count,
+                fixable: true,
+            });
+        }
 
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT source_type, COUNT(*) FROM embeddings e WHERE {} GROUP BY source_type",
+            ORPHANED_EMBEDDING
+        ))?;
+        let mut rows = stmt.query([])?;
         while let Some(row) = rows.next()? {
-            let id: i64 = row.get(0)?;
-            let name: String = row.get(1)?;
-            let field: Option<String> = row.get(2)?;
-            let contribution: Option<String> = row.get(3)?;
+            let source_type: String = row.get(0)?;
+            issues.push(IntegrityIssue {
+                check: "embedding".to_string(),
+                description: format!("{} embeddings whose source no longer exists", source_type),
+                count: row.get(1)?,
+                fixable: true,
+            });
+        }
 
-            let name_score = self.fuzzy_score(query, &name, query_words);
-            let contrib_score = contribution.as_ref()
-                .map(|c| self.fuzzy_score(query, c, query_words) * 0.7)
-                .unwrap_or(0.0);
-            let score = name_score.max(contrib_score);
+        let count = |sql: &str| -> Result<i64> { Ok(self.conn.query_row(sql, [], |row| row.get(0))?) };
+        let stale = count("SELECT COUNT(*) FROM search_index WHERE video_id NOT IN (SELECT id FROM videos)")?;
+        let missing = count("SELECT COUNT(*) FROM videos WHERE id NOT IN (SELECT video_id FROM search_index)")?;
+        if stale > 0 {
+            issues.push(IntegrityIssue {
+                check: "fts".to_string(),
+                description: "search index entries for deleted videos".to_string(),
+                count: stale,
+                fixable: true,
+            });
+        }
+        if missing > 0 {
+            issues.push(IntegrityIssue {
+                check: "fts".to_string(),
+                description: "videos missing from the search index".to_string(),
+                count: missing,
+                fixable: true,
+            });
+        }
 
-            if score >= threshold {
-                results.push(UnifiedSearchResult {
-                    result_type: SearchResultType::Scholar,
-                    id,
-                    title: name,
-                    subtitle: field,
-                    snippet: contribution,
-                    score,
-                    video_id: None,
-                    timestamp: None,
-                    location: None,
+        // Checking an FTS5 index against its content table goes through an INSERT
+        if self.conn.dialect() == Dialect::Sqlite && !self.is_read_only()? {
+            if let Err(e) = self.conn.execute("INSERT INTO transcript_fts(transcript_fts, rank) VALUES('integrity-check', 1)", []) {
+                issues.push(IntegrityIssue {
+                    check: "fts".to_string(),
+                    description: format!("transcript index doesn't match transcripts ({})", e),
+                    count: 1,
+                    fixable: true,
                 });
             }
         }
-        Ok(results)
+
+        Ok(issues)
     }
 
-    /// Search terms with fuzzy matching
-    fn search_terms_fuzzy(
-        &self,
-        query: &str,
-        query_words: &[&str],
-        video_filter: Option<&str>,
-        threshold: f64,
-    ) -> Result<Vec<UnifiedSearchResult>> {
-        let sql = if video_filter.is_some() {
-            "SELECT id, term, definition, domain, video_id, timestamp FROM terms WHERE video_id = ?1"
-        } else {
-            "SELECT id, term, definition, domain, video_id, timestamp FROM terms"
-        };
+    fn repair_integrity(&self) -> Result<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut repaired = 0;
 
-        let mut stmt = self.conn.prepare(sql)?;
-        let mut rows = if let Some(vid) = video_filter {
-            stmt.query(params![vid])?
-        } else {
-            stmt.query([])?
-        };
-        let mut results = Vec::new();
+        // Deleting a row can strand rows that pointed at it, so repeat until clean
+        for _ in 0..10 {
+            let dangling = self.dangling_references()?;
+            if dangling.is_empty() {
+                break;
+            }
+            for r in dangling {
+                if !r.required {
+                    repaired += self.conn.execute(&format!("UPDATE \"{}\" SET \"{}\" = NULL WHERE rowid = ?1", r.table, r.column), params![r.rowid])?;
+                    continue;
+                }
+                if self.has_column(&r.table, "id")? {
+                    // May already be gone as a dependent of an earlier row in this pass
+                    let id: Option<rusqlite::types::Value> = self.conn.query_row(
+                        &format!("SELECT id FROM \"{}\" WHERE rowid = ?1", r.table),
+                        params![r.rowid],
+                        |row| row.get(0),
+                    ).optional()?;
+                    if let Some(id) = id {
+                        self.delete_dependents(&r.table, &id)?;
+                    }
+                }
+                repaired += self.conn.execute(&format!("DELETE FROM \"{}\" WHERE rowid = ?1", r.table), params![r.rowid])?;
+            }
+        }
 
-        while let Some(row) = rows.next()? {
-            let id: i64 = row.get(0)?;
-            let term: String = row.get(1)?;
-            let definition: String = row.get(2)?;
-            let domain: Option<String> = row.get(3)?;
-            let video_id: Option<String> = row.get(4)?;
-            let timestamp: Option<f64> = row.get(5)?;
+        repaired += self.conn.execute(&format!("DELETE FROM embeddings WHERE id IN (SELECT id FROM embeddings e WHERE {})", ORPHANED_EMBEDDING), [])?;
+        if self.conn.dialect() == Dialect::Sqlite {
+            self.conn.execute("INSERT INTO transcript_fts(transcript_fts) VALUES('rebuild')", [])?;
+        }
+        tx.commit()?;
+        self.rebuild_search_index()?;
+        Ok(repaired)
+    }
 
-            let term_score = self.fuzzy_score(query, &term, query_words);
-            let def_score = self.fuzzy_score(query, &definition, query_words) * 0.8;
-            let score = term_score.max(def_score);
+    // Full export/import
 
-            if score >= threshold {
-                results.push(UnifiedSearchResult {
-                    result_type: SearchResultType::Term,
-                    id,
-                    title: term,
-                    subtitle: domain,
-                    snippet: Some(self.truncate_snippet(&definition, 150)),
-                    score,
-                    video_id,
-                    timestamp,
-                    location: None,
-                });
-            }
-        }
-        Ok(results)
+    fn schema_version(&self) -> Result<i64> {
+        let version: Option<i64> = self.conn.query_row("SELECT MAX(version) FROM schema_migrations", [], |row| row.get(0))?;
+        Ok(version.unwrap_or(0))
     }
 
-    /// Search quotes with fuzzy matching
-    fn search_quotes_fuzzy(
-        &self,
-        query: &str,
-        query_words: &[&str],
-        video_filter: Option<&str>,
-        threshold: f64,
-    ) -> Result<Vec<UnifiedSearchResult>> {
-        let sql = if video_filter.is_some() {
-            "SELECT id, text, speaker, video_id, timestamp, context FROM quotes WHERE video_id = ?1"
-        } else {
-            "SELECT id, text, speaker, video_id, timestamp, context FROM quotes"
+    fn size_bytes(&self) -> Result<i64> {
+        let sql = match self.conn.dialect() {
+            Dialect::Sqlite => "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+            Dialect::Postgres => "SELECT pg_database_size(current_database())",
         };
+        Ok(self.conn.query_row(sql, [], |row| row.get(0))?)
+    }
 
-        let mut stmt = self.conn.prepare(sql)?;
-        let mut rows = if let Some(vid) = video_filter {
-            stmt.query(params![vid])?
-        } else {
-            stmt.query([])?
-        };
-        let mut results = Vec::new();
+    fn entity_counts(&self) -> Result<Vec<(&'static str, i64)>> {
+        const TABLES: &[&str] = &[
+            "videos", "transcripts", "claims", "claim_links", "mocs", "research_questions", "detected_patterns",
+            "notes", "collections", "locations", "sources", "scholars", "terms", "visuals", "evidence", "quotes",
+            "cyclical_indicators", "causal_relations", "idea_transmissions", "geopolitical_entities",
+            "surplus_flows", "temporal_observations", "embeddings",
+        ];
+        TABLES
+            .iter()
+            .map(|table| {
+                let count = self.conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0))?;
+                Ok((*table, count))
+            })
+            .collect()
+    }
 
+    fn exportable_tables(&self) -> Result<Vec<String>> {
+        if self.conn.dialect() == Dialect::Postgres {
+            let mut stmt = self.conn.prepare(
+                "SELECT relname FROM pg_class
+                 WHERE relnamespace = CAST(current_schema() AS regnamespace) AND relkind = 'r'
+                   AND relname NOT IN ('schema_migrations', 'search_index', 'text_index')
+                 ORDER BY oid",
+            )?;
+            let tables = stmt.query_map([], |row| row.get(0))?.collect::<sql::Result<Vec<String>>>()?;
+            return Ok(tables);
+        }
+        let mut stmt = self.conn.prepare(
+            "SELECT name, sql FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY rowid"
+        )?;
+        let mut tables: Vec<(String, String)> = Vec::new();
+        let mut rows = stmt.query([])?;
         while let Some(row) = rows.next()? {
-            let id: i64 = row.get(0)?;
-            let text: String = row.get(1)?;
-            let speaker: Option<String> = row.get(2)?;
-            let video_id: String = row.get(3)?;
-            let timestamp: Option<f64> = row.get(4)?;
-            let context: Option<String> = row.get(5)?;
+            tables.push((row.get(0)?, row.get::<_, Option<String>>(1)?.unwrap_or_default()));
+        }
 
-            let text_score = self.fuzzy_score(query, &text, query_words);
-            let speaker_score = speaker.as_ref()
-                .map(|s| self.fuzzy_score(query, s, query_words) * 0.9)
-                .unwrap_or(0.0);
-            let score = text_score.max(speaker_score);
+        let virtual_tables: Vec<String> = tables
+            .iter()
+            .filter(|(_, sql)| sql.starts_with("CREATE VIRTUAL TABLE"))
+            .map(|(name, _)| name.clone())
+            .collect();
 
-            if score >= threshold {
-                results.push(UnifiedSearchResult {
-                    result_type: SearchResultType::Quote,
-                    id,
-                    title: self.truncate_snippet(&text, 100),
-                    subtitle: speaker.map(|s| format!("— {}", s)),
-                    snippet: context,
-                    score,
-                    video_id: Some(video_id),
-                    timestamp,
-                    location: None,
-                });
+        Ok(tables
+            .into_iter()
+            .map(|(name, _)| name)
+            .filter(|name| name != "schema_migrations")
+            .filter(|name| !virtual_tables.iter().any(|v| name == v || name.starts_with(&format!("{}_", v))))
+            .collect())
+    }
+
+    fn export_table_rows(
+        &self,
+        table: &str,
+        emit: &mut dyn FnMut(serde_json::Map<String, serde_json::Value>) -> Result<()>,
+    ) -> Result<usize> {
+        use rusqlite::types::ValueRef;
+        use serde_json::Value;
+
+        let columns = self.table_columns(table)?;
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM \"{}\"",
+            columns.iter().map(|c| format!("\"{}\"", c)).collect::<Vec<_>>().join(", "),
+            table
+        ))?;
+        let mut count = 0;
+        let mut rows = stmt.query([])?;
+
+        while let Some(row) = rows.next()? {
+            let mut record = serde_json::Map::new();
+            for (i, column) in columns.iter().enumerate() {
+                let value = match row.get_ref(i)? {
+                    ValueRef::Null => Value::Null,
+                    ValueRef::Integer(n) => Value::from(n),
+                    ValueRef::Real(f) => Value::from(f),
+                    ValueRef::Text(t) => Value::from(String::from_utf8_lossy(t).into_owned()),
+                    ValueRef::Blob(b) => {
+                        let hex: String = b.iter().map(|byte| format!("{:02x}", byte)).collect();
+                        serde_json::json!({ "$blob": hex })
+                    }
+                };
+                record.insert(column.clone(), value);
             }
+            emit(record)?;
+            count += 1;
         }
-        Ok(results)
+
+        Ok(count)
     }
 
-    /// Search evidence with fuzzy matching
-    fn search_evidence_fuzzy(
+    fn import_table_rows(
         &self,
-        query: &str,
-        query_words: &[&str],
-        video_filter: Option<&str>,
-        threshold: f64,
-    ) -> Result<Vec<UnifiedSearchResult>> {
-        let sql = if video_filter.is_some() {
-            "SELECT id, video_id, description, evidence_type, timestamp FROM evidence WHERE video_id = ?1"
-        } else {
-            "SELECT id, video_id, description, evidence_type, timestamp FROM evidence"
-        };
-
-        let mut stmt = self.conn.prepare(sql)?;
-        let mut rows = if let Some(vid) = video_filter {
-            stmt.query(params![vid])?
-        } else {
-            stmt.query([])?
+        records: &mut dyn Iterator<Item = Result<(String, serde_json::Map<String, serde_json::Value>)>>,
+    ) -> Result<Vec<(String, usize)>> {
+        use rusqlite::types::Value as SqlValue;
+        use serde_json::Value;
+
+        let mut columns_by_table: HashMap<String, Vec<String>> = HashMap::new();
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        // Tables load one at a time and rows replace existing ones, so references can't hold mid-import
+        let foreign_keys = match self.conn.sqlite() {
+            Some(conn) => {
+                let enabled: bool = conn.query_row("PRAGMA foreign_keys", [], |row| row.get(0))?;
+                conn.pragma_update(None, "foreign_keys", false)?;
+                enabled
+            }
+            None => false,
         };
-        let mut results = Vec::new();
+        let tx = self.conn.unchecked_transaction()?;
+        let mut identity_tables = HashSet::new();
+        if self.conn.dialect() == Dialect::Postgres {
+            // Every foreign key is deferrable, so they're checked once at commit
+            tx.execute_batch("SET CONSTRAINTS ALL DEFERRED")?;
+            identity_tables = tx
+                .prepare(
+                    "SELECT table_name FROM information_schema.columns
+                     WHERE table_schema = current_schema() AND column_name = 'id' AND is_identity = 'YES'",
+                )?
+                .query_map([], |row| row.get::<_, String>(0))?
+                .collect::<sql::Result<_>>()?;
+        }
+
+        for record in records {
+            let (table, mut row) = record?;
+            // Exports from before embeddings were stored as BLOBs carry vector_json instead
+            if table == "embeddings" && !row.contains_key("vector") {
+                if let Some(Value::String(json)) = row.get("vector_json") {
+                    let vector: Vec<f32> = serde_json::from_str(json)?;
+                    let hex: String = vector_to_blob(&vector).iter().map(|b| format!("{:02x}", b)).collect();
+                    row.insert("vector".to_string(), serde_json::json!({ "$blob": hex }));
+                }
+            }
 
-        while let Some(row) = rows.next()? {
-            let id: i64 = row.get(0)?;
-            let video_id: String = row.get(1)?;
-            let description: String = row.get(2)?;
-            let evidence_type: String = row.get(3)?;
-            let timestamp: Option<f64> = row.get(4)?;
+            if !columns_by_table.contains_key(&table) {
+                columns_by_table.insert(table.clone(), self.table_columns(&table)?);
+            }
+            let known = &columns_by_table[&table];
 
-            let score = self.fuzzy_score(query, &description, query_words);
+            let mut names = Vec::new();
+            let mut values = Vec::new();
+            for (column, value) in &row {
+                if !known.contains(column) {
+                    continue;
+                }
+                let value = match value {
+                    Value::Null => SqlValue::Null,
+                    Value::Bool(b) => SqlValue::Integer(*b as i64),
+                    Value::Number(n) => match n.as_i64() {
+                        Some(i) => SqlValue::Integer(i),
+                        None => SqlValue::Real(n.as_f64().unwrap_or_default()),
+                    },
+                    Value::String(s) => SqlValue::Text(s.clone()),
+                    Value::Object(o) if o.contains_key("$blob") => {
+                        // Slicing by byte pairs needs ASCII, and a trailing odd digit would be dropped
+                        let Some(hex) = o["$blob"].as_str().filter(|h| h.is_ascii() && h.len() % 2 == 0) else {
+                            anyhow::bail!("{}.{}: $blob is not an even-length hex string", table, column);
+                        };
+                        let bytes = (0..hex.len() / 2)
+                            .map(|i| u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16))
+                            .collect::<std::result::Result<Vec<u8>, _>>()?;
+                        SqlValue::Blob(bytes)
+                    }
+                    other => SqlValue::Text(other.to_string()),
+                };
+                names.push(format!("\"{}\"", column));
+                values.push(value);
+            }
 
-            if score >= threshold {
-                results.push(UnifiedSearchResult {
-                    result_type: SearchResultType::Evidence,
-                    id,
-                    title: self.truncate_snippet(&description, 100),
-                    subtitle: Some(evidence_type),
-                    snippet: None,
-                    score,
-                    video_id: Some(video_id),
-                    timestamp,
-                    location: None,
-                });
+            let label = if names.is_empty() { "(skipped)".to_string() } else { table.clone() };
+            if !names.is_empty() {
+                let placeholders = (1..=values.len()).map(|i| format!("?{}", i)).collect::<Vec<_>>().join(", ");
+                tx.execute(
+                    &format!("INSERT OR REPLACE INTO \"{}\" ({}) VALUES ({})", table, names.join(", "), placeholders),
+                    params_from_iter(values),
+                )?;
+                // Rows come in with their ids; move the identity past each one, as AUTOINCREMENT
+                // does, so rows written by triggers mid-import (audit_log) don't collide
+                if let (true, Some(id)) = (identity_tables.contains(&table), row.get("id").and_then(Value::as_i64)) {
+                    tx.query_row(
+                        &format!(
+                            "SELECT setval(CAST(seq AS regclass), ?1) FROM pg_get_serial_sequence('\"{}\"', 'id') seq
+                             WHERE ?1 >= COALESCE(pg_sequence_last_value(CAST(seq AS regclass)), 0)",
+                            table
+                        ),
+                        params![id],
+                        |_| Ok(()),
+                    )
+                    .optional()?;
+                }
+            }
+            match counts.iter_mut().find(|(t, _)| *t == label) {
+                Some((_, n)) => *n += 1,
+                None => counts.push((label, 1)),
             }
         }
-        Ok(results)
+
+        if self.conn.dialect() == Dialect::Sqlite {
+            tx.execute("INSERT INTO transcript_fts(transcript_fts) VALUES('rebuild')", [])?;
+        }
+        tx.commit()?;
+        if let Some(conn) = self.conn.sqlite() {
+            conn.pragma_update(None, "foreign_keys", foreign_keys)?;
+        }
+        self.rebuild_search_index()?;
+        Ok(counts)
     }
 
-    /// Search visuals with fuzzy matching
-    fn search_visuals_fuzzy(
-        &self,
-        query: &str,
-        query_words: &[&str],
-        video_filter: Option<&str>,
-        threshold: f64,
-    ) -> Result<Vec<UnifiedSearchResult>> {
-        let sql = if video_filter.is_some() {
-            "SELECT id, video_id, description, visual_type, timestamp, significance FROM visuals WHERE video_id = ?1"
+    fn get_video(&self, id: &str) -> Result<Option<Video>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, url, title, channel, upload_date, description, added_at, duration, view_count, thumbnail_url FROM videos WHERE id = ?1"
+        )?;
+
+        let mut rows = stmt.query(params![id])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(self.row_to_video(row)?))
         } else {
-            "SELECT id, video_id, description, visual_type, timestamp, significance FROM visuals"
-        };
+            Ok(None)
+        }
+    }
 
-        let mut stmt = self.conn.prepare(sql)?;
-        let mut rows = if let Some(vid) = video_filter {
-            stmt.query(params![vid])?
+    fn get_transcript(&self, video_id: &str) -> Result<Option<Transcript>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT video_id, language, full_text, segments_json FROM transcripts WHERE video_id = ?1"
+        )?;
+
+        let mut rows = stmt.query(params![video_id])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(self.row_to_transcript(row)?))
         } else {
-            stmt.query([])?
-        };
-        let mut results = Vec::new();
+            Ok(None)
+        }
+    }
+
+    fn list_videos(&self) -> Result<Vec<Video>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, url, title, channel, upload_date, description, added_at, duration, view_count, thumbnail_url FROM videos WHERE archived_at IS NULL ORDER BY added_at DESC"
+        )?;
+
+        let mut videos = Vec::new();
+        let mut rows = stmt.query([])?;
 
         while let Some(row) = rows.next()? {
-            let id: i64 = row.get(0)?;
-            let video_id: String = row.get(1)?;
-            let description: String = row.get(2)?;
-            let visual_type: String = row.get(3)?;
-            let timestamp: f64 = row.get(4)?;
-            let significance: Option<String> = row.get(5)?;
+            videos.push(self.row_to_video(row)?);
+        }
 
-            let desc_score = self.fuzzy_score(query, &description, query_words);
-            let sig_score = significance.as_ref()
-                .map(|s| self.fuzzy_score(query, s, query_words) * 0.7)
-                .unwrap_or(0.0);
-            let score = desc_score.max(sig_score);
+        Ok(videos)
+    }
 
-            if score >= threshold {
-                results.push(UnifiedSearchResult {
-                    result_type: SearchResultType::Visual,
-                    id,
-                    title: self.truncate_snippet(&description, 100),
-                    subtitle: Some(visual_type),
-                    snippet: significance,
-                    score,
-                    video_id: Some(video_id),
-                    timestamp: Some(timestamp),
-                    location: None,
-                });
-            }
+    fn list_videos_added_after(&self, since: DateTime<Utc>) -> Result<Vec<Video>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, url, title, channel, upload_date, description, added_at, duration, view_count, thumbnail_url FROM videos WHERE archived_at IS NULL AND added_at > ?1 ORDER BY added_at"
+        )?;
+
+        let mut videos = Vec::new();
+        let mut rows = stmt.query(params![since.to_rfc3339()])?;
+
+        while let Some(row) = rows.next()? {
+            videos.push(self.row_to_video(row)?);
         }
-        Ok(results)
+
+        Ok(videos)
     }
 
-    /// Search locations with fuzzy matching
-    fn search_locations_fuzzy(
-        &self,
-        query: &str,
-        query_words: &[&str],
-        threshold: f64,
-    ) -> Result<Vec<UnifiedSearchResult>> {
+    fn list_videos_by_language(&self, language: &str) -> Result<Vec<Video>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, name, lat, lon FROM locations"
+            "SELECT id, url, title, channel, upload_date, description, added_at, duration, view_count, thumbnail_url FROM videos v
+             WHERE v.archived_at IS NULL AND EXISTS (SELECT 1 FROM transcripts t WHERE t.video_id = v.id AND t.language = ?1)
+             ORDER BY added_at DESC"
         )?;
-        let mut rows = stmt.query([])?;
-        let mut results = Vec::new();
+
+        let mut videos = Vec::new();
+        let mut rows = stmt.query(params![normalize_language_tag(language)])?;
 
         while let Some(row) = rows.next()? {
-            let id: i64 = row.get(0)?;
-            let name: String = row.get(1)?;
-            let lat: f64 = row.get(2)?;
-            let lon: f64 = row.get(3)?;
+            videos.push(self.row_to_video(row)?);
+        }
 
-            let score = self.fuzzy_score(query, &name, query_words);
+        Ok(videos)
+    }
 
-            if score >= threshold {
-                results.push(UnifiedSearchResult {
-                    result_type: SearchResultType::Location,
-                    id,
-                    title: name,
-                    subtitle: Some(format!("{:.4}, {:.4}", lat, lon)),
-                    snippet: None,
-                    score,
-                    video_id: None,
-                    timestamp: None,
-                    location: Some((lat, lon)),
-                });
-            }
+    fn set_video_availability(&self, video_id: &str, availability: Availability, detail: Option<&str>) -> Result<bool> {
+        let affected = self.conn.execute(
+            "UPDATE videos SET availability = ?1, availability_checked_at = ?2, availability_detail = ?3 WHERE id = ?4",
+            params![availability.as_str(), Utc::now().to_rfc3339(), detail, video_id],
+        )?;
+        Ok(affected > 0)
+    }
+
+    fn get_video_availability(&self, video_id: &str) -> Result<Option<VideoAvailability>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT id, url, title, channel, upload_date, description, added_at, duration, view_count, thumbnail_url,
+                   availability, availability_checked_at, availability_detail
+            FROM videos WHERE id = ?1
+            "#
+        )?;
+        let mut rows = stmt.query(params![video_id])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(self.row_to_video_availability(row)?))
+        } else {
+            Ok(None)
         }
-        Ok(results)
     }
 
-    /// Search research questions with fuzzy matching
-    fn search_questions_fuzzy(
-        &self,
-        query: &str,
-        query_words: &[&str],
-        threshold: f64,
-    ) -> Result<Vec<UnifiedSearchResult>> {
+    fn list_unavailable_videos(&self) -> Result<Vec<VideoAvailability>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, question, notes, status FROM research_questions"
+            r#"
+            SELECT id, url, title, channel, upload_date, description, added_at, duration, view_count, thumbnail_url,
+                   availability, availability_checked_at, availability_detail
+            FROM videos
+            WHERE availability IN ('private', 'removed')
+            ORDER BY availability, added_at DESC
+            "#
         )?;
-        let mut rows = stmt.query([])?;
         let mut results = Vec::new();
+        let mut rows = stmt.query([])?;
 
         while let Some(row) = rows.next()? {
-            let id: i64 = row.get(0)?;
-            let question: String = row.get(1)?;
-            let notes: Option<String> = row.get(2)?;
-            let status: String = row.get(3)?;
+            results.push(self.row_to_video_availability(row)?);
+        }
+        Ok(results)
+    }
 
-            let q_score = self.fuzzy_score(query, &question, query_words);
-            let notes_score = notes.as_ref()
-                .map(|n| self.fuzzy_score(query, n, query_words) * 0.6)
-                .unwrap_or(0.0);
-            let score = q_score.max(notes_score);
+    fn suggest_spelling(&self, query: &str) -> Result<Option<String>> {
+        // Transcripts aren't stemmed, so their vocabulary holds real spellings
+        let vocab = match self.conn.dialect() {
+            Dialect::Sqlite => {
+                self.conn.execute_batch(
+                    "CREATE VIRTUAL TABLE IF NOT EXISTS temp.transcript_vocab USING fts5vocab(main, transcript_fts, row);"
+                )?;
+                "SELECT term, doc FROM temp.transcript_vocab"
+            }
+            Dialect::Postgres => "SELECT word AS term, ndoc AS doc FROM ts_stat('SELECT document FROM transcripts')",
+        };
+        let mut known = self.conn.prepare(&format!("SELECT 1 FROM ({vocab}) vocab WHERE term = ?1"))?;
+        let mut candidates = self.conn.prepare(&format!(
+            "SELECT term, doc FROM ({vocab}) vocab WHERE length(term) BETWEEN ?1 AND ?2"
+        ))?;
+
+        let mut suggestion = String::new();
+        let mut changed = false;
+        let mut chars = query.chars().peekable();
+        while let Some(&c) = chars.peek() {
+            if !c.is_alphanumeric() {
+                suggestion.push(c);
+                chars.next();
+                continue;
+            }
+            let mut word = String::new();
+            while let Some(&c) = chars.peek().filter(|c| c.is_alphanumeric()) {
+                word.push(c);
+                chars.next();
+            }
 
-            if score >= threshold {
-                results.push(UnifiedSearchResult {
-                    result_type: SearchResultType::Question,
-                    id,
-                    title: self.truncate_snippet(&question, 100),
-                    subtitle: Some(status),
-                    snippet: notes.map(|n| self.truncate_snippet(&n, 100)),
-                    score,
-                    video_id: None,
-                    timestamp: None,
-                    location: None,
-                });
+            let lower = word.to_lowercase();
+            let is_operator = matches!(word.as_str(), "AND" | "OR" | "NOT" | "NEAR");
+            if is_operator || lower.chars().all(|c| c.is_numeric()) || known.exists(params![lower])? {
+                suggestion.push_str(&word);
+                continue;
+            }
+
+            let len = lower.chars().count() as i64;
+            let mut best: Option<(f64, i64, String)> = None;
+            let mut rows = candidates.query(params![len - 2, len + 2])?;
+            while let Some(row) = rows.next()? {
+                let term: String = row.get(0)?;
+                let docs: i64 = row.get(1)?;
+                let score = normalized_levenshtein(&lower, &term).max(jaro_winkler(&lower, &term) - 0.1);
+                let better = match &best {
+                    Some((s, d, _)) => score > *s || (score == *s && docs > *d),
+                    None => true,
+                };
+                if score >= 0.75 && better {
+                    best = Some((score, docs, term));
+                }
+            }
+            match best {
+                Some((_, _, term)) => {
+                    suggestion.push_str(&term);
+                    changed = true;
+                }
+                None => suggestion.push_str(&word),
             }
         }
-        Ok(results)
-    }
 
-    /// Build search facets from results
-    fn build_search_facets(&self, results: &[UnifiedSearchResult]) -> Result<SearchFacets> {
-        let mut type_counts: HashMap<SearchResultType, usize> = HashMap::new();
-        let mut video_counts: HashMap<String, usize> = HashMap::new();
+        Ok(changed.then_some(suggestion))
+    }
+
+    fn search_text(&self, query: &str, kinds: &[&str], collection: Option<&str>) -> Result<Vec<TextMatch>> {
+        let query = &self.expand_query(query)?;
+        let (snippet, rank, from, matches) = match self.conn.dialect() {
+            Dialect::Sqlite => (
+                "snippet(text_index, 4, '>>>', '<<<', '...', 24)",
+                "bm25(text_index, 0.0, 0.0, 0.0, 5.0, 1.0)",
+                "text_index ti",
+                "text_index MATCH ?1",
+            ),
+            Dialect::Postgres => (
+                "ts_headline('english', ti.body, query, 'StartSel=>>>, StopSel=<<<, MaxFragments=1, MaxWords=24, MinWords=8')",
+                "-ts_rank('{0, 0, 0.2, 1}', ti.document, query)",
+                "text_index ti CROSS JOIN to_tsquery('english', ?1) query",
+                "ti.document @@ query",
+            ),
+        };
+        let mut stmt = self.conn.prepare(&format!(
+            r#"
+            SELECT ti.kind, ti.ref_id, ti.video_id, ti.title,
+                   {snippet} as snippet,
+                   {rank} as rank
+            FROM {from}
+            LEFT JOIN videos v ON v.id = ti.video_id
+            WHERE {matches} AND v.archived_at IS NULL
+              AND (?2 IS NULL OR ti.video_id IN (
+                  SELECT vc.video_id FROM video_collections vc
+                  JOIN collections c ON c.id = vc.collection_id
+                  WHERE c.name = ?2 COLLATE NOCASE
+              ))
+            ORDER BY rank
+            "#
+        ))?;
 
-        for result in results {
-            *type_counts.entry(result.result_type).or_insert(0) += 1;
-            if let Some(ref vid) = result.video_id {
-                *video_counts.entry(vid.clone()).or_insert(0) += 1;
+        let mut results = Vec::new();
+        let fts_query = self.match_query(query);
+        if fts_query.is_empty() {
+            return Ok(results);
+        }
+        let mut rows = stmt.query(params![fts_query, collection])?;
+
+        while let Some(row) = rows.next()? {
+            let kind: String = row.get(0)?;
+            if !kinds.is_empty() && !kinds.contains(&kind.as_str()) {
+                continue;
             }
+            results.push(TextMatch {
+                kind,
+                id: row.get(1)?,
+                video_id: row.get(2)?,
+                title: row.get(3)?,
+                snippet: row.get(4)?,
+                score: -row.get::<_, f64>(5)?,
+            });
         }
+        Ok(results)
+    }
 
-        // Get video titles for facets
-        let mut video_facets = Vec::new();
-        for (vid, count) in video_counts {
-            if let Some(video) = self.get_video(&vid)? {
-                video_facets.push((vid, video.title, count));
+    fn search(&self, query: &str) -> Result<Vec<(Video, String)>> {
+        let query = &self.expand_query(query)?;
+        let sql = match self.conn.dialect() {
+            Dialect::Sqlite => {
+                r#"
+                SELECT v.id, v.url, v.title, v.channel, v.upload_date, v.description, v.added_at, v.duration, v.view_count, v.thumbnail_url,
+                       snippet(transcript_fts, 1, '>>>', '<<<', '...', 32) as snippet
+                FROM transcript_fts
+                JOIN videos v ON v.id = transcript_fts.video_id
+                WHERE transcript_fts MATCH ?1 AND v.archived_at IS NULL
+                ORDER BY rank
+                "#
             }
+            // Transcripts are indexed unstemmed, as in transcript_fts
+            Dialect::Postgres => {
+                r#"
+                SELECT v.id, v.url, v.title, v.channel, v.upload_date, v.description, v.added_at, v.duration, v.view_count, v.thumbnail_url,
+                       ts_headline('simple', t.full_text, query, 'StartSel=>>>, StopSel=<<<, MaxFragments=1, MaxWords=32, MinWords=12') as snippet
+                FROM transcripts t
+                CROSS JOIN to_tsquery('simple', ?1) query
+                JOIN videos v ON v.id = t.video_id
+                WHERE t.document @@ query AND v.archived_at IS NULL
+                ORDER BY ts_rank(t.document, query) DESC
+                "#
+            }
+        };
+        let mut stmt = self.conn.prepare(sql)?;
+
+        let mut results = Vec::new();
+        let fts_query = self.match_query(query);
+        if fts_query.is_empty() {
+            return Ok(results);
         }
-        video_facets.sort_by(|a, b| b.2.cmp(&a.2));
+        let mut rows = stmt.query(params![fts_query])?;
 
-        let mut types_vec: Vec<(SearchResultType, usize)> = type_counts.into_iter().collect();
-        types_vec.sort_by(|a, b| b.1.cmp(&a.1));
+        while let Some(row) = rows.next()? {
+            let video = self.row_to_video(row)?;
+            let snippet: String = row.get(10)?;
+            results.push((video, snippet));
+        }
 
-        Ok(SearchFacets {
-            types: types_vec,
-            videos: video_facets,
-        })
+        Ok(results)
     }
 
-    fn row_to_video(&self, row: &rusqlite::Row) -> Result<Video> {
-        let upload_date: Option<String> = row.get(4)?;
-        let added_at: String = row.get(6)?;
+    fn search_with_timestamps(&self, query: &str) -> Result<Vec<SearchResult>> {
+        let query = &self.expand_query(query)?;
+        // Weighted by the ranking profile: title (10x), description (5x), transcript (1x) by default
+        // bm25() returns negative scores, lower is better
+        let mut stmt = self.conn.prepare(&format!(
+            r#"
+            SELECT v.id, v.url, v.title, v.channel, v.upload_date, v.description, v.added_at, v.duration, v.view_count, v.thumbnail_url,
+                   t.segments_json,
+                   {} as rank
+            FROM {}
+            JOIN videos v ON v.id = search_index.video_id
+            LEFT JOIN transcripts t ON t.video_id = v.id
+            WHERE {} AND v.archived_at IS NULL
+            ORDER BY rank
+            "#,
+            self.ranking_profile()?.rank_sql(self.conn.dialect()),
+            self.search_index_from(),
+            self.search_index_matches(),
+        ))?;
 
-        Ok(Video {
-            id: row.get(0)?,
-            url: row.get(1)?,
-            title: row.get(2)?,
-            channel: row.get(3)?,
-            upload_date: upload_date.and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok()),
-            description: row.get(5)?,
-            added_at: DateTime::parse_from_rfc3339(&added_at)?.with_timezone(&Utc),
-        })
-    }
+        let terms = match_terms(query);
+        let near = near_groups(query);
+        let mut results = Vec::new();
+        let fts_query = self.match_query(query);
+        if fts_query.is_empty() {
+            return Ok(results);
+        }
+        let mut rows = stmt.query(params![fts_query])?;
 
-    fn row_to_transcript(&self, row: &rusqlite::Row) -> Result<Transcript> {
-        let segments_json: String = row.get(3)?;
-        let segments: Vec<TranscriptSegment> = serde_json::from_str(&segments_json)?;
+        while let Some(row) = rows.next()? {
+            let video = self.row_to_video(row)?;
+            let segments_json: Option<String> = row.get(10)?;
 
-        Ok(Transcript {
-            video_id: row.get(0)?,
-            language: row.get(1)?,
-            full_text: row.get(2)?,
-            segments,
-        })
+            // Find segments containing the query (if transcript exists)
+            let mut matches = Vec::new();
+            if let Some(json) = segments_json {
+                let segments: Vec<TranscriptSegment> = serde_json::from_str(&json)?;
+                let close = near_segments(&segments, &near);
+                for (i, seg) in segments.iter().enumerate() {
+                    if segment_matches(&seg.text, &terms) || close.contains(&i) {
+                        matches.push(SegmentMatch {
+                            start_time: seg.start_time,
+                            duration: seg.duration,
+                            text: seg.text.clone(),
+                            speaker: seg.speaker.clone(),
+                        });
+                    }
+                }
+            }
+
+            // Include video even if no transcript matches (title/description matched)
+            results.push(SearchResult { video, matches });
+        }
+
+        Ok(results)
     }
 
-    // Era operations
+    // ========================================================================
+    // Unified Fuzzy Search
+    // ========================================================================
+
+    fn unified_search(
+        &self,
+        query: &str,
+        types: Option<&[&str]>,
+        video_filter: Option<&str>,
+        limit: usize,
+        fuzzy_threshold: f64,
+    ) -> Result<SearchResponse> {
+        let query_lower = query.to_lowercase();
+        let query_words: Vec<&str> = query_lower.split_whitespace().collect();
+        let mut all_results = Vec::new();
+
+        // Define which types to search
+        let default_types = ["claim", "video", "moc", "source", "scholar",
+            "term", "quote", "evidence", "visual", "location", "question"];
+        let search_types = types.unwrap_or(&default_types);
+
+        for search_type in search_types {
+            match *search_type {
+                "claim" => all_results.extend(self.search_claims_fuzzy(&query_lower, &query_words, video_filter, fuzzy_threshold)?),
+                "video" => all_results.extend(self.search_videos_fuzzy(&query_lower, &query_words, fuzzy_threshold)?),
+                "moc" => all_results.extend(self.search_mocs_fuzzy(&query_lower, &query_words, fuzzy_threshold)?),
+                "source" => all_results.extend(self.search_sources_fuzzy(&query_lower, &query_words, fuzzy_threshold)?),
+                "scholar" => all_results.extend(self.search_scholars_fuzzy(&query_lower, &query_words, fuzzy_threshold)?),
+                "term" => all_results.extend(self.search_terms_fuzzy(&query_lower, &query_words, video_filter, fuzzy_threshold)?),
+                "quote" => all_results.extend(self.search_quotes_fuzzy(&query_lower, &query_words, video_filter, fuzzy_threshold)?),
+                "evidence" => all_results.extend(self.search_evidence_fuzzy(&query_lower, &query_words, video_filter, fuzzy_threshold)?),
+                "visual" => all_results.extend(self.search_visuals_fuzzy(&query_lower, &query_words, video_filter, fuzzy_threshold)?),
+                "location" => all_results.extend(self.search_locations_fuzzy(&query_lower, &query_words, fuzzy_threshold)?),
+                "question" => all_results.extend(self.search_questions_fuzzy(&query_lower, &query_words, fuzzy_threshold)?),
+                _ => {}
+            }
+        }
+
+        // Sort by score descending
+        all_results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
 
-    fn seed_default_eras(&self) -> Result<()> {
-        let default_eras = [
-            ("Prehistoric", 0),
-            ("Bronze Age", 10),
-            ("Iron Age", 20),
-            ("Classical Antiquity", 30),
-            ("Late Antiquity", 40),
-            ("Medieval", 50),
-            ("Early Modern", 60),
-            ("Modern", 70),
-            ("Timeless", 100),  // For philosophy, spirituality, perennial wisdom
-        ];
+        // Build facets
+        let facets = self.build_search_facets(&all_results)?;
 
-        for (name, order) in default_eras {
-            self.conn.execute(
-                "INSERT OR IGNORE INTO eras (name, sort_order) VALUES (?1, ?2)",
-                params![name, order],
-            )?;
-        }
-        Ok(())
+        let total = all_results.len();
+        all_results.truncate(limit);
+
+        Ok(SearchResponse {
+            query: query.to_string(),
+            total,
+            results: all_results,
+            facets,
+        })
     }
 
-    pub fn list_eras(&self) -> Result<Vec<Era>> {
+    fn list_eras(&self) -> Result<Vec<Era>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, name, sort_order FROM eras ORDER BY sort_order"
+            "SELECT id, name, sort_order, start_year, end_year FROM eras ORDER BY sort_order"
         )?;
 
         let mut eras = Vec::new();
@@ -1528,14 +4518,16 @@ impl Database {
                 id: row.get(0)?,
                 name: row.get(1)?,
                 sort_order: row.get(2)?,
+                start_year: row.get(3)?,
+                end_year: row.get(4)?,
             });
         }
         Ok(eras)
     }
 
-    pub fn get_era_by_name(&self, name: &str) -> Result<Option<Era>> {
+    fn get_era_by_name(&self, name: &str) -> Result<Option<Era>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, name, sort_order FROM eras WHERE name = ?1 COLLATE NOCASE"
+            "SELECT id, name, sort_order, start_year, end_year FROM eras WHERE name = ?1 COLLATE NOCASE"
         )?;
         let mut rows = stmt.query(params![name])?;
 
@@ -1544,15 +4536,17 @@ impl Database {
                 id: row.get(0)?,
                 name: row.get(1)?,
                 sort_order: row.get(2)?,
+                start_year: row.get(3)?,
+                end_year: row.get(4)?,
             }))
         } else {
             Ok(None)
         }
     }
 
-    pub fn get_era(&self, id: i64) -> Result<Option<Era>> {
+    fn get_era(&self, id: i64) -> Result<Option<Era>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, name, sort_order FROM eras WHERE id = ?1"
+            "SELECT id, name, sort_order, start_year, end_year FROM eras WHERE id = ?1"
         )?;
         let mut rows = stmt.query(params![id])?;
 
@@ -1561,24 +4555,30 @@ impl Database {
                 id: row.get(0)?,
                 name: row.get(1)?,
                 sort_order: row.get(2)?,
+                start_year: row.get(3)?,
+                end_year: row.get(4)?,
             }))
         } else {
             Ok(None)
         }
     }
 
-    pub fn create_era(&self, name: &str, sort_order: i32) -> Result<Era> {
+    fn create_era(&self, name: &str, sort_order: i32, start_year: Option<i32>, end_year: Option<i32>) -> Result<Era> {
         self.conn.execute(
-            "INSERT INTO eras (name, sort_order) VALUES (?1, ?2)",
-            params![name, sort_order],
+            "INSERT INTO eras (name, sort_order, start_year, end_year) VALUES (?1, ?2, ?3, ?4)",
+            params![name, sort_order, start_year, end_year],
         )?;
         let id = self.conn.last_insert_rowid();
-        Ok(Era { id, name: name.to_string(), sort_order })
+        Ok(Era { id, name: name.to_string(), sort_order, start_year, end_year })
+    }
+
+    fn next_era_sort_order(&self) -> Result<i32> {
+        Ok(self.conn.query_row("SELECT COALESCE(MAX(sort_order), 0) + 10 FROM eras", [], |row| row.get(0))?)
     }
 
     // Region operations
 
-    pub fn list_regions(&self) -> Result<Vec<Region>> {
+    fn list_regions(&self) -> Result<Vec<Region>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, name, parent_id FROM regions ORDER BY name"
         )?;
@@ -1596,7 +4596,7 @@ impl Database {
         Ok(regions)
     }
 
-    pub fn get_region_by_name(&self, name: &str) -> Result<Option<Region>> {
+    fn get_region_by_name(&self, name: &str) -> Result<Option<Region>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, name, parent_id FROM regions WHERE name = ?1 COLLATE NOCASE"
         )?;
@@ -1613,7 +4613,7 @@ impl Database {
         }
     }
 
-    pub fn create_region(&self, name: &str, parent_id: Option<i64>) -> Result<Region> {
+    fn create_region(&self, name: &str, parent_id: Option<i64>) -> Result<Region> {
         self.conn.execute(
             "INSERT INTO regions (name, parent_id) VALUES (?1, ?2)",
             params![name, parent_id],
@@ -1622,9 +4622,50 @@ impl Database {
         Ok(Region { id, name: name.to_string(), parent_id })
     }
 
+    // Taxonomy cleanup
+
+    fn rename_tag(&self, table: &str, id: i64, name: &str) -> Result<bool> {
+        Self::check_tag_table(table)?;
+        let affected = self.conn.execute(
+            &format!("UPDATE {} SET name = ?1 WHERE id = ?2", table),
+            params![name, id],
+        )?;
+        Ok(affected > 0)
+    }
+
+    fn merge_tags(&self, table: &str, source_id: i64, target_id: i64) -> Result<()> {
+        Self::check_tag_table(table)?;
+        if source_id == target_id {
+            anyhow::bail!("Cannot merge {} row {} into itself", table, source_id);
+        }
+
+        let tx = self.conn.unchecked_transaction()?;
+        for (child, column, _) in self.foreign_keys_to(table)? {
+            self.conn.execute(
+                &format!("UPDATE OR IGNORE \"{}\" SET \"{}\" = ?1 WHERE \"{}\" = ?2", child, column, column),
+                params![target_id, source_id],
+            )?;
+            // Rows left behind already exist for the target (e.g. a video tagged with both)
+            self.conn.execute(
+                &format!("DELETE FROM \"{}\" WHERE \"{}\" = ?1", child, column),
+                params![source_id],
+            )?;
+        }
+        if table == "regions" {
+            // The target may have been a child of the source
+            self.conn.execute(
+                "UPDATE regions SET parent_id = (SELECT parent_id FROM regions WHERE id = ?2) WHERE id = ?1 AND parent_id = ?1",
+                params![target_id, source_id],
+            )?;
+        }
+        self.conn.execute(&format!("DELETE FROM {} WHERE id = ?1", table), params![source_id])?;
+        tx.commit()?;
+        Ok(())
+    }
+
     // Video tagging
 
-    pub fn tag_video_era(&self, video_id: &str, era_id: i64) -> Result<()> {
+    fn tag_video_era(&self, video_id: &str, era_id: i64) -> Result<()> {
         self.conn.execute(
             "INSERT OR IGNORE INTO video_eras (video_id, era_id) VALUES (?1, ?2)",
             params![video_id, era_id],
@@ -1632,7 +4673,7 @@ impl Database {
         Ok(())
     }
 
-    pub fn tag_video_region(&self, video_id: &str, region_id: i64) -> Result<()> {
+    fn tag_video_region(&self, video_id: &str, region_id: i64) -> Result<()> {
         self.conn.execute(
             "INSERT OR IGNORE INTO video_regions (video_id, region_id) VALUES (?1, ?2)",
             params![video_id, region_id],
@@ -1640,10 +4681,26 @@ impl Database {
         Ok(())
     }
 
-    pub fn get_video_eras(&self, video_id: &str) -> Result<Vec<Era>> {
+    fn untag_video_era(&self, video_id: &str, era_id: i64) -> Result<bool> {
+        let affected = self.conn.execute(
+            "DELETE FROM video_eras WHERE video_id = ?1 AND era_id = ?2",
+            params![video_id, era_id],
+        )?;
+        Ok(affected > 0)
+    }
+
+    fn untag_video_region(&self, video_id: &str, region_id: i64) -> Result<bool> {
+        let affected = self.conn.execute(
+            "DELETE FROM video_regions WHERE video_id = ?1 AND region_id = ?2",
+            params![video_id, region_id],
+        )?;
+        Ok(affected > 0)
+    }
+
+    fn get_video_eras(&self, video_id: &str) -> Result<Vec<Era>> {
         let mut stmt = self.conn.prepare(
             r#"
-            SELECT e.id, e.name, e.sort_order
+            SELECT e.id, e.name, e.sort_order, e.start_year, e.end_year
             FROM eras e
             JOIN video_eras ve ON ve.era_id = e.id
             WHERE ve.video_id = ?1
@@ -1659,12 +4716,14 @@ impl Database {
                 id: row.get(0)?,
                 name: row.get(1)?,
                 sort_order: row.get(2)?,
+                start_year: row.get(3)?,
+                end_year: row.get(4)?,
             });
         }
         Ok(eras)
     }
 
-    pub fn get_video_regions(&self, video_id: &str) -> Result<Vec<Region>> {
+    fn get_video_regions(&self, video_id: &str) -> Result<Vec<Region>> {
         let mut stmt = self.conn.prepare(
             r#"
             SELECT r.id, r.name, r.parent_id
@@ -1688,11 +4747,11 @@ impl Database {
         Ok(regions)
     }
 
-    pub fn browse_videos(&self, era: Option<&str>, region: Option<&str>) -> Result<Vec<Video>> {
+    fn browse_videos(&self, era: Option<&str>, region: Option<&str>, include_subregions: bool) -> Result<Vec<Video>> {
         let mut query = String::from(
-            "SELECT DISTINCT v.id, v.url, v.title, v.channel, v.upload_date, v.description, v.added_at FROM videos v"
+            "SELECT DISTINCT v.id, v.url, v.title, v.channel, v.upload_date, v.description, v.added_at, v.duration, v.view_count, v.thumbnail_url FROM videos v"
         );
-        let mut conditions: Vec<&str> = Vec::new();
+        let mut conditions: Vec<&str> = vec!["v.archived_at IS NULL"];
         let mut joins = Vec::new();
 
         if era.is_some() {
@@ -1702,11 +4761,12 @@ impl Database {
 
         if region.is_some() {
             joins.push("JOIN video_regions vr ON vr.video_id = v.id JOIN regions r ON r.id = vr.region_id");
-            if era.is_some() {
-                conditions.push("r.name = ?2 COLLATE NOCASE");
-            } else {
-                conditions.push("r.name = ?1 COLLATE NOCASE");
-            }
+            conditions.push(match (era.is_some(), include_subregions) {
+                (true, false) => "r.name = ?2 COLLATE NOCASE",
+                (false, false) => "r.name = ?1 COLLATE NOCASE",
+                (true, true) => "r.id IN (WITH RECURSIVE sub(id) AS (SELECT id FROM regions WHERE name = ?2 COLLATE NOCASE UNION SELECT c.id FROM regions c JOIN sub ON c.parent_id = sub.id) SELECT id FROM sub)",
+                (false, true) => "r.id IN (WITH RECURSIVE sub(id) AS (SELECT id FROM regions WHERE name = ?1 COLLATE NOCASE UNION SELECT c.id FROM regions c JOIN sub ON c.parent_id = sub.id) SELECT id FROM sub)",
+            });
         }
 
         for join in &joins {
@@ -1740,7 +4800,7 @@ impl Database {
 
     // Topic operations
 
-    pub fn list_topics(&self) -> Result<Vec<Topic>> {
+    fn list_topics(&self) -> Result<Vec<Topic>> {
         let mut stmt = self.conn.prepare("SELECT id, name FROM topics ORDER BY name")?;
         let mut topics = Vec::new();
         let mut rows = stmt.query([])?;
@@ -1754,7 +4814,7 @@ impl Database {
         Ok(topics)
     }
 
-    pub fn get_topic_by_name(&self, name: &str) -> Result<Option<Topic>> {
+    fn get_topic_by_name(&self, name: &str) -> Result<Option<Topic>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, name FROM topics WHERE name = ?1 COLLATE NOCASE"
         )?;
@@ -1770,13 +4830,13 @@ impl Database {
         }
     }
 
-    pub fn create_topic(&self, name: &str) -> Result<Topic> {
+    fn create_topic(&self, name: &str) -> Result<Topic> {
         self.conn.execute("INSERT INTO topics (name) VALUES (?1)", params![name])?;
         let id = self.conn.last_insert_rowid();
         Ok(Topic { id, name: name.to_string() })
     }
 
-    pub fn get_or_create_topic(&self, name: &str) -> Result<Topic> {
+    fn get_or_create_topic(&self, name: &str) -> Result<Topic> {
         if let Some(topic) = self.get_topic_by_name(name)? {
             Ok(topic)
         } else {
@@ -1784,7 +4844,7 @@ impl Database {
         }
     }
 
-    pub fn tag_video_topic(&self, video_id: &str, topic_id: i64) -> Result<()> {
+    fn tag_video_topic(&self, video_id: &str, topic_id: i64) -> Result<()> {
         self.conn.execute(
             "INSERT OR IGNORE INTO video_topics (video_id, topic_id) VALUES (?1, ?2)",
             params![video_id, topic_id],
@@ -1792,7 +4852,15 @@ impl Database {
         Ok(())
     }
 
-    pub fn get_video_topics(&self, video_id: &str) -> Result<Vec<Topic>> {
+    fn untag_video_topic(&self, video_id: &str, topic_id: i64) -> Result<bool> {
+        let affected = self.conn.execute(
+            "DELETE FROM video_topics WHERE video_id = ?1 AND topic_id = ?2",
+            params![video_id, topic_id],
+        )?;
+        Ok(affected > 0)
+    }
+
+    fn get_video_topics(&self, video_id: &str) -> Result<Vec<Topic>> {
         let mut stmt = self.conn.prepare(
             r#"
             SELECT t.id, t.name
@@ -1815,14 +4883,14 @@ impl Database {
         Ok(topics)
     }
 
-    pub fn browse_by_topic(&self, topic_name: &str) -> Result<Vec<Video>> {
+    fn browse_by_topic(&self, topic_name: &str) -> Result<Vec<Video>> {
         let mut stmt = self.conn.prepare(
             r#"
-            SELECT v.id, v.url, v.title, v.channel, v.upload_date, v.description, v.added_at
+            SELECT v.id, v.url, v.title, v.channel, v.upload_date, v.description, v.added_at, v.duration, v.view_count, v.thumbnail_url
             FROM videos v
             JOIN video_topics vt ON vt.video_id = v.id
             JOIN topics t ON t.id = vt.topic_id
-            WHERE t.name = ?1 COLLATE NOCASE
+            WHERE t.name = ?1 COLLATE NOCASE AND v.archived_at IS NULL
             ORDER BY v.added_at DESC
             "#
         )?;
@@ -1838,7 +4906,7 @@ impl Database {
 
     // Collection operations
 
-    pub fn list_collections(&self) -> Result<Vec<Collection>> {
+    fn list_collections(&self) -> Result<Vec<Collection>> {
         let mut stmt = self.conn.prepare("SELECT id, name, description FROM collections ORDER BY name")?;
         let mut collections = Vec::new();
         let mut rows = stmt.query([])?;
@@ -1853,7 +4921,7 @@ impl Database {
         Ok(collections)
     }
 
-    pub fn get_collection_by_name(&self, name: &str) -> Result<Option<Collection>> {
+    fn get_collection_by_name(&self, name: &str) -> Result<Option<Collection>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, name, description FROM collections WHERE name = ?1 COLLATE NOCASE"
         )?;
@@ -1870,7 +4938,7 @@ impl Database {
         }
     }
 
-    pub fn create_collection(&self, name: &str, description: Option<&str>) -> Result<Collection> {
+    fn create_collection(&self, name: &str, description: Option<&str>) -> Result<Collection> {
         self.conn.execute(
             "INSERT INTO collections (name, description) VALUES (?1, ?2)",
             params![name, description],
@@ -1879,7 +4947,7 @@ impl Database {
         Ok(Collection { id, name: name.to_string(), description: description.map(|s| s.to_string()) })
     }
 
-    pub fn add_video_to_collection(&self, video_id: &str, collection_id: i64) -> Result<()> {
+    fn add_video_to_collection(&self, video_id: &str, collection_id: i64) -> Result<()> {
         self.conn.execute(
             "INSERT OR IGNORE INTO video_collections (video_id, collection_id) VALUES (?1, ?2)",
             params![video_id, collection_id],
@@ -1887,14 +4955,41 @@ impl Database {
         Ok(())
     }
 
-    pub fn get_collection_videos(&self, collection_name: &str) -> Result<Vec<Video>> {
+    fn remove_video_from_collection(&self, video_id: &str, collection_id: i64) -> Result<bool> {
+        let affected = self.conn.execute(
+            "DELETE FROM video_collections WHERE video_id = ?1 AND collection_id = ?2",
+            params![video_id, collection_id],
+        )?;
+        Ok(affected > 0)
+    }
+
+    fn update_collection(&self, id: i64, name: Option<&str>, description: Option<Option<&str>>) -> Result<bool> {
+        let mut affected = 0;
+        if let Some(name) = name {
+            affected += self.conn.execute("UPDATE collections SET name = ?1 WHERE id = ?2", params![name, id])?;
+        }
+        if let Some(description) = description {
+            affected += self.conn.execute("UPDATE collections SET description = ?1 WHERE id = ?2", params![description, id])?;
+        }
+        Ok(affected > 0)
+    }
+
+    fn delete_collection(&self, id: i64) -> Result<bool> {
+        let tx = self.conn.unchecked_transaction()?;
+        self.conn.execute("DELETE FROM video_collections WHERE collection_id = ?1", params![id])?;
+        let affected = self.conn.execute("DELETE FROM collections WHERE id = ?1", params![id])?;
+        tx.commit()?;
+        Ok(affected > 0)
+    }
+
+    fn get_collection_videos(&self, collection_name: &str) -> Result<Vec<Video>> {
         let mut stmt = self.conn.prepare(
             r#"
-            SELECT v.id, v.url, v.title, v.channel, v.upload_date, v.description, v.added_at
+            SELECT v.id, v.url, v.title, v.channel, v.upload_date, v.description, v.added_at, v.duration, v.view_count, v.thumbnail_url
             FROM videos v
             JOIN video_collections vc ON vc.video_id = v.id
             JOIN collections c ON c.id = vc.collection_id
-            WHERE c.name = ?1 COLLATE NOCASE
+            WHERE c.name = ?1 COLLATE NOCASE AND v.archived_at IS NULL
             ORDER BY v.added_at DESC
             "#
         )?;
@@ -1908,7 +5003,7 @@ impl Database {
         Ok(videos)
     }
 
-    pub fn get_video_collections(&self, video_id: &str) -> Result<Vec<Collection>> {
+    fn get_video_collections(&self, video_id: &str) -> Result<Vec<Collection>> {
         let mut stmt = self.conn.prepare(
             r#"
             SELECT c.id, c.name, c.description
@@ -1934,7 +5029,7 @@ impl Database {
 
     // Note operations
 
-    pub fn add_note(&self, video_id: &str, timestamp: Option<f64>, text: &str) -> Result<Note> {
+    fn add_note(&self, video_id: &str, timestamp: Option<f64>, text: &str) -> Result<Note> {
         let created_at = Utc::now();
         self.conn.execute(
             "INSERT INTO notes (video_id, timestamp, text, created_at) VALUES (?1, ?2, ?3, ?4)",
@@ -1950,7 +5045,7 @@ impl Database {
         })
     }
 
-    pub fn get_video_notes(&self, video_id: &str) -> Result<Vec<Note>> {
+    fn get_video_notes(&self, video_id: &str) -> Result<Vec<Note>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, video_id, timestamp, text, created_at FROM notes WHERE video_id = ?1 ORDER BY timestamp NULLS FIRST, created_at"
         )?;
@@ -1971,14 +5066,43 @@ impl Database {
         Ok(notes)
     }
 
-    pub fn delete_note(&self, note_id: i64) -> Result<bool> {
+    fn get_note(&self, note_id: i64) -> Result<Option<Note>> {
+        let mut stmt = self.conn.prepare("SELECT id, video_id, timestamp, text, created_at FROM notes WHERE id = ?1")?;
+        let mut rows = stmt.query(params![note_id])?;
+        match rows.next()? {
+            Some(row) => {
+                let created_at: String = row.get(4)?;
+                Ok(Some(Note {
+                    id: row.get(0)?,
+                    video_id: row.get(1)?,
+                    timestamp: row.get(2)?,
+                    text: row.get(3)?,
+                    created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+                }))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn update_note(&self, note_id: i64, text: Option<&str>, timestamp: Option<Option<f64>>) -> Result<bool> {
+        let mut affected = 0;
+        if let Some(text) = text {
+            affected += self.conn.execute("UPDATE notes SET text = ?1 WHERE id = ?2", params![text, note_id])?;
+        }
+        if let Some(timestamp) = timestamp {
+            affected += self.conn.execute("UPDATE notes SET timestamp = ?1 WHERE id = ?2", params![timestamp, note_id])?;
+        }
+        Ok(affected > 0)
+    }
+
+    fn delete_note(&self, note_id: i64) -> Result<bool> {
         let affected = self.conn.execute("DELETE FROM notes WHERE id = ?1", params![note_id])?;
         Ok(affected > 0)
     }
 
     // Location operations
 
-    pub fn list_locations(&self) -> Result<Vec<Location>> {
+    fn list_locations(&self) -> Result<Vec<Location>> {
         let mut stmt = self.conn.prepare("SELECT id, name, lat, lon FROM locations ORDER BY name")?;
         let mut locations = Vec::new();
         let mut rows = stmt.query([])?;
@@ -1994,7 +5118,7 @@ impl Database {
         Ok(locations)
     }
 
-    pub fn get_location_by_name(&self, name: &str) -> Result<Option<Location>> {
+    fn get_location_by_name(&self, name: &str) -> Result<Option<Location>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, name, lat, lon FROM locations WHERE name = ?1 COLLATE NOCASE"
         )?;
@@ -2012,7 +5136,7 @@ impl Database {
         }
     }
 
-    pub fn create_location(&self, name: &str, lat: f64, lon: f64) -> Result<Location> {
+    fn create_location(&self, name: &str, lat: f64, lon: f64) -> Result<Location> {
         self.conn.execute(
             "INSERT INTO locations (name, lat, lon) VALUES (?1, ?2, ?3)",
             params![name, lat, lon],
@@ -2021,7 +5145,7 @@ impl Database {
         Ok(Location { id, name: name.to_string(), lat, lon })
     }
 
-    pub fn get_or_create_location(&self, name: &str, lat: f64, lon: f64) -> Result<Location> {
+    fn get_or_create_location(&self, name: &str, lat: f64, lon: f64) -> Result<Location> {
         if let Some(loc) = self.get_location_by_name(name)? {
             Ok(loc)
         } else {
@@ -2029,7 +5153,41 @@ impl Database {
         }
     }
 
-    pub fn add_video_location(
+    fn add_extracted_location(
+        &self,
+        video_id: &str,
+        name: &str,
+        coordinates: Option<(f64, f64)>,
+        timestamp: Option<f64>,
+        note: Option<&str>,
+        extracted_by: &str,
+    ) -> Result<Option<(Location, bool)>> {
+        let location = match (self.get_location_by_name(name)?, coordinates) {
+            (Some(location), _) => location,
+            (None, Some((lat, lon))) => {
+                self.conn.execute(
+                    "INSERT INTO locations (name, lat, lon, extracted_by) VALUES (?1, ?2, ?3, ?4)",
+                    params![name, lat, lon, extracted_by],
+                )?;
+                Location { id: self.conn.last_insert_rowid(), name: name.to_string(), lat, lon }
+            }
+            (None, None) => return Ok(None),
+        };
+        let pinned: bool = self.conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM video_locations WHERE video_id = ?1 AND location_id = ?2)",
+            params![video_id, location.id],
+            |row| row.get(0),
+        )?;
+        if !pinned {
+            self.conn.execute(
+                "INSERT INTO video_locations (video_id, location_id, timestamp, note, extracted_by) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![video_id, location.id, timestamp, note, extracted_by],
+            )?;
+        }
+        Ok(Some((location, !pinned)))
+    }
+
+    fn add_video_location(
         &self,
         video_id: &str,
         location_id: i64,
@@ -2045,7 +5203,7 @@ impl Database {
         Ok(self.conn.last_insert_rowid())
     }
 
-    pub fn get_map_pins(&self, era: Option<&str>, topic: Option<&str>) -> Result<Vec<MapPin>> {
+    fn get_map_pins(&self, era: Option<&str>, topic: Option<&str>) -> Result<Vec<MapPin>> {
         let mut query = String::from(
             r#"
             SELECT l.id, l.name, l.lat, l.lon,
@@ -2057,7 +5215,7 @@ impl Database {
             JOIN videos v ON v.id = vl.video_id
             LEFT JOIN eras e ON e.id = vl.era_id
             LEFT JOIN topics t ON t.id = vl.topic_id
-            WHERE 1=1
+            WHERE v.archived_at IS NULL
             "#
         );
 
@@ -2104,9 +5262,97 @@ impl Database {
         Ok(pins)
     }
 
+    fn timeline(&self, kinds: &[TimelineKind], from: Option<i32>, to: Option<i32>) -> Result<Timeline> {
+        let mut eras: Vec<Era> = self.list_eras()?
+            .into_iter()
+            .filter(|e| e.start_year.is_some() || e.end_year.is_some())
+            .collect();
+        eras.sort_by_key(|e| e.start_year.or(e.end_year));
+
+        // Each query yields id, label, era, start year, end year, video ID
+        let queries = [
+            (TimelineKind::Video, r#"
+                SELECT v.id, v.title, e.name, COALESCE(e.start_year, e.end_year), COALESCE(e.end_year, e.start_year), v.id
+                FROM video_eras ve
+                JOIN videos v ON v.id = ve.video_id
+                JOIN eras e ON e.id = ve.era_id
+                WHERE v.archived_at IS NULL AND (e.start_year IS NOT NULL OR e.end_year IS NOT NULL)
+            "#),
+            (TimelineKind::Claim, r#"
+                SELECT CAST(c.id AS TEXT), c.text, NULL, COALESCE(c.date_start, c.date_end), COALESCE(c.date_end, c.date_start), c.video_id
+                FROM claims c
+                WHERE c.archived_at IS NULL AND (c.date_start IS NOT NULL OR c.date_end IS NOT NULL)
+                UNION ALL
+                SELECT CAST(c.id AS TEXT), c.text, e.name, COALESCE(e.start_year, e.end_year), COALESCE(e.end_year, e.start_year), c.video_id
+                FROM claims c
+                JOIN eras e ON e.id IN (
+                    SELECT era_id FROM claim_eras WHERE claim_id = c.id
+                    UNION
+                    SELECT era_id FROM video_eras
+                    WHERE video_id = c.video_id AND NOT EXISTS (SELECT 1 FROM claim_eras WHERE claim_id = c.id)
+                )
+                WHERE c.archived_at IS NULL AND c.date_start IS NULL AND c.date_end IS NULL AND (e.start_year IS NOT NULL OR e.end_year IS NOT NULL)
+            "#),
+            (TimelineKind::CyclicalIndicator, r#"
+                SELECT CAST(ci.id AS TEXT), ci.entity || ': ' || ci.description, e.name, COALESCE(e.start_year, e.end_year), COALESCE(e.end_year, e.start_year), ci.video_id
+                FROM cyclical_indicators ci
+                JOIN eras e ON e.id = ci.era_id
+                WHERE (e.start_year IS NOT NULL OR e.end_year IS NOT NULL)
+            "#),
+            (TimelineKind::IdeaTransmission, r#"
+                SELECT CAST(it.id AS TEXT), it.idea || ' (' || it.source_entity || ' → ' || it.target_entity || ')', e.name, COALESCE(e.start_year, e.end_year), COALESCE(e.end_year, e.start_year), it.video_id
+                FROM idea_transmissions it
+                JOIN eras e ON e.id = it.era_id
+                WHERE (e.start_year IS NOT NULL OR e.end_year IS NOT NULL)
+            "#),
+            (TimelineKind::GeopoliticalEntity, r#"
+                SELECT CAST(ge.id AS TEXT), ge.name || ' (' || ge.position || ')', e.name, COALESCE(e.start_year, e.end_year), COALESCE(e.end_year, e.start_year), NULL
+                FROM geopolitical_entities ge
+                JOIN eras e ON e.id = ge.era_id
+                WHERE (e.start_year IS NOT NULL OR e.end_year IS NOT NULL)
+            "#),
+            (TimelineKind::SurplusFlow, r#"
+                SELECT CAST(sf.id AS TEXT), sf.commodity || ': ' || f.name || ' → ' || t.name, e.name, COALESCE(e.start_year, e.end_year), COALESCE(e.end_year, e.start_year), sf.video_id
+                FROM surplus_flows sf
+                JOIN geopolitical_entities f ON f.id = sf.from_entity_id
+                JOIN geopolitical_entities t ON t.id = sf.to_entity_id
+                JOIN eras e ON e.id = sf.era_id
+                WHERE (e.start_year IS NOT NULL OR e.end_year IS NOT NULL)
+            "#),
+        ];
+
+        let mut entries = Vec::new();
+        for (kind, sql) in queries {
+            if !kinds.is_empty() && !kinds.contains(&kind) {
+                continue;
+            }
+            let mut stmt = self.conn.prepare(sql)?;
+            let mut rows = stmt.query([])?;
+            while let Some(row) = rows.next()? {
+                let start_year: i32 = row.get(3)?;
+                let end_year: i32 = row.get(4)?;
+                if from.is_some_and(|from| end_year.max(start_year) < from) || to.is_some_and(|to| start_year.min(end_year) > to) {
+                    continue;
+                }
+                entries.push(TimelineEntry {
+                    kind,
+                    id: row.get(0)?,
+                    label: row.get(1)?,
+                    start_year,
+                    end_year,
+                    era: row.get(2)?,
+                    video_id: row.get(5)?,
+                });
+            }
+        }
+        entries.sort_by_key(|e| (e.start_year, e.end_year));
+
+        Ok(Timeline { eras, entries })
+    }
+
     // Auto-tagging from title and description
 
-    pub fn suggest_tags(&self, video_id: &str) -> Result<AutoTags> {
+    fn suggest_tags(&self, video_id: &str) -> Result<AutoTags> {
         let video = match self.get_video(video_id)? {
             Some(v) => v,
             None => return Ok(AutoTags::default()),
@@ -2263,7 +5509,7 @@ impl Database {
         Ok(tags)
     }
 
-    pub fn apply_auto_tags(&self, video_id: &str) -> Result<AutoTags> {
+    fn apply_auto_tags(&self, video_id: &str) -> Result<AutoTags> {
         let tags = self.suggest_tags(video_id)?;
 
         // Apply era tags
@@ -2291,7 +5537,84 @@ impl Database {
         Ok(tags)
     }
 
-    pub fn get_video_locations(&self, video_id: &str) -> Result<Vec<MapPin>> {
+    fn apply_tags(&self, video_id: &str, tags: &AutoTags, allow_new: bool) -> Result<(AutoTags, AutoTags)> {
+        let groups = self.alias_groups()?;
+        // The name itself, then the other names in its alias group
+        let candidates = |name: &str| -> Vec<String> {
+            let mut names = vec![name.to_string()];
+            for group in groups.iter().filter(|g| g.iter().any(|n| n.eq_ignore_ascii_case(name))) {
+                names.extend(group.iter().filter(|n| !n.eq_ignore_ascii_case(name)).cloned());
+            }
+            names
+        };
+
+        let mut applied = AutoTags::default();
+        let mut skipped = AutoTags::default();
+
+        for name in &tags.eras {
+            let mut era = None;
+            for candidate in candidates(name) {
+                era = self.get_era_by_name(&candidate)?;
+                if era.is_some() {
+                    break;
+                }
+            }
+            let era = match era {
+                Some(era) => era,
+                None if allow_new => self.create_era(name, self.next_era_sort_order()?, None, None)?,
+                None => {
+                    skipped.eras.push(name.clone());
+                    continue;
+                }
+            };
+            self.tag_video_era(video_id, era.id)?;
+            applied.eras.push(era.name);
+        }
+
+        for name in &tags.regions {
+            let mut region = None;
+            for candidate in candidates(name) {
+                region = self.get_region_by_name(&candidate)?;
+                if region.is_some() {
+                    break;
+                }
+            }
+            let region = match region {
+                Some(region) => region,
+                None if allow_new => self.create_region(name, None)?,
+                None => {
+                    skipped.regions.push(name.clone());
+                    continue;
+                }
+            };
+            self.tag_video_region(video_id, region.id)?;
+            applied.regions.push(region.name);
+        }
+
+        for name in &tags.topics {
+            let mut topic = None;
+            for candidate in candidates(name) {
+                topic = self.get_topic_by_name(&candidate)?;
+                if topic.is_some() {
+                    break;
+                }
+            }
+            let topic = match topic {
+                Some(topic) => topic,
+                None if allow_new => self.create_topic(name)?,
+                None => {
+                    skipped.topics.push(name.clone());
+                    continue;
+                }
+            };
+            self.tag_video_topic(video_id, topic.id)?;
+            applied.topics.push(topic.name);
+        }
+
+        Ok((applied, skipped))
+    }
+
+    fn get_video_locations(&self, video_id: &str) -> Result<Vec<MapPin>> {
         let mut stmt = self.conn.prepare(
             r#"
             SELECT l.id, l.name, l.lat, l.lon,
@@ -2333,88 +5656,95 @@ impl Database {
 
     // Phase 5: Research Tools
 
-    // Advanced search combining full-text with metadata filters
-    pub fn advanced_search(
+    // Advanced search combining full-text with metadata filters. Filtering happens in SQL
+    // over the tag join tables, and the facets count the matched videos per era, region,
+    // topic and channel so callers can offer drill-downs.
+    fn advanced_search(
         &self,
         query: Option<&str>,
         era: Option<&str>,
         region: Option<&str>,
         topic: Option<&str>,
-    ) -> Result<Vec<AdvancedSearchResult>> {
-        // Build the query dynamically based on filters
-        let has_text_query = query.is_some() && !query.unwrap().trim().is_empty();
-
-        // If we have a text query, start with FTS results
-        // Otherwise, start with all videos and filter by metadata
-        let video_ids: Vec<String> = if has_text_query {
-            let mut stmt = self.conn.prepare(
-                r#"
-                SELECT DISTINCT video_id
-                FROM search_index
-                WHERE search_index MATCH ?1
-                "#
-            )?;
-            let mut ids = Vec::new();
-            let mut rows = stmt.query(params![query.unwrap()])?;
-            while let Some(row) = rows.next()? {
-                ids.push(row.get(0)?);
+        collection: Option<&str>,
+        channel: Option<&str>,
+        date_from: Option<&str>,
+        date_to: Option<&str>,
+    ) -> Result<AdvancedSearchResponse> {
+        let expanded = query.map(|q| self.expand_query(q)).transpose()?;
+        let query = expanded.as_deref();
+        let fts_query = query.map(|q| self.match_query(q)).unwrap_or_default();
+        let has_text_query = !fts_query.is_empty();
+        if query.is_some_and(|q| !q.trim().is_empty()) && !has_text_query {
+            return Ok(AdvancedSearchResponse { results: Vec::new(), facets: MetadataFacets::default() });
+        }
+
+        let mut sql = String::from("SELECT v.id FROM videos v");
+        let mut conditions = vec!["v.archived_at IS NULL".to_string()];
+        let mut args: Vec<String> = Vec::new();
+
+        if has_text_query {
+            sql.push_str(&format!(
+                " JOIN (SELECT search_index.video_id, {} AS rank FROM {} JOIN videos v ON v.id = search_index.video_id WHERE {}) s ON s.video_id = v.id",
+                self.ranking_profile()?.rank_sql(self.conn.dialect()),
+                self.search_index_from(),
+                self.search_index_matches(),
+            ));
+            args.push(fts_query);
+        }
+        for (value, join_table, tag_table, column) in [
+            (era, "video_eras", "eras", "era_id"),
+            (region, "video_regions", "regions", "region_id"),
+            (topic, "video_topics", "topics", "topic_id"),
+            (collection, "video_collections", "collections", "collection_id"),
+        ] {
+            if let Some(value) = value {
+                args.push(value.to_string());
+                conditions.push(format!(
+                    "EXISTS (SELECT 1 FROM {join_table} j JOIN {tag_table} t ON t.id = j.{column} WHERE j.video_id = v.id AND t.name = ?{} COLLATE NOCASE)",
+                    args.len()
+                ));
             }
-            ids
-        } else {
-            let mut stmt = self.conn.prepare("SELECT id FROM videos")?;
-            let mut ids = Vec::new();
-            let mut rows = stmt.query([])?;
-            while let Some(row) = rows.next()? {
-                ids.push(row.get(0)?);
-            }
-            ids
-        };
-
-        // Now filter by metadata and build results
-        let mut results = Vec::new();
-        let query_lower = query.map(|q| q.to_lowercase());
-
-        for video_id in video_ids {
-            // Check era filter
-            if let Some(era_filter) = era {
-                let video_eras = self.get_video_eras(&video_id)?;
-                if !video_eras.iter().any(|e| e.name.eq_ignore_ascii_case(era_filter)) {
-                    continue;
-                }
+        }
+        for (value, condition) in [
+            (channel, "v.channel = ?{} COLLATE NOCASE"),
+            (date_from, "v.upload_date >= ?{}"),
+            (date_to, "v.upload_date <= ?{}"),
+        ] {
+            if let Some(value) = value {
+                args.push(value.to_string());
+                conditions.push(condition.replace("{}", &args.len().to_string()));
             }
+        }
+        sql.push_str(" WHERE ");
+        sql.push_str(&conditions.join(" AND "));
+        sql.push_str(if has_text_query { " ORDER BY s.rank" } else { " ORDER BY v.added_at DESC" });
 
-            // Check region filter
-            if let Some(region_filter) = region {
-                let video_regions = self.get_video_regions(&video_id)?;
-                if !video_regions.iter().any(|r| r.name.eq_ignore_ascii_case(region_filter)) {
-                    continue;
-                }
-            }
+        let mut stmt = self.conn.prepare(&sql)?;
+        let video_ids = stmt
+            .query_map(params_from_iter(&args), |row| row.get::<_, String>(0))?
+            .collect::<sql::Result<Vec<_>>>()?;
 
-            // Check topic filter
-            if let Some(topic_filter) = topic {
-                let video_topics = self.get_video_topics(&video_id)?;
-                if !video_topics.iter().any(|t| t.name.eq_ignore_ascii_case(topic_filter)) {
-                    continue;
-                }
-            }
+        let terms = query.map(|q| (match_terms(q), near_groups(q)));
+        let mut results = Vec::new();
 
-            // Get video and transcript
-            let video = match self.get_video(&video_id)? {
+        for video_id in &video_ids {
+            let video = match self.get_video(video_id)? {
                 Some(v) => v,
                 None => continue,
             };
 
             // Find matching segments if we have a text query
             let mut matches = Vec::new();
-            if let Some(ref q_lower) = query_lower {
-                if let Some(transcript) = self.get_transcript(&video_id)? {
-                    for seg in &transcript.segments {
-                        if seg.text.to_lowercase().contains(q_lower) {
+            if let Some((ref terms, ref near)) = terms {
+                if let Some(transcript) = self.get_transcript(video_id)? {
+                    let close = near_segments(&transcript.segments, near);
+                    for (i, seg) in transcript.segments.iter().enumerate() {
+                        if segment_matches(&seg.text, terms) || close.contains(&i) {
                             matches.push(SegmentMatch {
                                 start_time: seg.start_time,
                                 duration: seg.duration,
                                 text: seg.text.clone(),
+                                speaker: seg.speaker.clone(),
                             });
                         }
                     }
@@ -2422,11 +5752,11 @@ impl Database {
             }
 
             // Get metadata
-            let eras: Vec<String> = self.get_video_eras(&video_id)?
+            let eras: Vec<String> = self.get_video_eras(video_id)?
                 .into_iter().map(|e| e.name).collect();
-            let regions: Vec<String> = self.get_video_regions(&video_id)?
+            let regions: Vec<String> = self.get_video_regions(video_id)?
                 .into_iter().map(|r| r.name).collect();
-            let topics: Vec<String> = self.get_video_topics(&video_id)?
+            let topics: Vec<String> = self.get_video_topics(video_id)?
                 .into_iter().map(|t| t.name).collect();
 
             results.push(AdvancedSearchResult {
@@ -2438,92 +5768,209 @@ impl Database {
             });
         }
 
-        Ok(results)
+        let weights: Vec<(String, usize)> = video_ids.into_iter().map(|id| (id, 1)).collect();
+        let facets = self.metadata_facets(&weights)?;
+        Ok(AdvancedSearchResponse { results, facets })
+    }
+
+    fn metadata_facets(&self, hits: &[(String, usize)]) -> Result<MetadataFacets> {
+        let hits_json = serde_json::to_string(hits)?;
+        let unpack = match self.conn.dialect() {
+            Dialect::Sqlite => "SELECT json_extract(value, '$[0]'), json_extract(value, '$[1]') FROM json_each(?1)",
+            Dialect::Postgres => "SELECT value ->> 0, CAST(value ->> 1 AS BIGINT) FROM json_array_elements(CAST(?1 AS JSON)) value",
+        };
+        let facet = |select: &str| -> Result<Vec<(String, usize)>> {
+            let mut stmt = self.conn.prepare(&format!(
+                r#"
+                WITH hits(video_id, n) AS (
+                    {unpack}
+                )
+                {select}
+                ORDER BY 2 DESC, 1
+                "#
+            ))?;
+            let counts = stmt
+                .query_map(params![hits_json], |row| Ok((row.get(0)?, row.get::<_, i64>(1)? as usize)))?
+                .collect::<sql::Result<Vec<_>>>()?;
+            Ok(counts)
+        };
+
+        Ok(MetadataFacets {
+            eras: facet("SELECT e.name, SUM(h.n) FROM hits h JOIN video_eras ve ON ve.video_id = h.video_id JOIN eras e ON e.id = ve.era_id GROUP BY e.id")?,
+            regions: facet("SELECT r.name, SUM(h.n) FROM hits h JOIN video_regions vr ON vr.video_id = h.video_id JOIN regions r ON r.id = vr.region_id GROUP BY r.id")?,
+            topics: facet("SELECT t.name, SUM(h.n) FROM hits h JOIN video_topics vt ON vt.video_id = h.video_id JOIN topics t ON t.id = vt.topic_id GROUP BY t.id")?,
+            channels: facet("SELECT v.channel, SUM(h.n) FROM hits h JOIN videos v ON v.id = h.video_id WHERE v.channel IS NOT NULL GROUP BY v.channel")?,
+        })
     }
 
     // Saved search operations
 
-    pub fn save_search(
-        &self,
-        name: &str,
-        query: Option<&str>,
-        era: Option<&str>,
-        region: Option<&str>,
-        topic: Option<&str>,
-    ) -> Result<SavedSearch> {
+    fn save_search(&self, search: &SavedSearch) -> Result<SavedSearch> {
         let created_at = Utc::now();
+        let vector_json = search.vector.as_ref().map(serde_json::to_string).transpose()?;
         self.conn.execute(
-            "INSERT INTO saved_searches (name, query, era, region, topic, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            params![name, query, era, region, topic, created_at.to_rfc3339()],
+            "INSERT INTO saved_searches (name, query, era, region, topic, channel, collection, date_from, date_to, vector_json, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                search.name,
+                search.query,
+                search.era,
+                search.region,
+                search.topic,
+                search.channel,
+                search.collection,
+                search.date_from,
+                search.date_to,
+                vector_json,
+                created_at.to_rfc3339(),
+            ],
         )?;
-        let id = self.conn.last_insert_rowid();
         Ok(SavedSearch {
-            id,
-            name: name.to_string(),
-            query: query.map(|s| s.to_string()),
-            era: era.map(|s| s.to_string()),
-            region: region.map(|s| s.to_string()),
-            topic: topic.map(|s| s.to_string()),
+            id: self.conn.last_insert_rowid(),
             created_at,
+            ..search.clone()
         })
     }
 
-    pub fn list_saved_searches(&self) -> Result<Vec<SavedSearch>> {
+    fn list_saved_searches(&self) -> Result<Vec<SavedSearch>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, name, query, era, region, topic, created_at FROM saved_searches ORDER BY name"
+            "SELECT id, name, query, era, region, topic, channel, collection, date_from, date_to, vector_json, created_at
+             FROM saved_searches ORDER BY name"
         )?;
 
         let mut searches = Vec::new();
         let mut rows = stmt.query([])?;
 
         while let Some(row) = rows.next()? {
-            let created_at: String = row.get(6)?;
-            searches.push(SavedSearch {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                query: row.get(2)?,
-                era: row.get(3)?,
-                region: row.get(4)?,
-                topic: row.get(5)?,
-                created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
-            });
+            searches.push(self.row_to_saved_search(row)?);
+        }
+        Ok(searches)
+    }
+
+    fn get_saved_search(&self, name: &str) -> Result<Option<SavedSearch>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, query, era, region, topic, channel, collection, date_from, date_to, vector_json, created_at
+             FROM saved_searches WHERE name = ?1 COLLATE NOCASE"
+        )?;
+        let mut rows = stmt.query(params![name])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(self.row_to_saved_search(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn delete_saved_search(&self, name: &str) -> Result<bool> {
+        let affected = self.conn.execute(
+            "DELETE FROM saved_searches WHERE name = ?1 COLLATE NOCASE",
+            params![name],
+        )?;
+        Ok(affected > 0)
+    }
+
+    // Aliases
+
+    fn add_alias(&self, name: &str, alias: &str) -> Result<bool> {
+        let affected = self.conn.execute(
+            "INSERT OR IGNORE INTO aliases (name, alias, created_at) VALUES (?1, ?2, ?3)",
+            params![name, alias, Utc::now().to_rfc3339()],
+        )?;
+        Ok(affected > 0)
+    }
+
+    fn remove_alias(&self, name: &str, alias: Option<&str>) -> Result<usize> {
+        Ok(self.conn.execute(
+            "DELETE FROM aliases WHERE name = ?1 AND (?2 IS NULL OR alias = ?2)",
+            params![name, alias],
+        )?)
+    }
+
+    fn list_aliases(&self, name: Option<&str>) -> Result<Vec<Alias>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, alias, created_at FROM aliases
+             WHERE ?1 IS NULL OR name = ?1 OR alias = ?1
+             ORDER BY name, alias"
+        )?;
+        let mut aliases = Vec::new();
+        let mut rows = stmt.query(params![name])?;
+        while let Some(row) = rows.next()? {
+            let created_at: String = row.get(3)?;
+            aliases.push(Alias {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                alias: row.get(2)?,
+                created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+            });
+        }
+        Ok(aliases)
+    }
+
+    fn alias_groups(&self) -> Result<Vec<Vec<String>>> {
+        let mut groups: Vec<Vec<String>> = Vec::new();
+        for a in self.list_aliases(None)? {
+            match groups.last_mut() {
+                Some(group) if group[0].eq_ignore_ascii_case(&a.name) => group.push(a.alias),
+                _ => groups.push(vec![a.name, a.alias]),
+            }
+        }
+        Ok(groups)
+    }
+
+    // Search history
+
+    fn record_search(&self, entry: &SearchHistoryEntry) -> Result<i64> {
+        let scopes = if entry.scopes.is_empty() { None } else { Some(entry.scopes.join(",")) };
+        self.conn.execute(
+            "INSERT INTO search_history (query, era, region, topic, speaker, collection, scopes, result_count, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                entry.query,
+                entry.era,
+                entry.region,
+                entry.topic,
+                entry.speaker,
+                entry.collection,
+                scopes,
+                entry.result_count,
+                entry.created_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    fn list_search_history(&self, limit: usize) -> Result<Vec<SearchHistoryEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, query, era, region, topic, speaker, collection, scopes, result_count, created_at
+             FROM search_history ORDER BY id DESC LIMIT ?1"
+        )?;
+        let mut entries = Vec::new();
+        let mut rows = stmt.query(params![limit as i64])?;
+        while let Some(row) = rows.next()? {
+            entries.push(self.row_to_search_history(row)?);
         }
-        Ok(searches)
+        Ok(entries)
     }
 
-    pub fn get_saved_search(&self, name: &str) -> Result<Option<SavedSearch>> {
+    fn get_search_history(&self, id: i64) -> Result<Option<SearchHistoryEntry>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, name, query, era, region, topic, created_at FROM saved_searches WHERE name = ?1 COLLATE NOCASE"
+            "SELECT id, query, era, region, topic, speaker, collection, scopes, result_count, created_at
+             FROM search_history WHERE id = ?1"
         )?;
-        let mut rows = stmt.query(params![name])?;
-
-        if let Some(row) = rows.next()? {
-            let created_at: String = row.get(6)?;
-            Ok(Some(SavedSearch {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                query: row.get(2)?,
-                era: row.get(3)?,
-                region: row.get(4)?,
-                topic: row.get(5)?,
-                created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
-            }))
-        } else {
-            Ok(None)
+        let mut rows = stmt.query(params![id])?;
+        match rows.next()? {
+            Some(row) => Ok(Some(self.row_to_search_history(row)?)),
+            None => Ok(None),
         }
     }
 
-    pub fn delete_saved_search(&self, name: &str) -> Result<bool> {
-        let affected = self.conn.execute(
-            "DELETE FROM saved_searches WHERE name = ?1 COLLATE NOCASE",
-            params![name],
-        )?;
-        Ok(affected > 0)
+    fn clear_search_history(&self) -> Result<usize> {
+        Ok(self.conn.execute("DELETE FROM search_history", [])?)
     }
 
     // Report generation
 
-    pub fn report_by_era(&self) -> Result<Vec<ReportEntry>> {
+    fn report_by_era(&self) -> Result<Vec<ReportEntry>> {
         let mut stmt = self.conn.prepare(
             r#"
             SELECT e.name, COUNT(DISTINCT ve.video_id) as count
@@ -2546,30 +5993,55 @@ impl Database {
         Ok(entries)
     }
 
-    pub fn report_by_region(&self) -> Result<Vec<ReportEntry>> {
+    fn report_by_region(&self) -> Result<Vec<ReportEntry>> {
+        let mut entries: Vec<ReportEntry> = self
+            .region_video_counts()?
+            .into_iter()
+            .map(|r| ReportEntry { name: r.name, video_count: r.subtree_video_count })
+            .collect();
+        entries.sort_by(|a, b| b.video_count.cmp(&a.video_count).then_with(|| a.name.cmp(&b.name)));
+        Ok(entries)
+    }
+
+    fn region_video_counts(&self) -> Result<Vec<RegionCount>> {
         let mut stmt = self.conn.prepare(
             r#"
-            SELECT r.name, COUNT(DISTINCT vr.video_id) as count
-            FROM regions r
-            LEFT JOIN video_regions vr ON vr.region_id = r.id
-            GROUP BY r.id, r.name
-            ORDER BY count DESC, r.name
+            WITH RECURSIVE tree(ancestor, id) AS (
+                SELECT id, id FROM regions
+                UNION
+                SELECT t.ancestor, r.id FROM tree t JOIN regions r ON r.parent_id = t.id
+            )
+            SELECT a.id, a.name, a.parent_id,
+                   (SELECT COUNT(*) FROM video_regions WHERE region_id = a.id),
+                   COUNT(DISTINCT vr.video_id)
+            FROM regions a
+            JOIN tree t ON t.ancestor = a.id
+            LEFT JOIN video_regions vr ON vr.region_id = t.id
+            GROUP BY a.id
+            ORDER BY a.name
             "#
         )?;
 
-        let mut entries = Vec::new();
+        let mut counts = Vec::new();
         let mut rows = stmt.query([])?;
 
         while let Some(row) = rows.next()? {
-            entries.push(ReportEntry {
-                name: row.get(0)?,
-                video_count: row.get(1)?,
+            counts.push(RegionCount {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                parent_id: row.get(2)?,
+                video_count: row.get(3)?,
+                subtree_video_count: row.get(4)?,
             });
         }
-        Ok(entries)
+        Ok(counts)
+    }
+
+    fn count_region_tagged_videos(&self) -> Result<i64> {
+        Ok(self.conn.query_row("SELECT COUNT(DISTINCT video_id) FROM video_regions", [], |row| row.get(0))?)
     }
 
-    pub fn report_by_topic(&self) -> Result<Vec<ReportEntry>> {
+    fn report_by_topic(&self) -> Result<Vec<ReportEntry>> {
         let mut stmt = self.conn.prepare(
             r#"
             SELECT t.name, COUNT(DISTINCT vt.video_id) as count
@@ -2594,7 +6066,7 @@ impl Database {
 
     // Export functions
 
-    pub fn export_collection_markdown(&self, collection_name: &str) -> Result<Option<String>> {
+    fn export_collection_markdown(&self, collection_name: &str) -> Result<Option<String>> {
         let collection = match self.get_collection_by_name(collection_name)? {
             Some(c) => c,
             None => return Ok(None),
@@ -2673,7 +6145,7 @@ impl Database {
         Ok(Some(md))
     }
 
-    pub fn export_map_geojson(&self, era: Option<&str>, topic: Option<&str>) -> Result<GeoJsonCollection> {
+    fn export_map_geojson(&self, era: Option<&str>, topic: Option<&str>) -> Result<GeoJsonCollection> {
         let pins = self.get_map_pins(era, topic)?;
 
         let features: Vec<GeoJsonFeature> = pins.iter().map(|pin| {
@@ -2700,7 +6172,7 @@ impl Database {
         })
     }
 
-    pub fn get_summary_stats(&self) -> Result<(i64, i64, i64, i64, i64, i64, i64, i64, i64)> {
+    fn get_summary_stats(&self) -> Result<(i64, i64, i64, i64, i64, i64, i64, i64, i64)> {
         let video_count: i64 = self.conn.query_row(
             "SELECT COUNT(*) FROM videos", [], |row| row.get(0)
         )?;
@@ -2736,7 +6208,7 @@ impl Database {
 
     // Claim operations
 
-    pub fn create_claim(
+    fn create_claim(
         &self,
         text: &str,
         video_id: &str,
@@ -2771,12 +6243,176 @@ impl Database {
             category,
             confidence,
             created_at,
+            status: ClaimStatus::Unverified,
+            date_start: None,
+            date_end: None,
         })
     }
 
-    pub fn get_claim(&self, id: i64) -> Result<Option<Claim>> {
+    fn tag_claim_topic(&self, claim_id: i64, topic_id: i64) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO claim_topics (claim_id, topic_id) VALUES (?1, ?2)",
+            params![claim_id, topic_id],
+        )?;
+        Ok(())
+    }
+
+    fn untag_claim_topic(&self, claim_id: i64, topic_id: i64) -> Result<bool> {
+        let affected = self.conn.execute(
+            "DELETE FROM claim_topics WHERE claim_id = ?1 AND topic_id = ?2",
+            params![claim_id, topic_id],
+        )?;
+        Ok(affected > 0)
+    }
+
+    fn tag_claim_era(&self, claim_id: i64, era_id: i64) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO claim_eras (claim_id, era_id) VALUES (?1, ?2)",
+            params![claim_id, era_id],
+        )?;
+        Ok(())
+    }
+
+    fn untag_claim_era(&self, claim_id: i64, era_id: i64) -> Result<bool> {
+        let affected = self.conn.execute(
+            "DELETE FROM claim_eras WHERE claim_id = ?1 AND era_id = ?2",
+            params![claim_id, era_id],
+        )?;
+        Ok(affected > 0)
+    }
+
+    fn get_claim_topics(&self, claim_id: i64) -> Result<Vec<Topic>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT t.id, t.name
+            FROM topics t
+            JOIN claim_topics ct ON ct.topic_id = t.id
+            WHERE ct.claim_id = ?1
+            ORDER BY t.name
+            "#
+        )?;
+
+        let mut topics = Vec::new();
+        let mut rows = stmt.query(params![claim_id])?;
+
+        while let Some(row) = rows.next()? {
+            topics.push(Topic {
+                id: row.get(0)?,
+                name: row.get(1)?,
+            });
+        }
+        Ok(topics)
+    }
+
+    fn get_claim_eras(&self, claim_id: i64) -> Result<Vec<Era>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT e.id, e.name, e.sort_order, e.start_year, e.end_year
+            FROM eras e
+            JOIN claim_eras ce ON ce.era_id = e.id
+            WHERE ce.claim_id = ?1
+            ORDER BY e.sort_order
+            "#
+        )?;
+
+        let mut eras = Vec::new();
+        let mut rows = stmt.query(params![claim_id])?;
+
+        while let Some(row) = rows.next()? {
+            eras.push(Era {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                sort_order: row.get(2)?,
+                start_year: row.get(3)?,
+                end_year: row.get(4)?,
+            });
+        }
+        Ok(eras)
+    }
+
+    fn list_claims_by_topic(&self, topic: &str) -> Result<Vec<Claim>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT c.id, c.text, c.video_id, c.timestamp, c.source_quote, c.category, c.confidence, c.created_at, c.status, c.date_start, c.date_end
+            FROM claims c
+            WHERE c.archived_at IS NULL AND (
+                c.id IN (SELECT ct.claim_id FROM claim_topics ct JOIN topics t ON t.id = ct.topic_id WHERE t.name = ?1 COLLATE NOCASE)
+                OR c.video_id IN (SELECT vt.video_id FROM video_topics vt JOIN topics t ON t.id = vt.topic_id WHERE t.name = ?1 COLLATE NOCASE)
+            )
+            ORDER BY c.created_at DESC
+            "#
+        )?;
+
+        let mut claims = Vec::new();
+        let mut rows = stmt.query(params![topic])?;
+
+        while let Some(row) = rows.next()? {
+            claims.push(self.row_to_claim(row)?);
+        }
+        Ok(claims)
+    }
+
+    fn list_claims_by_era(&self, era: &str) -> Result<Vec<Claim>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT c.id, c.text, c.video_id, c.timestamp, c.source_quote, c.category, c.confidence, c.created_at, c.status, c.date_start, c.date_end
+            FROM claims c
+            WHERE c.archived_at IS NULL AND (
+                c.id IN (SELECT ce.claim_id FROM claim_eras ce JOIN eras e ON e.id = ce.era_id WHERE e.name = ?1 COLLATE NOCASE)
+                OR c.video_id IN (SELECT ve.video_id FROM video_eras ve JOIN eras e ON e.id = ve.era_id WHERE e.name = ?1 COLLATE NOCASE)
+            )
+            ORDER BY c.created_at DESC
+            "#
+        )?;
+
+        let mut claims = Vec::new();
+        let mut rows = stmt.query(params![era])?;
+
+        while let Some(row) = rows.next()? {
+            claims.push(self.row_to_claim(row)?);
+        }
+        Ok(claims)
+    }
+
+    fn set_claim_status(&self, id: i64, status: ClaimStatus) -> Result<bool> {
+        let affected = self.conn.execute(
+            "UPDATE claims SET status = ?1 WHERE id = ?2",
+            params![status.as_str(), id],
+        )?;
+        Ok(affected > 0)
+    }
+
+    fn set_claim_dates(&self, id: i64, start: Option<i32>, end: Option<i32>) -> Result<bool> {
+        let affected = self.conn.execute(
+            "UPDATE claims SET date_start = ?1, date_end = ?2 WHERE id = ?3",
+            params![start, end, id],
+        )?;
+        Ok(affected > 0)
+    }
+
+    fn list_dated_claims(&self, from: Option<i32>, to: Option<i32>) -> Result<Vec<Claim>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT id, text, video_id, timestamp, source_quote, category, confidence, created_at, status, date_start, date_end
+            FROM claims
+            WHERE archived_at IS NULL AND date_start IS NOT NULL
+              AND (?1 IS NULL OR COALESCE(date_end, date_start) >= ?1)
+              AND (?2 IS NULL OR date_start <= ?2)
+            ORDER BY date_start, COALESCE(date_end, date_start), id
+            "#
+        )?;
+
+        let mut claims = Vec::new();
+        let mut rows = stmt.query(params![from, to])?;
+        while let Some(row) = rows.next()? {
+            claims.push(self.row_to_claim(row)?);
+        }
+        Ok(claims)
+    }
+
+    fn get_claim(&self, id: i64) -> Result<Option<Claim>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, text, video_id, timestamp, source_quote, category, confidence, created_at FROM claims WHERE id = ?1"
+            "SELECT id, text, video_id, timestamp, source_quote, category, confidence, created_at, status, date_start, date_end FROM claims WHERE id = ?1"
         )?;
         let mut rows = stmt.query(params![id])?;
 
@@ -2787,73 +6423,294 @@ impl Database {
         }
     }
 
-    pub fn list_claims_for_video(&self, video_id: &str) -> Result<Vec<Claim>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, text, video_id, timestamp, source_quote, category, confidence, created_at FROM claims WHERE video_id = ?1 ORDER BY timestamp NULLS LAST, created_at"
+    fn list_claims_for_video(&self, video_id: &str) -> Result<Vec<Claim>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, text, video_id, timestamp, source_quote, category, confidence, created_at, status, date_start, date_end FROM claims WHERE video_id = ?1 AND archived_at IS NULL ORDER BY timestamp NULLS LAST, created_at"
+        )?;
+
+        let mut claims = Vec::new();
+        let mut rows = stmt.query(params![video_id])?;
+
+        while let Some(row) = rows.next()? {
+            claims.push(self.row_to_claim(row)?);
+        }
+        Ok(claims)
+    }
+
+    fn list_claims_by_category(&self, category: ClaimCategory) -> Result<Vec<Claim>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, text, video_id, timestamp, source_quote, category, confidence, created_at, status, date_start, date_end FROM claims WHERE category = ?1 AND archived_at IS NULL ORDER BY created_at DESC"
+        )?;
+
+        let mut claims = Vec::new();
+        let mut rows = stmt.query(params![category.as_str()])?;
+
+        while let Some(row) = rows.next()? {
+            claims.push(self.row_to_claim(row)?);
+        }
+        Ok(claims)
+    }
+
+    fn list_all_claims(&self) -> Result<Vec<Claim>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, text, video_id, timestamp, source_quote, category, confidence, created_at, status, date_start, date_end FROM claims WHERE archived_at IS NULL ORDER BY created_at DESC"
+        )?;
+
+        let mut claims = Vec::new();
+        let mut rows = stmt.query([])?;
+
+        while let Some(row) = rows.next()? {
+            claims.push(self.row_to_claim(row)?);
+        }
+        Ok(claims)
+    }
+
+    fn max_claim_id(&self) -> Result<i64> {
+        Ok(self.conn.query_row("SELECT COALESCE(MAX(id), 0) FROM claims", [], |row| row.get(0))?)
+    }
+
+    fn list_claims_after(&self, after: i64, limit: usize) -> Result<Vec<Claim>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, text, video_id, timestamp, source_quote, category, confidence, created_at, status, date_start, date_end FROM claims WHERE id > ?1 AND archived_at IS NULL ORDER BY id LIMIT ?2"
+        )?;
+
+        let mut claims = Vec::new();
+        let mut rows = stmt.query(params![after, limit as i64])?;
+
+        while let Some(row) = rows.next()? {
+            claims.push(self.row_to_claim(row)?);
+        }
+        Ok(claims)
+    }
+
+    fn get_all_claims_limited(&self, limit: usize) -> Result<Vec<Claim>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, text, video_id, timestamp, source_quote, category, confidence, created_at, status, date_start, date_end FROM claims WHERE archived_at IS NULL ORDER BY created_at DESC LIMIT ?1"
+        )?;
+
+        let mut claims = Vec::new();
+        let mut rows = stmt.query(params![limit as i64])?;
+
+        while let Some(row) = rows.next()? {
+            claims.push(self.row_to_claim(row)?);
+        }
+        Ok(claims)
+    }
+
+    fn find_duplicate_claims(&self, threshold: f32) -> Result<Vec<DuplicateClaims>> {
+        let claims: HashMap<i64, Claim> = self.list_all_claims()?.into_iter().map(|c| (c.id, c)).collect();
+        let mut embeddings: Vec<(i64, Embedding)> = self
+            .list_embeddings_by_type(EmbeddingSource::Claim)?
+            .into_iter()
+            .filter_map(|e| e.source_id.parse().ok().filter(|id| claims.contains_key(id)).map(|id| (id, e)))
+            .collect();
+        embeddings.sort_by_key(|(id, _)| *id);
+
+        let mut pairs = Vec::new();
+        for (i, (id_a, a)) in embeddings.iter().enumerate() {
+            for (id_b, b) in &embeddings[i + 1..] {
+                if id_a == id_b || a.model != b.model {
+                    continue;
+                }
+                let similarity = cosine_similarity(&a.vector, &b.vector);
+                if similarity >= threshold {
+                    pairs.push(DuplicateClaims {
+                        claim: claims[id_a].clone(),
+                        duplicate: claims[id_b].clone(),
+                        similarity,
+                    });
+                }
+            }
+        }
+        pairs.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap_or(std::cmp::Ordering::Equal));
+        Ok(pairs)
+    }
+
+    fn merge_claims(&self, keep: i64, duplicate: i64) -> Result<usize> {
+        if keep == duplicate {
+            anyhow::bail!("Cannot merge claim #{} into itself", keep);
+        }
+        for id in [keep, duplicate] {
+            if self.get_claim(id)?.is_none() {
+                anyhow::bail!("Claim not found: #{}", id);
+            }
+        }
+
+        let tx = self.conn.unchecked_transaction()?;
+        let mut moved = 0;
+        for (table, column, _) in self.foreign_keys_to("claims")? {
+            // Rows the kept claim already has (same MOC, same link) stay on the duplicate
+            // and are deleted with it
+            moved += self.conn.execute(
+                &format!("UPDATE OR IGNORE \"{}\" SET \"{}\" = ?1 WHERE \"{}\" = ?2", table, column, column),
+                params![keep, duplicate],
+            )?;
+        }
+        // A link between the two now points the kept claim at itself
+        self.conn.execute(
+            "DELETE FROM claim_links WHERE source_claim_id = ?1 AND target_claim_id = ?1",
+            params![keep],
+        )?;
+        self.conn.execute(
+            "DELETE FROM causal_relations WHERE cause_claim_id = ?1 AND effect_claim_id = ?1",
+            params![keep],
+        )?;
+        // The duplicate's embedding is only kept for models the kept claim has none for
+        self.conn.execute(
+            "UPDATE OR IGNORE embeddings SET source_id = ?1 WHERE source_type = 'claim' AND source_id = ?2",
+            params![keep.to_string(), duplicate.to_string()],
+        )?;
+        self.delete_claim_and_dependents(duplicate)?;
+        tx.commit()?;
+        Ok(moved)
+    }
+
+    fn delete_claim(&self, id: i64) -> Result<bool> {
+        if self.get_claim(id)?.is_none() {
+            return Ok(false);
+        }
+        let tx = self.conn.unchecked_transaction()?;
+        self.delete_claim_and_dependents(id)?;
+        tx.commit()?;
+        Ok(true)
+    }
+
+    // Soft delete: archived rows stay in the database but drop out of lists, search and the graph
+
+    fn archive_video(&self, id: &str) -> Result<bool> {
+        let now = Utc::now().to_rfc3339();
+        let affected = self.conn.execute(
+            "UPDATE videos SET archived_at = ?1 WHERE id = ?2 AND archived_at IS NULL",
+            params![now, id],
+        )?;
+        if affected > 0 {
+            self.conn.execute(
+                "UPDATE claims SET archived_at = ?1 WHERE video_id = ?2 AND archived_at IS NULL",
+                params![now, id],
+            )?;
+        }
+        Ok(affected > 0)
+    }
+
+    fn unarchive_video(&self, id: &str) -> Result<bool> {
+        let archived_at: Option<String> = self.conn.query_row(
+            "SELECT archived_at FROM videos WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        ).optional()?.flatten();
+        let Some(archived_at) = archived_at else {
+            return Ok(false);
+        };
+        self.conn.execute(
+            "UPDATE claims SET archived_at = NULL WHERE video_id = ?1 AND archived_at = ?2",
+            params![id, archived_at],
         )?;
+        self.conn.execute("UPDATE videos SET archived_at = NULL WHERE id = ?1", params![id])?;
+        Ok(true)
+    }
 
-        let mut claims = Vec::new();
-        let mut rows = stmt.query(params![video_id])?;
+    fn archive_claim(&self, id: i64) -> Result<bool> {
+        let affected = self.conn.execute(
+            "UPDATE claims SET archived_at = ?1 WHERE id = ?2 AND archived_at IS NULL",
+            params![Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(affected > 0)
+    }
 
-        while let Some(row) = rows.next()? {
-            claims.push(self.row_to_claim(row)?);
-        }
-        Ok(claims)
+    fn unarchive_claim(&self, id: i64) -> Result<bool> {
+        let affected = self.conn.execute(
+            "UPDATE claims SET archived_at = NULL WHERE id = ?1 AND archived_at IS NOT NULL",
+            params![id],
+        )?;
+        Ok(affected > 0)
     }
 
-    pub fn list_claims_by_category(&self, category: ClaimCategory) -> Result<Vec<Claim>> {
+    fn list_archived_videos(&self, before: Option<DateTime<Utc>>) -> Result<Vec<(Video, DateTime<Utc>)>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, text, video_id, timestamp, source_quote, category, confidence, created_at FROM claims WHERE category = ?1 ORDER BY created_at DESC"
+            "SELECT id, url, title, channel, upload_date, description, added_at, duration, view_count, thumbnail_url, archived_at
+             FROM videos
+             WHERE archived_at IS NOT NULL AND (?1 IS NULL OR archived_at < ?1)
+             ORDER BY archived_at"
         )?;
-
-        let mut claims = Vec::new();
-        let mut rows = stmt.query(params![category.as_str()])?;
-
+        let mut videos = Vec::new();
+        let mut rows = stmt.query(params![before.map(|b| b.to_rfc3339())])?;
         while let Some(row) = rows.next()? {
-            claims.push(self.row_to_claim(row)?);
+            let archived_at: String = row.get(10)?;
+            videos.push((
+                self.row_to_video(row)?,
+                DateTime::parse_from_rfc3339(&archived_at)?.with_timezone(&Utc),
+            ));
         }
-        Ok(claims)
+        Ok(videos)
     }
 
-    pub fn list_all_claims(&self) -> Result<Vec<Claim>> {
+    fn list_archived_claims(&self, before: Option<DateTime<Utc>>) -> Result<Vec<(Claim, DateTime<Utc>)>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, text, video_id, timestamp, source_quote, category, confidence, created_at FROM claims ORDER BY created_at DESC"
+            "SELECT c.id, c.text, c.video_id, c.timestamp, c.source_quote, c.category, c.confidence, c.created_at, c.status, c.date_start, c.date_end, c.archived_at
+             FROM claims c
+             LEFT JOIN videos v ON v.id = c.video_id
+             WHERE c.archived_at IS NOT NULL AND v.archived_at IS NULL AND (?1 IS NULL OR c.archived_at < ?1)
+             ORDER BY c.archived_at"
         )?;
-
         let mut claims = Vec::new();
-        let mut rows = stmt.query([])?;
-
+        let mut rows = stmt.query(params![before.map(|b| b.to_rfc3339())])?;
         while let Some(row) = rows.next()? {
-            claims.push(self.row_to_claim(row)?);
+            let archived_at: String = row.get(11)?;
+            claims.push((
+                self.row_to_claim(row)?,
+                DateTime::parse_from_rfc3339(&archived_at)?.with_timezone(&Utc),
+            ));
         }
         Ok(claims)
     }
 
-    pub fn get_all_claims_limited(&self, limit: usize) -> Result<Vec<Claim>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, text, video_id, timestamp, source_quote, category, confidence, created_at FROM claims ORDER BY created_at DESC LIMIT ?1"
+    fn purge_video(&self, id: &str) -> Result<bool> {
+        let archived: bool = self.conn.query_row(
+            "SELECT COUNT(*) > 0 FROM videos WHERE id = ?1 AND archived_at IS NOT NULL",
+            params![id],
+            |row| row.get(0),
         )?;
+        if !archived {
+            return Ok(false);
+        }
 
-        let mut claims = Vec::new();
-        let mut rows = stmt.query(params![limit as i64])?;
-
-        while let Some(row) = rows.next()? {
-            claims.push(self.row_to_claim(row)?);
+        let tx = self.conn.unchecked_transaction()?;
+        let mut stmt = self.conn.prepare("SELECT id FROM claims WHERE video_id = ?1")?;
+        let claim_ids = stmt.query_map(params![id], |row| row.get(0))?.collect::<sql::Result<Vec<i64>>>()?;
+        for claim_id in claim_ids {
+            self.delete_claim_and_dependents(claim_id)?;
         }
-        Ok(claims)
+        self.delete_dependents("videos", &id)?;
+        self.conn.execute("DELETE FROM embeddings WHERE source_type = 'video' AND source_id = ?1", params![id])?;
+        self.conn.execute("DELETE FROM search_index WHERE video_id = ?1", params![id])?;
+        self.conn.execute("DELETE FROM videos WHERE id = ?1", params![id])?;
+        tx.commit()?;
+        Ok(true)
     }
 
-    pub fn delete_claim(&self, id: i64) -> Result<bool> {
-        let affected = self.conn.execute("DELETE FROM claims WHERE id = ?1", params![id])?;
-        Ok(affected > 0)
+    fn purge_claim(&self, id: i64) -> Result<bool> {
+        let archived: bool = self.conn.query_row(
+            "SELECT COUNT(*) > 0 FROM claims WHERE id = ?1 AND archived_at IS NOT NULL",
+            params![id],
+            |row| row.get(0),
+        )?;
+        if !archived {
+            return Ok(false);
+        }
+        let tx = self.conn.unchecked_transaction()?;
+        self.delete_claim_and_dependents(id)?;
+        tx.commit()?;
+        Ok(true)
     }
 
-    pub fn update_claim(
+    fn update_claim(
         &self,
         id: i64,
         text: Option<&str>,
+        source_quote: Option<&str>,
         category: Option<ClaimCategory>,
         confidence: Option<Confidence>,
+        timestamp: Option<Option<f64>>,
     ) -> Result<bool> {
         let mut updates = Vec::new();
         let mut param_idx = 1;
@@ -2862,6 +6719,10 @@ impl Database {
             updates.push(format!("text = ?{}", param_idx));
             param_idx += 1;
         }
+        if source_quote.is_some() {
+            updates.push(format!("source_quote = ?{}", param_idx));
+            param_idx += 1;
+        }
         if category.is_some() {
             updates.push(format!("category = ?{}", param_idx));
             param_idx += 1;
@@ -2870,6 +6731,10 @@ impl Database {
             updates.push(format!("confidence = ?{}", param_idx));
             param_idx += 1;
         }
+        if timestamp.is_some() {
+            updates.push(format!("timestamp = ?{}", param_idx));
+            param_idx += 1;
+        }
 
         if updates.is_empty() {
             return Ok(false);
@@ -2885,12 +6750,18 @@ impl Database {
         if let Some(t) = text {
             params_vec.push(Box::new(t.to_string()));
         }
+        if let Some(q) = source_quote {
+            params_vec.push(Box::new(q.to_string()));
+        }
         if let Some(c) = category {
             params_vec.push(Box::new(c.as_str().to_string()));
         }
         if let Some(c) = confidence {
             params_vec.push(Box::new(c.as_str().to_string()));
         }
+        if let Some(t) = timestamp {
+            params_vec.push(Box::new(t));
+        }
         params_vec.push(Box::new(id));
 
         let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
@@ -2898,26 +6769,9 @@ impl Database {
         Ok(affected > 0)
     }
 
-    fn row_to_claim(&self, row: &rusqlite::Row) -> Result<Claim> {
-        let category_str: String = row.get(5)?;
-        let confidence_str: String = row.get(6)?;
-        let created_at: String = row.get(7)?;
-
-        Ok(Claim {
-            id: row.get(0)?,
-            text: row.get(1)?,
-            video_id: row.get(2)?,
-            timestamp: row.get(3)?,
-            source_quote: row.get(4)?,
-            category: ClaimCategory::from_str(&category_str).unwrap_or(ClaimCategory::Factual),
-            confidence: Confidence::from_str(&confidence_str).unwrap_or(Confidence::Medium),
-            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
-        })
-    }
-
     // Claim link operations
 
-    pub fn create_claim_link(
+    fn create_claim_link(
         &self,
         source_claim_id: i64,
         target_claim_id: i64,
@@ -2938,7 +6792,7 @@ impl Database {
         })
     }
 
-    pub fn delete_claim_link(&self, source_id: i64, target_id: i64) -> Result<bool> {
+    fn delete_claim_link(&self, source_id: i64, target_id: i64) -> Result<bool> {
         let affected = self.conn.execute(
             "DELETE FROM claim_links WHERE source_claim_id = ?1 AND target_claim_id = ?2",
             params![source_id, target_id],
@@ -2946,7 +6800,7 @@ impl Database {
         Ok(affected > 0)
     }
 
-    pub fn get_claim_with_links(&self, claim_id: i64) -> Result<Option<ClaimWithLinks>> {
+    fn get_claim_with_links(&self, claim_id: i64) -> Result<Option<ClaimWithLinks>> {
         let claim = match self.get_claim(claim_id)? {
             Some(c) => c,
             None => return Ok(None),
@@ -2956,7 +6810,7 @@ impl Database {
         let mut out_stmt = self.conn.prepare(
             r#"
             SELECT cl.id, cl.source_claim_id, cl.target_claim_id, cl.link_type, cl.created_at,
-                   c.id, c.text, c.video_id, c.timestamp, c.source_quote, c.category, c.confidence, c.created_at
+                   c.id, c.text, c.video_id, c.timestamp, c.source_quote, c.category, c.confidence, c.created_at, c.status, c.date_start, c.date_end
             FROM claim_links cl
             JOIN claims c ON c.id = cl.target_claim_id
             WHERE cl.source_claim_id = ?1
@@ -2975,7 +6829,7 @@ impl Database {
         let mut in_stmt = self.conn.prepare(
             r#"
             SELECT cl.id, cl.source_claim_id, cl.target_claim_id, cl.link_type, cl.created_at,
-                   c.id, c.text, c.video_id, c.timestamp, c.source_quote, c.category, c.confidence, c.created_at
+                   c.id, c.text, c.video_id, c.timestamp, c.source_quote, c.category, c.confidence, c.created_at, c.status, c.date_start, c.date_end
             FROM claim_links cl
             JOIN claims c ON c.id = cl.source_claim_id
             WHERE cl.target_claim_id = ?1
@@ -2997,12 +6851,12 @@ impl Database {
         }))
     }
 
-    pub fn get_unlinked_claims(&self) -> Result<Vec<Claim>> {
+    fn get_unlinked_claims(&self) -> Result<Vec<Claim>> {
         let mut stmt = self.conn.prepare(
             r#"
-            SELECT c.id, c.text, c.video_id, c.timestamp, c.source_quote, c.category, c.confidence, c.created_at
+            SELECT c.id, c.text, c.video_id, c.timestamp, c.source_quote, c.category, c.confidence, c.created_at, c.status, c.date_start, c.date_end
             FROM claims c
-            WHERE (
+            WHERE c.archived_at IS NULL AND (
                 SELECT COUNT(*) FROM claim_links cl
                 WHERE cl.source_claim_id = c.id OR cl.target_claim_id = c.id
             ) < 2
@@ -3019,7 +6873,7 @@ impl Database {
         Ok(claims)
     }
 
-    pub fn get_claim_link_count(&self, claim_id: i64) -> Result<i64> {
+    fn get_claim_link_count(&self, claim_id: i64) -> Result<i64> {
         let count: i64 = self.conn.query_row(
             "SELECT COUNT(*) FROM claim_links WHERE source_claim_id = ?1 OR target_claim_id = ?1",
             params![claim_id],
@@ -3028,39 +6882,411 @@ impl Database {
         Ok(count)
     }
 
-    fn row_to_claim_link(&self, row: &rusqlite::Row) -> Result<ClaimLink> {
-        let link_type_str: String = row.get(3)?;
-        let created_at: String = row.get(4)?;
+    fn list_all_claim_links(&self) -> Result<Vec<ClaimLink>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, source_claim_id, target_claim_id, link_type, created_at FROM claim_links ORDER BY id"
+        )?;
 
-        Ok(ClaimLink {
-            id: row.get(0)?,
-            source_claim_id: row.get(1)?,
-            target_claim_id: row.get(2)?,
-            link_type: LinkType::from_str(&link_type_str).unwrap_or(LinkType::Related),
-            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
-        })
+        let mut links = Vec::new();
+        let mut rows = stmt.query([])?;
+
+        while let Some(row) = rows.next()? {
+            links.push(self.row_to_claim_link(row)?);
+        }
+        Ok(links)
     }
 
-    fn row_to_claim_from_offset(&self, row: &rusqlite::Row, offset: usize) -> Result<Claim> {
-        let category_str: String = row.get(offset + 5)?;
-        let confidence_str: String = row.get(offset + 6)?;
-        let created_at: String = row.get(offset + 7)?;
+    fn claim_graph(&self, video_id: Option<&str>, moc_id: Option<i64>, era: Option<&str>) -> Result<ClaimGraph> {
+        let mut claims = self.list_all_claims()?;
+        if let Some(video_id) = video_id {
+            claims.retain(|c| c.video_id == video_id);
+        }
+        if let Some(moc_id) = moc_id {
+            let Some(moc) = self.get_moc_with_claims(moc_id)? else {
+                anyhow::bail!("MOC not found: {}", moc_id);
+            };
+            let ids: HashSet<i64> = moc.claims.iter().map(|c| c.id).collect();
+            claims.retain(|c| ids.contains(&c.id));
+        }
+        if let Some(era) = era {
+            let ids: HashSet<i64> = self.list_claims_by_era(era)?.into_iter().map(|c| c.id).collect();
+            claims.retain(|c| ids.contains(&c.id));
+        }
+        claims.sort_by_key(|c| c.id);
 
-        Ok(Claim {
-            id: row.get(offset)?,
-            text: row.get(offset + 1)?,
-            video_id: row.get(offset + 2)?,
-            timestamp: row.get(offset + 3)?,
-            source_quote: row.get(offset + 4)?,
-            category: ClaimCategory::from_str(&category_str).unwrap_or(ClaimCategory::Factual),
-            confidence: Confidence::from_str(&confidence_str).unwrap_or(Confidence::Medium),
-            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
-        })
+        let ids: HashSet<i64> = claims.iter().map(|c| c.id).collect();
+        let links = self
+            .list_all_claim_links()?
+            .into_iter()
+            .filter(|l| ids.contains(&l.source_claim_id) && ids.contains(&l.target_claim_id))
+            .collect();
+        let mut causal_relations: Vec<CausalRelation> = self
+            .list_all_causal_relations()?
+            .into_iter()
+            .filter(|r| ids.contains(&r.cause_claim_id) && ids.contains(&r.effect_claim_id))
+            .collect();
+        causal_relations.sort_by_key(|r| r.id);
+
+        let mut citations = HashMap::new();
+        for claim in &claims {
+            let cited = self.claim_citations(claim.id)?;
+            if !cited.is_empty() {
+                citations.insert(claim.id, cited);
+            }
+        }
+
+        Ok(ClaimGraph { claims, links, causal_relations, citations })
+    }
+
+    fn claim_graph_page(&self, filter: &ClaimGraphFilter, limit: usize, offset: usize) -> Result<ClaimGraphPage> {
+        let mut conditions = vec!["c.archived_at IS NULL".to_string()];
+        let mut args: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+        // Placeholders for the values pushed, numbered from the next free one
+        fn placeholders(args: &mut Vec<Box<dyn rusqlite::ToSql>>, values: Vec<Box<dyn rusqlite::ToSql>>) -> String {
+            let start = args.len() + 1;
+            let list: Vec<String> = (start..start + values.len()).map(|i| format!("?{}", i)).collect();
+            args.extend(values);
+            list.join(", ")
+        }
+
+        if let Some(video_id) = &filter.video_id {
+            let p = placeholders(&mut args, vec![Box::new(video_id.clone())]);
+            conditions.push(format!("c.video_id = {}", p));
+        }
+        if let Some(moc_id) = filter.moc_id {
+            let p = placeholders(&mut args, vec![Box::new(moc_id)]);
+            conditions.push(format!("c.id IN (SELECT claim_id FROM moc_claims WHERE moc_id = {})", p));
+        }
+        if !filter.eras.is_empty() {
+            let p = placeholders(&mut args, filter.eras.iter().map(|e| Box::new(e.clone()) as Box<dyn rusqlite::ToSql>).collect());
+            conditions.push(format!(
+                "(c.id IN (SELECT ce.claim_id FROM claim_eras ce JOIN eras e ON e.id = ce.era_id WHERE e.name COLLATE NOCASE IN ({p}))
+                  OR c.video_id IN (SELECT ve.video_id FROM video_eras ve JOIN eras e ON e.id = ve.era_id WHERE e.name COLLATE NOCASE IN ({p})))",
+                p = p
+            ));
+        }
+        if let Some(topic) = &filter.topic {
+            let p = placeholders(&mut args, vec![Box::new(topic.clone())]);
+            conditions.push(format!(
+                "(c.id IN (SELECT ct.claim_id FROM claim_topics ct JOIN topics t ON t.id = ct.topic_id WHERE t.name = {p} COLLATE NOCASE)
+                  OR c.video_id IN (SELECT vt.video_id FROM video_topics vt JOIN topics t ON t.id = vt.topic_id WHERE t.name = {p} COLLATE NOCASE))",
+                p = p
+            ));
+        }
+        if !filter.statuses.is_empty() {
+            let p = placeholders(&mut args, filter.statuses.iter().map(|s| Box::new(s.as_str()) as Box<dyn rusqlite::ToSql>).collect());
+            conditions.push(format!("c.status IN ({})", p));
+        }
+        if !filter.categories.is_empty() {
+            let p = placeholders(&mut args, filter.categories.iter().map(|c| Box::new(c.as_str()) as Box<dyn rusqlite::ToSql>).collect());
+            conditions.push(format!("c.category IN ({})", p));
+        }
+        if let Some(min) = filter.min_confidence {
+            let levels: &[Confidence] = match min {
+                Confidence::High => &[Confidence::High],
+                Confidence::Medium => &[Confidence::High, Confidence::Medium],
+                Confidence::Low => &[Confidence::High, Confidence::Medium, Confidence::Low],
+            };
+            let p = placeholders(&mut args, levels.iter().map(|c| Box::new(c.as_str()) as Box<dyn rusqlite::ToSql>).collect());
+            conditions.push(format!("c.confidence IN ({})", p));
+        }
+        let where_clause = conditions.join(" AND ");
+        let filter_args: Vec<&dyn rusqlite::ToSql> = args.iter().map(|a| a.as_ref()).collect();
+        // Link types are fixed names, so they go in as literals and both link queries
+        // can share the claim filter's arguments
+        let link_type_clause = if filter.link_types.is_empty() {
+            String::new()
+        } else {
+            let names: Vec<String> = filter.link_types.iter().map(|t| format!("'{}'", t.as_str())).collect();
+            format!(" AND l.link_type IN ({})", names.join(", "))
+        };
+
+        let total: i64 = self.conn.query_row(
+            &format!("SELECT COUNT(*) FROM claims c WHERE {}", where_clause),
+            filter_args.as_slice(),
+            |row| row.get(0),
+        )?;
+
+        // The page itself, shared by both queries
+        let page = format!(
+            "SELECT c.id FROM claims c WHERE {} ORDER BY c.created_at DESC, c.id DESC LIMIT {} OFFSET {}",
+            where_clause, limit, offset
+        );
+
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT c.id, c.text, c.video_id, c.timestamp, c.source_quote, c.category, c.confidence, c.created_at, c.status, c.date_start, c.date_end,
+                    (SELECT COUNT(*) FROM claim_links l WHERE (l.source_claim_id = c.id OR l.target_claim_id = c.id){})
+             FROM claims c
+             WHERE c.id IN ({})
+             ORDER BY c.created_at DESC, c.id DESC",
+            link_type_clause, page
+        ))?;
+        let mut claims = Vec::new();
+        let mut rows = stmt.query(filter_args.as_slice())?;
+        while let Some(row) = rows.next()? {
+            let link_count: i64 = row.get(11)?;
+            claims.push((self.row_to_claim(row)?, link_count as usize));
+        }
+
+        let mut stmt = self.conn.prepare(&format!(
+            "WITH page AS ({})
+             SELECT l.id, l.source_claim_id, l.target_claim_id, l.link_type, l.created_at
+             FROM claim_links l
+             JOIN page s ON s.id = l.source_claim_id
+             JOIN page t ON t.id = l.target_claim_id
+             WHERE 1 = 1{}
+             ORDER BY l.id",
+            page, link_type_clause
+        ))?;
+        let mut links = Vec::new();
+        let mut rows = stmt.query(filter_args.as_slice())?;
+        while let Some(row) = rows.next()? {
+            links.push(self.row_to_claim_link(row)?);
+        }
+
+        Ok(ClaimGraphPage { claims, links, total: total as usize })
+    }
+
+    fn list_contradictions(&self, by: &str) -> Result<Vec<ContradictionGroup>> {
+        if by != "topic" && by != "moc" {
+            anyhow::bail!("Invalid grouping: {} (use topic or moc)", by);
+        }
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT cl.id,
+                   a.id, a.text, a.video_id, a.timestamp, a.source_quote, a.category, a.confidence, a.created_at, a.status, a.date_start, a.date_end,
+                   b.id, b.text, b.video_id, b.timestamp, b.source_quote, b.category, b.confidence, b.created_at, b.status, b.date_start, b.date_end,
+                   va.title, vb.title
+            FROM claim_links cl
+            JOIN claims a ON a.id = cl.source_claim_id
+            JOIN claims b ON b.id = cl.target_claim_id
+            LEFT JOIN videos va ON va.id = a.video_id
+            LEFT JOIN videos vb ON vb.id = b.video_id
+            WHERE cl.link_type = 'contradicts' AND a.archived_at IS NULL AND b.archived_at IS NULL
+            ORDER BY cl.id
+            "#
+        )?;
+        let mut contradictions = Vec::new();
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            contradictions.push(Contradiction {
+                link_id: row.get(0)?,
+                claim: self.row_to_claim_from_offset(row, 1)?,
+                other: self.row_to_claim_from_offset(row, 12)?,
+                claim_video_title: row.get(23)?,
+                other_video_title: row.get(24)?,
+            });
+        }
+
+        let mut groups: std::collections::BTreeMap<String, Vec<Contradiction>> = std::collections::BTreeMap::new();
+        let mut unsorted = Vec::new();
+        for c in contradictions {
+            let mut names: Vec<String> = if by == "topic" {
+                let mut names: Vec<String> = self.get_video_topics(&c.claim.video_id)?.into_iter().map(|t| t.name).collect();
+                if c.other.video_id != c.claim.video_id {
+                    names.extend(self.get_video_topics(&c.other.video_id)?.into_iter().map(|t| t.name));
+                }
+                names
+            } else {
+                let mut stmt = self.conn.prepare_cached(
+                    "SELECT DISTINCT m.title FROM mocs m JOIN moc_claims mc ON mc.moc_id = m.id WHERE mc.claim_id IN (?1, ?2)"
+                )?;
+                let names = stmt.query_map(params![c.claim.id, c.other.id], |row| row.get(0))?;
+                names.collect::<sql::Result<_>>()?
+            };
+            names.sort();
+            names.dedup();
+            if names.is_empty() {
+                unsorted.push(c);
+                continue;
+            }
+            for name in names {
+                groups.entry(name).or_default().push(c.clone());
+            }
+        }
+
+        let mut result: Vec<ContradictionGroup> = groups
+            .into_iter()
+            .map(|(name, contradictions)| ContradictionGroup { name, contradictions })
+            .collect();
+        if !unsorted.is_empty() {
+            result.push(ContradictionGroup { name: "Unsorted".to_string(), contradictions: unsorted });
+        }
+        Ok(result)
+    }
+
+    fn claim_path(&self, from: i64, to: i64, max_depth: usize) -> Result<Option<Vec<ClaimPathStep>>> {
+        let steps: Option<String> = self.conn.query_row(
+            &format!(
+                r#"
+                WITH RECURSIVE {edges},
+                walk(node, depth, visited, steps) AS (
+                    SELECT CAST(?1 AS BIGINT), 0, ',' || ?1 || ',', ''
+                    UNION ALL
+                    SELECT e.dst, w.depth + 1, w.visited || e.dst || ',', w.steps || e.kind || ':' || e.forward || ':' || e.dst || ';'
+                    FROM walk w
+                    JOIN edges e ON e.src = w.node
+                    WHERE w.depth < ?3 AND w.node != ?2 AND instr(w.visited, ',' || e.dst || ',') = 0
+                )
+                SELECT steps FROM walk WHERE node = ?2 ORDER BY depth LIMIT 1
+                "#,
+                edges = CLAIM_EDGES_CTE
+            ),
+            params![from, to, max_depth as i64],
+            |row| row.get(0),
+        ).optional()?;
+        let Some(steps) = steps else { return Ok(None) };
+
+        let Some(start) = self.get_claim(from)? else { return Ok(None) };
+        let mut path = vec![ClaimPathStep { claim: start, via: None, forward: true }];
+        for step in steps.split(';').filter(|s| !s.is_empty()) {
+            let mut parts = step.splitn(3, ':');
+            let (Some(kind), Some(forward), Some(id)) = (parts.next(), parts.next(), parts.next()) else {
+                anyhow::bail!("Malformed path step: {}", step);
+            };
+            let Some(claim) = self.get_claim(id.parse()?)? else { return Ok(None) };
+            path.push(ClaimPathStep { claim, via: Some(kind.to_string()), forward: forward == "1" });
+        }
+        Ok(Some(path))
+    }
+
+    fn claim_neighborhood(&self, id: i64, depth: usize) -> Result<Vec<(Claim, usize)>> {
+        let mut stmt = self.conn.prepare(&format!(
+            r#"
+            WITH RECURSIVE {edges},
+            walk(node, depth) AS (
+                SELECT CAST(?1 AS BIGINT), 0
+                UNION
+                SELECT e.dst, w.depth + 1
+                FROM walk w
+                JOIN edges e ON e.src = w.node
+                WHERE w.depth < ?2
+            )
+            SELECT node, MIN(depth) FROM walk WHERE node != ?1 GROUP BY node ORDER BY MIN(depth), node
+            "#,
+            edges = CLAIM_EDGES_CTE
+        ))?;
+        let found = stmt
+            .query_map(params![id, depth as i64], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?
+            .collect::<sql::Result<Vec<_>>>()?;
+
+        let mut neighbors = Vec::new();
+        for (claim_id, distance) in found {
+            if let Some(claim) = self.get_claim(claim_id)? {
+                neighbors.push((claim, distance as usize));
+            }
+        }
+        Ok(neighbors)
+    }
+
+    fn set_claim_provenance(
+        &self,
+        claim_id: i64,
+        provider: Option<&str>,
+        model: Option<&str>,
+        prompt_version: Option<&str>,
+        run_id: Option<&str>,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO claim_provenance (claim_id, provider, model, prompt_version, run_id, reviewed_at) VALUES (?1, ?2, ?3, ?4, ?5, NULL)",
+            params![claim_id, provider, model, prompt_version, run_id],
+        )?;
+        Ok(())
+    }
+
+    fn get_claim_provenance(&self, claim_id: i64) -> Result<Option<ClaimProvenance>> {
+        let provenance = self.conn.query_row(
+            "SELECT claim_id, provider, model, prompt_version, run_id, reviewed_at FROM claim_provenance WHERE claim_id = ?1",
+            params![claim_id],
+            |row| Ok(self.row_to_claim_provenance(row)),
+        ).optional()?;
+        provenance.transpose()
+    }
+
+    fn list_claim_provenance(&self) -> Result<HashMap<i64, ClaimProvenance>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT claim_id, provider, model, prompt_version, run_id, reviewed_at FROM claim_provenance"
+        )?;
+        let mut provenance = HashMap::new();
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let p = self.row_to_claim_provenance(row)?;
+            provenance.insert(p.claim_id, p);
+        }
+        Ok(provenance)
+    }
+
+    fn mark_claim_reviewed(&self, claim_id: i64, reviewed: bool) -> Result<bool> {
+        let reviewed_at = reviewed.then(|| Utc::now().to_rfc3339());
+        let updated = self.conn.execute(
+            "UPDATE claim_provenance SET reviewed_at = ?2 WHERE claim_id = ?1",
+            params![claim_id, reviewed_at],
+        )?;
+        Ok(updated > 0)
+    }
+
+    fn list_claim_ids_for_run(&self, run_id: &str) -> Result<Vec<i64>> {
+        let mut stmt = self.conn.prepare("SELECT claim_id FROM claim_provenance WHERE run_id = ?1 ORDER BY claim_id")?;
+        let ids = stmt.query_map(params![run_id], |row| row.get(0))?;
+        Ok(ids.collect::<sql::Result<_>>()?)
+    }
+
+    fn compute_claim_metrics(&self) -> Result<Vec<ClaimMetrics>> {
+        let graph = self.claim_graph(None, None, None)?;
+        let mut metrics = super::graph::compute_metrics(&graph);
+
+        let tx = self.conn.unchecked_transaction()?;
+        self.conn.execute("DELETE FROM claim_metrics", [])?;
+        {
+            let mut stmt = self.conn.prepare(
+                "INSERT INTO claim_metrics (claim_id, degree, betweenness, component, computed_at) VALUES (?1, ?2, ?3, ?4, ?5)"
+            )?;
+            for m in &metrics {
+                stmt.execute(params![m.claim_id, m.degree as i64, m.betweenness, m.component as i64, m.computed_at.to_rfc3339()])?;
+            }
+        }
+        tx.commit()?;
+
+        metrics.sort_by(|a, b| b.betweenness.total_cmp(&a.betweenness).then(b.degree.cmp(&a.degree)));
+        Ok(metrics)
+    }
+
+    fn list_claim_metrics(&self) -> Result<HashMap<i64, ClaimMetrics>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT claim_id, degree, betweenness, component, computed_at FROM claim_metrics"
+        )?;
+        let mut metrics = HashMap::new();
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let m = self.row_to_claim_metrics(row)?;
+            metrics.insert(m.claim_id, m);
+        }
+        Ok(metrics)
+    }
+
+    fn list_bridging_claims(&self, limit: usize) -> Result<Vec<(Claim, ClaimMetrics)>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT c.id, c.text, c.video_id, c.timestamp, c.source_quote, c.category, c.confidence, c.created_at, c.status, c.date_start, c.date_end,
+                   m.claim_id, m.degree, m.betweenness, m.component, m.computed_at
+            FROM claim_metrics m
+            JOIN claims c ON c.id = m.claim_id
+            WHERE c.archived_at IS NULL AND m.betweenness > 0
+            ORDER BY m.betweenness DESC, m.degree DESC
+            LIMIT ?1
+            "#
+        )?;
+        let mut bridges = Vec::new();
+        let mut rows = stmt.query(params![limit as i64])?;
+        while let Some(row) = rows.next()? {
+            bridges.push((self.row_to_claim(row)?, self.row_to_claim_metrics_from_offset(row, 11)?));
+        }
+        Ok(bridges)
     }
 
     // Transcript layer operations (progressive summarization)
 
-    pub fn save_transcript_layer(&self, video_id: &str, layer: u8, content: &str) -> Result<TranscriptLayer> {
+    fn save_transcript_layer(&self, video_id: &str, layer: u8, content: &str) -> Result<TranscriptLayer> {
         let created_at = Utc::now();
         self.conn.execute(
             "INSERT OR REPLACE INTO transcript_layers (video_id, layer, content, created_at) VALUES (?1, ?2, ?3, ?4)",
@@ -3076,7 +7302,7 @@ impl Database {
         })
     }
 
-    pub fn get_transcript_layer(&self, video_id: &str, layer: u8) -> Result<Option<TranscriptLayer>> {
+    fn get_transcript_layer(&self, video_id: &str, layer: u8) -> Result<Option<TranscriptLayer>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, video_id, layer, content, created_at FROM transcript_layers WHERE video_id = ?1 AND layer = ?2"
         )?;
@@ -3096,7 +7322,7 @@ impl Database {
         }
     }
 
-    pub fn list_transcript_layers(&self, video_id: &str) -> Result<Vec<TranscriptLayer>> {
+    fn list_transcript_layers(&self, video_id: &str) -> Result<Vec<TranscriptLayer>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, video_id, layer, content, created_at FROM transcript_layers WHERE video_id = ?1 ORDER BY layer"
         )?;
@@ -3119,7 +7345,7 @@ impl Database {
 
     // Transcript chunk operations (intelligent chunking)
 
-    pub fn save_transcript_chunks(&self, video_id: &str, chunks: &[TranscriptChunk]) -> Result<()> {
+    fn save_transcript_chunks(&self, video_id: &str, chunks: &[TranscriptChunk]) -> Result<()> {
         // Clear existing chunks for this video
         self.conn.execute("DELETE FROM transcript_chunks WHERE video_id = ?1", params![video_id])?;
 
@@ -3143,9 +7369,15 @@ impl Database {
         Ok(())
     }
 
-    pub fn get_transcript_chunks(&self, video_id: &str) -> Result<Vec<TranscriptChunk>> {
+    fn get_transcript_chunks(&self, video_id: &str) -> Result<Vec<TranscriptChunk>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, video_id, chunk_index, start_time, end_time, text, token_count, overlap_with_previous FROM transcript_chunks WHERE video_id = ?1 ORDER BY chunk_index"
+            r#"
+            SELECT id, video_id, chunk_index, start_time, end_time, text, token_count, overlap_with_previous,
+                   (SELECT c.title FROM chapters c
+                    WHERE c.video_id = tc.video_id AND c.start_time <= tc.start_time
+                    ORDER BY c.start_time DESC LIMIT 1)
+            FROM transcript_chunks tc WHERE video_id = ?1 ORDER BY chunk_index
+            "#
         )?;
 
         let mut chunks = Vec::new();
@@ -3162,12 +7394,13 @@ impl Database {
                 text: row.get(5)?,
                 token_count: row.get(6)?,
                 overlap_with_previous: overlap != 0,
+                chapter: row.get(8)?,
             });
         }
         Ok(chunks)
     }
 
-    pub fn has_chunks(&self, video_id: &str) -> Result<bool> {
+    fn has_chunks(&self, video_id: &str) -> Result<bool> {
         let count: i64 = self.conn.query_row(
             "SELECT COUNT(*) FROM transcript_chunks WHERE video_id = ?1",
             params![video_id],
@@ -3178,7 +7411,7 @@ impl Database {
 
     // Claim statistics
 
-    pub fn get_claim_stats(&self) -> Result<(i64, i64, i64)> {
+    fn get_claim_stats(&self) -> Result<(i64, i64, i64)> {
         let total: i64 = self.conn.query_row(
             "SELECT COUNT(*) FROM claims", [], |row| row.get(0)
         )?;
@@ -3198,33 +7431,47 @@ impl Database {
 
     // Phase 7: Semantic Search & Embeddings
 
-    pub fn save_embedding(
+    fn save_embedding(
         &self,
         source_type: EmbeddingSource,
         source_id: &str,
         model: &str,
         vector: &[f32],
     ) -> Result<Embedding> {
+        self.record_embedding_model(model, None, vector.len())?;
         let created_at = Utc::now();
-        let vector_json = serde_json::to_string(vector)?;
+        let blob = vector_to_blob(vector);
         let dimensions = vector.len() as i32;
 
         self.conn.execute(
             r#"
-            INSERT OR REPLACE INTO embeddings (source_type, source_id, model, vector_json, dimensions, created_at)
+            INSERT OR REPLACE INTO embeddings (source_type, source_id, model, vector, dimensions, created_at)
             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
             "#,
             params![
                 source_type.as_str(),
                 source_id,
                 model,
-                vector_json,
+                blob,
                 dimensions,
                 created_at.to_rfc3339(),
             ],
         )?;
         let id = self.conn.last_insert_rowid();
 
+        #[cfg(feature = "sqlite-vec")]
+        if self.conn.sqlite().is_some() {
+            self.ensure_vector_table(vector.len())?;
+            self.conn.execute(
+                &format!("DELETE FROM vec_embeddings_{} WHERE rowid = ?1", vector.len()),
+                params![id],
+            )?;
+            self.conn.execute(
+                &format!("INSERT INTO vec_embeddings_{} (rowid, embedding, source_type, model) VALUES (?1, ?2, ?3, ?4)", vector.len()),
+                params![id, blob, source_type.as_str(), model],
+            )?;
+        }
+
         Ok(Embedding {
             id,
             source_type,
@@ -3235,14 +7482,14 @@ impl Database {
         })
     }
 
-    pub fn get_embedding(
+    fn get_embedding(
         &self,
         source_type: EmbeddingSource,
         source_id: &str,
         model: &str,
     ) -> Result<Option<Embedding>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, source_type, source_id, model, vector_json, created_at FROM embeddings WHERE source_type = ?1 AND source_id = ?2 AND model = ?3"
+            "SELECT id, source_type, source_id, model, vector, created_at FROM embeddings WHERE source_type = ?1 AND source_id = ?2 AND model = ?3"
         )?;
         let mut rows = stmt.query(params![source_type.as_str(), source_id, model])?;
 
@@ -3253,9 +7500,39 @@ impl Database {
         }
     }
 
-    pub fn list_embeddings_by_type(&self, source_type: EmbeddingSource) -> Result<Vec<Embedding>> {
+    fn list_embeddings_by_model(&self, model: &str) -> Result<Vec<Embedding>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, source_type, source_id, model, vector, created_at FROM embeddings WHERE model = ?1 ORDER BY source_type, source_id"
+        )?;
+
+        let mut embeddings = Vec::new();
+        let mut rows = stmt.query(params![model])?;
+
+        while let Some(row) = rows.next()? {
+            embeddings.push(self.row_to_embedding(row)?);
+        }
+        Ok(embeddings)
+    }
+
+    fn swap_embedding_model(&self, from: &str, to: &str, provider: &str, keep_old: bool) -> Result<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+        self.set_setting("embeddings.provider", provider)?;
+        self.set_setting("embeddings.model", to)?;
+        if self.get_setting("embeddings.default_model")?.as_deref() == Some(from) {
+            self.set_setting("embeddings.default_model", to)?;
+        }
+        let mut removed = 0;
+        if !keep_old {
+            removed = self.conn.execute("DELETE FROM embeddings WHERE model = ?1", params![from])?;
+            self.conn.execute("DELETE FROM embedding_models WHERE model = ?1", params![from])?;
+        }
+        tx.commit()?;
+        Ok(removed)
+    }
+
+    fn list_embeddings_by_type(&self, source_type: EmbeddingSource) -> Result<Vec<Embedding>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, source_type, source_id, model, vector_json, created_at FROM embeddings WHERE source_type = ?1 ORDER BY created_at DESC"
+            "SELECT id, source_type, source_id, model, vector, created_at FROM embeddings WHERE source_type = ?1 ORDER BY created_at DESC"
         )?;
 
         let mut embeddings = Vec::new();
@@ -3267,9 +7544,9 @@ impl Database {
         Ok(embeddings)
     }
 
-    pub fn list_all_embeddings(&self) -> Result<Vec<Embedding>> {
+    fn list_all_embeddings(&self) -> Result<Vec<Embedding>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, source_type, source_id, model, vector_json, created_at FROM embeddings ORDER BY source_type, source_id"
+            "SELECT id, source_type, source_id, model, vector, created_at FROM embeddings ORDER BY source_type, source_id"
         )?;
 
         let mut embeddings = Vec::new();
@@ -3281,7 +7558,7 @@ impl Database {
         Ok(embeddings)
     }
 
-    pub fn delete_embedding(&self, source_type: EmbeddingSource, source_id: &str) -> Result<bool> {
+    fn delete_embedding(&self, source_type: EmbeddingSource, source_id: &str) -> Result<bool> {
         let affected = self.conn.execute(
             "DELETE FROM embeddings WHERE source_type = ?1 AND source_id = ?2",
             params![source_type.as_str(), source_id],
@@ -3289,59 +7566,208 @@ impl Database {
         Ok(affected > 0)
     }
 
-    pub fn has_embedding(&self, source_type: EmbeddingSource, source_id: &str) -> Result<bool> {
+    fn has_embedding(&self, source_type: EmbeddingSource, source_id: &str) -> Result<bool> {
         let count: i64 = self.conn.query_row(
             "SELECT COUNT(*) FROM embeddings WHERE source_type = ?1 AND source_id = ?2",
             params![source_type.as_str(), source_id],
             |row| row.get(0),
         )?;
-        Ok(count > 0)
-    }
-
-    fn row_to_embedding(&self, row: &rusqlite::Row) -> Result<Embedding> {
-        let source_type_str: String = row.get(1)?;
-        let vector_json: String = row.get(4)?;
-        let created_at: String = row.get(5)?;
+        Ok(count > 0)
+    }
+
+    // Similarity search using cosine similarity
+    #[cfg(not(feature = "sqlite-vec"))]
+    fn find_similar(
+        &self,
+        query_vector: &[f32],
+        source_type: Option<EmbeddingSource>,
+        model: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<(Embedding, f32)>> {
+        self.scan_similar(query_vector, source_type, model, limit)
+    }
+
+    // Similarity search as an indexed KNN query
+    #[cfg(feature = "sqlite-vec")]
+    fn find_similar(
+        &self,
+        query_vector: &[f32],
+        source_type: Option<EmbeddingSource>,
+        model: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<(Embedding, f32)>> {
+        // The vec0 index lives in SQLite; other backends scan
+        if self.conn.sqlite().is_none() {
+            return self.scan_similar(query_vector, source_type, model, limit);
+        }
+        if !self.is_read_only()? {
+            self.sync_vector_table(query_vector.len())?;
+        }
+
+        // Metadata filters only narrow the KNN search as plain equality constraints;
+        // anything vec0 can't take is applied after the k nearest are picked, so k
+        // reaches past every archived vector that might be among them
+        let archived: i64 = self.conn.query_row(
+            &format!("SELECT COUNT(*) FROM embeddings e WHERE e.dimensions = ?1 AND {}", ARCHIVED_EMBEDDING),
+            params![query_vector.len() as i64],
+            |row| row.get(0),
+        )?;
+        let mut filters = String::new();
+        let mut values: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(vector_to_blob(query_vector)), Box::new(limit as i64 + archived)];
+        if let Some(st) = source_type {
+            values.push(Box::new(st.as_str()));
+            filters.push_str(&format!(" AND v.source_type = ?{}", values.len()));
+        }
+        if let Some(model) = model {
+            values.push(Box::new(model.to_string()));
+            filters.push_str(&format!(" AND v.model = ?{}", values.len()));
+        }
+        let mut stmt = self.conn.prepare(&format!(
+            r#"
+            SELECT e.id, e.source_type, e.source_id, e.model, e.vector, e.created_at, v.distance
+            FROM vec_embeddings_{} v
+            JOIN embeddings e ON e.id = v.rowid
+            WHERE v.embedding MATCH ?1 AND k = ?2{} AND NOT {}
+            ORDER BY v.distance
+            "#,
+            query_vector.len(),
+            filters,
+            ARCHIVED_EMBEDDING
+        ))?;
 
-        Ok(Embedding {
-            id: row.get(0)?,
-            source_type: EmbeddingSource::from_str(&source_type_str).unwrap_or(EmbeddingSource::Chunk),
-            source_id: row.get(2)?,
-            model: row.get(3)?,
-            vector: serde_json::from_str(&vector_json)?,
-            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
-        })
+        let mut results = Vec::new();
+        let mut rows = stmt.query(params_from_iter(values.iter().map(|v| v.as_ref())))?;
+        while let Some(row) = rows.next()? {
+            let distance: f64 = row.get(6)?;
+            results.push((self.row_to_embedding(row)?, 1.0 - distance as f32));
+        }
+        results.truncate(limit);
+        Ok(results)
     }
 
-    // Similarity search using cosine similarity
-    pub fn find_similar(
+    fn diversify(
         &self,
         query_vector: &[f32],
+        mut candidates: Vec<(Embedding, f32)>,
+        limit: usize,
+        lambda: f32,
+    ) -> Vec<(Embedding, f32)> {
+        let mut selected: Vec<(Embedding, f32)> = Vec::new();
+        while selected.len() < limit && !candidates.is_empty() {
+            let mut best = (0, f32::NEG_INFINITY);
+            for (i, (emb, _)) in candidates.iter().enumerate() {
+                let relevance = cosine_similarity(query_vector, &emb.vector);
+                let redundancy = selected
+                    .iter()
+                    .map(|(s, _)| cosine_similarity(&s.vector, &emb.vector))
+                    .fold(0.0_f32, f32::max);
+                let score = lambda * relevance - (1.0 - lambda) * redundancy;
+                if score > best.1 {
+                    best = (i, score);
+                }
+            }
+            selected.push(candidates.swap_remove(best.0));
+        }
+        selected
+    }
+
+    fn embedding_outliers(
+        &self,
         source_type: Option<EmbeddingSource>,
+        clusters: Option<usize>,
         limit: usize,
     ) -> Result<Vec<(Embedding, f32)>> {
-        let embeddings = if let Some(st) = source_type {
-            self.list_embeddings_by_type(st)?
-        } else {
-            self.list_all_embeddings()?
+        let embeddings = match source_type {
+            Some(st) => self.list_embeddings_by_type(st)?,
+            None => self
+                .list_all_embeddings()?
+                .into_iter()
+                .filter(|e| matches!(e.source_type, EmbeddingSource::Claim | EmbeddingSource::Chunk))
+                .collect(),
         };
 
-        let mut scored: Vec<(Embedding, f32)> = embeddings
-            .into_iter()
-            .map(|emb| {
-                let score = cosine_similarity(query_vector, &emb.vector);
-                (emb, score)
-            })
-            .collect();
+        let mut by_model: HashMap<String, Vec<Embedding>> = HashMap::new();
+        for emb in embeddings {
+            by_model.entry(emb.model.clone()).or_default().push(emb);
+        }
+
+        let mut outliers = Vec::new();
+        for (_, group) in by_model {
+            let k = clusters.unwrap_or_else(|| ((group.len() as f32 / 2.0).sqrt().round() as usize).max(1));
+            let centroids = kmeans_centroids(&group, k.clamp(1, group.len()));
+            for emb in group {
+                let nearest = centroids
+                    .iter()
+                    .map(|c| cosine_similarity(c, &emb.vector))
+                    .fold(f32::NEG_INFINITY, f32::max);
+                outliers.push((emb, 1.0 - nearest));
+            }
+        }
 
-        // Sort by score descending
-        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        outliers.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        outliers.truncate(limit);
+        Ok(outliers)
+    }
 
-        Ok(scored.into_iter().take(limit).collect())
+    fn embedding_projection(&self, source_type: Option<EmbeddingSource>, model: Option<&str>) -> Result<Vec<ProjectionPoint>> {
+        let embeddings = match source_type {
+            Some(st) => self.list_embeddings_by_type(st)?,
+            None => self.list_all_embeddings()?,
+        };
+        let model = match model {
+            Some(m) => m.to_string(),
+            None => match self.get_setting("embeddings.default_model")? {
+                Some(m) => m,
+                None => {
+                    let mut counts: HashMap<&str, usize> = HashMap::new();
+                    for emb in &embeddings {
+                        *counts.entry(emb.model.as_str()).or_default() += 1;
+                    }
+                    match counts.into_iter().max_by_key(|(m, n)| (*n, std::cmp::Reverse(*m))) {
+                        Some((m, _)) => m.to_string(),
+                        None => return Ok(Vec::new()),
+                    }
+                }
+            },
+        };
+        let embeddings: Vec<Embedding> = embeddings.into_iter().filter(|e| e.model == model).collect();
+        let vectors: Vec<&[f32]> = embeddings.iter().map(|e| e.vector.as_slice()).collect();
+        let coords = pca_2d(&vectors);
+
+        let claims: HashMap<i64, Claim> = self.list_all_claims()?.into_iter().map(|c| (c.id, c)).collect();
+        let mut points = Vec::new();
+        for (emb, (x, y)) in embeddings.iter().zip(coords) {
+            let (label, category, video_id) = match emb.source_type {
+                EmbeddingSource::Claim => {
+                    // Archived claims drop out of the map
+                    let Some(claim) = emb.source_id.parse().ok().and_then(|id: i64| claims.get(&id)) else { continue };
+                    (claim.text.clone(), Some(claim.category.as_str().to_string()), Some(claim.video_id.clone()))
+                }
+                EmbeddingSource::Video => {
+                    let title = self.get_video(&emb.source_id)?.map(|v| v.title).unwrap_or_default();
+                    (title, None, Some(emb.source_id.clone()))
+                }
+                EmbeddingSource::Chunk | EmbeddingSource::Summary => {
+                    let text = self.get_text_for_embedding(emb)?.unwrap_or_default();
+                    (text, None, emb.source_id.split(':').next().map(|s| s.to_string()))
+                }
+            };
+            let label: String = label.chars().take(120).collect();
+            points.push(ProjectionPoint {
+                source_type: emb.source_type,
+                source_id: emb.source_id.clone(),
+                x,
+                y,
+                label,
+                category,
+                video_id,
+            });
+        }
+        Ok(points)
     }
 
     // Get text for a similarity result
-    pub fn get_text_for_embedding(&self, embedding: &Embedding) -> Result<Option<String>> {
+    fn get_text_for_embedding(&self, embedding: &Embedding) -> Result<Option<String>> {
         match embedding.source_type {
             EmbeddingSource::Video => {
                 if let Some(video) = self.get_video(&embedding.source_id)? {
@@ -3388,13 +7814,21 @@ impl Database {
     }
 
     // Build similarity results with text
-    pub fn build_similarity_results(
+    fn build_similarity_results(
         &self,
         similar: Vec<(Embedding, f32)>,
     ) -> Result<Vec<SimilarityResult>> {
         let mut results = Vec::new();
 
         for (emb, score) in similar {
+            let archived: bool = self.conn.query_row(
+                &format!("SELECT {} FROM embeddings e WHERE e.id = ?1", ARCHIVED_EMBEDDING),
+                params![emb.id],
+                |row| row.get(0),
+            ).optional()?.unwrap_or(false);
+            if archived {
+                continue;
+            }
             let text = self.get_text_for_embedding(&emb)?.unwrap_or_default();
             let video_id = match emb.source_type {
                 EmbeddingSource::Video => Some(emb.source_id.clone()),
@@ -3420,13 +7854,14 @@ impl Database {
     }
 
     // Hybrid search combining keyword and semantic
-    pub fn hybrid_search(
+    fn hybrid_search(
         &self,
         query: &str,
         query_vector: Option<&[f32]>,
         keyword_weight: f32,
         semantic_weight: f32,
         limit: usize,
+        fusion: Fusion,
     ) -> Result<Vec<HybridSearchResult>> {
         // Get keyword results
         let keyword_results = self.search_with_timestamps(query)?;
@@ -3447,13 +7882,13 @@ impl Database {
         // Get semantic results if we have a query vector
         let mut semantic_scores: std::collections::HashMap<String, f32> = std::collections::HashMap::new();
         if let Some(qv) = query_vector {
-            let similar = self.find_similar(qv, Some(EmbeddingSource::Video), limit * 2)?;
+            let similar = self.find_similar(qv, Some(EmbeddingSource::Video), None, limit * 2)?;
             for (emb, score) in similar {
                 semantic_scores.insert(emb.source_id, score);
             }
 
             // Also check chunk embeddings
-            let chunk_similar = self.find_similar(qv, Some(EmbeddingSource::Chunk), limit * 2)?;
+            let chunk_similar = self.find_similar(qv, Some(EmbeddingSource::Chunk), None, limit * 2)?;
             for (emb, score) in chunk_similar {
                 if let Some(video_id) = emb.source_id.split(':').next() {
                     let entry = semantic_scores.entry(video_id.to_string()).or_insert(0.0);
@@ -3467,12 +7902,33 @@ impl Database {
         all_video_ids.extend(keyword_scores.keys().cloned());
         all_video_ids.extend(semantic_scores.keys().cloned());
 
+        // For RRF, each list's rank (0-based) replaces its score
+        let keyword_ranks: HashMap<&str, usize> = keyword_results
+            .iter()
+            .enumerate()
+            .map(|(i, r)| (r.video.id.as_str(), i))
+            .collect();
+        let mut by_semantic: Vec<(&String, &f32)> = semantic_scores.iter().collect();
+        by_semantic.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap_or(std::cmp::Ordering::Equal));
+        let semantic_ranks: HashMap<&str, usize> = by_semantic
+            .into_iter()
+            .enumerate()
+            .map(|(i, (vid, _))| (vid.as_str(), i))
+            .collect();
+        let rrf = |rank: Option<&usize>| rank.map_or(0.0, |r| 1.0 / (RRF_K + *r as f32 + 1.0));
+
         let mut combined: Vec<(String, f32, f32, f32)> = all_video_ids
             .into_iter()
             .map(|vid| {
                 let kw = keyword_scores.get(&vid).copied().unwrap_or(0.0);
                 let sem = semantic_scores.get(&vid).copied().unwrap_or(0.0);
-                let combined = kw * keyword_weight + sem * semantic_weight;
+                let combined = match fusion {
+                    Fusion::Weighted => kw * keyword_weight + sem * semantic_weight,
+                    Fusion::Rrf => {
+                        rrf(keyword_ranks.get(vid.as_str())) * keyword_weight
+                            + rrf(semantic_ranks.get(vid.as_str())) * semantic_weight
+                    }
+                };
                 (vid, kw, sem, combined)
             })
             .collect();
@@ -3480,6 +7936,7 @@ impl Database {
         combined.sort_by(|a, b| b.3.partial_cmp(&a.3).unwrap_or(std::cmp::Ordering::Equal));
 
         // Build results
+        let model = self.default_embedding_model()?.unwrap_or_else(|| "default".to_string());
         let mut results = Vec::new();
         for (video_id, kw_score, sem_score, comb_score) in combined.into_iter().take(limit) {
             if let Some(video) = self.get_video(&video_id)? {
@@ -3490,7 +7947,7 @@ impl Database {
                         .into_iter()
                         .filter_map(|chunk| {
                             let chunk_id = format!("{}:{}", video_id, chunk.chunk_index);
-                            if let Ok(Some(emb)) = self.get_embedding(EmbeddingSource::Chunk, &chunk_id, "default") {
+                            if let Ok(Some(emb)) = self.get_embedding(EmbeddingSource::Chunk, &chunk_id, &model) {
                                 let score = cosine_similarity(qv, &emb.vector);
                                 Some(ChunkMatch { chunk, score })
                             } else {
@@ -3508,7 +7965,7 @@ impl Database {
                     claims
                         .into_iter()
                         .filter(|claim| {
-                            if let Ok(Some(emb)) = self.get_embedding(EmbeddingSource::Claim, &claim.id.to_string(), "default") {
+                            if let Ok(Some(emb)) = self.get_embedding(EmbeddingSource::Claim, &claim.id.to_string(), &model) {
                                 let score = cosine_similarity(qv, &emb.vector);
                                 score > 0.5 // threshold
                             } else {
@@ -3534,8 +7991,89 @@ impl Database {
         Ok(results)
     }
 
+    fn record_embedding_model(&self, model: &str, provider: Option<&str>, dimensions: usize) -> Result<()> {
+        if let Some(known) = self.get_embedding_model(model)? {
+            if known.dimensions != dimensions {
+                anyhow::bail!(
+                    "Model {} returned {}-dimensional vectors, but its stored embeddings have {}",
+                    model, dimensions, known.dimensions
+                );
+            }
+            return Ok(());
+        }
+        self.conn.execute(
+            "INSERT INTO embedding_models (model, provider, dimensions, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![model, provider, dimensions as i64, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    fn get_embedding_model(&self, model: &str) -> Result<Option<EmbeddingModel>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT model, provider, dimensions, created_at FROM embedding_models WHERE model = ?1"
+        )?;
+        let mut rows = stmt.query(params![model])?;
+        match rows.next()? {
+            Some(row) => Ok(Some(self.row_to_embedding_model(row)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn default_embedding_model(&self) -> Result<Option<String>> {
+        if let Some(model) = self.get_setting("embeddings.default_model")? {
+            return Ok(Some(model));
+        }
+        Ok(self.conn.query_row(
+            "SELECT model FROM embeddings GROUP BY model ORDER BY COUNT(*) DESC, model LIMIT 1",
+            [],
+            |row| row.get(0),
+        ).optional()?)
+    }
+
+    fn embedding_stats_by_model(&self) -> Result<Vec<EmbeddingModelStats>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT e.model, m.provider, MAX(e.dimensions),
+                   COUNT(CASE WHEN e.source_type = 'video' THEN 1 END), COUNT(CASE WHEN e.source_type = 'chunk' THEN 1 END),
+                   COUNT(CASE WHEN e.source_type = 'claim' THEN 1 END), COUNT(CASE WHEN e.source_type = 'summary' THEN 1 END), COUNT(*)
+            FROM embeddings e
+            LEFT JOIN embedding_models m ON m.model = e.model
+            GROUP BY e.model, m.provider
+            ORDER BY COUNT(*) DESC, e.model
+            "#
+        )?;
+        let mut stats = Vec::new();
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let dimensions: i64 = row.get(2)?;
+            stats.push(EmbeddingModelStats {
+                model: row.get(0)?,
+                provider: row.get(1)?,
+                dimensions: dimensions as usize,
+                videos: row.get(3)?,
+                chunks: row.get(4)?,
+                claims: row.get(5)?,
+                summaries: row.get(6)?,
+                total: row.get(7)?,
+            });
+        }
+        Ok(stats)
+    }
+
+    fn list_embedding_models(&self) -> Result<Vec<EmbeddingModel>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT model, provider, dimensions, created_at FROM embedding_models ORDER BY model"
+        )?;
+        let mut models = Vec::new();
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            models.push(self.row_to_embedding_model(row)?);
+        }
+        Ok(models)
+    }
+
     // Embedding statistics
-    pub fn get_embedding_stats(&self) -> Result<EmbeddingStats> {
+    fn get_embedding_stats(&self) -> Result<EmbeddingStats> {
         let total: i64 = self.conn.query_row(
             "SELECT COUNT(*) FROM embeddings", [], |row| row.get(0)
         )?;
@@ -3571,11 +8109,59 @@ impl Database {
             summary_embeddings: summary_count,
             model: model_dims.as_ref().map(|(m, _)| m.clone()),
             dimensions: model_dims.map(|(_, d)| d),
+            queued: self.conn.query_row("SELECT COUNT(*) FROM embedding_queue", [], |row| row.get(0))?,
         })
     }
 
+    fn queue_embedding(&self, source_type: EmbeddingSource, source_id: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO embedding_queue (source_type, source_id, attempts, error, created_at)
+             VALUES (?1, ?2, 0, NULL, ?3)
+             ON CONFLICT(source_type, source_id) DO UPDATE SET attempts = 0, error = NULL",
+            params![source_type.as_str(), source_id, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    fn list_embedding_queue(&self) -> Result<Vec<EmbeddingQueueItem>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, source_type, source_id, attempts, error, created_at FROM embedding_queue ORDER BY id"
+        )?;
+        let mut items = Vec::new();
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let source_type: String = row.get(1)?;
+            let created_at: String = row.get(5)?;
+            items.push(EmbeddingQueueItem {
+                id: row.get(0)?,
+                source_type: EmbeddingSource::from_str(&source_type).unwrap_or(EmbeddingSource::Chunk),
+                source_id: row.get(2)?,
+                attempts: row.get(3)?,
+                error: row.get(4)?,
+                created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+            });
+        }
+        Ok(items)
+    }
+
+    fn dequeue_embedding(&self, source_type: EmbeddingSource, source_id: &str) -> Result<bool> {
+        let affected = self.conn.execute(
+            "DELETE FROM embedding_queue WHERE source_type = ?1 AND source_id = ?2",
+            params![source_type.as_str(), source_id],
+        )?;
+        Ok(affected > 0)
+    }
+
+    fn fail_queued_embedding(&self, source_type: EmbeddingSource, source_id: &str, error: &str) -> Result<bool> {
+        let affected = self.conn.execute(
+            "UPDATE embedding_queue SET attempts = attempts + 1, error = ?3 WHERE source_type = ?1 AND source_id = ?2",
+            params![source_type.as_str(), source_id, error],
+        )?;
+        Ok(affected > 0)
+    }
+
     // Get items that need embeddings
-    pub fn get_items_needing_embeddings(&self) -> Result<(Vec<String>, Vec<String>, Vec<i64>)> {
+    fn get_items_needing_embeddings(&self) -> Result<(Vec<String>, Vec<String>, Vec<i64>)> {
         // Videos without embeddings
         let mut video_stmt = self.conn.prepare(
             r#"
@@ -3628,7 +8214,7 @@ impl Database {
 
     // 8.1 Cyclical Indicator Operations
 
-    pub fn create_cyclical_indicator(
+    fn create_cyclical_indicator(
         &self,
         video_id: &str,
         claim_id: Option<i64>,
@@ -3669,7 +8255,7 @@ impl Database {
         })
     }
 
-    pub fn get_cyclical_indicator(&self, id: i64) -> Result<Option<CyclicalIndicator>> {
+    fn get_cyclical_indicator(&self, id: i64) -> Result<Option<CyclicalIndicator>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, video_id, claim_id, indicator_type, entity, era_id, description, timestamp, created_at FROM cyclical_indicators WHERE id = ?1"
         )?;
@@ -3682,7 +8268,7 @@ impl Database {
         }
     }
 
-    pub fn list_cyclical_indicators_by_type(&self, indicator_type: CyclicalType) -> Result<Vec<CyclicalIndicator>> {
+    fn list_cyclical_indicators_by_type(&self, indicator_type: CyclicalType) -> Result<Vec<CyclicalIndicator>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, video_id, claim_id, indicator_type, entity, era_id, description, timestamp, created_at FROM cyclical_indicators WHERE indicator_type = ?1 ORDER BY created_at DESC"
         )?;
@@ -3696,7 +8282,7 @@ impl Database {
         Ok(indicators)
     }
 
-    pub fn list_cyclical_indicators_by_entity(&self, entity: &str) -> Result<Vec<CyclicalIndicator>> {
+    fn list_cyclical_indicators_by_entity(&self, entity: &str) -> Result<Vec<CyclicalIndicator>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, video_id, claim_id, indicator_type, entity, era_id, description, timestamp, created_at FROM cyclical_indicators WHERE entity = ?1 ORDER BY created_at DESC"
         )?;
@@ -3710,7 +8296,7 @@ impl Database {
         Ok(indicators)
     }
 
-    pub fn list_all_cyclical_indicators(&self) -> Result<Vec<CyclicalIndicator>> {
+    fn list_all_cyclical_indicators(&self) -> Result<Vec<CyclicalIndicator>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, video_id, claim_id, indicator_type, entity, era_id, description, timestamp, created_at FROM cyclical_indicators ORDER BY entity, indicator_type, created_at DESC"
         )?;
@@ -3724,31 +8310,14 @@ impl Database {
         Ok(indicators)
     }
 
-    pub fn delete_cyclical_indicator(&self, id: i64) -> Result<bool> {
+    fn delete_cyclical_indicator(&self, id: i64) -> Result<bool> {
         let affected = self.conn.execute("DELETE FROM cyclical_indicators WHERE id = ?1", params![id])?;
         Ok(affected > 0)
     }
 
-    fn row_to_cyclical_indicator(&self, row: &rusqlite::Row) -> Result<CyclicalIndicator> {
-        let indicator_type_str: String = row.get(3)?;
-        let created_at: String = row.get(8)?;
-
-        Ok(CyclicalIndicator {
-            id: row.get(0)?,
-            video_id: row.get(1)?,
-            claim_id: row.get(2)?,
-            indicator_type: CyclicalType::from_str(&indicator_type_str).unwrap_or(CyclicalType::SocialUnrest),
-            entity: row.get(4)?,
-            era_id: row.get(5)?,
-            description: row.get(6)?,
-            timestamp: row.get(7)?,
-            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
-        })
-    }
-
     // 8.2 Causal Relation Operations
 
-    pub fn create_causal_relation(
+    fn create_causal_relation(
         &self,
         cause_claim_id: i64,
         effect_claim_id: i64,
@@ -3786,7 +8355,7 @@ impl Database {
         })
     }
 
-    pub fn get_causal_relation(&self, id: i64) -> Result<Option<CausalRelation>> {
+    fn get_causal_relation(&self, id: i64) -> Result<Option<CausalRelation>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, cause_claim_id, effect_claim_id, loop_type, strength, video_id, notes, created_at FROM causal_relations WHERE id = ?1"
         )?;
@@ -3799,7 +8368,7 @@ impl Database {
         }
     }
 
-    pub fn get_causal_relations_for_claim(&self, claim_id: i64) -> Result<Vec<CausalRelation>> {
+    fn get_causal_relations_for_claim(&self, claim_id: i64) -> Result<Vec<CausalRelation>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, cause_claim_id, effect_claim_id, loop_type, strength, video_id, notes, created_at FROM causal_relations WHERE cause_claim_id = ?1 OR effect_claim_id = ?1 ORDER BY created_at DESC"
         )?;
@@ -3813,7 +8382,56 @@ impl Database {
         Ok(relations)
     }
 
-    pub fn list_causal_relations_by_type(&self, loop_type: LoopType) -> Result<Vec<CausalRelation>> {
+    fn causal_chain(&self, claim_id: i64, depth: usize) -> Result<Option<CausalChain>> {
+        let Some(claim) = self.get_claim(claim_id)? else { return Ok(None) };
+        let walk = r#"
+            WITH RECURSIVE
+            live(id) AS (SELECT id FROM claims WHERE archived_at IS NULL),
+            edges(src, dst) AS (
+                SELECT cause_claim_id, effect_claim_id FROM causal_relations
+                UNION ALL SELECT effect_claim_id, cause_claim_id FROM causal_relations
+            ),
+            walk(node, depth) AS (
+                SELECT CAST(?1 AS BIGINT), 0
+                UNION
+                SELECT e.dst, w.depth + 1
+                FROM walk w
+                JOIN edges e ON e.src = w.node
+                WHERE w.depth < ?2 AND e.dst IN (SELECT id FROM live)
+            ),
+            reached(node, depth) AS (SELECT node, MIN(depth) FROM walk GROUP BY node)
+        "#;
+
+        let mut stmt = self.conn.prepare(&format!(
+            "{} SELECT node, depth FROM reached WHERE node != ?1 ORDER BY depth, node",
+            walk
+        ))?;
+        let found = stmt
+            .query_map(params![claim_id, depth as i64], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?
+            .collect::<sql::Result<Vec<_>>>()?;
+        let mut claims = vec![(claim, 0)];
+        for (id, distance) in found {
+            if let Some(claim) = self.get_claim(id)? {
+                claims.push((claim, distance as usize));
+            }
+        }
+
+        let mut stmt = self.conn.prepare(&format!(
+            "{} SELECT id, cause_claim_id, effect_claim_id, loop_type, strength, video_id, notes, created_at \
+             FROM causal_relations \
+             WHERE cause_claim_id IN (SELECT node FROM reached) AND effect_claim_id IN (SELECT node FROM reached) \
+             ORDER BY id",
+            walk
+        ))?;
+        let mut relations = Vec::new();
+        let mut rows = stmt.query(params![claim_id, depth as i64])?;
+        while let Some(row) = rows.next()? {
+            relations.push(self.row_to_causal_relation(row)?);
+        }
+        Ok(Some(CausalChain { claims, relations }))
+    }
+
+    fn list_causal_relations_by_type(&self, loop_type: LoopType) -> Result<Vec<CausalRelation>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, cause_claim_id, effect_claim_id, loop_type, strength, video_id, notes, created_at FROM causal_relations WHERE loop_type = ?1 ORDER BY created_at DESC"
         )?;
@@ -3827,7 +8445,7 @@ impl Database {
         Ok(relations)
     }
 
-    pub fn list_all_causal_relations(&self) -> Result<Vec<CausalRelation>> {
+    fn list_all_causal_relations(&self) -> Result<Vec<CausalRelation>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, cause_claim_id, effect_claim_id, loop_type, strength, video_id, notes, created_at FROM causal_relations ORDER BY loop_type, created_at DESC"
         )?;
@@ -3841,31 +8459,14 @@ impl Database {
         Ok(relations)
     }
 
-    pub fn delete_causal_relation(&self, id: i64) -> Result<bool> {
+    fn delete_causal_relation(&self, id: i64) -> Result<bool> {
         let affected = self.conn.execute("DELETE FROM causal_relations WHERE id = ?1", params![id])?;
         Ok(affected > 0)
     }
 
-    fn row_to_causal_relation(&self, row: &rusqlite::Row) -> Result<CausalRelation> {
-        let loop_type_str: String = row.get(3)?;
-        let strength_str: String = row.get(4)?;
-        let created_at: String = row.get(7)?;
-
-        Ok(CausalRelation {
-            id: row.get(0)?,
-            cause_claim_id: row.get(1)?,
-            effect_claim_id: row.get(2)?,
-            loop_type: LoopType::from_str(&loop_type_str).unwrap_or(LoopType::Linear),
-            strength: RelationStrength::from_str(&strength_str).unwrap_or(RelationStrength::Moderate),
-            video_id: row.get(5)?,
-            notes: row.get(6)?,
-            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
-        })
-    }
-
     // 8.3 Idea Transmission Operations
 
-    pub fn create_idea_transmission(
+    fn create_idea_transmission(
         &self,
         idea: &str,
         source_entity: &str,
@@ -3912,7 +8513,7 @@ impl Database {
         })
     }
 
-    pub fn get_idea_transmission(&self, id: i64) -> Result<Option<IdeaTransmission>> {
+    fn get_idea_transmission(&self, id: i64) -> Result<Option<IdeaTransmission>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, idea, source_entity, target_entity, transmission_type, era_id, region_id, video_id, claim_id, notes, created_at FROM idea_transmissions WHERE id = ?1"
         )?;
@@ -3925,7 +8526,7 @@ impl Database {
         }
     }
 
-    pub fn list_idea_transmissions_by_idea(&self, idea: &str) -> Result<Vec<IdeaTransmission>> {
+    fn list_idea_transmissions_by_idea(&self, idea: &str) -> Result<Vec<IdeaTransmission>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, idea, source_entity, target_entity, transmission_type, era_id, region_id, video_id, claim_id, notes, created_at FROM idea_transmissions WHERE idea LIKE ?1 ORDER BY created_at DESC"
         )?;
@@ -3940,7 +8541,7 @@ impl Database {
         Ok(transmissions)
     }
 
-    pub fn list_idea_transmissions_by_type(&self, transmission_type: TransmissionType) -> Result<Vec<IdeaTransmission>> {
+    fn list_idea_transmissions_by_type(&self, transmission_type: TransmissionType) -> Result<Vec<IdeaTransmission>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, idea, source_entity, target_entity, transmission_type, era_id, region_id, video_id, claim_id, notes, created_at FROM idea_transmissions WHERE transmission_type = ?1 ORDER BY created_at DESC"
         )?;
@@ -3954,7 +8555,7 @@ impl Database {
         Ok(transmissions)
     }
 
-    pub fn list_all_idea_transmissions(&self) -> Result<Vec<IdeaTransmission>> {
+    fn list_all_idea_transmissions(&self) -> Result<Vec<IdeaTransmission>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, idea, source_entity, target_entity, transmission_type, era_id, region_id, video_id, claim_id, notes, created_at FROM idea_transmissions ORDER BY idea, created_at DESC"
         )?;
@@ -3968,33 +8569,14 @@ impl Database {
         Ok(transmissions)
     }
 
-    pub fn delete_idea_transmission(&self, id: i64) -> Result<bool> {
+    fn delete_idea_transmission(&self, id: i64) -> Result<bool> {
         let affected = self.conn.execute("DELETE FROM idea_transmissions WHERE id = ?1", params![id])?;
         Ok(affected > 0)
     }
 
-    fn row_to_idea_transmission(&self, row: &rusqlite::Row) -> Result<IdeaTransmission> {
-        let transmission_type_str: String = row.get(4)?;
-        let created_at: String = row.get(10)?;
-
-        Ok(IdeaTransmission {
-            id: row.get(0)?,
-            idea: row.get(1)?,
-            source_entity: row.get(2)?,
-            target_entity: row.get(3)?,
-            transmission_type: TransmissionType::from_str(&transmission_type_str).unwrap_or(TransmissionType::Horizontal),
-            era_id: row.get(5)?,
-            region_id: row.get(6)?,
-            video_id: row.get(7)?,
-            claim_id: row.get(8)?,
-            notes: row.get(9)?,
-            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
-        })
-    }
-
     // 8.4 Geopolitical Entity Operations
 
-    pub fn create_geopolitical_entity(
+    fn create_geopolitical_entity(
         &self,
         name: &str,
         era_id: i64,
@@ -4026,7 +8608,7 @@ impl Database {
         })
     }
 
-    pub fn get_geopolitical_entity(&self, id: i64) -> Result<Option<GeopoliticalEntity>> {
+    fn get_geopolitical_entity(&self, id: i64) -> Result<Option<GeopoliticalEntity>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, name, era_id, position, notes, created_at FROM geopolitical_entities WHERE id = ?1"
         )?;
@@ -4039,7 +8621,7 @@ impl Database {
         }
     }
 
-    pub fn get_geopolitical_entity_by_name(&self, name: &str, era_id: i64) -> Result<Option<GeopoliticalEntity>> {
+    fn get_geopolitical_entity_by_name(&self, name: &str, era_id: i64) -> Result<Option<GeopoliticalEntity>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, name, era_id, position, notes, created_at FROM geopolitical_entities WHERE name = ?1 AND era_id = ?2"
         )?;
@@ -4052,7 +8634,7 @@ impl Database {
         }
     }
 
-    pub fn list_geopolitical_entities_by_era(&self, era_id: i64) -> Result<Vec<GeopoliticalEntity>> {
+    fn list_geopolitical_entities_by_era(&self, era_id: i64) -> Result<Vec<GeopoliticalEntity>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, name, era_id, position, notes, created_at FROM geopolitical_entities WHERE era_id = ?1 ORDER BY position, name"
         )?;
@@ -4066,7 +8648,7 @@ impl Database {
         Ok(entities)
     }
 
-    pub fn list_geopolitical_entities_by_position(&self, position: SystemPosition) -> Result<Vec<GeopoliticalEntity>> {
+    fn list_geopolitical_entities_by_position(&self, position: SystemPosition) -> Result<Vec<GeopoliticalEntity>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, name, era_id, position, notes, created_at FROM geopolitical_entities WHERE position = ?1 ORDER BY era_id, name"
         )?;
@@ -4080,7 +8662,7 @@ impl Database {
         Ok(entities)
     }
 
-    pub fn list_all_geopolitical_entities(&self) -> Result<Vec<GeopoliticalEntity>> {
+    fn list_all_geopolitical_entities(&self) -> Result<Vec<GeopoliticalEntity>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, name, era_id, position, notes, created_at FROM geopolitical_entities ORDER BY era_id, position, name"
         )?;
@@ -4094,7 +8676,7 @@ impl Database {
         Ok(entities)
     }
 
-    pub fn update_geopolitical_entity_position(&self, id: i64, position: SystemPosition) -> Result<bool> {
+    fn update_geopolitical_entity_position(&self, id: i64, position: SystemPosition) -> Result<bool> {
         let affected = self.conn.execute(
             "UPDATE geopolitical_entities SET position = ?1 WHERE id = ?2",
             params![position.as_str(), id],
@@ -4102,28 +8684,14 @@ impl Database {
         Ok(affected > 0)
     }
 
-    pub fn delete_geopolitical_entity(&self, id: i64) -> Result<bool> {
+    fn delete_geopolitical_entity(&self, id: i64) -> Result<bool> {
         let affected = self.conn.execute("DELETE FROM geopolitical_entities WHERE id = ?1", params![id])?;
         Ok(affected > 0)
     }
 
-    fn row_to_geopolitical_entity(&self, row: &rusqlite::Row) -> Result<GeopoliticalEntity> {
-        let position_str: String = row.get(3)?;
-        let created_at: String = row.get(5)?;
-
-        Ok(GeopoliticalEntity {
-            id: row.get(0)?,
-            name: row.get(1)?,
-            era_id: row.get(2)?,
-            position: SystemPosition::from_str(&position_str).unwrap_or(SystemPosition::Periphery),
-            notes: row.get(4)?,
-            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
-        })
-    }
-
     // Surplus Flow Operations
 
-    pub fn create_surplus_flow(
+    fn create_surplus_flow(
         &self,
         from_entity_id: i64,
         to_entity_id: i64,
@@ -4164,7 +8732,7 @@ impl Database {
         })
     }
 
-    pub fn get_surplus_flow(&self, id: i64) -> Result<Option<SurplusFlow>> {
+    fn get_surplus_flow(&self, id: i64) -> Result<Option<SurplusFlow>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, from_entity_id, to_entity_id, commodity, era_id, video_id, claim_id, notes, created_at FROM surplus_flows WHERE id = ?1"
         )?;
@@ -4177,7 +8745,7 @@ impl Database {
         }
     }
 
-    pub fn list_surplus_flows_for_entity(&self, entity_id: i64) -> Result<Vec<SurplusFlow>> {
+    fn list_surplus_flows_for_entity(&self, entity_id: i64) -> Result<Vec<SurplusFlow>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, from_entity_id, to_entity_id, commodity, era_id, video_id, claim_id, notes, created_at FROM surplus_flows WHERE from_entity_id = ?1 OR to_entity_id = ?1 ORDER BY created_at DESC"
         )?;
@@ -4191,7 +8759,7 @@ impl Database {
         Ok(flows)
     }
 
-    pub fn list_surplus_flows_by_era(&self, era_id: i64) -> Result<Vec<SurplusFlow>> {
+    fn list_surplus_flows_by_era(&self, era_id: i64) -> Result<Vec<SurplusFlow>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, from_entity_id, to_entity_id, commodity, era_id, video_id, claim_id, notes, created_at FROM surplus_flows WHERE era_id = ?1 ORDER BY commodity, created_at DESC"
         )?;
@@ -4205,7 +8773,7 @@ impl Database {
         Ok(flows)
     }
 
-    pub fn list_all_surplus_flows(&self) -> Result<Vec<SurplusFlow>> {
+    fn list_all_surplus_flows(&self) -> Result<Vec<SurplusFlow>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, from_entity_id, to_entity_id, commodity, era_id, video_id, claim_id, notes, created_at FROM surplus_flows ORDER BY era_id, commodity, created_at DESC"
         )?;
@@ -4219,30 +8787,14 @@ impl Database {
         Ok(flows)
     }
 
-    pub fn delete_surplus_flow(&self, id: i64) -> Result<bool> {
+    fn delete_surplus_flow(&self, id: i64) -> Result<bool> {
         let affected = self.conn.execute("DELETE FROM surplus_flows WHERE id = ?1", params![id])?;
         Ok(affected > 0)
     }
 
-    fn row_to_surplus_flow(&self, row: &rusqlite::Row) -> Result<SurplusFlow> {
-        let created_at: String = row.get(8)?;
-
-        Ok(SurplusFlow {
-            id: row.get(0)?,
-            from_entity_id: row.get(1)?,
-            to_entity_id: row.get(2)?,
-            commodity: row.get(3)?,
-            era_id: row.get(4)?,
-            video_id: row.get(5)?,
-            claim_id: row.get(6)?,
-            notes: row.get(7)?,
-            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
-        })
-    }
-
     // Temporal Observation Operations
 
-    pub fn create_temporal_observation(
+    fn create_temporal_observation(
         &self,
         claim_id: i64,
         timescale: BraudelTimescale,
@@ -4263,7 +8815,7 @@ impl Database {
         })
     }
 
-    pub fn get_temporal_observation(&self, id: i64) -> Result<Option<TemporalObservation>> {
+    fn get_temporal_observation(&self, id: i64) -> Result<Option<TemporalObservation>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, claim_id, timescale, notes, created_at FROM temporal_observations WHERE id = ?1"
         )?;
@@ -4276,7 +8828,7 @@ impl Database {
         }
     }
 
-    pub fn get_temporal_observations_for_claim(&self, claim_id: i64) -> Result<Vec<TemporalObservation>> {
+    fn get_temporal_observations_for_claim(&self, claim_id: i64) -> Result<Vec<TemporalObservation>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, claim_id, timescale, notes, created_at FROM temporal_observations WHERE claim_id = ?1 ORDER BY timescale"
         )?;
@@ -4290,7 +8842,7 @@ impl Database {
         Ok(observations)
     }
 
-    pub fn list_temporal_observations_by_timescale(&self, timescale: BraudelTimescale) -> Result<Vec<TemporalObservation>> {
+    fn list_temporal_observations_by_timescale(&self, timescale: BraudelTimescale) -> Result<Vec<TemporalObservation>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, claim_id, timescale, notes, created_at FROM temporal_observations WHERE timescale = ?1 ORDER BY created_at DESC"
         )?;
@@ -4304,7 +8856,7 @@ impl Database {
         Ok(observations)
     }
 
-    pub fn list_all_temporal_observations(&self) -> Result<Vec<TemporalObservation>> {
+    fn list_all_temporal_observations(&self) -> Result<Vec<TemporalObservation>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, claim_id, timescale, notes, created_at FROM temporal_observations ORDER BY timescale, created_at DESC"
         )?;
@@ -4318,27 +8870,14 @@ impl Database {
         Ok(observations)
     }
 
-    pub fn delete_temporal_observation(&self, id: i64) -> Result<bool> {
+    fn delete_temporal_observation(&self, id: i64) -> Result<bool> {
         let affected = self.conn.execute("DELETE FROM temporal_observations WHERE id = ?1", params![id])?;
         Ok(affected > 0)
     }
 
-    fn row_to_temporal_observation(&self, row: &rusqlite::Row) -> Result<TemporalObservation> {
-        let timescale_str: String = row.get(2)?;
-        let created_at: String = row.get(4)?;
-
-        Ok(TemporalObservation {
-            id: row.get(0)?,
-            claim_id: row.get(1)?,
-            timescale: BraudelTimescale::from_str(&timescale_str).unwrap_or(BraudelTimescale::Event),
-            notes: row.get(3)?,
-            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
-        })
-    }
-
     // Framework Statistics
 
-    pub fn get_framework_stats(&self) -> Result<FrameworkStats> {
+    fn get_framework_stats(&self) -> Result<FrameworkStats> {
         let cyclical_count: i64 = self.conn.query_row(
             "SELECT COUNT(*) FROM cyclical_indicators", [], |row| row.get(0)
         )?;
@@ -4372,7 +8911,7 @@ impl Database {
 
     // 9.1 Maps of Content
 
-    pub fn create_moc(&self, title: &str, description: Option<&str>) -> Result<MapOfContent> {
+    fn create_moc(&self, title: &str, description: Option<&str>) -> Result<MapOfContent> {
         let now = Utc::now();
         self.conn.execute(
             "INSERT INTO mocs (title, description, created_at, updated_at) VALUES (?1, ?2, ?3, ?4)",
@@ -4388,7 +8927,7 @@ impl Database {
         })
     }
 
-    pub fn get_moc(&self, id: i64) -> Result<Option<MapOfContent>> {
+    fn get_moc(&self, id: i64) -> Result<Option<MapOfContent>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, title, description, created_at, updated_at FROM mocs WHERE id = ?1"
         )?;
@@ -4401,7 +8940,7 @@ impl Database {
         }
     }
 
-    pub fn get_moc_by_title(&self, title: &str) -> Result<Option<MapOfContent>> {
+    fn get_moc_by_title(&self, title: &str) -> Result<Option<MapOfContent>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, title, description, created_at, updated_at FROM mocs WHERE title = ?1 COLLATE NOCASE"
         )?;
@@ -4414,7 +8953,7 @@ impl Database {
         }
     }
 
-    pub fn list_mocs(&self) -> Result<Vec<MapOfContent>> {
+    fn list_mocs(&self) -> Result<Vec<MapOfContent>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, title, description, created_at, updated_at FROM mocs ORDER BY title"
         )?;
@@ -4428,7 +8967,7 @@ impl Database {
         Ok(mocs)
     }
 
-    pub fn update_moc(&self, id: i64, title: Option<&str>, description: Option<&str>) -> Result<bool> {
+    fn update_moc(&self, id: i64, title: Option<&str>, description: Option<&str>) -> Result<bool> {
         let now = Utc::now();
         let mut updates = vec!["updated_at = ?1"];
         let mut param_idx = 2;
@@ -4460,12 +8999,12 @@ impl Database {
         Ok(affected > 0)
     }
 
-    pub fn delete_moc(&self, id: i64) -> Result<bool> {
+    fn delete_moc(&self, id: i64) -> Result<bool> {
         let affected = self.conn.execute("DELETE FROM mocs WHERE id = ?1", params![id])?;
         Ok(affected > 0)
     }
 
-    pub fn add_claim_to_moc(&self, moc_id: i64, claim_id: i64, sort_order: i32) -> Result<()> {
+    fn add_claim_to_moc(&self, moc_id: i64, claim_id: i64, sort_order: i32) -> Result<()> {
         let now = Utc::now();
         self.conn.execute(
             "INSERT OR REPLACE INTO moc_claims (moc_id, claim_id, sort_order, added_at) VALUES (?1, ?2, ?3, ?4)",
@@ -4479,7 +9018,7 @@ impl Database {
         Ok(())
     }
 
-    pub fn remove_claim_from_moc(&self, moc_id: i64, claim_id: i64) -> Result<bool> {
+    fn remove_claim_from_moc(&self, moc_id: i64, claim_id: i64) -> Result<bool> {
         let affected = self.conn.execute(
             "DELETE FROM moc_claims WHERE moc_id = ?1 AND claim_id = ?2",
             params![moc_id, claim_id],
@@ -4487,13 +9026,37 @@ impl Database {
         Ok(affected > 0)
     }
 
-    pub fn get_moc_claims(&self, moc_id: i64) -> Result<Vec<Claim>> {
+    fn next_moc_sort_order(&self, moc_id: i64) -> Result<i32> {
+        Ok(self.conn.query_row(
+            "SELECT COALESCE(MAX(sort_order) + 1, 0) FROM moc_claims WHERE moc_id = ?1",
+            params![moc_id],
+            |row| row.get(0),
+        )?)
+    }
+
+    fn reorder_moc_claims(&self, moc_id: i64, claim_ids: &[i64]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        for (order, claim_id) in claim_ids.iter().enumerate() {
+            tx.execute(
+                "UPDATE moc_claims SET sort_order = ?1 WHERE moc_id = ?2 AND claim_id = ?3",
+                params![order as i32, moc_id, claim_id],
+            )?;
+        }
+        tx.execute(
+            "UPDATE mocs SET updated_at = ?1 WHERE id = ?2",
+            params![Utc::now().to_rfc3339(), moc_id],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn get_moc_claims(&self, moc_id: i64) -> Result<Vec<Claim>> {
         let mut stmt = self.conn.prepare(
             r#"
-            SELECT c.id, c.text, c.video_id, c.timestamp, c.source_quote, c.category, c.confidence, c.created_at
+            SELECT c.id, c.text, c.video_id, c.timestamp, c.source_quote, c.category, c.confidence, c.created_at, c.status, c.date_start, c.date_end
             FROM claims c
             JOIN moc_claims mc ON mc.claim_id = c.id
-            WHERE mc.moc_id = ?1
+            WHERE mc.moc_id = ?1 AND c.archived_at IS NULL
             ORDER BY mc.sort_order, c.created_at
             "#
         )?;
@@ -4507,7 +9070,7 @@ impl Database {
         Ok(claims)
     }
 
-    pub fn add_sub_moc(&self, parent_id: i64, child_id: i64) -> Result<()> {
+    fn add_sub_moc(&self, parent_id: i64, child_id: i64) -> Result<()> {
         self.conn.execute(
             "INSERT OR IGNORE INTO moc_hierarchy (parent_moc_id, child_moc_id) VALUES (?1, ?2)",
             params![parent_id, child_id],
@@ -4515,7 +9078,7 @@ impl Database {
         Ok(())
     }
 
-    pub fn get_sub_mocs(&self, moc_id: i64) -> Result<Vec<MapOfContent>> {
+    fn get_sub_mocs(&self, moc_id: i64) -> Result<Vec<MapOfContent>> {
         let mut stmt = self.conn.prepare(
             r#"
             SELECT m.id, m.title, m.description, m.created_at, m.updated_at
@@ -4535,43 +9098,30 @@ impl Database {
         Ok(mocs)
     }
 
-    pub fn get_moc_with_claims(&self, moc_id: i64) -> Result<Option<MocWithClaims>> {
+    fn get_moc_with_claims(&self, moc_id: i64) -> Result<Option<MocWithClaims>> {
         let moc = match self.get_moc(moc_id)? {
             Some(m) => m,
             None => return Ok(None),
         };
 
         let claims = self.get_moc_claims(moc_id)?;
-        let sub_mocs = self.get_sub_mocs(moc_id)?;
-
-        Ok(Some(MocWithClaims { moc, claims, sub_mocs }))
-    }
-
-    pub fn get_moc_claim_count(&self, moc_id: i64) -> Result<i64> {
-        let count: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM moc_claims WHERE moc_id = ?1",
-            params![moc_id],
-            |row| row.get(0),
-        )?;
-        Ok(count)
-    }
-
-    fn row_to_moc(&self, row: &rusqlite::Row) -> Result<MapOfContent> {
-        let created_at: String = row.get(3)?;
-        let updated_at: String = row.get(4)?;
-
-        Ok(MapOfContent {
-            id: row.get(0)?,
-            title: row.get(1)?,
-            description: row.get(2)?,
-            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
-            updated_at: DateTime::parse_from_rfc3339(&updated_at)?.with_timezone(&Utc),
-        })
+        let sub_mocs = self.get_sub_mocs(moc_id)?;
+
+        Ok(Some(MocWithClaims { moc, claims, sub_mocs }))
+    }
+
+    fn get_moc_claim_count(&self, moc_id: i64) -> Result<i64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM moc_claims WHERE moc_id = ?1",
+            params![moc_id],
+            |row| row.get(0),
+        )?;
+        Ok(count)
     }
 
     // 9.2 Research Questions
 
-    pub fn create_research_question(
+    fn create_research_question(
         &self,
         question: &str,
         parent_id: Option<i64>,
@@ -4604,7 +9154,7 @@ impl Database {
         })
     }
 
-    pub fn get_research_question(&self, id: i64) -> Result<Option<ResearchQuestion>> {
+    fn get_research_question(&self, id: i64) -> Result<Option<ResearchQuestion>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, question, status, parent_question_id, notes, created_at, updated_at FROM research_questions WHERE id = ?1"
         )?;
@@ -4617,7 +9167,7 @@ impl Database {
         }
     }
 
-    pub fn list_research_questions(&self, status: Option<QuestionStatus>) -> Result<Vec<ResearchQuestion>> {
+    fn list_research_questions(&self, status: Option<QuestionStatus>) -> Result<Vec<ResearchQuestion>> {
         let questions = if let Some(s) = status {
             let mut stmt = self.conn.prepare(
                 "SELECT id, question, status, parent_question_id, notes, created_at, updated_at FROM research_questions WHERE status = ?1 ORDER BY created_at DESC"
@@ -4642,7 +9192,7 @@ impl Database {
         Ok(questions)
     }
 
-    pub fn update_question_status(&self, id: i64, status: QuestionStatus) -> Result<bool> {
+    fn update_question_status(&self, id: i64, status: QuestionStatus) -> Result<bool> {
         let now = Utc::now();
         let affected = self.conn.execute(
             "UPDATE research_questions SET status = ?1, updated_at = ?2 WHERE id = ?3",
@@ -4651,12 +9201,13 @@ impl Database {
         Ok(affected > 0)
     }
 
-    pub fn delete_research_question(&self, id: i64) -> Result<bool> {
+    fn delete_research_question(&self, id: i64) -> Result<bool> {
+        self.delete_dependents("research_questions", &id)?;
         let affected = self.conn.execute("DELETE FROM research_questions WHERE id = ?1", params![id])?;
         Ok(affected > 0)
     }
 
-    pub fn add_evidence_to_question(
+    fn add_evidence_to_question(
         &self,
         question_id: i64,
         claim_id: Option<i64>,
@@ -4676,10 +9227,10 @@ impl Database {
         Ok(())
     }
 
-    pub fn get_question_evidence_claims(&self, question_id: i64) -> Result<Vec<Claim>> {
+    fn get_question_evidence_claims(&self, question_id: i64) -> Result<Vec<Claim>> {
         let mut stmt = self.conn.prepare(
             r#"
-            SELECT c.id, c.text, c.video_id, c.timestamp, c.source_quote, c.category, c.confidence, c.created_at
+            SELECT c.id, c.text, c.video_id, c.timestamp, c.source_quote, c.category, c.confidence, c.created_at, c.status, c.date_start, c.date_end
             FROM claims c
             JOIN question_evidence qe ON qe.claim_id = c.id
             WHERE qe.question_id = ?1
@@ -4696,10 +9247,10 @@ impl Database {
         Ok(claims)
     }
 
-    pub fn get_question_evidence_videos(&self, question_id: i64) -> Result<Vec<Video>> {
+    fn get_question_evidence_videos(&self, question_id: i64) -> Result<Vec<Video>> {
         let mut stmt = self.conn.prepare(
             r#"
-            SELECT v.id, v.url, v.title, v.channel, v.upload_date, v.description, v.added_at
+            SELECT v.id, v.url, v.title, v.channel, v.upload_date, v.description, v.added_at, v.duration, v.view_count, v.thumbnail_url
             FROM videos v
             JOIN question_evidence qe ON qe.video_id = v.id
             WHERE qe.question_id = ?1
@@ -4716,7 +9267,7 @@ impl Database {
         Ok(videos)
     }
 
-    pub fn get_sub_questions(&self, parent_id: i64) -> Result<Vec<ResearchQuestion>> {
+    fn get_sub_questions(&self, parent_id: i64) -> Result<Vec<ResearchQuestion>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, question, status, parent_question_id, notes, created_at, updated_at FROM research_questions WHERE parent_question_id = ?1 ORDER BY created_at"
         )?;
@@ -4730,7 +9281,7 @@ impl Database {
         Ok(questions)
     }
 
-    pub fn get_question_with_evidence(&self, question_id: i64) -> Result<Option<QuestionWithEvidence>> {
+    fn get_question_with_evidence(&self, question_id: i64) -> Result<Option<QuestionWithEvidence>> {
         let question = match self.get_research_question(question_id)? {
             Some(q) => q,
             None => return Ok(None),
@@ -4748,25 +9299,164 @@ impl Database {
         }))
     }
 
-    fn row_to_research_question(&self, row: &rusqlite::Row) -> Result<ResearchQuestion> {
-        let status_str: String = row.get(2)?;
-        let created_at: String = row.get(5)?;
-        let updated_at: String = row.get(6)?;
+    // Suggested research questions
 
-        Ok(ResearchQuestion {
-            id: row.get(0)?,
-            question: row.get(1)?,
-            status: QuestionStatus::from_str(&status_str).unwrap_or(QuestionStatus::Active),
-            parent_question_id: row.get(3)?,
-            notes: row.get(4)?,
-            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
-            updated_at: DateTime::parse_from_rfc3339(&updated_at)?.with_timezone(&Utc),
-        })
+    fn generate_question_suggestions(&self) -> Result<Vec<SuggestedQuestion>> {
+        let mut candidates: Vec<(String, &str, Vec<i64>, Vec<String>)> = Vec::new();
+
+        // Contradicting claim pairs
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT a.id, a.text, a.video_id, b.id, b.text, b.video_id
+            FROM claim_links cl
+            JOIN claims a ON a.id = cl.source_claim_id
+            JOIN claims b ON b.id = cl.target_claim_id
+            WHERE cl.link_type = 'contradicts'
+            "#
+        )?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let (a_id, a_text, a_video): (i64, String, String) = (row.get(0)?, row.get(1)?, row.get(2)?);
+            let (b_id, b_text, b_video): (i64, String, String) = (row.get(3)?, row.get(4)?, row.get(5)?);
+            let mut videos = vec![a_video];
+            if !videos.contains(&b_video) {
+                videos.push(b_video);
+            }
+            candidates.push((
+                format!("Which holds up: \"{}\" or \"{}\"?", a_text, b_text),
+                "contradiction",
+                vec![a_id, b_id],
+                videos,
+            ));
+        }
+
+        // Contradictions found by pattern detection
+        for pattern in self.list_detected_patterns(Some(PatternType::Contradiction))? {
+            candidates.push((
+                format!("What resolves the contradiction: {}?", pattern.description.trim_end_matches(['.', '?'])),
+                "contradiction",
+                pattern.claim_ids,
+                pattern.video_ids,
+            ));
+        }
+
+        // Topics covered by a single video
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT t.name, GROUP_CONCAT(vt.video_id)
+            FROM topics t
+            JOIN video_topics vt ON vt.topic_id = t.id
+            GROUP BY t.id
+            HAVING COUNT(vt.video_id) = 1
+            "#
+        )?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let topic: String = row.get(0)?;
+            let videos: String = row.get(1)?;
+            candidates.push((
+                format!("What do other sources say about {}?", topic),
+                "thin_topic",
+                Vec::new(),
+                videos.split(',').map(|v| v.to_string()).collect(),
+            ));
+        }
+
+        // Connected claim clusters (3+) with no claim in any MOC
+        for cluster in self.unmapped_claim_clusters(3)? {
+            let first = self.get_claim(cluster[0])?;
+            let Some(first) = first else { continue };
+            let mut videos = Vec::new();
+            for id in &cluster {
+                if let Some(c) = self.get_claim(*id)? {
+                    if !videos.contains(&c.video_id) {
+                        videos.push(c.video_id);
+                    }
+                }
+            }
+            candidates.push((
+                format!("What theme connects \"{}\" and {} linked claims?", first.text, cluster.len() - 1),
+                "unmapped_cluster",
+                cluster,
+                videos,
+            ));
+        }
+
+        let now = Utc::now().to_rfc3339();
+        for (question, reason, claim_ids, video_ids) in candidates {
+            self.conn.execute(
+                "INSERT OR IGNORE INTO question_suggestions (question, reason, claim_ids_json, video_ids_json, status, created_at)
+                 VALUES (?1, ?2, ?3, ?4, 'pending', ?5)",
+                params![question, reason, serde_json::to_string(&claim_ids)?, serde_json::to_string(&video_ids)?, now],
+            )?;
+        }
+
+        self.list_question_suggestions(false)
+    }
+
+    fn list_question_suggestions(&self, include_resolved: bool) -> Result<Vec<SuggestedQuestion>> {
+        let sql = if include_resolved {
+            "SELECT id, question, reason, claim_ids_json, video_ids_json, status, created_at FROM question_suggestions ORDER BY id"
+        } else {
+            "SELECT id, question, reason, claim_ids_json, video_ids_json, status, created_at FROM question_suggestions WHERE status = 'pending' ORDER BY id"
+        };
+        let mut stmt = self.conn.prepare(sql)?;
+        let mut suggestions = Vec::new();
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            suggestions.push(self.row_to_suggested_question(row)?);
+        }
+        Ok(suggestions)
+    }
+
+    fn get_question_suggestion(&self, id: i64) -> Result<Option<SuggestedQuestion>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, question, reason, claim_ids_json, video_ids_json, status, created_at FROM question_suggestions WHERE id = ?1"
+        )?;
+        let mut rows = stmt.query(params![id])?;
+        if let Some(row) = rows.next()? {
+            Ok(Some(self.row_to_suggested_question(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn accept_question_suggestion(&self, id: i64) -> Result<Option<ResearchQuestion>> {
+        let suggestion = match self.get_question_suggestion(id)? {
+            Some(s) if s.status == "pending" => s,
+            _ => return Ok(None),
+        };
+
+        let tx = self.conn.unchecked_transaction()?;
+        let question = self.create_research_question(&suggestion.question, None, Some(&format!("Suggested ({})", suggestion.reason)))?;
+        for claim_id in &suggestion.claim_ids {
+            self.add_evidence_to_question(question.id, Some(*claim_id), None, Some(&suggestion.reason))?;
+        }
+        if suggestion.claim_ids.is_empty() {
+            for video_id in &suggestion.video_ids {
+                self.add_evidence_to_question(question.id, None, Some(video_id), Some(&suggestion.reason))?;
+            }
+        }
+
+        self.conn.execute(
+            "UPDATE question_suggestions SET status = 'accepted' WHERE id = ?1",
+            params![id],
+        )?;
+        tx.commit()?;
+        Ok(Some(question))
+    }
+
+    fn dismiss_question_suggestion(&self, id: i64) -> Result<bool> {
+        let affected = self.conn.execute(
+            "UPDATE question_suggestions SET status = 'dismissed' WHERE id = ?1 AND status = 'pending'",
+            params![id],
+        )?;
+        Ok(affected > 0)
     }
 
     // 9.3 Pattern Detection
 
-    pub fn save_detected_pattern(
+    fn save_detected_pattern(
         &self,
         pattern_type: PatternType,
         description: &str,
@@ -4804,7 +9494,7 @@ impl Database {
         })
     }
 
-    pub fn get_detected_pattern(&self, id: i64) -> Result<Option<DetectedPattern>> {
+    fn get_detected_pattern(&self, id: i64) -> Result<Option<DetectedPattern>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, pattern_type, description, video_ids_json, claim_ids_json, confidence, detected_at FROM detected_patterns WHERE id = ?1"
         )?;
@@ -4817,7 +9507,7 @@ impl Database {
         }
     }
 
-    pub fn list_detected_patterns(&self, pattern_type: Option<PatternType>) -> Result<Vec<DetectedPattern>> {
+    fn list_detected_patterns(&self, pattern_type: Option<PatternType>) -> Result<Vec<DetectedPattern>> {
         let patterns = if let Some(pt) = pattern_type {
             let mut stmt = self.conn.prepare(
                 "SELECT id, pattern_type, description, video_ids_json, claim_ids_json, confidence, detected_at FROM detected_patterns WHERE pattern_type = ?1 ORDER BY detected_at DESC"
@@ -4842,31 +9532,100 @@ impl Database {
         Ok(patterns)
     }
 
-    pub fn delete_detected_pattern(&self, id: i64) -> Result<bool> {
+    fn delete_detected_pattern(&self, id: i64) -> Result<bool> {
         let affected = self.conn.execute("DELETE FROM detected_patterns WHERE id = ?1", params![id])?;
         Ok(affected > 0)
     }
 
-    fn row_to_detected_pattern(&self, row: &rusqlite::Row) -> Result<DetectedPattern> {
-        let pattern_type_str: String = row.get(1)?;
-        let video_ids_json: String = row.get(3)?;
-        let claim_ids_json: String = row.get(4)?;
-        let detected_at: String = row.get(6)?;
+    fn set_pattern_provenance(&self, provenance: &PatternProvenance) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO pattern_provenance (pattern_id, provider, model, prompt_version, run_id, scope) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                provenance.pattern_id,
+                provenance.provider,
+                provenance.model,
+                provenance.prompt_version,
+                provenance.run_id,
+                provenance.scope,
+            ],
+        )?;
+        Ok(())
+    }
 
-        Ok(DetectedPattern {
-            id: row.get(0)?,
-            pattern_type: PatternType::from_str(&pattern_type_str).unwrap_or(PatternType::RecurringTheme),
-            description: row.get(2)?,
-            video_ids: serde_json::from_str(&video_ids_json)?,
-            claim_ids: serde_json::from_str(&claim_ids_json)?,
-            confidence: row.get(5)?,
-            detected_at: DateTime::parse_from_rfc3339(&detected_at)?.with_timezone(&Utc),
-        })
+    fn list_pattern_provenance(&self) -> Result<HashMap<i64, PatternProvenance>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT pattern_id, provider, model, prompt_version, run_id, scope FROM pattern_provenance"
+        )?;
+        let mut provenance = HashMap::new();
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let p = PatternProvenance {
+                pattern_id: row.get(0)?,
+                provider: row.get(1)?,
+                model: row.get(2)?,
+                prompt_version: row.get(3)?,
+                run_id: row.get(4)?,
+                scope: row.get(5)?,
+            };
+            provenance.insert(p.pattern_id, p);
+        }
+        Ok(provenance)
+    }
+
+    fn cluster_claims(&self, claims: &[Claim], max_size: usize) -> Result<Vec<Vec<Claim>>> {
+        let max_size = max_size.max(2);
+        let wanted: HashMap<String, &Claim> = claims.iter().map(|c| (c.id.to_string(), c)).collect();
+
+        let mut by_model: HashMap<String, Vec<Embedding>> = HashMap::new();
+        for emb in self.list_embeddings_by_type(EmbeddingSource::Claim)? {
+            if wanted.contains_key(&emb.source_id) {
+                by_model.entry(emb.model.clone()).or_default().push(emb);
+            }
+        }
+        let embedded = by_model
+            .into_values()
+            .max_by_key(|group| group.len())
+            .unwrap_or_default();
+
+        let mut groups: Vec<Vec<Claim>> = Vec::new();
+        if !embedded.is_empty() {
+            let k = embedded.len().div_ceil(max_size);
+            let centroids = kmeans_centroids(&embedded, k);
+            let mut members: Vec<Vec<Claim>> = vec![Vec::new(); centroids.len()];
+            for emb in &embedded {
+                let nearest = (0..centroids.len())
+                    .max_by(|&a, &b| {
+                        cosine_similarity(&centroids[a], &emb.vector)
+                            .partial_cmp(&cosine_similarity(&centroids[b], &emb.vector))
+                            .unwrap_or(std::cmp::Ordering::Equal)
+                    })
+                    .unwrap_or(0);
+                members[nearest].push(wanted[&emb.source_id].clone());
+            }
+            groups.extend(members);
+        }
+
+        let embedded_ids: HashSet<&str> = embedded.iter().map(|e| e.source_id.as_str()).collect();
+        let mut by_category: Vec<(ClaimCategory, Vec<Claim>)> = Vec::new();
+        for claim in claims.iter().filter(|c| !embedded_ids.contains(c.id.to_string().as_str())) {
+            match by_category.iter_mut().find(|(category, _)| *category == claim.category) {
+                Some((_, group)) => group.push(claim.clone()),
+                None => by_category.push((claim.category, vec![claim.clone()])),
+            }
+        }
+        groups.extend(by_category.into_iter().map(|(_, group)| group));
+
+        // k-means clusters can come out uneven, so cap every group at max_size
+        Ok(groups
+            .into_iter()
+            .flat_map(|group| group.chunks(max_size).map(|c| c.to_vec()).collect::<Vec<_>>())
+            .filter(|group| group.len() >= 2)
+            .collect())
     }
 
     // 9.4 Review System
 
-    pub fn record_claim_access(&self, claim_id: i64) -> Result<()> {
+    fn record_claim_access(&self, claim_id: i64) -> Result<()> {
         let now = Utc::now();
         self.conn.execute(
             "INSERT OR REPLACE INTO claim_access (claim_id, last_accessed) VALUES (?1, ?2)",
@@ -4875,7 +9634,7 @@ impl Database {
         Ok(())
     }
 
-    pub fn get_stale_claims(&self, days: i64) -> Result<Vec<Claim>> {
+    fn get_stale_claims(&self, days: i64) -> Result<Vec<Claim>> {
         let cutoff = Utc::now() - chrono::Duration::days(days);
 
         // Get claims that either:
@@ -4883,11 +9642,11 @@ impl Database {
         // 2. Were last accessed before the cutoff
         let mut stmt = self.conn.prepare(
             r#"
-            SELECT c.id, c.text, c.video_id, c.timestamp, c.source_quote, c.category, c.confidence, c.created_at
+            SELECT c.id, c.text, c.video_id, c.timestamp, c.source_quote, c.category, c.confidence, c.created_at, c.status, c.date_start, c.date_end
             FROM claims c
             LEFT JOIN claim_access ca ON ca.claim_id = c.id
-            WHERE ca.claim_id IS NULL
-               OR ca.last_accessed < ?1
+            WHERE c.archived_at IS NULL
+              AND (ca.claim_id IS NULL OR ca.last_accessed < ?1)
             ORDER BY COALESCE(ca.last_accessed, c.created_at)
             "#
         )?;
@@ -4901,14 +9660,53 @@ impl Database {
         Ok(claims)
     }
 
-    pub fn get_orphan_claims(&self) -> Result<Vec<Claim>> {
+    fn suggest_claim_links(&self, claim_id: i64, limit: usize) -> Result<Vec<(Claim, f32)>> {
+        let Some(with_links) = self.get_claim_with_links(claim_id)? else {
+            anyhow::bail!("Claim not found: #{}", claim_id);
+        };
+        let mut excluded: std::collections::HashSet<i64> = with_links
+            .outgoing_links
+            .iter()
+            .chain(&with_links.incoming_links)
+            .map(|(_, other)| other.id)
+            .collect();
+        excluded.insert(claim_id);
+
+        let embeddings = self.list_embeddings_by_type(EmbeddingSource::Claim)?;
+        let own_id = claim_id.to_string();
+        let own: Vec<&Embedding> = embeddings.iter().filter(|e| e.source_id == own_id).collect();
+
+        let mut best: HashMap<i64, f32> = HashMap::new();
+        for emb in &embeddings {
+            let Ok(other_id) = emb.source_id.parse::<i64>() else { continue };
+            if excluded.contains(&other_id) {
+                continue;
+            }
+            for mine in own.iter().filter(|m| m.model == emb.model) {
+                let score = cosine_similarity(&mine.vector, &emb.vector);
+                let entry = best.entry(other_id).or_insert(score);
+                *entry = entry.max(score);
+            }
+        }
+
+        let claims: HashMap<i64, Claim> = self.list_all_claims()?.into_iter().map(|c| (c.id, c)).collect();
+        let mut suggestions: Vec<(Claim, f32)> = best
+            .into_iter()
+            .filter_map(|(id, score)| claims.get(&id).map(|c| (c.clone(), score)))
+            .collect();
+        suggestions.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        suggestions.truncate(limit);
+        Ok(suggestions)
+    }
+
+    fn get_orphan_claims(&self) -> Result<Vec<Claim>> {
         // Claims with fewer than 2 links
         self.get_unlinked_claims()
     }
 
-    pub fn get_random_claims(&self, count: usize) -> Result<Vec<Claim>> {
+    fn get_random_claims(&self, count: usize) -> Result<Vec<Claim>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, text, video_id, timestamp, source_quote, category, confidence, created_at FROM claims ORDER BY RANDOM() LIMIT ?1"
+            "SELECT id, text, video_id, timestamp, source_quote, category, confidence, created_at, status, date_start, date_end FROM claims WHERE archived_at IS NULL ORDER BY RANDOM() LIMIT ?1"
         )?;
 
         let mut claims = Vec::new();
@@ -4920,7 +9718,7 @@ impl Database {
         Ok(claims)
     }
 
-    pub fn get_review_queue(&self, stale_days: i64, random_count: usize) -> Result<ReviewQueue> {
+    fn get_review_queue(&self, stale_days: i64, random_count: usize) -> Result<ReviewQueue> {
         let stale_claims = self.get_stale_claims(stale_days)?;
         let orphan_claims = self.get_orphan_claims()?;
         let random_suggestions = self.get_random_claims(random_count)?;
@@ -4932,9 +9730,100 @@ impl Database {
         })
     }
 
+    // Spaced repetition (quiz)
+
+    fn get_due_claims(&self, topic: Option<&str>, limit: usize) -> Result<Vec<Claim>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT c.id, c.text, c.video_id, c.timestamp, c.source_quote, c.category, c.confidence, c.created_at, c.status, c.date_start, c.date_end
+            FROM claims c
+            LEFT JOIN claim_schedule cs ON cs.claim_id = c.id
+            WHERE c.archived_at IS NULL
+              AND (cs.claim_id IS NULL OR cs.due_at <= ?1)
+              AND (?2 IS NULL OR c.video_id IN (
+                    SELECT vt.video_id FROM video_topics vt
+                    JOIN topics t ON t.id = vt.topic_id
+                    WHERE t.name = ?2 COLLATE NOCASE))
+            ORDER BY cs.due_at IS NOT NULL, cs.due_at, RANDOM()
+            LIMIT ?3
+            "#
+        )?;
+
+        let mut claims = Vec::new();
+        let mut rows = stmt.query(params![Utc::now().to_rfc3339(), topic, limit as i64])?;
+        while let Some(row) = rows.next()? {
+            claims.push(self.row_to_claim(row)?);
+        }
+        Ok(claims)
+    }
+
+    fn get_claim_schedule(&self, claim_id: i64) -> Result<Option<ClaimSchedule>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT claim_id, interval_days, ease, repetitions, lapses, due_at, last_reviewed
+             FROM claim_schedule WHERE claim_id = ?1"
+        )?;
+        let mut rows = stmt.query(params![claim_id])?;
+        if let Some(row) = rows.next()? {
+            let due_at: String = row.get(5)?;
+            let last_reviewed: String = row.get(6)?;
+            Ok(Some(ClaimSchedule {
+                claim_id: row.get(0)?,
+                interval_days: row.get(1)?,
+                ease: row.get(2)?,
+                repetitions: row.get(3)?,
+                lapses: row.get(4)?,
+                due_at: DateTime::parse_from_rfc3339(&due_at)?.with_timezone(&Utc),
+                last_reviewed: DateTime::parse_from_rfc3339(&last_reviewed)?.with_timezone(&Utc),
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn record_review_result(&self, claim_id: i64, correct: bool) -> Result<ClaimSchedule> {
+        let now = Utc::now();
+        let (mut interval, mut ease, mut reps, mut lapses) = match self.get_claim_schedule(claim_id)? {
+            Some(s) => (s.interval_days, s.ease, s.repetitions, s.lapses),
+            None => (0.0, 2.5, 0, 0),
+        };
+
+        if correct {
+            reps += 1;
+            interval = match reps {
+                1 => 1.0,
+                2 => 3.0,
+                _ => interval * ease,
+            };
+            ease = (ease + 0.1).min(3.0);
+        } else {
+            reps = 0;
+            lapses += 1;
+            interval = 1.0;
+            ease = (ease - 0.2).max(1.3);
+        }
+
+        let due_at = now + chrono::Duration::seconds((interval * 86400.0) as i64);
+        self.conn.execute(
+            "INSERT OR REPLACE INTO claim_schedule (claim_id, interval_days, ease, repetitions, lapses, due_at, last_reviewed)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![claim_id, interval, ease, reps, lapses, due_at.to_rfc3339(), now.to_rfc3339()],
+        )?;
+        self.record_claim_access(claim_id)?;
+
+        Ok(ClaimSchedule {
+            claim_id,
+            interval_days: interval,
+            ease,
+            repetitions: reps,
+            lapses,
+            due_at,
+            last_reviewed: now,
+        })
+    }
+
     // 9.5 Synthesis Statistics
 
-    pub fn get_synthesis_stats(&self) -> Result<SynthesisStats> {
+    fn get_synthesis_stats(&self) -> Result<SynthesisStats> {
         let moc_count: i64 = self.conn.query_row(
             "SELECT COUNT(*) FROM mocs", [], |row| row.get(0)
         )?;
@@ -4966,7 +9855,7 @@ impl Database {
 
     // Phase 10: AI Processing Queue
 
-    pub fn add_to_queue(&self, video_id: &str, priority: i32) -> Result<i64> {
+    fn add_to_queue(&self, video_id: &str, priority: i32) -> Result<i64> {
         let now = Utc::now().to_rfc3339();
         self.conn.execute(
             "INSERT INTO ai_processing_queue (video_id, status, priority, created_at, claims_extracted)
@@ -4974,10 +9863,12 @@ impl Database {
              ON CONFLICT(video_id) DO UPDATE SET priority = ?2",
             params![video_id, priority, now],
         )?;
-        Ok(self.conn.last_insert_rowid())
+        let id = self.conn.last_insert_rowid();
+        self.record_queue_event(video_id, ProcessingStatus::Pending, Some(&format!("priority {}", priority)))?;
+        Ok(id)
     }
 
-    pub fn get_queue(&self, include_completed: bool) -> Result<Vec<AIProcessingQueue>> {
+    fn get_queue(&self, include_completed: bool) -> Result<Vec<AIProcessingQueue>> {
         let sql = if include_completed {
             "SELECT id, video_id, status, priority, created_at, started_at, completed_at, error_message, claims_extracted
              FROM ai_processing_queue
@@ -5013,7 +9904,7 @@ impl Database {
         Ok(items)
     }
 
-    pub fn get_queue_item(&self, video_id: &str) -> Result<Option<AIProcessingQueue>> {
+    fn get_queue_item(&self, video_id: &str) -> Result<Option<AIProcessingQueue>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, video_id, status, priority, created_at, started_at, completed_at, error_message, claims_extracted
              FROM ai_processing_queue WHERE video_id = ?1"
@@ -5027,7 +9918,7 @@ impl Database {
         }
     }
 
-    pub fn get_next_pending(&self) -> Result<Option<AIProcessingQueue>> {
+    fn get_next_pending(&self) -> Result<Option<AIProcessingQueue>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, video_id, status, priority, created_at, started_at, completed_at, error_message, claims_extracted
              FROM ai_processing_queue
@@ -5044,87 +9935,284 @@ impl Database {
         }
     }
 
-    pub fn queue_start(&self, video_id: &str) -> Result<bool> {
+    fn queue_start(&self, video_id: &str) -> Result<bool> {
         let now = Utc::now().to_rfc3339();
         let rows = self.conn.execute(
             "UPDATE ai_processing_queue SET status = 'in_progress', started_at = ?1 WHERE video_id = ?2 AND status = 'pending'",
             params![now, video_id],
         )?;
+        if rows > 0 {
+            self.record_queue_event(video_id, ProcessingStatus::InProgress, None)?;
+        }
+        Ok(rows > 0)
+    }
+
+    fn queue_complete(&self, video_id: &str, claims_extracted: i32) -> Result<bool> {
+        let now = Utc::now().to_rfc3339();
+        let rows = self.conn.execute(
+            "UPDATE ai_processing_queue SET status = 'completed', completed_at = ?1, claims_extracted = ?2 WHERE video_id = ?3",
+            params![now, claims_extracted, video_id],
+        )?;
+        if rows > 0 {
+            self.record_queue_event(video_id, ProcessingStatus::Completed, Some(&format!("{} claims", claims_extracted)))?;
+        }
+        Ok(rows > 0)
+    }
+
+    fn queue_fail(&self, video_id: &str, error_message: &str) -> Result<bool> {
+        let now = Utc::now().to_rfc3339();
+        let rows = self.conn.execute(
+            "UPDATE ai_processing_queue SET status = 'failed', completed_at = ?1, error_message = ?2 WHERE video_id = ?3",
+            params![now, error_message, video_id],
+        )?;
+        if rows > 0 {
+            self.record_queue_event(video_id, ProcessingStatus::Failed, Some(error_message))?;
+        }
+        Ok(rows > 0)
+    }
+
+    fn queue_skip(&self, video_id: &str) -> Result<bool> {
+        let now = Utc::now().to_rfc3339();
+        let rows = self.conn.execute(
+            "UPDATE ai_processing_queue SET status = 'skipped', completed_at = ?1 WHERE video_id = ?2",
+            params![now, video_id],
+        )?;
+        if rows > 0 {
+            self.record_queue_event(video_id, ProcessingStatus::Skipped, None)?;
+        }
+        Ok(rows > 0)
+    }
+
+    fn queue_reset(&self, video_id: &str) -> Result<bool> {
+        let rows = self.conn.execute(
+            "UPDATE ai_processing_queue SET status = 'pending', started_at = NULL, completed_at = NULL, error_message = NULL WHERE video_id = ?1",
+            params![video_id],
+        )?;
+        if rows > 0 {
+            self.record_queue_event(video_id, ProcessingStatus::Pending, Some("reset"))?;
+        }
+        Ok(rows > 0)
+    }
+
+    fn queue_set_priority(&self, video_id: &str, priority: i32) -> Result<bool> {
+        let rows = self.conn.execute(
+            "UPDATE ai_processing_queue SET priority = ?1 WHERE video_id = ?2",
+            params![priority, video_id],
+        )?;
         Ok(rows > 0)
     }
 
-    pub fn queue_complete(&self, video_id: &str, claims_extracted: i32) -> Result<bool> {
-        let now = Utc::now().to_rfc3339();
-        let rows = self.conn.execute(
-            "UPDATE ai_processing_queue SET status = 'completed', completed_at = ?1, claims_extracted = ?2 WHERE video_id = ?3",
-            params![now, claims_extracted, video_id],
+    fn queue_clear(&self, status: ProcessingStatus) -> Result<usize> {
+        let rows = self.conn.execute(
+            "DELETE FROM ai_processing_queue WHERE status = ?1",
+            params![status.as_str()],
+        )?;
+        Ok(rows)
+    }
+
+    fn get_pending_video_ids(&self) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT video_id FROM ai_processing_queue WHERE status = 'pending' ORDER BY priority DESC, created_at ASC"
+        )?;
+        let mut ids = Vec::new();
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            ids.push(row.get(0)?);
+        }
+        Ok(ids)
+    }
+
+    fn get_queue_history(&self, video_id: &str) -> Result<Vec<QueueEvent>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT video_id, status, detail, created_at FROM queue_history WHERE video_id = ?1 ORDER BY id"
+        )?;
+        let mut events = Vec::new();
+        let mut rows = stmt.query(params![video_id])?;
+        while let Some(row) = rows.next()? {
+            let status_str: String = row.get(1)?;
+            let created_str: String = row.get(3)?;
+            events.push(QueueEvent {
+                video_id: row.get(0)?,
+                status: ProcessingStatus::from_str(&status_str).unwrap_or(ProcessingStatus::Pending),
+                detail: row.get(2)?,
+                created_at: DateTime::parse_from_rfc3339(&created_str)?.with_timezone(&Utc),
+            });
+        }
+        Ok(events)
+    }
+
+    // Per-video pipeline status
+
+    fn get_video_status(&self, video_id: &str) -> Result<Option<VideoStatus>> {
+        let video = match self.get_video(video_id)? {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        let transcript = self.get_transcript(video_id)?;
+        let (transcript_segments, transcript_chars, transcript_punctuated) = match &transcript {
+            Some(t) => {
+                let sentence_ends = t.full_text.matches(['.', '?', '!']).count();
+                let words = t.full_text.split_whitespace().count();
+                // Roughly one sentence end per 40 words is normal for punctuated speech
+                (t.segments.len(), t.full_text.len(), words > 0 && sentence_ends * 40 >= words)
+            }
+            None => (0, 0, false),
+        };
+
+        let count = |sql: &str| -> Result<i64> {
+            Ok(self.conn.query_row(sql, params![video_id], |row| row.get(0))?)
+        };
+
+        let chunks = count("SELECT COUNT(*) FROM transcript_chunks WHERE video_id = ?1")?;
+        let video_embedded = count("SELECT COUNT(*) FROM embeddings WHERE source_type = 'video' AND source_id = ?1")? > 0;
+        let chunk_embeddings = count("SELECT COUNT(*) FROM embeddings WHERE source_type = 'chunk' AND substr(source_id, 1, length(?1) + 1) = ?1 || ':'")?;
+        let summary_embeddings = count("SELECT COUNT(*) FROM embeddings WHERE source_type = 'summary' AND substr(source_id, 1, length(?1) + 1) = ?1 || ':'")?;
+        let claim_embeddings = count(
+            "SELECT COUNT(*) FROM embeddings e JOIN claims c ON e.source_id = CAST(c.id AS TEXT)
+             WHERE e.source_type = 'claim' AND c.video_id = ?1"
+        )?;
+        let claims = count("SELECT COUNT(*) FROM claims WHERE video_id = ?1")?;
+        let claim_links = count(
+            "SELECT COUNT(*) FROM claim_links cl
+             WHERE cl.source_claim_id IN (SELECT id FROM claims WHERE video_id = ?1)
+                OR cl.target_claim_id IN (SELECT id FROM claims WHERE video_id = ?1)"
+        )?;
+        let unlinked_claims = count(
+            "SELECT COUNT(*) FROM claims c WHERE c.video_id = ?1
+             AND NOT EXISTS (SELECT 1 FROM claim_links cl WHERE cl.source_claim_id = c.id OR cl.target_claim_id = c.id)"
+        )?;
+
+        Ok(Some(VideoStatus {
+            video,
+            transcript_language: transcript.map(|t| t.language),
+            transcript_segments,
+            transcript_chars,
+            transcript_punctuated,
+            chunks,
+            video_embedded,
+            chunk_embeddings,
+            claim_embeddings,
+            summary_embeddings,
+            layers: self.list_transcript_layers(video_id)?.into_iter().map(|l| l.layer).collect(),
+            claims,
+            claim_links,
+            unlinked_claims,
+            sources_cited: count("SELECT COUNT(*) FROM video_sources WHERE video_id = ?1")?,
+            scholars_cited: count("SELECT COUNT(*) FROM video_scholars WHERE video_id = ?1")?,
+            quotes: count("SELECT COUNT(*) FROM quotes WHERE video_id = ?1")?,
+            pending_quotes: count("SELECT COUNT(*) FROM pending_quotes WHERE video_id = ?1")?,
+            evidence: count("SELECT COUNT(*) FROM evidence WHERE video_id = ?1")?,
+            queue: self.get_queue_item(video_id)?,
+            queue_history: self.get_queue_history(video_id)?,
+        }))
+    }
+
+    // LLM usage
+
+    fn record_llm_usage(&self, usage: &LlmUsage) -> Result<i64> {
+        self.conn.execute(
+            r#"
+            INSERT INTO llm_usage (task, provider, model, input_tokens, output_tokens, latency_ms, cost_usd, success, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            "#,
+            params![
+                usage.task,
+                usage.provider,
+                usage.model,
+                usage.input_tokens,
+                usage.output_tokens,
+                usage.latency_ms,
+                usage.cost_usd,
+                usage.success,
+                usage.created_at.to_rfc3339(),
+            ],
         )?;
-        Ok(rows > 0)
+        Ok(self.conn.last_insert_rowid())
     }
 
-    pub fn queue_fail(&self, video_id: &str, error_message: &str) -> Result<bool> {
-        let now = Utc::now().to_rfc3339();
-        let rows = self.conn.execute(
-            "UPDATE ai_processing_queue SET status = 'failed', completed_at = ?1, error_message = ?2 WHERE video_id = ?3",
-            params![now, error_message, video_id],
-        )?;
-        Ok(rows > 0)
+    fn llm_usage_summary(&self, grouping: UsageGrouping, since: Option<DateTime<Utc>>) -> Result<Vec<LlmUsageSummary>> {
+        let (key, order) = match grouping {
+            UsageGrouping::Model => ("provider || '/' || model", "COALESCE(SUM(cost_usd), 0) DESC, SUM(COALESCE(input_tokens, 0) + COALESCE(output_tokens, 0)) DESC"),
+            UsageGrouping::Day => ("substr(created_at, 1, 10)", "1 DESC"),
+            UsageGrouping::Task => ("task", "COALESCE(SUM(cost_usd), 0) DESC, SUM(COALESCE(input_tokens, 0) + COALESCE(output_tokens, 0)) DESC"),
+        };
+        let sql = format!(
+            r#"
+            SELECT {key}, COUNT(*), COUNT(CASE WHEN success = 0 THEN 1 END),
+                   COALESCE(SUM(input_tokens), 0), COALESCE(SUM(output_tokens), 0),
+                   SUM(latency_ms), SUM(cost_usd), COUNT(CASE WHEN success = 1 AND cost_usd IS NULL THEN 1 END)
+            FROM llm_usage
+            WHERE ?1 IS NULL OR created_at >= ?1
+            GROUP BY 1
+            ORDER BY {order}
+            "#,
+            key = key,
+            order = order,
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(params![since.map(|t| t.to_rfc3339())], |row| {
+            Ok(LlmUsageSummary {
+                key: row.get(0)?,
+                calls: row.get(1)?,
+                failed: row.get(2)?,
+                input_tokens: row.get(3)?,
+                output_tokens: row.get(4)?,
+                latency_ms: row.get(5)?,
+                cost_usd: row.get(6)?,
+                unpriced: row.get(7)?,
+            })
+        })?;
+        Ok(rows.collect::<sql::Result<_>>()?)
     }
 
-    pub fn queue_skip(&self, video_id: &str) -> Result<bool> {
-        let now = Utc::now().to_rfc3339();
-        let rows = self.conn.execute(
-            "UPDATE ai_processing_queue SET status = 'skipped', completed_at = ?1 WHERE video_id = ?2",
-            params![now, video_id],
+    // API tokens
+
+    fn create_api_token(&self, name: &str, scope: TokenScope) -> Result<ApiToken> {
+        let mut secret = [0u8; 32];
+        getrandom::fill(&mut secret).map_err(|e| anyhow::anyhow!("No randomness for the token: {}", e))?;
+        let token = format!("kb_{}", secret.iter().map(|b| format!("{:02x}", b)).collect::<String>());
+        let prefix = token_prefix(&token);
+
+        let created_at = Utc::now();
+        self.conn.execute(
+            "INSERT INTO api_tokens (name, token_hash, prefix, scope, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![name, hash_api_token(&token), prefix, scope.as_str(), created_at.to_rfc3339()],
         )?;
-        Ok(rows > 0)
+        Ok(ApiToken { id: self.conn.last_insert_rowid(), name: name.to_string(), token, prefix, scope, created_at, last_used_at: None })
     }
 
-    pub fn queue_reset(&self, video_id: &str) -> Result<bool> {
-        let rows = self.conn.execute(
-            "UPDATE ai_processing_queue SET status = 'pending', started_at = NULL, completed_at = NULL, error_message = NULL WHERE video_id = ?1",
-            params![video_id],
+    fn list_api_tokens(&self) -> Result<Vec<ApiToken>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, prefix, scope, created_at, last_used_at FROM api_tokens ORDER BY name"
         )?;
-        Ok(rows > 0)
+        let rows = stmt.query_map([], |row| Ok(Self::row_to_api_token(row)))?;
+        rows.map(|r| r?).collect()
     }
 
-    pub fn queue_clear(&self, status: ProcessingStatus) -> Result<usize> {
-        let rows = self.conn.execute(
-            "DELETE FROM ai_processing_queue WHERE status = ?1",
-            params![status.as_str()],
-        )?;
-        Ok(rows)
+    fn find_api_token(&self, token: &str) -> Result<Option<ApiToken>> {
+        self.conn
+            .query_row(
+                "SELECT id, name, prefix, scope, created_at, last_used_at FROM api_tokens WHERE token_hash = ?1",
+                params![hash_api_token(token)],
+                |row| Ok(Self::row_to_api_token(row)),
+            )
+            .optional()?
+            .transpose()
     }
 
-    pub fn get_pending_video_ids(&self) -> Result<Vec<String>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT video_id FROM ai_processing_queue WHERE status = 'pending' ORDER BY priority DESC, created_at ASC"
-        )?;
-        let mut ids = Vec::new();
-        let mut rows = stmt.query([])?;
-        while let Some(row) = rows.next()? {
-            ids.push(row.get(0)?);
-        }
-        Ok(ids)
+    fn touch_api_token(&self, id: i64) -> Result<()> {
+        self.conn.execute("UPDATE api_tokens SET last_used_at = ?1 WHERE id = ?2", params![Utc::now().to_rfc3339(), id])?;
+        Ok(())
     }
 
-    fn row_to_queue_item(&self, row: &rusqlite::Row) -> Result<AIProcessingQueue> {
-        let status_str: String = row.get(2)?;
-        let created_str: String = row.get(4)?;
-        let started_str: Option<String> = row.get(5)?;
-        let completed_str: Option<String> = row.get(6)?;
+    fn has_api_tokens(&self) -> Result<bool> {
+        Ok(self.conn.query_row("SELECT EXISTS (SELECT 1 FROM api_tokens)", [], |row| row.get(0))?)
+    }
 
-        Ok(AIProcessingQueue {
-            id: row.get(0)?,
-            video_id: row.get(1)?,
-            status: ProcessingStatus::from_str(&status_str).unwrap_or(ProcessingStatus::Pending),
-            priority: row.get(3)?,
-            created_at: DateTime::parse_from_rfc3339(&created_str)?.with_timezone(&Utc),
-            started_at: started_str.map(|s| DateTime::parse_from_rfc3339(&s).ok()).flatten().map(|d| d.with_timezone(&Utc)),
-            completed_at: completed_str.map(|s| DateTime::parse_from_rfc3339(&s).ok()).flatten().map(|d| d.with_timezone(&Utc)),
-            error_message: row.get(7)?,
-            claims_extracted: row.get(8)?,
-        })
+    fn revoke_api_token(&self, name: &str) -> Result<bool> {
+        let affected = self.conn.execute("DELETE FROM api_tokens WHERE name = ?1", params![name])?;
+        Ok(affected > 0)
     }
 
     // ============================================
@@ -5133,7 +10221,7 @@ impl Database {
 
     // --- Sources ---
 
-    pub fn add_source(
+    fn add_source(
         &self,
         title: &str,
         author: Option<&str>,
@@ -5183,7 +10271,7 @@ impl Database {
         Ok(self.conn.last_insert_rowid())
     }
 
-    pub fn get_sources(&self) -> Result<Vec<Source>> {
+    fn get_sources(&self) -> Result<Vec<Source>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, title, author, source_type, year, url, notes, created_at FROM sources ORDER BY title"
         )?;
@@ -5204,7 +10292,7 @@ impl Database {
         Ok(sources)
     }
 
-    pub fn get_source(&self, id: i64) -> Result<Option<Source>> {
+    fn get_source(&self, id: i64) -> Result<Option<Source>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, title, author, source_type, year, url, notes, created_at FROM sources WHERE id = ?1"
         )?;
@@ -5227,7 +10315,7 @@ impl Database {
         }
     }
 
-    pub fn find_source_by_title(&self, title: &str) -> Result<Option<Source>> {
+    fn find_source_by_title(&self, title: &str) -> Result<Option<Source>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, title, author, source_type, year, url, notes, created_at FROM sources WHERE title = ?1"
         )?;
@@ -5250,7 +10338,7 @@ impl Database {
         }
     }
 
-    pub fn cite_source(&self, video_id: &str, source_id: i64, timestamp: Option<f64>, context: Option<&str>) -> Result<()> {
+    fn cite_source(&self, video_id: &str, source_id: i64, timestamp: Option<f64>, context: Option<&str>) -> Result<()> {
         self.conn.execute(
             "INSERT OR REPLACE INTO video_sources (video_id, source_id, timestamp, context) VALUES (?1, ?2, ?3, ?4)",
             params![video_id, source_id, timestamp, context],
@@ -5258,9 +10346,40 @@ impl Database {
         Ok(())
     }
 
+    fn get_sources_for_video(&self, video_id: &str) -> Result<Vec<Cited<Source>>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT s.id, s.title, s.author, s.source_type, s.year, s.url, s.notes, s.created_at, vs.timestamp, vs.context
+            FROM sources s
+            JOIN video_sources vs ON vs.source_id = s.id
+            WHERE vs.video_id = ?1
+            ORDER BY vs.timestamp IS NULL, vs.timestamp, s.title
+            "#
+        )?;
+        let sources = stmt.query_map(params![video_id], |row| {
+            Ok((
+                Source {
+                    id: row.get(0)?,
+                    title: row.get(1)?,
+                    author: row.get(2)?,
+                    source_type: SourceType::from_str(&row.get::<_, String>(3)?).unwrap_or(SourceType::Book),
+                    year: row.get(4)?,
+                    url: row.get(5)?,
+                    notes: row.get(6)?,
+                    created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(7)?)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
+                },
+                row.get(8)?,
+                row.get(9)?,
+            ))
+        })?.collect::<Result<Vec<_>, _>>()?;
+        Ok(sources)
+    }
+
     // --- Scholars ---
 
-    pub fn add_scholar(
+    fn add_scholar(
         &self,
         name: &str,
         field: Option<&str>,
@@ -5306,9 +10425,9 @@ impl Database {
         Ok(self.conn.last_insert_rowid())
     }
 
-    pub fn get_scholars(&self) -> Result<Vec<Scholar>> {
+    fn get_scholars(&self) -> Result<Vec<Scholar>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, name, field, era, contribution, created_at FROM scholars ORDER BY name"
+            "SELECT id, name, field, era, contribution, created_at, extracted_by FROM scholars ORDER BY name"
         )?;
         let scholars = stmt.query_map([], |row| {
             Ok(Scholar {
@@ -5320,14 +10439,15 @@ impl Database {
                 created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
                     .map(|dt| dt.with_timezone(&Utc))
                     .unwrap_or_else(|_| Utc::now()),
+                extracted_by: row.get(6)?,
             })
         })?.collect::<Result<Vec<_>, _>>()?;
         Ok(scholars)
     }
 
-    pub fn get_scholar(&self, id: i64) -> Result<Option<Scholar>> {
+    fn get_scholar(&self, id: i64) -> Result<Option<Scholar>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, name, field, era, contribution, created_at FROM scholars WHERE id = ?1"
+            "SELECT id, name, field, era, contribution, created_at, extracted_by FROM scholars WHERE id = ?1"
         )?;
         let mut rows = stmt.query(params![id])?;
         if let Some(row) = rows.next()? {
@@ -5340,15 +10460,16 @@ impl Database {
                 created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
                     .map(|dt| dt.with_timezone(&Utc))
                     .unwrap_or_else(|_| Utc::now()),
+                extracted_by: row.get(6)?,
             }))
         } else {
             Ok(None)
         }
     }
 
-    pub fn find_scholar_by_name(&self, name: &str) -> Result<Option<Scholar>> {
+    fn find_scholar_by_name(&self, name: &str) -> Result<Option<Scholar>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, name, field, era, contribution, created_at FROM scholars WHERE name = ?1"
+            "SELECT id, name, field, era, contribution, created_at, extracted_by FROM scholars WHERE name = ?1"
         )?;
         let mut rows = stmt.query(params![name])?;
         if let Some(row) = rows.next()? {
@@ -5361,13 +10482,14 @@ impl Database {
                 created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
                     .map(|dt| dt.with_timezone(&Utc))
                     .unwrap_or_else(|_| Utc::now()),
+                extracted_by: row.get(6)?,
             }))
         } else {
             Ok(None)
         }
     }
 
-    pub fn cite_scholar(&self, video_id: &str, scholar_id: i64, timestamp: Option<f64>, context: Option<&str>) -> Result<()> {
+    fn cite_scholar(&self, video_id: &str, scholar_id: i64, timestamp: Option<f64>, context: Option<&str>) -> Result<()> {
         self.conn.execute(
             "INSERT OR REPLACE INTO video_scholars (video_id, scholar_id, timestamp, context) VALUES (?1, ?2, ?3, ?4)",
             params![video_id, scholar_id, timestamp, context],
@@ -5375,9 +10497,173 @@ impl Database {
         Ok(())
     }
 
+    fn get_scholars_for_video(&self, video_id: &str) -> Result<Vec<Cited<Scholar>>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT s.id, s.name, s.field, s.era, s.contribution, s.created_at, s.extracted_by, vs.timestamp, vs.context
+            FROM scholars s
+            JOIN video_scholars vs ON vs.scholar_id = s.id
+            WHERE vs.video_id = ?1
+            ORDER BY vs.timestamp IS NULL, vs.timestamp, s.name
+            "#
+        )?;
+        let scholars = stmt.query_map(params![video_id], |row| {
+            Ok((
+                Scholar {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    field: row.get(2)?,
+                    era: row.get(3)?,
+                    contribution: row.get(4)?,
+                    created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
+                    extracted_by: row.get(6)?,
+                },
+                row.get(7)?,
+                row.get(8)?,
+            ))
+        })?.collect::<Result<Vec<_>, _>>()?;
+        Ok(scholars)
+    }
+
+    fn add_extracted_scholar(
+        &self,
+        video_id: &str,
+        name: &str,
+        field: Option<&str>,
+        timestamp: Option<f64>,
+        context: Option<&str>,
+        extracted_by: &str,
+    ) -> Result<(i64, bool)> {
+        let existing: Option<i64> = self.conn.query_row(
+            "SELECT id FROM scholars WHERE name = ?1 COLLATE NOCASE",
+            params![name],
+            |row| row.get(0),
+        ).optional()?;
+        let (id, created) = match existing {
+            Some(id) => (id, false),
+            None => {
+                self.conn.execute(
+                    "INSERT INTO scholars (name, field, created_at, extracted_by) VALUES (?1, ?2, ?3, ?4)",
+                    params![name, field, Utc::now().to_rfc3339(), extracted_by],
+                )?;
+                (self.conn.last_insert_rowid(), true)
+            }
+        };
+        self.conn.execute(
+            "INSERT OR IGNORE INTO video_scholars (video_id, scholar_id, timestamp, context, extracted_by) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![video_id, id, timestamp, context, extracted_by],
+        )?;
+        Ok((id, created))
+    }
+
+    // --- Claim citations ---
+
+    fn cite_claim_source(&self, claim_id: i64, source_id: i64) -> Result<bool> {
+        let inserted = self.conn.execute(
+            "INSERT OR IGNORE INTO claim_sources (claim_id, source_id) VALUES (?1, ?2)",
+            params![claim_id, source_id],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    fn uncite_claim_source(&self, claim_id: i64, source_id: i64) -> Result<bool> {
+        let deleted = self.conn.execute(
+            "DELETE FROM claim_sources WHERE claim_id = ?1 AND source_id = ?2",
+            params![claim_id, source_id],
+        )?;
+        Ok(deleted > 0)
+    }
+
+    fn cite_claim_scholar(&self, claim_id: i64, scholar_id: i64, attribution: Option<&str>) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO claim_scholars (claim_id, scholar_id, attribution_type) VALUES (?1, ?2, ?3)",
+            params![claim_id, scholar_id, attribution],
+        )?;
+        Ok(())
+    }
+
+    fn uncite_claim_scholar(&self, claim_id: i64, scholar_id: i64) -> Result<bool> {
+        let deleted = self.conn.execute(
+            "DELETE FROM claim_scholars WHERE claim_id = ?1 AND scholar_id = ?2",
+            params![claim_id, scholar_id],
+        )?;
+        Ok(deleted > 0)
+    }
+
+    fn get_claim_sources(&self, claim_id: i64) -> Result<Vec<Source>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT s.id, s.title, s.author, s.source_type, s.year, s.url, s.notes, s.created_at
+            FROM sources s
+            JOIN claim_sources cs ON cs.source_id = s.id
+            WHERE cs.claim_id = ?1
+            ORDER BY s.year, s.title
+            "#
+        )?;
+        let sources = stmt.query_map(params![claim_id], |row| {
+            Ok(Source {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                author: row.get(2)?,
+                source_type: SourceType::from_str(&row.get::<_, String>(3)?).unwrap_or(SourceType::Book),
+                year: row.get(4)?,
+                url: row.get(5)?,
+                notes: row.get(6)?,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(7)?)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+            })
+        })?.collect::<Result<Vec<_>, _>>()?;
+        Ok(sources)
+    }
+
+    fn get_claim_scholars(&self, claim_id: i64) -> Result<Vec<ClaimScholar>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT s.id, s.name, s.field, s.era, s.contribution, s.created_at, cs.attribution_type, s.extracted_by
+            FROM scholars s
+            JOIN claim_scholars cs ON cs.scholar_id = s.id
+            WHERE cs.claim_id = ?1
+            ORDER BY s.name
+            "#
+        )?;
+        let scholars = stmt.query_map(params![claim_id], |row| {
+            Ok(ClaimScholar {
+                scholar: Scholar {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    field: row.get(2)?,
+                    era: row.get(3)?,
+                    contribution: row.get(4)?,
+                    created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
+                    extracted_by: row.get(7)?,
+                },
+                attribution: row.get(6)?,
+            })
+        })?.collect::<Result<Vec<_>, _>>()?;
+        Ok(scholars)
+    }
+
+    fn claim_citations(&self, claim_id: i64) -> Result<Vec<String>> {
+        let mut citations: Vec<String> = self
+            .get_claim_scholars(claim_id)?
+            .into_iter()
+            .map(|cs| match cs.attribution {
+                Some(a) => format!("{} ({})", cs.scholar.name, a),
+                None => cs.scholar.name,
+            })
+            .collect();
+        citations.extend(self.get_claim_sources(claim_id)?.iter().map(|s| format!("per {}", s.citation())));
+        Ok(citations)
+    }
+
     // --- Visuals ---
 
-    pub fn add_visual(
+    fn add_visual(
         &self,
         video_id: &str,
         timestamp: f64,
@@ -5406,7 +10692,7 @@ impl Database {
         Ok(self.conn.last_insert_rowid())
     }
 
-    pub fn get_visuals_for_video(&self, video_id: &str) -> Result<Vec<Visual>> {
+    fn get_visuals_for_video(&self, video_id: &str) -> Result<Vec<Visual>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, video_id, timestamp, visual_type, description, significance, location_id, era_id, created_at
              FROM visuals WHERE video_id = ?1 ORDER BY timestamp"
@@ -5431,7 +10717,7 @@ impl Database {
 
     // --- Terms ---
 
-    pub fn add_term(
+    fn add_term(
         &self,
         term: &str,
         definition: &str,
@@ -5472,9 +10758,9 @@ impl Database {
         Ok(self.conn.last_insert_rowid())
     }
 
-    pub fn get_terms(&self) -> Result<Vec<Term>> {
+    fn get_terms(&self) -> Result<Vec<Term>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, term, definition, domain, video_id, timestamp, scholar_id, created_at FROM terms ORDER BY term"
+            "SELECT id, term, definition, domain, video_id, timestamp, scholar_id, created_at, extracted_by FROM terms ORDER BY term"
         )?;
         let terms = stmt.query_map([], |row| {
             Ok(Term {
@@ -5488,14 +10774,15 @@ impl Database {
                 created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(7)?)
                     .map(|dt| dt.with_timezone(&Utc))
                     .unwrap_or_else(|_| Utc::now()),
+                extracted_by: row.get(8)?,
             })
         })?.collect::<Result<Vec<_>, _>>()?;
         Ok(terms)
     }
 
-    pub fn find_term(&self, term: &str) -> Result<Option<Term>> {
+    fn find_term(&self, term: &str) -> Result<Option<Term>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, term, definition, domain, video_id, timestamp, scholar_id, created_at FROM terms WHERE term = ?1"
+            "SELECT id, term, definition, domain, video_id, timestamp, scholar_id, created_at, extracted_by FROM terms WHERE term = ?1"
         )?;
         let mut rows = stmt.query(params![term])?;
         if let Some(row) = rows.next()? {
@@ -5510,15 +10797,42 @@ impl Database {
                 created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(7)?)
                     .map(|dt| dt.with_timezone(&Utc))
                     .unwrap_or_else(|_| Utc::now()),
+                extracted_by: row.get(8)?,
             }))
         } else {
             Ok(None)
         }
     }
 
+    fn add_extracted_term(
+        &self,
+        term: &str,
+        definition: &str,
+        video_id: &str,
+        timestamp: Option<f64>,
+        extracted_by: &str,
+    ) -> Result<Option<i64>> {
+        let exists: bool = self.conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM terms WHERE LOWER(term) = LOWER(?1))",
+            params![term],
+            |row| row.get(0),
+        )?;
+        if exists {
+            return Ok(None);
+        }
+        self.conn.execute(
+            r#"
+            INSERT INTO terms (term, definition, video_id, timestamp, created_at, extracted_by)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+            params![term, definition, video_id, timestamp, Utc::now().to_rfc3339(), extracted_by],
+        )?;
+        Ok(Some(self.conn.last_insert_rowid()))
+    }
+
     // --- Evidence ---
 
-    pub fn add_evidence(
+    fn add_evidence(
         &self,
         video_id: &str,
         evidence_type: EvidenceType,
@@ -5547,7 +10861,7 @@ impl Database {
         Ok(self.conn.last_insert_rowid())
     }
 
-    pub fn get_evidence_for_video(&self, video_id: &str) -> Result<Vec<Evidence>> {
+    fn get_evidence_for_video(&self, video_id: &str) -> Result<Vec<Evidence>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, video_id, evidence_type, description, location_id, era_id, timestamp, source_id, created_at
              FROM evidence WHERE video_id = ?1 ORDER BY timestamp"
@@ -5570,7 +10884,7 @@ impl Database {
         Ok(evidence)
     }
 
-    pub fn link_claim_evidence(&self, claim_id: i64, evidence_id: i64, relationship: Option<&str>) -> Result<()> {
+    fn link_claim_evidence(&self, claim_id: i64, evidence_id: i64, relationship: Option<&str>) -> Result<()> {
         self.conn.execute(
             "INSERT OR REPLACE INTO claim_evidence (claim_id, evidence_id, relationship) VALUES (?1, ?2, ?3)",
             params![claim_id, evidence_id, relationship],
@@ -5580,7 +10894,7 @@ impl Database {
 
     // --- Quotes ---
 
-    pub fn add_quote(
+    fn add_quote(
         &self,
         video_id: &str,
         text: &str,
@@ -5607,7 +10921,7 @@ impl Database {
         Ok(self.conn.last_insert_rowid())
     }
 
-    pub fn get_quotes_for_video(&self, video_id: &str) -> Result<Vec<Quote>> {
+    fn get_quotes_for_video(&self, video_id: &str) -> Result<Vec<Quote>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, video_id, text, speaker, scholar_id, timestamp, context, created_at
              FROM quotes WHERE video_id = ?1 ORDER BY timestamp"
@@ -5629,9 +10943,96 @@ impl Database {
         Ok(quotes)
     }
 
+    // --- Pending quotes ---
+
+    fn stage_pending_quote(
+        &self,
+        video_id: &str,
+        text: &str,
+        speaker: Option<&str>,
+        timestamp: Option<f64>,
+        reason: &str,
+        context: Option<&str>,
+    ) -> Result<Option<i64>> {
+        let exists: bool = self.conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM quotes WHERE video_id = ?1 AND lower(text) = lower(?2))
+                 OR EXISTS(SELECT 1 FROM pending_quotes WHERE video_id = ?1 AND lower(text) = lower(?2))",
+            params![video_id, text],
+            |row| row.get(0),
+        )?;
+        if exists {
+            return Ok(None);
+        }
+
+        self.conn.execute(
+            r#"
+            INSERT INTO pending_quotes (video_id, text, speaker, timestamp, reason, context, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+            params![video_id, text, speaker, timestamp, reason, context, Utc::now().to_rfc3339()],
+        )?;
+        Ok(Some(self.conn.last_insert_rowid()))
+    }
+
+    fn get_pending_quotes(&self, video_id: Option<&str>) -> Result<Vec<PendingQuote>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, video_id, text, speaker, timestamp, reason, context, created_at
+             FROM pending_quotes WHERE ?1 IS NULL OR video_id = ?1 ORDER BY video_id, timestamp"
+        )?;
+        let mut quotes = Vec::new();
+        let mut rows = stmt.query(params![video_id])?;
+        while let Some(row) = rows.next()? {
+            quotes.push(self.row_to_pending_quote(row)?);
+        }
+        Ok(quotes)
+    }
+
+    fn get_pending_quote(&self, id: i64) -> Result<Option<PendingQuote>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, video_id, text, speaker, timestamp, reason, context, created_at
+             FROM pending_quotes WHERE id = ?1"
+        )?;
+        let mut rows = stmt.query(params![id])?;
+        if let Some(row) = rows.next()? {
+            Ok(Some(self.row_to_pending_quote(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn confirm_pending_quote(&self, id: i64, speaker: Option<&str>) -> Result<Option<i64>> {
+        let pending = match self.get_pending_quote(id)? {
+            Some(p) => p,
+            None => return Ok(None),
+        };
+
+        let speaker = speaker.or(pending.speaker.as_deref());
+        let scholar_id = if let Some(name) = speaker {
+            self.find_scholar_by_name(name)?.map(|s| s.id)
+        } else {
+            None
+        };
+
+        let quote_id = self.add_quote(
+            &pending.video_id,
+            &pending.text,
+            speaker,
+            scholar_id,
+            pending.timestamp,
+            pending.context.as_deref(),
+        )?;
+        self.conn.execute("DELETE FROM pending_quotes WHERE id = ?1", params![id])?;
+        Ok(Some(quote_id))
+    }
+
+    fn reject_pending_quote(&self, id: i64) -> Result<bool> {
+        let affected = self.conn.execute("DELETE FROM pending_quotes WHERE id = ?1", params![id])?;
+        Ok(affected > 0)
+    }
+
     // --- Entity counts for stats ---
 
-    pub fn get_expanded_stats(&self) -> Result<(i64, i64, i64, i64, i64, i64)> {
+    fn get_expanded_stats(&self) -> Result<(i64, i64, i64, i64, i64, i64)> {
         let sources: i64 = self.conn.query_row("SELECT COUNT(*) FROM sources", [], |row| row.get(0))?;
         let scholars: i64 = self.conn.query_row("SELECT COUNT(*) FROM scholars", [], |row| row.get(0))?;
         let visuals: i64 = self.conn.query_row("SELECT COUNT(*) FROM visuals", [], |row| row.get(0))?;
@@ -5643,7 +11044,7 @@ impl Database {
 
     // --- Get all entities for API ---
 
-    pub fn get_all_visuals(&self) -> Result<Vec<Visual>> {
+    fn get_all_visuals(&self) -> Result<Vec<Visual>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, video_id, timestamp, visual_type, description, significance, location_id, era_id, created_at
              FROM visuals ORDER BY created_at DESC"
@@ -5667,7 +11068,7 @@ impl Database {
         Ok(visuals)
     }
 
-    pub fn get_all_evidence(&self) -> Result<Vec<Evidence>> {
+    fn get_all_evidence(&self) -> Result<Vec<Evidence>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, video_id, evidence_type, description, location_id, era_id, timestamp, source_id, created_at
              FROM evidence ORDER BY created_at DESC"
@@ -5691,7 +11092,7 @@ impl Database {
         Ok(evidence)
     }
 
-    pub fn get_all_quotes(&self) -> Result<Vec<Quote>> {
+    fn get_all_quotes(&self) -> Result<Vec<Quote>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, video_id, text, speaker, scholar_id, timestamp, context, created_at
              FROM quotes ORDER BY created_at DESC"
@@ -5714,6 +11115,133 @@ impl Database {
     }
 }
 
+/// SHA-256 of an API token, hex encoded; the only form of the secret that is stored.
+fn hash_api_token(token: &str) -> String {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(token.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Enough of a token to tell it apart in `token list`.
+fn token_prefix(token: &str) -> String {
+    token.chars().take(9).collect()
+}
+
+/// Pack a vector as little-endian f32s, the layout sqlite-vec reads as well.
+fn vector_to_blob(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+fn blob_to_vector(blob: &[u8]) -> Result<Vec<f32>> {
+    if !blob.len().is_multiple_of(4) {
+        anyhow::bail!("Corrupt embedding: {} bytes is not a whole number of f32s", blob.len());
+    }
+    Ok(blob
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect())
+}
+
+// Spherical k-means over same-length vectors. Seeds are spread evenly through the input
+// so runs are repeatable; farthest-first seeding would make outliers their own clusters.
+fn kmeans_centroids(embeddings: &[Embedding], k: usize) -> Vec<Vec<f32>> {
+    const ITERATIONS: usize = 25;
+
+    let k = k.min(embeddings.len());
+    let mut centroids: Vec<Vec<f32>> = (0..k).map(|i| embeddings[i * embeddings.len() / k].vector.clone()).collect();
+
+    for _ in 0..ITERATIONS {
+        let mut sums: Vec<Vec<f32>> = centroids.iter().map(|c| vec![0.0; c.len()]).collect();
+        for emb in embeddings {
+            let nearest = (0..centroids.len())
+                .max_by(|&a, &b| {
+                    cosine_similarity(&centroids[a], &emb.vector)
+                        .partial_cmp(&cosine_similarity(&centroids[b], &emb.vector))
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .unwrap_or(0);
+            let norm = emb.vector.iter().map(|x| x * x).sum::<f32>().sqrt().max(f32::EPSILON);
+            for (s, x) in sums[nearest].iter_mut().zip(&emb.vector) {
+                *s += x / norm;
+            }
+        }
+        let mut moved = false;
+        for (centroid, sum) in centroids.iter_mut().zip(sums) {
+            // An empty cluster keeps its old centroid
+            if sum.iter().any(|x| *x != 0.0) && *centroid != sum {
+                *centroid = sum;
+                moved = true;
+            }
+        }
+        if !moved {
+            break;
+        }
+    }
+    centroids
+}
+
+// Coordinates of each vector on the first two principal components of the set, found
+// by power iteration. Vectors are normalised first, so distances follow cosine similarity.
+fn pca_2d(vectors: &[&[f32]]) -> Vec<(f32, f32)> {
+    const ITERATIONS: usize = 100;
+
+    let dims = vectors.first().map_or(0, |v| v.len());
+    let rows: Vec<Vec<f32>> = vectors
+        .iter()
+        .filter(|v| v.len() == dims)
+        .map(|v| {
+            let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt().max(f32::EPSILON);
+            v.iter().map(|x| x / norm).collect()
+        })
+        .collect();
+    if rows.len() != vectors.len() || dims == 0 {
+        return vec![(0.0, 0.0); vectors.len()];
+    }
+
+    let mut mean = vec![0.0_f32; dims];
+    for row in &rows {
+        for (m, x) in mean.iter_mut().zip(row) {
+            *m += x / rows.len() as f32;
+        }
+    }
+    let centered: Vec<Vec<f32>> = rows.iter().map(|r| r.iter().zip(&mean).map(|(x, m)| x - m).collect()).collect();
+    let dot = |a: &[f32], b: &[f32]| a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>();
+
+    let mut components: Vec<Vec<f32>> = Vec::new();
+    for c in 0..2 {
+        // Deterministic start that is unlikely to be orthogonal to the component
+        let mut v: Vec<f32> = (0..dims).map(|i| 1.0 + ((i + c) % 7) as f32 * 0.1).collect();
+        for _ in 0..ITERATIONS {
+            let mut next = vec![0.0_f32; dims];
+            for row in &centered {
+                let weight = dot(row, &v);
+                for (n, x) in next.iter_mut().zip(row) {
+                    *n += weight * x;
+                }
+            }
+            for prev in &components {
+                let overlap = dot(&next, prev);
+                for (n, p) in next.iter_mut().zip(prev) {
+                    *n -= overlap * p;
+                }
+            }
+            let norm = dot(&next, &next).sqrt();
+            if norm < f32::EPSILON {
+                // No variance left in this direction
+                v = vec![0.0; dims];
+                break;
+            }
+            next.iter_mut().for_each(|n| *n /= norm);
+            v = next;
+        }
+        components.push(v);
+    }
+
+    centered.iter().map(|row| (dot(row, &components[0]), dot(row, &components[1]))).collect()
+}
+
+// Rank offset for reciprocal rank fusion; 60 is the value from the original paper
+const RRF_K: f32 = 60.0;
+
 // Cosine similarity helper function
 fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     if a.len() != b.len() || a.is_empty() {
@@ -0,0 +1,325 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+use chrono::Utc;
+
+use crate::storage::models::{
+    CausalLoop, CausalRelation, ClaimCategory, ClaimGraph, ClaimMetrics, LinkType, LoopType, RelationStrength,
+};
+
+/// Render a claim graph in Graphviz DOT, e.g. for `dot -Tsvg`.
+pub fn to_dot(graph: &ClaimGraph) -> String {
+    let mut out = String::from("digraph claims {\n");
+    out.push_str("  rankdir=LR;\n");
+    out.push_str("  node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\", fontsize=10];\n");
+    out.push_str("  edge [fontname=\"Helvetica\", fontsize=8];\n\n");
+
+    for claim in &graph.claims {
+        let tooltip = match graph.citations.get(&claim.id) {
+            Some(cited) => format!("{}\n{}", claim.text, cited.join("; ")),
+            None => claim.text.clone(),
+        };
+        out.push_str(&format!(
+            "  c{} [label=\"{}\", tooltip=\"{}\", fillcolor=\"{}\"];\n",
+            claim.id,
+            dot_escape(&wrap(&format!("#{} {}", claim.id, claim.text), 32)),
+            dot_escape(&tooltip),
+            category_color(claim.category),
+        ));
+    }
+    if !graph.links.is_empty() || !graph.causal_relations.is_empty() {
+        out.push('\n');
+    }
+
+    for link in &graph.links {
+        let style = match link.link_type {
+            LinkType::Contradicts | LinkType::Related => ", style=dashed",
+            _ => "",
+        };
+        out.push_str(&format!(
+            "  c{} -> c{} [label=\"{}\", color=\"{}\"{}];\n",
+            link.source_claim_id,
+            link.target_claim_id,
+            link.link_type.as_str(),
+            link_color(link.link_type),
+            style,
+        ));
+    }
+    for rel in &graph.causal_relations {
+        let style = match rel.strength {
+            RelationStrength::Strong => "bold",
+            RelationStrength::Moderate => "solid",
+            RelationStrength::Weak => "dashed",
+            RelationStrength::Speculative => "dotted",
+        };
+        out.push_str(&format!(
+            "  c{} -> c{} [label=\"causes ({})\", color=\"#FF9800\", style={}];\n",
+            rel.cause_claim_id,
+            rel.effect_claim_id,
+            rel.loop_type.as_str(),
+            style,
+        ));
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Render a claim graph as GraphML, which Gephi, yEd and networkx read directly.
+pub fn to_graphml(graph: &ClaimGraph) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    for (id, domain, name, kind) in [
+        ("label", "node", "label", "string"),
+        ("category", "node", "category", "string"),
+        ("confidence", "node", "confidence", "string"),
+        ("video_id", "node", "video_id", "string"),
+        ("timestamp", "node", "timestamp", "double"),
+        ("citations", "node", "citations", "string"),
+        ("kind", "edge", "kind", "string"),
+        ("type", "edge", "type", "string"),
+        ("strength", "edge", "strength", "string"),
+    ] {
+        out.push_str(&format!(
+            "  <key id=\"{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"{}\"/>\n",
+            id, domain, name, kind
+        ));
+    }
+    out.push_str("  <graph id=\"claims\" edgedefault=\"directed\">\n");
+
+    for claim in &graph.claims {
+        out.push_str(&format!("    <node id=\"c{}\">\n", claim.id));
+        out.push_str(&format!("      <data key=\"label\">{}</data>\n", xml_escape(&claim.text)));
+        out.push_str(&format!("      <data key=\"category\">{}</data>\n", claim.category.as_str()));
+        out.push_str(&format!("      <data key=\"confidence\">{}</data>\n", claim.confidence.as_str()));
+        out.push_str(&format!("      <data key=\"video_id\">{}</data>\n", xml_escape(&claim.video_id)));
+        if let Some(t) = claim.timestamp {
+            out.push_str(&format!("      <data key=\"timestamp\">{}</data>\n", t));
+        }
+        if let Some(cited) = graph.citations.get(&claim.id) {
+            out.push_str(&format!("      <data key=\"citations\">{}</data>\n", xml_escape(&cited.join("; "))));
+        }
+        out.push_str("    </node>\n");
+    }
+
+    for link in &graph.links {
+        out.push_str(&format!(
+            "    <edge id=\"l{}\" source=\"c{}\" target=\"c{}\">\n",
+            link.id, link.source_claim_id, link.target_claim_id
+        ));
+        out.push_str("      <data key=\"kind\">link</data>\n");
+        out.push_str(&format!("      <data key=\"type\">{}</data>\n", link.link_type.as_str()));
+        out.push_str("    </edge>\n");
+    }
+    for rel in &graph.causal_relations {
+        out.push_str(&format!(
+            "    <edge id=\"r{}\" source=\"c{}\" target=\"c{}\">\n",
+            rel.id, rel.cause_claim_id, rel.effect_claim_id
+        ));
+        out.push_str("      <data key=\"kind\">causal</data>\n");
+        out.push_str(&format!("      <data key=\"type\">{}</data>\n", rel.loop_type.as_str()));
+        out.push_str(&format!("      <data key=\"strength\">{}</data>\n", rel.strength.as_str()));
+        out.push_str("    </edge>\n");
+    }
+
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+/// Degree, betweenness centrality (Brandes) and connected component for every claim in the
+/// graph. Edges are undirected and parallel links between the same pair count once.
+pub fn compute_metrics(graph: &ClaimGraph) -> Vec<ClaimMetrics> {
+    let index: HashMap<i64, usize> = graph.claims.iter().enumerate().map(|(i, c)| (c.id, i)).collect();
+    let n = graph.claims.len();
+    let mut adjacency: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); n];
+    let pairs = graph
+        .links
+        .iter()
+        .map(|l| (l.source_claim_id, l.target_claim_id))
+        .chain(graph.causal_relations.iter().map(|r| (r.cause_claim_id, r.effect_claim_id)));
+    for (a, b) in pairs {
+        if let (Some(&a), Some(&b)) = (index.get(&a), index.get(&b)) {
+            if a != b {
+                adjacency[a].insert(b);
+                adjacency[b].insert(a);
+            }
+        }
+    }
+
+    // Brandes: one BFS per source, then accumulate dependencies back up the tree
+    let mut betweenness = vec![0.0f64; n];
+    for s in 0..n {
+        let mut stack = Vec::with_capacity(n);
+        let mut preds: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut paths = vec![0.0f64; n];
+        let mut dist = vec![usize::MAX; n];
+        paths[s] = 1.0;
+        dist[s] = 0;
+        let mut queue = VecDeque::from([s]);
+        while let Some(v) = queue.pop_front() {
+            stack.push(v);
+            for &w in &adjacency[v] {
+                if dist[w] == usize::MAX {
+                    dist[w] = dist[v] + 1;
+                    queue.push_back(w);
+                }
+                if dist[w] == dist[v] + 1 {
+                    paths[w] += paths[v];
+                    preds[w].push(v);
+                }
+            }
+        }
+        let mut dependency = vec![0.0f64; n];
+        while let Some(w) = stack.pop() {
+            for &v in &preds[w] {
+                dependency[v] += paths[v] / paths[w] * (1.0 + dependency[w]);
+            }
+            if w != s {
+                betweenness[w] += dependency[w];
+            }
+        }
+    }
+    // Each undirected path was counted from both ends
+    let scale = if n > 2 { 1.0 / ((n - 1) * (n - 2)) as f64 } else { 0.0 };
+
+    let mut components: Vec<Vec<usize>> = Vec::new();
+    let mut seen = vec![false; n];
+    for start in 0..n {
+        if seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        let mut members = vec![start];
+        let mut queue = VecDeque::from([start]);
+        while let Some(v) = queue.pop_front() {
+            for &w in &adjacency[v] {
+                if !seen[w] {
+                    seen[w] = true;
+                    members.push(w);
+                    queue.push_back(w);
+                }
+            }
+        }
+        components.push(members);
+    }
+    // Stable, so equal-sized components keep the order of their first claim
+    components.sort_by_key(|c| std::cmp::Reverse(c.len()));
+    let mut component = vec![0; n];
+    for (number, members) in components.iter().enumerate() {
+        for &v in members {
+            component[v] = number;
+        }
+    }
+
+    let computed_at = Utc::now();
+    graph
+        .claims
+        .iter()
+        .enumerate()
+        .map(|(i, claim)| ClaimMetrics {
+            claim_id: claim.id,
+            degree: adjacency[i].len(),
+            betweenness: betweenness[i] * scale,
+            component: component[i],
+            computed_at,
+        })
+        .collect()
+}
+
+/// The feedback loops among causal relations, following each from cause to effect: every
+/// simple cycle, shortest first, up to `limit` of them. Parallel relations between the same
+/// claims make separate loops.
+pub fn causal_loops(relations: &[CausalRelation], limit: usize) -> Vec<CausalLoop> {
+    let mut outgoing: HashMap<i64, Vec<&CausalRelation>> = HashMap::new();
+    for r in relations {
+        outgoing.entry(r.cause_claim_id).or_default().push(r);
+    }
+    let starts: BTreeSet<i64> = relations.iter().map(|r| r.cause_claim_id).collect();
+
+    // Each cycle is found once, from its lowest claim, visiting only claims above it
+    let mut loops = Vec::new();
+    for &start in &starts {
+        let mut path: Vec<&CausalRelation> = Vec::new();
+        let mut stack = vec![(start, 0usize)];
+        while let Some((node, next)) = stack.pop() {
+            let edges = outgoing.get(&node).map(Vec::as_slice).unwrap_or_default();
+            let Some(&edge) = edges.get(next) else {
+                path.pop();
+                continue;
+            };
+            stack.push((node, next + 1));
+            let target = edge.effect_claim_id;
+            if target == start {
+                path.push(edge);
+                let negatives = path.iter().filter(|r| r.loop_type == LoopType::Negative).count();
+                loops.push(CausalLoop {
+                    relations: path.iter().map(|r| r.id).collect(),
+                    polarity: if negatives % 2 == 0 { LoopType::Positive } else { LoopType::Negative },
+                });
+                path.pop();
+                if loops.len() >= limit {
+                    loops.sort_by_key(|l| l.relations.len());
+                    return loops;
+                }
+            } else if target > start && !path.iter().any(|r| r.effect_claim_id == target) {
+                path.push(edge);
+                stack.push((target, 0));
+            }
+        }
+    }
+    loops.sort_by_key(|l| l.relations.len());
+    loops
+}
+
+// Same palette as the web UI's graph view
+fn link_color(link_type: LinkType) -> &'static str {
+    match link_type {
+        LinkType::Supports => "#4CAF50",
+        LinkType::Contradicts => "#f44336",
+        LinkType::Elaborates => "#2196F3",
+        LinkType::Causes | LinkType::CausedBy => "#FF9800",
+        LinkType::Related => "#9E9E9E",
+    }
+}
+
+fn category_color(category: ClaimCategory) -> &'static str {
+    match category {
+        ClaimCategory::CyclicalPattern => "#E1BEE7",
+        ClaimCategory::CausalClaim => "#FFE0B2",
+        ClaimCategory::MemeticTransmission => "#C8E6C9",
+        ClaimCategory::GeopoliticalDynamic => "#BBDEFB",
+        ClaimCategory::Factual => "#F5F5F5",
+        ClaimCategory::Phenomenological => "#FFF9C4",
+        ClaimCategory::Metaphysical => "#D7CCC8",
+    }
+}
+
+/// Break text into lines of about `width` characters at word boundaries.
+fn wrap(text: &str, width: usize) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
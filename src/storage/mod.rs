@@ -1,2 +1,9 @@
+pub mod cache;
 pub mod database;
+pub mod graph;
 pub mod models;
+#[cfg(feature = "postgres")]
+mod postgres;
+pub mod query;
+pub mod sql;
+pub mod store;
@@ -1,5 +1,7 @@
 use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
+use super::sql::Dialect;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Video {
@@ -10,6 +12,10 @@ pub struct Video {
     pub upload_date: Option<NaiveDate>,
     pub description: Option<String>,
     pub added_at: DateTime<Utc>,
+    /// Length in seconds
+    pub duration: Option<f64>,
+    pub view_count: Option<i64>,
+    pub thumbnail_url: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +31,125 @@ pub struct TranscriptSegment {
     pub start_time: f64,
     pub duration: f64,
     pub text: String,
+    /// Speaker label from diarized input, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Chapter {
+    pub video_id: String,
+    pub start_time: f64,
+    /// Start of the next chapter; None for the last one
+    pub end_time: Option<f64>,
+    pub title: String,
+}
+
+/// A channel or playlist polled by `watch` for new uploads.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Subscription {
+    pub id: i64,
+    pub url: String,
+    pub name: Option<String>,
+    pub last_checked: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Raw payload captured at fetch time, kept so transcripts can be re-parsed
+/// without downloading again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Archive {
+    pub id: i64,
+    pub video_id: String,
+    pub kind: String,               // metadata, captions
+    pub format: String,             // json, json3, srt, vtt, txt
+    pub language: Option<String>,
+    pub content: String,
+    pub fetched_at: DateTime<Utc>,
+}
+
+/// A file (map, diagram, scan) kept alongside a video or claim.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attachment {
+    pub id: i64,
+    pub video_id: Option<String>,
+    pub claim_id: Option<i64>,
+    pub file_name: String,
+    pub mime_type: String,
+    pub caption: Option<String>,
+    pub size: i64,
+    pub path: Option<String>,       // Set when the file is referenced on disk instead of stored
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Availability {
+    Unknown,    // Never checked, or the last check failed transiently
+    Available,
+    Private,    // Private, members-only or age-gated without cookies
+    Removed,    // Deleted, taken down or the channel terminated
+}
+
+impl Availability {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Availability::Unknown => "unknown",
+            Availability::Available => "available",
+            Availability::Private => "private",
+            Availability::Removed => "removed",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "unknown" => Some(Availability::Unknown),
+            "available" | "ok" => Some(Availability::Available),
+            "private" => Some(Availability::Private),
+            "removed" | "deleted" | "dead" => Some(Availability::Removed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VideoAvailability {
+    pub video: Video,
+    pub availability: Availability,
+    pub checked_at: Option<DateTime<Utc>>,
+    pub detail: Option<String>,
+}
+
+/// One recorded insert, update or delete.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: i64,
+    /// Table name, e.g. claims or video_topics
+    pub entity_type: String,
+    pub entity_id: String,
+    /// insert, update or delete
+    pub action: String,
+    /// JSON snapshot of the row's leading columns (for updates, also which columns changed)
+    pub summary: String,
+    /// Command that made the change, e.g. cli:auto-tag
+    pub source: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A problem found by `engine doctor`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityIssue {
+    /// Which check found it: integrity, orphan, embedding or fts
+    pub check: String,
+    pub description: String,
+    pub count: i64,
+    pub fixable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub name: String,
+    pub applied_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone)]
@@ -38,6 +163,19 @@ pub struct SegmentMatch {
     pub start_time: f64,
     pub duration: f64,
     pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+}
+
+/// A full-text hit outside transcripts: a note, quote, term definition or summary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextMatch {
+    pub kind: String,               // note, quote, term, summary
+    pub id: i64,
+    pub video_id: Option<String>,
+    pub title: String,              // Quote speaker, term, or summary layer
+    pub snippet: String,
+    pub score: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +183,10 @@ pub struct Era {
     pub id: i64,
     pub name: String,
     pub sort_order: i32,
+    /// First year, negative for BCE
+    pub start_year: Option<i32>,
+    /// Last year, negative for BCE
+    pub end_year: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,6 +196,17 @@ pub struct Region {
     pub parent_id: Option<i64>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionCount {
+    pub id: i64,
+    pub name: String,
+    pub parent_id: Option<i64>,
+    /// Videos tagged with this region itself
+    pub video_count: i64,
+    /// Videos tagged with this region or any region nested under it
+    pub subtree_video_count: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Topic {
     pub id: i64,
@@ -106,6 +259,53 @@ pub struct MapPin {
     pub note: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimelineKind {
+    Video,
+    Claim,
+    CyclicalIndicator,
+    IdeaTransmission,
+    GeopoliticalEntity,
+    SurplusFlow,
+}
+
+impl TimelineKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimelineKind::Video => "video",
+            TimelineKind::Claim => "claim",
+            TimelineKind::CyclicalIndicator => "cyclical",
+            TimelineKind::IdeaTransmission => "transmission",
+            TimelineKind::GeopoliticalEntity => "entity",
+            TimelineKind::SurplusFlow => "flow",
+        }
+    }
+}
+
+/// Something placed on the timeline. Claims with dates of their own sit at those years;
+/// everything else spans its era's years, once per era it is tagged with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    pub kind: TimelineKind,
+    /// The video ID for videos, the row ID for everything else
+    pub id: String,
+    pub label: String,
+    /// Negative for BCE
+    pub start_year: i32,
+    pub end_year: i32,
+    /// The era placing the entry; none for claims dated directly
+    pub era: Option<String>,
+    pub video_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Timeline {
+    /// Eras with years, oldest first, for the timeline's bands
+    pub eras: Vec<Era>,
+    /// Oldest first
+    pub entries: Vec<TimelineEntry>,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct AutoTags {
     pub eras: Vec<String>,
@@ -123,6 +323,37 @@ pub struct SavedSearch {
     pub era: Option<String>,
     pub region: Option<String>,
     pub topic: Option<String>,
+    pub channel: Option<String>,
+    pub collection: Option<String>,
+    /// Upload date bounds, inclusive, as YYYY-MM-DD
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+    /// Query embedding; when set the search runs as a hybrid/semantic search
+    pub vector: Option<Vec<f32>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Another name for a searchable entity; searches for either name find both.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alias {
+    pub id: i64,
+    pub name: String,
+    pub alias: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A search as it was run from the CLI, kept so it can be listed and re-run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHistoryEntry {
+    pub id: i64,
+    pub query: String,
+    pub era: Option<String>,
+    pub region: Option<String>,
+    pub topic: Option<String>,
+    pub speaker: Option<String>,
+    pub collection: Option<String>,
+    pub scopes: Vec<String>,
+    pub result_count: i64,
     pub created_at: DateTime<Utc>,
 }
 
@@ -135,6 +366,76 @@ pub struct AdvancedSearchResult {
     pub topics: Vec<String>,
 }
 
+/// How full-text matches on videos are ordered: bm25 column weights plus an optional
+/// boost for recently published videos (0 disables it).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RankingProfile {
+    pub title_weight: f64,
+    pub description_weight: f64,
+    pub transcript_weight: f64,
+    pub recency_boost: f64,
+}
+
+impl Default for RankingProfile {
+    fn default() -> Self {
+        RankingProfile { title_weight: 10.0, description_weight: 5.0, transcript_weight: 1.0, recency_boost: 0.0 }
+    }
+}
+
+impl RankingProfile {
+    /// Parse "title,description,transcript" weights, e.g. "10,5,1".
+    pub fn parse_weights(s: &str) -> Option<(f64, f64, f64)> {
+        let weights: Vec<f64> = s.split(',').map(|w| w.trim().parse::<f64>().ok()).collect::<Option<_>>()?;
+        match weights[..] {
+            [t, d, x] if [t, d, x].iter().all(|w| w.is_finite() && *w >= 0.0) => Some((t, d, x)),
+            _ => None,
+        }
+    }
+
+    /// Rank expression over search_index joined with videos as `v` (lower is better). On
+    /// PostgreSQL the tsquery must be in scope as `query`.
+    pub fn rank_sql(&self, dialect: Dialect) -> String {
+        let bm25 = match dialect {
+            Dialect::Sqlite => format!(
+                "bm25(search_index, 0.0, {:?}, {:?}, {:?})",
+                self.title_weight, self.description_weight, self.transcript_weight
+            ),
+            // ts_rank takes weights for D, C, B, A (transcript, description, title), none above 1
+            Dialect::Postgres => {
+                let top = self.title_weight.max(self.description_weight).max(self.transcript_weight).max(f64::MIN_POSITIVE);
+                format!(
+                    "-ts_rank('{{0, {:?}, {:?}, {:?}}}', search_index.document, query)",
+                    self.transcript_weight / top, self.description_weight / top, self.title_weight / top
+                )
+            }
+        };
+        if self.recency_boost > 0.0 {
+            // Decays to half the boost after a year
+            format!(
+                "{} * (1.0 + {:?} / (1.0 + MAX(julianday('now') - julianday(COALESCE(v.upload_date, v.added_at)), 0.0) / 365.0))",
+                bm25, self.recency_boost
+            )
+        } else {
+            bm25
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdvancedSearchResponse {
+    pub results: Vec<AdvancedSearchResult>,
+    pub facets: MetadataFacets,
+}
+
+/// Hit counts per tag value, highest first, for drilling into search results.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetadataFacets {
+    pub eras: Vec<(String, usize)>,
+    pub regions: Vec<(String, usize)>,
+    pub topics: Vec<(String, usize)>,
+    pub channels: Vec<(String, usize)>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReportEntry {
     pub name: String,
@@ -236,6 +537,37 @@ impl Confidence {
     }
 }
 
+/// Where a claim stands after review. Disputed and retracted claims are kept, with
+/// their links, so the history of a changed mind stays visible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClaimStatus {
+    Unverified,
+    Verified,
+    Disputed,
+    Retracted,
+}
+
+impl ClaimStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClaimStatus::Unverified => "unverified",
+            ClaimStatus::Verified => "verified",
+            ClaimStatus::Disputed => "disputed",
+            ClaimStatus::Retracted => "retracted",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "unverified" => Some(ClaimStatus::Unverified),
+            "verified" => Some(ClaimStatus::Verified),
+            "disputed" => Some(ClaimStatus::Disputed),
+            "retracted" => Some(ClaimStatus::Retracted),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claim {
     pub id: i64,
@@ -246,6 +578,10 @@ pub struct Claim {
     pub category: ClaimCategory,
     pub confidence: Confidence,
     pub created_at: DateTime<Utc>,
+    pub status: ClaimStatus,
+    /// Years the claim is about, negative for BCE (e.g. -1200 to -1150)
+    pub date_start: Option<i32>,
+    pub date_end: Option<i32>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -292,6 +628,62 @@ pub struct ClaimLink {
     pub created_at: DateTime<Utc>,
 }
 
+/// One hop along a path through the claim graph. `via` is the link type that led here
+/// (None for the start), `forward` whether the link points from the previous claim to this one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimPathStep {
+    pub claim: Claim,
+    pub via: Option<String>,
+    pub forward: bool,
+}
+
+/// How an AI-extracted claim was produced, and whether a person has checked it since.
+/// Claims added by hand have none.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimProvenance {
+    pub claim_id: i64,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub prompt_version: Option<String>,
+    pub run_id: Option<String>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+}
+
+impl ClaimProvenance {
+    pub fn needs_review(&self) -> bool {
+        self.reviewed_at.is_none()
+    }
+}
+
+/// Where a claim sits in the claim graph, as computed by `graph-metrics`. Links and causal
+/// relations count as undirected edges; betweenness is normalized to 0..1 and components
+/// are numbered from the largest (0) down.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimMetrics {
+    pub claim_id: i64,
+    pub degree: usize,
+    pub betweenness: f64,
+    pub component: usize,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Two claims joined by a `contradicts` link, with the videos they come from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Contradiction {
+    pub link_id: i64,
+    pub claim: Claim,
+    pub claim_video_title: Option<String>,
+    pub other: Claim,
+    pub other_video_title: Option<String>,
+}
+
+/// Contradictions sharing a topic or MOC. A pair spanning several appears in each.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContradictionGroup {
+    pub name: String,
+    pub contradictions: Vec<Contradiction>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClaimWithLinks {
     pub claim: Claim,
@@ -299,6 +691,14 @@ pub struct ClaimWithLinks {
     pub incoming_links: Vec<(ClaimLink, Claim)>,
 }
 
+/// Two claims whose embeddings are nearly identical; `claim` is the older one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateClaims {
+    pub claim: Claim,
+    pub duplicate: Claim,
+    pub similarity: f32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranscriptLayer {
     pub id: i64,
@@ -318,6 +718,8 @@ pub struct TranscriptChunk {
     pub text: String,
     pub token_count: i32,
     pub overlap_with_previous: bool,
+    /// Title of the chapter the chunk starts in, if the video has chapters
+    pub chapter: Option<String>,
 }
 
 // Phase 7: Semantic Search & Embeddings
@@ -370,6 +772,42 @@ pub struct SimilarityResult {
     pub video_id: Option<String>,
 }
 
+/// An embedding reduced to two dimensions for plotting.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectionPoint {
+    pub source_type: EmbeddingSource,
+    pub source_id: String,
+    pub x: f32,
+    pub y: f32,
+    pub label: String,
+    pub category: Option<String>, // Claim category; None for videos and chunks
+    pub video_id: Option<String>,
+}
+
+/// How hybrid search combines its keyword and semantic result lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Fusion {
+    Weighted, // Weighted sum of the two scores
+    Rrf,      // Reciprocal rank fusion: only each list's ranking counts
+}
+
+impl Fusion {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Fusion::Weighted => "weighted",
+            Fusion::Rrf => "rrf",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "weighted" | "sum" => Some(Fusion::Weighted),
+            "rrf" => Some(Fusion::Rrf),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HybridSearchResult {
     pub video: Video,
@@ -395,6 +833,40 @@ pub struct EmbeddingStats {
     pub summary_embeddings: i64,
     pub model: Option<String>,
     pub dimensions: Option<i32>,
+    pub queued: i64,
+}
+
+/// An item waiting to be (re-)embedded, added when new content is ingested.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingQueueItem {
+    pub id: i64,
+    pub source_type: EmbeddingSource,
+    pub source_id: String,
+    pub attempts: i32,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Vector size produced by an embedding model, recorded when it is first used.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingModel {
+    pub model: String,
+    pub provider: Option<String>,
+    pub dimensions: usize,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Embedding counts for one model, broken down by source type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingModelStats {
+    pub model: String,
+    pub provider: Option<String>,
+    pub dimensions: usize,
+    pub videos: i64,
+    pub chunks: i64,
+    pub claims: i64,
+    pub summaries: i64,
+    pub total: i64,
 }
 
 // Phase 8: Analytical Frameworks
@@ -529,6 +1001,63 @@ pub struct CausalRelation {
     pub created_at: DateTime<Utc>,
 }
 
+/// The causal relations around a claim: the claims reached by following relations either
+/// way, with how many hops away each is (0 for the claim itself), and every relation
+/// among them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CausalChain {
+    pub claims: Vec<(Claim, usize)>,
+    pub relations: Vec<CausalRelation>,
+}
+
+/// A feedback loop: causal relations, by ID, leading from a claim back to itself.
+/// `polarity` is Positive (reinforcing) when an even number of them are negative, and
+/// Negative (balancing) otherwise.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CausalLoop {
+    pub relations: Vec<i64>,
+    pub polarity: LoopType,
+}
+
+/// A set of claims with the links and causal relations between them, and the sources
+/// and scholars each claim cites (as short citations, by claim ID).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimGraph {
+    pub claims: Vec<Claim>,
+    pub links: Vec<ClaimLink>,
+    pub causal_relations: Vec<CausalRelation>,
+    pub citations: HashMap<i64, Vec<String>>,
+}
+
+/// Which claims `Database::claim_graph_page` returns; every filter given must match.
+#[derive(Debug, Clone, Default)]
+pub struct ClaimGraphFilter {
+    pub video_id: Option<String>,
+    pub moc_id: Option<i64>,
+    /// Tagged with any of these eras, directly or through the claim's video
+    pub eras: Vec<String>,
+    /// Tagged with this topic, directly or through the claim's video
+    pub topic: Option<String>,
+    /// Any of these statuses; empty for all
+    pub statuses: Vec<ClaimStatus>,
+    /// Any of these categories; empty for all
+    pub categories: Vec<ClaimCategory>,
+    /// At least this confident
+    pub min_confidence: Option<Confidence>,
+    /// Only links of these types are returned and counted; empty for all
+    pub link_types: Vec<LinkType>,
+}
+
+/// A page of the claim graph: claims, newest first, with how many links each has in
+/// the whole graph, and the links among the claims on the page.
+#[derive(Debug, Clone)]
+pub struct ClaimGraphPage {
+    pub claims: Vec<(Claim, usize)>,
+    pub links: Vec<ClaimLink>,
+    /// Claims matching the filter, across all pages
+    pub total: usize,
+}
+
 // 8.3 Memetic Transmission Tracking (Boyd/Richerson dual inheritance)
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -759,6 +1288,19 @@ pub struct QuestionWithEvidence {
 
 // 9.3 Pattern Detection Results
 
+// Proposed research questions, generated from contradictions and coverage gaps
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuggestedQuestion {
+    pub id: i64,
+    pub question: String,
+    pub reason: String,             // contradiction, thin_topic, unmapped_cluster
+    pub claim_ids: Vec<i64>,
+    pub video_ids: Vec<String>,
+    pub status: String,             // pending, accepted, dismissed
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DetectedPattern {
     pub id: i64,
@@ -770,6 +1312,18 @@ pub struct DetectedPattern {
     pub detected_at: DateTime<Utc>,
 }
 
+/// How an LLM-detected pattern was produced. Patterns entered by hand have none.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatternProvenance {
+    pub pattern_id: i64,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub prompt_version: Option<String>,
+    pub run_id: Option<String>,
+    /// The `detect-patterns --scope` the claims were drawn from, e.g. `era:Bronze Age`
+    pub scope: Option<String>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PatternType {
     RecurringTheme,
@@ -817,6 +1371,19 @@ pub struct ClaimAccess {
     pub last_accessed: DateTime<Utc>,
 }
 
+// Spaced-repetition schedule, updated by quiz results
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimSchedule {
+    pub claim_id: i64,
+    pub interval_days: f64,
+    pub ease: f64,
+    pub repetitions: i32,         // Consecutive correct answers
+    pub lapses: i32,              // Total incorrect answers
+    pub due_at: DateTime<Utc>,
+    pub last_reviewed: DateTime<Utc>,
+}
+
 // 9.5 LLM Configuration (for future use)
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -850,6 +1417,87 @@ pub struct LLMConfig {
     pub provider: LLMProvider,
     pub model: String,
     pub temperature: f32,
+    /// Environment variable holding the API key, when not the provider's usual one
+    #[serde(default)]
+    pub key_env: Option<String>,
+    /// Server URL, when not the provider's default or its *_BASE_URL variable
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Per-request timeout, when not the default
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+/// One LLM call as logged in `llm_usage`. Token counts are None when the provider
+/// didn't report them, and the cost when the model's price is unknown.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmUsage {
+    pub id: i64,
+    /// What the call was for, e.g. "extract-claims" or "summarize"
+    pub task: String,
+    pub provider: String,
+    pub model: String,
+    pub input_tokens: Option<i64>,
+    pub output_tokens: Option<i64>,
+    pub latency_ms: i64,
+    /// Estimated at the time of the call, in USD
+    pub cost_usd: Option<f64>,
+    pub success: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// What `llm usage` totals are grouped by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UsageGrouping {
+    Model,
+    Day,
+    Task,
+}
+
+/// LLM usage totals for one model, day or task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmUsageSummary {
+    pub key: String,
+    pub calls: i64,
+    pub failed: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub latency_ms: i64,
+    /// None when no call in the group had a known price
+    pub cost_usd: Option<f64>,
+    /// Successful calls left out of the cost because their price is unknown
+    pub unpriced: i64,
+}
+
+/// What an API token may do: read lets a client use GET requests only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TokenScope {
+    Read,
+    Write,
+}
+
+impl TokenScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenScope::Read => "read",
+            TokenScope::Write => "write",
+        }
+    }
+}
+
+/// A token for the HTTP API. Once any exist, `serve` requires one on every API request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub id: i64,
+    pub name: String,
+    /// The secret itself, known only to the `ApiToken` that `create_api_token` returns
+    #[serde(skip_serializing)]
+    pub token: String,
+    /// The first characters of the secret, kept to tell tokens apart
+    pub prefix: String,
+    pub scope: TokenScope,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
 }
 
 // Synthesis statistics
@@ -910,6 +1558,41 @@ pub struct AIProcessingQueue {
     pub claims_extracted: i32,                // Count of claims added
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueEvent {
+    pub video_id: String,
+    pub status: ProcessingStatus,
+    pub detail: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+// Per-video pipeline status (everything known about one video's processing)
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VideoStatus {
+    pub video: Video,
+    pub transcript_language: Option<String>,
+    pub transcript_segments: usize,
+    pub transcript_chars: usize,
+    pub transcript_punctuated: bool,   // Auto-captions usually lack sentence punctuation
+    pub chunks: i64,
+    pub video_embedded: bool,
+    pub chunk_embeddings: i64,
+    pub claim_embeddings: i64,
+    pub summary_embeddings: i64,
+    pub layers: Vec<u8>,
+    pub claims: i64,
+    pub claim_links: i64,              // Links touching at least one of this video's claims
+    pub unlinked_claims: i64,
+    pub sources_cited: i64,
+    pub scholars_cited: i64,
+    pub quotes: i64,
+    pub pending_quotes: i64,
+    pub evidence: i64,
+    pub queue: Option<AIProcessingQueue>,
+    pub queue_history: Vec<QueueEvent>,
+}
+
 // Phase 12: Expanded Knowledge Entities
 
 // 12.1 Sources (books, papers, documentaries)
@@ -961,6 +1644,19 @@ pub struct Source {
     pub created_at: DateTime<Utc>,
 }
 
+impl Source {
+    /// Short in-text citation: "Cline 2014" (author surname and year), else the title.
+    pub fn citation(&self) -> String {
+        let author = self.author.as_deref().and_then(|a| a.split_whitespace().last());
+        match (author, self.year) {
+            (Some(author), Some(year)) => format!("{} {}", author, year),
+            (Some(author), None) => author.to_string(),
+            (None, Some(year)) => format!("{} ({})", self.title, year),
+            (None, None) => self.title.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VideoSource {
     pub video_id: String,
@@ -979,6 +1675,9 @@ pub struct Scholar {
     pub era: Option<String>,
     pub contribution: Option<String>,
     pub created_at: DateTime<Utc>,
+    /// Set when the scholar was added by `extract-entities`: provider/model and prompt version
+    #[serde(default)]
+    pub extracted_by: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -989,6 +1688,13 @@ pub struct VideoScholar {
     pub context: Option<String>,
 }
 
+/// A scholar a claim is attributed to, and how (e.g. "argues", "disputes").
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimScholar {
+    pub scholar: Scholar,
+    pub attribution: Option<String>,
+}
+
 // 12.3 Visuals (images, diagrams, artifacts described)
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -1063,6 +1769,9 @@ pub struct Term {
     pub timestamp: Option<f64>,
     pub scholar_id: Option<i64>,
     pub created_at: DateTime<Utc>,
+    /// Set when the term was added by `extract-entities`: provider/model and prompt version
+    #[serde(default)]
+    pub extracted_by: Option<String>,
 }
 
 // 12.5 Evidence (specific evidence cited)
@@ -1135,8 +1844,25 @@ pub struct Quote {
     pub created_at: DateTime<Utc>,
 }
 
+// Quotes staged by extract-quotes, awaiting confirmation
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingQuote {
+    pub id: i64,
+    pub video_id: String,
+    pub text: String,
+    pub speaker: Option<String>,
+    pub timestamp: Option<f64>,
+    pub reason: String,
+    pub context: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
 // Composite types for rich display
 
+/// A source or scholar as cited by a video: the timestamp and context of the citation.
+pub type Cited<T> = (T, Option<f64>, Option<String>);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SourceWithVideos {
     pub source: Source,
@@ -1238,4 +1964,6 @@ pub struct SearchResponse {
 pub struct SearchFacets {
     pub types: Vec<(SearchResultType, usize)>,
     pub videos: Vec<(String, String, usize)>,
+    #[serde(flatten)]
+    pub metadata: MetadataFacets,
 }
@@ -0,0 +1,980 @@
+//! PostgreSQL connection for `Database`. Statements are the SQLite ones, translated on the
+//! way through (placeholders, `INSERT OR IGNORE`/`OR REPLACE`, `UPDATE OR IGNORE`, LIKE's
+//! case-insensitivity, a few functions); the handful that can't be are written per dialect
+//! in database.rs. The blocking client can't run inside the server's async runtime, so the
+//! connection lives on a thread of its own and statements are sent to it.
+//!
+//! Translating keeps a single copy of the several hundred statements in database.rs. The
+//! differences between the two dialects are almost all in the spellings handled here, and
+//! a second copy per backend would drift from the first with every change to the schema;
+//! full-text search, schema introspection and DDL are where the SQL genuinely differs, and
+//! those are the statements written per dialect. The rewrites only touch text outside
+//! quotes and comments, and are covered by the tests at the end of this file.
+
+use super::sql::{Error, Result};
+use postgres::error::SqlState;
+use postgres::types::private::BytesMut;
+use postgres::types::{FromSql, IsNull, ToSql, Type};
+use postgres::NoTls;
+use rusqlite::types::Value;
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc;
+
+type Job = Box<dyn FnOnce(&mut Worker) + Send>;
+
+// Prepared statements kept per connection; cleared when full or when the schema changes
+const STATEMENT_CACHE: usize = 256;
+
+pub struct Client {
+    jobs: mpsc::Sender<Job>,
+    last_insert_id: Cell<i64>,
+    in_transaction: Cell<bool>,
+}
+
+impl Client {
+    pub fn connect(url: &str) -> Result<Self> {
+        let (jobs, receiver) = mpsc::channel::<Job>();
+        let (ready, connected) = mpsc::channel();
+        let url = url.to_string();
+        std::thread::Builder::new()
+            .name("postgres".to_string())
+            .spawn(move || {
+                let mut worker = match postgres::Client::connect(&url, NoTls) {
+                    Ok(client) => {
+                        let _ = ready.send(Ok(()));
+                        Worker { client, statements: HashMap::new(), schema: None }
+                    }
+                    Err(e) => {
+                        let _ = ready.send(Err(e));
+                        return;
+                    }
+                };
+                for job in receiver {
+                    job(&mut worker);
+                }
+            })
+            .expect("spawn postgres connection thread");
+        connected.recv().expect("postgres connection thread")?;
+        Ok(Self { jobs, last_insert_id: Cell::new(0), in_transaction: Cell::new(false) })
+    }
+
+    fn run<T: Send + 'static>(&self, f: impl FnOnce(&mut Worker) -> T + Send + 'static) -> T {
+        let (reply, result) = mpsc::sync_channel(1);
+        self.jobs
+            .send(Box::new(move |worker| {
+                let _ = reply.send(f(worker));
+            }))
+            .expect("postgres connection thread");
+        result.recv().expect("postgres connection thread")
+    }
+
+    pub fn query(&self, sql: &str, values: Vec<Value>) -> Result<Vec<Vec<Value>>> {
+        let sql = sql.to_string();
+        self.run(move |worker| worker.query(&sql, values))
+    }
+
+    pub fn execute(&self, sql: &str, values: Vec<Value>) -> Result<usize> {
+        let sql = sql.to_string();
+        let (affected, id) = self.run(move |worker| worker.execute(&sql, values))?;
+        if let Some(id) = id {
+            self.last_insert_id.set(id);
+        }
+        Ok(affected)
+    }
+
+    pub fn column_names(&self, sql: &str) -> Result<Vec<String>> {
+        let sql = sql.to_string();
+        self.run(move |worker| {
+            let prepared = worker.prepare(&sql)?;
+            Ok(prepared.statement.columns().iter().map(|c| c.name().to_string()).collect())
+        })
+    }
+
+    pub fn batch_execute(&self, sql: &str) -> Result<()> {
+        let sql = sql.to_string();
+        self.run(move |worker| {
+            let translated = worker.translate(&sql, false)?.sql;
+            worker.schema_changed();
+            Ok(worker.client.batch_execute(&translated)?)
+        })
+    }
+
+    pub fn last_insert_id(&self) -> i64 {
+        self.last_insert_id.get()
+    }
+
+    // PostgreSQL only warns about a nested BEGIN and the inner COMMIT would end the outer
+    // transaction, so nesting is refused here as SQLite refuses it
+    pub fn begin(&self) -> Result<()> {
+        if self.in_transaction.get() {
+            return Err(Error::NestedTransaction);
+        }
+        self.run(|worker| worker.client.batch_execute("BEGIN"))?;
+        self.in_transaction.set(true);
+        Ok(())
+    }
+
+    pub fn end(&self, commit: bool) -> Result<()> {
+        // A failed COMMIT still ends the transaction
+        self.in_transaction.set(false);
+        self.run(move |worker| Ok(worker.client.batch_execute(if commit { "COMMIT" } else { "ROLLBACK" })?))
+    }
+}
+
+struct Worker {
+    client: postgres::Client,
+    statements: HashMap<String, Prepared>,
+    schema: Option<Schema>,
+}
+
+#[derive(Clone)]
+struct Prepared {
+    statement: postgres::Statement,
+    /// `RETURNING id` was added to an INSERT, to answer last_insert_rowid
+    returns_id: bool,
+}
+
+// What translation needs to know about the tables
+struct Schema {
+    /// Tables whose `id` column is generated
+    identity: HashSet<String>,
+    /// Unique keys per table, primary key first
+    keys: HashMap<String, Vec<Vec<String>>>,
+}
+
+impl Worker {
+    fn schema(&mut self) -> Result<&Schema> {
+        if self.schema.is_none() {
+            let identity = self
+                .client
+                .query(
+                    "SELECT table_name::text FROM information_schema.columns
+                     WHERE table_schema = current_schema() AND column_name = 'id' AND is_identity = 'YES'",
+                    &[],
+                )?
+                .iter()
+                .map(|row| row.get(0))
+                .collect();
+            let mut keys: HashMap<String, Vec<Vec<String>>> = HashMap::new();
+            for row in self.client.query(
+                "SELECT t.relname::text, string_agg(a.attname::text, ',' ORDER BY k.n)
+                 FROM pg_index i
+                 JOIN pg_class t ON t.oid = i.indrelid
+                 CROSS JOIN LATERAL unnest(i.indkey) WITH ORDINALITY AS k(attnum, n)
+                 JOIN pg_attribute a ON a.attrelid = t.oid AND a.attnum = k.attnum
+                 WHERE i.indisunique AND t.relnamespace = current_schema()::regnamespace
+                 GROUP BY t.relname, i.indexrelid, i.indisprimary
+                 ORDER BY t.relname, i.indisprimary DESC, i.indexrelid",
+                &[],
+            )? {
+                let columns: String = row.get(1);
+                keys.entry(row.get(0)).or_default().push(columns.split(',').map(str::to_string).collect());
+            }
+            self.schema = Some(Schema { identity, keys });
+        }
+        Ok(self.schema.as_ref().expect("schema loaded"))
+    }
+
+    fn schema_changed(&mut self) {
+        self.schema = None;
+        self.statements.clear();
+    }
+
+    fn translate(&mut self, sql: &str, single: bool) -> Result<Translated> {
+        let mut translator = Translator { schema: self.schema()?, next_param: 0 };
+        Ok(translator.statements(sql, single))
+    }
+
+    fn prepare(&mut self, sql: &str) -> Result<Prepared> {
+        if let Some(prepared) = self.statements.get(sql) {
+            return Ok(prepared.clone());
+        }
+        let translated = self.translate(sql, true)?;
+        if is_ddl(sql) {
+            self.schema_changed();
+        }
+        let prepared = Prepared { statement: self.client.prepare(&translated.sql)?, returns_id: translated.returns_id };
+        if self.statements.len() >= STATEMENT_CACHE {
+            self.statements.clear();
+        }
+        self.statements.insert(sql.to_string(), prepared.clone());
+        Ok(prepared)
+    }
+
+    // Parameters PostgreSQL can't settle on a type for (e.g. `?1 IS NULL OR col >= ?1`) are
+    // retried with their NULL tests folded to constants, leaving the other uses to decide;
+    // failing that, each takes the type of the value given
+    fn prepare_for(&mut self, sql: &str, values: &[Value]) -> Result<Prepared> {
+        match self.prepare(sql) {
+            Err(Error::Postgres(e))
+                if matches!(e.code(), Some(&SqlState::INDETERMINATE_DATATYPE | &SqlState::AMBIGUOUS_PARAMETER)) =>
+            {
+                let translated = self.translate(sql, true)?;
+                let folded = fold_null_tests(&translated.sql, values);
+                let types: Vec<Type> = values
+                    .iter()
+                    .enumerate()
+                    .map(|(i, value)| if mentions_param(&folded, i + 1) { Type::UNKNOWN } else { value_type(value) })
+                    .collect();
+                let statement = match self.client.prepare_typed(&folded, &types) {
+                    Ok(statement) => statement,
+                    Err(_) => {
+                        let types: Vec<Type> = values.iter().map(value_type).collect();
+                        self.client.prepare_typed(&translated.sql, &types)?
+                    }
+                };
+                Ok(Prepared { statement, returns_id: translated.returns_id })
+            }
+            other => other,
+        }
+    }
+
+    fn query(&mut self, sql: &str, values: Vec<Value>) -> Result<Vec<Vec<Value>>> {
+        let prepared = self.prepare_for(sql, &values)?;
+        let params: Vec<Param> = values.into_iter().map(Param).collect();
+        let refs: Vec<&(dyn ToSql + Sync)> = params.iter().map(|p| p as &(dyn ToSql + Sync)).collect();
+        let rows = self.client.query(&prepared.statement, &refs)?;
+        let mut result = Vec::with_capacity(rows.len());
+        for row in rows {
+            let values = (0..row.len())
+                .map(|i| row.try_get::<_, Field>(i).map(|c| c.0))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            result.push(values);
+        }
+        Ok(result)
+    }
+
+    fn execute(&mut self, sql: &str, values: Vec<Value>) -> Result<(usize, Option<i64>)> {
+        let prepared = self.prepare_for(sql, &values)?;
+        let params: Vec<Param> = values.into_iter().map(Param).collect();
+        let refs: Vec<&(dyn ToSql + Sync)> = params.iter().map(|p| p as &(dyn ToSql + Sync)).collect();
+        if prepared.returns_id {
+            let rows = self.client.query(&prepared.statement, &refs)?;
+            let id = rows.last().map(|row| row.try_get::<_, i64>(0)).transpose()?;
+            return Ok((rows.len(), id));
+        }
+        Ok((self.client.execute(&prepared.statement, &refs)? as usize, None))
+    }
+}
+
+fn is_ddl(sql: &str) -> bool {
+    let first = sql.split_whitespace().next().unwrap_or_default().to_ascii_uppercase();
+    matches!(first.as_str(), "CREATE" | "ALTER" | "DROP")
+}
+
+// `$N IS NULL` and `$N IS NOT NULL` replaced with what they are for these values
+fn fold_null_tests(sql: &str, values: &[Value]) -> String {
+    let mut sql = sql.to_string();
+    for (i, value) in values.iter().enumerate().rev() {
+        let null = matches!(value, Value::Null);
+        let param = format!("${}", i + 1);
+        sql = sql
+            .replace(&format!("{param} IS NOT NULL"), if null { "FALSE" } else { "TRUE" })
+            .replace(&format!("{param} IS NULL"), if null { "TRUE" } else { "FALSE" });
+    }
+    sql
+}
+
+fn mentions_param(sql: &str, n: usize) -> bool {
+    let param = format!("${n}");
+    sql.match_indices(&param)
+        .any(|(i, _)| !sql[i + param.len()..].starts_with(|c: char| c.is_ascii_digit()))
+}
+
+fn value_type(value: &Value) -> Type {
+    match value {
+        Value::Integer(_) => Type::INT8,
+        Value::Real(_) => Type::FLOAT8,
+        Value::Blob(_) => Type::BYTEA,
+        Value::Null | Value::Text(_) => Type::TEXT,
+    }
+}
+
+/// A parameter, converted to whatever type PostgreSQL expects in its place the way
+/// SQLite's column affinity would (numbers into text columns, numeric text into numbers).
+#[derive(Debug)]
+struct Param(Value);
+
+type BoxError = Box<dyn std::error::Error + Sync + Send>;
+
+impl Param {
+    fn integer(&self) -> std::result::Result<i64, BoxError> {
+        match &self.0 {
+            Value::Integer(n) => Ok(*n),
+            Value::Real(f) if f.fract() == 0.0 => Ok(*f as i64),
+            Value::Text(s) => Ok(s.trim().parse()?),
+            other => Err(format!("expected an integer, got {:?}", other).into()),
+        }
+    }
+
+    fn real(&self) -> std::result::Result<f64, BoxError> {
+        match &self.0 {
+            Value::Integer(n) => Ok(*n as f64),
+            Value::Real(f) => Ok(*f),
+            Value::Text(s) => Ok(s.trim().parse()?),
+            other => Err(format!("expected a number, got {:?}", other).into()),
+        }
+    }
+
+    fn text(&self) -> String {
+        match &self.0 {
+            Value::Integer(n) => n.to_string(),
+            Value::Real(f) => f.to_string(),
+            Value::Text(s) => s.clone(),
+            Value::Blob(b) => String::from_utf8_lossy(b).into_owned(),
+            Value::Null => String::new(),
+        }
+    }
+}
+
+impl ToSql for Param {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> std::result::Result<IsNull, BoxError> {
+        if self.0 == Value::Null {
+            return Ok(IsNull::Yes);
+        }
+        match *ty {
+            Type::BOOL => (self.integer()? != 0).to_sql(ty, out),
+            Type::INT2 => i16::try_from(self.integer()?)?.to_sql(ty, out),
+            Type::INT4 => i32::try_from(self.integer()?)?.to_sql(ty, out),
+            Type::INT8 | Type::OID => self.integer()?.to_sql(ty, out),
+            Type::FLOAT4 => (self.real()? as f32).to_sql(ty, out),
+            Type::FLOAT8 => self.real()?.to_sql(ty, out),
+            Type::NUMERIC => {
+                encode_numeric(&self.real()?, out);
+                Ok(IsNull::No)
+            }
+            Type::BYTEA => match &self.0 {
+                Value::Blob(b) => b.to_sql(ty, out),
+                _ => self.text().into_bytes().to_sql(ty, out),
+            },
+            Type::JSONB => {
+                out.extend_from_slice(&[1]);
+                out.extend_from_slice(self.text().as_bytes());
+                Ok(IsNull::No)
+            }
+            Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME | Type::UNKNOWN | Type::JSON => {
+                out.extend_from_slice(self.text().as_bytes());
+                Ok(IsNull::No)
+            }
+            _ => Err(format!("unsupported parameter type {}", ty).into()),
+        }
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+
+    postgres::types::to_sql_checked!();
+}
+
+// NUMERIC's binary form: base-10000 digits with a weight, sign and display scale
+fn encode_numeric(value: &f64, out: &mut BytesMut) {
+    let text = format!("{}", value.abs());
+    let (int_part, frac_part) = text.split_once('.').unwrap_or((&text, ""));
+    let int_part = int_part.trim_start_matches('0');
+    // Pad both parts out to whole groups of four decimal digits
+    let int_padded = format!("{}{}", "0".repeat((4 - int_part.len() % 4) % 4), int_part);
+    let frac_padded = format!("{}{}", frac_part, "0".repeat((4 - frac_part.len() % 4) % 4));
+    let mut digits: Vec<i16> = int_padded
+        .as_bytes()
+        .chunks(4)
+        .chain(frac_padded.as_bytes().chunks(4))
+        .map(|c| std::str::from_utf8(c).unwrap_or("0").parse().unwrap_or(0))
+        .collect();
+    let mut weight = (int_padded.len() / 4) as i16 - 1;
+    while digits.first() == Some(&0) {
+        digits.remove(0);
+        weight -= 1;
+    }
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    if digits.is_empty() {
+        weight = 0;
+    }
+    let sign: u16 = if value.is_sign_negative() && !digits.is_empty() { 0x4000 } else { 0 };
+    out.extend_from_slice(&(digits.len() as i16).to_be_bytes());
+    out.extend_from_slice(&weight.to_be_bytes());
+    out.extend_from_slice(&sign.to_be_bytes());
+    out.extend_from_slice(&(frac_part.len() as i16).to_be_bytes());
+    for digit in digits {
+        out.extend_from_slice(&digit.to_be_bytes());
+    }
+}
+
+/// A column value read as the SQLite type it would have had: booleans and integers as
+/// INTEGER, other numbers as REAL (NUMERIC as INTEGER when it's whole), text as TEXT.
+struct Field(Value);
+
+impl<'a> FromSql<'a> for Field {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> std::result::Result<Self, BoxError> {
+        let value = match *ty {
+            Type::BOOL => Value::Integer(bool::from_sql(ty, raw)? as i64),
+            Type::INT2 => Value::Integer(i16::from_sql(ty, raw)? as i64),
+            Type::INT4 => Value::Integer(i32::from_sql(ty, raw)? as i64),
+            Type::INT8 => Value::Integer(i64::from_sql(ty, raw)?),
+            Type::OID => Value::Integer(u32::from_sql(ty, raw)? as i64),
+            Type::FLOAT4 => Value::Real(f32::from_sql(ty, raw)? as f64),
+            Type::FLOAT8 => Value::Real(f64::from_sql(ty, raw)?),
+            Type::NUMERIC => decode_numeric(raw)?,
+            Type::BYTEA => Value::Blob(raw.to_vec()),
+            Type::JSONB => Value::Text(String::from_utf8(raw.get(1..).unwrap_or_default().to_vec())?),
+            Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME | Type::UNKNOWN | Type::JSON => {
+                Value::Text(String::from_utf8(raw.to_vec())?)
+            }
+            // Functions run for their effect, such as pg_advisory_lock
+            Type::VOID => Value::Null,
+            _ => return Err(format!("unsupported column type {}", ty).into()),
+        };
+        Ok(Field(value))
+    }
+
+    fn from_sql_null(_: &Type) -> std::result::Result<Self, BoxError> {
+        Ok(Field(Value::Null))
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+}
+
+fn decode_numeric(raw: &[u8]) -> std::result::Result<Value, BoxError> {
+    let word = |i: usize| -> std::result::Result<i16, BoxError> {
+        Ok(i16::from_be_bytes(raw.get(i * 2..i * 2 + 2).ok_or("invalid numeric")?.try_into()?))
+    };
+    let (count, weight, sign, scale) = (word(0)?, word(1)?, word(2)? as u16, word(3)?);
+    if sign == 0xC000 {
+        return Ok(Value::Real(f64::NAN));
+    }
+    let digits = (0..count as usize).map(|i| word(4 + i)).collect::<std::result::Result<Vec<_>, _>>()?;
+    let negative = sign == 0x4000;
+    if scale == 0 && weight < 4 {
+        let mut n: i64 = 0;
+        for (i, d) in digits.iter().enumerate() {
+            if (i as i16) <= weight {
+                n = n * 10_000 + *d as i64;
+            }
+        }
+        for _ in digits.len() as i16..=weight {
+            n *= 10_000;
+        }
+        return Ok(Value::Integer(if negative { -n } else { n }));
+    }
+    let mut f = 0.0;
+    for (i, d) in digits.iter().enumerate() {
+        f += *d as f64 * 10_000f64.powi(weight as i32 - i as i32);
+    }
+    Ok(Value::Real(if negative { -f } else { f }))
+}
+
+struct Translated {
+    sql: String,
+    returns_id: bool,
+}
+
+// Rewrites SQLite statements for PostgreSQL, leaving quoted text and comments alone
+struct Translator<'s> {
+    schema: &'s Schema,
+    next_param: usize,
+}
+
+#[derive(Default)]
+struct Pending {
+    /// Appended at the end of the statement
+    suffix: String,
+    insert_into: Option<String>,
+    has_returning: bool,
+}
+
+impl Translator<'_> {
+    fn statements(&mut self, sql: &str, single: bool) -> Translated {
+        let mut out = String::with_capacity(sql.len() + 32);
+        let mut pending = Pending::default();
+        let mut returns_id = false;
+        let mut rest = sql;
+        loop {
+            let (end, at_semicolon) = statement_end(rest);
+            let statement = &rest[..end];
+            let translated = self.statement(statement, &mut pending);
+            pending.has_returning = contains_word(statement, "RETURNING");
+            out.push_str(translated.trim_end());
+            out.push_str(&pending.suffix);
+            if single && !pending.has_returning {
+                if let Some(table) = &pending.insert_into {
+                    if self.schema.identity.contains(table) {
+                        out.push_str(" RETURNING id");
+                        returns_id = true;
+                    }
+                }
+            }
+            pending = Pending::default();
+            if !at_semicolon {
+                break;
+            }
+            out.push(';');
+            rest = &rest[end + 1..];
+        }
+        Translated { sql: out, returns_id }
+    }
+
+    fn statement(&mut self, sql: &str, pending: &mut Pending) -> String {
+        let words = leading_words(sql, 4);
+        let upper: Vec<String> = words.iter().map(|(w, _)| w.to_ascii_uppercase()).collect();
+        let upper: Vec<&str> = upper.iter().map(String::as_str).collect();
+        match upper.as_slice() {
+            ["INSERT", "OR", "IGNORE", "INTO", ..] => {
+                pending.suffix = " ON CONFLICT DO NOTHING".to_string();
+                let (name, _) = table_and_columns(&sql[words[3].1..]);
+                pending.insert_into = Some(name);
+                format!("INSERT{}", self.span(&sql[words[2].1..]))
+            }
+            ["INSERT", "OR", "REPLACE", "INTO", ..] | ["REPLACE", "INTO", ..] => {
+                let into = if upper[0] == "REPLACE" { words[1].1 } else { words[3].1 };
+                let (name, columns) = table_and_columns(&sql[into..]);
+                pending.suffix = self.upsert(&name, &columns);
+                pending.insert_into = Some(name);
+                format!("INSERT INTO{}", self.span(&sql[into..]))
+            }
+            ["INSERT", "INTO", ..] => {
+                let (name, _) = table_and_columns(&sql[words[1].1..]);
+                pending.insert_into = Some(name);
+                self.span(sql)
+            }
+            ["UPDATE", "OR", "IGNORE", ..] => self.update_or_ignore(&sql[words[2].1..]),
+            _ => self.span(sql),
+        }
+    }
+
+    // ON CONFLICT for a REPLACE: the first unique key among the inserted columns
+    fn upsert(&self, table: &str, columns: &[String]) -> String {
+        let keys = self.schema.keys.get(table).map(Vec::as_slice).unwrap_or_default();
+        let Some(key) = keys.iter().find(|key| key.iter().all(|k| columns.contains(k))) else {
+            return String::new();
+        };
+        let updates: Vec<String> = columns
+            .iter()
+            .filter(|c| !key.contains(c))
+            .map(|c| format!("\"{0}\" = EXCLUDED.\"{0}\"", c))
+            .collect();
+        let target = key.iter().map(|k| format!("\"{}\"", k)).collect::<Vec<_>>().join(", ");
+        if updates.is_empty() {
+            format!(" ON CONFLICT ({}) DO NOTHING", target)
+        } else {
+            format!(" ON CONFLICT ({}) DO UPDATE SET {}", target, updates.join(", "))
+        }
+    }
+
+    // `UPDATE OR IGNORE t SET c = x WHERE ...` skips rows whose new value would collide
+    // with a unique key; the same rows are excluded up front here
+    fn update_or_ignore(&mut self, sql: &str) -> String {
+        let (table, after_table) = identifier(sql);
+        let body = self.span(&sql[after_table..]);
+        let Some((assignment, condition)) = split_keyword(&body, "WHERE") else {
+            return format!("UPDATE \"{}\"{}", table, body);
+        };
+        let Some((column, value)) = assignment.trim_start().strip_prefix("SET").and_then(|a| a.split_once('=')) else {
+            return format!("UPDATE \"{}\"{}", table, body);
+        };
+        let column = column.trim().trim_matches('"').to_string();
+        let value = value.trim();
+        let mut clashes = Vec::new();
+        for key in self.schema.keys.get(&table).map(Vec::as_slice).unwrap_or_default() {
+            if !key.contains(&column) {
+                continue;
+            }
+            let matches: Vec<String> = key
+                .iter()
+                .map(|k| {
+                    if *k == column {
+                        format!("o.\"{}\" = {}", k, value)
+                    } else {
+                        format!("o.\"{0}\" = \"{1}\".\"{0}\"", k, table)
+                    }
+                })
+                .collect();
+            clashes.push(format!(" AND NOT EXISTS (SELECT 1 FROM \"{}\" o WHERE {})", table, matches.join(" AND ")));
+        }
+        format!("UPDATE \"{}\" {} WHERE ({}){}", table, assignment.trim(), condition.trim(), clashes.concat())
+    }
+
+    fn span(&mut self, sql: &str) -> String {
+        let mut out = String::with_capacity(sql.len());
+        let bytes = sql.as_bytes();
+        let mut i = 0;
+        let mut last_word = String::new();
+        while i < bytes.len() {
+            let c = bytes[i];
+            match c {
+                b'\'' | b'"' => {
+                    let end = quoted_end(sql, i);
+                    out.push_str(&sql[i..end]);
+                    i = end;
+                }
+                b'-' if sql[i..].starts_with("--") => {
+                    let end = sql[i..].find('\n').map_or(sql.len(), |n| i + n);
+                    out.push_str(&sql[i..end]);
+                    i = end;
+                }
+                b'/' if sql[i..].starts_with("/*") => {
+                    let end = sql[i + 2..].find("*/").map_or(sql.len(), |n| i + n + 4);
+                    out.push_str(&sql[i..end]);
+                    i = end;
+                }
+                b'$' => {
+                    let end = dollar_quoted_end(sql, i);
+                    let digits = sql[i + 1..end].parse::<usize>().ok();
+                    if let Some(n) = digits {
+                        self.next_param = self.next_param.max(n);
+                    }
+                    out.push_str(&sql[i..end]);
+                    i = end;
+                }
+                b'?' => {
+                    let digits = sql[i + 1..].bytes().take_while(u8::is_ascii_digit).count();
+                    let n = if digits > 0 {
+                        sql[i + 1..i + 1 + digits].parse().unwrap_or(0)
+                    } else {
+                        self.next_param + 1
+                    };
+                    self.next_param = self.next_param.max(n);
+                    out.push_str(&format!("${}", n));
+                    i += 1 + digits;
+                }
+                c if c.is_ascii_alphabetic() || c == b'_' => {
+                    let end = i + sql[i..].bytes().take_while(|b| b.is_ascii_alphanumeric() || *b == b'_').count();
+                    let word = &sql[i..end];
+                    let upper = word.to_ascii_uppercase();
+                    let call = sql[end..].trim_start().starts_with('(');
+                    let was = std::mem::replace(&mut last_word, upper.clone());
+                    match upper.as_str() {
+                        "LIKE" => out.push_str("ILIKE"),
+                        "REAL" if was == "AS" => out.push_str("DOUBLE PRECISION"),
+                        "INSTR" if call => out.push_str("strpos"),
+                        "IFNULL" if call => out.push_str("coalesce"),
+                        "MAX" | "MIN" | "GROUP_CONCAT" if call => {
+                            let open = end + sql[end..].find('(').unwrap_or(0);
+                            let close = paren_end(sql, open);
+                            let args: Vec<String> = split_args(&sql[open + 1..close - 1]).into_iter().map(|a| self.span(a)).collect();
+                            out.push_str(&match (upper.as_str(), args.len()) {
+                                ("GROUP_CONCAT", 1) => format!("string_agg(CAST({} AS TEXT), ',')", args[0].trim()),
+                                ("GROUP_CONCAT", _) => format!("string_agg(CAST({} AS TEXT), {})", args[0].trim(), args[1].trim()),
+                                ("MAX", n) if n > 1 => format!("greatest({})", args.join(",")),
+                                ("MIN", n) if n > 1 => format!("least({})", args.join(",")),
+                                _ => format!("{}({})", word, args.join(",")),
+                            });
+                            i = close;
+                            continue;
+                        }
+                        _ => out.push_str(word),
+                    }
+                    i = end;
+                }
+                _ => {
+                    let len = sql[i..].chars().next().map_or(1, char::len_utf8);
+                    out.push_str(&sql[i..i + len]);
+                    i += len;
+                }
+            }
+        }
+        out
+    }
+}
+
+// Index just past a quoted string or identifier starting at `start` (quotes doubled inside)
+fn quoted_end(sql: &str, start: usize) -> usize {
+    let quote = sql.as_bytes()[start];
+    let bytes = sql.as_bytes();
+    let mut i = start + 1;
+    while i < bytes.len() {
+        if bytes[i] == quote {
+            if bytes.get(i + 1) == Some(&quote) {
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+// `$1` placeholders end after their digits; `$tag$ ... $tag$` bodies after the closing tag
+fn dollar_quoted_end(sql: &str, start: usize) -> usize {
+    let after = &sql[start + 1..];
+    let digits = after.bytes().take_while(u8::is_ascii_digit).count();
+    if digits > 0 {
+        return start + 1 + digits;
+    }
+    let tag_len = after.bytes().take_while(|b| b.is_ascii_alphanumeric() || *b == b'_').count();
+    if after.as_bytes().get(tag_len) != Some(&b'$') {
+        return start + 1;
+    }
+    let tag = &sql[start..start + tag_len + 2];
+    let body = start + tag.len();
+    sql[body..].find(tag).map_or(sql.len(), |n| body + n + tag.len())
+}
+
+// Index just past the parenthesis matching the one at `open`
+fn paren_end(sql: &str, open: usize) -> usize {
+    let bytes = sql.as_bytes();
+    let mut depth = 0;
+    let mut i = open;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' | b'"' => {
+                i = quoted_end(sql, i);
+                continue;
+            }
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+// Top-level comma-separated arguments
+fn split_args(sql: &str) -> Vec<&str> {
+    let bytes = sql.as_bytes();
+    let mut args = Vec::new();
+    let (mut depth, mut start, mut i) = (0, 0, 0);
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' | b'"' => {
+                i = quoted_end(sql, i);
+                continue;
+            }
+            b'(' => depth += 1,
+            b')' => depth -= 1,
+            b',' if depth == 0 => {
+                args.push(&sql[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    args.push(&sql[start..]);
+    args
+}
+
+// End of the first statement, and whether a semicolon ends it
+fn statement_end(sql: &str) -> (usize, bool) {
+    let bytes = sql.as_bytes();
+    let mut depth = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' | b'"' => {
+                i = quoted_end(sql, i);
+                continue;
+            }
+            b'$' => {
+                i = dollar_quoted_end(sql, i);
+                continue;
+            }
+            b'-' if sql[i..].starts_with("--") => {
+                i = sql[i..].find('\n').map_or(sql.len(), |n| i + n);
+                continue;
+            }
+            b'(' => depth += 1,
+            b')' => depth -= 1,
+            // BEGIN ... END bodies only appear inside dollar quotes here
+            b';' if depth == 0 => return (i, true),
+            _ => {}
+        }
+        i += 1;
+    }
+    (bytes.len(), false)
+}
+
+// The first `n` words of a statement with the offset just past each
+fn leading_words(sql: &str, n: usize) -> Vec<(&str, usize)> {
+    let mut words = Vec::new();
+    let mut i = 0;
+    while words.len() < n {
+        let rest = &sql[i..];
+        let skip = rest.len() - rest.trim_start().len();
+        let start = i + skip;
+        let len = sql[start..].bytes().take_while(|b| b.is_ascii_alphanumeric() || *b == b'_').count();
+        if len == 0 {
+            break;
+        }
+        words.push((&sql[start..start + len], start + len));
+        i = start + len;
+    }
+    words
+}
+
+// A table name (possibly quoted) at the start of `sql`, and the offset after it
+fn identifier(sql: &str) -> (String, usize) {
+    let skip = sql.len() - sql.trim_start().len();
+    let rest = &sql[skip..];
+    if rest.starts_with('"') {
+        let end = quoted_end(rest, 0);
+        return (rest[1..end - 1].replace("\"\"", "\""), skip + end);
+    }
+    let len = rest.bytes().take_while(|b| b.is_ascii_alphanumeric() || *b == b'_' || *b == b'.').count();
+    (rest[..len].to_string(), skip + len)
+}
+
+// The table an INSERT writes to and its column list
+fn table_and_columns(sql: &str) -> (String, Vec<String>) {
+    let (table, end) = identifier(sql);
+    let rest = sql[end..].trim_start();
+    let columns = if rest.starts_with('(') {
+        let close = paren_end(rest, 0);
+        split_args(&rest[1..close - 1]).into_iter().map(|c| c.trim().trim_matches('"').to_string()).collect()
+    } else {
+        Vec::new()
+    };
+    (table, columns)
+}
+
+fn contains_word(sql: &str, word: &str) -> bool {
+    sql.split(|c: char| !c.is_ascii_alphanumeric() && c != '_').any(|w| w.eq_ignore_ascii_case(word))
+}
+
+// Split at the first top-level occurrence of a keyword
+fn split_keyword<'a>(sql: &'a str, keyword: &str) -> Option<(&'a str, &'a str)> {
+    let bytes = sql.as_bytes();
+    let (mut depth, mut i) = (0, 0);
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' | b'"' => {
+                i = quoted_end(sql, i);
+                continue;
+            }
+            b'(' => depth += 1,
+            b')' => depth -= 1,
+            _ if depth == 0
+                && sql[i..].len() >= keyword.len()
+                && sql[i..i + keyword.len()].eq_ignore_ascii_case(keyword)
+                && (i == 0 || !bytes[i - 1].is_ascii_alphanumeric())
+                && !bytes.get(i + keyword.len()).is_some_and(|b| b.is_ascii_alphanumeric() || *b == b'_') =>
+            {
+                return Some((&sql[..i], &sql[i + keyword.len()..]));
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> Schema {
+        let key = |columns: &[&str]| columns.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        Schema {
+            identity: HashSet::from(["notes".to_string()]),
+            keys: HashMap::from([
+                ("videos".to_string(), vec![key(&["id"])]),
+                ("notes".to_string(), vec![key(&["id"])]),
+                ("video_topics".to_string(), vec![key(&["video_id", "topic_id"])]),
+                ("transcripts".to_string(), vec![key(&["id"]), key(&["video_id", "language"])]),
+            ]),
+        }
+    }
+
+    fn translate(sql: &str) -> String {
+        let schema = schema();
+        Translator { schema: &schema, next_param: 0 }.statements(sql, true).sql
+    }
+
+    #[test]
+    fn numbers_placeholders() {
+        assert_eq!(translate("SELECT id FROM videos WHERE id = ?1 OR url = ?1 OR title = ?2"), "SELECT id FROM videos WHERE id = $1 OR url = $1 OR title = $2");
+        assert_eq!(translate("SELECT ?, ?"), "SELECT $1, $2");
+    }
+
+    #[test]
+    fn leaves_quoted_text_and_comments_alone() {
+        assert_eq!(
+            translate("SELECT 'what? LIKE' AS \"a?\" FROM videos -- like ?\nWHERE title LIKE ?1"),
+            "SELECT 'what? LIKE' AS \"a?\" FROM videos -- like ?\nWHERE title ILIKE $1"
+        );
+        assert_eq!(translate("SELECT 'it''s ?1'"), "SELECT 'it''s ?1'");
+    }
+
+    #[test]
+    fn rewrites_functions() {
+        assert_eq!(translate("SELECT IFNULL(a, 0), instr(b, ' ')"), "SELECT coalesce(a, 0), strpos(b, ' ')");
+        assert_eq!(translate("SELECT MAX(a), MAX(a, b), min(a, 1)"), "SELECT MAX(a), greatest(a, b), least(a, 1)");
+        assert_eq!(translate("SELECT GROUP_CONCAT(name), GROUP_CONCAT(id, ';')"), "SELECT string_agg(CAST(name AS TEXT), ','), string_agg(CAST(id AS TEXT), ';')");
+        assert_eq!(translate("SELECT CAST(x AS REAL), real FROM t"), "SELECT CAST(x AS DOUBLE PRECISION), real FROM t");
+    }
+
+    #[test]
+    fn insert_or_ignore_skips_conflicts() {
+        assert_eq!(
+            translate("INSERT OR IGNORE INTO video_topics (video_id, topic_id) VALUES (?1, ?2)"),
+            "INSERT INTO video_topics (video_id, topic_id) VALUES ($1, $2) ON CONFLICT DO NOTHING"
+        );
+    }
+
+    #[test]
+    fn insert_or_replace_updates_on_the_first_covered_key() {
+        assert_eq!(
+            translate("INSERT OR REPLACE INTO videos (id, url, title) VALUES (?1, ?2, ?3)"),
+            "INSERT INTO videos (id, url, title) VALUES ($1, $2, $3) ON CONFLICT (\"id\") DO UPDATE SET \"url\" = EXCLUDED.\"url\", \"title\" = EXCLUDED.\"title\""
+        );
+        // Without an id, the (video_id, language) key is the one that can clash
+        assert_eq!(
+            translate("REPLACE INTO transcripts (video_id, language, full_text) VALUES (?1, ?2, ?3)"),
+            "INSERT INTO transcripts (video_id, language, full_text) VALUES ($1, $2, $3) ON CONFLICT (\"video_id\", \"language\") DO UPDATE SET \"full_text\" = EXCLUDED.\"full_text\""
+        );
+        assert_eq!(
+            translate("INSERT OR REPLACE INTO video_topics (video_id, topic_id) VALUES (?1, ?2)"),
+            "INSERT INTO video_topics (video_id, topic_id) VALUES ($1, $2) ON CONFLICT (\"video_id\", \"topic_id\") DO NOTHING"
+        );
+    }
+
+    #[test]
+    fn inserts_into_identity_tables_return_the_id() {
+        let schema = schema();
+        let translated = Translator { schema: &schema, next_param: 0 }.statements("INSERT INTO notes (video_id, text) VALUES (?1, ?2)", true);
+        assert_eq!(translated.sql, "INSERT INTO notes (video_id, text) VALUES ($1, $2) RETURNING id");
+        assert!(translated.returns_id);
+        assert_eq!(translate("INSERT INTO notes (text) VALUES (?1) RETURNING id"), "INSERT INTO notes (text) VALUES ($1) RETURNING id");
+        assert_eq!(translate("INSERT INTO videos (id) VALUES (?1)"), "INSERT INTO videos (id) VALUES ($1)");
+    }
+
+    #[test]
+    fn update_or_ignore_skips_rows_that_would_clash() {
+        assert_eq!(
+            translate("UPDATE OR IGNORE video_topics SET topic_id = ?1 WHERE topic_id = ?2"),
+            "UPDATE \"video_topics\" SET topic_id = $1 WHERE (topic_id = $2) AND NOT EXISTS (SELECT 1 FROM \"video_topics\" o WHERE o.\"video_id\" = \"video_topics\".\"video_id\" AND o.\"topic_id\" = $1)"
+        );
+    }
+
+    #[test]
+    fn batches_keep_function_bodies_whole() {
+        let schema = schema();
+        let sql = "CREATE FUNCTION f() RETURNS trigger LANGUAGE plpgsql AS $$ BEGIN PERFORM 1; RETURN NULL; END $$;\nINSERT OR IGNORE INTO notes (text) VALUES ('a;b')";
+        let translated = Translator { schema: &schema, next_param: 0 }.statements(sql, false);
+        assert_eq!(
+            translated.sql,
+            "CREATE FUNCTION f() RETURNS trigger LANGUAGE plpgsql AS $$ BEGIN PERFORM 1; RETURN NULL; END $$;INSERT INTO notes (text) VALUES ('a;b') ON CONFLICT DO NOTHING"
+        );
+        assert!(!translated.returns_id);
+    }
+
+    #[test]
+    fn folds_null_tests_for_the_values_given() {
+        let values = [Value::Null, Value::Integer(1)];
+        assert_eq!(
+            fold_null_tests("($1 IS NULL OR a = $1) AND ($2 IS NOT NULL) AND $10 IS NULL", &values),
+            "(TRUE OR a = $1) AND (TRUE) AND $10 IS NULL"
+        );
+    }
+}
@@ -0,0 +1,584 @@
+-- Schema for PostgreSQL databases, equivalent to the SQLite schema after every
+-- migration up to 5 (soft_delete). Run once on an empty database, after which those
+-- migrations are recorded as applied; later migrations run on both backends.
+--
+-- Needs PostgreSQL 14 or later built with ICU, for the case-insensitive collation.
+
+-- SQLite's NOCASE
+CREATE COLLATION nocase (provider = icu, locale = 'und-u-ks-level2', deterministic = false);
+
+-- SQLite's julianday(); NULL where the text isn't a date
+CREATE FUNCTION julianday(value TEXT) RETURNS DOUBLE PRECISION LANGUAGE plpgsql STABLE AS $$
+BEGIN
+    IF value = 'now' THEN
+        RETURN extract(epoch FROM now()) / 86400.0 + 2440587.5;
+    END IF;
+    RETURN extract(epoch FROM CAST(value AS TIMESTAMPTZ)) / 86400.0 + 2440587.5;
+EXCEPTION WHEN others THEN
+    RETURN NULL;
+END
+$$;
+
+-- Bumped by a trigger on every table, so other connections see writes
+CREATE SEQUENCE data_version;
+
+CREATE FUNCTION engine_data_version() RETURNS trigger LANGUAGE plpgsql AS $$
+BEGIN
+    PERFORM nextval('data_version');
+    RETURN NULL;
+END
+$$;
+
+CREATE TABLE schema_migrations (
+    version BIGINT PRIMARY KEY,
+    name TEXT NOT NULL,
+    applied_at TEXT NOT NULL
+);
+
+CREATE TABLE videos (
+    id TEXT PRIMARY KEY,
+    url TEXT NOT NULL,
+    title TEXT NOT NULL,
+    channel TEXT,
+    upload_date TEXT,
+    description TEXT,
+    added_at TEXT NOT NULL,
+    duration DOUBLE PRECISION,
+    view_count BIGINT,
+    thumbnail_url TEXT,
+    availability TEXT NOT NULL DEFAULT 'unknown',
+    availability_checked_at TEXT,
+    availability_detail TEXT,
+    archived_at TEXT
+);
+
+CREATE TABLE transcripts (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    language TEXT NOT NULL,
+    full_text TEXT NOT NULL,
+    segments_json TEXT NOT NULL,
+    -- Unstemmed, like SQLite's transcript_fts, so suggestions are real spellings
+    document tsvector GENERATED ALWAYS AS (to_tsvector('simple', full_text)) STORED,
+    UNIQUE(video_id, language)
+);
+CREATE INDEX idx_transcripts_video ON transcripts(video_id);
+CREATE INDEX idx_transcripts_document ON transcripts USING gin (document);
+
+CREATE TABLE subscriptions (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    url TEXT NOT NULL UNIQUE,
+    name TEXT,
+    last_checked TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE archives (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    kind TEXT NOT NULL,
+    format TEXT NOT NULL,
+    language TEXT,
+    content TEXT NOT NULL,
+    fetched_at TEXT NOT NULL
+);
+CREATE INDEX idx_archives_video ON archives(video_id, kind);
+
+CREATE TABLE chapters (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    start_time DOUBLE PRECISION NOT NULL,
+    title TEXT NOT NULL,
+    UNIQUE(video_id, start_time)
+);
+
+CREATE TABLE eras (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    sort_order BIGINT NOT NULL DEFAULT 0
+);
+
+CREATE TABLE regions (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    parent_id BIGINT REFERENCES regions(id) DEFERRABLE
+);
+
+CREATE TABLE video_eras (
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    era_id BIGINT NOT NULL REFERENCES eras(id) DEFERRABLE,
+    PRIMARY KEY (video_id, era_id)
+);
+
+CREATE TABLE video_regions (
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    region_id BIGINT NOT NULL REFERENCES regions(id) DEFERRABLE,
+    PRIMARY KEY (video_id, region_id)
+);
+CREATE INDEX idx_video_eras_era ON video_eras(era_id);
+CREATE INDEX idx_video_regions_region ON video_regions(region_id);
+
+CREATE TABLE topics (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE video_topics (
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    topic_id BIGINT NOT NULL REFERENCES topics(id) DEFERRABLE,
+    PRIMARY KEY (video_id, topic_id)
+);
+CREATE INDEX idx_video_topics_topic ON video_topics(topic_id);
+
+CREATE TABLE collections (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT
+);
+
+CREATE TABLE video_collections (
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    collection_id BIGINT NOT NULL REFERENCES collections(id) DEFERRABLE,
+    PRIMARY KEY (video_id, collection_id)
+);
+CREATE INDEX idx_video_collections_collection ON video_collections(collection_id);
+
+CREATE TABLE notes (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    timestamp DOUBLE PRECISION,
+    text TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX idx_notes_video ON notes(video_id);
+
+CREATE TABLE locations (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    lat DOUBLE PRECISION NOT NULL,
+    lon DOUBLE PRECISION NOT NULL
+);
+
+CREATE TABLE video_locations (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    location_id BIGINT NOT NULL REFERENCES locations(id) DEFERRABLE,
+    era_id BIGINT REFERENCES eras(id) DEFERRABLE,
+    topic_id BIGINT REFERENCES topics(id) DEFERRABLE,
+    timestamp DOUBLE PRECISION,
+    note TEXT
+);
+CREATE INDEX idx_video_locations_video ON video_locations(video_id);
+CREATE INDEX idx_video_locations_location ON video_locations(location_id);
+CREATE INDEX idx_video_locations_era ON video_locations(era_id);
+CREATE INDEX idx_video_locations_topic ON video_locations(topic_id);
+
+CREATE TABLE saved_searches (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    query TEXT,
+    era TEXT,
+    region TEXT,
+    topic TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE claims (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    text TEXT NOT NULL,
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    timestamp DOUBLE PRECISION,
+    source_quote TEXT NOT NULL,
+    category TEXT NOT NULL,
+    confidence TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    archived_at TEXT
+);
+CREATE INDEX idx_claims_video ON claims(video_id);
+CREATE INDEX idx_claims_category ON claims(category);
+
+CREATE TABLE claim_links (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    source_claim_id BIGINT NOT NULL REFERENCES claims(id) ON DELETE CASCADE DEFERRABLE,
+    target_claim_id BIGINT NOT NULL REFERENCES claims(id) ON DELETE CASCADE DEFERRABLE,
+    link_type TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE(source_claim_id, target_claim_id, link_type)
+);
+CREATE INDEX idx_claim_links_source ON claim_links(source_claim_id);
+CREATE INDEX idx_claim_links_target ON claim_links(target_claim_id);
+
+CREATE TABLE transcript_layers (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    layer BIGINT NOT NULL,
+    content TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE(video_id, layer)
+);
+CREATE INDEX idx_transcript_layers_video ON transcript_layers(video_id);
+
+CREATE TABLE transcript_chunks (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    chunk_index BIGINT NOT NULL,
+    start_time DOUBLE PRECISION NOT NULL,
+    end_time DOUBLE PRECISION NOT NULL,
+    text TEXT NOT NULL,
+    token_count BIGINT NOT NULL,
+    overlap_with_previous BIGINT NOT NULL DEFAULT 0,
+    UNIQUE(video_id, chunk_index)
+);
+CREATE INDEX idx_transcript_chunks_video ON transcript_chunks(video_id);
+
+CREATE TABLE embeddings (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    source_type TEXT NOT NULL,
+    source_id TEXT NOT NULL,
+    model TEXT NOT NULL,
+    vector_json TEXT NOT NULL,
+    dimensions BIGINT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE(source_type, source_id, model)
+);
+CREATE INDEX idx_embeddings_source ON embeddings(source_type, source_id);
+CREATE INDEX idx_embeddings_model ON embeddings(model);
+
+CREATE TABLE cyclical_indicators (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    claim_id BIGINT REFERENCES claims(id) DEFERRABLE,
+    indicator_type TEXT NOT NULL,
+    entity TEXT NOT NULL,
+    era_id BIGINT REFERENCES eras(id) DEFERRABLE,
+    description TEXT NOT NULL,
+    timestamp DOUBLE PRECISION,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX idx_cyclical_indicators_video ON cyclical_indicators(video_id);
+CREATE INDEX idx_cyclical_indicators_type ON cyclical_indicators(indicator_type);
+CREATE INDEX idx_cyclical_indicators_entity ON cyclical_indicators(entity);
+
+CREATE TABLE causal_relations (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    cause_claim_id BIGINT NOT NULL REFERENCES claims(id) ON DELETE CASCADE DEFERRABLE,
+    effect_claim_id BIGINT NOT NULL REFERENCES claims(id) ON DELETE CASCADE DEFERRABLE,
+    loop_type TEXT NOT NULL,
+    strength TEXT NOT NULL,
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    UNIQUE(cause_claim_id, effect_claim_id)
+);
+CREATE INDEX idx_causal_relations_cause ON causal_relations(cause_claim_id);
+CREATE INDEX idx_causal_relations_effect ON causal_relations(effect_claim_id);
+CREATE INDEX idx_causal_relations_loop ON causal_relations(loop_type);
+
+CREATE TABLE idea_transmissions (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    idea TEXT NOT NULL,
+    source_entity TEXT NOT NULL,
+    target_entity TEXT NOT NULL,
+    transmission_type TEXT NOT NULL,
+    era_id BIGINT REFERENCES eras(id) DEFERRABLE,
+    region_id BIGINT REFERENCES regions(id) DEFERRABLE,
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    claim_id BIGINT REFERENCES claims(id) DEFERRABLE,
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX idx_idea_transmissions_video ON idea_transmissions(video_id);
+CREATE INDEX idx_idea_transmissions_type ON idea_transmissions(transmission_type);
+CREATE INDEX idx_idea_transmissions_idea ON idea_transmissions(idea);
+
+CREATE TABLE geopolitical_entities (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    name TEXT NOT NULL,
+    era_id BIGINT NOT NULL REFERENCES eras(id) DEFERRABLE,
+    position TEXT NOT NULL,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    UNIQUE(name, era_id)
+);
+CREATE INDEX idx_geopolitical_entities_era ON geopolitical_entities(era_id);
+CREATE INDEX idx_geopolitical_entities_position ON geopolitical_entities(position);
+
+CREATE TABLE surplus_flows (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    from_entity_id BIGINT NOT NULL REFERENCES geopolitical_entities(id) ON DELETE CASCADE DEFERRABLE,
+    to_entity_id BIGINT NOT NULL REFERENCES geopolitical_entities(id) ON DELETE CASCADE DEFERRABLE,
+    commodity TEXT NOT NULL,
+    era_id BIGINT NOT NULL REFERENCES eras(id) DEFERRABLE,
+    video_id TEXT REFERENCES videos(id) DEFERRABLE,
+    claim_id BIGINT REFERENCES claims(id) DEFERRABLE,
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX idx_surplus_flows_from ON surplus_flows(from_entity_id);
+CREATE INDEX idx_surplus_flows_to ON surplus_flows(to_entity_id);
+CREATE INDEX idx_surplus_flows_era ON surplus_flows(era_id);
+
+CREATE TABLE temporal_observations (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    claim_id BIGINT NOT NULL REFERENCES claims(id) ON DELETE CASCADE DEFERRABLE,
+    timescale TEXT NOT NULL,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    UNIQUE(claim_id, timescale)
+);
+CREATE INDEX idx_temporal_observations_claim ON temporal_observations(claim_id);
+CREATE INDEX idx_temporal_observations_timescale ON temporal_observations(timescale);
+
+CREATE TABLE mocs (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    title TEXT NOT NULL UNIQUE,
+    description TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE moc_claims (
+    moc_id BIGINT NOT NULL REFERENCES mocs(id) ON DELETE CASCADE DEFERRABLE,
+    claim_id BIGINT NOT NULL REFERENCES claims(id) ON DELETE CASCADE DEFERRABLE,
+    sort_order BIGINT NOT NULL DEFAULT 0,
+    added_at TEXT NOT NULL,
+    PRIMARY KEY (moc_id, claim_id)
+);
+
+CREATE TABLE moc_hierarchy (
+    parent_moc_id BIGINT NOT NULL REFERENCES mocs(id) ON DELETE CASCADE DEFERRABLE,
+    child_moc_id BIGINT NOT NULL REFERENCES mocs(id) ON DELETE CASCADE DEFERRABLE,
+    PRIMARY KEY (parent_moc_id, child_moc_id)
+);
+CREATE INDEX idx_moc_claims_moc ON moc_claims(moc_id);
+CREATE INDEX idx_moc_claims_claim ON moc_claims(claim_id);
+
+CREATE TABLE research_questions (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    question TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'active',
+    parent_question_id BIGINT REFERENCES research_questions(id) DEFERRABLE,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE question_evidence (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    question_id BIGINT NOT NULL REFERENCES research_questions(id) ON DELETE CASCADE DEFERRABLE,
+    claim_id BIGINT REFERENCES claims(id) ON DELETE CASCADE DEFERRABLE,
+    video_id TEXT REFERENCES videos(id) DEFERRABLE,
+    relevance TEXT,
+    added_at TEXT NOT NULL
+);
+CREATE INDEX idx_research_questions_status ON research_questions(status);
+CREATE INDEX idx_research_questions_parent ON research_questions(parent_question_id);
+CREATE INDEX idx_question_evidence_question ON question_evidence(question_id);
+CREATE INDEX idx_question_evidence_claim ON question_evidence(claim_id);
+
+CREATE TABLE question_suggestions (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    question TEXT NOT NULL UNIQUE,
+    reason TEXT NOT NULL,
+    claim_ids_json TEXT NOT NULL,
+    video_ids_json TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE detected_patterns (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    pattern_type TEXT NOT NULL,
+    description TEXT NOT NULL,
+    video_ids_json TEXT NOT NULL,
+    claim_ids_json TEXT NOT NULL,
+    confidence DOUBLE PRECISION NOT NULL,
+    detected_at TEXT NOT NULL
+);
+CREATE INDEX idx_detected_patterns_type ON detected_patterns(pattern_type);
+
+CREATE TABLE claim_access (
+    claim_id BIGINT PRIMARY KEY REFERENCES claims(id) ON DELETE CASCADE DEFERRABLE,
+    last_accessed TEXT NOT NULL
+);
+
+CREATE TABLE claim_schedule (
+    claim_id BIGINT PRIMARY KEY REFERENCES claims(id) ON DELETE CASCADE DEFERRABLE,
+    interval_days DOUBLE PRECISION NOT NULL,
+    ease DOUBLE PRECISION NOT NULL,
+    repetitions BIGINT NOT NULL DEFAULT 0,
+    lapses BIGINT NOT NULL DEFAULT 0,
+    due_at TEXT NOT NULL,
+    last_reviewed TEXT NOT NULL
+);
+CREATE INDEX idx_claim_schedule_due ON claim_schedule(due_at);
+
+CREATE TABLE ai_processing_queue (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    video_id TEXT NOT NULL UNIQUE REFERENCES videos(id) DEFERRABLE,
+    status TEXT NOT NULL DEFAULT 'pending',
+    priority BIGINT NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    started_at TEXT,
+    completed_at TEXT,
+    error_message TEXT,
+    claims_extracted BIGINT NOT NULL DEFAULT 0
+);
+CREATE INDEX idx_ai_queue_status ON ai_processing_queue(status);
+CREATE INDEX idx_ai_queue_priority ON ai_processing_queue(priority DESC);
+
+CREATE TABLE queue_history (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    status TEXT NOT NULL,
+    detail TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX idx_queue_history_video ON queue_history(video_id);
+
+CREATE TABLE sources (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    title TEXT NOT NULL,
+    author TEXT,
+    source_type TEXT NOT NULL,
+    year BIGINT,
+    url TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE video_sources (
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    source_id BIGINT NOT NULL REFERENCES sources(id) DEFERRABLE,
+    timestamp DOUBLE PRECISION,
+    context TEXT,
+    PRIMARY KEY (video_id, source_id)
+);
+
+CREATE TABLE claim_sources (
+    claim_id BIGINT NOT NULL REFERENCES claims(id) DEFERRABLE,
+    source_id BIGINT NOT NULL REFERENCES sources(id) DEFERRABLE,
+    PRIMARY KEY (claim_id, source_id)
+);
+
+CREATE TABLE scholars (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    field TEXT,
+    era TEXT,
+    contribution TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE video_scholars (
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    scholar_id BIGINT NOT NULL REFERENCES scholars(id) DEFERRABLE,
+    timestamp DOUBLE PRECISION,
+    context TEXT,
+    PRIMARY KEY (video_id, scholar_id)
+);
+
+CREATE TABLE claim_scholars (
+    claim_id BIGINT NOT NULL REFERENCES claims(id) DEFERRABLE,
+    scholar_id BIGINT NOT NULL REFERENCES scholars(id) DEFERRABLE,
+    attribution_type TEXT,
+    PRIMARY KEY (claim_id, scholar_id)
+);
+
+CREATE TABLE visuals (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    timestamp DOUBLE PRECISION NOT NULL,
+    visual_type TEXT NOT NULL,
+    description TEXT NOT NULL,
+    significance TEXT,
+    location_id BIGINT REFERENCES locations(id) DEFERRABLE,
+    era_id BIGINT REFERENCES eras(id) DEFERRABLE,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE claim_visuals (
+    claim_id BIGINT NOT NULL REFERENCES claims(id) DEFERRABLE,
+    visual_id BIGINT NOT NULL REFERENCES visuals(id) DEFERRABLE,
+    PRIMARY KEY (claim_id, visual_id)
+);
+
+CREATE TABLE terms (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    term TEXT NOT NULL,
+    definition TEXT NOT NULL,
+    domain TEXT,
+    video_id TEXT REFERENCES videos(id) DEFERRABLE,
+    timestamp DOUBLE PRECISION,
+    scholar_id BIGINT REFERENCES scholars(id) DEFERRABLE,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE claim_terms (
+    claim_id BIGINT NOT NULL REFERENCES claims(id) DEFERRABLE,
+    term_id BIGINT NOT NULL REFERENCES terms(id) DEFERRABLE,
+    PRIMARY KEY (claim_id, term_id)
+);
+
+CREATE TABLE evidence (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    evidence_type TEXT NOT NULL,
+    description TEXT NOT NULL,
+    location_id BIGINT REFERENCES locations(id) DEFERRABLE,
+    era_id BIGINT REFERENCES eras(id) DEFERRABLE,
+    timestamp DOUBLE PRECISION,
+    source_id BIGINT REFERENCES sources(id) DEFERRABLE,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE claim_evidence (
+    claim_id BIGINT NOT NULL REFERENCES claims(id) DEFERRABLE,
+    evidence_id BIGINT NOT NULL REFERENCES evidence(id) DEFERRABLE,
+    relationship TEXT,
+    PRIMARY KEY (claim_id, evidence_id)
+);
+
+CREATE TABLE quotes (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    text TEXT NOT NULL,
+    speaker TEXT,
+    scholar_id BIGINT REFERENCES scholars(id) DEFERRABLE,
+    timestamp DOUBLE PRECISION,
+    context TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE pending_quotes (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    video_id TEXT NOT NULL REFERENCES videos(id) DEFERRABLE,
+    text TEXT NOT NULL,
+    speaker TEXT,
+    timestamp DOUBLE PRECISION,
+    reason TEXT NOT NULL,
+    context TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX idx_sources_title ON sources(title);
+CREATE INDEX idx_scholars_name ON scholars(name);
+CREATE INDEX idx_visuals_video ON visuals(video_id);
+CREATE INDEX idx_terms_term ON terms(term);
+CREATE INDEX idx_evidence_video ON evidence(video_id);
+CREATE INDEX idx_quotes_video ON quotes(video_id);
+CREATE INDEX idx_pending_quotes_video ON pending_quotes(video_id);
+
+-- Stands in for the search_index FTS5 table; rows are written by the application
+CREATE TABLE search_index (
+    video_id TEXT NOT NULL,
+    title TEXT,
+    description TEXT,
+    transcript TEXT,
+    document tsvector GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', coalesce(title, '')), 'A') ||
+        setweight(to_tsvector('english', coalesce(description, '')), 'B') ||
+        setweight(to_tsvector('english', coalesce(transcript, '')), 'C')
+    ) STORED
+);
+CREATE INDEX idx_search_index_video ON search_index(video_id);
+CREATE INDEX idx_search_index_document ON search_index USING gin (document);
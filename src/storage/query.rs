@@ -0,0 +1,350 @@
+// Translate user search input into FTS5 query syntax. Supports "quoted phrases",
+// AND / OR / NOT (uppercase, as in FTS5), prefix wildcards (bronz*),
+// NEAR(famine revolt, 10) proximity groups and parentheses. Anything else is treated as plain words, so stray punctuation
+// can no longer cause FTS syntax errors.
+
+// FTS5's distance when NEAR() doesn't give one
+pub const DEFAULT_NEAR_DISTANCE: usize = 10;
+
+/// Words or phrases that must all occur within `distance` tokens of each other.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Near {
+    pub terms: Vec<String>,
+    pub distance: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Term { text: String, prefix: bool },
+    Near { terms: Vec<(String, bool)>, distance: usize },
+    Op(&'static str),
+    Open,
+    Close,
+}
+
+/// Build a safe FTS5 MATCH expression. Returns an empty string when the input
+/// has nothing searchable in it.
+pub fn to_fts_query(input: &str) -> String {
+    render(&clean(tokenize(input)))
+}
+
+/// Widen terms that name an alias group to any name in the group, e.g. Byzantium
+/// becomes ("byzantium" OR "eastern roman empire"). Unquoted words are matched as a
+/// run, so Persian Empire finds the same group as "Persian Empire". Prefix terms and
+/// NEAR groups are left alone. Returns the query in the same syntax it accepts.
+pub fn expand_aliases(input: &str, groups: &[Vec<String>]) -> String {
+    let groups: Vec<Vec<String>> = groups
+        .iter()
+        .map(|g| g.iter().map(|name| normalize(name).to_lowercase()).filter(|n| !n.is_empty()).collect())
+        .collect();
+    let longest = groups.iter().flatten().map(|n| n.split(' ').count()).max().unwrap_or(0);
+    let tokens = clean(tokenize(input));
+    if longest == 0 {
+        return render(&tokens);
+    }
+
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        // Longest run of plain terms starting here that names a group
+        let mut found = None;
+        let mut words: Vec<&str> = Vec::new();
+        for token in &tokens[i..] {
+            match token {
+                Token::Term { text, prefix: false } if words.len() < longest => words.push(text),
+                _ => break,
+            }
+            let name = words.join(" ").to_lowercase();
+            if let Some(group) = groups.iter().find(|g| g.contains(&name)) {
+                found = Some((words.len(), name, group));
+            }
+        }
+
+        match found {
+            Some((len, name, group)) => {
+                out.push(Token::Open);
+                out.push(Token::Term { text: name.clone(), prefix: false });
+                for other in group.iter().filter(|n| **n != name) {
+                    out.push(Token::Op("OR"));
+                    out.push(Token::Term { text: other.clone(), prefix: false });
+                }
+                out.push(Token::Close);
+                i += len;
+            }
+            None => {
+                out.push(tokens[i].clone());
+                i += 1;
+            }
+        }
+    }
+    render(&clean(out))
+}
+
+fn render(tokens: &[Token]) -> String {
+    let mut out = String::new();
+    for token in tokens {
+        if !out.is_empty() && !out.ends_with('(') && *token != Token::Close {
+            out.push(' ');
+        }
+        match token {
+            Token::Term { text, prefix } => {
+                out.push('"');
+                out.push_str(text);
+                out.push('"');
+                if *prefix {
+                    out.push('*');
+                }
+            }
+            Token::Near { terms, distance } => {
+                let quoted: Vec<String> = terms
+                    .iter()
+                    .map(|(text, prefix)| format!("\"{}\"{}", text, if *prefix { "*" } else { "" }))
+                    .collect();
+                out.push_str(&format!("NEAR({}, {})", quoted.join(" "), distance));
+            }
+            Token::Op(op) => out.push_str(op),
+            Token::Open => out.push('('),
+            Token::Close => out.push(')'),
+        }
+    }
+    out
+}
+
+/// The same query as a PostgreSQL tsquery, for `to_tsquery()`. tsquery has no
+/// unordered proximity operator, so a NEAR group only requires all of its terms.
+pub fn to_tsquery(input: &str) -> String {
+    let mut out = String::new();
+    let mut after_operand = false;
+    for token in clean(tokenize(input)) {
+        if after_operand && matches!(token, Token::Term { .. } | Token::Near { .. } | Token::Open) {
+            out.push_str(" & ");
+        }
+        after_operand = matches!(token, Token::Term { .. } | Token::Near { .. } | Token::Close);
+        match token {
+            Token::Term { text, prefix } => out.push_str(&ts_phrase(&text, prefix)),
+            Token::Near { terms, .. } => {
+                let terms: Vec<String> = terms.iter().map(|(text, prefix)| ts_phrase(text, *prefix)).collect();
+                out.push_str(&format!("({})", terms.join(" & ")));
+            }
+            Token::Op("OR") => out.push_str(" | "),
+            Token::Op("NOT") => out.push_str(" & !"),
+            Token::Op(_) => out.push_str(" & "),
+            Token::Open => out.push('('),
+            Token::Close => out.push(')'),
+        }
+    }
+    out
+}
+
+// Words of a phrase must follow each other; a prefix applies to the last one
+fn ts_phrase(text: &str, prefix: bool) -> String {
+    let words: Vec<String> = text.split(' ').map(|word| format!("'{}'", word)).collect();
+    let phrase = format!("{}{}", words.join(" <-> "), if prefix { ":*" } else { "" });
+    if words.len() > 1 {
+        format!("({})", phrase)
+    } else {
+        phrase
+    }
+}
+
+/// Lowercased words and phrases a matching text should contain, for picking out
+/// transcript segments. Negated terms, NEAR groups and wildcard markers are left out.
+pub fn match_terms(input: &str) -> Vec<String> {
+    let tokens = clean(tokenize(input));
+    let mut terms = Vec::new();
+    let mut negated = false;
+    for token in tokens {
+        match token {
+            Token::Op("NOT") => negated = true,
+            Token::Term { text, .. } => {
+                if !negated {
+                    terms.push(text.to_lowercase());
+                }
+                negated = false;
+            }
+            Token::Near { .. } => negated = false,
+            _ => {}
+        }
+    }
+    terms
+}
+
+/// The NEAR groups in a query (lowercased), for picking out the transcript passages
+/// where their terms occur close together. Negated groups are left out.
+pub fn near_groups(input: &str) -> Vec<Near> {
+    let tokens = clean(tokenize(input));
+    let mut groups = Vec::new();
+    let mut negated = false;
+    for token in tokens {
+        match token {
+            Token::Op("NOT") => negated = true,
+            Token::Near { terms, distance } => {
+                if !negated {
+                    groups.push(Near {
+                        terms: terms.into_iter().map(|(text, _)| text.to_lowercase()).collect(),
+                        distance,
+                    });
+                }
+                negated = false;
+            }
+            Token::Term { .. } => negated = false,
+            _ => {}
+        }
+    }
+    groups
+}
+
+/// Build a NEAR() expression from a "famine,revolt,10" list: terms, then an optional distance.
+pub fn near_expression(spec: &str) -> String {
+    let mut parts: Vec<&str> = spec.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()).collect();
+    let distance = match parts.last().and_then(|p| p.parse::<usize>().ok()) {
+        Some(d) if parts.len() > 1 => {
+            parts.pop();
+            d
+        }
+        _ => DEFAULT_NEAR_DISTANCE,
+    };
+    let terms: Vec<String> = parts.iter().map(|p| format!("\"{}\"", p.replace('"', ""))).collect();
+    format!("NEAR({}, {})", terms.join(" "), distance)
+}
+
+fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            // An unterminated quote runs to the end of the input
+            let phrase: String = chars.by_ref().take_while(|&c| c != '"').collect();
+            let prefix = chars.peek() == Some(&'*');
+            if prefix {
+                chars.next();
+            }
+            push_term(&mut tokens, &phrase, prefix);
+        } else if c == '(' {
+            chars.next();
+            tokens.push(Token::Open);
+        } else if c == ')' {
+            chars.next();
+            tokens.push(Token::Close);
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' || c == '(' || c == ')' {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            match word.as_str() {
+                "NEAR" if chars.peek() == Some(&'(') => {
+                    chars.next();
+                    let inner: String = chars.by_ref().take_while(|&c| c != ')').collect();
+                    push_near(&mut tokens, &inner);
+                }
+                "AND" => tokens.push(Token::Op("AND")),
+                "OR" => tokens.push(Token::Op("OR")),
+                "NOT" => tokens.push(Token::Op("NOT")),
+                _ => {
+                    let prefix = word.ends_with('*');
+                    push_term(&mut tokens, word.trim_end_matches('*'), prefix);
+                }
+            }
+        }
+    }
+    tokens
+}
+
+/// Reduce a term to letters, digits and single spaces; drop it if nothing is left.
+fn push_term(tokens: &mut Vec<Token>, raw: &str, prefix: bool) {
+    let text = normalize(raw);
+    if !text.is_empty() {
+        tokens.push(Token::Term { text, prefix });
+    }
+}
+
+fn normalize(raw: &str) -> String {
+    raw.chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parse the inside of NEAR(...): terms, then an optional ", N" distance. A group with
+/// fewer than two terms is just those terms.
+fn push_near(tokens: &mut Vec<Token>, inner: &str) {
+    let (list, distance) = match inner.rsplit_once(',') {
+        Some((list, d)) => (list, d.trim().parse().unwrap_or(DEFAULT_NEAR_DISTANCE)),
+        None => (inner, DEFAULT_NEAR_DISTANCE),
+    };
+    let mut terms = Vec::new();
+    for token in tokenize(list) {
+        if let Token::Term { text, prefix } = token {
+            terms.push((text, prefix));
+        }
+    }
+    if terms.len() < 2 {
+        tokens.extend(terms.into_iter().map(|(text, prefix)| Token::Term { text, prefix }));
+    } else {
+        tokens.push(Token::Near { terms, distance });
+    }
+}
+
+/// Drop operators without operands on both sides, empty or unbalanced groups,
+/// and make the AND next to a group explicit.
+fn clean(tokens: Vec<Token>) -> Vec<Token> {
+    let balanced = {
+        let mut depth = 0i32;
+        tokens.iter().all(|t| {
+            match t {
+                Token::Open => depth += 1,
+                Token::Close => depth -= 1,
+                _ => {}
+            }
+            depth >= 0
+        }) && depth == 0
+    };
+
+    let mut out: Vec<Token> = Vec::new();
+    for token in tokens {
+        let after_operand = matches!(out.last(), Some(Token::Term { .. }) | Some(Token::Near { .. }) | Some(Token::Close));
+        match token {
+            Token::Op(_) if !after_operand => {}
+            Token::Open | Token::Close if !balanced => {}
+            Token::Open => {
+                if after_operand {
+                    out.push(Token::Op("AND"));
+                }
+                out.push(Token::Open);
+            }
+            Token::Close => {
+                while matches!(out.last(), Some(Token::Op(_))) {
+                    out.pop();
+                }
+                if matches!(out.last(), Some(Token::Open)) {
+                    out.pop();
+                    // The AND inserted before the now-empty group
+                    if matches!(out.last(), Some(Token::Op("AND"))) {
+                        out.pop();
+                    }
+                } else {
+                    out.push(Token::Close);
+                }
+            }
+            Token::Term { .. } | Token::Near { .. } if matches!(out.last(), Some(Token::Close)) => {
+                out.push(Token::Op("AND"));
+                out.push(token);
+            }
+            other => out.push(other),
+        }
+    }
+    while matches!(out.last(), Some(Token::Op(_)) | Some(Token::Open)) {
+        out.pop();
+    }
+    out
+}
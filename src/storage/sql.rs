@@ -0,0 +1,397 @@
+//! The connection `Database` runs its SQL through: the slice of rusqlite's API it uses,
+//! answered by SQLite or, with the postgres feature, a PostgreSQL server. Values are
+//! rusqlite's types on both, so rows convert the same way whichever backend produced them.
+
+use rusqlite::types::{FromSql, FromSqlError, ToSql, ToSqlOutput, Value, ValueRef};
+#[cfg(feature = "postgres")]
+use super::postgres::Client;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// From SQLite, or a value that doesn't convert to what was asked for (on either backend)
+    Sqlite(rusqlite::Error),
+    #[cfg(feature = "postgres")]
+    Postgres(postgres::Error),
+    /// BEGIN inside an open transaction; SQLite refuses these itself
+    #[cfg(feature = "postgres")]
+    NestedTransaction,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Sqlite(e) => e.fmt(f),
+            #[cfg(feature = "postgres")]
+            Error::Postgres(e) => match e.as_db_error() {
+                Some(db) => write!(f, "{}", db.message()),
+                None => e.fmt(f),
+            },
+            #[cfg(feature = "postgres")]
+            Error::NestedTransaction => f.write_str("cannot start a transaction within a transaction"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Sqlite(e) => Some(e),
+            #[cfg(feature = "postgres")]
+            Error::Postgres(e) => Some(e),
+            #[cfg(feature = "postgres")]
+            Error::NestedTransaction => None,
+        }
+    }
+}
+
+impl From<rusqlite::Error> for Error {
+    fn from(e: rusqlite::Error) -> Self {
+        Error::Sqlite(e)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl From<postgres::Error> for Error {
+    fn from(e: postgres::Error) -> Self {
+        Error::Postgres(e)
+    }
+}
+
+/// `query_row` results where no row is an answer rather than an error.
+pub trait OptionalExtension<T> {
+    fn optional(self) -> Result<Option<T>>;
+}
+
+impl<T> OptionalExtension<T> for Result<T> {
+    fn optional(self) -> Result<Option<T>> {
+        match self {
+            Ok(value) => Ok(Some(value)),
+            Err(Error::Sqlite(rusqlite::Error::QueryReturnedNoRows)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Which SQL the connection speaks, for the few statements written per backend.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dialect {
+    Sqlite,
+    Postgres,
+}
+
+/// Statement parameters: `params![...]`, `[]` or `params_from_iter(...)`.
+pub trait Params {
+    fn into_values(self) -> Result<Vec<Value>>;
+}
+
+fn to_value(param: &dyn ToSql) -> Result<Value> {
+    Ok(match param.to_sql()? {
+        ToSqlOutput::Borrowed(value) => value.into(),
+        ToSqlOutput::Owned(value) => value,
+        _ => return Err(rusqlite::Error::ToSqlConversionFailure("unsupported parameter type".into()).into()),
+    })
+}
+
+impl Params for [&(dyn ToSql + Send + Sync); 0] {
+    fn into_values(self) -> Result<Vec<Value>> {
+        Ok(Vec::new())
+    }
+}
+
+impl Params for &[&dyn ToSql] {
+    fn into_values(self) -> Result<Vec<Value>> {
+        self.iter().map(|p| to_value(*p)).collect()
+    }
+}
+
+pub struct ParamsFromIter<I>(I);
+
+/// Parameters from any iterator of values, for statements built at runtime.
+pub fn params_from_iter<I>(iter: I) -> ParamsFromIter<I>
+where
+    I: IntoIterator,
+    I::Item: ToSql,
+{
+    ParamsFromIter(iter)
+}
+
+impl<I> Params for ParamsFromIter<I>
+where
+    I: IntoIterator,
+    I::Item: ToSql,
+{
+    fn into_values(self) -> Result<Vec<Value>> {
+        self.0.into_iter().map(|p| to_value(&p)).collect()
+    }
+}
+
+pub trait RowIndex {
+    fn index(self) -> usize;
+}
+
+impl RowIndex for usize {
+    fn index(self) -> usize {
+        self
+    }
+}
+
+/// One result row, read in full.
+pub struct Row {
+    values: Vec<Value>,
+}
+
+impl Row {
+    fn from_sqlite(row: &rusqlite::Row) -> Result<Self> {
+        let values = (0..row.as_ref().column_count())
+            .map(|i| row.get_ref(i).map(Value::from))
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(Self { values })
+    }
+
+    pub fn get_ref<I: RowIndex>(&self, idx: I) -> Result<ValueRef<'_>> {
+        let idx = idx.index();
+        match self.values.get(idx) {
+            Some(value) => Ok(ValueRef::from(value)),
+            None => Err(rusqlite::Error::InvalidColumnIndex(idx).into()),
+        }
+    }
+
+    pub fn get<I: RowIndex, T: FromSql>(&self, idx: I) -> Result<T> {
+        let idx = idx.index();
+        let value = self.get_ref(idx)?;
+        T::column_result(value).map_err(|e| {
+            match e {
+                FromSqlError::InvalidType => rusqlite::Error::InvalidColumnType(idx, String::new(), value.data_type()),
+                FromSqlError::OutOfRange(n) => rusqlite::Error::IntegralValueOutOfRange(idx, n),
+                FromSqlError::Other(err) => rusqlite::Error::FromSqlConversionFailure(idx, value.data_type(), err),
+                other => rusqlite::Error::FromSqlConversionFailure(idx, value.data_type(), Box::new(other)),
+            }
+            .into()
+        })
+    }
+}
+
+pub struct Rows<'s> {
+    source: RowSource<'s>,
+    current: Option<Row>,
+}
+
+enum RowSource<'s> {
+    Sqlite(rusqlite::Rows<'s>),
+    // PostgreSQL results arrive whole
+    #[cfg_attr(not(feature = "postgres"), allow(dead_code))]
+    Buffered(std::vec::IntoIter<Vec<Value>>),
+}
+
+impl Rows<'_> {
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<Option<&Row>> {
+        self.current = match &mut self.source {
+            RowSource::Sqlite(rows) => rows.next()?.map(Row::from_sqlite).transpose()?,
+            RowSource::Buffered(rows) => rows.next().map(|values| Row { values }),
+        };
+        Ok(self.current.as_ref())
+    }
+}
+
+pub struct MappedRows<'s, F> {
+    rows: Rows<'s>,
+    map: F,
+}
+
+impl<T, F: FnMut(&Row) -> Result<T>> Iterator for MappedRows<'_, F> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.rows.next() {
+            Ok(Some(row)) => Some((self.map)(row)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+pub struct Statement<'c> {
+    inner: StatementInner<'c>,
+}
+
+enum StatementInner<'c> {
+    Sqlite(rusqlite::Statement<'c>),
+    SqliteCached(rusqlite::CachedStatement<'c>),
+    #[cfg(feature = "postgres")]
+    Postgres(&'c Client, String),
+}
+
+impl<'c> Statement<'c> {
+    fn sqlite(&mut self) -> Option<&mut rusqlite::Statement<'c>> {
+        match &mut self.inner {
+            StatementInner::Sqlite(stmt) => Some(stmt),
+            StatementInner::SqliteCached(stmt) => Some(stmt),
+            #[cfg(feature = "postgres")]
+            StatementInner::Postgres(..) => None,
+        }
+    }
+
+    pub fn query<P: Params>(&mut self, params: P) -> Result<Rows<'_>> {
+        let values = params.into_values()?;
+        #[cfg(feature = "postgres")]
+        if let StatementInner::Postgres(client, sql) = &self.inner {
+            let rows = client.query(sql, values)?;
+            return Ok(Rows { source: RowSource::Buffered(rows.into_iter()), current: None });
+        }
+        let stmt = self.sqlite().expect("SQLite statement");
+        let rows = stmt.query(rusqlite::params_from_iter(values))?;
+        Ok(Rows { source: RowSource::Sqlite(rows), current: None })
+    }
+
+    pub fn query_map<T, P: Params, F: FnMut(&Row) -> Result<T>>(&mut self, params: P, f: F) -> Result<MappedRows<'_, F>> {
+        Ok(MappedRows { rows: self.query(params)?, map: f })
+    }
+
+    pub fn query_row<T, P: Params, F: FnOnce(&Row) -> Result<T>>(&mut self, params: P, f: F) -> Result<T> {
+        let mut rows = self.query(params)?;
+        match rows.next()? {
+            Some(row) => f(row),
+            None => Err(rusqlite::Error::QueryReturnedNoRows.into()),
+        }
+    }
+
+    pub fn exists<P: Params>(&mut self, params: P) -> Result<bool> {
+        Ok(self.query(params)?.next()?.is_some())
+    }
+
+    pub fn execute<P: Params>(&mut self, params: P) -> Result<usize> {
+        let values = params.into_values()?;
+        #[cfg(feature = "postgres")]
+        if let StatementInner::Postgres(client, sql) = &self.inner {
+            return client.execute(sql, values);
+        }
+        Ok(self.sqlite().expect("SQLite statement").execute(rusqlite::params_from_iter(values))?)
+    }
+
+    pub fn column_names(&mut self) -> Result<Vec<String>> {
+        #[cfg(feature = "postgres")]
+        if let StatementInner::Postgres(client, sql) = &self.inner {
+            return client.column_names(sql);
+        }
+        Ok(self.sqlite().expect("SQLite statement").column_names().iter().map(|c| c.to_string()).collect())
+    }
+}
+
+/// Open until committed; dropping it rolls back. Derefs to the connection it runs on.
+pub struct Transaction<'c> {
+    conn: &'c Conn,
+    done: bool,
+}
+
+impl Transaction<'_> {
+    pub fn commit(mut self) -> Result<()> {
+        self.done = true;
+        self.conn.end_transaction(true)
+    }
+}
+
+impl std::ops::Deref for Transaction<'_> {
+    type Target = Conn;
+
+    fn deref(&self) -> &Conn {
+        self.conn
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            let _ = self.conn.end_transaction(false);
+        }
+    }
+}
+
+pub enum Conn {
+    Sqlite(rusqlite::Connection),
+    #[cfg(feature = "postgres")]
+    Postgres(Client),
+}
+
+impl Conn {
+    pub fn dialect(&self) -> Dialect {
+        match self {
+            Conn::Sqlite(_) => Dialect::Sqlite,
+            #[cfg(feature = "postgres")]
+            Conn::Postgres(_) => Dialect::Postgres,
+        }
+    }
+
+    /// The SQLite connection, for settings and extensions PostgreSQL has no counterpart to.
+    pub fn sqlite(&self) -> Option<&rusqlite::Connection> {
+        match self {
+            Conn::Sqlite(conn) => Some(conn),
+            #[cfg(feature = "postgres")]
+            Conn::Postgres(_) => None,
+        }
+    }
+
+    pub fn prepare(&self, sql: &str) -> Result<Statement<'_>> {
+        let inner = match self {
+            Conn::Sqlite(conn) => StatementInner::Sqlite(conn.prepare(sql)?),
+            #[cfg(feature = "postgres")]
+            Conn::Postgres(client) => StatementInner::Postgres(client, sql.to_string()),
+        };
+        Ok(Statement { inner })
+    }
+
+    pub fn prepare_cached(&self, sql: &str) -> Result<Statement<'_>> {
+        let inner = match self {
+            Conn::Sqlite(conn) => StatementInner::SqliteCached(conn.prepare_cached(sql)?),
+            #[cfg(feature = "postgres")]
+            Conn::Postgres(client) => StatementInner::Postgres(client, sql.to_string()),
+        };
+        Ok(Statement { inner })
+    }
+
+    pub fn execute<P: Params>(&self, sql: &str, params: P) -> Result<usize> {
+        self.prepare(sql)?.execute(params)
+    }
+
+    pub fn query_row<T, P: Params, F: FnOnce(&Row) -> Result<T>>(&self, sql: &str, params: P, f: F) -> Result<T> {
+        self.prepare(sql)?.query_row(params, f)
+    }
+
+    pub fn execute_batch(&self, sql: &str) -> Result<()> {
+        match self {
+            Conn::Sqlite(conn) => Ok(conn.execute_batch(sql)?),
+            #[cfg(feature = "postgres")]
+            Conn::Postgres(client) => client.batch_execute(sql),
+        }
+    }
+
+    /// ID of the latest row inserted into a table with an `id` key.
+    pub fn last_insert_rowid(&self) -> i64 {
+        match self {
+            Conn::Sqlite(conn) => conn.last_insert_rowid(),
+            #[cfg(feature = "postgres")]
+            Conn::Postgres(client) => client.last_insert_id(),
+        }
+    }
+
+    /// Begin a transaction through a shared reference. Nesting one fails on both backends:
+    /// SQLite refuses the inner BEGIN, and the PostgreSQL client checks before sending it.
+    pub fn unchecked_transaction(&self) -> Result<Transaction<'_>> {
+        match self {
+            Conn::Sqlite(_) => self.execute_batch("BEGIN DEFERRED")?,
+            #[cfg(feature = "postgres")]
+            Conn::Postgres(client) => client.begin()?,
+        }
+        Ok(Transaction { conn: self, done: false })
+    }
+
+    fn end_transaction(&self, commit: bool) -> Result<()> {
+        match self {
+            Conn::Sqlite(conn) => Ok(conn.execute_batch(if commit { "COMMIT" } else { "ROLLBACK" })?),
+            #[cfg(feature = "postgres")]
+            Conn::Postgres(client) => client.end(commit),
+        }
+    }
+}
@@ -0,0 +1,1123 @@
+//! The storage interface the CLI and web server work against. `Database`
+//! implements it over SQLite, or PostgreSQL in builds with the postgres feature;
+//! open one with `Database::open_with` and pass it around as `&dyn Storage`.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use super::models::{Video, Transcript, TranscriptSegment, Chapter, Subscription, Archive, Attachment, Availability, VideoAvailability, SearchResult, TextMatch, Era, Region, RegionCount, Topic, Collection, Note, Location, MapPin, Cited, TimelineKind, Timeline, AutoTags, SavedSearch, SearchHistoryEntry, Alias, AdvancedSearchResponse, MetadataFacets, RankingProfile, ReportEntry, GeoJsonCollection, Claim, ClaimCategory, Confidence, ClaimStatus, ClaimLink, LinkType, ClaimWithLinks, ClaimGraph, ClaimGraphFilter, ClaimGraphPage, ClaimPathStep, ClaimMetrics, ClaimProvenance, ContradictionGroup, DuplicateClaims, TranscriptLayer, TranscriptChunk, Embedding, EmbeddingSource, SimilarityResult, ProjectionPoint, HybridSearchResult, Fusion, EmbeddingStats, EmbeddingModel, EmbeddingModelStats, EmbeddingQueueItem, CyclicalType, CyclicalIndicator, LoopType, RelationStrength, CausalRelation, CausalChain, TransmissionType, IdeaTransmission, SystemPosition, GeopoliticalEntity, SurplusFlow, BraudelTimescale, TemporalObservation, FrameworkStats, MapOfContent, MocWithClaims, QuestionStatus, ResearchQuestion, QuestionWithEvidence, SuggestedQuestion, DetectedPattern, PatternProvenance, PatternType, ReviewQueue, ClaimSchedule, SynthesisStats, LlmUsage, UsageGrouping, LlmUsageSummary, TokenScope, ApiToken, ProcessingStatus, AIProcessingQueue, QueueEvent, VideoStatus, SourceType, Source, Scholar, ClaimScholar, VisualType, Visual, Term, EvidenceType, Evidence, Quote, PendingQuote, AuditEntry, IntegrityIssue, MigrationStatus, SearchResponse};
+
+pub trait Storage: Send {
+    fn is_read_only(&self) -> Result<bool>;
+
+    /// Changes whenever data may have changed: writes from this process, or
+    /// another process touching the database (e.g. a CLI import while serving).
+    fn generation(&self) -> u64;
+
+    /// Apply pending migrations in version order, each in its own transaction.
+    /// Returns the migrations applied by this call.
+    fn migrate(&self) -> Result<Vec<MigrationStatus>>;
+
+    /// Every known migration, with when it was applied to this database (if at all).
+    fn migration_status(&self) -> Result<Vec<MigrationStatus>>;
+
+    fn insert_video(&self, video: &Video) -> Result<()>;
+    fn insert_transcript(&self, transcript: &Transcript) -> Result<()>;
+
+    /// Replace a transcript's segments, regenerating full_text and the search index.
+    fn update_transcript_segments(&self, video_id: &str, segments: &[TranscriptSegment]) -> Result<bool>;
+
+    // Subscription operations
+
+    fn add_subscription(&self, url: &str, name: Option<&str>) -> Result<Subscription>;
+    fn list_subscriptions(&self) -> Result<Vec<Subscription>>;
+    fn mark_subscription_checked(&self, id: i64) -> Result<()>;
+    fn delete_subscription(&self, id: i64) -> Result<bool>;
+
+    /// Store a raw payload. Skipped when identical to the latest one of the same kind.
+    fn save_archive(&self, archive: &Archive) -> Result<Option<i64>>;
+
+    fn get_archives(&self, video_id: &str) -> Result<Vec<Archive>>;
+    fn get_latest_archive(&self, video_id: &str, kind: &str) -> Result<Option<Archive>>;
+
+    // Settings
+
+    /// Run `f` in a transaction, committing if it succeeds and rolling back otherwise.
+    /// Callers holding a `dyn Storage` use the generic `with_transaction` wrapper.
+    fn transaction(&self, f: &mut dyn FnMut(&dyn Storage) -> Result<()>) -> Result<()>;
+
+    fn get_setting(&self, key: &str) -> Result<Option<String>>;
+    fn set_setting(&self, key: &str, value: &str) -> Result<()>;
+    fn delete_setting(&self, key: &str) -> Result<bool>;
+
+    /// Ranking profile from the search.weights and search.recency_boost settings.
+    fn ranking_profile(&self) -> Result<RankingProfile>;
+
+    // Attachments
+
+    /// Save an attachment. Pass `data` to store the file in the database; otherwise `attachment.path` is referenced.
+    fn add_attachment(&self, attachment: &Attachment, data: Option<&[u8]>) -> Result<i64>;
+
+    fn get_attachment(&self, id: i64) -> Result<Option<Attachment>>;
+    fn get_video_attachments(&self, video_id: &str) -> Result<Vec<Attachment>>;
+    fn get_claim_attachments(&self, claim_id: i64) -> Result<Vec<Attachment>>;
+
+    /// File contents, from the database or from disk for referenced attachments.
+    fn read_attachment(&self, id: i64) -> Result<Option<Vec<u8>>>;
+
+    fn delete_attachment(&self, id: i64) -> Result<bool>;
+
+    /// Replace a video's chapters.
+    fn save_chapters(&self, video_id: &str, chapters: &[Chapter]) -> Result<()>;
+
+    fn get_chapters(&self, video_id: &str) -> Result<Vec<Chapter>>;
+    fn rebuild_search_index(&self) -> Result<usize>;
+
+    /// Label recorded with every change made through this connection from now on.
+    fn set_audit_source(&self, source: &str) -> Result<()>;
+
+    /// A value that changes whenever any of `tables` does, for validating cached responses.
+    /// Audited tables are tracked through their latest audit log entry, so writes that
+    /// bypass this program's connections go unnoticed; the unaudited ones that matter
+    /// are tracked by row count and their timestamp column.
+    fn watermark(&self, tables: &[&str]) -> Result<u64>;
+
+    fn list_audit_log(
+        &self,
+        entity_type: Option<&str>,
+        entity_id: Option<&str>,
+        source: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<AuditEntry>>;
+
+    // Integrity checks (engine doctor)
+
+    /// Run SQLite's own integrity check and look for dangling references, embeddings
+    /// whose source is gone, and search indexes out of step with their tables.
+    fn check_integrity(&self) -> Result<Vec<IntegrityIssue>>;
+
+    /// Fix what check_integrity reports as fixable. Dangling required references are
+    /// deleted, optional ones cleared, orphaned embeddings dropped and both search
+    /// indexes rebuilt. Returns the number of rows removed or cleared.
+    fn repair_integrity(&self) -> Result<usize>;
+
+    // Full export/import
+
+    /// Highest applied migration version.
+    fn schema_version(&self) -> Result<i64>;
+
+    /// Bytes in the database's pages; changes still in the write-ahead log aren't counted.
+    fn size_bytes(&self) -> Result<i64>;
+
+    /// Rows in each of the main knowledge tables, archived videos and claims included.
+    fn entity_counts(&self) -> Result<Vec<(&'static str, i64)>>;
+
+    /// Tables holding user data, in creation order so parents come before children.
+    /// Search indexes are derived and rebuilt on import, so they're left out.
+    fn exportable_tables(&self) -> Result<Vec<String>>;
+
+    /// Stream every row of a table as a column -> value JSON object. BLOBs are hex-encoded as {"$blob": "..."}.
+    fn export_table_rows(
+        &self,
+        table: &str,
+        emit: &mut dyn FnMut(serde_json::Map<String, serde_json::Value>) -> Result<()>,
+    ) -> Result<usize>;
+
+    /// Load exported rows in a single transaction, then rebuild the search indexes.
+    /// Columns this schema doesn't know are dropped and missing ones take their defaults,
+    /// so archives from older or newer versions still load. Returns rows imported per table;
+    /// rows for unknown tables are counted under "(skipped)".
+    fn import_table_rows(
+        &self,
+        records: &mut dyn Iterator<Item = Result<(String, serde_json::Map<String, serde_json::Value>)>>,
+    ) -> Result<Vec<(String, usize)>>;
+
+    fn get_video(&self, id: &str) -> Result<Option<Video>>;
+    fn get_transcript(&self, video_id: &str) -> Result<Option<Transcript>>;
+    fn list_videos(&self) -> Result<Vec<Video>>;
+
+    /// Videos added (or fetched again) after `since`, oldest first.
+    fn list_videos_added_after(&self, since: DateTime<Utc>) -> Result<Vec<Video>>;
+
+    fn list_videos_by_language(&self, language: &str) -> Result<Vec<Video>>;
+    fn set_video_availability(&self, video_id: &str, availability: Availability, detail: Option<&str>) -> Result<bool>;
+    fn get_video_availability(&self, video_id: &str) -> Result<Option<VideoAvailability>>;
+
+    /// Videos whose last check found them private or removed.
+    fn list_unavailable_videos(&self) -> Result<Vec<VideoAvailability>>;
+
+    /// Suggest a respelling of `query` using words that occur in transcripts, or None
+    /// when every word is already known or nothing close enough exists.
+    fn suggest_spelling(&self, query: &str) -> Result<Option<String>>;
+
+    /// Full-text search over notes, quotes, term definitions and transcript summaries.
+    /// `kinds` narrows to some of "note", "quote", "term", "summary"; empty means all.
+    /// With `collection`, only entries belonging to a video in that collection match.
+    fn search_text(&self, query: &str, kinds: &[&str], collection: Option<&str>) -> Result<Vec<TextMatch>>;
+
+    fn search(&self, query: &str) -> Result<Vec<(Video, String)>>;
+    fn search_with_timestamps(&self, query: &str) -> Result<Vec<SearchResult>>;
+
+    // ========================================================================
+    // Unified Fuzzy Search
+    // ========================================================================
+
+    /// Unified search across all entity types with fuzzy matching
+    fn unified_search(
+        &self,
+        query: &str,
+        types: Option<&[&str]>,
+        video_filter: Option<&str>,
+        limit: usize,
+        fuzzy_threshold: f64,
+    ) -> Result<SearchResponse>;
+
+    fn list_eras(&self) -> Result<Vec<Era>>;
+    fn get_era_by_name(&self, name: &str) -> Result<Option<Era>>;
+    fn get_era(&self, id: i64) -> Result<Option<Era>>;
+    fn create_era(&self, name: &str, sort_order: i32, start_year: Option<i32>, end_year: Option<i32>) -> Result<Era>;
+
+    /// Sort order that places a new era after all existing ones.
+    fn next_era_sort_order(&self) -> Result<i32>;
+
+    // Region operations
+
+    fn list_regions(&self) -> Result<Vec<Region>>;
+    fn get_region_by_name(&self, name: &str) -> Result<Option<Region>>;
+    fn create_region(&self, name: &str, parent_id: Option<i64>) -> Result<Region>;
+
+    // Taxonomy cleanup
+
+    /// Rename an era, region or topic in place; every tag pointing at it follows.
+    fn rename_tag(&self, table: &str, id: i64, name: &str) -> Result<bool>;
+
+    /// Fold `source_id` into `target_id`: every table referencing the source is repointed
+    /// at the target (duplicate join rows are dropped) and the source is deleted, all in
+    /// one transaction.
+    fn merge_tags(&self, table: &str, source_id: i64, target_id: i64) -> Result<()>;
+
+    // Video tagging
+
+    fn tag_video_era(&self, video_id: &str, era_id: i64) -> Result<()>;
+    fn tag_video_region(&self, video_id: &str, region_id: i64) -> Result<()>;
+    fn untag_video_era(&self, video_id: &str, era_id: i64) -> Result<bool>;
+    fn untag_video_region(&self, video_id: &str, region_id: i64) -> Result<bool>;
+    fn get_video_eras(&self, video_id: &str) -> Result<Vec<Era>>;
+    fn get_video_regions(&self, video_id: &str) -> Result<Vec<Region>>;
+
+    /// With `include_subregions`, a region filter also matches videos tagged with any region nested under it.
+    fn browse_videos(&self, era: Option<&str>, region: Option<&str>, include_subregions: bool) -> Result<Vec<Video>>;
+
+    // Topic operations
+
+    fn list_topics(&self) -> Result<Vec<Topic>>;
+    fn get_topic_by_name(&self, name: &str) -> Result<Option<Topic>>;
+    fn create_topic(&self, name: &str) -> Result<Topic>;
+    fn get_or_create_topic(&self, name: &str) -> Result<Topic>;
+    fn tag_video_topic(&self, video_id: &str, topic_id: i64) -> Result<()>;
+    fn untag_video_topic(&self, video_id: &str, topic_id: i64) -> Result<bool>;
+    fn get_video_topics(&self, video_id: &str) -> Result<Vec<Topic>>;
+    fn browse_by_topic(&self, topic_name: &str) -> Result<Vec<Video>>;
+
+    // Collection operations
+
+    fn list_collections(&self) -> Result<Vec<Collection>>;
+    fn get_collection_by_name(&self, name: &str) -> Result<Option<Collection>>;
+    fn create_collection(&self, name: &str, description: Option<&str>) -> Result<Collection>;
+    fn add_video_to_collection(&self, video_id: &str, collection_id: i64) -> Result<()>;
+    fn remove_video_from_collection(&self, video_id: &str, collection_id: i64) -> Result<bool>;
+
+    /// Rename a collection or change its description; None leaves a field as it is.
+    fn update_collection(&self, id: i64, name: Option<&str>, description: Option<Option<&str>>) -> Result<bool>;
+
+    /// Delete a collection; its videos stay in the knowledge base.
+    fn delete_collection(&self, id: i64) -> Result<bool>;
+
+    fn get_collection_videos(&self, collection_name: &str) -> Result<Vec<Video>>;
+    fn get_video_collections(&self, video_id: &str) -> Result<Vec<Collection>>;
+
+    // Note operations
+
+    fn add_note(&self, video_id: &str, timestamp: Option<f64>, text: &str) -> Result<Note>;
+    fn get_video_notes(&self, video_id: &str) -> Result<Vec<Note>>;
+    fn get_note(&self, note_id: i64) -> Result<Option<Note>>;
+
+    /// Change a note's text or timestamp; None leaves a field as it is.
+    fn update_note(&self, note_id: i64, text: Option<&str>, timestamp: Option<Option<f64>>) -> Result<bool>;
+
+    fn delete_note(&self, note_id: i64) -> Result<bool>;
+
+    // Location operations
+
+    fn list_locations(&self) -> Result<Vec<Location>>;
+    fn get_location_by_name(&self, name: &str) -> Result<Option<Location>>;
+    fn create_location(&self, name: &str, lat: f64, lon: f64) -> Result<Location>;
+    fn get_or_create_location(&self, name: &str, lat: f64, lon: f64) -> Result<Location>;
+
+    /// Pin a place an LLM found mentioned in a video. A known location of that name keeps
+    /// its coordinates; otherwise the given ones are used and the location is flagged with
+    /// `extracted_by`. Returns the location and whether the video was newly pinned there.
+    fn add_extracted_location(
+        &self,
+        video_id: &str,
+        name: &str,
+        coordinates: Option<(f64, f64)>,
+        timestamp: Option<f64>,
+        note: Option<&str>,
+        extracted_by: &str,
+    ) -> Result<Option<(Location, bool)>>;
+
+    fn add_video_location(
+        &self,
+        video_id: &str,
+        location_id: i64,
+        era_id: Option<i64>,
+        topic_id: Option<i64>,
+        timestamp: Option<f64>,
+        note: Option<&str>,
+    ) -> Result<i64>;
+
+    fn get_map_pins(&self, era: Option<&str>, topic: Option<&str>) -> Result<Vec<MapPin>>;
+
+    /// Videos, claims and framework entries laid out by year, keeping those that overlap
+    /// `from`..=`to` when given. Claims dated directly use their own years; other claims
+    /// use their eras, or their video's when they have none. An era missing one bound is
+    /// placed at the other; eras with neither are left off.
+    fn timeline(&self, kinds: &[TimelineKind], from: Option<i32>, to: Option<i32>) -> Result<Timeline>;
+
+    // Auto-tagging from title and description
+
+    fn suggest_tags(&self, video_id: &str) -> Result<AutoTags>;
+    fn apply_auto_tags(&self, video_id: &str) -> Result<AutoTags>;
+
+    /// Tag a video with eras, regions and topics given by name. Names match existing
+    /// ones case-insensitively or through an alias; unknown names are created only
+    /// with `allow_new`. Returns the names applied and the unknown names skipped.
+    fn apply_tags(&self, video_id: &str, tags: &AutoTags, allow_new: bool) -> Result<(AutoTags, AutoTags)>;
+
+    fn get_video_locations(&self, video_id: &str) -> Result<Vec<MapPin>>;
+
+    // Phase 5: Research Tools
+
+    /// Videos matching a query and/or metadata filters. Dates bound the upload date (YYYY-MM-DD, inclusive).
+    #[allow(clippy::too_many_arguments)]
+    fn advanced_search(
+        &self,
+        query: Option<&str>,
+        era: Option<&str>,
+        region: Option<&str>,
+        topic: Option<&str>,
+        collection: Option<&str>,
+        channel: Option<&str>,
+        date_from: Option<&str>,
+        date_to: Option<&str>,
+    ) -> Result<AdvancedSearchResponse>;
+
+    /// Count hits per era, region, topic and channel. `hits` pairs a video with how many
+    /// results it contributed; the counts are summed over the tag join tables.
+    fn metadata_facets(&self, hits: &[(String, usize)]) -> Result<MetadataFacets>;
+
+    // Saved search operations
+
+    fn save_search(&self, search: &SavedSearch) -> Result<SavedSearch>;
+    fn list_saved_searches(&self) -> Result<Vec<SavedSearch>>;
+    fn get_saved_search(&self, name: &str) -> Result<Option<SavedSearch>>;
+    fn delete_saved_search(&self, name: &str) -> Result<bool>;
+
+    // Aliases
+
+    /// Returns false if the alias was already recorded.
+    fn add_alias(&self, name: &str, alias: &str) -> Result<bool>;
+
+    /// Remove one alias of a name, or all of them when `alias` is None.
+    fn remove_alias(&self, name: &str, alias: Option<&str>) -> Result<usize>;
+
+    /// Aliases of every name, or of names and aliases matching `name`.
+    fn list_aliases(&self, name: Option<&str>) -> Result<Vec<Alias>>;
+
+    /// Each name with its aliases, for widening search queries.
+    fn alias_groups(&self) -> Result<Vec<Vec<String>>>;
+
+    // Search history
+
+    fn record_search(&self, entry: &SearchHistoryEntry) -> Result<i64>;
+
+    /// Most recent first.
+    fn list_search_history(&self, limit: usize) -> Result<Vec<SearchHistoryEntry>>;
+
+    fn get_search_history(&self, id: i64) -> Result<Option<SearchHistoryEntry>>;
+    fn clear_search_history(&self) -> Result<usize>;
+
+    // Report generation
+
+    fn report_by_era(&self) -> Result<Vec<ReportEntry>>;
+
+    /// Video counts per region, rolled up so a video tagged "Athens" also counts toward "Greece".
+    fn report_by_region(&self) -> Result<Vec<ReportEntry>>;
+
+    /// Every region with its direct video count and the count including all subregions, ordered by name.
+    fn region_video_counts(&self) -> Result<Vec<RegionCount>>;
+
+    fn count_region_tagged_videos(&self) -> Result<i64>;
+    fn report_by_topic(&self) -> Result<Vec<ReportEntry>>;
+
+    // Export functions
+
+    fn export_collection_markdown(&self, collection_name: &str) -> Result<Option<String>>;
+    fn export_map_geojson(&self, era: Option<&str>, topic: Option<&str>) -> Result<GeoJsonCollection>;
+    fn get_summary_stats(&self) -> Result<(i64, i64, i64, i64, i64, i64, i64, i64, i64)>;
+
+    // Phase 6: Claim Extraction & Atomic Notes
+    // Claim operations
+
+    fn create_claim(
+        &self,
+        text: &str,
+        video_id: &str,
+        timestamp: Option<f64>,
+        source_quote: &str,
+        category: ClaimCategory,
+        confidence: Confidence,
+    ) -> Result<Claim>;
+
+    fn tag_claim_topic(&self, claim_id: i64, topic_id: i64) -> Result<()>;
+    fn untag_claim_topic(&self, claim_id: i64, topic_id: i64) -> Result<bool>;
+    fn tag_claim_era(&self, claim_id: i64, era_id: i64) -> Result<()>;
+    fn untag_claim_era(&self, claim_id: i64, era_id: i64) -> Result<bool>;
+
+    /// Topics tagged on the claim itself (not those of its video).
+    fn get_claim_topics(&self, claim_id: i64) -> Result<Vec<Topic>>;
+
+    /// Eras tagged on the claim itself (not those of its video).
+    fn get_claim_eras(&self, claim_id: i64) -> Result<Vec<Era>>;
+
+    /// Claims tagged with the topic directly or coming from a video tagged with it.
+    fn list_claims_by_topic(&self, topic: &str) -> Result<Vec<Claim>>;
+
+    /// Claims tagged with the era directly or coming from a video tagged with it.
+    fn list_claims_by_era(&self, era: &str) -> Result<Vec<Claim>>;
+
+    fn set_claim_status(&self, id: i64, status: ClaimStatus) -> Result<bool>;
+
+    /// Set the years a claim is about (negative for BCE), or clear them with None.
+    fn set_claim_dates(&self, id: i64, start: Option<i32>, end: Option<i32>) -> Result<bool>;
+
+    /// Dated claims whose years overlap `from`..=`to` (either end open), earliest first.
+    fn list_dated_claims(&self, from: Option<i32>, to: Option<i32>) -> Result<Vec<Claim>>;
+
+    fn get_claim(&self, id: i64) -> Result<Option<Claim>>;
+    fn list_claims_for_video(&self, video_id: &str) -> Result<Vec<Claim>>;
+    fn list_claims_by_category(&self, category: ClaimCategory) -> Result<Vec<Claim>>;
+    fn list_all_claims(&self) -> Result<Vec<Claim>>;
+
+    /// The highest claim id, or 0 when there are no claims.
+    fn max_claim_id(&self) -> Result<i64>;
+
+    /// Claims with ids above `after`, lowest first.
+    fn list_claims_after(&self, after: i64, limit: usize) -> Result<Vec<Claim>>;
+
+    fn get_all_claims_limited(&self, limit: usize) -> Result<Vec<Claim>>;
+
+    /// Pairs of active claims embedded with the same model whose cosine similarity is at
+    /// least `threshold`, most similar first.
+    fn find_duplicate_claims(&self, threshold: f32) -> Result<Vec<DuplicateClaims>>;
+
+    /// Fold a duplicate claim into another. Every reference to the duplicate (links, MOC
+    /// membership, question evidence, framework entries, ...) is re-pointed at the kept
+    /// claim, then the duplicate is deleted. Returns the number of references moved.
+    fn merge_claims(&self, keep: i64, duplicate: i64) -> Result<usize>;
+
+    fn delete_claim(&self, id: i64) -> Result<bool>;
+
+    // Soft delete: archived rows stay in the database but drop out of lists, search and the graph
+
+    /// Archive a video along with its claims. Returns false if missing or already archived.
+    fn archive_video(&self, id: &str) -> Result<bool>;
+
+    /// Restore a video and the claims that were archived with it.
+    fn unarchive_video(&self, id: &str) -> Result<bool>;
+
+    fn archive_claim(&self, id: i64) -> Result<bool>;
+    fn unarchive_claim(&self, id: i64) -> Result<bool>;
+
+    /// Archived videos, oldest first, optionally only those archived before a cutoff.
+    fn list_archived_videos(&self, before: Option<DateTime<Utc>>) -> Result<Vec<(Video, DateTime<Utc>)>>;
+
+    /// Archived claims whose video is still live (the rest go with their video).
+    fn list_archived_claims(&self, before: Option<DateTime<Utc>>) -> Result<Vec<(Claim, DateTime<Utc>)>>;
+
+    /// Permanently delete an archived video, its claims and everything referencing them.
+    fn purge_video(&self, id: &str) -> Result<bool>;
+
+    /// Permanently delete an archived claim and everything referencing it.
+    fn purge_claim(&self, id: i64) -> Result<bool>;
+
+    /// Update the given fields of a claim. `timestamp` is `Some(None)` to clear it.
+    fn update_claim(
+        &self,
+        id: i64,
+        text: Option<&str>,
+        source_quote: Option<&str>,
+        category: Option<ClaimCategory>,
+        confidence: Option<Confidence>,
+        timestamp: Option<Option<f64>>,
+    ) -> Result<bool>;
+
+    // Claim link operations
+
+    fn create_claim_link(
+        &self,
+        source_claim_id: i64,
+        target_claim_id: i64,
+        link_type: LinkType,
+    ) -> Result<ClaimLink>;
+
+    fn delete_claim_link(&self, source_id: i64, target_id: i64) -> Result<bool>;
+    fn get_claim_with_links(&self, claim_id: i64) -> Result<Option<ClaimWithLinks>>;
+    fn get_unlinked_claims(&self) -> Result<Vec<Claim>>;
+    fn get_claim_link_count(&self, claim_id: i64) -> Result<i64>;
+    fn list_all_claim_links(&self) -> Result<Vec<ClaimLink>>;
+
+    /// Claims matching every given filter, with the links and causal relations among them.
+    /// An era matches claims tagged with it directly or through their video.
+    fn claim_graph(&self, video_id: Option<&str>, moc_id: Option<i64>, era: Option<&str>) -> Result<ClaimGraph>;
+
+    /// One page of the claims matching `filter`, for drawing: two queries, one for the
+    /// claims with their link counts and one for the links among them.
+    fn claim_graph_page(&self, filter: &ClaimGraphFilter, limit: usize, offset: usize) -> Result<ClaimGraphPage>;
+
+    /// Every `contradicts` link between live claims, grouped by the topics of the claims'
+    /// videos (`by` = "topic") or the MOCs holding either claim ("moc"). Pairs with no
+    /// topic or MOC are grouped under "Unsorted", listed last.
+    fn list_contradictions(&self, by: &str) -> Result<Vec<ContradictionGroup>>;
+
+    /// Shortest chain of links or causal relations from one claim to another, followed
+    /// in either direction, up to `max_depth` hops. None if they aren't connected.
+    fn claim_path(&self, from: i64, to: i64, max_depth: usize) -> Result<Option<Vec<ClaimPathStep>>>;
+
+    /// Claims within `depth` hops of a claim (links and causal relations, either direction),
+    /// with their distance, nearest first. The claim itself is not included.
+    fn claim_neighborhood(&self, id: i64, depth: usize) -> Result<Vec<(Claim, usize)>>;
+
+    /// Record how a claim was extracted, replacing any earlier record. Resets review.
+    fn set_claim_provenance(
+        &self,
+        claim_id: i64,
+        provider: Option<&str>,
+        model: Option<&str>,
+        prompt_version: Option<&str>,
+        run_id: Option<&str>,
+    ) -> Result<()>;
+
+    fn get_claim_provenance(&self, claim_id: i64) -> Result<Option<ClaimProvenance>>;
+
+    /// Provenance for every AI-extracted claim, by claim ID.
+    fn list_claim_provenance(&self) -> Result<HashMap<i64, ClaimProvenance>>;
+
+    /// Mark an AI-extracted claim as checked by a person (or unchecked again).
+    /// Returns false for claims without provenance, which were added by hand.
+    fn mark_claim_reviewed(&self, claim_id: i64, reviewed: bool) -> Result<bool>;
+
+    /// Claim IDs from one extraction run, for reviewing or re-running it as a batch.
+    fn list_claim_ids_for_run(&self, run_id: &str) -> Result<Vec<i64>>;
+
+    /// Recompute centrality and components over all live claims, replacing the stored
+    /// metrics. Returns them sorted by betweenness, highest first.
+    fn compute_claim_metrics(&self) -> Result<Vec<ClaimMetrics>>;
+
+    /// Stored metrics from the last `compute_claim_metrics`, by claim ID.
+    fn list_claim_metrics(&self) -> Result<HashMap<i64, ClaimMetrics>>;
+
+    /// Live claims that bridge otherwise distant parts of the graph, by stored betweenness.
+    fn list_bridging_claims(&self, limit: usize) -> Result<Vec<(Claim, ClaimMetrics)>>;
+
+    // Transcript layer operations (progressive summarization)
+
+    fn save_transcript_layer(&self, video_id: &str, layer: u8, content: &str) -> Result<TranscriptLayer>;
+    fn get_transcript_layer(&self, video_id: &str, layer: u8) -> Result<Option<TranscriptLayer>>;
+    fn list_transcript_layers(&self, video_id: &str) -> Result<Vec<TranscriptLayer>>;
+
+    // Transcript chunk operations (intelligent chunking)
+
+    fn save_transcript_chunks(&self, video_id: &str, chunks: &[TranscriptChunk]) -> Result<()>;
+    fn get_transcript_chunks(&self, video_id: &str) -> Result<Vec<TranscriptChunk>>;
+    fn has_chunks(&self, video_id: &str) -> Result<bool>;
+
+    // Claim statistics
+
+    fn get_claim_stats(&self) -> Result<(i64, i64, i64)>;
+
+    // Phase 7: Semantic Search & Embeddings
+
+    fn save_embedding(
+        &self,
+        source_type: EmbeddingSource,
+        source_id: &str,
+        model: &str,
+        vector: &[f32],
+    ) -> Result<Embedding>;
+
+    fn get_embedding(
+        &self,
+        source_type: EmbeddingSource,
+        source_id: &str,
+        model: &str,
+    ) -> Result<Option<Embedding>>;
+
+    fn list_embeddings_by_model(&self, model: &str) -> Result<Vec<Embedding>>;
+
+    /// Make `to` the model queries are embedded with and, unless `keep_old`, drop the
+    /// vectors from `from`, in one transaction. Returns the number of embeddings removed.
+    fn swap_embedding_model(&self, from: &str, to: &str, provider: &str, keep_old: bool) -> Result<usize>;
+
+    fn list_embeddings_by_type(&self, source_type: EmbeddingSource) -> Result<Vec<Embedding>>;
+    fn list_all_embeddings(&self) -> Result<Vec<Embedding>>;
+    fn delete_embedding(&self, source_type: EmbeddingSource, source_id: &str) -> Result<bool>;
+    fn has_embedding(&self, source_type: EmbeddingSource, source_id: &str) -> Result<bool>;
+
+    fn find_similar(
+        &self,
+        query_vector: &[f32],
+        source_type: Option<EmbeddingSource>,
+        model: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<(Embedding, f32)>>;
+
+    /// Re-rank similarity results by maximal marginal relevance: each pick trades its
+    /// similarity to the query against its similarity to what was already picked.
+    /// `lambda` = 1 keeps the plain ranking, lower values favour diversity.
+    fn diversify(
+        &self,
+        query_vector: &[f32],
+        candidates: Vec<(Embedding, f32)>,
+        limit: usize,
+        lambda: f32,
+    ) -> Vec<(Embedding, f32)>;
+
+    /// Embeddings furthest from every cluster centroid, with their cosine distance to the
+    /// nearest one. Each model is clustered separately (spherical k-means, `clusters`
+    /// centroids or about sqrt(n/2) by default); claims and chunks unless `source_type` is given.
+    fn embedding_outliers(
+        &self,
+        source_type: Option<EmbeddingSource>,
+        clusters: Option<usize>,
+        limit: usize,
+    ) -> Result<Vec<(Embedding, f32)>>;
+
+    /// Project embeddings of one model onto their first two principal components.
+    /// Uses the model with the most embeddings unless one is given.
+    fn embedding_projection(&self, source_type: Option<EmbeddingSource>, model: Option<&str>) -> Result<Vec<ProjectionPoint>>;
+
+    fn get_text_for_embedding(&self, embedding: &Embedding) -> Result<Option<String>>;
+
+    fn build_similarity_results(
+        &self,
+        similar: Vec<(Embedding, f32)>,
+    ) -> Result<Vec<SimilarityResult>>;
+
+    fn hybrid_search(
+        &self,
+        query: &str,
+        query_vector: Option<&[f32]>,
+        keyword_weight: f32,
+        semantic_weight: f32,
+        limit: usize,
+        fusion: Fusion,
+    ) -> Result<Vec<HybridSearchResult>>;
+
+    /// Remember the vector size a model produces. Fails if the model already
+    /// produced vectors of a different size, which would make them incomparable.
+    fn record_embedding_model(&self, model: &str, provider: Option<&str>, dimensions: usize) -> Result<()>;
+
+    fn get_embedding_model(&self, model: &str) -> Result<Option<EmbeddingModel>>;
+
+    /// The model query commands look up stored vectors with: the embeddings.default_model
+    /// setting, else the model with the most embeddings. None when nothing is embedded.
+    fn default_embedding_model(&self) -> Result<Option<String>>;
+
+    fn embedding_stats_by_model(&self) -> Result<Vec<EmbeddingModelStats>>;
+    fn list_embedding_models(&self) -> Result<Vec<EmbeddingModel>>;
+    fn get_embedding_stats(&self) -> Result<EmbeddingStats>;
+
+    /// Schedule an item for embedding. Queuing it again (e.g. after re-chunking)
+    /// clears any earlier failure.
+    fn queue_embedding(&self, source_type: EmbeddingSource, source_id: &str) -> Result<()>;
+
+    fn list_embedding_queue(&self) -> Result<Vec<EmbeddingQueueItem>>;
+    fn dequeue_embedding(&self, source_type: EmbeddingSource, source_id: &str) -> Result<bool>;
+
+    /// Keep a queued item for the next run, noting why it failed.
+    fn fail_queued_embedding(&self, source_type: EmbeddingSource, source_id: &str, error: &str) -> Result<bool>;
+
+    fn get_items_needing_embeddings(&self) -> Result<(Vec<String>, Vec<String>, Vec<i64>)>;
+
+    // Phase 8: Analytical Frameworks
+    // 8.1 Cyclical Indicator Operations
+
+    fn create_cyclical_indicator(
+        &self,
+        video_id: &str,
+        claim_id: Option<i64>,
+        indicator_type: CyclicalType,
+        entity: &str,
+        era_id: Option<i64>,
+        description: &str,
+        timestamp: Option<f64>,
+    ) -> Result<CyclicalIndicator>;
+
+    fn get_cyclical_indicator(&self, id: i64) -> Result<Option<CyclicalIndicator>>;
+    fn list_cyclical_indicators_by_type(&self, indicator_type: CyclicalType) -> Result<Vec<CyclicalIndicator>>;
+    fn list_cyclical_indicators_by_entity(&self, entity: &str) -> Result<Vec<CyclicalIndicator>>;
+    fn list_all_cyclical_indicators(&self) -> Result<Vec<CyclicalIndicator>>;
+    fn delete_cyclical_indicator(&self, id: i64) -> Result<bool>;
+
+    // 8.2 Causal Relation Operations
+
+    fn create_causal_relation(
+        &self,
+        cause_claim_id: i64,
+        effect_claim_id: i64,
+        loop_type: LoopType,
+        strength: RelationStrength,
+        video_id: &str,
+        notes: Option<&str>,
+    ) -> Result<CausalRelation>;
+
+    fn get_causal_relation(&self, id: i64) -> Result<Option<CausalRelation>>;
+    fn get_causal_relations_for_claim(&self, claim_id: i64) -> Result<Vec<CausalRelation>>;
+
+    /// Claims within `depth` causal relations of a claim, following them either way, and
+    /// the relations among those claims. None if the claim doesn't exist.
+    fn causal_chain(&self, claim_id: i64, depth: usize) -> Result<Option<CausalChain>>;
+
+    fn list_causal_relations_by_type(&self, loop_type: LoopType) -> Result<Vec<CausalRelation>>;
+    fn list_all_causal_relations(&self) -> Result<Vec<CausalRelation>>;
+    fn delete_causal_relation(&self, id: i64) -> Result<bool>;
+
+    // 8.3 Idea Transmission Operations
+
+    fn create_idea_transmission(
+        &self,
+        idea: &str,
+        source_entity: &str,
+        target_entity: &str,
+        transmission_type: TransmissionType,
+        era_id: Option<i64>,
+        region_id: Option<i64>,
+        video_id: &str,
+        claim_id: Option<i64>,
+        notes: Option<&str>,
+    ) -> Result<IdeaTransmission>;
+
+    fn get_idea_transmission(&self, id: i64) -> Result<Option<IdeaTransmission>>;
+    fn list_idea_transmissions_by_idea(&self, idea: &str) -> Result<Vec<IdeaTransmission>>;
+    fn list_idea_transmissions_by_type(&self, transmission_type: TransmissionType) -> Result<Vec<IdeaTransmission>>;
+    fn list_all_idea_transmissions(&self) -> Result<Vec<IdeaTransmission>>;
+    fn delete_idea_transmission(&self, id: i64) -> Result<bool>;
+
+    // 8.4 Geopolitical Entity Operations
+
+    fn create_geopolitical_entity(
+        &self,
+        name: &str,
+        era_id: i64,
+        position: SystemPosition,
+        notes: Option<&str>,
+    ) -> Result<GeopoliticalEntity>;
+
+    fn get_geopolitical_entity(&self, id: i64) -> Result<Option<GeopoliticalEntity>>;
+    fn get_geopolitical_entity_by_name(&self, name: &str, era_id: i64) -> Result<Option<GeopoliticalEntity>>;
+    fn list_geopolitical_entities_by_era(&self, era_id: i64) -> Result<Vec<GeopoliticalEntity>>;
+    fn list_geopolitical_entities_by_position(&self, position: SystemPosition) -> Result<Vec<GeopoliticalEntity>>;
+    fn list_all_geopolitical_entities(&self) -> Result<Vec<GeopoliticalEntity>>;
+    fn update_geopolitical_entity_position(&self, id: i64, position: SystemPosition) -> Result<bool>;
+    fn delete_geopolitical_entity(&self, id: i64) -> Result<bool>;
+
+    // Surplus Flow Operations
+
+    fn create_surplus_flow(
+        &self,
+        from_entity_id: i64,
+        to_entity_id: i64,
+        commodity: &str,
+        era_id: i64,
+        video_id: Option<&str>,
+        claim_id: Option<i64>,
+        notes: Option<&str>,
+    ) -> Result<SurplusFlow>;
+
+    fn get_surplus_flow(&self, id: i64) -> Result<Option<SurplusFlow>>;
+    fn list_surplus_flows_for_entity(&self, entity_id: i64) -> Result<Vec<SurplusFlow>>;
+    fn list_surplus_flows_by_era(&self, era_id: i64) -> Result<Vec<SurplusFlow>>;
+    fn list_all_surplus_flows(&self) -> Result<Vec<SurplusFlow>>;
+    fn delete_surplus_flow(&self, id: i64) -> Result<bool>;
+
+    // Temporal Observation Operations
+
+    fn create_temporal_observation(
+        &self,
+        claim_id: i64,
+        timescale: BraudelTimescale,
+        notes: Option<&str>,
+    ) -> Result<TemporalObservation>;
+
+    fn get_temporal_observation(&self, id: i64) -> Result<Option<TemporalObservation>>;
+    fn get_temporal_observations_for_claim(&self, claim_id: i64) -> Result<Vec<TemporalObservation>>;
+    fn list_temporal_observations_by_timescale(&self, timescale: BraudelTimescale) -> Result<Vec<TemporalObservation>>;
+    fn list_all_temporal_observations(&self) -> Result<Vec<TemporalObservation>>;
+    fn delete_temporal_observation(&self, id: i64) -> Result<bool>;
+
+    // Framework Statistics
+
+    fn get_framework_stats(&self) -> Result<FrameworkStats>;
+
+    // Phase 9: Synthesis & Pattern Detection
+    // 9.1 Maps of Content
+
+    fn create_moc(&self, title: &str, description: Option<&str>) -> Result<MapOfContent>;
+    fn get_moc(&self, id: i64) -> Result<Option<MapOfContent>>;
+    fn get_moc_by_title(&self, title: &str) -> Result<Option<MapOfContent>>;
+    fn list_mocs(&self) -> Result<Vec<MapOfContent>>;
+    fn update_moc(&self, id: i64, title: Option<&str>, description: Option<&str>) -> Result<bool>;
+    fn delete_moc(&self, id: i64) -> Result<bool>;
+    fn add_claim_to_moc(&self, moc_id: i64, claim_id: i64, sort_order: i32) -> Result<()>;
+    fn remove_claim_from_moc(&self, moc_id: i64, claim_id: i64) -> Result<bool>;
+
+    /// The sort order that puts a claim after every other in the MOC.
+    fn next_moc_sort_order(&self, moc_id: i64) -> Result<i32>;
+
+    /// Put a MOC's claims in the order given, numbering them from 0.
+    fn reorder_moc_claims(&self, moc_id: i64, claim_ids: &[i64]) -> Result<()>;
+
+    fn get_moc_claims(&self, moc_id: i64) -> Result<Vec<Claim>>;
+    fn add_sub_moc(&self, parent_id: i64, child_id: i64) -> Result<()>;
+    fn get_sub_mocs(&self, moc_id: i64) -> Result<Vec<MapOfContent>>;
+    fn get_moc_with_claims(&self, moc_id: i64) -> Result<Option<MocWithClaims>>;
+    fn get_moc_claim_count(&self, moc_id: i64) -> Result<i64>;
+
+    // 9.2 Research Questions
+
+    fn create_research_question(
+        &self,
+        question: &str,
+        parent_id: Option<i64>,
+        notes: Option<&str>,
+    ) -> Result<ResearchQuestion>;
+
+    fn get_research_question(&self, id: i64) -> Result<Option<ResearchQuestion>>;
+    fn list_research_questions(&self, status: Option<QuestionStatus>) -> Result<Vec<ResearchQuestion>>;
+    fn update_question_status(&self, id: i64, status: QuestionStatus) -> Result<bool>;
+    fn delete_research_question(&self, id: i64) -> Result<bool>;
+
+    fn add_evidence_to_question(
+        &self,
+        question_id: i64,
+        claim_id: Option<i64>,
+        video_id: Option<&str>,
+        relevance: Option<&str>,
+    ) -> Result<()>;
+
+    fn get_question_evidence_claims(&self, question_id: i64) -> Result<Vec<Claim>>;
+    fn get_question_evidence_videos(&self, question_id: i64) -> Result<Vec<Video>>;
+    fn get_sub_questions(&self, parent_id: i64) -> Result<Vec<ResearchQuestion>>;
+    fn get_question_with_evidence(&self, question_id: i64) -> Result<Option<QuestionWithEvidence>>;
+
+    // Suggested research questions
+
+    /// Scan for contradictions, thinly covered topics and linked claim clusters
+    /// outside any MOC, staging a question for each. Returns all pending suggestions.
+    fn generate_question_suggestions(&self) -> Result<Vec<SuggestedQuestion>>;
+
+    fn list_question_suggestions(&self, include_resolved: bool) -> Result<Vec<SuggestedQuestion>>;
+    fn get_question_suggestion(&self, id: i64) -> Result<Option<SuggestedQuestion>>;
+
+    /// Turn a pending suggestion into a research question with its evidence attached.
+    fn accept_question_suggestion(&self, id: i64) -> Result<Option<ResearchQuestion>>;
+
+    fn dismiss_question_suggestion(&self, id: i64) -> Result<bool>;
+
+    // 9.3 Pattern Detection
+
+    fn save_detected_pattern(
+        &self,
+        pattern_type: PatternType,
+        description: &str,
+        video_ids: &[String],
+        claim_ids: &[i64],
+        confidence: f32,
+    ) -> Result<DetectedPattern>;
+
+    fn get_detected_pattern(&self, id: i64) -> Result<Option<DetectedPattern>>;
+    fn list_detected_patterns(&self, pattern_type: Option<PatternType>) -> Result<Vec<DetectedPattern>>;
+    fn delete_detected_pattern(&self, id: i64) -> Result<bool>;
+
+    /// Record how a pattern was detected, replacing any earlier record.
+    fn set_pattern_provenance(&self, provenance: &PatternProvenance) -> Result<()>;
+
+    /// Provenance for every LLM-detected pattern, by pattern ID.
+    fn list_pattern_provenance(&self) -> Result<HashMap<i64, PatternProvenance>>;
+
+    /// Split claims into clusters of related claims, at most `max_size` each, for pattern
+    /// detection. Claims with embeddings are grouped by k-means over the model that covers
+    /// most of them; the rest are grouped by category.
+    fn cluster_claims(&self, claims: &[Claim], max_size: usize) -> Result<Vec<Vec<Claim>>>;
+
+    // 9.4 Review System
+
+    fn record_claim_access(&self, claim_id: i64) -> Result<()>;
+    fn get_stale_claims(&self, days: i64) -> Result<Vec<Claim>>;
+
+    /// Claims most similar to `claim_id` by embedding that it isn't linked to yet,
+    /// compared within each model the claim is embedded with.
+    fn suggest_claim_links(&self, claim_id: i64, limit: usize) -> Result<Vec<(Claim, f32)>>;
+
+    fn get_orphan_claims(&self) -> Result<Vec<Claim>>;
+    fn get_random_claims(&self, count: usize) -> Result<Vec<Claim>>;
+    fn get_review_queue(&self, stale_days: i64, random_count: usize) -> Result<ReviewQueue>;
+
+    // Spaced repetition (quiz)
+
+    /// Claims due for review (never quizzed or past due), optionally limited to a topic.
+    fn get_due_claims(&self, topic: Option<&str>, limit: usize) -> Result<Vec<Claim>>;
+
+    fn get_claim_schedule(&self, claim_id: i64) -> Result<Option<ClaimSchedule>>;
+
+    /// Record a quiz answer and reschedule the claim (simplified SM-2).
+    fn record_review_result(&self, claim_id: i64, correct: bool) -> Result<ClaimSchedule>;
+
+    // 9.5 Synthesis Statistics
+
+    fn get_synthesis_stats(&self) -> Result<SynthesisStats>;
+
+    // Phase 10: AI Processing Queue
+
+    fn add_to_queue(&self, video_id: &str, priority: i32) -> Result<i64>;
+    fn get_queue(&self, include_completed: bool) -> Result<Vec<AIProcessingQueue>>;
+    fn get_queue_item(&self, video_id: &str) -> Result<Option<AIProcessingQueue>>;
+    fn get_next_pending(&self) -> Result<Option<AIProcessingQueue>>;
+    fn queue_start(&self, video_id: &str) -> Result<bool>;
+    fn queue_complete(&self, video_id: &str, claims_extracted: i32) -> Result<bool>;
+    fn queue_fail(&self, video_id: &str, error_message: &str) -> Result<bool>;
+    fn queue_skip(&self, video_id: &str) -> Result<bool>;
+    fn queue_reset(&self, video_id: &str) -> Result<bool>;
+
+    /// Change a queued video's priority, whatever its status.
+    fn queue_set_priority(&self, video_id: &str, priority: i32) -> Result<bool>;
+
+    fn queue_clear(&self, status: ProcessingStatus) -> Result<usize>;
+    fn get_pending_video_ids(&self) -> Result<Vec<String>>;
+    fn get_queue_history(&self, video_id: &str) -> Result<Vec<QueueEvent>>;
+
+    // Per-video pipeline status
+
+    fn get_video_status(&self, video_id: &str) -> Result<Option<VideoStatus>>;
+
+    // LLM usage
+
+    fn record_llm_usage(&self, usage: &LlmUsage) -> Result<i64>;
+
+    /// LLM usage totals per model, day (UTC) or task, optionally only since a time.
+    /// Days come newest first; models and tasks by cost, then tokens.
+    fn llm_usage_summary(&self, grouping: UsageGrouping, since: Option<DateTime<Utc>>) -> Result<Vec<LlmUsageSummary>>;
+
+    // API tokens
+
+    /// Create a token with a fresh random secret. Only its hash is stored, so the
+    /// returned token is the one chance to see the secret.
+    fn create_api_token(&self, name: &str, scope: TokenScope) -> Result<ApiToken>;
+
+    fn list_api_tokens(&self) -> Result<Vec<ApiToken>>;
+
+    /// The token with this secret, if any.
+    fn find_api_token(&self, token: &str) -> Result<Option<ApiToken>>;
+
+    fn touch_api_token(&self, id: i64) -> Result<()>;
+    fn has_api_tokens(&self) -> Result<bool>;
+    fn revoke_api_token(&self, name: &str) -> Result<bool>;
+
+    // ============================================
+    // Phase 12: Expanded Knowledge Entity Methods
+    // ============================================
+    // --- Sources ---
+
+    fn add_source(
+        &self,
+        title: &str,
+        author: Option<&str>,
+        source_type: SourceType,
+        year: Option<i32>,
+        url: Option<&str>,
+        notes: Option<&str>,
+    ) -> Result<i64>;
+
+    fn get_sources(&self) -> Result<Vec<Source>>;
+    fn get_source(&self, id: i64) -> Result<Option<Source>>;
+    fn find_source_by_title(&self, title: &str) -> Result<Option<Source>>;
+    fn cite_source(&self, video_id: &str, source_id: i64, timestamp: Option<f64>, context: Option<&str>) -> Result<()>;
+
+    /// Sources a video cites, with where and how, in the order they come up.
+    fn get_sources_for_video(&self, video_id: &str) -> Result<Vec<Cited<Source>>>;
+
+    // --- Scholars ---
+
+    fn add_scholar(
+        &self,
+        name: &str,
+        field: Option<&str>,
+        era: Option<&str>,
+        contribution: Option<&str>,
+    ) -> Result<i64>;
+
+    fn get_scholars(&self) -> Result<Vec<Scholar>>;
+    fn get_scholar(&self, id: i64) -> Result<Option<Scholar>>;
+    fn find_scholar_by_name(&self, name: &str) -> Result<Option<Scholar>>;
+    fn cite_scholar(&self, video_id: &str, scholar_id: i64, timestamp: Option<f64>, context: Option<&str>) -> Result<()>;
+
+    /// Scholars a video mentions, with where and how, in the order they come up.
+    fn get_scholars_for_video(&self, video_id: &str) -> Result<Vec<Cited<Scholar>>>;
+
+    /// Record a scholar an LLM found mentioned in a video. The scholar is matched by name,
+    /// ignoring case, and created flagged with `extracted_by` if unknown; a citation already
+    /// recorded for the video is left as it is. Returns the scholar ID and whether it is new.
+    fn add_extracted_scholar(
+        &self,
+        video_id: &str,
+        name: &str,
+        field: Option<&str>,
+        timestamp: Option<f64>,
+        context: Option<&str>,
+        extracted_by: &str,
+    ) -> Result<(i64, bool)>;
+
+    // --- Claim citations ---
+
+    fn cite_claim_source(&self, claim_id: i64, source_id: i64) -> Result<bool>;
+    fn uncite_claim_source(&self, claim_id: i64, source_id: i64) -> Result<bool>;
+
+    /// Attribute a claim to a scholar; citing again replaces the attribution.
+    fn cite_claim_scholar(&self, claim_id: i64, scholar_id: i64, attribution: Option<&str>) -> Result<()>;
+
+    fn uncite_claim_scholar(&self, claim_id: i64, scholar_id: i64) -> Result<bool>;
+    fn get_claim_sources(&self, claim_id: i64) -> Result<Vec<Source>>;
+    fn get_claim_scholars(&self, claim_id: i64) -> Result<Vec<ClaimScholar>>;
+
+    /// Short citations for a claim, scholars first: "per Cline 2014", "Braudel (argues)".
+    fn claim_citations(&self, claim_id: i64) -> Result<Vec<String>>;
+
+    // --- Visuals ---
+
+    fn add_visual(
+        &self,
+        video_id: &str,
+        timestamp: f64,
+        visual_type: VisualType,
+        description: &str,
+        significance: Option<&str>,
+        location_id: Option<i64>,
+        era_id: Option<i64>,
+    ) -> Result<i64>;
+
+    fn get_visuals_for_video(&self, video_id: &str) -> Result<Vec<Visual>>;
+
+    // --- Terms ---
+
+    fn add_term(
+        &self,
+        term: &str,
+        definition: &str,
+        domain: Option<&str>,
+        video_id: Option<&str>,
+        timestamp: Option<f64>,
+        scholar_id: Option<i64>,
+    ) -> Result<i64>;
+
+    fn get_terms(&self) -> Result<Vec<Term>>;
+    fn find_term(&self, term: &str) -> Result<Option<Term>>;
+
+    /// Add a term an LLM found defined in a video, flagged with `extracted_by`.
+    /// Returns None if a term with that name (ignoring case) already exists.
+    fn add_extracted_term(
+        &self,
+        term: &str,
+        definition: &str,
+        video_id: &str,
+        timestamp: Option<f64>,
+        extracted_by: &str,
+    ) -> Result<Option<i64>>;
+
+    // --- Evidence ---
+
+    fn add_evidence(
+        &self,
+        video_id: &str,
+        evidence_type: EvidenceType,
+        description: &str,
+        location_id: Option<i64>,
+        era_id: Option<i64>,
+        timestamp: Option<f64>,
+        source_id: Option<i64>,
+    ) -> Result<i64>;
+
+    fn get_evidence_for_video(&self, video_id: &str) -> Result<Vec<Evidence>>;
+    fn link_claim_evidence(&self, claim_id: i64, evidence_id: i64, relationship: Option<&str>) -> Result<()>;
+
+    // --- Quotes ---
+
+    fn add_quote(
+        &self,
+        video_id: &str,
+        text: &str,
+        speaker: Option<&str>,
+        scholar_id: Option<i64>,
+        timestamp: Option<f64>,
+        context: Option<&str>,
+    ) -> Result<i64>;
+
+    fn get_quotes_for_video(&self, video_id: &str) -> Result<Vec<Quote>>;
+
+    // --- Pending quotes ---
+
+    /// Stage an extracted quote. Returns None if the text is already a quote or pending.
+    fn stage_pending_quote(
+        &self,
+        video_id: &str,
+        text: &str,
+        speaker: Option<&str>,
+        timestamp: Option<f64>,
+        reason: &str,
+        context: Option<&str>,
+    ) -> Result<Option<i64>>;
+
+    fn get_pending_quotes(&self, video_id: Option<&str>) -> Result<Vec<PendingQuote>>;
+    fn get_pending_quote(&self, id: i64) -> Result<Option<PendingQuote>>;
+
+    /// Promote a pending quote into the quotes table, returning the new quote ID.
+    fn confirm_pending_quote(&self, id: i64, speaker: Option<&str>) -> Result<Option<i64>>;
+
+    fn reject_pending_quote(&self, id: i64) -> Result<bool>;
+
+    // --- Entity counts for stats ---
+
+    fn get_expanded_stats(&self) -> Result<(i64, i64, i64, i64, i64, i64)>;
+
+    // --- Get all entities for API ---
+
+    fn get_all_visuals(&self) -> Result<Vec<Visual>>;
+    fn get_all_evidence(&self) -> Result<Vec<Evidence>>;
+    fn get_all_quotes(&self) -> Result<Vec<Quote>>;
+}
+
+impl dyn Storage + '_ {
+    /// Run `f` in a transaction, committing if it succeeds and rolling back otherwise.
+    pub fn with_transaction<T>(&self, f: impl FnOnce(&dyn Storage) -> Result<T>) -> Result<T> {
+        let mut f = Some(f);
+        let mut result = None;
+        self.transaction(&mut |db| {
+            if let Some(f) = f.take() {
+                result = Some(f(db)?);
+            }
+            Ok(())
+        })?;
+        result.ok_or_else(|| anyhow::anyhow!("Transaction finished without running"))
+    }
+}
@@ -0,0 +1,103 @@
+use crate::storage::models::{Transcript, TranscriptSegment};
+
+/// Render a transcript as SubRip (SRT) subtitles.
+pub fn to_srt(transcript: &Transcript) -> String {
+    let mut out = String::new();
+    for (i, seg) in transcript.segments.iter().enumerate() {
+        out.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            i + 1,
+            format_timestamp(seg.start_time, ','),
+            format_timestamp(seg.start_time + seg.duration, ','),
+            voiced(seg)
+        ));
+    }
+    out
+}
+
+/// Render a transcript as WebVTT subtitles.
+pub fn to_vtt(transcript: &Transcript) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for seg in &transcript.segments {
+        out.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            format_timestamp(seg.start_time, '.'),
+            format_timestamp(seg.start_time + seg.duration, '.'),
+            voiced(seg)
+        ));
+    }
+    out
+}
+
+/// Render a transcript as plain text, one segment per line, without timings.
+pub fn to_plain_text(transcript: &Transcript) -> String {
+    let mut out = String::new();
+    for seg in &transcript.segments {
+        if !seg.text.trim().is_empty() {
+            out.push_str(&labelled(seg));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Pause, in seconds, that starts a new paragraph, and the length past which a paragraph
+/// ends at the next sentence break.
+const PARAGRAPH_PAUSE: f64 = 2.0;
+const PARAGRAPH_CHARS: usize = 500;
+
+/// Merge segments into paragraphs for reading: a new one starts when the speaker changes,
+/// after a long pause, or at the first sentence end once a paragraph is long enough.
+/// Each paragraph keeps the start of its first segment and spans to the end of its last.
+pub fn to_paragraphs(segments: &[TranscriptSegment]) -> Vec<TranscriptSegment> {
+    let mut paragraphs: Vec<TranscriptSegment> = Vec::new();
+    let mut open = false;
+    for seg in segments {
+        let text = seg.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        if let Some(last) = paragraphs.last_mut().filter(|_| open) {
+            let end = last.start_time + last.duration;
+            if last.speaker == seg.speaker && seg.start_time - end < PARAGRAPH_PAUSE {
+                last.text.push(' ');
+                last.text.push_str(text);
+                last.duration = (seg.start_time + seg.duration - last.start_time).max(last.duration);
+                open = !(last.text.len() >= PARAGRAPH_CHARS && ends_sentence(&last.text));
+                continue;
+            }
+        }
+        paragraphs.push(TranscriptSegment { text: text.to_string(), ..seg.clone() });
+        open = !(text.len() >= PARAGRAPH_CHARS && ends_sentence(text));
+    }
+    paragraphs
+}
+
+fn ends_sentence(text: &str) -> bool {
+    text.ends_with(['.', '!', '?'])
+}
+
+// A WebVTT voice tag; parse_subtitles reads the speaker back from it in SRT files too
+fn voiced(seg: &TranscriptSegment) -> String {
+    match &seg.speaker {
+        Some(speaker) => format!("<v {}>{}", speaker, seg.text.trim()),
+        None => seg.text.trim().to_string(),
+    }
+}
+
+// "Speaker: text" for reading; parse_plain_text only takes all-caps labels back as speakers
+fn labelled(seg: &TranscriptSegment) -> String {
+    match &seg.speaker {
+        Some(speaker) => format!("{}: {}", speaker, seg.text.trim()),
+        None => seg.text.trim().to_string(),
+    }
+}
+
+// HH:MM:SS,mmm for SRT, HH:MM:SS.mmm for VTT
+fn format_timestamp(seconds: f64, separator: char) -> String {
+    let total_ms = (seconds.max(0.0) * 1000.0).round() as u64;
+    let (hours, rest) = (total_ms / 3_600_000, total_ms % 3_600_000);
+    let (minutes, rest) = (rest / 60_000, rest % 60_000);
+    let (secs, ms) = (rest / 1000, rest % 1000);
+    format!("{:02}:{:02}:{:02}{}{:03}", hours, minutes, secs, separator, ms)
+}
@@ -1,34 +1,255 @@
 use anyhow::Result;
-use super::parser;
-use crate::storage::models::{Video, Transcript};
+use super::{language, parser};
+use crate::storage::models::{Archive, Availability, Video, Transcript};
+use std::path::{Path, PathBuf};
+use std::process::Output;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// Shared across all fetchers in the process so concurrent fetches respect one limit
+static LAST_REQUEST: Mutex<Option<Instant>> = Mutex::new(None);
+
+// yt-dlp stderr fragments that indicate a failure worth retrying
+const TRANSIENT_ERRORS: &[&str] = &[
+    "HTTP Error 429",
+    "Too Many Requests",
+    "HTTP Error 500",
+    "HTTP Error 502",
+    "HTTP Error 503",
+    "HTTP Error 504",
+    "timed out",
+    "Connection reset",
+    "Temporary failure in name resolution",
+    "Remote end closed connection",
+];
+
+// yt-dlp stderr fragments for videos that are gone for good, or locked away
+const REMOVED_ERRORS: &[&str] = &[
+    "Video unavailable",
+    "This video has been removed",
+    "account associated with this video has been terminated",
+    "This video is no longer available",
+    "does not exist",
+    "HTTP Error 404",
+    "HTTP Error 410",
+];
+const PRIVATE_ERRORS: &[&str] = &[
+    "Private video",
+    "members-only",
+    "Join this channel",
+    "Sign in to confirm your age",
+    "age-restricted",
+];
+
+// Checked in order when no proxy is configured explicitly
+const PROXY_ENV_VARS: &[&str] = &["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy", "ALL_PROXY", "all_proxy"];
+
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts per yt-dlp invocation or HTTP request, including the first
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Random spread applied to each backoff, as a fraction (0.25 = ±25%)
+    pub jitter: f64,
+}
+
+impl RetryPolicy {
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn backoff_for(&self, attempt: u32) -> Duration {
+        let base = self.initial_backoff.as_secs_f64() * 2f64.powi(attempt.saturating_sub(1) as i32);
+        let capped = base.min(self.max_backoff.as_secs_f64());
+        let spread = (pseudo_random() * 2.0 - 1.0) * self.jitter.clamp(0.0, 1.0);
+        Duration::try_from_secs_f64((capped * (1.0 + spread)).max(0.0)).unwrap_or(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_backoff: Duration::from_secs(2),
+            max_backoff: Duration::from_secs(60),
+            jitter: 0.25,
+        }
+    }
+}
 
 pub struct Fetcher {
     yt_dlp_path: String,
+    retry: RetryPolicy,
+    min_interval: Duration,
+    proxy: Option<String>,
+    cookies_file: Option<PathBuf>,
 }
 
 impl Fetcher {
     pub fn new() -> Self {
-        Self {
-            yt_dlp_path: "yt-dlp".to_string(),
-        }
+        Self::with_path("yt-dlp")
     }
 
     pub fn with_path(path: &str) -> Self {
         Self {
             yt_dlp_path: path.to_string(),
+            retry: RetryPolicy::default(),
+            min_interval: Duration::ZERO,
+            proxy: None,
+            cookies_file: None,
         }
     }
 
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Minimum time between yt-dlp invocations across the whole process.
+    pub fn with_rate_limit(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Route requests through a proxy. Falls back to HTTP(S)_PROXY when unset.
+    pub fn with_proxy(mut self, proxy: &str) -> Self {
+        self.proxy = Some(proxy.to_string());
+        self
+    }
+
+    /// Netscape-format cookies file, for age-restricted or members-only videos.
+    pub fn with_cookies_file<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.cookies_file = Some(path.as_ref().to_path_buf());
+        self
+    }
+
     pub fn fetch(&self, url: &str) -> Result<(Video, Option<Transcript>)> {
-        let video = self.fetch_metadata(url)?;
-        let transcript = self.fetch_transcript(url, &video.id)?;
+        let (video, transcript, _) = self.fetch_archived(url)?;
         Ok((video, transcript))
     }
 
-    fn fetch_metadata(&self, url: &str) -> Result<Video> {
-        let output = std::process::Command::new(&self.yt_dlp_path)
-            .args(["--dump-json", "--no-download", url])
-            .output()?;
+    /// Like `fetch`, also returning the raw metadata and caption payloads.
+    pub fn fetch_archived(&self, url: &str) -> Result<(Video, Option<Transcript>, Vec<Archive>)> {
+        let (video, declared_language, metadata_json) = self.fetch_metadata(url)?;
+        let mut archives = vec![Archive {
+            id: 0,
+            video_id: video.id.clone(),
+            kind: "metadata".to_string(),
+            format: "json".to_string(),
+            language: None,
+            content: metadata_json,
+            fetched_at: chrono::Utc::now(),
+        }];
+
+        let transcript = match self.fetch_transcript(url, &video.id, declared_language.as_deref())? {
+            Some((transcript, lang, content)) => {
+                archives.push(Archive {
+                    id: 0,
+                    video_id: video.id.clone(),
+                    kind: "captions".to_string(),
+                    format: "json3".to_string(),
+                    language: Some(lang),
+                    content,
+                    fetched_at: chrono::Utc::now(),
+                });
+                Some(transcript)
+            }
+            None => None,
+        };
+
+        Ok((video, transcript, archives))
+    }
+
+    /// Probe whether a video can still be fetched, without downloading anything.
+    /// Returns Unknown (with the error) when the failure doesn't identify the cause.
+    pub fn check_availability(&self, url: &str) -> Result<(Availability, Option<String>)> {
+        if let Some(path) = url.strip_prefix("file://") {
+            return Ok(if Path::new(path).exists() {
+                (Availability::Available, None)
+            } else {
+                (Availability::Removed, Some(format!("{} no longer exists", path)))
+            });
+        }
+
+        let output = self.run_yt_dlp(&["--simulate", "--quiet", "--no-warnings", "--no-playlist", url])?;
+        if output.status.success() {
+            return Ok((Availability::Available, None));
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = stderr
+            .lines()
+            .find(|l| l.contains("ERROR"))
+            .unwrap_or(stderr.trim())
+            .trim()
+            .to_string();
+        // Private checks come first: yt-dlp prefixes many of them with "Video unavailable"
+        let availability = if PRIVATE_ERRORS.iter().any(|e| stderr.contains(e)) {
+            Availability::Private
+        } else if REMOVED_ERRORS.iter().any(|e| stderr.contains(e)) {
+            Availability::Removed
+        } else {
+            Availability::Unknown
+        };
+        Ok((availability, Some(detail)))
+    }
+
+    /// IDs of the most recent uploads on a channel or playlist, newest first.
+    pub fn list_channel_videos(&self, url: &str, limit: usize) -> Result<Vec<String>> {
+        let limit = limit.to_string();
+        let output = self.run_yt_dlp(&[
+            "--flat-playlist",
+            "--print", "id",
+            "--playlist-end", &limit,
+            url,
+        ])?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("yt-dlp failed: {}", stderr);
+        }
+
+        Ok(String::from_utf8(output.stdout)?
+            .lines()
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty())
+            .collect())
+    }
+
+    /// Download the video's thumbnail into `dir` as `{video_id}.{ext}`.
+    pub fn download_thumbnail(&self, url: &str, video_id: &str, dir: &Path) -> Result<Option<PathBuf>> {
+        std::fs::create_dir_all(dir)?;
+        let template = dir.join(format!("{}.%(ext)s", video_id));
+        let template = template
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Thumbnail folder is not valid UTF-8: {}", dir.display()))?;
+        let output = self.run_yt_dlp(&[
+            "--write-thumbnail",
+            "--skip-download",
+            "--no-playlist",
+            "-o", template,
+            url,
+        ])?;
+
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.file_stem().and_then(|s| s.to_str()) == Some(video_id) {
+                return Ok(Some(path));
+            }
+        }
+        Ok(None)
+    }
+
+    fn fetch_metadata(&self, url: &str) -> Result<(Video, Option<String>, String)> {
+        let output = self.run_yt_dlp(&["--dump-json", "--no-download", url])?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -36,44 +257,113 @@ impl Fetcher {
         }
 
         let json = String::from_utf8(output.stdout)?;
-        parser::parse_video_metadata(&json)
+        let video = parser::parse_video_metadata(&json)?;
+        let language = parser::parse_metadata_language(&json);
+        Ok((video, language, json))
     }
 
-    fn fetch_transcript(&self, url: &str, video_id: &str) -> Result<Option<Transcript>> {
+    // Returns the transcript with the subtitle language and raw json3 it was parsed from
+    fn fetch_transcript(&self, url: &str, video_id: &str, declared_language: Option<&str>) -> Result<Option<(Transcript, String, String)>> {
         let temp_dir = std::env::temp_dir();
         let output_template = temp_dir.join(format!("{}.%(ext)s", video_id));
 
-        let output = std::process::Command::new(&self.yt_dlp_path)
-            .args([
-                "--write-subs",
-                "--write-auto-subs",
-                "--sub-langs", "en",
-                "--sub-format", "json3",
-                "--skip-download",
-                "-o", output_template.to_str().unwrap(),
-                url,
-            ])
-            .output()?;
+        // Prefer subtitles in the video's own language, falling back to English
+        let mut languages = vec!["en".to_string()];
+        if let Some(lang) = declared_language.filter(|l| *l != "en" && *l != "und") {
+            languages.insert(0, lang.to_string());
+        }
+        let sub_langs = languages.join(",");
+
+        let output = self.run_yt_dlp(&[
+            "--write-subs",
+            "--write-auto-subs",
+            "--sub-langs", &sub_langs,
+            "--sub-format", "json3",
+            "--skip-download",
+            "-o", output_template.to_str().unwrap(),
+            url,
+        ])?;
 
         if !output.status.success() {
             return Ok(None);
         }
 
         // Look for the transcript file
-        let patterns = [
-            temp_dir.join(format!("{}.en.json3", video_id)),
-            temp_dir.join(format!("{}.en-orig.json3", video_id)),
-        ];
-
-        for pattern in patterns {
-            if pattern.exists() {
-                let content = std::fs::read_to_string(&pattern)?;
-                let _ = std::fs::remove_file(&pattern);
-                return Ok(Some(parser::parse_transcript(&content, video_id)?));
+        let mut found = None;
+        for lang in &languages {
+            for suffix in ["", "-orig"] {
+                let path = temp_dir.join(format!("{}.{}{}.json3", video_id, lang, suffix));
+                if path.exists() {
+                    if found.is_none() {
+                        found = Some((lang.clone(), std::fs::read_to_string(&path)?));
+                    }
+                    let _ = std::fs::remove_file(&path);
+                }
             }
         }
 
-        Ok(None)
+        match found {
+            Some((lang, content)) => {
+                let mut transcript = parser::parse_transcript(&content, video_id)?;
+                transcript.language = language::resolve_language(Some(&lang), &transcript.full_text);
+                Ok(Some((transcript, lang, content)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Run yt-dlp, retrying transient failures with exponential backoff.
+    /// Returns the last output, successful or not; callers decide how to treat failure.
+    fn run_yt_dlp(&self, args: &[&str]) -> Result<Output> {
+        if let Some(cookies) = &self.cookies_file {
+            if !cookies.exists() {
+                anyhow::bail!("Cookies file not found: {}", cookies.display());
+            }
+        }
+
+        let mut attempt = 1;
+        loop {
+            self.throttle();
+            let output = self.command().args(args).output()?;
+
+            if output.status.success() || attempt >= self.retry.max_attempts {
+                return Ok(output);
+            }
+
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if !TRANSIENT_ERRORS.iter().any(|e| stderr.contains(e)) {
+                return Ok(output);
+            }
+
+            std::thread::sleep(self.retry.backoff_for(attempt));
+            attempt += 1;
+        }
+    }
+
+    fn command(&self) -> std::process::Command {
+        let mut cmd = std::process::Command::new(&self.yt_dlp_path);
+        if let Some(proxy) = self.proxy.clone().or_else(proxy_from_env) {
+            cmd.args(["--proxy", &proxy]);
+        }
+        if let Some(cookies) = &self.cookies_file {
+            cmd.arg("--cookies").arg(cookies);
+        }
+        cmd
+    }
+
+    fn throttle(&self) {
+        if self.min_interval.is_zero() {
+            return;
+        }
+
+        let mut last = LAST_REQUEST.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(prev) = *last {
+            let elapsed = prev.elapsed();
+            if elapsed < self.min_interval {
+                std::thread::sleep(self.min_interval - elapsed);
+            }
+        }
+        *last = Some(Instant::now());
     }
 }
 
@@ -82,3 +372,19 @@ impl Default for Fetcher {
         Self::new()
     }
 }
+
+fn proxy_from_env() -> Option<String> {
+    PROXY_ENV_VARS
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|v| !v.is_empty())
+}
+
+// Good enough for jitter; avoids pulling in a rand dependency
+fn pseudo_random() -> f64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    (nanos % 1_000_000) as f64 / 1_000_000.0
+}
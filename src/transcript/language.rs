@@ -0,0 +1,110 @@
+// Lightweight language identification: script detection for non-Latin
+// alphabets, stopword frequency for common Latin-script languages.
+
+const STOPWORDS: &[(&str, &[&str])] = &[
+    ("en", &["the", "and", "of", "to", "is", "that", "in", "it", "was", "this", "you", "for"]),
+    ("es", &["el", "la", "de", "que", "y", "en", "los", "es", "por", "una", "las", "con"]),
+    ("fr", &["le", "la", "les", "de", "et", "est", "que", "une", "des", "pas", "dans", "pour"]),
+    ("de", &["der", "die", "und", "das", "ist", "nicht", "ein", "zu", "den", "mit", "sich", "auch"]),
+    ("it", &["il", "di", "che", "e", "la", "non", "per", "una", "sono", "della", "gli", "anche"]),
+    ("pt", &["o", "de", "que", "e", "do", "da", "em", "um", "para", "não", "uma", "os"]),
+    ("nl", &["de", "het", "een", "en", "van", "is", "dat", "niet", "op", "te", "zijn", "ook"]),
+];
+
+// Fraction of sampled words that must be stopwords before trusting a guess
+const MIN_CONFIDENCE: f64 = 0.08;
+
+/// Normalize a language tag to its lowercase primary subtag ("en-US" -> "en").
+pub fn normalize_language_tag(tag: &str) -> String {
+    let primary = tag
+        .trim()
+        .split(['-', '_', '.'])
+        .next()
+        .unwrap_or("")
+        .to_lowercase();
+    if primary.is_empty() {
+        "und".to_string()
+    } else {
+        primary
+    }
+}
+
+/// Guess the language of a text. Returns an ISO 639-1 code and a confidence in 0..1.
+pub fn detect_language(text: &str) -> Option<(&'static str, f64)> {
+    let sample: String = text.chars().take(5000).collect();
+
+    if let Some(script) = detect_script(&sample) {
+        return Some(script);
+    }
+
+    let words: Vec<String> = sample
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect();
+    if words.len() < 5 {
+        return None;
+    }
+
+    let (lang, hits) = STOPWORDS
+        .iter()
+        .map(|(lang, stops)| (*lang, words.iter().filter(|w| stops.contains(&w.as_str())).count()))
+        .max_by_key(|(_, hits)| *hits)?;
+
+    let confidence = hits as f64 / words.len() as f64;
+    (confidence >= MIN_CONFIDENCE).then_some((lang, confidence.min(1.0)))
+}
+
+/// Pick the language to store: trust detection when it is confident and
+/// disagrees with the declared tag, otherwise keep the normalized declaration.
+pub fn resolve_language(declared: Option<&str>, text: &str) -> String {
+    let declared = declared.map(normalize_language_tag).filter(|l| l != "und");
+    match (declared, detect_language(text)) {
+        (Some(d), Some((detected, confidence))) if d != detected && confidence >= 0.15 => detected.to_string(),
+        (Some(d), _) => d,
+        (None, Some((detected, _))) => detected.to_string(),
+        (None, None) => "und".to_string(),
+    }
+}
+
+fn detect_script(sample: &str) -> Option<(&'static str, f64)> {
+    let mut letters = 0usize;
+    let mut counts = [0usize; 4]; // Cyrillic, Arabic, Greek, Hebrew
+    let mut hangul = 0usize;
+    let mut kana = 0usize;
+    let mut han = 0usize;
+
+    for c in sample.chars().filter(|c| c.is_alphabetic()) {
+        letters += 1;
+        match c as u32 {
+            0x0400..=0x04FF => counts[0] += 1,
+            0x0600..=0x06FF => counts[1] += 1,
+            0x0370..=0x03FF => counts[2] += 1,
+            0x0590..=0x05FF => counts[3] += 1,
+            0x3040..=0x30FF => kana += 1,
+            0x4E00..=0x9FFF => han += 1,
+            0xAC00..=0xD7AF => hangul += 1,
+            _ => {}
+        }
+    }
+    if letters == 0 {
+        return None;
+    }
+
+    let share = |n: usize| n as f64 / letters as f64;
+    if share(kana) > 0.1 {
+        return Some(("ja", share(kana + han)));
+    }
+    if share(hangul) > 0.3 {
+        return Some(("ko", share(hangul)));
+    }
+    if share(han) > 0.3 {
+        return Some(("zh", share(han)));
+    }
+    for (lang, n) in ["ru", "ar", "el", "he"].iter().zip(counts.iter()) {
+        if share(*n) > 0.3 {
+            return Some((lang, share(*n)));
+        }
+    }
+    None
+}
@@ -1,2 +1,5 @@
+pub mod export;
 pub mod fetcher;
+pub mod language;
 pub mod parser;
+pub mod quotes;
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use serde::Deserialize;
-use crate::storage::models::{Video, Transcript, TranscriptSegment};
+use crate::storage::models::{Archive, Video, Transcript, TranscriptSegment, Chapter};
+use super::language;
 use chrono::{NaiveDate, Utc};
 
 #[derive(Deserialize)]
@@ -13,6 +14,10 @@ struct YtDlpMetadata {
     description: Option<String>,
     webpage_url: Option<String>,
     original_url: Option<String>,
+    language: Option<String>,
+    duration: Option<f64>,
+    view_count: Option<i64>,
+    thumbnail: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -53,9 +58,18 @@ pub fn parse_video_metadata(json: &str) -> Result<Video> {
         upload_date,
         description: meta.description,
         added_at: Utc::now(),
+        duration: meta.duration,
+        view_count: meta.view_count,
+        thumbnail_url: meta.thumbnail,
     })
 }
 
+/// Declared spoken language from yt-dlp metadata, normalized (often missing).
+pub fn parse_metadata_language(json: &str) -> Option<String> {
+    let meta: YtDlpMetadata = serde_json::from_str(json).ok()?;
+    meta.language.map(|l| language::normalize_language_tag(&l))
+}
+
 pub fn parse_transcript(json: &str, video_id: &str) -> Result<Transcript> {
     let data: Json3Transcript = serde_json::from_str(json)?;
 
@@ -81,6 +95,7 @@ pub fn parse_transcript(json: &str, video_id: &str) -> Result<Transcript> {
                         start_time: start_ms as f64 / 1000.0,
                         duration: duration_ms as f64 / 1000.0,
                         text,
+                        speaker: None,
                     });
                 }
             }
@@ -94,3 +109,224 @@ pub fn parse_transcript(json: &str, video_id: &str) -> Result<Transcript> {
         full_text: full_text_parts.join(" "),
     })
 }
+
+/// Re-parse an archived caption payload with the current parsers.
+pub fn parse_archive(archive: &Archive) -> Result<Transcript> {
+    let mut transcript = match archive.format.as_str() {
+        "json3" => parse_transcript(&archive.content, &archive.video_id)?,
+        "srt" | "vtt" => parse_subtitles(&archive.content, &archive.video_id)?,
+        "txt" => parse_plain_text(&archive.content, &archive.video_id)?,
+        other => anyhow::bail!("Can't parse archived {} payload", other),
+    };
+    transcript.language = language::resolve_language(archive.language.as_deref(), &transcript.full_text);
+    Ok(transcript)
+}
+
+/// Extract chapter markers ("03:15 The Fall of Ur") from a video description.
+/// Returns nothing unless at least two ascending timestamps are found.
+pub fn parse_chapters(description: &str, video_id: &str) -> Vec<Chapter> {
+    let mut chapters: Vec<Chapter> = Vec::new();
+
+    for line in description.lines() {
+        let line = line.trim().trim_start_matches(['-', '*', '\u{2022}', ' ']);
+        let Some((start_time, title)) = split_chapter_line(line) else {
+            continue;
+        };
+        if chapters.last().is_some_and(|c| c.start_time >= start_time) {
+            continue;
+        }
+        if let Some(prev) = chapters.last_mut() {
+            prev.end_time = Some(start_time);
+        }
+        chapters.push(Chapter {
+            video_id: video_id.to_string(),
+            start_time,
+            end_time: None,
+            title,
+        });
+    }
+
+    if chapters.len() < 2 {
+        return Vec::new();
+    }
+    chapters
+}
+
+// "03:15 Title", "(1:02:03) Title", "Title - 03:15"
+fn split_chapter_line(line: &str) -> Option<(f64, String)> {
+    let is_time = |t: &str| {
+        let t = t.trim_matches(['(', ')', '[', ']']);
+        let parts: Vec<&str> = t.split(':').collect();
+        (2..=3).contains(&parts.len())
+            && parts.iter().all(|p| !p.is_empty() && p.len() <= 2 && p.chars().all(|c| c.is_ascii_digit()))
+    };
+    let separators = ['-', '\u{2013}', '\u{2014}', ':', '|', '.', ' '];
+
+    let (time, title) = match line.split_once(' ') {
+        Some((first, rest)) if is_time(first) => (first, rest.trim_start_matches(separators)),
+        _ => {
+            let (rest, last) = line.rsplit_once(' ')?;
+            if !is_time(last) {
+                return None;
+            }
+            (last, rest.trim_end_matches(separators))
+        }
+    };
+
+    let title = title.trim();
+    if title.is_empty() {
+        return None;
+    }
+    let seconds = parse_timestamp(time.trim_matches(['(', ')', '[', ']'])).ok()?;
+    Some((seconds, title.to_string()))
+}
+
+/// Parse an SRT or WebVTT subtitle file into a transcript.
+pub fn parse_subtitles(content: &str, video_id: &str) -> Result<Transcript> {
+    let mut segments = Vec::new();
+    let normalized = content.replace("\r\n", "\n");
+
+    for block in normalized.split("\n\n") {
+        let mut lines = block.lines().map(|l| l.trim()).filter(|l| !l.is_empty());
+        let mut timing = None;
+        let mut text_lines = Vec::new();
+
+        let mut speaker = None;
+
+        for line in lines.by_ref() {
+            if timing.is_none() {
+                if let Some((start, end)) = line.split_once("-->") {
+                    timing = Some((parse_timestamp(start)?, parse_timestamp(end)?));
+                }
+                // Cue numbers, WEBVTT headers and cue identifiers precede the timing line
+                continue;
+            }
+            let (label, text) = split_speaker(line);
+            if text_lines.is_empty() {
+                speaker = label;
+            }
+            text_lines.push(text);
+        }
+
+        if let Some((start, end)) = timing {
+            let text = text_lines.join(" ").trim().to_string();
+            if !text.is_empty() {
+                segments.push(TranscriptSegment {
+                    start_time: start,
+                    duration: (end - start).max(0.0),
+                    text,
+                    speaker,
+                });
+            }
+        }
+    }
+
+    if segments.is_empty() {
+        anyhow::bail!("No subtitle cues found");
+    }
+
+    Ok(build_transcript(video_id, segments))
+}
+
+/// Parse plain text with no timing. Each paragraph becomes a segment with
+/// times estimated from speaking rate (~150 words per minute).
+pub fn parse_plain_text(content: &str, video_id: &str) -> Result<Transcript> {
+    const WORDS_PER_SECOND: f64 = 2.5;
+
+    let mut segments = Vec::new();
+    let mut clock = 0.0;
+    let normalized = content.replace("\r\n", "\n");
+
+    for paragraph in normalized.split("\n\n") {
+        let text = paragraph.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.is_empty() {
+            continue;
+        }
+        let (speaker, text) = split_speaker(&text);
+        let duration = text.split_whitespace().count() as f64 / WORDS_PER_SECOND;
+        segments.push(TranscriptSegment {
+            start_time: clock,
+            duration,
+            text,
+            speaker,
+        });
+        clock += duration;
+    }
+
+    if segments.is_empty() {
+        anyhow::bail!("Transcript file is empty");
+    }
+
+    Ok(build_transcript(video_id, segments))
+}
+
+fn build_transcript(video_id: &str, segments: Vec<TranscriptSegment>) -> Transcript {
+    let full_text = segments.iter().map(|s| s.text.as_str()).collect::<Vec<_>>().join(" ");
+    Transcript {
+        video_id: video_id.to_string(),
+        language: language::resolve_language(None, &full_text),
+        segments,
+        full_text,
+    }
+}
+
+// Accepts "HH:MM:SS,mmm", "HH:MM:SS.mmm" and "MM:SS.mmm", ignoring trailing cue settings
+fn parse_timestamp(s: &str) -> Result<f64> {
+    let token = s.split_whitespace().next().unwrap_or("").replace(',', ".");
+    let mut seconds = 0.0;
+    for part in token.split(':') {
+        let value: f64 = part.parse().map_err(|_| anyhow::anyhow!("Invalid timestamp: {}", s.trim()))?;
+        seconds = seconds * 60.0 + value;
+    }
+    Ok(seconds)
+}
+
+/// Split a diarized line into speaker and text. Recognizes only explicit
+/// labels: WebVTT voice tags (`<v Jane Doe>`) and all-caps `JANE DOE:` prefixes,
+/// so headings like "Rome: the eternal city" stay in the text.
+fn split_speaker(line: &str) -> (Option<String>, String) {
+    let line = line.trim().trim_start_matches("- ");
+
+    if let Some(rest) = line.strip_prefix("<v") {
+        if let Some((tag, text)) = rest.split_once('>') {
+            // Skip voice classes, as in <v.loud Jane>
+            let name = tag.split_once(' ').map(|(_, n)| n).unwrap_or(tag).trim();
+            let name = (!name.is_empty() && !name.starts_with('.')).then(|| name.to_string());
+            return (name, strip_tags(text).trim().to_string());
+        }
+    }
+
+    if let Some((name, text)) = line.split_once(':') {
+        let name = name.trim();
+        if looks_like_speaker(name) && !text.trim().is_empty() {
+            return (Some(name.to_string()), strip_tags(text).trim().to_string());
+        }
+    }
+
+    (None, strip_tags(line).trim().to_string())
+}
+
+fn looks_like_speaker(name: &str) -> bool {
+    let words: Vec<&str> = name.split_whitespace().collect();
+    !words.is_empty()
+        && words.len() <= 4
+        && name.len() <= 40
+        && words.iter().all(|w| {
+            w.chars().any(|c| c.is_alphabetic())
+                && w.chars().all(|c| c.is_uppercase() || matches!(c, '.' | '\'' | '-'))
+        })
+}
+
+fn strip_tags(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut in_tag = false;
+    for c in line.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
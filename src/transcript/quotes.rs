@@ -0,0 +1,202 @@
+use crate::storage::models::Transcript;
+
+const REPORTING_VERBS: &[&str] = &[
+    "said", "says", "wrote", "writes", "argued", "argues", "observed", "declared",
+    "noted", "remarked", "insisted", "claimed", "warned", "taught", "put",
+];
+
+const SPEAKER_ADVERBS: &[&str] = &["once", "famously", "also", "later", "rightly", "himself", "herself"];
+
+const SENTENCE_OPENERS: &[&str] = &["as", "then", "so", "and", "but", "the", "when", "because", "what", "this"];
+
+const APHORISM_OPENERS: &[&str] = &[
+    "those who ", "he who ", "she who ", "whoever ", "never ", "every ", "all ",
+    "history ", "no one ", "nothing ", "the more ",
+];
+
+// Captions without punctuation would otherwise become one giant sentence
+const MAX_SENTENCE_WORDS: usize = 60;
+
+#[derive(Debug, Clone)]
+pub struct QuoteCandidate {
+    pub text: String,
+    pub speaker: Option<String>,
+    pub timestamp: f64,
+    pub reason: &'static str,
+    pub context: String,
+}
+
+struct Sentence {
+    text: String,
+    start: f64,
+    speaker: Option<String>,
+}
+
+/// Find quoted speech, attributed statements and aphorisms in a transcript.
+pub fn extract_quotes(transcript: &Transcript) -> Vec<QuoteCandidate> {
+    let mut candidates: Vec<QuoteCandidate> = Vec::new();
+
+    for sentence in split_sentences(transcript) {
+        let found = find_quoted(&sentence)
+            .or_else(|| find_reported(&sentence))
+            .or_else(|| find_aphorism(&sentence));
+
+        if let Some(mut c) = found {
+            // Unattributed lines in a diarized transcript belong to whoever said them
+            if c.speaker.is_none() {
+                c.speaker = sentence.speaker.clone();
+            }
+            let key = c.text.to_lowercase();
+            if !candidates.iter().any(|e| e.text.to_lowercase() == key) {
+                candidates.push(c);
+            }
+        }
+    }
+
+    candidates
+}
+
+fn split_sentences(transcript: &Transcript) -> Vec<Sentence> {
+    let mut sentences = Vec::new();
+    let mut words: Vec<&str> = Vec::new();
+    let mut start = 0.0;
+    let mut speaker = None;
+
+    for seg in &transcript.segments {
+        for word in seg.text.split_whitespace() {
+            if words.is_empty() {
+                start = seg.start_time;
+                speaker = seg.speaker.clone();
+            }
+            words.push(word);
+
+            let ends = word
+                .trim_end_matches(['"', '\'', '\u{201d}', '\u{2019}', ')'])
+                .ends_with(['.', '!', '?']);
+            if ends || words.len() >= MAX_SENTENCE_WORDS {
+                sentences.push(Sentence { text: words.join(" "), start, speaker: speaker.take() });
+                words.clear();
+            }
+        }
+    }
+
+    if !words.is_empty() {
+        sentences.push(Sentence { text: words.join(" "), start, speaker });
+    }
+    sentences
+}
+
+fn find_quoted(sentence: &Sentence) -> Option<QuoteCandidate> {
+    let normalized = sentence.text.replace(['\u{201c}', '\u{201d}'], "\"");
+    let quoted = normalized
+        .split('"')
+        .skip(1)
+        .step_by(2)
+        .map(|s| s.trim())
+        .find(|s| s.split_whitespace().count() >= 4)?;
+
+    Some(QuoteCandidate {
+        text: quoted.trim_matches([',', ' ']).to_string(),
+        speaker: find_reported(sentence).and_then(|c| c.speaker),
+        timestamp: sentence.start,
+        reason: "quoted",
+        context: sentence.text.clone(),
+    })
+}
+
+fn find_reported(sentence: &Sentence) -> Option<QuoteCandidate> {
+    let words: Vec<&str> = sentence.text.split_whitespace().collect();
+
+    for (i, word) in words.iter().enumerate() {
+        let bare = word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+        if !REPORTING_VERBS.contains(&bare.as_str()) {
+            continue;
+        }
+
+        // "put" only counts in "as X put it"
+        let mut rest_from = i + 1;
+        if bare == "put" {
+            match words.get(i + 1) {
+                Some(w) if w.trim_matches(|c: char| !c.is_alphanumeric()).eq_ignore_ascii_case("it") => rest_from = i + 2,
+                _ => continue,
+            }
+        }
+
+        let Some(speaker) = speaker_before(&words[..i]) else {
+            continue;
+        };
+
+        let mut rest = &words[rest_from.min(words.len())..];
+        if let Some(first) = rest.first() {
+            if first.trim_matches(|c: char| !c.is_alphanumeric()).eq_ignore_ascii_case("that") {
+                rest = &rest[1..];
+            }
+        }
+        if rest.len() < 5 {
+            continue;
+        }
+
+        let text = rest
+            .join(" ")
+            .trim_start_matches([',', ':', ' ', '"', '\u{201c}'])
+            .trim_end_matches(['"', '\u{201d}'])
+            .to_string();
+
+        return Some(QuoteCandidate {
+            text,
+            speaker: Some(speaker),
+            timestamp: sentence.start,
+            reason: "reporting-verb",
+            context: sentence.text.clone(),
+        });
+    }
+    None
+}
+
+fn speaker_before(words: &[&str]) -> Option<String> {
+    let mut end = words.len();
+    while end > 0 && SPEAKER_ADVERBS.contains(&words[end - 1].to_lowercase().as_str()) {
+        end -= 1;
+    }
+
+    let mut begin = end;
+    while begin > 0 && end - begin < 3 {
+        let w = words[begin - 1].trim_matches(|c: char| !c.is_alphanumeric() && c != '\'');
+        let capitalized = w.chars().next().map(|c| c.is_uppercase()).unwrap_or(false);
+        if !capitalized || w == "I" || SENTENCE_OPENERS.contains(&w.to_lowercase().as_str()) {
+            break;
+        }
+        begin -= 1;
+    }
+
+    if begin == end {
+        return None;
+    }
+
+    let name = words[begin..end]
+        .iter()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric() && c != '\''))
+        .collect::<Vec<_>>()
+        .join(" ");
+    Some(name.trim_end_matches("'s").to_string())
+}
+
+fn find_aphorism(sentence: &Sentence) -> Option<QuoteCandidate> {
+    let word_count = sentence.text.split_whitespace().count();
+    if !(5..=20).contains(&word_count) {
+        return None;
+    }
+
+    let lower = sentence.text.to_lowercase();
+    if !APHORISM_OPENERS.iter().any(|o| lower.starts_with(o)) {
+        return None;
+    }
+
+    Some(QuoteCandidate {
+        text: sentence.text.clone(),
+        speaker: None,
+        timestamp: sentence.start,
+        reason: "aphorism",
+        context: sentence.text.clone(),
+    })
+}